@@ -17,8 +17,12 @@ pub struct RawBlameData {
 }
 
 /// Parse `git blame --porcelain` output into a map of line number -> blame data.
+///
+/// Porcelain output prints a commit's author fields only on its first line,
+/// so they are remembered per commit and reused for its later lines.
 pub fn parse_porcelain_blame(output: &str) -> HashMap<usize, RawBlameData> {
     let mut result = HashMap::new();
+    let mut commits: HashMap<String, (String, String, i64)> = HashMap::new();
     let mut current_line: Option<usize> = None;
     let mut current_commit = String::new();
     let mut current_author = String::new();
@@ -34,6 +38,11 @@ pub fn parse_porcelain_blame(output: &str) -> HashMap<usize, RawBlameData> {
                 if let Ok(ln) = parts[2].parse::<usize>() {
                     current_line = Some(ln);
                 }
+                if let Some((author, email, timestamp)) = commits.get(&current_commit) {
+                    current_author.clone_from(author);
+                    current_email.clone_from(email);
+                    current_timestamp = *timestamp;
+                }
             }
         } else if let Some(stripped) = line.strip_prefix("author ") {
             current_author = stripped.to_string();
@@ -44,6 +53,13 @@ pub fn parse_porcelain_blame(output: &str) -> HashMap<usize, RawBlameData> {
         } else if line.starts_with('\t') {
             // Content line marks the end of a blame entry
             if let Some(ln) = current_line.take() {
+                commits.entry(current_commit.clone()).or_insert_with(|| {
+                    (
+                        current_author.clone(),
+                        current_email.clone(),
+                        current_timestamp,
+                    )
+                });
                 result.insert(
                     ln,
                     RawBlameData {
@@ -97,6 +113,9 @@ pub fn parse_duration_days(s: &str) -> Result<u64> {
 }
 
 /// Build blame entries for all TODO items in a scan result.
+///
+/// Runs `git blame` once per file and looks every item's line up in the
+/// parsed output, so a hotspot file with many TODOs costs one git call.
 pub fn compute_blame(
     scan: &ScanResult,
     root: &Path,
@@ -111,13 +130,14 @@ pub fn compute_blame(
     let mut entries: Vec<BlameEntry> = Vec::new();
 
     for (file, items) in &by_file {
-        let blame_data = match blame_file(file, root) {
-            Ok(data) => data,
-            Err(_) => continue, // Skip files not tracked by git
-        };
+        // Files git can't blame (untracked, outside a repo) keep their items
+        // with unknown blame
+        let blame_data = blame_file(file, root).unwrap_or_default();
 
         for item in items {
-            let raw = blame_data.get(&item.line);
+            // Notebook items count lines within their cell, which git
+            // can't map back to the file
+            let raw = blame_data.get(&item.line).filter(|_| item.cell.is_none());
             let blame_info = match raw {
                 Some(raw) => {
                     let age_days = compute_age_days(raw.timestamp);
@@ -129,16 +149,10 @@ pub fn compute_blame(
                         commit: raw.commit.clone(),
                     }
                 }
-                None => BlameInfo {
-                    author: "Unknown".to_string(),
-                    email: String::new(),
-                    date: String::new(),
-                    age_days: 0,
-                    commit: String::new(),
-                },
+                None => BlameInfo::unknown(),
             };
 
-            let stale = blame_info.age_days >= stale_threshold_days;
+            let stale = !blame_info.is_unknown() && blame_info.age_days >= stale_threshold_days;
 
             entries.push(BlameEntry {
                 item: (*item).clone(),
@@ -156,26 +170,103 @@ pub fn compute_blame(
             .then(a.item.line.cmp(&b.item.line))
     });
 
-    let total = entries.len();
-    let stale_count = entries.iter().filter(|e| e.stale).count();
-    let avg_age_days = if total > 0 {
-        entries.iter().map(|e| e.blame.age_days).sum::<u64>() / total as u64
+    let mut result = BlameResult {
+        entries,
+        total: 0,
+        avg_age_days: 0,
+        stale_count: 0,
+        unknown_count: 0,
+        stale_threshold_days,
+    };
+    summarize(&mut result);
+    Ok(result)
+}
+
+/// Recompute the summary counts from `result.entries`. Unknown-blame entries
+/// count toward `total` and `unknown_count` but not the average age.
+pub fn summarize(result: &mut BlameResult) {
+    result.total = result.entries.len();
+    result.stale_count = result.entries.iter().filter(|e| e.stale).count();
+    result.unknown_count = result
+        .entries
+        .iter()
+        .filter(|e| e.blame.is_unknown())
+        .count();
+    let known = result.total - result.unknown_count;
+    result.avg_age_days = if known > 0 {
+        result.entries.iter().map(|e| e.blame.age_days).sum::<u64>() / known as u64
     } else {
         0
     };
+}
 
-    Ok(BlameResult {
-        entries,
-        total,
-        avg_age_days,
-        stale_count,
-        stale_threshold_days,
-    })
+/// Placeholder written over redacted blame fields.
+pub const REDACTED: &str = "redacted";
+
+/// Strip identifying blame data so a report can be shared publicly.
+///
+/// Emails are always replaced; author names are replaced too when `authors`
+/// is set. Empty emails (lines git could not attribute) stay empty.
+pub fn redact(result: &mut BlameResult, authors: bool) {
+    for entry in &mut result.entries {
+        if !entry.blame.email.is_empty() {
+            entry.blame.email = REDACTED.to_string();
+        }
+        if authors {
+            entry.blame.author = REDACTED.to_string();
+        }
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::model::Tag;
+    use crate::test_helpers::helpers::make_item;
+
+    fn sample_result() -> BlameResult {
+        let entry = |author: &str, email: &str| BlameEntry {
+            item: make_item("main.rs", 1, Tag::Todo, "task"),
+            blame: BlameInfo {
+                author: author.to_string(),
+                email: email.to_string(),
+                date: "2025-01-01".to_string(),
+                age_days: 10,
+                commit: "abc123".to_string(),
+            },
+            stale: false,
+        };
+        BlameResult {
+            entries: vec![entry("Alice", "alice@example.com"), entry("Unknown", "")],
+            total: 2,
+            avg_age_days: 10,
+            stale_count: 0,
+            unknown_count: 0,
+            stale_threshold_days: 365,
+        }
+    }
+
+    #[test]
+    fn test_redact_replaces_emails_in_json() {
+        let mut result = sample_result();
+        redact(&mut result, false);
+
+        let json = serde_json::to_string(&result).unwrap();
+        assert!(!json.contains("alice@example.com"));
+        assert_eq!(result.entries[0].blame.email, REDACTED);
+        assert_eq!(result.entries[0].blame.author, "Alice");
+        assert_eq!(result.entries[1].blame.email, "");
+    }
+
+    #[test]
+    fn test_redact_authors() {
+        let mut result = sample_result();
+        redact(&mut result, true);
+
+        let json = serde_json::to_string(&result).unwrap();
+        assert!(!json.contains("Alice"));
+        assert!(result.entries.iter().all(|e| e.blame.author == REDACTED));
+    }
 
     #[test]
     fn test_parse_duration_days_with_suffix() {
@@ -269,6 +360,33 @@ filename test.rs
         assert_eq!(result.get(&2).unwrap().author, "Bob");
     }
 
+    #[test]
+    fn test_parse_porcelain_blame_repeated_commit_reuses_author() {
+        // The second block for commit abc... carries no author fields
+        let output = "\
+abc1234567890123456789012345678901234567 1 1 1
+author Alice
+author-mail <alice@test.com>
+author-time 1704067200
+filename test.rs
+\tline one
+def4567890123456789012345678901234567890 2 2 1
+author Bob
+author-mail <bob@test.com>
+author-time 1704153600
+filename test.rs
+\tline two
+abc1234567890123456789012345678901234567 2 3 1
+\tline three
+";
+        let result = parse_porcelain_blame(output);
+        let data = result.get(&3).unwrap();
+        assert_eq!(data.author, "Alice");
+        assert_eq!(data.email, "alice@test.com");
+        assert_eq!(data.timestamp, 1704067200);
+        assert_eq!(data.commit, "abc12345");
+    }
+
     #[test]
     fn test_parse_porcelain_blame_empty() {
         let result = parse_porcelain_blame("");
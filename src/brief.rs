@@ -69,7 +69,11 @@ mod tests {
         let scan = ScanResult {
             items,
             files_scanned: 2,
+            files_with_items: 2,
+            bytes_scanned: 0,
             ignored_items: vec![],
+            truncated: false,
+            total_found: None,
         };
 
         let result = compute_brief(&scan, None);
@@ -93,7 +97,11 @@ mod tests {
         let scan = ScanResult {
             items,
             files_scanned: 3,
+            files_with_items: 3,
+            bytes_scanned: 0,
             ignored_items: vec![],
+            truncated: false,
+            total_found: None,
         };
 
         let result = compute_brief(&scan, None);
@@ -114,7 +122,11 @@ mod tests {
         let scan = ScanResult {
             items,
             files_scanned: 2,
+            files_with_items: 2,
+            bytes_scanned: 0,
             ignored_items: vec![],
+            truncated: false,
+            total_found: None,
         };
 
         let result = compute_brief(&scan, None);
@@ -126,7 +138,11 @@ mod tests {
         let scan = ScanResult {
             items: vec![make_item("a.rs", 1, Tag::Todo, "task")],
             files_scanned: 1,
+            files_with_items: 1,
+            bytes_scanned: 0,
             ignored_items: vec![],
+            truncated: false,
+            total_found: None,
         };
         let diff = DiffResult {
             entries: vec![],
@@ -155,7 +171,11 @@ mod tests {
         let scan = ScanResult {
             items,
             files_scanned: 2,
+            files_with_items: 2,
+            bytes_scanned: 0,
             ignored_items: vec![],
+            truncated: false,
+            total_found: None,
         };
 
         let result = compute_brief(&scan, None);
@@ -175,7 +195,11 @@ mod tests {
         let scan = ScanResult {
             items,
             files_scanned: 2,
+            files_with_items: 2,
+            bytes_scanned: 0,
             ignored_items: vec![],
+            truncated: false,
+            total_found: None,
         };
 
         let result = compute_brief(&scan, None);
@@ -194,7 +218,11 @@ mod tests {
         let scan = ScanResult {
             items,
             files_scanned: 1,
+            files_with_items: 1,
+            bytes_scanned: 0,
             ignored_items: vec![],
+            truncated: false,
+            total_found: None,
         };
 
         let result = compute_brief(&scan, None);
@@ -216,7 +244,11 @@ mod tests {
         let scan = ScanResult {
             items,
             files_scanned: 3,
+            files_with_items: 3,
+            bytes_scanned: 0,
             ignored_items: vec![],
+            truncated: false,
+            total_found: None,
         };
 
         let result = compute_brief(&scan, None);
@@ -231,7 +263,11 @@ mod tests {
         let scan = ScanResult {
             items: vec![],
             files_scanned: 0,
+            files_with_items: 0,
+            bytes_scanned: 0,
             ignored_items: vec![],
+            truncated: false,
+            total_found: None,
         };
 
         let result = compute_brief(&scan, None);
@@ -1,4 +1,4 @@
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
 use std::fs;
 use std::path::{Path, PathBuf};
 use std::time::SystemTime;
@@ -7,7 +7,7 @@ use anyhow::Result;
 use serde::{Deserialize, Serialize};
 
 use crate::config::Config;
-use crate::model::TodoItem;
+use crate::model::{IgnoreReason, TodoItem};
 
 #[derive(Debug, Serialize, Deserialize)]
 pub struct CacheEntry {
@@ -15,6 +15,10 @@ pub struct CacheEntry {
     pub items: Vec<TodoItem>,
     #[serde(default)]
     pub ignored_items: Vec<TodoItem>,
+    /// `ignore_reason` of each ignored item, which `TodoItem` itself does not
+    /// serialize.
+    #[serde(default)]
+    pub ignore_reasons: Vec<IgnoreReason>,
     pub mtime_secs: u64,
     pub mtime_nanos: u32,
 }
@@ -31,6 +35,10 @@ pub struct ScanCache {
     pub entries: HashMap<PathBuf, CacheEntry>,
 }
 
+/// Bumped when the cached item layout changes, so caches written by an older
+/// build are discarded instead of misread.
+const CACHE_FORMAT_VERSION: u32 = 4;
+
 /// Maximum cache file size (50 MiB) to prevent memory exhaustion from
 /// crafted or corrupted cache files.
 const MAX_CACHE_SIZE: usize = 50 * 1024 * 1024;
@@ -50,7 +58,13 @@ impl ScanCache {
         if data.len() > max_size {
             return None;
         }
-        bincode::deserialize(data).ok()
+        let mut cache: Self = bincode::deserialize(data).ok()?;
+        for entry in cache.entries.values_mut() {
+            for (item, reason) in entry.ignored_items.iter_mut().zip(&entry.ignore_reasons) {
+                item.ignore_reason = Some(*reason);
+            }
+        }
+        Some(cache)
     }
 
     /// Load cache from disk. Returns None if missing, oversized, or corrupt.
@@ -81,7 +95,8 @@ impl ScanCache {
     /// Compute a deterministic hash of the config fields that affect scanning.
     pub fn config_hash(config: &Config) -> [u8; 32] {
         let mut hasher = blake3::Hasher::new();
-        for tag in &config.tags {
+        hasher.update(&CACHE_FORMAT_VERSION.to_le_bytes());
+        for tag in config.active_tags() {
             hasher.update(tag.as_bytes());
             hasher.update(b"\0");
         }
@@ -95,6 +110,21 @@ impl ScanCache {
             hasher.update(pat.as_bytes());
             hasher.update(b"\0");
         }
+        hasher.update(b"\x01");
+        hasher.update(format!("{:?}", config.continuation_lines).as_bytes());
+        hasher.update(format!("{:?}", config.strip_comment_terminators).as_bytes());
+        hasher.update(format!("{:?}", config.collapse_whitespace).as_bytes());
+        hasher.update(format!("{:?}", config.string_literals_as_ignored).as_bytes());
+        hasher.update(format!("{:?}", config.max_line_length).as_bytes());
+        hasher.update(format!("{:?}", config.case_insensitive_tags).as_bytes());
+        for marker in &config.resolved_markers {
+            hasher.update(marker.as_bytes());
+            hasher.update(b"\0");
+        }
+        hasher.update(b"\x01");
+        let comment_prefixes: BTreeMap<_, _> = config.comment_prefixes.iter().collect();
+        hasher.update(format!("{:?}", comment_prefixes).as_bytes());
+        hasher.update(format!("{:?}", config.permissive_unknown).as_bytes());
         *hasher.finalize().as_bytes()
     }
 
@@ -138,12 +168,17 @@ impl ScanCache {
         mtime: SystemTime,
     ) {
         let (secs, nanos) = system_time_to_parts(mtime);
+        let ignore_reasons = ignored_items
+            .iter()
+            .map(|i| i.ignore_reason.unwrap_or(IgnoreReason::Marker))
+            .collect();
         self.entries.insert(
             path,
             CacheEntry {
                 content_hash,
                 items,
                 ignored_items,
+                ignore_reasons,
                 mtime_secs: secs,
                 mtime_nanos: nanos,
             },
@@ -189,6 +224,9 @@ mod tests {
         TodoItem {
             file: file.to_string(),
             line: 1,
+            byte_offset: 0,
+            cell: None,
+            labels: vec![],
             tag: Tag::Todo,
             message: msg.to_string(),
             author: Some("alice".to_string()),
@@ -199,6 +237,8 @@ mod tests {
                 month: 6,
                 day: 1,
             }),
+            ignore_reason: None,
+            severity: None,
         }
     }
 
@@ -346,6 +386,39 @@ mod tests {
         assert_eq!(loaded.entries.len(), 1);
     }
 
+    #[test]
+    fn test_ignore_reasons_survive_round_trip() {
+        let mut cache = ScanCache::new(ScanCache::config_hash(&Config::default()));
+        let mut literal = make_item("test.rs", 2, Tag::Todo, "in a string");
+        literal.ignore_reason = Some(IgnoreReason::StringLiteral);
+        let mut marked = make_item("test.rs", 3, Tag::Todo, "suppressed");
+        marked.ignore_reason = Some(IgnoreReason::Marker);
+        cache.insert(
+            PathBuf::from("test.rs"),
+            *blake3::hash(b"content").as_bytes(),
+            vec![make_item("test.rs", 1, Tag::Todo, "task")],
+            vec![literal, marked],
+            SystemTime::UNIX_EPOCH,
+        );
+
+        let data = bincode::serialize(&cache).unwrap();
+        let loaded = ScanCache::deserialize_with_limit(&data, MAX_CACHE_SIZE).unwrap();
+        let entry = &loaded.entries[&PathBuf::from("test.rs")];
+        let reasons: Vec<_> = entry
+            .ignored_items
+            .iter()
+            .map(|i| i.ignore_reason)
+            .collect();
+        assert_eq!(
+            reasons,
+            vec![
+                Some(IgnoreReason::StringLiteral),
+                Some(IgnoreReason::Marker)
+            ]
+        );
+        assert_eq!(entry.items[0].ignore_reason, None);
+    }
+
     #[test]
     fn test_load_corrupt_file_returns_none() {
         let dir = tempfile::tempdir().unwrap();
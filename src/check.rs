@@ -1,4 +1,4 @@
-use std::collections::HashSet;
+use std::collections::{BTreeMap, HashSet};
 
 use crate::config::Config;
 use crate::deadline::Deadline;
@@ -9,8 +9,12 @@ pub struct CheckOverrides {
     pub block_tags: Vec<String>,
     pub max_new: Option<usize>,
     pub expired: bool,
+    pub require_issue: bool,
 }
 
+/// Tags checked by `require_issue` when `require_issue_tags` is not set.
+const DEFAULT_REQUIRE_ISSUE_TAGS: &[&str] = &["FIXME", "BUG"];
+
 pub fn run_check(
     scan: &ScanResult,
     diff: Option<&DiffResult>,
@@ -87,6 +91,35 @@ pub fn run_check(
         }
     }
 
+    // Step 5: untracked high-severity items
+    let require_issue = overrides.require_issue || config.check.require_issue.unwrap_or(false);
+    if require_issue {
+        let tracked_tags: HashSet<String> = if config.check.require_issue_tags.is_empty() {
+            DEFAULT_REQUIRE_ISSUE_TAGS
+                .iter()
+                .map(|t| t.to_string())
+                .collect()
+        } else {
+            config
+                .check
+                .require_issue_tags
+                .iter()
+                .map(|t| t.to_uppercase())
+                .collect()
+        };
+        for item in &scan.items {
+            if item.issue_ref.is_none() && tracked_tags.contains(item.tag.as_str()) {
+                violations.push(CheckViolation {
+                    rule: format!("untracked_{}", item.tag.as_str().to_lowercase()),
+                    message: format!(
+                        "{} without an issue reference in {}:{}",
+                        item.tag, item.file, item.line
+                    ),
+                });
+            }
+        }
+    }
+
     let passed = violations.is_empty();
     let total = scan.items.len();
 
@@ -97,6 +130,52 @@ pub fn run_check(
     }
 }
 
+/// Flag every blamed item older than `max_age_days` as an `old_todo` violation.
+pub fn check_max_age(blame: &BlameResult, max_age_days: u64) -> Vec<CheckViolation> {
+    blame
+        .entries
+        .iter()
+        .filter(|e| e.blame.age_days > max_age_days)
+        .map(|e| CheckViolation {
+            rule: "old_todo".to_string(),
+            message: format!(
+                "{} in {}:{} is {} days old (max: {})",
+                e.item.tag, e.item.file, e.item.line, e.blame.age_days, max_age_days
+            ),
+        })
+        .collect()
+}
+
+/// Bucket used for items without an author when `count_unassigned` is set.
+const UNASSIGNED: &str = "unassigned";
+
+/// Flag every author owning more than `max` items as an `author_budget`
+/// violation. `authors` holds one attributed author per item; items without
+/// one are exempt unless `count_unassigned` pools them into a single bucket.
+pub fn check_author_budget<'a>(
+    authors: impl IntoIterator<Item = Option<&'a str>>,
+    max: usize,
+    count_unassigned: bool,
+) -> Vec<CheckViolation> {
+    let mut counts: BTreeMap<&str, usize> = BTreeMap::new();
+    for author in authors {
+        match author {
+            Some(author) => *counts.entry(author).or_default() += 1,
+            None if count_unassigned => *counts.entry(UNASSIGNED).or_default() += 1,
+            None => {}
+        }
+    }
+
+    counts
+        .into_iter()
+        .filter(|&(_, count)| count > max)
+        .map(|(author, count)| CheckViolation {
+            rule: "author_budget".to_string(),
+            message: format!("{} owns {} TODOs (max per author: {})", author, count, max),
+        })
+        .collect()
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -109,6 +188,7 @@ mod tests {
             block_tags: vec![],
             max_new: None,
             expired: false,
+            require_issue: false,
         }
     }
 
@@ -125,7 +205,11 @@ mod tests {
         let scan = ScanResult {
             items: vec![make_item("a.rs", 1, Tag::Todo, "do something")],
             files_scanned: 1,
+            files_with_items: 1,
+            bytes_scanned: 0,
             ignored_items: vec![],
+            truncated: false,
+            total_found: None,
         };
         let config = Config::default();
         let overrides = CheckOverrides {
@@ -147,7 +231,11 @@ mod tests {
         let scan = ScanResult {
             items,
             files_scanned: 1,
+            files_with_items: 1,
+            bytes_scanned: 0,
             ignored_items: vec![],
+            truncated: false,
+            total_found: None,
         };
         let config = Config::default();
         let overrides = CheckOverrides {
@@ -171,7 +259,11 @@ mod tests {
                 make_item("b.rs", 5, Tag::Todo, "normal todo"),
             ],
             files_scanned: 2,
+            files_with_items: 2,
+            bytes_scanned: 0,
             ignored_items: vec![],
+            truncated: false,
+            total_found: None,
         };
         let config = Config::default();
         let overrides = CheckOverrides {
@@ -192,7 +284,11 @@ mod tests {
         let scan = ScanResult {
             items: vec![make_item("a.rs", 1, Tag::Todo, "new todo")],
             files_scanned: 1,
+            files_with_items: 1,
+            bytes_scanned: 0,
             ignored_items: vec![],
+            truncated: false,
+            total_found: None,
         };
         let diff = DiffResult {
             entries: vec![DiffEntry {
@@ -225,7 +321,11 @@ mod tests {
                 make_item("b.rs", 2, Tag::Note, "just a note"),
             ],
             files_scanned: 2,
+            files_with_items: 2,
+            bytes_scanned: 0,
             ignored_items: vec![],
+            truncated: false,
+            total_found: None,
         };
         let config = Config::default();
         let overrides = default_overrides();
@@ -247,7 +347,11 @@ mod tests {
         let scan = ScanResult {
             items: vec![item],
             files_scanned: 1,
+            files_with_items: 1,
+            bytes_scanned: 0,
             ignored_items: vec![],
+            truncated: false,
+            total_found: None,
         };
         let config = Config::default();
         let overrides = CheckOverrides {
@@ -273,7 +377,11 @@ mod tests {
         let scan = ScanResult {
             items: vec![item],
             files_scanned: 1,
+            files_with_items: 1,
+            bytes_scanned: 0,
             ignored_items: vec![],
+            truncated: false,
+            total_found: None,
         };
         let config = Config::default();
         let overrides = CheckOverrides {
@@ -297,7 +405,11 @@ mod tests {
         let scan = ScanResult {
             items: vec![item],
             files_scanned: 1,
+            files_with_items: 1,
+            bytes_scanned: 0,
             ignored_items: vec![],
+            truncated: false,
+            total_found: None,
         };
         let config = Config::default();
         let overrides = default_overrides(); // expired: false
@@ -315,7 +427,11 @@ mod tests {
         let scan = ScanResult {
             items,
             files_scanned: 1,
+            files_with_items: 1,
+            bytes_scanned: 0,
             ignored_items: vec![],
+            truncated: false,
+            total_found: None,
         };
         let mut config = Config::default();
         config.check.max = Some(5);
@@ -334,7 +450,11 @@ mod tests {
                 make_item("b.rs", 2, Tag::Hack, "hack"),
             ],
             files_scanned: 2,
+            files_with_items: 2,
+            bytes_scanned: 0,
             ignored_items: vec![],
+            truncated: false,
+            total_found: None,
         };
         let mut config = Config::default();
         config.check.block_tags = vec!["BUG".to_string()];
@@ -354,7 +474,11 @@ mod tests {
         let scan = ScanResult {
             items: vec![make_item("a.rs", 1, Tag::Todo, "task")],
             files_scanned: 1,
+            files_with_items: 1,
+            bytes_scanned: 0,
             ignored_items: vec![],
+            truncated: false,
+            total_found: None,
         };
         let diff = DiffResult {
             entries: vec![],
@@ -376,7 +500,11 @@ mod tests {
         let scan = ScanResult {
             items: vec![make_item("a.rs", 1, Tag::Todo, "task")],
             files_scanned: 1,
+            files_with_items: 1,
+            bytes_scanned: 0,
             ignored_items: vec![],
+            truncated: false,
+            total_found: None,
         };
         let config = Config::default();
         let overrides = CheckOverrides {
@@ -400,7 +528,11 @@ mod tests {
         let scan = ScanResult {
             items: vec![item],
             files_scanned: 1,
+            files_with_items: 1,
+            bytes_scanned: 0,
             ignored_items: vec![],
+            truncated: false,
+            total_found: None,
         };
         let mut config = Config::default();
         config.check.expired = Some(true);
@@ -428,7 +560,11 @@ mod tests {
         let scan = ScanResult {
             items: all_items,
             files_scanned: 2,
+            files_with_items: 2,
+            bytes_scanned: 0,
             ignored_items: vec![],
+            truncated: false,
+            total_found: None,
         };
         let diff = DiffResult {
             entries: vec![],
@@ -442,6 +578,7 @@ mod tests {
             block_tags: vec!["BUG".to_string()],
             max_new: Some(3),
             expired: true,
+            require_issue: false,
         };
 
         let result = run_check(&scan, Some(&diff), &config, &overrides, &test_today());
@@ -460,7 +597,11 @@ mod tests {
         let scan = ScanResult {
             items: vec![make_item("a.rs", 1, Tag::Hack, "workaround")],
             files_scanned: 1,
+            files_with_items: 1,
+            bytes_scanned: 0,
             ignored_items: vec![],
+            truncated: false,
+            total_found: None,
         };
         let config = Config::default();
         let overrides = CheckOverrides {
@@ -478,7 +619,11 @@ mod tests {
         let scan = ScanResult {
             items: vec![make_item("a.rs", 1, Tag::Todo, "task")],
             files_scanned: 1,
+            files_with_items: 1,
+            bytes_scanned: 0,
             ignored_items: vec![],
+            truncated: false,
+            total_found: None,
         };
         let diff = DiffResult {
             entries: vec![],
@@ -507,7 +652,11 @@ mod tests {
         let scan = ScanResult {
             items: vec![item],
             files_scanned: 1,
+            files_with_items: 1,
+            bytes_scanned: 0,
             ignored_items: vec![],
+            truncated: false,
+            total_found: None,
         };
         let config = Config::default();
         let overrides = CheckOverrides {
@@ -524,7 +673,11 @@ mod tests {
         let scan = ScanResult {
             items: vec![make_item("a.rs", 1, Tag::Todo, "no deadline")],
             files_scanned: 1,
+            files_with_items: 1,
+            bytes_scanned: 0,
             ignored_items: vec![],
+            truncated: false,
+            total_found: None,
         };
         let config = Config::default();
         let overrides = CheckOverrides {
@@ -541,7 +694,11 @@ mod tests {
         let scan = ScanResult {
             items: vec![],
             files_scanned: 0,
+            files_with_items: 0,
+            bytes_scanned: 0,
             ignored_items: vec![],
+            truncated: false,
+            total_found: None,
         };
         let config = Config::default();
         let overrides = CheckOverrides {
@@ -554,4 +711,124 @@ mod tests {
         assert!(result.passed);
         assert_eq!(result.total, 0);
     }
+
+    // --- require_issue ---
+
+    fn scan_of(items: Vec<TodoItem>) -> ScanResult {
+        ScanResult {
+            items,
+            files_scanned: 1,
+            files_with_items: 1,
+            bytes_scanned: 0,
+            ignored_items: vec![],
+            truncated: false,
+            total_found: None,
+        }
+    }
+
+    #[test]
+    fn test_require_issue_tracked_bug_passes() {
+        let mut bug = make_item("a.rs", 1, Tag::Bug, "crash on save #12");
+        bug.issue_ref = Some("#12".to_string());
+        let scan = scan_of(vec![
+            bug,
+            make_item("a.rs", 2, Tag::Todo, "no issue needed"),
+        ]);
+        let overrides = CheckOverrides {
+            require_issue: true,
+            ..default_overrides()
+        };
+
+        let result = run_check(&scan, None, &Config::default(), &overrides, &test_today());
+        assert!(result.passed);
+    }
+
+    #[test]
+    fn test_require_issue_untracked_fixme_fails() {
+        let scan = scan_of(vec![make_item("a.rs", 3, Tag::Fixme, "leaks handles")]);
+        let overrides = CheckOverrides {
+            require_issue: true,
+            ..default_overrides()
+        };
+
+        let result = run_check(&scan, None, &Config::default(), &overrides, &test_today());
+        assert!(!result.passed);
+        assert_eq!(result.violations.len(), 1);
+        assert_eq!(result.violations[0].rule, "untracked_fixme");
+        assert!(result.violations[0].message.contains("a.rs:3"));
+    }
+
+    #[test]
+    fn test_require_issue_uses_configured_tags() {
+        let scan = scan_of(vec![
+            make_item("a.rs", 1, Tag::Fixme, "exempt now"),
+            make_item("a.rs", 2, Tag::Hack, "needs tracking"),
+        ]);
+        let mut config = Config::default();
+        config.check.require_issue = Some(true);
+        config.check.require_issue_tags = vec!["hack".to_string()];
+
+        let result = run_check(&scan, None, &config, &default_overrides(), &test_today());
+        let rules: Vec<&str> = result.violations.iter().map(|v| v.rule.as_str()).collect();
+        assert_eq!(rules, vec!["untracked_hack"]);
+    }
+
+    // --- max age (blame) ---
+
+    #[test]
+    fn test_check_max_age_flags_items_over_threshold() {
+        let entry = |line, age_days| BlameEntry {
+            item: make_item("a.rs", line, Tag::Todo, "task"),
+            blame: BlameInfo {
+                author: "A".to_string(),
+                email: String::new(),
+                date: String::new(),
+                age_days,
+                commit: String::new(),
+            },
+            stale: false,
+        };
+        let blame = BlameResult {
+            entries: vec![entry(1, 400), entry(2, 365), entry(3, 10)],
+            total: 3,
+            avg_age_days: 258,
+            stale_count: 0,
+            unknown_count: 0,
+            stale_threshold_days: 365,
+        };
+
+        let violations = check_max_age(&blame, 365);
+        assert_eq!(violations.len(), 1);
+        assert_eq!(violations[0].rule, "old_todo");
+        assert!(violations[0].message.contains("a.rs:1"));
+        assert!(violations[0].message.contains("400 days"));
+    }
+
+    #[test]
+    fn test_author_budget_at_limit_passes() {
+        let authors = [Some("alice"), Some("alice"), Some("bob")];
+        assert!(check_author_budget(authors, 2, false).is_empty());
+    }
+
+    #[test]
+    fn test_author_budget_over_limit_fails() {
+        let authors = [Some("alice"), Some("alice"), Some("alice"), Some("bob")];
+        let violations = check_author_budget(authors, 2, false);
+        assert_eq!(violations.len(), 1);
+        assert_eq!(violations[0].rule, "author_budget");
+        assert_eq!(
+            violations[0].message,
+            "alice owns 3 TODOs (max per author: 2)"
+        );
+    }
+
+    #[test]
+    fn test_author_budget_unassigned_exempt_by_default() {
+        let authors = [None, None, None, Some("bob")];
+        assert!(check_author_budget(authors, 2, false).is_empty());
+
+        let violations = check_author_budget(authors, 2, true);
+        assert_eq!(violations.len(), 1);
+        assert!(violations[0].message.starts_with("unassigned owns 3"));
+    }
 }
@@ -1,17 +1,24 @@
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::sync::LazyLock;
 
 use anyhow::Result;
 use regex::Regex;
 
 use crate::blame::parse_duration_days;
-use crate::config::Config;
+use crate::config::{Config, DupScope};
 use crate::date_utils;
+use crate::deadline::{self, Deadline};
 use crate::model::{CleanResult, CleanViolation, ScanResult, TodoItem};
 
 static ISO8601_RE: LazyLock<Regex> =
     LazyLock::new(|| Regex::new(r"(\d{4})-(\d{2})-(\d{2})T(\d{2}):(\d{2}):(\d{2})").unwrap());
 
+static CREATED_RE: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"\bcreated=(\d{4}-\d{2}-\d{2})\b").unwrap());
+
+/// Age threshold for the `created=` fallback when `clean.max_age` is unset.
+const DEFAULT_MAX_AGE: &str = "365d";
+
 static ISSUE_NUMBER_RE: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"^#(\d+)$").unwrap());
 
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -115,32 +122,94 @@ fn extract_issue_number(issue_ref: &str) -> Option<u32> {
     caps[1].parse().ok()
 }
 
-/// Run clean analysis on scan results.
+/// Extract the `created=YYYY-MM-DD` metadata date from a TODO message.
+fn extract_created_date(message: &str) -> Option<Deadline> {
+    let caps = CREATED_RE.captures(message)?;
+    deadline::parse_deadline(&caps[1])
+}
+
+/// Which clean results make the command exit non-zero.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct CleanGate {
+    /// `--check`: fail on any violation.
+    pub check: bool,
+    /// `--fail-on-stale`: fail when stale items were found.
+    pub fail_on_stale: bool,
+    /// `--fail-on-duplicate`: fail when duplicates were found.
+    pub fail_on_duplicate: bool,
+}
+
+impl CleanGate {
+    /// When either `fail_on_*` flag is set, only the selected categories fail
+    /// (so `--check` alone doesn't turn the others back into failures);
+    /// otherwise `check` fails on any violation.
+    pub fn fails(&self, result: &CleanResult) -> bool {
+        if self.fail_on_stale || self.fail_on_duplicate {
+            (self.fail_on_stale && result.stale_count > 0)
+                || (self.fail_on_duplicate && result.duplicate_count > 0)
+        } else {
+            self.check && !result.passed
+        }
+    }
+}
+
+/// Run clean analysis on scan results. Items whose match key is in `grace`
+/// (recently added or moved) are exempt from the `created=` age rule.
+/// Fails when `since` or `clean.max_age` is not a valid duration.
 pub fn run_clean(
     scan: &ScanResult,
     config: &Config,
     issue_checker: Option<&dyn IssueChecker>,
     since_cli: Option<&str>,
-) -> CleanResult {
+    grace: &HashSet<String>,
+) -> Result<CleanResult> {
     let mut violations = Vec::new();
 
     let enable_stale = config.clean.stale_issues.unwrap_or(true);
     let enable_duplicates = config.clean.duplicates.unwrap_or(true);
+    let enable_owner = config.clean.deadline_without_owner.unwrap_or(false);
 
     // Resolve since: CLI > config
     let since_str = since_cli.or(config.clean.since.as_deref());
-    let since_days = since_str.and_then(|s| parse_duration_days(s).ok());
+    let since_days = since_str.map(parse_duration_days).transpose()?;
 
-    // Phase 1: Stale issue detection
+    // Phase 1: Stale issue detection. Items whose issue state could not be
+    // checked (no gh, JIRA refs, lookup failures) fall back to their
+    // `created=` date so shallow or offline checkouts still get results.
     if enable_stale {
-        if let Some(checker) = issue_checker {
-            detect_stale_issues(&scan.items, checker, since_days, &mut violations);
-        }
+        let mut resolved = match issue_checker {
+            Some(checker) => detect_stale_issues(&scan.items, checker, since_days, &mut violations),
+            None => HashSet::new(),
+        };
+        // Items still within the grace period keep an old `created=` date
+        // after a move, but are not stale yet.
+        resolved.extend(
+            scan.items
+                .iter()
+                .enumerate()
+                .filter(|(_, item)| grace.contains(&item.match_key()))
+                .map(|(idx, _)| idx),
+        );
+        let max_age_str = config.clean.max_age.as_deref().unwrap_or(DEFAULT_MAX_AGE);
+        let max_age_days = parse_duration_days(max_age_str)?;
+        detect_stale_by_age(
+            &scan.items,
+            &resolved,
+            max_age_days,
+            &deadline::today(),
+            &mut violations,
+        );
     }
 
     // Phase 2: Duplicate detection
     if enable_duplicates {
-        detect_duplicates(&scan.items, &mut violations);
+        let scope = config.clean.duplicate_scope.unwrap_or(DupScope::Repo);
+        detect_duplicates(&scan.items, scope, &mut violations);
+    }
+
+    // Phase 3: Deadlines need an owner (opt-in)
+    if enable_owner {
+        detect_deadline_without_owner(&scan.items, &mut violations);
     }
 
     // Sort by file, then line
@@ -151,28 +220,37 @@ pub fn run_clean(
         .filter(|v| v.rule == "stale_issue")
         .count();
     let duplicate_count = violations.iter().filter(|v| v.rule == "duplicate").count();
+    let deadline_without_owner_count = violations
+        .iter()
+        .filter(|v| v.rule == "deadline_without_owner")
+        .count();
+    let stale_age_count = violations.iter().filter(|v| v.rule == "stale_age").count();
 
-    CleanResult {
+    Ok(CleanResult {
         passed: violations.is_empty(),
         total_items: scan.items.len(),
         stale_count,
         duplicate_count,
+        deadline_without_owner_count,
+        stale_age_count,
         violations,
-    }
+    })
 }
 
+/// Flag items whose linked issue is closed. Returns the indices of items
+/// whose issue state was determined, whether open or closed.
 fn detect_stale_issues(
     items: &[TodoItem],
     checker: &dyn IssueChecker,
     since_days: Option<u64>,
     violations: &mut Vec<CleanViolation>,
-) {
+) -> HashSet<usize> {
     // Collect unique issue numbers first
-    let mut issue_items: Vec<(&TodoItem, u32)> = Vec::new();
-    for item in items {
+    let mut issue_items: Vec<(usize, &TodoItem, u32)> = Vec::new();
+    for (idx, item) in items.iter().enumerate() {
         if let Some(ref issue_ref) = item.issue_ref {
             if let Some(num) = extract_issue_number(issue_ref) {
-                issue_items.push((item, num));
+                issue_items.push((idx, item, num));
             }
             // Skip JIRA-style refs — gh can't query them
         }
@@ -183,11 +261,13 @@ fn detect_stale_issues(
         .unwrap_or_default()
         .as_secs() as i64;
 
-    for (item, issue_num) in &issue_items {
+    let mut resolved = HashSet::new();
+    for (idx, item, issue_num) in &issue_items {
         let state = match checker.check_issue(*issue_num) {
             Ok(Some(state)) => state,
-            _ => continue, // Skip if we can't determine state
+            _ => continue, // Leave for the created= fallback
         };
+        resolved.insert(*idx);
 
         if let IssueState::Closed { closed_at } = state {
             // Apply since filter if configured
@@ -216,18 +296,83 @@ fn detect_stale_issues(
             });
         }
     }
+    resolved
+}
+
+/// Flag items older than `max_age_days` according to their `created=` date,
+/// skipping those already judged by the issue checker. Items with an issue
+/// reference are `stale_issue`; the rest are `stale_age`, since nothing says
+/// their issue is done.
+fn detect_stale_by_age(
+    items: &[TodoItem],
+    resolved: &HashSet<usize>,
+    max_age_days: u64,
+    today: &Deadline,
+    violations: &mut Vec<CleanViolation>,
+) {
+    for (idx, item) in items.iter().enumerate() {
+        if resolved.contains(&idx) {
+            continue;
+        }
+        let Some(created) = extract_created_date(&item.message) else {
+            continue;
+        };
+        let age_days = -created.days_until(today);
+        if age_days < 0 || (age_days as u64) < max_age_days {
+            continue;
+        }
+
+        let rule = if item.issue_ref.is_some() {
+            "stale_issue"
+        } else {
+            "stale_age"
+        };
+        violations.push(CleanViolation {
+            rule: rule.to_string(),
+            message: format!("Created {} ({} days ago)", created, age_days),
+            file: item.file.clone(),
+            line: item.line,
+            issue_ref: item.issue_ref.clone(),
+            duplicate_of: None,
+        });
+    }
+}
+
+/// Flag items that carry a deadline but name nobody responsible for it.
+fn detect_deadline_without_owner(items: &[TodoItem], violations: &mut Vec<CleanViolation>) {
+    for item in items {
+        let Some(deadline) = &item.deadline else {
+            continue;
+        };
+        if item.author.is_some() {
+            continue;
+        }
+        violations.push(CleanViolation {
+            rule: "deadline_without_owner".to_string(),
+            message: format!("Deadline {} has no owner", deadline),
+            file: item.file.clone(),
+            line: item.line,
+            issue_ref: item.issue_ref.clone(),
+            duplicate_of: None,
+        });
+    }
 }
 
-fn detect_duplicates(items: &[TodoItem], violations: &mut Vec<CleanViolation>) {
-    // Group by normalized message
-    let mut groups: HashMap<String, Vec<&TodoItem>> = HashMap::new();
+fn detect_duplicates(items: &[TodoItem], scope: DupScope, violations: &mut Vec<CleanViolation>) {
+    // Group by normalized message, within the file or directory for narrower scopes
+    let mut groups: HashMap<(&str, String), Vec<&TodoItem>> = HashMap::new();
 
     for item in items {
         let normalized = normalize_message(&item.message);
         if normalized.is_empty() {
             continue; // Skip empty messages
         }
-        groups.entry(normalized).or_default().push(item);
+        let within = match scope {
+            DupScope::Repo => "",
+            DupScope::File => item.file.as_str(),
+            DupScope::Dir => item.file.rsplit_once('/').map_or("", |(dir, _)| dir),
+        };
+        groups.entry((within, normalized)).or_default().push(item);
     }
 
     for group in groups.values() {
@@ -287,12 +432,17 @@ mod tests {
         TodoItem {
             file: file.to_string(),
             line,
+            byte_offset: 0,
+            cell: None,
+            labels: vec![],
             tag,
             message: message.to_string(),
             author: None,
             issue_ref: Some(issue_ref.to_string()),
             priority: Priority::Normal,
             deadline: None,
+            ignore_reason: None,
+            severity: None,
         }
     }
 
@@ -313,11 +463,22 @@ mod tests {
                 "#42",
             )],
             files_scanned: 1,
+            files_with_items: 1,
+            bytes_scanned: 0,
             ignored_items: vec![],
+            truncated: false,
+            total_found: None,
         };
         let checker =
             MockIssueChecker::new(vec![(42, Some(IssueState::Closed { closed_at: None }))]);
-        let result = run_clean(&scan, &default_config(), Some(&checker), None);
+        let result = run_clean(
+            &scan,
+            &default_config(),
+            Some(&checker),
+            None,
+            &HashSet::new(),
+        )
+        .unwrap();
         assert!(!result.passed);
         assert_eq!(result.stale_count, 1);
         assert_eq!(result.violations[0].rule, "stale_issue");
@@ -335,10 +496,21 @@ mod tests {
                 "#42",
             )],
             files_scanned: 1,
+            files_with_items: 1,
+            bytes_scanned: 0,
             ignored_items: vec![],
+            truncated: false,
+            total_found: None,
         };
         let checker = MockIssueChecker::new(vec![(42, Some(IssueState::Open))]);
-        let result = run_clean(&scan, &default_config(), Some(&checker), None);
+        let result = run_clean(
+            &scan,
+            &default_config(),
+            Some(&checker),
+            None,
+            &HashSet::new(),
+        )
+        .unwrap();
         assert!(result.passed);
         assert_eq!(result.stale_count, 0);
     }
@@ -362,7 +534,11 @@ mod tests {
                 "#42",
             )],
             files_scanned: 1,
+            files_with_items: 1,
+            bytes_scanned: 0,
             ignored_items: vec![],
+            truncated: false,
+            total_found: None,
         };
         let checker = MockIssueChecker::new(vec![(
             42,
@@ -372,7 +548,14 @@ mod tests {
         )]);
 
         // Since 30 days — closed 5 days ago should NOT be flagged
-        let result = run_clean(&scan, &default_config(), Some(&checker), Some("30d"));
+        let result = run_clean(
+            &scan,
+            &default_config(),
+            Some(&checker),
+            Some("30d"),
+            &HashSet::new(),
+        )
+        .unwrap();
         assert!(result.passed);
         assert_eq!(result.stale_count, 0);
     }
@@ -396,7 +579,11 @@ mod tests {
                 "#42",
             )],
             files_scanned: 1,
+            files_with_items: 1,
+            bytes_scanned: 0,
             ignored_items: vec![],
+            truncated: false,
+            total_found: None,
         };
         let checker = MockIssueChecker::new(vec![(
             42,
@@ -406,7 +593,14 @@ mod tests {
         )]);
 
         // Since 30 days — closed 60 days ago SHOULD be flagged
-        let result = run_clean(&scan, &default_config(), Some(&checker), Some("30d"));
+        let result = run_clean(
+            &scan,
+            &default_config(),
+            Some(&checker),
+            Some("30d"),
+            &HashSet::new(),
+        )
+        .unwrap();
         assert!(!result.passed);
         assert_eq!(result.stale_count, 1);
     }
@@ -419,10 +613,21 @@ mod tests {
         let scan = ScanResult {
             items: vec![item],
             files_scanned: 1,
+            files_with_items: 1,
+            bytes_scanned: 0,
             ignored_items: vec![],
+            truncated: false,
+            total_found: None,
         };
         let checker = MockIssueChecker::new(vec![]);
-        let result = run_clean(&scan, &default_config(), Some(&checker), None);
+        let result = run_clean(
+            &scan,
+            &default_config(),
+            Some(&checker),
+            None,
+            &HashSet::new(),
+        )
+        .unwrap();
         assert!(result.passed);
         assert_eq!(result.stale_count, 0);
     }
@@ -438,9 +643,13 @@ mod tests {
                 "#42",
             )],
             files_scanned: 1,
+            files_with_items: 1,
+            bytes_scanned: 0,
             ignored_items: vec![],
+            truncated: false,
+            total_found: None,
         };
-        let result = run_clean(&scan, &default_config(), None, None);
+        let result = run_clean(&scan, &default_config(), None, None, &HashSet::new()).unwrap();
         assert!(result.passed);
         assert_eq!(result.stale_count, 0);
     }
@@ -455,9 +664,13 @@ mod tests {
                 make_item("b.rs", 5, Tag::Todo, "implement feature"),
             ],
             files_scanned: 2,
+            files_with_items: 2,
+            bytes_scanned: 0,
             ignored_items: vec![],
+            truncated: false,
+            total_found: None,
         };
-        let result = run_clean(&scan, &default_config(), None, None);
+        let result = run_clean(&scan, &default_config(), None, None, &HashSet::new()).unwrap();
         assert!(!result.passed);
         assert_eq!(result.duplicate_count, 1);
         assert_eq!(result.violations[0].rule, "duplicate");
@@ -471,9 +684,13 @@ mod tests {
                 make_item("b.rs", 5, Tag::Todo, "implement feature"),
             ],
             files_scanned: 2,
+            files_with_items: 2,
+            bytes_scanned: 0,
             ignored_items: vec![],
+            truncated: false,
+            total_found: None,
         };
-        let result = run_clean(&scan, &default_config(), None, None);
+        let result = run_clean(&scan, &default_config(), None, None, &HashSet::new()).unwrap();
         assert!(!result.passed);
         assert_eq!(result.duplicate_count, 1);
     }
@@ -486,9 +703,13 @@ mod tests {
                 make_item("b.rs", 5, Tag::Todo, "implement feature"),
             ],
             files_scanned: 2,
+            files_with_items: 2,
+            bytes_scanned: 0,
             ignored_items: vec![],
+            truncated: false,
+            total_found: None,
         };
-        let result = run_clean(&scan, &default_config(), None, None);
+        let result = run_clean(&scan, &default_config(), None, None, &HashSet::new()).unwrap();
         assert!(!result.passed);
         assert_eq!(result.duplicate_count, 1);
     }
@@ -501,9 +722,13 @@ mod tests {
                 make_item("b.rs", 5, Tag::Todo, "implement feature B"),
             ],
             files_scanned: 2,
+            files_with_items: 2,
+            bytes_scanned: 0,
             ignored_items: vec![],
+            truncated: false,
+            total_found: None,
         };
-        let result = run_clean(&scan, &default_config(), None, None);
+        let result = run_clean(&scan, &default_config(), None, None, &HashSet::new()).unwrap();
         assert!(result.passed);
         assert_eq!(result.duplicate_count, 0);
     }
@@ -516,9 +741,13 @@ mod tests {
                 make_item("b.rs", 5, Tag::Todo, ""),
             ],
             files_scanned: 2,
+            files_with_items: 2,
+            bytes_scanned: 0,
             ignored_items: vec![],
+            truncated: false,
+            total_found: None,
         };
-        let result = run_clean(&scan, &default_config(), None, None);
+        let result = run_clean(&scan, &default_config(), None, None, &HashSet::new()).unwrap();
         assert!(result.passed);
         assert_eq!(result.duplicate_count, 0);
     }
@@ -528,9 +757,13 @@ mod tests {
         let scan = ScanResult {
             items: vec![make_item("a.rs", 1, Tag::Todo, "unique message")],
             files_scanned: 1,
+            files_with_items: 1,
+            bytes_scanned: 0,
             ignored_items: vec![],
+            truncated: false,
+            total_found: None,
         };
-        let result = run_clean(&scan, &default_config(), None, None);
+        let result = run_clean(&scan, &default_config(), None, None, &HashSet::new()).unwrap();
         assert!(result.passed);
         assert_eq!(result.stale_count, 0);
         assert_eq!(result.duplicate_count, 0);
@@ -566,13 +799,17 @@ mod tests {
         let scan = ScanResult {
             items: vec![make_item_with_issue("a.rs", 1, Tag::Todo, "fix #42", "#42")],
             files_scanned: 1,
+            files_with_items: 1,
+            bytes_scanned: 0,
             ignored_items: vec![],
+            truncated: false,
+            total_found: None,
         };
         let checker =
             MockIssueChecker::new(vec![(42, Some(IssueState::Closed { closed_at: None }))]);
         let mut config = default_config();
         config.clean.stale_issues = Some(false);
-        let result = run_clean(&scan, &config, Some(&checker), None);
+        let result = run_clean(&scan, &config, Some(&checker), None, &HashSet::new()).unwrap();
         assert!(result.passed);
     }
 
@@ -584,11 +821,70 @@ mod tests {
                 make_item("b.rs", 2, Tag::Todo, "same message"),
             ],
             files_scanned: 2,
+            files_with_items: 2,
+            bytes_scanned: 0,
             ignored_items: vec![],
+            truncated: false,
+            total_found: None,
         };
         let mut config = default_config();
         config.clean.duplicates = Some(false);
-        let result = run_clean(&scan, &config, None, None);
+        let result = run_clean(&scan, &config, None, None, &HashSet::new()).unwrap();
+        assert!(result.passed);
+    }
+
+    // --- deadline_without_owner ---
+
+    fn owner_scan(author: Option<&str>, deadline: Option<&str>) -> ScanResult {
+        let mut item = make_item("a.rs", 3, Tag::Todo, "ship it");
+        item.author = author.map(str::to_string);
+        item.deadline = deadline.and_then(deadline::parse_deadline);
+        ScanResult {
+            items: vec![item],
+            files_scanned: 1,
+            files_with_items: 1,
+            bytes_scanned: 0,
+            ignored_items: vec![],
+            truncated: false,
+            total_found: None,
+        }
+    }
+
+    fn owner_config() -> Config {
+        let mut config = default_config();
+        config.clean.deadline_without_owner = Some(true);
+        config
+    }
+
+    #[test]
+    fn test_deadline_with_owner_passes() {
+        let scan = owner_scan(Some("alice"), Some("2099-01-01"));
+        let result = run_clean(&scan, &owner_config(), None, None, &HashSet::new()).unwrap();
+        assert!(result.passed);
+    }
+
+    #[test]
+    fn test_deadline_without_owner_flagged() {
+        let scan = owner_scan(None, Some("2099-01-01"));
+        let result = run_clean(&scan, &owner_config(), None, None, &HashSet::new()).unwrap();
+        assert!(!result.passed);
+        assert_eq!(result.deadline_without_owner_count, 1);
+        assert_eq!(result.violations[0].rule, "deadline_without_owner");
+        assert_eq!(result.violations[0].line, 3);
+        assert!(result.violations[0].message.contains("2099-01-01"));
+    }
+
+    #[test]
+    fn test_no_deadline_ignored_by_owner_rule() {
+        let scan = owner_scan(None, None);
+        let result = run_clean(&scan, &owner_config(), None, None, &HashSet::new()).unwrap();
+        assert!(result.passed);
+    }
+
+    #[test]
+    fn test_deadline_without_owner_off_by_default() {
+        let scan = owner_scan(None, Some("2099-01-01"));
+        let result = run_clean(&scan, &default_config(), None, None, &HashSet::new()).unwrap();
         assert!(result.passed);
     }
 
@@ -625,13 +921,24 @@ mod tests {
                 "#42",
             )],
             files_scanned: 1,
+            files_with_items: 1,
+            bytes_scanned: 0,
             ignored_items: vec![],
+            truncated: false,
+            total_found: None,
         };
         let checker =
             MockIssueChecker::new(vec![(42, Some(IssueState::Closed { closed_at: None }))]);
 
         // Since 30 days — but closed_at is None, so it should still be flagged
-        let result = run_clean(&scan, &default_config(), Some(&checker), Some("30d"));
+        let result = run_clean(
+            &scan,
+            &default_config(),
+            Some(&checker),
+            Some("30d"),
+            &HashSet::new(),
+        )
+        .unwrap();
         assert!(!result.passed);
         assert_eq!(result.stale_count, 1);
         assert!(result.violations[0].message.contains("#42"));
@@ -658,7 +965,11 @@ mod tests {
                 "#42",
             )],
             files_scanned: 1,
+            files_with_items: 1,
+            bytes_scanned: 0,
             ignored_items: vec![],
+            truncated: false,
+            total_found: None,
         };
         let checker = MockIssueChecker::new(vec![(
             42,
@@ -670,7 +981,7 @@ mod tests {
         // Set since in config (not CLI), 30 days — closed 5 days ago should NOT be flagged
         let mut config = default_config();
         config.clean.since = Some("30d".to_string());
-        let result = run_clean(&scan, &config, Some(&checker), None);
+        let result = run_clean(&scan, &config, Some(&checker), None, &HashSet::new()).unwrap();
         assert!(result.passed);
         assert_eq!(result.stale_count, 0);
     }
@@ -694,7 +1005,11 @@ mod tests {
                 "#42",
             )],
             files_scanned: 1,
+            files_with_items: 1,
+            bytes_scanned: 0,
             ignored_items: vec![],
+            truncated: false,
+            total_found: None,
         };
         let checker = MockIssueChecker::new(vec![(
             42,
@@ -706,7 +1021,8 @@ mod tests {
         // Config says 90d (would skip), CLI says 30d (should flag)
         let mut config = default_config();
         config.clean.since = Some("90d".to_string());
-        let result = run_clean(&scan, &config, Some(&checker), Some("30d"));
+        let result =
+            run_clean(&scan, &config, Some(&checker), Some("30d"), &HashSet::new()).unwrap();
         assert!(!result.passed);
         assert_eq!(result.stale_count, 1);
     }
@@ -721,12 +1037,23 @@ mod tests {
                 make_item_with_issue("b.rs", 10, Tag::Todo, "second ref to #42", "#42"),
             ],
             files_scanned: 2,
+            files_with_items: 2,
+            bytes_scanned: 0,
             ignored_items: vec![],
+            truncated: false,
+            total_found: None,
         };
         let checker =
             MockIssueChecker::new(vec![(42, Some(IssueState::Closed { closed_at: None }))]);
 
-        let result = run_clean(&scan, &default_config(), Some(&checker), None);
+        let result = run_clean(
+            &scan,
+            &default_config(),
+            Some(&checker),
+            None,
+            &HashSet::new(),
+        )
+        .unwrap();
         assert!(!result.passed);
         // Both items should be flagged as stale
         assert_eq!(result.stale_count, 2);
@@ -753,7 +1080,11 @@ mod tests {
                 "#42",
             )],
             files_scanned: 1,
+            files_with_items: 1,
+            bytes_scanned: 0,
             ignored_items: vec![],
+            truncated: false,
+            total_found: None,
         };
         let checker = MockIssueChecker::new(vec![(
             42,
@@ -763,11 +1094,54 @@ mod tests {
         )]);
 
         // Since 30 days — future closed_at gives 0 age_days, which is < 30
-        let result = run_clean(&scan, &default_config(), Some(&checker), Some("30d"));
+        let result = run_clean(
+            &scan,
+            &default_config(),
+            Some(&checker),
+            Some("30d"),
+            &HashSet::new(),
+        )
+        .unwrap();
         assert!(result.passed);
         assert_eq!(result.stale_count, 0);
     }
 
+    #[test]
+    fn test_duplicate_scope_limits_grouping() {
+        let scan = ScanResult {
+            items: vec![
+                make_item("src/a.rs", 1, Tag::Todo, "implement feature"),
+                make_item("src/b.rs", 5, Tag::Todo, "implement feature"),
+                make_item("lib/c.rs", 2, Tag::Todo, "implement feature"),
+                make_item("lib/c.rs", 9, Tag::Todo, "implement feature"),
+            ],
+            files_scanned: 3,
+            files_with_items: 3,
+            bytes_scanned: 0,
+            ignored_items: vec![],
+            truncated: false,
+            total_found: None,
+        };
+        let count = |scope: DupScope| {
+            let mut config = default_config();
+            config.clean.duplicate_scope = Some(scope);
+            run_clean(&scan, &config, None, None, &HashSet::new())
+                .unwrap()
+                .duplicate_count
+        };
+        assert_eq!(count(DupScope::Repo), 3);
+        assert_eq!(count(DupScope::Dir), 2);
+        let mut config = default_config();
+        config.clean.duplicate_scope = Some(DupScope::File);
+        let result = run_clean(&scan, &config, None, None, &HashSet::new()).unwrap();
+        assert_eq!(result.duplicate_count, 1);
+        assert_eq!(result.violations[0].file, "lib/c.rs");
+        assert_eq!(
+            result.violations[0].duplicate_of.as_deref(),
+            Some("lib/c.rs:2")
+        );
+    }
+
     // --- Three or more duplicates ---
 
     #[test]
@@ -779,9 +1153,13 @@ mod tests {
                 make_item("c.rs", 3, Tag::Todo, "same msg"),
             ],
             files_scanned: 3,
+            files_with_items: 3,
+            bytes_scanned: 0,
             ignored_items: vec![],
+            truncated: false,
+            total_found: None,
         };
-        let result = run_clean(&scan, &default_config(), None, None);
+        let result = run_clean(&scan, &default_config(), None, None, &HashSet::new()).unwrap();
         assert!(!result.passed);
         assert_eq!(result.duplicate_count, 2);
         // The first item is the "original", the other two are duplicates
@@ -847,14 +1225,25 @@ mod tests {
                 make_item_with_issue("a.rs", 5, Tag::Todo, "fix #3", "#3"),
             ],
             files_scanned: 3,
+            files_with_items: 3,
+            bytes_scanned: 0,
             ignored_items: vec![],
+            truncated: false,
+            total_found: None,
         };
         let checker = MockIssueChecker::new(vec![
             (1, Some(IssueState::Closed { closed_at: None })),
             (2, Some(IssueState::Closed { closed_at: None })),
             (3, Some(IssueState::Closed { closed_at: None })),
         ]);
-        let result = run_clean(&scan, &default_config(), Some(&checker), None);
+        let result = run_clean(
+            &scan,
+            &default_config(),
+            Some(&checker),
+            None,
+            &HashSet::new(),
+        )
+        .unwrap();
         assert_eq!(result.violations.len(), 3);
         assert_eq!(result.violations[0].file, "a.rs");
         assert_eq!(result.violations[0].line, 5);
@@ -875,11 +1264,22 @@ mod tests {
                 make_item("c.rs", 3, Tag::Todo, "same message"),
             ],
             files_scanned: 3,
+            files_with_items: 3,
+            bytes_scanned: 0,
             ignored_items: vec![],
+            truncated: false,
+            total_found: None,
         };
         let checker =
             MockIssueChecker::new(vec![(42, Some(IssueState::Closed { closed_at: None }))]);
-        let result = run_clean(&scan, &default_config(), Some(&checker), None);
+        let result = run_clean(
+            &scan,
+            &default_config(),
+            Some(&checker),
+            None,
+            &HashSet::new(),
+        )
+        .unwrap();
         assert!(!result.passed);
         assert_eq!(result.stale_count, 1);
         assert_eq!(result.duplicate_count, 1);
@@ -907,13 +1307,282 @@ mod tests {
                 "#42",
             )],
             files_scanned: 1,
+            files_with_items: 1,
+            bytes_scanned: 0,
             ignored_items: vec![],
+            truncated: false,
+            total_found: None,
         };
         let checker = ErrorIssueChecker;
 
         // When the checker returns Err, the issue should be skipped (not flagged)
-        let result = run_clean(&scan, &default_config(), Some(&checker), None);
+        let result = run_clean(
+            &scan,
+            &default_config(),
+            Some(&checker),
+            None,
+            &HashSet::new(),
+        )
+        .unwrap();
         assert!(result.passed);
         assert_eq!(result.stale_count, 0);
     }
+
+    // --- created= fallback ---
+
+    fn scan_of(items: Vec<TodoItem>) -> ScanResult {
+        ScanResult {
+            files_scanned: items.len(),
+            files_with_items: items.len(),
+            items,
+            bytes_scanned: 0,
+            ignored_items: vec![],
+            truncated: false,
+            total_found: None,
+        }
+    }
+
+    #[test]
+    fn test_extract_created_date() {
+        let d = extract_created_date("refactor parser created=2024-03-05").unwrap();
+        assert_eq!((d.year, d.month, d.day), (2024, 3, 5));
+        assert!(extract_created_date("no metadata here").is_none());
+        assert!(extract_created_date("created=2024-13-01").is_none());
+        assert!(extract_created_date("recreated=2024-03-05").is_none());
+    }
+
+    #[test]
+    fn test_created_fallback_flags_old_item_without_checker() {
+        let scan = scan_of(vec![make_item_with_issue(
+            "a.rs",
+            1,
+            Tag::Todo,
+            "fix bug #42 created=2000-01-01",
+            "#42",
+        )]);
+        let result = run_clean(&scan, &default_config(), None, None, &HashSet::new()).unwrap();
+        assert!(!result.passed);
+        assert_eq!(result.stale_count, 1);
+        assert!(result.violations[0].message.contains("Created 2000-01-01"));
+    }
+
+    #[test]
+    fn test_created_fallback_without_issue_is_stale_age() {
+        let scan = scan_of(vec![make_item(
+            "a.rs",
+            1,
+            Tag::Todo,
+            "tidy up created=2000-01-01",
+        )]);
+        let result = run_clean(&scan, &default_config(), None, None, &HashSet::new()).unwrap();
+        assert!(!result.passed);
+        assert_eq!(result.violations[0].rule, "stale_age");
+        assert_eq!(result.stale_age_count, 1);
+        assert_eq!(result.stale_count, 0);
+
+        let fail_on_stale = CleanGate {
+            check: false,
+            fail_on_stale: true,
+            fail_on_duplicate: false,
+        };
+        assert!(!fail_on_stale.fails(&result));
+    }
+
+    #[test]
+    fn test_grace_exempts_moved_old_item_but_flags_stationary_one() {
+        let moved = make_item(
+            "new/a.rs",
+            1,
+            Tag::Todo,
+            "migrate config created=2000-01-01",
+        );
+        let stationary = make_item("b.rs", 1, Tag::Todo, "drop shim created=2000-01-01");
+        let grace: HashSet<String> = [moved.match_key()].into_iter().collect();
+        let scan = scan_of(vec![moved, stationary]);
+
+        let result = run_clean(&scan, &default_config(), None, None, &grace).unwrap();
+        assert_eq!(result.stale_age_count, 1);
+        assert_eq!(result.violations[0].file, "b.rs");
+    }
+
+    #[test]
+    fn test_grace_does_not_hide_closed_issue() {
+        let item = make_item_with_issue("a.rs", 1, Tag::Todo, "fix bug #42", "#42");
+        let grace: HashSet<String> = [item.match_key()].into_iter().collect();
+        let scan = scan_of(vec![item]);
+        let checker =
+            MockIssueChecker::new(vec![(42, Some(IssueState::Closed { closed_at: None }))]);
+
+        let result = run_clean(&scan, &default_config(), Some(&checker), None, &grace).unwrap();
+        assert_eq!(result.stale_count, 1);
+    }
+
+    #[test]
+    fn test_created_fallback_skips_recent_item() {
+        let today = deadline::today();
+        let scan = scan_of(vec![make_item(
+            "a.rs",
+            1,
+            Tag::Todo,
+            &format!("new work created={}", today),
+        )]);
+        let result = run_clean(&scan, &default_config(), None, None, &HashSet::new()).unwrap();
+        assert!(result.passed);
+        assert_eq!(result.stale_count, 0);
+    }
+
+    #[test]
+    fn test_created_fallback_respects_max_age() {
+        let today_days = {
+            let t = deadline::today();
+            date_utils::ymd_to_days(t.year as i64, t.month as u32, t.day as u32)
+        };
+        let (y, m, d) = date_utils::days_to_ymd(today_days - 40);
+        let message = format!("tidy up created={:04}-{:02}-{:02}", y, m, d);
+        let scan = scan_of(vec![make_item("a.rs", 1, Tag::Todo, &message)]);
+
+        let mut config = default_config();
+        config.clean.max_age = Some("30d".to_string());
+        assert_eq!(
+            run_clean(&scan, &config, None, None, &HashSet::new())
+                .unwrap()
+                .stale_age_count,
+            1
+        );
+
+        config.clean.max_age = Some("60d".to_string());
+        assert_eq!(
+            run_clean(&scan, &config, None, None, &HashSet::new())
+                .unwrap()
+                .stale_age_count,
+            0
+        );
+    }
+
+    #[test]
+    fn test_invalid_max_age_is_an_error() {
+        let scan = scan_of(vec![make_item("a.rs", 1, Tag::Todo, "tidy up")]);
+        let mut config = default_config();
+        config.clean.max_age = Some("soon".to_string());
+
+        let err = run_clean(&scan, &config, None, None, &HashSet::new()).unwrap_err();
+        assert!(err.to_string().contains("invalid duration: soon"));
+    }
+
+    #[test]
+    fn test_created_fallback_when_checker_errors() {
+        let scan = scan_of(vec![make_item_with_issue(
+            "a.rs",
+            1,
+            Tag::Todo,
+            "fix bug #42 created=2000-01-01",
+            "#42",
+        )]);
+        let result = run_clean(
+            &scan,
+            &default_config(),
+            Some(&ErrorIssueChecker),
+            None,
+            &HashSet::new(),
+        )
+        .unwrap();
+        assert_eq!(result.stale_count, 1);
+    }
+
+    #[test]
+    fn test_created_fallback_not_used_when_issue_state_known() {
+        let scan = scan_of(vec![make_item_with_issue(
+            "a.rs",
+            1,
+            Tag::Todo,
+            "fix bug #42 created=2000-01-01",
+            "#42",
+        )]);
+        let checker = MockIssueChecker::new(vec![(42, Some(IssueState::Open))]);
+        let result = run_clean(
+            &scan,
+            &default_config(),
+            Some(&checker),
+            None,
+            &HashSet::new(),
+        )
+        .unwrap();
+        assert!(result.passed);
+    }
+
+    #[test]
+    fn test_created_fallback_disabled_with_stale_issues() {
+        let scan = scan_of(vec![make_item(
+            "a.rs",
+            1,
+            Tag::Todo,
+            "old created=2000-01-01",
+        )]);
+        let mut config = default_config();
+        config.clean.stale_issues = Some(false);
+        assert!(
+            run_clean(&scan, &config, None, None, &HashSet::new())
+                .unwrap()
+                .passed
+        );
+    }
+
+    // --- CleanGate ---
+
+    fn counts(stale_count: usize, duplicate_count: usize) -> CleanResult {
+        CleanResult {
+            passed: stale_count == 0 && duplicate_count == 0,
+            total_items: 0,
+            stale_count,
+            duplicate_count,
+            deadline_without_owner_count: 0,
+            stale_age_count: 0,
+            violations: vec![],
+        }
+    }
+
+    #[test]
+    fn test_gate_fail_flags_each_category_independently() {
+        let gate = |fail_on_stale, fail_on_duplicate| CleanGate {
+            check: false,
+            fail_on_stale,
+            fail_on_duplicate,
+        };
+        // (fail_on_stale, fail_on_duplicate, stale, duplicate) -> fails
+        let cases = [
+            (true, false, 1, 0, true),
+            (true, false, 0, 1, false),
+            (false, true, 1, 0, false),
+            (false, true, 0, 1, true),
+            (true, true, 1, 0, true),
+            (true, true, 0, 1, true),
+            (true, true, 0, 0, false),
+            (false, false, 1, 1, false),
+        ];
+        for (on_stale, on_dup, stale, dup, expected) in cases {
+            assert_eq!(
+                gate(on_stale, on_dup).fails(&counts(stale, dup)),
+                expected,
+                "fail_on_stale={on_stale} fail_on_duplicate={on_dup} stale={stale} dup={dup}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_gate_check_fails_on_any_violation_unless_narrowed() {
+        let check = CleanGate {
+            check: true,
+            ..CleanGate::default()
+        };
+        assert!(check.fails(&counts(0, 1)));
+        assert!(!check.fails(&counts(0, 0)));
+
+        let narrowed = CleanGate {
+            check: true,
+            fail_on_stale: true,
+            fail_on_duplicate: false,
+        };
+        assert!(!narrowed.fails(&counts(0, 1)));
+        assert!(narrowed.fails(&counts(1, 0)));
+    }
 }
@@ -2,7 +2,9 @@ use clap::{Parser, Subcommand, ValueEnum};
 use clap_complete::Shell;
 use std::path::PathBuf;
 
+use crate::config;
 use crate::model;
+use crate::relate;
 
 #[derive(Parser)]
 #[command(
@@ -14,11 +16,17 @@ pub struct Cli {
     #[arg(long, global = true, value_enum, default_value = "text")]
     pub format: Format,
 
-    #[arg(long, global = true)]
+    /// Directory to scan (default: current directory); reported paths are relative to it
+    #[arg(long, global = true, value_name = "DIR")]
     pub root: Option<PathBuf>,
 
+    /// Config file to load; repeat to layer files, later ones overriding earlier ones
     #[arg(long, global = true)]
-    pub config: Option<PathBuf>,
+    pub config: Vec<PathBuf>,
+
+    /// Ignore any discovered .todo-scan.toml and use built-in defaults plus CLI flags
+    #[arg(long, global = true, conflicts_with = "config")]
+    pub no_config: bool,
 
     /// Disable scan result caching
     #[arg(long, global = true)]
@@ -32,6 +40,71 @@ pub struct Cli {
     #[arg(long, global = true)]
     pub show_ignored: bool,
 
+    /// Scan files excluded by .gitignore and other ignore files
+    #[arg(long, global = true)]
+    pub no_ignore: bool,
+
+    /// Scan node_modules, vendor, target and .venv unless the config excludes them itself
+    #[arg(long, global = true)]
+    pub no_default_excludes: bool,
+
+    /// Descend into symlinked directories (each directory is scanned at most once)
+    #[arg(long, global = true)]
+    pub follow_symlinks: bool,
+
+    /// Also report tags in file and directory names (e.g. TODO_rename_this.rs) as items on line 0
+    #[arg(long, global = true)]
+    pub scan_filenames: bool,
+
+    /// Stream-scan files over 10 MiB line by line instead of skipping them
+    #[arg(long, global = true)]
+    pub scan_large: bool,
+
+    /// For files whose extension has no `comment_prefixes` entry: match with the
+    /// generic comment prefixes (true, default), or with the known language's
+    /// prefixes, skipping unknown languages (false)
+    #[arg(long, global = true, value_name = "BOOL")]
+    pub permissive_unknown: Option<bool>,
+
+    /// Stop collecting items after N matches (the rest are still counted) to bound memory;
+    /// not supported by commands that gate or compare (check, lint, clean, diff, watch,
+    /// brief, report, bundle, tasks, workspace)
+    #[arg(long, global = true, value_name = "N")]
+    pub max_items: Option<usize>,
+
+    /// Print phase timings and scan throughput to stderr
+    #[arg(long, global = true)]
+    pub profile: bool,
+
+    /// How to print file paths: relative to --root, absolute, or relative to the git repo root
+    #[arg(long, global = true, value_enum, default_value = "relative")]
+    pub path_style: PathStyle,
+
+    /// Render authors in text and markdown output through this template, e.g.
+    /// "<@{author}>" for chat mentions; unassigned items stay plain
+    #[arg(long, global = true, value_name = "TEMPLATE")]
+    pub author_mention_format: Option<String>,
+
+    /// Deadline format for text, markdown and HTML output, e.g. "%b %-d, %Y" or "%d/%m/%Y"
+    #[arg(long, global = true, value_name = "FORMAT")]
+    pub date_format: Option<String>,
+
+    /// Wrap JSON output in an object under this key, e.g. {"results": ...}
+    #[arg(long, global = true, value_name = "KEY")]
+    pub json_root_key: Option<String>,
+
+    /// Previous SARIF log or id list (one per line); SARIF results get baselineState new/unchanged
+    #[arg(long, global = true, value_name = "FILE")]
+    pub sarif_baseline: Option<PathBuf>,
+
+    /// Print the resolved config and active ignore/exclude rules as JSON, then exit without scanning
+    #[arg(long, global = true)]
+    pub explain_config: bool,
+
+    /// Print the tag regex built from the config (tags, enabled_tags) to stderr, then exit without scanning
+    #[arg(long, global = true)]
+    pub show_pattern: bool,
+
     #[command(subcommand)]
     pub command: Command,
 }
@@ -43,6 +116,13 @@ pub enum DetailLevel {
     Full,
 }
 
+#[derive(Clone, Debug, PartialEq, Eq, ValueEnum)]
+pub enum PathStyle {
+    Relative,
+    Absolute,
+    RepoRoot,
+}
+
 #[derive(Clone, ValueEnum)]
 pub enum Format {
     Text,
@@ -50,12 +130,39 @@ pub enum Format {
     GithubActions,
     Sarif,
     Markdown,
+    /// Markdown with the list table wrapped in a collapsible <details> block
+    MarkdownCollapsible,
+    /// Markdown with diff entries tallied into per-tag added/removed counts
+    MarkdownSummary,
+    /// iCalendar file with one event per TODO deadline (`deadlines` only)
+    Ics,
+    /// CSV with context_before/context_after columns (`list` only)
+    CsvWide,
+    /// JUnit XML with added TODOs as failing test cases (`diff` only)
+    Junit,
+    /// JUnit XML with one testsuite per file (`diff` only)
+    JunitByFile,
+    /// CodeClimate issue JSON, as read by GitLab code quality (`list` only)
+    #[value(name = "codeclimate")]
+    CodeClimate,
+    /// Prometheus text exposition metrics (`stats` only)
+    Prometheus,
+    /// Clang-style `path:line: warning: [TAG] message` lines, surfaced by
+    /// Xcode as inline issues (`list` only)
+    Clang,
+    /// Standalone HTML page with a table of items (`list` only)
+    Html,
 }
 
 #[derive(Subcommand)]
 pub enum Command {
     #[command(alias = "ls")]
     List {
+        /// Directories or files to scan instead of --root; results are merged
+        /// and each item's path is prefixed with its directory
+        #[arg(value_name = "PATH", conflicts_with = "package")]
+        roots: Vec<std::path::PathBuf>,
+
         #[arg(long)]
         tag: Vec<String>,
 
@@ -65,12 +172,22 @@ pub enum Command {
         #[arg(long, value_enum, default_value = "file")]
         group_by: GroupBy,
 
+        /// Order items within each group by file and line, whatever --sort
+        /// says, for byte-identical output across runs
+        #[arg(long)]
+        stable_order: bool,
+
         #[arg(long, value_enum)]
         priority: Vec<PriorityFilter>,
 
         #[arg(long)]
         author: Option<String>,
 
+        /// Only show items carrying this label (from `TODO(labels=a,b)`);
+        /// repeat to match any of several
+        #[arg(long)]
+        label: Vec<String>,
+
         #[arg(long)]
         path: Option<String>,
 
@@ -81,6 +198,33 @@ pub enum Command {
         #[arg(short = 'C', long)]
         context: Option<usize>,
 
+        /// Emit only aggregate counts as JSON ({ total, files, by_tag, by_priority })
+        #[arg(long, conflicts_with_all = ["limit", "context"])]
+        summary: bool,
+
+        /// With a markdown format, emit a `## TAG` section and table per tag, most severe first
+        #[arg(long)]
+        by_tag_section: bool,
+
+        /// With a markdown format, start with shields.io badges for the total,
+        /// urgent and stale (by blame) item counts
+        #[arg(long)]
+        badges: bool,
+
+        /// With csv-wide or a markdown format, output only these columns, in
+        /// this order (e.g. `file,line,tag,message`)
+        #[arg(long, value_enum, value_delimiter = ',')]
+        columns: Vec<Column>,
+
+        /// With --group-by file, show file headers relative to the git
+        /// repository root, whatever --root or --path-style say
+        #[arg(long)]
+        repo_root_headers: bool,
+
+        /// Scan the staged (git index) version of files instead of the working tree
+        #[arg(long, conflicts_with_all = ["roots", "context"])]
+        staged: bool,
+
         /// Scope scan to a single workspace package
         #[arg(long)]
         package: Option<String>,
@@ -92,6 +236,18 @@ pub enum Command {
         #[arg(long)]
         tag: Vec<String>,
 
+        /// Prefix entries with [+]/[-] text markers (colorblind-friendly)
+        #[arg(long)]
+        symbols: bool,
+
+        /// Disable colored output
+        #[arg(long)]
+        no_color: bool,
+
+        /// Fail instead of comparing all files when git cannot list changed files
+        #[arg(long)]
+        no_fallback: bool,
+
         /// Number of context lines to show around each TODO
         #[arg(short = 'C', long)]
         context: Option<usize>,
@@ -103,12 +259,19 @@ pub enum Command {
 
     /// Show code context around a TODO at FILE:LINE
     Context {
-        /// Location in FILE:LINE format
-        location: String,
+        /// Location in FILE:LINE format (with --interactive: a file or
+        /// directory to pick from, or omit to pick from every TODO)
+        #[arg(required_unless_present = "interactive")]
+        location: Option<String>,
 
         /// Number of context lines (default: 5)
         #[arg(short = 'C', long, default_value = "5")]
         context: usize,
+
+        /// Pick one of the matching TODOs from a numbered list (prints all
+        /// of them when stdin or stdout is not a terminal)
+        #[arg(long)]
+        interactive: bool,
     },
 
     /// Generate a .todo-scan.toml configuration file
@@ -125,6 +288,9 @@ pub enum Command {
         shell: Shell,
     },
 
+    /// List recognized tags with their severity and whether the config enables them
+    Tags,
+
     /// Show git blame metadata for TODO comments
     Blame {
         #[arg(long, value_enum, default_value = "file")]
@@ -145,11 +311,43 @@ pub enum Command {
 
         #[arg(long)]
         path: Option<String>,
+
+        /// Replace author emails with "redacted" in every output format
+        #[arg(long)]
+        redact_emails: bool,
+
+        /// Also replace author names with "redacted" (implies --redact-emails)
+        #[arg(long)]
+        redact_authors: bool,
+
+        /// Show the average age in months or years in text and markdown summaries
+        #[arg(long)]
+        humanize_age: bool,
     },
 
     Stats {
         #[arg(long)]
         since: Option<String>,
+
+        /// Leave TODOs in files matching `test_paths` out of the counts
+        #[arg(long)]
+        exclude_tests: bool,
+
+        /// Add a per-directory rollup of TODO counts
+        #[arg(long)]
+        by_dir: bool,
+
+        /// Add a nested directory tree of TODO counts, each node rolled up
+        /// from its children
+        #[arg(long)]
+        tree: bool,
+    },
+
+    /// List TODOs with deadlines, soonest first (`--format ics` for a calendar file)
+    Deadlines {
+        /// Filter by tag (repeatable)
+        #[arg(long)]
+        tag: Vec<String>,
     },
 
     /// Compressed summary of TODO landscape (2-4 lines)
@@ -161,6 +359,10 @@ pub enum Command {
         /// Maximum output lines
         #[arg(long)]
         budget: Option<usize>,
+
+        /// Line kept after the summary when --budget drops one of them
+        #[arg(long, value_enum, default_value = "urgent")]
+        brief_prefer: BriefPrefer,
     },
 
     /// Search TODO comments by message text or issue reference
@@ -173,6 +375,10 @@ pub enum Command {
         #[arg(long)]
         exact: bool,
 
+        /// Match case-insensitively, even with --exact
+        #[arg(short = 'i', long)]
+        ignore_case: bool,
+
         /// Number of context lines to show around each match
         #[arg(short = 'C', long)]
         context: Option<usize>,
@@ -191,6 +397,11 @@ pub enum Command {
 
         #[arg(long, value_enum, default_value = "file")]
         group_by: GroupBy,
+
+        /// Order items within each group by file and line, whatever --sort
+        /// says, for byte-identical output across runs
+        #[arg(long)]
+        stable_order: bool,
     },
 
     Check {
@@ -209,12 +420,33 @@ pub enum Command {
         #[arg(long)]
         expired: bool,
 
+        /// Fail on FIXME/BUG items (or `require_issue_tags`) without an issue reference
+        #[arg(long)]
+        require_issue: bool,
+
+        /// Fail when any TODO is older than this many days (uses git blame)
+        #[arg(long, value_name = "DAYS")]
+        max_age_days: Option<u64>,
+
+        /// Leave TODOs in files matching `test_paths` out of the budget
+        #[arg(long)]
+        exclude_tests: bool,
+
+        /// Fail when any author owns more than N TODOs (explicit author, else git blame)
+        #[arg(long, value_name = "N")]
+        max_per_author: Option<usize>,
+
+        /// With --max-per-author, pool items without an author into an
+        /// "unassigned" bucket instead of exempting them
+        #[arg(long, requires = "max_per_author")]
+        count_unassigned: bool,
+
         /// Scope scan to a single workspace package
         #[arg(long)]
         package: Option<String>,
 
         /// Run check across all workspace packages with per-package thresholds
-        #[arg(long)]
+        #[arg(long, conflicts_with_all = ["max_age_days", "max_per_author"])]
         workspace: bool,
     },
 
@@ -230,6 +462,18 @@ pub enum Command {
         /// Debounce interval in milliseconds
         #[arg(long, default_value = "300")]
         debounce: u64,
+
+        /// Event timestamps: wall-clock time or elapsed since watch started
+        #[arg(long, value_enum, default_value = "absolute")]
+        timestamps: WatchTimestamps,
+
+        /// Exit after the first change event
+        #[arg(long)]
+        once: bool,
+
+        /// With --once, give up after this many seconds without a change (exits 1)
+        #[arg(long, value_name = "SECONDS", requires = "once")]
+        timeout: Option<u64>,
     },
 
     /// Find stale issue references and duplicate TODOs
@@ -238,9 +482,32 @@ pub enum Command {
         #[arg(long)]
         check: bool,
 
+        /// Exit with code 1 if stale TODOs are found; with either --fail-on-*
+        /// flag, only the selected categories fail
+        #[arg(long)]
+        fail_on_stale: bool,
+
+        /// Exit with code 1 if duplicate TODOs are found
+        #[arg(long)]
+        fail_on_duplicate: bool,
+
         /// Only flag issues closed longer than this duration (e.g., "30d")
         #[arg(long)]
         since: Option<String>,
+
+        /// Also flag TODOs that have a deadline but no (author) owner
+        #[arg(long)]
+        deadline_without_owner: bool,
+
+        /// Don't flag TODOs added or moved within this many days as stale,
+        /// even if their `created=` date is old (needs git)
+        #[arg(long, value_name = "DAYS")]
+        stale_grace_days: Option<u64>,
+
+        /// Only flag duplicates within the same file or directory
+        /// (default: anywhere in the repo)
+        #[arg(long, value_enum, value_name = "SCOPE")]
+        dup_scope: Option<config::DupScope>,
     },
 
     /// Generate an HTML technical debt dashboard report
@@ -253,6 +520,31 @@ pub enum Command {
         #[arg(long, default_value = "10")]
         history: usize,
 
+        /// Sample the trend from commits after this ref (e.g., the last
+        /// release tag) instead of the last 500 commits
+        #[arg(long, value_name = "REF")]
+        history_from: Option<String>,
+
+        /// Count each history sample with one `git grep` instead of scanning
+        /// every file; faster on large repos, but approximate
+        #[arg(long)]
+        history_fast: bool,
+
+        /// Days threshold for marking TODOs as stale (default: 365)
+        #[arg(long)]
+        stale_threshold: Option<String>,
+    },
+
+    /// Write list.json, stats.json, blame.json and report.html from one scan
+    Bundle {
+        /// Directory to write the artifacts into (created if missing)
+        #[arg(long, value_name = "DIR")]
+        out: PathBuf,
+
+        /// Number of historical commits to sample for the report's trend chart
+        #[arg(long, default_value = "10")]
+        history: usize,
+
         /// Days threshold for marking TODOs as stale (default: 365)
         #[arg(long)]
         stale_threshold: Option<String>,
@@ -317,6 +609,24 @@ pub enum Command {
         /// Line proximity threshold for same-file detection
         #[arg(long, default_value = "10")]
         proximity: usize,
+
+        /// Split clusters so none has more than N members
+        #[arg(long, value_name = "N", requires = "cluster")]
+        max_cluster_size: Option<usize>,
+
+        /// Return only the K highest-scoring clusters
+        #[arg(long, value_name = "K", requires = "cluster")]
+        max_clusters: Option<usize>,
+
+        /// Explain each relationship in a full sentence instead of the short reason
+        #[arg(long)]
+        explain: bool,
+
+        /// Above this many TODOs, only compare nearby pairs in one file and pairs
+        /// sharing a keyword, issue or author not shared by over 500 TODOs;
+        /// pairs related by tag alone are then skipped
+        #[arg(long, value_name = "N", default_value_t = relate::DEFAULT_MAX_PAIRWISE_ITEMS)]
+        max_pairwise: usize,
     },
 
     /// Lint TODO comment formatting against configurable rules
@@ -344,6 +654,14 @@ pub enum Command {
         /// Enforce colon after tag
         #[arg(long)]
         require_colon: bool,
+
+        /// Apply autofixes in place (requires an author source such as --author-from-git)
+        #[arg(long, requires = "author_from_git")]
+        fix: bool,
+
+        /// Use the git user.name as the author for require_author suggestions and fixes
+        #[arg(long)]
+        author_from_git: bool,
     },
 }
 
@@ -361,6 +679,43 @@ pub enum GroupBy {
     Priority,
     Author,
     Dir,
+    Severity,
+    /// No group headers: one `file:line [TAG] message` line per item, in scan order
+    None,
+}
+
+/// A column of the tabular `list` formats, selected with `--columns`.
+#[derive(Clone, Copy, Debug, PartialEq, ValueEnum)]
+pub enum Column {
+    File,
+    Line,
+    Tag,
+    Priority,
+    Author,
+    #[value(name = "issue_ref")]
+    IssueRef,
+    Deadline,
+    Message,
+    /// Context lines before the TODO (`csv-wide` only)
+    #[value(name = "context_before")]
+    ContextBefore,
+    /// Context lines after the TODO (`csv-wide` only)
+    #[value(name = "context_after")]
+    ContextAfter,
+}
+
+#[derive(Clone, ValueEnum)]
+pub enum WatchTimestamps {
+    /// ISO 8601 UTC time of the event
+    Absolute,
+    /// Time since the watch started, e.g. +00:03:12
+    Relative,
+}
+
+#[derive(Clone, ValueEnum)]
+pub enum BriefPrefer {
+    Urgent,
+    Trend,
 }
 
 #[derive(Clone, ValueEnum)]
@@ -2,13 +2,14 @@ use std::path::Path;
 
 use anyhow::{Context, Result};
 
-use crate::blame::{compute_blame, parse_duration_days};
-use crate::cli::{BlameSortBy, Format};
+use crate::blame::{compute_blame, parse_duration_days, redact, summarize};
+use crate::cli::{BlameSortBy, PathStyle};
 use crate::config::Config;
 use crate::model::Tag;
-use crate::output::print_blame;
+use crate::output::{print_blame, OutputOptions};
 
 use super::do_scan;
+use super::paths::PathRewriter;
 
 pub struct BlameOptions {
     pub sort: BlameSortBy,
@@ -17,16 +18,20 @@ pub struct BlameOptions {
     pub stale_threshold: Option<String>,
     pub tag: Vec<String>,
     pub path: Option<String>,
+    pub redact_emails: bool,
+    pub redact_authors: bool,
+    pub humanize_age: bool,
+    pub path_style: PathStyle,
 }
 
 pub fn cmd_blame(
     root: &Path,
     config: &Config,
-    format: &Format,
+    out: &OutputOptions,
     opts: BlameOptions,
     no_cache: bool,
 ) -> Result<()> {
-    let scan = do_scan(root, config, no_cache)?;
+    let scan = do_scan(root, config, &out.profiler, no_cache)?;
 
     // Resolve stale threshold: CLI > config > default (365d)
     let threshold_str = opts
@@ -35,7 +40,9 @@ pub fn cmd_blame(
         .unwrap_or_else(|| "365d".to_string());
     let stale_threshold = parse_duration_days(&threshold_str)?;
 
-    let mut result = compute_blame(&scan, root, stale_threshold)?;
+    let mut result = out
+        .profiler
+        .time("git", || compute_blame(&scan, root, stale_threshold))?;
 
     // Apply tag filter
     if !opts.tag.is_empty() {
@@ -79,7 +86,7 @@ pub fn cmd_blame(
         }),
         BlameSortBy::Age => result
             .entries
-            .sort_by(|a, b| b.blame.age_days.cmp(&a.blame.age_days)),
+            .sort_by_key(|e| std::cmp::Reverse(e.blame.age_days)),
         BlameSortBy::Author => result
             .entries
             .sort_by(|a, b| a.blame.author.cmp(&b.blame.author)),
@@ -89,14 +96,20 @@ pub fn cmd_blame(
     }
 
     // Recompute summary after filtering
-    result.total = result.entries.len();
-    result.stale_count = result.entries.iter().filter(|e| e.stale).count();
-    result.avg_age_days = if result.total > 0 {
-        result.entries.iter().map(|e| e.blame.age_days).sum::<u64>() / result.total as u64
-    } else {
-        0
-    };
-
-    print_blame(&result, format);
+    summarize(&mut result);
+
+    // Redact last so author filtering and sorting still see real names
+    if opts.redact_emails || opts.redact_authors {
+        redact(&mut result, opts.redact_authors);
+    }
+
+    out.severity
+        .resolve(result.entries.iter_mut().map(|e| &mut e.item), root);
+    let paths = PathRewriter::new(root, &opts.path_style);
+    for entry in &mut result.entries {
+        entry.item.file = paths.rewrite(&entry.item.file);
+    }
+
+    print_blame(&result, out, opts.humanize_age);
     Ok(())
 }
@@ -3,30 +3,34 @@ use std::path::Path;
 use anyhow::Result;
 
 use crate::brief::compute_brief;
-use crate::cli::Format;
+use crate::cli::BriefPrefer;
 use crate::config::Config;
 use crate::diff::compute_diff;
-use crate::output::print_brief;
+use crate::output::{print_brief, OutputOptions};
 
 use super::do_scan;
 
 pub fn cmd_brief(
     root: &Path,
     config: &Config,
-    format: &Format,
+    out: &OutputOptions,
     since: Option<String>,
     budget: Option<usize>,
+    prefer: &BriefPrefer,
     no_cache: bool,
 ) -> Result<()> {
-    let scan = do_scan(root, config, no_cache)?;
+    let scan = do_scan(root, config, &out.profiler, no_cache)?;
 
     let diff = if let Some(ref base_ref) = since {
-        Some(compute_diff(&scan, base_ref, root, config)?)
+        Some(
+            out.profiler
+                .time("git", || compute_diff(&scan, base_ref, root, config, false))?,
+        )
     } else {
         None
     };
 
     let result = compute_brief(&scan, diff.as_ref());
-    print_brief(&result, format, budget);
+    print_brief(&result, out, budget, prefer);
     Ok(())
 }
@@ -0,0 +1,55 @@
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context, Result};
+
+use crate::blame::{compute_blame, parse_duration_days};
+use crate::config::Config;
+use crate::output::{write_bundle, OutputOptions};
+use crate::report::compute_report_with_blame;
+use crate::stats::{compute_stats, tag_priority_floors};
+
+use super::do_scan;
+
+pub struct BundleOptions {
+    pub out_dir: PathBuf,
+    pub history_count: usize,
+    pub stale_threshold: Option<String>,
+}
+
+/// Write list, stats, blame and report artifacts from a single scan (and a
+/// single blame pass, shared by `blame.json` and the report).
+pub fn cmd_bundle(
+    root: &Path,
+    config: &Config,
+    out: &OutputOptions,
+    opts: BundleOptions,
+    no_cache: bool,
+) -> Result<()> {
+    let scan = do_scan(root, config, &out.profiler, no_cache)?;
+
+    let threshold_str = opts
+        .stale_threshold
+        .or_else(|| config.blame.stale_threshold.clone())
+        .unwrap_or_else(|| "365d".to_string());
+    let stale_threshold = parse_duration_days(&threshold_str)?;
+
+    let stats = compute_stats(&scan, None, &tag_priority_floors(config)?, false);
+    let blame = out
+        .profiler
+        .time("git", || compute_blame(&scan, root, stale_threshold))?;
+    let report = compute_report_with_blame(
+        &scan,
+        root,
+        config,
+        opts.history_count,
+        None,
+        false,
+        Some(&blame),
+    )?;
+
+    std::fs::create_dir_all(&opts.out_dir)
+        .with_context(|| format!("Failed to create {}", opts.out_dir.display()))?;
+    write_bundle(&opts.out_dir, &scan, &stats, &blame, &report, out)
+        .with_context(|| format!("Failed to write bundle to {}", opts.out_dir.display()))?;
+    Ok(())
+}
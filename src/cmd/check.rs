@@ -1,43 +1,115 @@
 use std::path::Path;
-use std::process;
 
 use anyhow::Result;
 
-use crate::check::{run_check, CheckOverrides};
-use crate::cli::Format;
+use crate::blame::compute_blame;
+use crate::check::{check_author_budget, check_max_age, run_check, CheckOverrides};
 use crate::config::Config;
 use crate::deadline;
 use crate::diff::compute_diff;
+use crate::git::git_command;
 use crate::model;
-use crate::output::print_check;
+use crate::output::{print_check, OutputOptions};
 use crate::workspace;
 
-use super::do_scan;
+use super::filter::TestPathFilter;
+use super::{do_scan, exit_with};
+
+pub struct CheckOptions {
+    pub overrides: CheckOverrides,
+    pub since: Option<String>,
+    pub max_age_days: Option<u64>,
+    pub exclude_tests: bool,
+    pub max_per_author: Option<usize>,
+    pub count_unassigned: bool,
+}
 
 pub fn cmd_check(
     root: &Path,
     config: &Config,
-    format: &Format,
-    overrides: CheckOverrides,
-    since: Option<String>,
+    out: &OutputOptions,
+    opts: CheckOptions,
     no_cache: bool,
 ) -> Result<()> {
-    let scan = do_scan(root, config, no_cache)?;
-
-    let diff = if let Some(ref base_ref) = since {
-        Some(compute_diff(&scan, base_ref, root, config)?)
+    let CheckOptions {
+        overrides,
+        since,
+        max_age_days,
+        exclude_tests,
+        max_per_author,
+        count_unassigned,
+    } = opts;
+    let tests = exclude_tests
+        .then(|| TestPathFilter::from_config(config))
+        .transpose()?;
+    let mut scan = do_scan(root, config, &out.profiler, no_cache)?;
+
+    let mut diff = if let Some(ref base_ref) = since {
+        Some(
+            out.profiler
+                .time("git", || compute_diff(&scan, base_ref, root, config, false))?,
+        )
     } else {
         None
     };
 
+    if let Some(tests) = &tests {
+        tests.apply(&mut scan.items);
+        if let Some(diff) = &mut diff {
+            tests.apply_diff(diff);
+        }
+    }
+
     let today = deadline::today();
-    let result = run_check(&scan, diff.as_ref(), config, &overrides, &today);
+    let mut result = run_check(&scan, diff.as_ref(), config, &overrides, &today);
+
+    // Ages and implicit authors come from blame, which needs a git work tree
+    let blame = if max_age_days.is_some() || max_per_author.is_some() {
+        if git_command(&["rev-parse", "--is-inside-work-tree"], root).is_ok() {
+            Some(out.profiler.time("git", || {
+                compute_blame(&scan, root, max_age_days.unwrap_or(u64::MAX))
+            })?)
+        } else {
+            None
+        }
+    } else {
+        None
+    };
+
+    if let Some(max_age) = max_age_days {
+        match &blame {
+            Some(blame) => result.violations.extend(check_max_age(blame, max_age)),
+            None => eprintln!("note: not a git repository, skipping --max-age-days"),
+        }
+    }
+
+    if let Some(max) = max_per_author {
+        // An explicit `TODO(author)` wins; otherwise fall back to the blamed
+        // committer when git could attribute the line
+        let authors: Vec<Option<&str>> = match &blame {
+            Some(blame) => blame
+                .entries
+                .iter()
+                .map(|e| {
+                    e.item
+                        .author
+                        .as_deref()
+                        .or((!e.blame.is_unknown()).then_some(e.blame.author.as_str()))
+                })
+                .collect(),
+            None => scan.items.iter().map(|i| i.author.as_deref()).collect(),
+        };
+        result
+            .violations
+            .extend(check_author_budget(authors, max, count_unassigned));
+    }
+    result.passed = result.violations.is_empty();
     let passed = result.passed;
 
-    print_check(&result, format);
+    print_check(&result, out);
 
     if !passed {
-        process::exit(1);
+        exit_with(out, 1);
     }
 
     Ok(())
@@ -46,7 +118,7 @@ pub fn cmd_check(
 pub fn cmd_workspace_check(
     root: &Path,
     config: &Config,
-    format: &Format,
+    out: &OutputOptions,
     no_cache: bool,
 ) -> Result<()> {
     let ws = workspace::detect_workspace(root, config)?
@@ -57,7 +129,7 @@ pub fn cmd_workspace_check(
 
     for pkg in &ws.packages {
         let pkg_root = root.join(&pkg.path);
-        let scan = do_scan(&pkg_root, config, no_cache)?;
+        let scan = do_scan(&pkg_root, config, &out.profiler, no_cache)?;
         let todo_count = scan.items.len();
 
         let pkg_config = config.workspace.packages.get(&pkg.name);
@@ -103,10 +175,10 @@ pub fn cmd_workspace_check(
         violations,
     };
 
-    print_check(&result, format);
+    print_check(&result, out);
 
     if !all_passed {
-        process::exit(1);
+        exit_with(out, 1);
     }
 
     Ok(())
@@ -1,43 +1,96 @@
+use std::collections::HashSet;
 use std::path::Path;
-use std::process;
 
 use anyhow::Result;
 
 use crate::clean;
-use crate::cli::Format;
 use crate::config::Config;
-use crate::output::print_clean;
+use crate::diff::compute_diff;
+use crate::git::git_command;
+use crate::model::{DiffStatus, ScanResult};
+use crate::output::{print_clean, OutputOptions};
 
-use super::do_scan;
+use super::{do_scan, exit_with};
+
+/// Match keys of items added (or moved to another file) since the last commit
+/// older than `days`. When that commit can't be resolved (no HEAD yet, a
+/// history younger than `days`, or a shallow clone that cuts it off), the grace
+/// set is empty so `created=` dates still apply.
+fn grace_keys(
+    scan: &ScanResult,
+    root: &Path,
+    config: &Config,
+    days: u64,
+) -> Result<HashSet<String>> {
+    let before = format!("--before={} days ago", days);
+    let base = match git_command(&["rev-list", "-1", &before, "HEAD"], root) {
+        Ok(out) => out.trim().to_string(),
+        Err(_) => String::new(),
+    };
+    if base.is_empty() {
+        eprintln!(
+            "warning: no commit older than {} days, skipping --stale-grace-days",
+            days
+        );
+        return Ok(HashSet::new());
+    }
+
+    let diff = match compute_diff(scan, &base, root, config, false) {
+        Ok(diff) => diff,
+        Err(e) => {
+            eprintln!(
+                "warning: cannot read {}, skipping --stale-grace-days: {:#}",
+                base, e
+            );
+            return Ok(HashSet::new());
+        }
+    };
+    Ok(diff
+        .entries
+        .iter()
+        .filter(|e| matches!(e.status, DiffStatus::Added))
+        .map(|e| e.item.match_key())
+        .collect())
+}
 
 pub fn cmd_clean(
     root: &Path,
     config: &Config,
-    format: &Format,
-    check_mode: bool,
+    out: &OutputOptions,
+    gate: clean::CleanGate,
     since: Option<String>,
     no_cache: bool,
 ) -> Result<()> {
-    let scan = do_scan(root, config, no_cache)?;
+    let scan = do_scan(root, config, &out.profiler, no_cache)?;
 
     // Try to create GhIssueChecker; warn if gh is unavailable
     let gh_checker = clean::GhIssueChecker::new();
     if gh_checker.is_none() && config.clean.stale_issues.unwrap_or(true) {
-        eprintln!("warning: gh CLI not found, skipping stale issue detection");
+        eprintln!("warning: gh CLI not found, using created= dates for stale detection");
     }
 
+    let grace = match config.clean.stale_grace_days {
+        Some(days) if git_command(&["rev-parse", "--is-inside-work-tree"], root).is_ok() => out
+            .profiler
+            .time("git", || grace_keys(&scan, root, config, days))?,
+        Some(_) => {
+            eprintln!("note: not a git repository, skipping --stale-grace-days");
+            HashSet::new()
+        }
+        None => HashSet::new(),
+    };
+
     let result = clean::run_clean(
         &scan,
         config,
         gh_checker.as_ref().map(|c| c as &dyn clean::IssueChecker),
         since.as_deref(),
-    );
-    let has_violations = !result.passed;
-
-    print_clean(&result, format);
+        &grace,
+    )?;
+    print_clean(&result, out);
 
-    if check_mode && has_violations {
-        process::exit(1);
+    if gate.fails(&result) {
+        exit_with(out, 1);
     }
 
     Ok(())
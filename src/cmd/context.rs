@@ -1,31 +1,123 @@
+use std::io::{self, BufRead, IsTerminal, Write};
 use std::path::Path;
 
 use anyhow::Result;
 
-use crate::cli::Format;
 use crate::config::Config;
-use crate::context::{build_rich_context, resolve_location};
-use crate::model;
-use crate::output::print_context;
+use crate::context::{build_rich_context, parse_selection, resolve_location};
+use crate::model::{self, TodoItem};
+use crate::output::{print_context, sanitize_for_terminal, OutputOptions};
 
 use super::do_scan;
 
+/// Items an interactive `context` can pick from: the exact item when the
+/// location resolves to one, otherwise everything under a file or directory.
+fn candidates<'a>(location: Option<&str>, items: &'a [TodoItem]) -> Vec<&'a TodoItem> {
+    let Some(location) = location else {
+        return items.iter().collect();
+    };
+    if let Ok((file, line)) = resolve_location(location, items) {
+        let exact: Vec<&TodoItem> = items
+            .iter()
+            .filter(|i| i.location() == file && i.line == line)
+            .collect();
+        if !exact.is_empty() {
+            return exact;
+        }
+    }
+    let prefix = location.trim_end_matches('/');
+    items
+        .iter()
+        .filter(|i| {
+            i.file == prefix
+                || i.file
+                    .strip_prefix(prefix)
+                    .is_some_and(|rest| rest.starts_with('/'))
+        })
+        .collect()
+}
+
+/// Show a numbered list and read choices from stdin until one is valid.
+/// Returns `None` when the user quits or stdin closes.
+fn pick(items: &[&TodoItem]) -> Result<Option<usize>> {
+    for (i, item) in items.iter().enumerate() {
+        println!(
+            "{:>3}. {}:{} [{}] {}",
+            i + 1,
+            sanitize_for_terminal(&item.location()),
+            item.line,
+            item.tag,
+            sanitize_for_terminal(&item.message)
+        );
+    }
+
+    let stdin = io::stdin();
+    let mut input = String::new();
+    loop {
+        print!("Select 1-{} (q to quit): ", items.len());
+        io::stdout().flush()?;
+        input.clear();
+        if stdin.lock().read_line(&mut input)? == 0 || input.trim() == "q" {
+            return Ok(None);
+        }
+        match parse_selection(&input, items.len()) {
+            Ok(idx) => return Ok(Some(idx)),
+            Err(e) => eprintln!("{}", e),
+        }
+    }
+}
+
+fn print_item_context(
+    root: &Path,
+    items: &[TodoItem],
+    file: &str,
+    line: usize,
+    n: usize,
+    out: &OutputOptions,
+) -> Result<()> {
+    let todos_in_file: Vec<&model::TodoItem> =
+        items.iter().filter(|i| i.location() == file).collect();
+
+    let rich = build_rich_context(root, file, line, n, &todos_in_file)?;
+    print_context(&rich, out);
+    Ok(())
+}
+
 pub fn cmd_context(
     root: &Path,
     config: &Config,
-    format: &Format,
-    location: &str,
+    out: &OutputOptions,
+    location: Option<&str>,
     n: usize,
+    interactive: bool,
     no_cache: bool,
 ) -> Result<()> {
     // Scan first so we have items available for ID-based resolution
-    let scan = do_scan(root, config, no_cache)?;
-    let (file, line) = resolve_location(location, &scan.items)?;
+    let scan = do_scan(root, config, &out.profiler, no_cache)?;
 
-    let todos_in_file: Vec<&model::TodoItem> =
-        scan.items.iter().filter(|i| i.file == file).collect();
+    if !interactive {
+        let location = location.unwrap_or_default();
+        let (file, line) = resolve_location(location, &scan.items)?;
+        return print_item_context(root, &scan.items, &file, line, n, out);
+    }
+
+    let matches = candidates(location, &scan.items);
+    if matches.is_empty() {
+        anyhow::bail!("no TODOs match '{}'", location.unwrap_or("."));
+    }
+
+    let tty = io::stdin().is_terminal() && io::stdout().is_terminal();
+    if matches.len() == 1 || !tty {
+        for item in matches {
+            print_item_context(root, &scan.items, &item.location(), item.line, n, out)?;
+        }
+        return Ok(());
+    }
 
-    let rich = build_rich_context(root, &file, line, n, &todos_in_file)?;
-    print_context(&rich, format);
+    if let Some(idx) = pick(&matches)? {
+        let item = matches[idx];
+        println!();
+        print_item_context(root, &scan.items, &item.location(), item.line, n, out)?;
+    }
     Ok(())
 }
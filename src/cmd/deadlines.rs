@@ -0,0 +1,48 @@
+use std::path::Path;
+
+use anyhow::Result;
+
+use crate::cli::PathStyle;
+use crate::config::Config;
+use crate::output::{print_deadlines, OutputOptions};
+
+use super::do_scan;
+use super::filter::{apply_filters, FilterOptions};
+use super::paths::PathRewriter;
+
+pub fn cmd_deadlines(
+    root: &Path,
+    config: &Config,
+    out: &OutputOptions,
+    tag: Vec<String>,
+    path_style: &PathStyle,
+    no_cache: bool,
+) -> Result<()> {
+    let mut items = do_scan(root, config, &out.profiler, no_cache)?.items;
+    items.retain(|item| item.deadline.is_some());
+
+    apply_filters(
+        &mut items,
+        &FilterOptions {
+            tags: tag,
+            author: None,
+            path: None,
+            priority: vec![],
+            labels: vec![],
+        },
+    )?;
+
+    // Soonest deadline first
+    items.sort_by(|a, b| {
+        let key = |d: &crate::deadline::Deadline| (d.year, d.month, d.day);
+        a.deadline
+            .map(|d| key(&d))
+            .cmp(&b.deadline.map(|d| key(&d)))
+            .then(a.file.cmp(&b.file))
+            .then(a.line.cmp(&b.line))
+    });
+
+    PathRewriter::new(root, path_style).apply(&mut items);
+    print_deadlines(&items, out);
+    Ok(())
+}
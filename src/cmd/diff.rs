@@ -3,31 +3,38 @@ use std::path::Path;
 
 use anyhow::Result;
 
-use crate::cli::{DetailLevel, Format};
+use crate::cli::{DetailLevel, PathStyle};
 use crate::config::Config;
-use crate::context::collect_context_map;
+use crate::context::{collect_context_map, collect_ref_context_map};
 use crate::diff::compute_diff;
 use crate::model::{DiffStatus, Tag};
-use crate::output::print_diff;
+use crate::output::{print_diff, OutputOptions};
 
 use super::do_scan;
+use super::paths::PathRewriter;
 
 pub struct DiffOptions {
     pub git_ref: String,
     pub tag: Vec<String>,
     pub context: Option<usize>,
     pub detail: DetailLevel,
+    pub symbols: bool,
+    pub no_color: bool,
+    pub no_fallback: bool,
+    pub path_style: PathStyle,
 }
 
 pub fn cmd_diff(
     root: &Path,
     config: &Config,
-    format: &Format,
+    out: &OutputOptions,
     opts: DiffOptions,
     no_cache: bool,
 ) -> Result<()> {
-    let current = do_scan(root, config, no_cache)?;
-    let mut diff_result = compute_diff(&current, &opts.git_ref, root, config)?;
+    let current = do_scan(root, config, &out.profiler, no_cache)?;
+    let mut diff_result = out.profiler.time("git", || {
+        compute_diff(&current, &opts.git_ref, root, config, opts.no_fallback)
+    })?;
 
     // Apply tag filter
     if !opts.tag.is_empty() {
@@ -51,15 +58,47 @@ pub fn cmd_diff(
             .count();
     }
 
-    let items: Vec<_> = diff_result.entries.iter().map(|e| e.item.clone()).collect();
-    let context_map = if let Some(n) = opts.context {
-        collect_context_map(root, &items, n)
-    } else if opts.detail == DetailLevel::Full {
-        collect_context_map(root, &items, 3)
-    } else {
-        HashMap::new()
+    // Removed items no longer exist in the working tree, so their context
+    // comes from the base ref.
+    let lines = opts
+        .context
+        .or((opts.detail == DetailLevel::Full).then_some(3));
+    let (context_map, base_context_map) = match lines {
+        Some(n) => {
+            let (added, removed): (Vec<_>, Vec<_>) = diff_result
+                .entries
+                .iter()
+                .partition(|e| matches!(e.status, DiffStatus::Added));
+            let added: Vec<_> = added.into_iter().map(|e| e.item.clone()).collect();
+            let removed: Vec<_> = removed.into_iter().map(|e| e.item.clone()).collect();
+            (
+                collect_context_map(root, &added, n),
+                collect_ref_context_map(root, &opts.git_ref, &removed, n),
+            )
+        }
+        None => (HashMap::new(), HashMap::new()),
     };
 
-    print_diff(&diff_result, format, &context_map, &opts.detail);
+    out.severity
+        .resolve(diff_result.entries.iter_mut().map(|e| &mut e.item), root);
+    let paths = PathRewriter::new(root, &opts.path_style);
+    for entry in &mut diff_result.entries {
+        entry.item.file = paths.rewrite(&entry.item.file);
+    }
+    let context_map = paths.apply_context(context_map);
+    let base_context_map = paths.apply_context(base_context_map);
+
+    if opts.no_color {
+        colored::control::set_override(false);
+    }
+
+    print_diff(
+        &diff_result,
+        out,
+        &context_map,
+        &base_context_map,
+        &opts.detail,
+        opts.symbols,
+    );
     Ok(())
 }
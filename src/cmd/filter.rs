@@ -1,13 +1,17 @@
 use anyhow::{Context, Result};
+use globset::{GlobBuilder, GlobSet, GlobSetBuilder};
 
 use crate::cli::PriorityFilter;
-use crate::model::{self, Tag, TodoItem};
+use crate::config::Config;
+use crate::model::{self, DiffResult, DiffStatus, Tag, TodoItem};
 
 pub struct FilterOptions {
     pub tags: Vec<String>,
     pub author: Option<String>,
     pub path: Option<String>,
     pub priority: Vec<PriorityFilter>,
+    /// Keep items carrying any of these labels.
+    pub labels: Vec<String>,
 }
 
 pub fn apply_filters(items: &mut Vec<TodoItem>, filters: &FilterOptions) -> Result<()> {
@@ -33,6 +37,11 @@ pub fn apply_filters(items: &mut Vec<TodoItem>, filters: &FilterOptions) -> Resu
         items.retain(|item| item.author.as_deref() == Some(author.as_str()));
     }
 
+    // Apply label filter
+    if !filters.labels.is_empty() {
+        items.retain(|item| item.labels.iter().any(|l| filters.labels.contains(l)));
+    }
+
     // Apply path filter
     if let Some(ref pattern) = filters.path {
         let glob = globset::Glob::new(pattern)
@@ -44,6 +53,49 @@ pub fn apply_filters(items: &mut Vec<TodoItem>, filters: &FilterOptions) -> Resu
     Ok(())
 }
 
+/// Compiled `test_paths` globs, used by `--exclude-tests` to keep TODOs in
+/// test code out of `check` and `stats` budgets.
+pub struct TestPathFilter {
+    globs: GlobSet,
+}
+
+impl TestPathFilter {
+    pub fn from_config(config: &Config) -> Result<Self> {
+        anyhow::ensure!(
+            !config.test_paths.is_empty(),
+            "--exclude-tests requires test_paths in the config"
+        );
+        let mut builder = GlobSetBuilder::new();
+        for pattern in &config.test_paths {
+            let glob = GlobBuilder::new(pattern)
+                .literal_separator(true)
+                .build()
+                .with_context(|| format!("Invalid test_paths glob: {}", pattern))?;
+            builder.add(glob);
+        }
+        Ok(Self {
+            globs: builder.build()?,
+        })
+    }
+
+    /// Drop items in test files.
+    pub fn apply(&self, items: &mut Vec<TodoItem>) {
+        items.retain(|item| !self.globs.is_match(&item.file));
+    }
+
+    /// Drop diff entries in test files and recount added/removed.
+    pub fn apply_diff(&self, diff: &mut DiffResult) {
+        diff.entries
+            .retain(|entry| !self.globs.is_match(&entry.item.file));
+        diff.added_count = diff
+            .entries
+            .iter()
+            .filter(|e| matches!(e.status, DiffStatus::Added))
+            .count();
+        diff.removed_count = diff.entries.len() - diff.added_count;
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -74,6 +126,7 @@ mod tests {
             author: None,
             path: None,
             priority: vec![],
+            labels: vec![],
         };
         apply_filters(&mut items, &filters).unwrap();
         assert_eq!(items.len(), 1);
@@ -92,6 +145,7 @@ mod tests {
             author: None,
             path: None,
             priority: vec![],
+            labels: vec![],
         };
         apply_filters(&mut items, &filters).unwrap();
         assert_eq!(items.len(), 2);
@@ -111,6 +165,7 @@ mod tests {
             author: None,
             path: None,
             priority: vec![PriorityFilter::High],
+            labels: vec![],
         };
         apply_filters(&mut items, &filters).unwrap();
         assert_eq!(items.len(), 1);
@@ -129,12 +184,39 @@ mod tests {
             author: Some("alice".to_string()),
             path: None,
             priority: vec![],
+            labels: vec![],
         };
         apply_filters(&mut items, &filters).unwrap();
         assert_eq!(items.len(), 1);
         assert_eq!(items[0].author.as_deref(), Some("alice"));
     }
 
+    #[test]
+    fn filter_by_label_matches_multi_label_items() {
+        let mut a = make_filter_item("a.rs", Tag::Todo, Priority::Normal, None);
+        a.labels = vec!["frontend".to_string(), "perf".to_string()];
+        let mut b = make_filter_item("b.rs", Tag::Todo, Priority::Normal, None);
+        b.labels = vec!["backend".to_string()];
+        let c = make_filter_item("c.rs", Tag::Todo, Priority::Normal, None);
+        let filters = |labels: &[&str]| FilterOptions {
+            tags: vec![],
+            author: None,
+            path: None,
+            priority: vec![],
+            labels: labels.iter().map(|l| l.to_string()).collect(),
+        };
+
+        let mut items = vec![a.clone(), b.clone(), c.clone()];
+        apply_filters(&mut items, &filters(&["perf"])).unwrap();
+        assert_eq!(items.len(), 1);
+        assert_eq!(items[0].file, "a.rs");
+
+        // Repeated labels match any of them
+        let mut items = vec![a, b, c];
+        apply_filters(&mut items, &filters(&["frontend", "backend"])).unwrap();
+        assert_eq!(items.len(), 2);
+    }
+
     #[test]
     fn filter_by_path() {
         let mut items = vec![
@@ -147,6 +229,7 @@ mod tests {
             author: None,
             path: Some("src/*.rs".to_string()),
             priority: vec![],
+            labels: vec![],
         };
         apply_filters(&mut items, &filters).unwrap();
         assert_eq!(items.len(), 2);
@@ -166,6 +249,7 @@ mod tests {
             author: Some("alice".to_string()),
             path: Some("src/**".to_string()),
             priority: vec![PriorityFilter::High],
+            labels: vec![],
         };
         apply_filters(&mut items, &filters).unwrap();
         assert_eq!(items.len(), 1);
@@ -183,6 +267,7 @@ mod tests {
             author: None,
             path: None,
             priority: vec![],
+            labels: vec![],
         };
         apply_filters(&mut items, &filters).unwrap();
         assert_eq!(items.len(), 2);
@@ -200,6 +285,7 @@ mod tests {
             author: None,
             path: None,
             priority: vec![PriorityFilter::Normal],
+            labels: vec![],
         };
         apply_filters(&mut items, &filters).unwrap();
         assert_eq!(items.len(), 1);
@@ -214,6 +300,7 @@ mod tests {
             author: None,
             path: Some("[invalid".to_string()),
             priority: vec![],
+            labels: vec![],
         };
         assert!(apply_filters(&mut items, &filters).is_err());
     }
@@ -230,6 +317,7 @@ mod tests {
             author: None,
             path: None,
             priority: vec![PriorityFilter::High, PriorityFilter::Urgent],
+            labels: vec![],
         };
         apply_filters(&mut items, &filters).unwrap();
         assert_eq!(items.len(), 2);
@@ -247,6 +335,7 @@ mod tests {
             author: None,
             path: Some("tests/**".to_string()),
             priority: vec![],
+            labels: vec![],
         };
         apply_filters(&mut items, &filters).unwrap();
         assert!(items.is_empty());
@@ -263,6 +352,7 @@ mod tests {
             author: None,
             path: None,
             priority: vec![],
+            labels: vec![],
         };
         apply_filters(&mut items, &filters).unwrap();
         assert!(items.is_empty());
@@ -279,6 +369,7 @@ mod tests {
             author: Some("charlie".to_string()),
             path: None,
             priority: vec![],
+            labels: vec![],
         };
         apply_filters(&mut items, &filters).unwrap();
         assert!(items.is_empty());
@@ -296,6 +387,7 @@ mod tests {
             author: None,
             path: None,
             priority: vec![],
+            labels: vec![],
         };
         apply_filters(&mut items, &filters).unwrap();
         assert_eq!(items.len(), 3);
@@ -317,8 +409,56 @@ mod tests {
             author: Some("alice".to_string()),
             path: Some("src/**".to_string()),
             priority: vec![PriorityFilter::Urgent],
+            labels: vec![],
         };
         apply_filters(&mut items, &filters).unwrap();
         assert_eq!(items.len(), 1);
     }
+
+    #[test]
+    fn test_path_filter_drops_matching_items_and_diff_entries() {
+        let config = Config {
+            test_paths: vec!["tests/**".to_string(), "**/*_test.go".to_string()],
+            ..Config::default()
+        };
+        let filter = TestPathFilter::from_config(&config).unwrap();
+
+        let mut items = vec![
+            make_item("src/lib.rs", 1, Tag::Todo, "keep"),
+            make_item("tests/it.rs", 1, Tag::Todo, "drop"),
+            make_item("pkg/db_test.go", 1, Tag::Todo, "drop"),
+        ];
+        filter.apply(&mut items);
+        assert_eq!(items.len(), 1);
+        assert_eq!(items[0].file, "src/lib.rs");
+
+        let mut diff = DiffResult {
+            entries: vec![
+                model::DiffEntry {
+                    status: DiffStatus::Added,
+                    item: make_item("src/lib.rs", 2, Tag::Todo, "new"),
+                },
+                model::DiffEntry {
+                    status: DiffStatus::Added,
+                    item: make_item("tests/it.rs", 2, Tag::Todo, "new test"),
+                },
+                model::DiffEntry {
+                    status: DiffStatus::Removed,
+                    item: make_item("tests/it.rs", 3, Tag::Todo, "old test"),
+                },
+            ],
+            added_count: 2,
+            removed_count: 1,
+            base_ref: "main".to_string(),
+        };
+        filter.apply_diff(&mut diff);
+        assert_eq!(diff.entries.len(), 1);
+        assert_eq!(diff.added_count, 1);
+        assert_eq!(diff.removed_count, 0);
+    }
+
+    #[test]
+    fn test_path_filter_requires_test_paths() {
+        assert!(TestPathFilter::from_config(&Config::default()).is_err());
+    }
 }
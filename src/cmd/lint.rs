@@ -1,31 +1,63 @@
 use std::path::Path;
-use std::process;
 
-use anyhow::Result;
+use anyhow::{Context, Result};
 
-use crate::cli::Format;
 use crate::config::Config;
-use crate::lint::{run_lint, LintOverrides};
-use crate::output::print_lint;
+use crate::git::git_command;
+use crate::lint::{exit_code, fix_missing_author, run_lint, suggest_author, LintOverrides};
+use crate::output::{print_lint, OutputOptions};
 
-use super::do_scan;
+use super::{do_scan, exit_with};
+
+pub struct LintFixOptions {
+    pub fix: bool,
+    pub author_from_git: bool,
+}
 
 pub fn cmd_lint(
     root: &Path,
     config: &Config,
-    format: &Format,
+    out: &OutputOptions,
     overrides: LintOverrides,
+    fix_opts: LintFixOptions,
     no_cache: bool,
 ) -> Result<()> {
-    let scan = do_scan(root, config, no_cache)?;
-    let result = run_lint(&scan, config, &overrides, root);
-    let passed = result.passed;
+    let scan = do_scan(root, config, &out.profiler, no_cache)?;
+    let mut result = run_lint(&scan, config, &overrides, root);
+
+    if fix_opts.author_from_git {
+        let author = git_user_name(root)?;
+
+        if fix_opts.fix {
+            let fixed = fix_missing_author(&result, config, root, &author)?;
+            if fixed > 0 {
+                eprintln!("Fixed {} require_author violation(s)", fixed);
+                let scan = do_scan(root, config, &out.profiler, no_cache)?;
+                result = run_lint(&scan, config, &overrides, root);
+            }
+        }
+
+        suggest_author(&mut result, &author);
+    }
+
+    let code = exit_code(&result, config);
 
-    print_lint(&result, format);
+    print_lint(&result, out);
 
-    if !passed {
-        process::exit(1);
+    if code != 0 {
+        exit_with(out, code);
     }
 
     Ok(())
 }
+
+/// Read `user.name` from the git config visible at `root`.
+fn git_user_name(root: &Path) -> Result<String> {
+    let name = git_command(&["config", "user.name"], root)
+        .context("cannot determine author: git user.name is not configured")?;
+    let name = name.trim();
+    if name.is_empty() {
+        anyhow::bail!("cannot determine author: git user.name is empty");
+    }
+    Ok(name.to_string())
+}
@@ -1,37 +1,88 @@
 use std::collections::HashMap;
-use std::path::Path;
+use std::path::{Path, PathBuf};
 
 use anyhow::Result;
 
-use crate::cli::{DetailLevel, Format, GroupBy, PriorityFilter, SortBy};
+use crate::cli::{Column, DetailLevel, Format, GroupBy, PathStyle, PriorityFilter, SortBy};
 use crate::config::Config;
 use crate::context::collect_context_map;
-use crate::output::print_list;
+use crate::model::{BadgeCounts, ScanResult};
+use crate::output::{print_list, print_list_summary, OutputOptions};
+use crate::profile::Profiler;
+use crate::scanner::scan_staged;
+use crate::stats::{summarize, tag_priority_floors};
 
-use super::do_scan;
 use super::filter::{apply_filters, FilterOptions};
+use super::paths::PathRewriter;
+use super::{do_scan, do_scan_roots, stale_count};
 
 pub struct ListOptions {
+    pub roots: Vec<PathBuf>,
     pub tag: Vec<String>,
     pub sort: SortBy,
     pub group_by: GroupBy,
+    pub stable_order: bool,
     pub priority: Vec<PriorityFilter>,
     pub author: Option<String>,
+    pub labels: Vec<String>,
     pub path: Option<String>,
     pub limit: Option<usize>,
     pub context: Option<usize>,
+    pub summary: bool,
+    pub by_tag_section: bool,
+    pub badges: bool,
+    pub columns: Vec<Column>,
+    pub repo_root_headers: bool,
+    pub staged: bool,
     pub show_ignored: bool,
     pub detail: DetailLevel,
+    pub path_style: PathStyle,
 }
 
 pub fn cmd_list(
     root: &Path,
     config: &Config,
-    format: &Format,
+    out: &OutputOptions,
     opts: ListOptions,
     no_cache: bool,
 ) -> Result<()> {
-    let mut result = do_scan(root, config, no_cache)?;
+    let mut result = if opts.staged {
+        scan_staged(root, config)?
+    } else if opts.roots.is_empty() {
+        do_scan(root, config, &out.profiler, no_cache)?
+    } else {
+        do_scan_roots(root, &opts.roots, config, &out.profiler, no_cache)?
+    };
+
+    let markdown = matches!(
+        out.format,
+        Format::Markdown | Format::MarkdownCollapsible | Format::MarkdownSummary
+    );
+    anyhow::ensure!(
+        !opts.by_tag_section || markdown,
+        "--by-tag-section requires a markdown format"
+    );
+    anyhow::ensure!(
+        !opts.badges || markdown,
+        "--badges requires a markdown format"
+    );
+    anyhow::ensure!(
+        opts.columns.is_empty() || markdown || matches!(out.format, Format::CsvWide),
+        "--columns requires the csv-wide or a markdown format"
+    );
+    anyhow::ensure!(
+        !markdown
+            || !opts
+                .columns
+                .iter()
+                .any(|c| matches!(c, Column::ContextBefore | Column::ContextAfter)),
+        "context_before and context_after columns require the csv-wide format"
+    );
+    anyhow::ensure!(
+        !opts.repo_root_headers
+            || (matches!(out.format, Format::Text) && matches!(opts.group_by, GroupBy::File)),
+        "--repo-root-headers requires text output grouped by file"
+    );
 
     let ignored_count = result.ignored_items.len();
 
@@ -42,9 +93,15 @@ pub fn cmd_list(
             author: opts.author,
             path: opts.path,
             priority: opts.priority,
+            labels: opts.labels,
         },
     )?;
 
+    if opts.summary {
+        print_list_summary(&summarize(&result, &tag_priority_floors(config)?), out);
+        return Ok(());
+    }
+
     // Apply sort
     match opts.sort {
         SortBy::File => result
@@ -66,10 +123,19 @@ pub fn cmd_list(
         }),
     }
 
-    // Apply limit
+    // Badge counts cover every matching item, not just those kept by --limit
+    let badges = if opts.badges {
+        Some(badge_counts(&result, root, config, &out.profiler)?)
+    } else {
+        None
+    };
+
+    // Apply limit, remembering how many items were cut for the "... and N more" note
+    let total = result.items.len();
     if let Some(n) = opts.limit {
         result.items.truncate(n);
     }
+    let omitted = total - result.items.len();
 
     let context_map = if let Some(n) = opts.context {
         collect_context_map(root, &result.items, n)
@@ -79,14 +145,55 @@ pub fn cmd_list(
         HashMap::new()
     };
 
+    out.severity.resolve(
+        result.items.iter_mut().chain(&mut result.ignored_items),
+        root,
+    );
+    let paths = PathRewriter::new(root, &opts.path_style);
+    // Headers are keyed by the printed path but derived from the
+    // scan-relative one
+    let mut file_headers: HashMap<String, String> = HashMap::new();
+    if opts.repo_root_headers {
+        let repo_root = PathRewriter::new(root, &PathStyle::RepoRoot);
+        for item in result.items.iter().chain(&result.ignored_items) {
+            file_headers.insert(paths.rewrite(&item.file), repo_root.rewrite(&item.file));
+        }
+    }
+    paths.apply(&mut result.items);
+    paths.apply(&mut result.ignored_items);
+    let context_map = paths.apply_context(context_map);
+
     print_list(
         &result,
-        format,
+        out,
         &opts.group_by,
         &context_map,
         ignored_count,
         opts.show_ignored,
+        omitted,
         &opts.detail,
+        opts.by_tag_section,
+        opts.stable_order,
+        badges.as_ref(),
+        &opts.columns,
+        &file_headers,
     );
     Ok(())
 }
+
+/// Total and urgent counts (with `stats.tag_priority` floors applied) plus
+/// the number of items older than `blame.stale_threshold`, when `root` is a
+/// git work tree.
+fn badge_counts(
+    result: &ScanResult,
+    root: &Path,
+    config: &Config,
+    profiler: &Profiler,
+) -> Result<BadgeCounts> {
+    let summary = summarize(result, &tag_priority_floors(config)?);
+    Ok(BadgeCounts {
+        total: summary.total,
+        urgent: summary.by_priority.urgent,
+        stale: stale_count(result, root, config, profiler)?,
+    })
+}
@@ -1,45 +1,143 @@
 mod blame;
 mod brief;
+mod bundle;
 mod check;
 mod clean;
 mod context;
+mod deadlines;
 mod diff;
 mod filter;
 mod lint;
 mod list;
+mod paths;
 mod relate;
 mod report;
 mod search;
 mod stats;
+mod tags;
 mod tasks;
 mod workspace;
 
 pub use self::blame::{cmd_blame, BlameOptions};
 pub use self::brief::cmd_brief;
-pub use self::check::{cmd_check, cmd_workspace_check};
+pub use self::bundle::{cmd_bundle, BundleOptions};
+pub use self::check::{cmd_check, cmd_workspace_check, CheckOptions};
 pub use self::clean::cmd_clean;
 pub use self::context::cmd_context;
+pub use self::deadlines::cmd_deadlines;
 pub use self::diff::{cmd_diff, DiffOptions};
-pub use self::lint::cmd_lint;
+pub use self::lint::{cmd_lint, LintFixOptions};
 pub use self::list::{cmd_list, ListOptions};
 pub use self::relate::{cmd_relate, RelateOptions};
-pub use self::report::cmd_report;
+pub use self::report::{cmd_report, ReportOptions};
 pub use self::search::{cmd_search, SearchOptions};
-pub use self::stats::cmd_stats;
+pub use self::stats::{cmd_stats, StatsOptions};
+pub use self::tags::cmd_tags;
 pub use self::tasks::{cmd_tasks, TasksOptions};
 pub use self::workspace::cmd_workspace_list;
 
 use anyhow::Result;
 use std::path::{Path, PathBuf};
 
+use crate::blame::{compute_blame, parse_duration_days};
 use crate::cache;
 use crate::config::Config;
+use crate::git::git_command;
 use crate::model;
+use crate::output::OutputOptions;
+use crate::profile::Profiler;
 use crate::scanner;
 use crate::workspace as ws;
 
+/// Exit with `code`, printing the `--profile` report first since `main` never
+/// gets to it.
+pub(crate) fn exit_with(out: &OutputOptions, code: i32) -> ! {
+    out.profiler.report();
+    std::process::exit(code)
+}
+
 /// Perform a directory scan, optionally using cache for performance.
-pub(crate) fn do_scan(root: &Path, config: &Config, no_cache: bool) -> Result<model::ScanResult> {
+pub(crate) fn do_scan(
+    root: &Path,
+    config: &Config,
+    profiler: &Profiler,
+    no_cache: bool,
+) -> Result<model::ScanResult> {
+    let result = profiler.time("scan", || scan_root(root, config, no_cache))?;
+    profiler.record_scan(result.files_scanned, result.bytes_scanned);
+    if let Some(total) = result.total_found {
+        eprintln!(
+            "warning: stopped collecting at {} of {} items (--max-items)",
+            result.items.len(),
+            total
+        );
+    }
+    Ok(result)
+}
+
+/// Scan several roots and merge them into one result. Each root is resolved
+/// against `base`, and item paths are prefixed with the root as given so
+/// they stay relative to `base`. A root that is a file is scanned on its own
+/// and its items carry the path as given.
+pub(crate) fn do_scan_roots(
+    base: &Path,
+    roots: &[PathBuf],
+    config: &Config,
+    profiler: &Profiler,
+    no_cache: bool,
+) -> Result<model::ScanResult> {
+    let mut merged = model::ScanResult {
+        items: Vec::new(),
+        ignored_items: Vec::new(),
+        files_scanned: 0,
+        files_with_items: 0,
+        bytes_scanned: 0,
+        truncated: false,
+        total_found: None,
+    };
+    let mut total_found = 0;
+    for root in roots {
+        let path = base.join(root);
+        let mut result = if path.is_file() {
+            scanner::scan_file(&path, &root.to_string_lossy(), config)?
+        } else {
+            anyhow::ensure!(path.is_dir(), "not a directory: {}", root.display());
+            let mut result = do_scan(&path, config, profiler, no_cache)?;
+            for item in result.items.iter_mut().chain(&mut result.ignored_items) {
+                item.file = root.join(&item.file).to_string_lossy().into_owned();
+            }
+            result
+        };
+        total_found += result.total_found.unwrap_or(result.items.len());
+        merged.items.append(&mut result.items);
+        merged.ignored_items.append(&mut result.ignored_items);
+        merged.files_scanned += result.files_scanned;
+        merged.files_with_items += result.files_with_items;
+        merged.bytes_scanned += result.bytes_scanned;
+        merged.truncated |= result.truncated;
+    }
+    merged.total_found = merged.truncated.then_some(total_found);
+    Ok(merged)
+}
+
+/// Number of items older than `blame.stale_threshold`, or `None` when `root`
+/// is not inside a git work tree.
+pub(crate) fn stale_count(
+    scan: &model::ScanResult,
+    root: &Path,
+    config: &Config,
+    profiler: &Profiler,
+) -> Result<Option<usize>> {
+    if git_command(&["rev-parse", "--is-inside-work-tree"], root).is_err() {
+        return Ok(None);
+    }
+    let threshold = config.blame.stale_threshold.as_deref().unwrap_or("365d");
+    let threshold = parse_duration_days(threshold)?;
+    let blame = profiler.time("git", || compute_blame(scan, root, threshold))?;
+    Ok(Some(blame.stale_count))
+}
+
+fn scan_root(root: &Path, config: &Config, no_cache: bool) -> Result<model::ScanResult> {
     if no_cache {
         return scanner::scan_directory(root, config);
     }
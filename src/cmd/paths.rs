@@ -0,0 +1,142 @@
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+
+use crate::cli::PathStyle;
+use crate::context::ContextInfo;
+use crate::git::git_command;
+use crate::model::TodoItem;
+
+/// Rewrites scan-relative file paths into the style chosen by `--path-style`.
+///
+/// Applied just before printing: anything that reads files (context, blame)
+/// must still see the scan-relative paths.
+pub struct PathRewriter {
+    /// Prefix joined onto each path; `None` leaves paths untouched.
+    base: Option<PathBuf>,
+}
+
+impl PathRewriter {
+    pub fn new(root: &Path, style: &PathStyle) -> Self {
+        let root = root.canonicalize().unwrap_or_else(|_| root.to_path_buf());
+        let base = match style {
+            PathStyle::Relative => None,
+            PathStyle::Absolute => Some(root),
+            // Outside a git repo there is no repo root; keep paths relative
+            PathStyle::RepoRoot => repo_prefix(&root),
+        };
+        Self { base }
+    }
+
+    pub fn rewrite(&self, file: &str) -> String {
+        match &self.base {
+            Some(base) => base.join(file).to_string_lossy().to_string(),
+            None => file.to_string(),
+        }
+    }
+
+    pub fn apply(&self, items: &mut [TodoItem]) {
+        if self.base.is_none() {
+            return;
+        }
+        for item in items {
+            item.file = self.rewrite(&item.file);
+        }
+    }
+
+    /// Re-key a context map (keyed by `file:line`) to match rewritten items.
+    pub fn apply_context(
+        &self,
+        context_map: HashMap<String, ContextInfo>,
+    ) -> HashMap<String, ContextInfo> {
+        if self.base.is_none() {
+            return context_map;
+        }
+        context_map
+            .into_iter()
+            .map(|(key, ctx)| match key.rsplit_once(':') {
+                Some((file, line)) => (format!("{}:{}", self.rewrite(file), line), ctx),
+                None => (key, ctx),
+            })
+            .collect()
+    }
+}
+
+/// Path of `root` relative to the enclosing git repository's top level.
+fn repo_prefix(root: &Path) -> Option<PathBuf> {
+    let toplevel = git_command(&["rev-parse", "--show-toplevel"], root).ok()?;
+    let toplevel = PathBuf::from(toplevel.trim());
+    let toplevel = toplevel.canonicalize().unwrap_or(toplevel);
+    root.strip_prefix(&toplevel).ok().map(Path::to_path_buf)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::model::Tag;
+    use crate::test_helpers::helpers::make_item;
+    use std::process::Command;
+    use tempfile::TempDir;
+
+    fn git_init(dir: &Path) {
+        Command::new("git")
+            .args(["init", "-q"])
+            .current_dir(dir)
+            .output()
+            .unwrap();
+    }
+
+    #[test]
+    fn relative_keeps_paths() {
+        let dir = TempDir::new().unwrap();
+        let rewriter = PathRewriter::new(dir.path(), &PathStyle::Relative);
+        assert_eq!(rewriter.rewrite("src/a.rs"), "src/a.rs");
+    }
+
+    #[test]
+    fn absolute_joins_canonical_root() {
+        let dir = TempDir::new().unwrap();
+        let rewriter = PathRewriter::new(dir.path(), &PathStyle::Absolute);
+        let expected = dir.path().canonicalize().unwrap().join("src/a.rs");
+        assert_eq!(rewriter.rewrite("src/a.rs"), expected.to_string_lossy());
+    }
+
+    #[test]
+    fn repo_root_prefixes_subdirectory() {
+        let dir = TempDir::new().unwrap();
+        git_init(dir.path());
+        let sub = dir.path().join("crates/core");
+        std::fs::create_dir_all(&sub).unwrap();
+
+        let rewriter = PathRewriter::new(&sub, &PathStyle::RepoRoot);
+        assert_eq!(rewriter.rewrite("src/a.rs"), "crates/core/src/a.rs");
+
+        let at_top = PathRewriter::new(dir.path(), &PathStyle::RepoRoot);
+        assert_eq!(at_top.rewrite("src/a.rs"), "src/a.rs");
+    }
+
+    #[test]
+    fn repo_root_outside_git_falls_back_to_relative() {
+        let dir = TempDir::new().unwrap();
+        let rewriter = PathRewriter::new(dir.path(), &PathStyle::RepoRoot);
+        assert_eq!(rewriter.rewrite("src/a.rs"), "src/a.rs");
+    }
+
+    #[test]
+    fn apply_rewrites_items_and_context_keys() {
+        let dir = TempDir::new().unwrap();
+        let rewriter = PathRewriter::new(dir.path(), &PathStyle::Absolute);
+        let mut items = vec![make_item("a.rs", 3, Tag::Todo, "x")];
+        rewriter.apply(&mut items);
+
+        let mut context_map = HashMap::new();
+        context_map.insert(
+            "a.rs:3".to_string(),
+            ContextInfo {
+                before: vec![],
+                after: vec![],
+            },
+        );
+        let context_map = rewriter.apply_context(context_map);
+        assert!(context_map.contains_key(&format!("{}:3", items[0].file)));
+    }
+}
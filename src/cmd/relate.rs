@@ -2,10 +2,9 @@ use std::path::Path;
 
 use anyhow::Result;
 
-use crate::cli::Format;
 use crate::config::Config;
 use crate::context::parse_location;
-use crate::output::print_relate;
+use crate::output::{print_relate, OutputOptions};
 use crate::relate;
 
 use super::do_scan;
@@ -15,17 +14,26 @@ pub struct RelateOptions {
     pub for_item: Option<String>,
     pub min_score: f64,
     pub proximity: usize,
+    pub max_cluster_size: Option<usize>,
+    pub max_clusters: Option<usize>,
+    pub explain: bool,
+    pub max_pairwise: usize,
 }
 
 pub fn cmd_relate(
     root: &Path,
     config: &Config,
-    format: &Format,
+    out: &OutputOptions,
     opts: RelateOptions,
     no_cache: bool,
 ) -> Result<()> {
-    let scan = do_scan(root, config, no_cache)?;
-    let mut result = relate::compute_relations(&scan, opts.min_score, opts.proximity);
+    if opts.max_cluster_size.is_some_and(|n| n < 2) {
+        anyhow::bail!("--max-cluster-size must be at least 2");
+    }
+
+    let scan = do_scan(root, config, &out.profiler, no_cache)?;
+    let mut result =
+        relate::compute_relations(&scan, opts.min_score, opts.proximity, opts.max_pairwise);
 
     if let Some(ref location) = opts.for_item {
         let (file, line) = parse_location(location)?;
@@ -33,10 +41,14 @@ pub fn cmd_relate(
     }
 
     if opts.cluster {
-        let clusters = relate::build_clusters(&result.relationships, &scan.items);
+        let mut clusters =
+            relate::build_clusters(&result.relationships, &scan.items, opts.max_cluster_size);
+        if let Some(max) = opts.max_clusters {
+            clusters = relate::limit_clusters(clusters, max);
+        }
         result.clusters = Some(clusters);
     }
 
-    print_relate(&result, format);
+    print_relate(&result, out, opts.explain);
     Ok(())
 }
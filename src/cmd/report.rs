@@ -4,27 +4,49 @@ use anyhow::Result;
 
 use crate::blame;
 use crate::config::Config;
-use crate::output::print_report;
+use crate::output::{print_report, OutputOptions};
 use crate::report;
 
 use super::do_scan;
 
+pub struct ReportOptions {
+    pub output_path: String,
+    pub history_count: usize,
+    pub history_from: Option<String>,
+    pub history_fast: bool,
+    pub stale_threshold: Option<String>,
+}
+
 pub fn cmd_report(
     root: &Path,
     config: &Config,
-    output_path: &str,
-    history_count: usize,
-    stale_threshold_cli: Option<String>,
+    out: &OutputOptions,
+    opts: ReportOptions,
     no_cache: bool,
 ) -> Result<()> {
-    let scan = do_scan(root, config, no_cache)?;
+    let ReportOptions {
+        output_path,
+        history_count,
+        history_from,
+        history_fast,
+        stale_threshold: stale_threshold_cli,
+    } = opts;
+    let scan = do_scan(root, config, &out.profiler, no_cache)?;
 
     let threshold_str = stale_threshold_cli
         .or_else(|| config.blame.stale_threshold.clone())
         .unwrap_or_else(|| "365d".to_string());
     let stale_threshold = blame::parse_duration_days(&threshold_str)?;
 
-    let result = report::compute_report(&scan, root, config, history_count, stale_threshold)?;
-    print_report(&result, output_path)?;
+    let result = report::compute_report(
+        &scan,
+        root,
+        config,
+        history_count,
+        history_from.as_deref(),
+        history_fast,
+        stale_threshold,
+    )?;
+    print_report(&result, &output_path, out)?;
     Ok(())
 }
@@ -3,36 +3,40 @@ use std::path::Path;
 
 use anyhow::Result;
 
-use crate::cli::{DetailLevel, Format, GroupBy, SortBy};
+use crate::cli::{DetailLevel, GroupBy, PathStyle, SortBy};
 use crate::config::Config;
 use crate::context::collect_context_map;
-use crate::output::print_search;
+use crate::output::{print_search, OutputOptions};
 use crate::search::search_items;
 
 use super::do_scan;
 use super::filter::{apply_filters, FilterOptions};
+use super::paths::PathRewriter;
 
 pub struct SearchOptions {
     pub query: String,
     pub exact: bool,
+    pub ignore_case: bool,
     pub context: Option<usize>,
     pub author: Option<String>,
     pub tag: Vec<String>,
     pub path: Option<String>,
     pub sort: SortBy,
     pub group_by: GroupBy,
+    pub stable_order: bool,
     pub detail: DetailLevel,
+    pub path_style: PathStyle,
 }
 
 pub fn cmd_search(
     root: &Path,
     config: &Config,
-    format: &Format,
+    out: &OutputOptions,
     opts: SearchOptions,
     no_cache: bool,
 ) -> Result<()> {
-    let scan = do_scan(root, config, no_cache)?;
-    let mut result = search_items(&scan, &opts.query, opts.exact);
+    let scan = do_scan(root, config, &out.profiler, no_cache)?;
+    let mut result = search_items(&scan, &opts.query, opts.exact, opts.ignore_case);
 
     apply_filters(
         &mut result.items,
@@ -41,6 +45,7 @@ pub fn cmd_search(
             author: opts.author,
             path: opts.path,
             priority: vec![],
+            labels: vec![],
         },
     )?;
 
@@ -82,6 +87,18 @@ pub fn cmd_search(
         HashMap::new()
     };
 
-    print_search(&result, format, &opts.group_by, &context_map, &opts.detail);
+    out.severity.resolve(&mut result.items, root);
+    let paths = PathRewriter::new(root, &opts.path_style);
+    paths.apply(&mut result.items);
+    let context_map = paths.apply_context(context_map);
+
+    print_search(
+        &result,
+        out,
+        &opts.group_by,
+        &context_map,
+        &opts.detail,
+        opts.stable_order,
+    );
     Ok(())
 }
@@ -5,27 +5,57 @@ use anyhow::Result;
 use crate::cli::Format;
 use crate::config::Config;
 use crate::diff::compute_diff;
-use crate::output::print_stats;
-use crate::stats::compute_stats;
+use crate::output::{print_stats, print_stats_prometheus, OutputOptions};
+use crate::stats::{build_dir_tree, compute_stats, tag_priority_floors};
 
-use super::do_scan;
+use super::filter::TestPathFilter;
+use super::{do_scan, stale_count};
+
+pub struct StatsOptions {
+    pub since: Option<String>,
+    pub exclude_tests: bool,
+    pub by_dir: bool,
+    pub tree: bool,
+}
 
 pub fn cmd_stats(
     root: &Path,
     config: &Config,
-    format: &Format,
-    since: Option<String>,
+    out: &OutputOptions,
+    opts: StatsOptions,
     no_cache: bool,
 ) -> Result<()> {
-    let scan = do_scan(root, config, no_cache)?;
+    let tag_priority = tag_priority_floors(config)?;
+    let tests = opts
+        .exclude_tests
+        .then(|| TestPathFilter::from_config(config))
+        .transpose()?;
+    let mut scan = do_scan(root, config, &out.profiler, no_cache)?;
 
-    let diff = if let Some(ref base_ref) = since {
-        Some(compute_diff(&scan, base_ref, root, config)?)
+    let mut diff = if let Some(ref base_ref) = opts.since {
+        Some(
+            out.profiler
+                .time("git", || compute_diff(&scan, base_ref, root, config, false))?,
+        )
     } else {
         None
     };
 
-    let result = compute_stats(&scan, diff.as_ref());
-    print_stats(&result, format);
+    if let Some(tests) = &tests {
+        tests.apply(&mut scan.items);
+        if let Some(diff) = &mut diff {
+            tests.apply_diff(diff);
+        }
+    }
+
+    let mut result = compute_stats(&scan, diff.as_ref(), &tag_priority, opts.by_dir);
+    if opts.tree {
+        result.tree = Some(build_dir_tree(&scan.items));
+    }
+    if matches!(out.format, Format::Prometheus) {
+        print_stats_prometheus(&result, stale_count(&scan, root, config, &out.profiler)?);
+    } else {
+        print_stats(&result, out);
+    }
     Ok(())
 }
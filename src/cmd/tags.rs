@@ -0,0 +1,10 @@
+use anyhow::Result;
+
+use crate::config::Config;
+use crate::output::{print_tags, OutputOptions};
+use crate::tags::compute_tags;
+
+pub fn cmd_tags(config: &Config, out: &OutputOptions) -> Result<()> {
+    print_tags(&compute_tags(config), out);
+    Ok(())
+}
@@ -2,12 +2,12 @@ use std::path::Path;
 
 use anyhow::{Context, Result};
 
-use crate::cli::{Format, PriorityFilter};
+use crate::cli::PriorityFilter;
 use crate::config::Config;
 use crate::context::collect_context_map;
 use crate::diff::compute_diff;
 use crate::model;
-use crate::output::print_tasks;
+use crate::output::{print_tasks, write_file, OutputOptions};
 use crate::tasks;
 
 use super::do_scan;
@@ -27,15 +27,17 @@ pub struct TasksOptions {
 pub fn cmd_tasks(
     root: &Path,
     config: &Config,
-    format: &Format,
+    out: &OutputOptions,
     opts: TasksOptions,
     no_cache: bool,
 ) -> Result<()> {
-    let scan = do_scan(root, config, no_cache)?;
+    let scan = do_scan(root, config, &out.profiler, no_cache)?;
 
     let mut items = if let Some(ref base_ref) = opts.since {
         // Only TODOs added since the git ref
-        let diff = compute_diff(&scan, base_ref, root, config)?;
+        let diff = out
+            .profiler
+            .time("git", || compute_diff(&scan, base_ref, root, config, false))?;
         diff.entries
             .into_iter()
             .filter(|e| matches!(e.status, model::DiffStatus::Added))
@@ -52,6 +54,7 @@ pub fn cmd_tasks(
             author: opts.author,
             path: opts.path,
             priority: opts.priority,
+            labels: vec![],
         },
     )?;
 
@@ -76,7 +79,7 @@ pub fn cmd_tasks(
                 let path = dir.join(&filename);
                 let json =
                     serde_json::to_string_pretty(task).context("failed to serialize task")?;
-                std::fs::write(&path, json)
+                write_file(&path, &json, out)
                     .with_context(|| format!("cannot write task file: {}", path.display()))?;
             }
 
@@ -85,7 +88,7 @@ pub fn cmd_tasks(
                 total,
                 output_dir: Some(dir.to_string_lossy().to_string()),
             };
-            print_tasks(&result, format);
+            print_tasks(&result, out);
         }
         _ => {
             let result = model::TasksResult {
@@ -93,7 +96,7 @@ pub fn cmd_tasks(
                 total,
                 output_dir: None,
             };
-            print_tasks(&result, format);
+            print_tasks(&result, out);
         }
     }
 
@@ -2,10 +2,9 @@ use std::path::Path;
 
 use anyhow::Result;
 
-use crate::cli::Format;
 use crate::config::Config;
 use crate::model;
-use crate::output::print_workspace_list;
+use crate::output::{print_workspace_list, OutputOptions};
 use crate::workspace;
 
 use super::do_scan;
@@ -13,7 +12,7 @@ use super::do_scan;
 pub fn cmd_workspace_list(
     root: &Path,
     config: &Config,
-    format: &Format,
+    out: &OutputOptions,
     no_cache: bool,
 ) -> Result<()> {
     let ws = workspace::detect_workspace(root, config)?
@@ -24,7 +23,7 @@ pub fn cmd_workspace_list(
 
     for pkg in &ws.packages {
         let pkg_root = root.join(&pkg.path);
-        let scan = do_scan(&pkg_root, config, no_cache)?;
+        let scan = do_scan(&pkg_root, config, &out.profiler, no_cache)?;
         let todo_count = scan.items.len();
         total_todos += todo_count;
 
@@ -51,6 +50,6 @@ pub fn cmd_workspace_list(
         total_todos,
     };
 
-    print_workspace_list(&result, format, &ws.kind);
+    print_workspace_list(&result, out, &ws.kind);
     Ok(())
 }
@@ -1,19 +1,81 @@
 use anyhow::{Context, Result};
 use schemars::JsonSchema;
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 use std::path::{Path, PathBuf};
 
+use crate::model::{Priority, Severity};
+
+/// Dependency and build output directories skipped unless a config file sets
+/// its own `exclude_dirs` or `--no-default-excludes` is given.
+pub const DEFAULT_EXCLUDE_DIRS: &[&str] = &["node_modules", "vendor", "target", ".venv"];
+
 /// Configuration for todo-scan TODO tracking tool
-#[derive(Debug, Deserialize, JsonSchema)]
+#[derive(Debug, Deserialize, Serialize, JsonSchema)]
 #[serde(default)]
 #[schemars(deny_unknown_fields, title = "todo-scan Configuration")]
 pub struct Config {
     /// Tags to scan for (e.g., TODO, FIXME, HACK)
     pub tags: Vec<String>,
-    /// Directory names to skip during scanning
+    /// Subset of `tags` to match; other tags are never reported (default: all tags)
+    pub enabled_tags: Option<Vec<String>>,
+    /// Match tags in any case (`todo:`, `Fixme:`), reporting them under the
+    /// canonical tag; false matches only the spelling in `tags` (default: true)
+    pub case_insensitive_tags: Option<bool>,
+    /// Directory names to skip during scanning; setting this replaces the
+    /// built-in list (node_modules, vendor, target, .venv)
     pub exclude_dirs: Vec<String>,
     /// Regex patterns; matching file paths are excluded
     pub exclude_patterns: Vec<String>,
+    /// Globs for test code; `check --exclude-tests` and `stats --exclude-tests`
+    /// leave TODOs in matching files out of their counts
+    pub test_paths: Vec<String>,
+    /// Maximum indented continuation comment lines joined into a TODO message (default: 0, disabled)
+    pub continuation_lines: Option<usize>,
+    /// Skip files matched by .gitignore, .git/info/exclude, the global gitignore and .ignore files (default: true)
+    pub respect_gitignore: Option<bool>,
+    /// Descend into symlinked directories, skipping any directory already visited (default: false)
+    pub follow_symlinks: Option<bool>,
+    /// Also report tags in file and directory names (e.g., TODO_rename_this.rs) as items on line 0 (default: false)
+    pub scan_filenames: Option<bool>,
+    /// Stream-scan files over 10 MiB line by line instead of skipping them (default: false)
+    pub scan_large: Option<bool>,
+    /// Strip a trailing block comment terminator (`*/`, `-->`, `*)`, `-}`) from messages (default: true)
+    pub strip_comment_terminators: Option<bool>,
+    /// Collapse runs of spaces and tabs inside messages to a single space; changes the ids of affected items (default: false)
+    pub collapse_whitespace: Option<bool>,
+    /// Keep tags found inside string literals as ignored items (reason "string-literal", shown with --show-ignored) instead of dropping them (default: true)
+    pub string_literals_as_ignored: Option<bool>,
+    /// Skip individual lines longer than this many bytes, such as minified code (default: 10000, 0 disables)
+    pub max_line_length: Option<usize>,
+    /// Markers that flag a finished TODO, such as "[done]" in `TODO[done]: x`;
+    /// matching items become ignored items with reason "resolved" (default: none)
+    pub resolved_markers: Vec<String>,
+    /// Comment prefixes per file extension (e.g., md = ["<!--"]); tags in files
+    /// with that extension only match after one of these prefixes (default: built-in list)
+    pub comment_prefixes: HashMap<String, Vec<String>>,
+    /// For files whose extension has no `comment_prefixes` entry: match against the
+    /// generic built-in prefixes (true), or use the known language's own prefixes
+    /// and skip files of unknown languages (false) (default: true)
+    pub permissive_unknown: Option<bool>,
+    /// Stop collecting items once this many are found; the scan still counts the rest.
+    /// Ignored by commands that gate or compare (check, lint, clean, diff, watch,
+    /// brief, report, bundle, tasks, workspace), which need every item (default: no limit)
+    pub max_items: Option<usize>,
+    /// Raise the severity of TODOs in files matching a glob (SARIF and GitHub Actions output)
+    pub severity_overrides: Vec<SeverityOverride>,
+    /// Display labels for tags in text, markdown and HTML output (e.g., BUG = "🐛 BUG").
+    /// JSON, SARIF and GitHub Actions output keep the canonical tag names.
+    pub tag_labels: HashMap<String, String>,
+    /// Emoji put before the `[TAG]` of GitHub Actions annotation messages, per tag
+    /// (e.g., BUG = "🐛"). Tags without an entry get no prefix.
+    pub github_actions_emoji: HashMap<String, String>,
+    /// strftime-style deadline format for text, markdown and HTML output, e.g. "%b %-d, %Y"
+    /// (default: %Y-%m-%d). JSON, SARIF and iCalendar output keep ISO dates.
+    pub date_format: Option<String>,
+    /// End files written by `report --output` and `tasks --output` with exactly
+    /// one newline (true) or none (false); unset leaves each format as is
+    pub trailing_newline: Option<bool>,
     /// CI gate check settings
     pub check: CheckConfig,
     /// Git blame analysis settings
@@ -22,12 +84,24 @@ pub struct Config {
     pub lint: LintConfig,
     /// Clean detection settings
     pub clean: CleanConfig,
+    /// Stats computation settings
+    pub stats: StatsConfig,
     /// Workspace/monorepo settings
     pub workspace: WorkspaceConfig,
 }
 
+/// Minimum severity for TODOs in files matching `path`
+#[derive(Debug, Clone, Deserialize, Serialize, JsonSchema)]
+#[schemars(deny_unknown_fields)]
+pub struct SeverityOverride {
+    /// Glob matched against paths relative to the project root (e.g., "src/crypto/**")
+    pub path: String,
+    /// Severity applied to matching TODOs: error, warning, or notice
+    pub level: Severity,
+}
+
 /// CI gate check settings
-#[derive(Debug, Default, Deserialize, JsonSchema)]
+#[derive(Debug, Default, Deserialize, Serialize, JsonSchema)]
 #[serde(default)]
 #[schemars(deny_unknown_fields)]
 pub struct CheckConfig {
@@ -39,10 +113,14 @@ pub struct CheckConfig {
     pub block_tags: Vec<String>,
     /// Fail if any TODOs have expired deadlines
     pub expired: Option<bool>,
+    /// Fail on items of `require_issue_tags` without an issue reference
+    pub require_issue: Option<bool>,
+    /// Tags that must carry an issue reference under `require_issue` (default: FIXME, BUG)
+    pub require_issue_tags: Vec<String>,
 }
 
 /// Git blame analysis settings
-#[derive(Debug, Default, Deserialize, JsonSchema)]
+#[derive(Debug, Default, Deserialize, Serialize, JsonSchema)]
 #[serde(default)]
 #[schemars(deny_unknown_fields)]
 pub struct BlameConfig {
@@ -51,7 +129,7 @@ pub struct BlameConfig {
 }
 
 /// Lint rule settings for TODO comment formatting
-#[derive(Debug, Default, Deserialize, JsonSchema)]
+#[derive(Debug, Default, Deserialize, Serialize, JsonSchema)]
 #[serde(default)]
 #[schemars(deny_unknown_fields)]
 pub struct LintConfig {
@@ -67,10 +145,38 @@ pub struct LintConfig {
     pub uppercase_tag: Option<bool>,
     /// Enforce colon after tag (default: true)
     pub require_colon: Option<bool>,
+    /// Severity per rule name (default: error for every rule)
+    pub severity: HashMap<String, Severity>,
+    /// Exit code for the most severe violation found
+    pub exit_codes: LintExitCodes,
+}
+
+/// Exit codes `lint` uses for its most severe violation
+#[derive(Debug, Default, Deserialize, Serialize, JsonSchema)]
+#[serde(default)]
+#[schemars(deny_unknown_fields)]
+pub struct LintExitCodes {
+    /// Exit code when any violation is an error (default: 1)
+    pub error: Option<u8>,
+    /// Exit code when the worst violation is a warning (default: 0)
+    pub warning: Option<u8>,
+    /// Exit code when every violation is a notice (default: 0)
+    pub notice: Option<u8>,
+}
+
+impl LintExitCodes {
+    pub fn code_for(&self, severity: Severity) -> i32 {
+        let code = match severity {
+            Severity::Error => self.error.unwrap_or(1),
+            Severity::Warning => self.warning.unwrap_or(0),
+            Severity::Notice => self.notice.unwrap_or(0),
+        };
+        i32::from(code)
+    }
 }
 
 /// Clean detection settings for stale issues and duplicates
-#[derive(Debug, Default, Deserialize, JsonSchema)]
+#[derive(Debug, Default, Deserialize, Serialize, JsonSchema)]
 #[serde(default)]
 #[schemars(deny_unknown_fields)]
 pub struct CleanConfig {
@@ -80,21 +186,56 @@ pub struct CleanConfig {
     pub duplicates: Option<bool>,
     /// Only flag issues closed longer than this duration (e.g., "30d")
     pub since: Option<String>,
+    /// Flag TODOs whose `created=` date is older than this when their issue
+    /// state cannot be checked (default: "365d")
+    pub max_age: Option<String>,
+    /// Flag TODOs that have a deadline but no (author) owner (default: false)
+    pub deadline_without_owner: Option<bool>,
+    /// Exempt TODOs added or moved within this many days (per a diff against
+    /// the last commit older than that) from the `created=` age rule
+    pub stale_grace_days: Option<u64>,
+    /// Where duplicates are looked for: across the whole repo, or only
+    /// within the same file or directory (default: repo)
+    pub duplicate_scope: Option<DupScope>,
+}
+
+/// Grouping scope for `clean` duplicate detection.
+#[derive(
+    Debug, Clone, Copy, PartialEq, Eq, Deserialize, Serialize, JsonSchema, clap::ValueEnum,
+)]
+#[serde(rename_all = "lowercase")]
+pub enum DupScope {
+    /// Any two TODOs with the same message
+    Repo,
+    /// Only TODOs in the same directory
+    Dir,
+    /// Only TODOs in the same file
+    File,
+}
+
+/// Stats computation settings
+#[derive(Debug, Default, Deserialize, Serialize, JsonSchema)]
+#[serde(default)]
+#[schemars(deny_unknown_fields)]
+pub struct StatsConfig {
+    /// Minimum priority counted for a tag in stats priority counts
+    /// (e.g., BUG = "urgent"). Unlisted tags count by their own priority.
+    pub tag_priority: HashMap<String, Priority>,
 }
 
 /// Workspace/monorepo settings
-#[derive(Debug, Default, Deserialize, JsonSchema)]
+#[derive(Debug, Default, Deserialize, Serialize, JsonSchema)]
 #[serde(default)]
 #[schemars(deny_unknown_fields)]
 pub struct WorkspaceConfig {
     /// Enable automatic workspace detection (default: true)
     pub auto_detect: Option<bool>,
     /// Per-package check configuration
-    pub packages: std::collections::HashMap<String, PackageCheckConfig>,
+    pub packages: HashMap<String, PackageCheckConfig>,
 }
 
 /// Per-package check configuration
-#[derive(Debug, Default, Deserialize, JsonSchema)]
+#[derive(Debug, Default, Deserialize, Serialize, JsonSchema)]
 #[serde(default)]
 #[schemars(deny_unknown_fields)]
 pub struct PackageCheckConfig {
@@ -115,42 +256,344 @@ impl Default for Config {
                 "BUG".into(),
                 "NOTE".into(),
             ],
-            exclude_dirs: vec![],
+            enabled_tags: None,
+            case_insensitive_tags: None,
+            exclude_dirs: DEFAULT_EXCLUDE_DIRS.iter().map(|d| d.to_string()).collect(),
             exclude_patterns: vec![],
+            test_paths: vec![],
+            continuation_lines: None,
+            respect_gitignore: None,
+            follow_symlinks: None,
+            scan_filenames: None,
+            scan_large: None,
+            strip_comment_terminators: None,
+            collapse_whitespace: None,
+            string_literals_as_ignored: None,
+            max_line_length: None,
+            resolved_markers: vec![],
+            comment_prefixes: HashMap::new(),
+            permissive_unknown: None,
+            max_items: None,
+            severity_overrides: vec![],
+            tag_labels: HashMap::new(),
+            github_actions_emoji: HashMap::new(),
+            date_format: None,
+            trailing_newline: None,
             check: CheckConfig::default(),
             blame: BlameConfig::default(),
             lint: LintConfig::default(),
             clean: CleanConfig::default(),
+            stats: StatsConfig::default(),
             workspace: WorkspaceConfig::default(),
         }
     }
 }
 
 impl Config {
-    /// Build regex pattern from configured tags.
+    /// Configured tags that are enabled for matching, in `tags` order.
+    pub fn active_tags(&self) -> Vec<&str> {
+        self.tags
+            .iter()
+            .filter(|tag| match &self.enabled_tags {
+                Some(enabled) => enabled.iter().any(|e| e.eq_ignore_ascii_case(tag)),
+                None => true,
+            })
+            .map(String::as_str)
+            .collect()
+    }
+
+    /// Check settings that can't be expressed in the schema.
+    pub fn validate(&self) -> Result<()> {
+        if let Some(enabled) = &self.enabled_tags {
+            for tag in enabled {
+                anyhow::ensure!(
+                    self.tags.iter().any(|t| t.eq_ignore_ascii_case(tag)),
+                    "Unknown tag in enabled_tags: {}",
+                    tag
+                );
+            }
+        }
+        anyhow::ensure!(
+            !self.active_tags().is_empty(),
+            "No tags enabled: enabled_tags must include at least one tag"
+        );
+        for rule in self.lint.severity.keys() {
+            anyhow::ensure!(
+                crate::lint::RULES.contains(&rule.as_str()),
+                "Unknown rule in lint.severity: {} (expected one of: {})",
+                rule,
+                crate::lint::RULES.join(", ")
+            );
+        }
+        Ok(())
+    }
+
+    /// Build regex pattern from the enabled tags.
     /// Each tag is escaped to prevent regex injection from config values.
     pub fn tags_pattern(&self) -> String {
         let tags = self
-            .tags
-            .iter()
-            .map(|t| regex::escape(t))
+            .active_tags()
+            .into_iter()
+            .map(regex::escape)
             .collect::<Vec<_>>()
             .join("|");
-        format!(r"(?i)\b({tags})\b(?:\(([^)]+)\))?:?\s*(!{{1,2}})?\s*(.*)$")
+        let flags = if self.case_insensitive_tags.unwrap_or(true) {
+            "(?i)"
+        } else {
+            ""
+        };
+        format!(r"{flags}\b({tags})\b(?:\(([^)]+)\))?:?\s*(!{{1,2}})?\s*(.*)$")
     }
 
     /// Load config from .todo-scan.toml, searching up from the given directory
     pub fn load(start_dir: &Path) -> Result<Self> {
         if let Some(path) = find_config_file(start_dir) {
-            let content = std::fs::read_to_string(&path)
-                .with_context(|| format!("Failed to read config: {}", path.display()))?;
-            let config: Config = toml::from_str(&content)
-                .with_context(|| format!("Failed to parse config: {}", path.display()))?;
-            Ok(config)
+            Self::from_file(&path)
         } else {
             Ok(Config::default())
         }
     }
+
+    /// Load and merge several config files; later files override earlier ones.
+    /// See [`Config::merge`] for the layering rules.
+    pub fn load_layered(paths: &[PathBuf]) -> Result<Self> {
+        paths.iter().try_fold(Config::default(), |merged, path| {
+            Ok(merged.merge(Self::from_file(path)?))
+        })
+    }
+
+    fn from_file(path: &Path) -> Result<Self> {
+        let content = std::fs::read_to_string(path)
+            .with_context(|| format!("Failed to read config: {}", path.display()))?;
+        toml::from_str(&content)
+            .with_context(|| format!("Failed to parse config: {}", path.display()))
+    }
+
+    /// Layer `other` on top of `self`.
+    ///
+    /// - Scalar settings (`Option` fields) from `other` replace those in `self`
+    ///   when set.
+    /// - `tags` and the lint tag lists replace the earlier list when set;
+    ///   `tags` counts as set when it differs from the default list.
+    /// - Exclusion and blocking lists (`exclude_dirs`, `exclude_patterns`,
+    ///   `test_paths`, `resolved_markers`, `block_tags`) are appended,
+    ///   skipping duplicates. The
+    ///   built-in `exclude_dirs` only survive while no layer sets its own.
+    /// - `severity_overrides` are appended; every matching rule applies.
+    /// - `tag_labels`, `github_actions_emoji` and `stats.tag_priority` are
+    ///   merged by tag, and `comment_prefixes` by extension; a later entry
+    ///   replaces an earlier one.
+    /// - Workspace packages are merged by name using the same rules.
+    pub fn merge(self, other: Config) -> Config {
+        let tags = if other.tags == Config::default().tags {
+            self.tags
+        } else {
+            other.tags
+        };
+        let exclude_dirs = if other.exclude_dirs == DEFAULT_EXCLUDE_DIRS {
+            self.exclude_dirs
+        } else if self.exclude_dirs == DEFAULT_EXCLUDE_DIRS {
+            other.exclude_dirs
+        } else {
+            append_unique(self.exclude_dirs, other.exclude_dirs)
+        };
+        Config {
+            tags,
+            enabled_tags: other.enabled_tags.or(self.enabled_tags),
+            case_insensitive_tags: other.case_insensitive_tags.or(self.case_insensitive_tags),
+            exclude_dirs,
+            exclude_patterns: append_unique(self.exclude_patterns, other.exclude_patterns),
+            test_paths: append_unique(self.test_paths, other.test_paths),
+            continuation_lines: other.continuation_lines.or(self.continuation_lines),
+            respect_gitignore: other.respect_gitignore.or(self.respect_gitignore),
+            follow_symlinks: other.follow_symlinks.or(self.follow_symlinks),
+            scan_filenames: other.scan_filenames.or(self.scan_filenames),
+            scan_large: other.scan_large.or(self.scan_large),
+            strip_comment_terminators: other
+                .strip_comment_terminators
+                .or(self.strip_comment_terminators),
+            collapse_whitespace: other.collapse_whitespace.or(self.collapse_whitespace),
+            string_literals_as_ignored: other
+                .string_literals_as_ignored
+                .or(self.string_literals_as_ignored),
+            max_line_length: other.max_line_length.or(self.max_line_length),
+            resolved_markers: append_unique(self.resolved_markers, other.resolved_markers),
+            comment_prefixes: self
+                .comment_prefixes
+                .into_iter()
+                .chain(other.comment_prefixes)
+                .collect(),
+            permissive_unknown: other.permissive_unknown.or(self.permissive_unknown),
+            max_items: other.max_items.or(self.max_items),
+            severity_overrides: [self.severity_overrides, other.severity_overrides].concat(),
+            tag_labels: self
+                .tag_labels
+                .into_iter()
+                .chain(other.tag_labels)
+                .collect(),
+            github_actions_emoji: self
+                .github_actions_emoji
+                .into_iter()
+                .chain(other.github_actions_emoji)
+                .collect(),
+            date_format: other.date_format.or(self.date_format),
+            trailing_newline: other.trailing_newline.or(self.trailing_newline),
+            check: CheckConfig {
+                max: other.check.max.or(self.check.max),
+                max_new: other.check.max_new.or(self.check.max_new),
+                block_tags: append_unique(self.check.block_tags, other.check.block_tags),
+                expired: other.check.expired.or(self.check.expired),
+                require_issue: other.check.require_issue.or(self.check.require_issue),
+                require_issue_tags: append_unique(
+                    self.check.require_issue_tags,
+                    other.check.require_issue_tags,
+                ),
+            },
+            blame: BlameConfig {
+                stale_threshold: other.blame.stale_threshold.or(self.blame.stale_threshold),
+            },
+            lint: LintConfig {
+                no_bare_tags: other.lint.no_bare_tags.or(self.lint.no_bare_tags),
+                max_message_length: other
+                    .lint
+                    .max_message_length
+                    .or(self.lint.max_message_length),
+                require_author: other.lint.require_author.or(self.lint.require_author),
+                require_issue_ref: other.lint.require_issue_ref.or(self.lint.require_issue_ref),
+                uppercase_tag: other.lint.uppercase_tag.or(self.lint.uppercase_tag),
+                require_colon: other.lint.require_colon.or(self.lint.require_colon),
+                severity: {
+                    let mut severity = self.lint.severity;
+                    severity.extend(other.lint.severity);
+                    severity
+                },
+                exit_codes: LintExitCodes {
+                    error: other.lint.exit_codes.error.or(self.lint.exit_codes.error),
+                    warning: other
+                        .lint
+                        .exit_codes
+                        .warning
+                        .or(self.lint.exit_codes.warning),
+                    notice: other.lint.exit_codes.notice.or(self.lint.exit_codes.notice),
+                },
+            },
+            clean: CleanConfig {
+                stale_issues: other.clean.stale_issues.or(self.clean.stale_issues),
+                duplicates: other.clean.duplicates.or(self.clean.duplicates),
+                since: other.clean.since.or(self.clean.since),
+                max_age: other.clean.max_age.or(self.clean.max_age),
+                deadline_without_owner: other
+                    .clean
+                    .deadline_without_owner
+                    .or(self.clean.deadline_without_owner),
+                stale_grace_days: other.clean.stale_grace_days.or(self.clean.stale_grace_days),
+                duplicate_scope: other.clean.duplicate_scope.or(self.clean.duplicate_scope),
+            },
+            stats: StatsConfig {
+                tag_priority: self
+                    .stats
+                    .tag_priority
+                    .into_iter()
+                    .chain(other.stats.tag_priority)
+                    .collect(),
+            },
+            workspace: WorkspaceConfig {
+                auto_detect: other.workspace.auto_detect.or(self.workspace.auto_detect),
+                packages: merge_packages(self.workspace.packages, other.workspace.packages),
+            },
+        }
+    }
+}
+
+/// Append the items of `extra` that are not already in `base`.
+fn append_unique(mut base: Vec<String>, extra: Vec<String>) -> Vec<String> {
+    for item in extra {
+        if !base.contains(&item) {
+            base.push(item);
+        }
+    }
+    base
+}
+
+fn merge_packages(
+    mut base: HashMap<String, PackageCheckConfig>,
+    overlay: HashMap<String, PackageCheckConfig>,
+) -> HashMap<String, PackageCheckConfig> {
+    for (name, pkg) in overlay {
+        let merged = match base.remove(&name) {
+            Some(existing) => PackageCheckConfig {
+                max: pkg.max.or(existing.max),
+                block_tags: append_unique(existing.block_tags, pkg.block_tags),
+            },
+            None => pkg,
+        };
+        base.insert(name, merged);
+    }
+    base
+}
+
+/// The config files that [`Config::load`] or [`Config::load_layered`] read
+/// for this invocation, in layering order.
+pub fn config_sources(start_dir: &Path, explicit: &[PathBuf]) -> Vec<PathBuf> {
+    if explicit.is_empty() {
+        find_config_file(start_dir).into_iter().collect()
+    } else {
+        explicit.to_vec()
+    }
+}
+
+/// Resolved config plus the ignore/exclude rules a scan would apply,
+/// printed by `--explain-config`.
+#[derive(Debug, Serialize)]
+pub struct ConfigExplanation<'a> {
+    pub config_files: Vec<PathBuf>,
+    pub config: &'a Config,
+    pub ignore: IgnoreRules,
+}
+
+/// Ignore and exclude rules active for a scan.
+#[derive(Debug, Serialize)]
+pub struct IgnoreRules {
+    pub respect_gitignore: bool,
+    /// Ignore file sources honored by the walker (empty when disabled)
+    pub ignore_files: Vec<&'static str>,
+    pub exclude_dirs: Vec<String>,
+    pub exclude_patterns: Vec<String>,
+    /// Patterns that fail to compile; the scanner silently skips them
+    pub invalid_exclude_patterns: Vec<String>,
+}
+
+impl Config {
+    pub fn explain(&self, config_files: Vec<PathBuf>) -> ConfigExplanation<'_> {
+        let respect_gitignore = self.respect_gitignore.unwrap_or(true);
+        let ignore_files = if respect_gitignore {
+            vec![
+                ".gitignore",
+                ".git/info/exclude",
+                "core.excludesFile",
+                ".ignore",
+            ]
+        } else {
+            vec![]
+        };
+        let invalid_exclude_patterns = self
+            .exclude_patterns
+            .iter()
+            .filter(|p| regex::Regex::new(p).is_err())
+            .cloned()
+            .collect();
+        ConfigExplanation {
+            config_files,
+            config: self,
+            ignore: IgnoreRules {
+                respect_gitignore,
+                ignore_files,
+                exclude_dirs: self.exclude_dirs.clone(),
+                exclude_patterns: self.exclude_patterns.clone(),
+                invalid_exclude_patterns,
+            },
+        }
+    }
 }
 
 /// Search for .todo-scan.toml from start_dir upward
@@ -250,6 +693,64 @@ max = 10
         assert!(re.is_ok());
     }
 
+    #[test]
+    fn test_tags_pattern_case_sensitive_when_disabled() {
+        let re = regex::Regex::new(&Config::default().tags_pattern()).unwrap();
+        assert!(re.is_match("// todo: lowercase"));
+
+        let config = Config {
+            case_insensitive_tags: Some(false),
+            ..Config::default()
+        };
+        let re = regex::Regex::new(&config.tags_pattern()).unwrap();
+        assert!(re.is_match("// TODO: uppercase"));
+        assert!(!re.is_match("// todo: lowercase"));
+        assert!(!re.is_match("// Note: prose"));
+    }
+
+    #[test]
+    fn test_tags_pattern_skips_disabled_tags() {
+        let config = Config {
+            enabled_tags: Some(vec!["todo".into(), "FIXME".into()]),
+            ..Config::default()
+        };
+        assert_eq!(config.active_tags(), vec!["TODO", "FIXME"]);
+        let re = regex::Regex::new(&config.tags_pattern()).unwrap();
+        assert!(re.is_match("// FIXME: broken"));
+        assert!(!re.is_match("// HACK: workaround"));
+        assert!(!re.is_match("// NOTE: context"));
+    }
+
+    #[test]
+    fn test_validate_rejects_no_enabled_tags() {
+        let config = Config {
+            enabled_tags: Some(vec![]),
+            ..Config::default()
+        };
+        let err = config.validate().unwrap_err();
+        assert!(err.to_string().contains("at least one tag"));
+    }
+
+    #[test]
+    fn test_validate_rejects_unknown_enabled_tag() {
+        let config = Config {
+            enabled_tags: Some(vec!["TODO".into(), "WIP".into()]),
+            ..Config::default()
+        };
+        let err = config.validate().unwrap_err();
+        assert!(err.to_string().contains("enabled_tags: WIP"));
+        assert!(Config::default().validate().is_ok());
+    }
+
+    #[test]
+    fn test_validate_rejects_unknown_lint_severity_rule() {
+        let config = parse("[lint.severity]\nvague_message = \"warning\"\n");
+        let err = config.validate().unwrap_err();
+        assert!(err.to_string().contains("lint.severity: vague_message"));
+        let config = parse("[lint.severity]\nmax_message_length = \"warning\"\n");
+        assert!(config.validate().is_ok());
+    }
+
     #[test]
     fn test_tags_pattern_escapes_parentheses() {
         let config = Config {
@@ -405,6 +906,134 @@ auto_detect = true
         assert_eq!(config.workspace.auto_detect, Some(true));
     }
 
+    // --- Config::merge() tests ---
+
+    fn parse(toml_str: &str) -> Config {
+        toml::from_str(toml_str).unwrap()
+    }
+
+    #[test]
+    fn test_merge_scalars_later_wins() {
+        let base = parse("continuation_lines = 2\n[check]\nmax = 100\nexpired = true\n");
+        let overlay = parse("[check]\nmax = 20\n");
+        let merged = base.merge(overlay);
+        assert_eq!(merged.check.max, Some(20));
+        // Unset scalars in the overlay keep the earlier value
+        assert_eq!(merged.check.expired, Some(true));
+        assert_eq!(merged.continuation_lines, Some(2));
+    }
+
+    #[test]
+    fn test_merge_exclusion_lists_append_unique() {
+        let base = parse("exclude_dirs = [\"vendor\"]\n[check]\nblock_tags = [\"BUG\"]\n");
+        let overlay =
+            parse("exclude_dirs = [\"vendor\", \"dist\"]\n[check]\nblock_tags = [\"HACK\"]\n");
+        let merged = base.merge(overlay);
+        assert_eq!(merged.exclude_dirs, vec!["vendor", "dist"]);
+        assert_eq!(merged.check.block_tags, vec!["BUG", "HACK"]);
+    }
+
+    #[test]
+    fn test_merge_tag_lists_replace_when_set() {
+        let base = parse("tags = [\"TODO\", \"FIXME\"]\n[lint]\nrequire_author = [\"TODO\"]\n");
+        let merged = base.merge(parse(
+            "tags = [\"BUG\"]\n[lint]\nrequire_author = [\"BUG\"]\n",
+        ));
+        assert_eq!(merged.tags, vec!["BUG"]);
+        assert_eq!(merged.lint.require_author, Some(vec!["BUG".to_string()]));
+
+        // An overlay that does not set tags keeps the earlier list
+        let merged = merged.merge(parse("exclude_dirs = [\"dist\"]\n"));
+        assert_eq!(merged.tags, vec!["BUG"]);
+        assert_eq!(merged.lint.require_author, Some(vec!["BUG".to_string()]));
+    }
+
+    #[test]
+    fn test_merge_tag_labels_by_tag() {
+        let base = parse("[tag_labels]\nBUG = \"Bug\"\nTODO = \"To do\"\n");
+        let merged = base.merge(parse("[tag_labels]\nBUG = \"Defect\"\n"));
+        assert_eq!(merged.tag_labels["BUG"], "Defect");
+        assert_eq!(merged.tag_labels["TODO"], "To do");
+    }
+
+    #[test]
+    fn test_merge_lint_severity_by_rule() {
+        let base = parse(
+            "[lint.severity]\nrequire_colon = \"warning\"\nuppercase_tag = \"notice\"\n[lint.exit_codes]\nwarning = 2\n",
+        );
+        let merged = base.merge(parse(
+            "[lint.severity]\nrequire_colon = \"error\"\n[lint.exit_codes]\nerror = 4\n",
+        ));
+        assert_eq!(merged.lint.severity["require_colon"], Severity::Error);
+        assert_eq!(merged.lint.severity["uppercase_tag"], Severity::Notice);
+        assert_eq!(merged.lint.exit_codes.code_for(Severity::Error), 4);
+        assert_eq!(merged.lint.exit_codes.code_for(Severity::Warning), 2);
+        assert_eq!(merged.lint.exit_codes.code_for(Severity::Notice), 0);
+    }
+
+    #[test]
+    fn test_merge_workspace_packages_by_name() {
+        let base = parse(
+            "[workspace.packages.core]\nmax = 50\nblock_tags = [\"BUG\"]\n[workspace.packages.cli]\nmax = 5\n",
+        );
+        let overlay = parse("[workspace.packages.core]\nmax = 10\nblock_tags = [\"HACK\"]\n");
+        let merged = base.merge(overlay);
+        assert_eq!(merged.workspace.packages["core"].max, Some(10));
+        assert_eq!(
+            merged.workspace.packages["core"].block_tags,
+            vec!["BUG", "HACK"]
+        );
+        assert_eq!(merged.workspace.packages["cli"].max, Some(5));
+    }
+
+    #[test]
+    fn test_config_sources_prefers_explicit_files() {
+        let dir = tempfile::TempDir::new().unwrap();
+        assert!(config_sources(dir.path(), &[]).is_empty());
+
+        let found = dir.path().join(".todo-scan.toml");
+        std::fs::write(&found, "").unwrap();
+        assert_eq!(config_sources(dir.path(), &[]), vec![found]);
+
+        let explicit = vec![PathBuf::from("org.toml")];
+        assert_eq!(config_sources(dir.path(), &explicit), explicit);
+    }
+
+    #[test]
+    fn test_explain_lists_ignore_rules() {
+        let config = parse("exclude_patterns = [\"gen\", \"(\"]\n");
+        let explanation = config.explain(vec![]);
+        assert!(explanation.ignore.respect_gitignore);
+        assert!(explanation.ignore.ignore_files.contains(&".gitignore"));
+        assert_eq!(explanation.ignore.invalid_exclude_patterns, vec!["("]);
+
+        let config = parse("respect_gitignore = false\n");
+        assert!(config.explain(vec![]).ignore.ignore_files.is_empty());
+    }
+
+    #[test]
+    fn test_load_layered_applies_files_in_order() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let org = dir.path().join("org.toml");
+        let repo = dir.path().join("repo.toml");
+        std::fs::write(&org, "exclude_dirs = [\"vendor\"]\n[check]\nmax = 100\n").unwrap();
+        std::fs::write(&repo, "[check]\nmax = 10\n").unwrap();
+
+        let config = Config::load_layered(&[org, repo]).unwrap();
+        assert_eq!(config.check.max, Some(10));
+        assert_eq!(config.exclude_dirs, vec!["vendor"]);
+    }
+
+    #[test]
+    fn test_load_layered_reports_failing_file() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let bad = dir.path().join("bad.toml");
+        std::fs::write(&bad, "not [valid").unwrap();
+        let err = format!("{:#}", Config::load_layered(&[bad]).unwrap_err());
+        assert!(err.contains("Failed to parse config"), "got: {err}");
+        assert!(err.contains("bad.toml"), "got: {err}");
+    }
+
     /// Validates that schema/todo-scan.schema.json matches the current Config structs.
     ///
     /// To regenerate the schema after changing Config:
@@ -443,7 +1072,7 @@ mod coverage_tests {
         let config = Config::load(dir.path()).unwrap();
         // Should return defaults when no .todo-scan.toml exists
         assert_eq!(config.tags.len(), 6);
-        assert!(config.exclude_dirs.is_empty());
+        assert_eq!(config.exclude_dirs, DEFAULT_EXCLUDE_DIRS);
         assert!(config.exclude_patterns.is_empty());
     }
 
@@ -532,7 +1161,8 @@ exclude_dirs = ["vendor"]
             config.tags,
             vec!["TODO", "FIXME", "HACK", "XXX", "BUG", "NOTE"]
         );
-        assert!(config.exclude_dirs.is_empty());
+        assert!(config.exclude_dirs.contains(&"target".to_string()));
+        assert!(config.exclude_dirs.contains(&"node_modules".to_string()));
         assert!(config.exclude_patterns.is_empty());
         assert!(config.check.max.is_none());
         assert!(config.check.max_new.is_none());
@@ -1,8 +1,11 @@
+use regex::Regex;
 use serde::Serialize;
 use std::path::Path;
+use std::sync::LazyLock;
 
 use anyhow::{Context, Result};
 
+use crate::git::git_command;
 use crate::model::TodoItem;
 
 #[derive(Debug, Clone, Serialize)]
@@ -24,6 +27,8 @@ pub struct RichContext {
     pub before: Vec<ContextLine>,
     pub todo_line: String,
     pub after: Vec<ContextLine>,
+    /// Nearest `fn`/`def`/`function` declared at or above the TODO, if any
+    pub enclosing_symbol: Option<String>,
     pub related_todos: Vec<RelatedTodo>,
 }
 
@@ -74,18 +79,82 @@ pub fn extract_context(content: &str, target_line: usize, n: usize) -> ContextIn
     ContextInfo { before, after }
 }
 
-/// Read file and extract context around the given line.
-/// Returns (ContextInfo, todo_line_content).
-pub fn read_file_context(
-    root: &Path,
-    file: &str,
-    line: usize,
-    n: usize,
-) -> Result<(ContextInfo, String)> {
-    let path = root.join(file);
-    let content =
-        std::fs::read_to_string(&path).with_context(|| format!("cannot read file: {}", file))?;
+static RUST_FN_RE: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r#"^\s*(?:pub(?:\([^)]*\))?\s+)?(?:(?:const|async|unsafe|extern(?:\s+"[^"]*")?)\s+)*fn\s+([A-Za-z_][A-Za-z0-9_]*)"#)
+        .unwrap()
+});
+static PYTHON_DEF_RE: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"^\s*(?:async\s+)?def\s+([A-Za-z_][A-Za-z0-9_]*)").unwrap());
+static JS_FUNCTION_RE: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"\bfunction\s*\*?\s*([A-Za-z_$][A-Za-z0-9_$]*)").unwrap());
+static ANY_DECL_RE: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"^\s*(?:[a-z]+\s+)*(?:fn|def|function)\s+([A-Za-z_$][A-Za-z0-9_$]*)").unwrap()
+});
+
+/// Pick the declaration pattern for a file by its extension; unknown
+/// extensions accept any of `fn`, `def` and `function`.
+fn symbol_pattern(file: &str) -> &'static Regex {
+    let ext = Path::new(file)
+        .extension()
+        .and_then(|e| e.to_str())
+        .unwrap_or("");
+    match ext {
+        "rs" => &RUST_FN_RE,
+        "py" | "pyi" => &PYTHON_DEF_RE,
+        "js" | "jsx" | "mjs" | "cjs" | "ts" | "tsx" | "mts" | "cts" => &JS_FUNCTION_RE,
+        _ => &ANY_DECL_RE,
+    }
+}
+
+/// Find the name of the nearest function declared at or above `target_line`
+/// (1-based). This is a per-language regex heuristic: it does not track
+/// scopes, so a TODO after the end of a function still reports that function.
+pub fn enclosing_symbol(file: &str, content: &str, target_line: usize) -> Option<String> {
+    let re = symbol_pattern(file);
+    content
+        .lines()
+        .take(target_line)
+        .collect::<Vec<_>>()
+        .into_iter()
+        .rev()
+        .find_map(|line| re.captures(line).map(|caps| caps[1].to_string()))
+}
+
+/// Read a location relative to `root`, naming it in the error. A notebook
+/// location (`nb.ipynb#cell3`) reads that cell's source.
+fn read_source(root: &Path, location: &str) -> Result<String> {
+    let (file, cell) = match split_cell(location) {
+        Some((file, cell)) => (file, Some(cell)),
+        None => (location, None),
+    };
+    let content = std::fs::read_to_string(root.join(file))
+        .with_context(|| format!("cannot read file: {}", file))?;
+    match cell {
+        Some(cell) => cell_text(&content, cell)
+            .with_context(|| format!("no cell {} in notebook: {}", cell, file)),
+        None => Ok(content),
+    }
+}
+
+/// Split a notebook location `nb.ipynb#cell3` into its file and cell.
+fn split_cell(location: &str) -> Option<(&str, usize)> {
+    let (file, cell) = location.rsplit_once("#cell")?;
+    Some((file, cell.parse().ok()?))
+}
+
+#[cfg(feature = "notebooks")]
+fn cell_text(content: &str, cell: usize) -> Option<String> {
+    crate::notebook::cell_text(content, cell)
+}
+
+#[cfg(not(feature = "notebooks"))]
+fn cell_text(_content: &str, _cell: usize) -> Option<String> {
+    None
+}
 
+/// Extract context around `line` (1-based) from file content.
+/// Returns (ContextInfo, todo_line_content).
+fn context_in(content: &str, line: usize, n: usize) -> (ContextInfo, String) {
     let lines: Vec<&str> = content.lines().collect();
     let todo_line = if line > 0 && line <= lines.len() {
         lines[line - 1].to_string()
@@ -93,8 +162,8 @@ pub fn read_file_context(
         String::new()
     };
 
-    let ctx = extract_context(&content, line, n);
-    Ok((ctx, todo_line))
+    let ctx = extract_context(content, line, n);
+    (ctx, todo_line)
 }
 
 /// Build a RichContext for the standalone `context` subcommand.
@@ -105,7 +174,9 @@ pub fn build_rich_context(
     n: usize,
     todos_in_file: &[&TodoItem],
 ) -> Result<RichContext> {
-    let (ctx, todo_line) = read_file_context(root, file, line, n)?;
+    let content = read_source(root, file)?;
+    let (ctx, todo_line) = context_in(&content, line, n);
+    let enclosing_symbol = enclosing_symbol(file, &content, line);
 
     let window_start = line.saturating_sub(n);
     let window_end = line + n;
@@ -126,6 +197,7 @@ pub fn build_rich_context(
         before: ctx.before,
         todo_line,
         after: ctx.after,
+        enclosing_symbol,
         related_todos,
     })
 }
@@ -135,6 +207,30 @@ pub fn collect_context_map(
     root: &Path,
     items: &[TodoItem],
     n: usize,
+) -> std::collections::HashMap<String, ContextInfo> {
+    context_map_from(items, n, |file| {
+        std::fs::read_to_string(root.join(file)).unwrap_or_default()
+    })
+}
+
+/// Collect context for TODO items as they appear at `git_ref`, reading each
+/// unique file once with `git show`. Used for items that no longer exist in
+/// the working tree, such as entries removed since a base ref.
+pub fn collect_ref_context_map(
+    root: &Path,
+    git_ref: &str,
+    items: &[TodoItem],
+    n: usize,
+) -> std::collections::HashMap<String, ContextInfo> {
+    context_map_from(items, n, |file| {
+        git_command(&["show", &format!("{}:{}", git_ref, file)], root).unwrap_or_default()
+    })
+}
+
+fn context_map_from(
+    items: &[TodoItem],
+    n: usize,
+    mut read: impl FnMut(&str) -> String,
 ) -> std::collections::HashMap<String, ContextInfo> {
     use std::collections::HashMap;
 
@@ -142,13 +238,18 @@ pub fn collect_context_map(
     let mut context_map: HashMap<String, ContextInfo> = HashMap::new();
 
     for item in items {
-        let content = file_contents.entry(item.file.clone()).or_insert_with(|| {
-            let path = root.join(&item.file);
-            std::fs::read_to_string(&path).unwrap_or_default()
-        });
-
-        let ctx = extract_context(content, item.line, n);
-        let key = format!("{}:{}", item.file, item.line);
+        let content = file_contents
+            .entry(item.file.clone())
+            .or_insert_with(|| read(&item.file));
+
+        // Notebook items count lines within their cell
+        let ctx = match item.cell {
+            Some(cell) => {
+                extract_context(&cell_text(content, cell).unwrap_or_default(), item.line, n)
+            }
+            None => extract_context(content, item.line, n),
+        };
+        let key = format!("{}:{}", item.location(), item.line);
         context_map.insert(key, ctx);
     }
 
@@ -161,12 +262,25 @@ pub fn collect_context_map(
 pub fn resolve_location(location: &str, items: &[TodoItem]) -> Result<(String, usize)> {
     for item in items {
         if item.id() == location {
-            return Ok((item.file.clone(), item.line));
+            return Ok((item.location(), item.line));
         }
     }
     parse_location(location)
 }
 
+/// Parse a picker answer into a zero-based index into `count` choices.
+/// Choices are numbered from 1 as shown to the user.
+pub fn parse_selection(input: &str, count: usize) -> Result<usize> {
+    let input = input.trim();
+    let n: usize = input
+        .parse()
+        .with_context(|| format!("not a number: '{}'", input))?;
+    if n == 0 || n > count {
+        anyhow::bail!("choose a number between 1 and {}", count);
+    }
+    Ok(n - 1)
+}
+
 /// Parse a location string like "file.rs:42" into (file, line).
 pub fn parse_location(location: &str) -> Result<(String, usize)> {
     let parts: Vec<&str> = location.rsplitn(2, ':').collect();
@@ -193,6 +307,27 @@ pub fn parse_location(location: &str) -> Result<(String, usize)> {
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_parse_selection_valid_index() {
+        assert_eq!(parse_selection("1", 3).unwrap(), 0);
+        assert_eq!(parse_selection(" 3\n", 3).unwrap(), 2);
+    }
+
+    #[test]
+    fn test_parse_selection_out_of_range() {
+        assert!(parse_selection("0", 3).is_err());
+        let err = parse_selection("4", 3).unwrap_err();
+        assert!(err.to_string().contains("between 1 and 3"));
+    }
+
+    #[test]
+    fn test_parse_selection_non_numeric() {
+        let err = parse_selection("abc", 3).unwrap_err();
+        assert!(err.to_string().contains("not a number"));
+        assert!(parse_selection("", 3).is_err());
+        assert!(parse_selection("-1", 3).is_err());
+    }
+
     #[test]
     fn test_extract_context_basic() {
         let content = "line1\nline2\nline3\nline4\nline5\n";
@@ -270,12 +405,17 @@ mod tests {
         let items = vec![TodoItem {
             file: "src/main.rs".to_string(),
             line: 42,
+            byte_offset: 0,
+            cell: None,
+            labels: vec![],
             tag: crate::model::Tag::Todo,
             message: "fix this bug".to_string(),
             author: None,
             issue_ref: None,
             priority: crate::model::Priority::Normal,
             deadline: None,
+            ignore_reason: None,
+            severity: None,
         }];
         let (file, line) = resolve_location("src/main.rs:TODO:fix this bug", &items).unwrap();
         assert_eq!(file, "src/main.rs");
@@ -287,12 +427,17 @@ mod tests {
         let items = vec![TodoItem {
             file: "src/main.rs".to_string(),
             line: 42,
+            byte_offset: 0,
+            cell: None,
+            labels: vec![],
             tag: crate::model::Tag::Todo,
             message: "fix this bug".to_string(),
             author: None,
             issue_ref: None,
             priority: crate::model::Priority::Normal,
             deadline: None,
+            ignore_reason: None,
+            severity: None,
         }];
         // No ID match, falls back to parse_location
         let (file, line) = resolve_location("src/lib.rs:10", &items).unwrap();
@@ -306,12 +451,17 @@ mod tests {
         let items = vec![TodoItem {
             file: "src/main.rs".to_string(),
             line: 99,
+            byte_offset: 0,
+            cell: None,
+            labels: vec![],
             tag: crate::model::Tag::Fixme,
             message: "urgent problem".to_string(),
             author: None,
             issue_ref: None,
             priority: crate::model::Priority::Normal,
             deadline: None,
+            ignore_reason: None,
+            severity: None,
         }];
         let (file, line) = resolve_location("src/main.rs:FIXME:urgent problem", &items).unwrap();
         assert_eq!(file, "src/main.rs");
@@ -348,12 +498,13 @@ mod tests {
     }
 
     #[test]
-    fn test_read_file_context_basic() {
+    fn test_context_in_basic() {
         let dir = tempfile::tempdir().unwrap();
         let file_path = dir.path().join("test.rs");
         std::fs::write(&file_path, "line1\nline2\nline3\nline4\nline5\n").unwrap();
 
-        let (ctx, todo_line) = read_file_context(dir.path(), "test.rs", 3, 1).unwrap();
+        let content = read_source(dir.path(), "test.rs").unwrap();
+        let (ctx, todo_line) = context_in(&content, 3, 1);
         assert_eq!(todo_line, "line3");
         assert_eq!(ctx.before.len(), 1);
         assert_eq!(ctx.before[0].content, "line2");
@@ -362,33 +513,35 @@ mod tests {
     }
 
     #[test]
-    fn test_read_file_context_line_beyond_file() {
+    fn test_context_in_line_beyond_file() {
         let dir = tempfile::tempdir().unwrap();
         let file_path = dir.path().join("test.rs");
         std::fs::write(&file_path, "only line\n").unwrap();
 
-        let (ctx, todo_line) = read_file_context(dir.path(), "test.rs", 100, 2).unwrap();
+        let content = read_source(dir.path(), "test.rs").unwrap();
+        let (ctx, todo_line) = context_in(&content, 100, 2);
         assert_eq!(todo_line, "");
         assert!(ctx.before.is_empty());
         assert!(ctx.after.is_empty());
     }
 
     #[test]
-    fn test_read_file_context_line_zero() {
+    fn test_context_in_line_zero() {
         let dir = tempfile::tempdir().unwrap();
         let file_path = dir.path().join("test.rs");
         std::fs::write(&file_path, "line1\n").unwrap();
 
-        let (ctx, todo_line) = read_file_context(dir.path(), "test.rs", 0, 2).unwrap();
+        let content = read_source(dir.path(), "test.rs").unwrap();
+        let (ctx, todo_line) = context_in(&content, 0, 2);
         assert_eq!(todo_line, "");
         assert!(ctx.before.is_empty());
         assert!(ctx.after.is_empty());
     }
 
     #[test]
-    fn test_read_file_context_file_not_found() {
+    fn test_read_source_file_not_found() {
         let dir = tempfile::tempdir().unwrap();
-        let result = read_file_context(dir.path(), "nonexistent.rs", 1, 2);
+        let result = read_source(dir.path(), "nonexistent.rs");
         assert!(result.is_err());
     }
 
@@ -405,12 +558,17 @@ mod tests {
         let item1 = TodoItem {
             file: "test.rs".to_string(),
             line: 2,
+            byte_offset: 0,
+            cell: None,
+            labels: vec![],
             tag: crate::model::Tag::Todo,
             message: "fix this".to_string(),
             author: None,
             issue_ref: None,
             priority: crate::model::Priority::Normal,
             deadline: None,
+            ignore_reason: None,
+            severity: None,
         };
 
         let todos_in_file: Vec<&TodoItem> = vec![&item1];
@@ -422,6 +580,51 @@ mod tests {
         assert_eq!(rich.after.len(), 1);
         // The item itself is not included in related_todos (line == target line)
         assert!(rich.related_todos.is_empty());
+        assert_eq!(rich.enclosing_symbol.as_deref(), Some("main"));
+    }
+
+    #[test]
+    fn test_enclosing_symbol_rust_fn() {
+        let content = "use std::io;\n\npub(crate) async fn load_config() {\n    let x = 1;\n    // TODO: cache\n}\n";
+        assert_eq!(
+            enclosing_symbol("src/lib.rs", content, 5).as_deref(),
+            Some("load_config")
+        );
+        // Nearest declaration wins
+        let content =
+            "fn outer() {}\nimpl A {\n    fn inner(&self) {\n        // TODO: x\n    }\n}\n";
+        assert_eq!(
+            enclosing_symbol("a.rs", content, 4).as_deref(),
+            Some("inner")
+        );
+    }
+
+    #[test]
+    fn test_enclosing_symbol_python_def() {
+        let content =
+            "class Loader:\n    async def fetch(self):\n        # TODO: retry\n        pass\n";
+        assert_eq!(
+            enclosing_symbol("loader.py", content, 3).as_deref(),
+            Some("fetch")
+        );
+        // A Rust-style `fn` is not a declaration in Python
+        assert_eq!(enclosing_symbol("x.py", "fn = 1\n# TODO: y\n", 2), None);
+    }
+
+    #[test]
+    fn test_enclosing_symbol_javascript_function() {
+        let content = "export async function render(props) {\n  // TODO: memo\n}\n";
+        assert_eq!(
+            enclosing_symbol("view.ts", content, 2).as_deref(),
+            Some("render")
+        );
+    }
+
+    #[test]
+    fn test_enclosing_symbol_none_at_top_level() {
+        let content = "// TODO: top-level\nfn later() {}\n";
+        assert_eq!(enclosing_symbol("a.rs", content, 1), None);
+        assert_eq!(enclosing_symbol("notes.txt", "TODO: plan\n", 1), None);
     }
 
     #[test]
@@ -437,22 +640,32 @@ mod tests {
         let item1 = TodoItem {
             file: "test.rs".to_string(),
             line: 2,
+            byte_offset: 0,
+            cell: None,
+            labels: vec![],
             tag: crate::model::Tag::Todo,
             message: "first".to_string(),
             author: None,
             issue_ref: None,
             priority: crate::model::Priority::Normal,
             deadline: None,
+            ignore_reason: None,
+            severity: None,
         };
         let item2 = TodoItem {
             file: "test.rs".to_string(),
             line: 4,
+            byte_offset: 0,
+            cell: None,
+            labels: vec![],
             tag: crate::model::Tag::Fixme,
             message: "second".to_string(),
             author: None,
             issue_ref: None,
             priority: crate::model::Priority::Normal,
             deadline: None,
+            ignore_reason: None,
+            severity: None,
         };
 
         let todos_in_file: Vec<&TodoItem> = vec![&item1, &item2];
@@ -474,12 +687,17 @@ mod tests {
         let items = vec![TodoItem {
             file: "test.rs".to_string(),
             line: 2,
+            byte_offset: 0,
+            cell: None,
+            labels: vec![],
             tag: crate::model::Tag::Todo,
             message: "do something".to_string(),
             author: None,
             issue_ref: None,
             priority: crate::model::Priority::Normal,
             deadline: None,
+            ignore_reason: None,
+            severity: None,
         }];
 
         let map = collect_context_map(dir.path(), &items, 1);
@@ -501,22 +719,32 @@ mod tests {
             TodoItem {
                 file: "test.rs".to_string(),
                 line: 2,
+                byte_offset: 0,
+                cell: None,
+                labels: vec![],
                 tag: crate::model::Tag::Todo,
                 message: "first".to_string(),
                 author: None,
                 issue_ref: None,
                 priority: crate::model::Priority::Normal,
                 deadline: None,
+                ignore_reason: None,
+                severity: None,
             },
             TodoItem {
                 file: "test.rs".to_string(),
                 line: 4,
+                byte_offset: 0,
+                cell: None,
+                labels: vec![],
                 tag: crate::model::Tag::Fixme,
                 message: "second".to_string(),
                 author: None,
                 issue_ref: None,
                 priority: crate::model::Priority::Normal,
                 deadline: None,
+                ignore_reason: None,
+                severity: None,
             },
         ];
 
@@ -532,12 +760,17 @@ mod tests {
         let items = vec![TodoItem {
             file: "nonexistent.rs".to_string(),
             line: 1,
+            byte_offset: 0,
+            cell: None,
+            labels: vec![],
             tag: crate::model::Tag::Todo,
             message: "missing".to_string(),
             author: None,
             issue_ref: None,
             priority: crate::model::Priority::Normal,
             deadline: None,
+            ignore_reason: None,
+            severity: None,
         }];
 
         let map = collect_context_map(dir.path(), &items, 1);
@@ -65,6 +65,35 @@ pub fn now_iso8601() -> String {
     format_iso8601_utc(secs)
 }
 
+/// Format an elapsed duration as `HH:MM:SS`, truncating sub-second parts.
+///
+/// Hours are not wrapped, so runs longer than a day show e.g. `26:00:05`.
+pub fn format_hms(elapsed: std::time::Duration) -> String {
+    let secs = elapsed.as_secs();
+    format!(
+        "{:02}:{:02}:{:02}",
+        secs / 3600,
+        (secs % 3600) / 60,
+        secs % 60
+    )
+}
+
+/// Format a day count for humans: up to 90 days stays in days, anything
+/// shorter than a year is shown in whole months, and longer spans in years
+/// with one decimal (e.g. `45 days`, `7 months`, `2.5 years`).
+pub fn humanize_duration_days(days: u64) -> String {
+    const DAYS_PER_MONTH: f64 = 365.25 / 12.0;
+    if days <= 90 {
+        let unit = if days == 1 { "day" } else { "days" };
+        format!("{days} {unit}")
+    } else if days < 365 {
+        let months = (days as f64 / DAYS_PER_MONTH).round() as u64;
+        format!("{months} months")
+    } else {
+        format!("{:.1} years", days as f64 / 365.25)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -177,4 +206,30 @@ mod tests {
         assert_eq!(ts.len(), 20, "ISO-8601 UTC should be 20 chars: {ts}");
         assert!(ts.contains('T'), "should contain T separator: {ts}");
     }
+
+    // ── format_hms ───────────────────────────────────────────
+
+    #[test]
+    fn format_hms_pads_and_truncates() {
+        use std::time::Duration;
+        assert_eq!(format_hms(Duration::ZERO), "00:00:00");
+        assert_eq!(format_hms(Duration::from_millis(192_900)), "00:03:12");
+        assert_eq!(format_hms(Duration::from_secs(3 * 3600 + 5)), "03:00:05");
+        assert_eq!(format_hms(Duration::from_secs(26 * 3600)), "26:00:00");
+    }
+
+    // ── humanize_duration_days ───────────────────────────────
+
+    #[test]
+    fn humanize_duration_days_units() {
+        assert_eq!(humanize_duration_days(0), "0 days");
+        assert_eq!(humanize_duration_days(1), "1 day");
+        assert_eq!(humanize_duration_days(90), "90 days");
+        assert_eq!(humanize_duration_days(91), "3 months");
+        assert_eq!(humanize_duration_days(200), "7 months");
+        assert_eq!(humanize_duration_days(364), "12 months");
+        assert_eq!(humanize_duration_days(365), "1.0 years");
+        assert_eq!(humanize_duration_days(700), "1.9 years");
+        assert_eq!(humanize_duration_days(3653), "10.0 years");
+    }
 }
@@ -1,6 +1,8 @@
 use serde::{Deserialize, Serialize};
 use std::fmt;
 
+use crate::date_utils::ymd_to_days;
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub struct Deadline {
     pub year: u16,
@@ -12,6 +14,87 @@ impl Deadline {
     pub fn is_expired(&self, today: &Deadline) -> bool {
         (self.year, self.month, self.day) < (today.year, today.month, today.day)
     }
+
+    /// Whole days from `today` until this deadline; negative once it has passed.
+    pub fn days_until(&self, today: &Deadline) -> i64 {
+        let days = |d: &Deadline| ymd_to_days(d.year as i64, d.month as u32, d.day as u32);
+        days(self) - days(today)
+    }
+
+    /// Render with a strftime-style `fmt`; see [`check_format`] for the
+    /// supported specifiers. Unknown specifiers are copied through.
+    pub fn format_with(&self, fmt: &str) -> String {
+        let mut out = String::new();
+        let mut chars = fmt.chars();
+        while let Some(c) = chars.next() {
+            if c != '%' {
+                out.push(c);
+                continue;
+            }
+            let mut spec = chars.next();
+            let unpadded = spec == Some('-');
+            if unpadded {
+                spec = chars.next();
+            }
+            let month_name = MONTH_NAMES[(self.month.clamp(1, 12) - 1) as usize];
+            match (spec, unpadded) {
+                (Some('Y'), false) => out.push_str(&format!("{:04}", self.year)),
+                (Some('y'), false) => out.push_str(&format!("{:02}", self.year % 100)),
+                (Some('m'), false) => out.push_str(&format!("{:02}", self.month)),
+                (Some('m'), true) => out.push_str(&self.month.to_string()),
+                (Some('d'), false) => out.push_str(&format!("{:02}", self.day)),
+                (Some('d'), true) => out.push_str(&self.day.to_string()),
+                (Some('b'), false) => out.push_str(&month_name[..3]),
+                (Some('B'), false) => out.push_str(month_name),
+                (Some('%'), false) => out.push('%'),
+                (spec, _) => {
+                    out.push('%');
+                    if unpadded {
+                        out.push('-');
+                    }
+                    out.extend(spec);
+                }
+            }
+        }
+        out
+    }
+}
+
+const MONTH_NAMES: [&str; 12] = [
+    "January",
+    "February",
+    "March",
+    "April",
+    "May",
+    "June",
+    "July",
+    "August",
+    "September",
+    "October",
+    "November",
+    "December",
+];
+
+/// Check that `fmt` only uses supported specifiers: `%Y` (2025), `%y` (25),
+/// `%m` (06), `%-m` (6), `%d` (01), `%-d` (1), `%b` (Jun), `%B` (June) and
+/// `%%`.
+pub fn check_format(fmt: &str) -> anyhow::Result<()> {
+    let mut chars = fmt.chars();
+    while let Some(c) = chars.next() {
+        if c != '%' {
+            continue;
+        }
+        let spec = match chars.next() {
+            Some('-') => chars.next().filter(|s| matches!(s, 'm' | 'd')),
+            spec => spec.filter(|s| matches!(s, 'Y' | 'y' | 'm' | 'd' | 'b' | 'B' | '%')),
+        };
+        anyhow::ensure!(
+            spec.is_some(),
+            "Unsupported date format \"{}\": use %Y, %y, %m, %-m, %d, %-d, %b, %B or %%",
+            fmt
+        );
+    }
+    Ok(())
 }
 
 impl fmt::Display for Deadline {
@@ -94,6 +177,20 @@ pub fn today() -> Deadline {
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_days_until() {
+        let today = Deadline {
+            year: 2025,
+            month: 2,
+            day: 26,
+        };
+        let d = |year, month, day| Deadline { year, month, day };
+        assert_eq!(d(2025, 2, 26).days_until(&today), 0);
+        assert_eq!(d(2025, 3, 5).days_until(&today), 7);
+        assert_eq!(d(2025, 2, 20).days_until(&today), -6);
+        assert_eq!(d(2026, 2, 26).days_until(&today), 365);
+    }
+
     #[test]
     fn test_parse_date_format() {
         let d = parse_deadline("2025-06-01").unwrap();
@@ -363,4 +460,37 @@ mod tests {
         };
         assert!(deadline.is_expired(&today));
     }
+
+    #[test]
+    fn test_format_with_month_name() {
+        let d = Deadline {
+            year: 2025,
+            month: 6,
+            day: 1,
+        };
+        assert_eq!(d.format_with("%b %-d, %Y"), "Jun 1, 2025");
+        assert_eq!(d.format_with("%B %d"), "June 01");
+    }
+
+    #[test]
+    fn test_format_with_numeric() {
+        let d = Deadline {
+            year: 2025,
+            month: 6,
+            day: 1,
+        };
+        assert_eq!(d.format_with("%d/%m/%Y"), "01/06/2025");
+        assert_eq!(d.format_with("%-m/%-d/%y"), "6/1/25");
+        assert_eq!(d.format_with("%Y-%m-%d"), d.to_string());
+        assert_eq!(d.format_with("100%%"), "100%");
+    }
+
+    #[test]
+    fn test_check_format() {
+        assert!(check_format("%b %-d, %Y").is_ok());
+        assert!(check_format("%d/%m/%Y").is_ok());
+        assert!(check_format("%H:%M").is_err());
+        assert!(check_format("%-Y").is_err());
+        assert!(check_format("trailing %").is_err());
+    }
 }
@@ -6,28 +6,38 @@ use std::path::Path;
 use crate::config::Config;
 use crate::git::git_command;
 use crate::model::*;
-use crate::scanner::scan_content;
+use crate::scanner::{scan_file_content, ScanOptions};
 
 /// Detect which files changed between `base_ref` and the current working tree.
 ///
 /// Uses `git diff --name-only` to find files that differ. Falls back to treating
-/// all files as changed if the git diff commands fail (e.g., shallow clone).
+/// all files as changed if the git diff commands fail (e.g., shallow clone),
+/// unless `no_fallback` is set, in which case the git error is returned.
 fn detect_changed_files(
     base_ref: &str,
     root: &Path,
     base_files: &HashSet<String>,
     current: &ScanResult,
-) -> HashSet<String> {
+    no_fallback: bool,
+) -> Result<HashSet<String>> {
     let diff_from_ref = git_command(&["diff", "--name-only", "--", base_ref], root);
     let diff_unstaged = git_command(&["diff", "--name-only"], root);
 
     // If either diff command failed, fall back to all files
     let (diff_ref_output, diff_unstaged_output) = match (diff_from_ref, diff_unstaged) {
         (Ok(a), Ok(b)) => (a, b),
-        _ => {
+        (Err(e), _) | (_, Err(e)) => {
+            if no_fallback {
+                return Err(e).with_context(|| {
+                    format!(
+                        "git could not determine files changed since {} (rerun without --no-fallback to compare all files)",
+                        base_ref
+                    )
+                });
+            }
             let mut all: HashSet<String> = base_files.clone();
             all.extend(current.items.iter().map(|i| i.file.clone()));
-            return all;
+            return Ok(all);
         }
     };
 
@@ -48,7 +58,7 @@ fn detect_changed_files(
         }
     }
 
-    changed_files
+    Ok(changed_files)
 }
 
 pub fn compute_diff(
@@ -56,6 +66,7 @@ pub fn compute_diff(
     base_ref: &str,
     root: &Path,
     config: &Config,
+    no_fallback: bool,
 ) -> Result<DiffResult> {
     anyhow::ensure!(
         !base_ref.starts_with('-'),
@@ -68,6 +79,7 @@ pub fn compute_diff(
 
     let pattern = config.tags_pattern();
     let re = Regex::new(&pattern).with_context(|| format!("Invalid tags pattern: {}", pattern))?;
+    let options = ScanOptions::from_config(config);
 
     let base_files: HashSet<String> = file_list
         .lines()
@@ -75,7 +87,7 @@ pub fn compute_diff(
         .filter(|l| !l.is_empty())
         .collect();
 
-    let changed_files = detect_changed_files(base_ref, root, &base_files, current);
+    let changed_files = detect_changed_files(base_ref, root, &base_files, current, no_fallback)?;
 
     // Only scan changed files from base ref (instead of all files)
     let mut base_items: Vec<TodoItem> = Vec::new();
@@ -89,7 +101,7 @@ pub fn compute_diff(
             Err(_) => continue, // skip binary or inaccessible files
         };
 
-        let result = scan_content(&content, path, &re);
+        let result = scan_file_content(Path::new(path), &content, path, &re, &options);
         base_items.extend(result.items);
     }
 
@@ -207,15 +219,44 @@ mod tests {
         TodoItem {
             file: file.to_string(),
             line,
+            byte_offset: 0,
+            cell: None,
+            labels: vec![],
             tag,
             message: message.to_string(),
             author: None,
             issue_ref: None,
             priority: Priority::Normal,
             deadline: None,
+            ignore_reason: None,
+            severity: None,
         }
     }
 
+    // ---- detect_changed_files no_fallback ----
+
+    #[test]
+    fn test_detect_changed_files_no_fallback_errors_outside_git() {
+        let dir = tempfile::tempdir().unwrap();
+        let current = ScanResult {
+            items: vec![],
+            files_scanned: 0,
+            files_with_items: 0,
+            bytes_scanned: 0,
+            ignored_items: vec![],
+            truncated: false,
+            total_found: None,
+        };
+
+        let result = detect_changed_files("HEAD", dir.path(), &HashSet::new(), &current, true);
+
+        let err = format!("{:#}", result.unwrap_err());
+        assert!(
+            err.contains("git could not determine files changed since HEAD"),
+            "unexpected error: {err}"
+        );
+    }
+
     // ---- Existing test ----
 
     #[test]
@@ -223,11 +264,15 @@ mod tests {
         let current = ScanResult {
             items: vec![],
             files_scanned: 0,
+            files_with_items: 0,
+            bytes_scanned: 0,
             ignored_items: vec![],
+            truncated: false,
+            total_found: None,
         };
         let config = Config::default();
         let root = Path::new(".");
-        let result = compute_diff(&current, "--output=/tmp/leak", root, &config);
+        let result = compute_diff(&current, "--output=/tmp/leak", root, &config, false);
         assert!(result.is_err());
         let err_msg = result.unwrap_err().to_string();
         assert!(
@@ -253,7 +298,7 @@ mod tests {
 
         let config = Config::default();
         let current = crate::scanner::scan_directory(cwd, &config).unwrap();
-        let result = compute_diff(&current, "HEAD", cwd, &config).unwrap();
+        let result = compute_diff(&current, "HEAD", cwd, &config, false).unwrap();
 
         assert_eq!(result.added_count, 2);
         assert_eq!(result.removed_count, 0);
@@ -289,7 +334,7 @@ mod tests {
 
         let config = Config::default();
         let current = crate::scanner::scan_directory(cwd, &config).unwrap();
-        let result = compute_diff(&current, "HEAD", cwd, &config).unwrap();
+        let result = compute_diff(&current, "HEAD", cwd, &config, false).unwrap();
 
         assert_eq!(result.added_count, 0);
         assert_eq!(result.removed_count, 2);
@@ -316,7 +361,7 @@ mod tests {
 
         let config = Config::default();
         let current = crate::scanner::scan_directory(cwd, &config).unwrap();
-        let result = compute_diff(&current, "HEAD", cwd, &config).unwrap();
+        let result = compute_diff(&current, "HEAD", cwd, &config, false).unwrap();
 
         assert_eq!(result.added_count, 0);
         assert_eq!(result.removed_count, 0);
@@ -332,10 +377,14 @@ mod tests {
         let current = ScanResult {
             items: vec![],
             files_scanned: 1,
+            files_with_items: 0,
+            bytes_scanned: 0,
             ignored_items: vec![],
+            truncated: false,
+            total_found: None,
         };
         let config = Config::default();
-        let result = compute_diff(&current, "HEAD", cwd, &config).unwrap();
+        let result = compute_diff(&current, "HEAD", cwd, &config, false).unwrap();
 
         assert_eq!(result.added_count, 0);
         assert_eq!(result.removed_count, 0);
@@ -350,10 +399,14 @@ mod tests {
         let current = ScanResult {
             items: vec![],
             files_scanned: 0,
+            files_with_items: 0,
+            bytes_scanned: 0,
             ignored_items: vec![],
+            truncated: false,
+            total_found: None,
         };
         let config = Config::default();
-        let result = compute_diff(&current, "nonexistent-ref-abc123", cwd, &config);
+        let result = compute_diff(&current, "nonexistent-ref-abc123", cwd, &config, false);
 
         assert!(result.is_err());
         let err_msg = result.unwrap_err().to_string();
@@ -381,7 +434,7 @@ mod tests {
 
         let config = Config::default();
         let current = crate::scanner::scan_directory(cwd, &config).unwrap();
-        let result = compute_diff(&current, "HEAD", cwd, &config).unwrap();
+        let result = compute_diff(&current, "HEAD", cwd, &config, false).unwrap();
 
         assert_eq!(result.added_count, 1);
         assert_eq!(result.removed_count, 1);
@@ -422,7 +475,7 @@ mod tests {
 
         let config = Config::default();
         let current = crate::scanner::scan_directory(cwd, &config).unwrap();
-        let result = compute_diff(&current, "HEAD", cwd, &config).unwrap();
+        let result = compute_diff(&current, "HEAD", cwd, &config, false).unwrap();
 
         assert_eq!(result.added_count, 2);
         assert_eq!(result.removed_count, 0);
@@ -446,7 +499,7 @@ mod tests {
 
         let config = Config::default();
         let current = crate::scanner::scan_directory(cwd, &config).unwrap();
-        let result = compute_diff(&current, "HEAD", cwd, &config).unwrap();
+        let result = compute_diff(&current, "HEAD", cwd, &config, false).unwrap();
 
         assert_eq!(result.added_count, 0);
         assert_eq!(result.removed_count, 1);
@@ -472,7 +525,7 @@ mod tests {
 
         let config = Config::default();
         let current = crate::scanner::scan_directory(cwd, &config).unwrap();
-        let result = compute_diff(&current, "HEAD", cwd, &config).unwrap();
+        let result = compute_diff(&current, "HEAD", cwd, &config, false).unwrap();
 
         assert_eq!(result.added_count, 1);
         assert_eq!(result.removed_count, 0);
@@ -494,7 +547,7 @@ mod tests {
 
         let config = Config::default();
         let current = crate::scanner::scan_directory(cwd, &config).unwrap();
-        let result = compute_diff(&current, "HEAD", cwd, &config).unwrap();
+        let result = compute_diff(&current, "HEAD", cwd, &config, false).unwrap();
 
         assert_eq!(result.base_ref, "HEAD");
     }
@@ -517,7 +570,7 @@ mod tests {
 
         let config = Config::default();
         let current = crate::scanner::scan_directory(cwd, &config).unwrap();
-        let result = compute_diff(&current, "baseline", cwd, &config).unwrap();
+        let result = compute_diff(&current, "baseline", cwd, &config, false).unwrap();
 
         assert_eq!(result.added_count, 1);
         assert_eq!(result.base_ref, "baseline");
@@ -539,7 +592,7 @@ mod tests {
 
         let config = Config::default();
         let current = crate::scanner::scan_directory(cwd, &config).unwrap();
-        let result = compute_diff(&current, "HEAD", cwd, &config).unwrap();
+        let result = compute_diff(&current, "HEAD", cwd, &config, false).unwrap();
 
         assert_eq!(result.added_count, 1);
         assert_eq!(result.removed_count, 1);
@@ -574,7 +627,7 @@ mod tests {
 
         let config = Config::default();
         let current = crate::scanner::scan_directory(cwd, &config).unwrap();
-        let result = compute_diff(&current, "HEAD", cwd, &config).unwrap();
+        let result = compute_diff(&current, "HEAD", cwd, &config, false).unwrap();
 
         assert_eq!(result.added_count, 1);
         assert_eq!(result.removed_count, 1);
@@ -610,7 +663,7 @@ mod tests {
 
         let config = Config::default();
         let current = crate::scanner::scan_directory(cwd, &config).unwrap();
-        let result = compute_diff(&current, "HEAD", cwd, &config).unwrap();
+        let result = compute_diff(&current, "HEAD", cwd, &config, false).unwrap();
 
         assert_eq!(result.added_count, 0);
         assert_eq!(result.removed_count, 0);
@@ -633,7 +686,7 @@ mod tests {
 
         let config = Config::default();
         let current = crate::scanner::scan_directory(cwd, &config).unwrap();
-        let result = compute_diff(&current, "HEAD", cwd, &config).unwrap();
+        let result = compute_diff(&current, "HEAD", cwd, &config, false).unwrap();
 
         assert_eq!(result.added_count, 1);
         let item = &result.entries[0].item;
@@ -655,7 +708,7 @@ mod tests {
 
         let config = Config::default();
         let current = crate::scanner::scan_directory(cwd, &config).unwrap();
-        let result = compute_diff(&current, "HEAD", cwd, &config).unwrap();
+        let result = compute_diff(&current, "HEAD", cwd, &config, false).unwrap();
 
         assert_eq!(result.added_count, 1);
         assert_eq!(result.entries[0].item.file, "src/lib.rs");
@@ -676,7 +729,7 @@ mod tests {
 
         let config = Config::default();
         let current = crate::scanner::scan_directory(cwd, &config).unwrap();
-        let result = compute_diff(&current, "HEAD", cwd, &config).unwrap();
+        let result = compute_diff(&current, "HEAD", cwd, &config, false).unwrap();
 
         assert_eq!(result.added_count, 6);
         let tags: Vec<Tag> = result.entries.iter().map(|e| e.item.tag).collect();
@@ -705,10 +758,14 @@ mod tests {
         let current = ScanResult {
             items: vec![],
             files_scanned: 2,
+            files_with_items: 0,
+            bytes_scanned: 0,
             ignored_items: vec![],
+            truncated: false,
+            total_found: None,
         };
 
-        let changed = detect_changed_files("HEAD", cwd, &base_files, &current);
+        let changed = detect_changed_files("HEAD", cwd, &base_files, &current, false).unwrap();
 
         assert!(
             changed.contains("a.rs"),
@@ -736,10 +793,14 @@ mod tests {
         let current = ScanResult {
             items: vec![make_item("newfile.rs", 1, Tag::Todo, "new")],
             files_scanned: 2,
+            files_with_items: 2,
+            bytes_scanned: 0,
             ignored_items: vec![],
+            truncated: false,
+            total_found: None,
         };
 
-        let changed = detect_changed_files("HEAD", cwd, &base_files, &current);
+        let changed = detect_changed_files("HEAD", cwd, &base_files, &current, false).unwrap();
 
         assert!(
             changed.contains("newfile.rs"),
@@ -760,10 +821,14 @@ mod tests {
         let current = ScanResult {
             items: vec![make_item("current.rs", 1, Tag::Todo, "task")],
             files_scanned: 1,
+            files_with_items: 1,
+            bytes_scanned: 0,
             ignored_items: vec![],
+            truncated: false,
+            total_found: None,
         };
 
-        let changed = detect_changed_files("HEAD", cwd, &base_files, &current);
+        let changed = detect_changed_files("HEAD", cwd, &base_files, &current, false).unwrap();
 
         // Fallback: should include both base_files and current item files
         assert!(
@@ -793,10 +858,14 @@ mod tests {
                 make_item("base1.rs", 3, Tag::Hack, "t3"), // overlaps with base
             ],
             files_scanned: 3,
+            files_with_items: 3,
+            bytes_scanned: 0,
             ignored_items: vec![],
+            truncated: false,
+            total_found: None,
         };
 
-        let changed = detect_changed_files("HEAD", cwd, &base_files, &current);
+        let changed = detect_changed_files("HEAD", cwd, &base_files, &current, false).unwrap();
 
         assert!(changed.contains("base1.rs"));
         assert!(changed.contains("base2.rs"));
@@ -814,10 +883,14 @@ mod tests {
         let current = ScanResult {
             items: vec![],
             files_scanned: 0,
+            files_with_items: 0,
+            bytes_scanned: 0,
             ignored_items: vec![],
+            truncated: false,
+            total_found: None,
         };
 
-        let changed = detect_changed_files("HEAD", cwd, &base_files, &current);
+        let changed = detect_changed_files("HEAD", cwd, &base_files, &current, false).unwrap();
 
         // No files changed, no new files
         assert!(changed.is_empty());
@@ -838,10 +911,14 @@ mod tests {
         let current = ScanResult {
             items: vec![],
             files_scanned: 1,
+            files_with_items: 0,
+            bytes_scanned: 0,
             ignored_items: vec![],
+            truncated: false,
+            total_found: None,
         };
 
-        let changed = detect_changed_files("HEAD", cwd, &base_files, &current);
+        let changed = detect_changed_files("HEAD", cwd, &base_files, &current, false).unwrap();
 
         assert!(
             changed.contains("b.rs"),
@@ -862,11 +939,15 @@ mod tests {
         let current = ScanResult {
             items: vec![make_item("main.rs", 1, Tag::Todo, "task")],
             files_scanned: 1,
+            files_with_items: 1,
+            bytes_scanned: 0,
             ignored_items: vec![],
+            truncated: false,
+            total_found: None,
         };
         let config = Config::default();
 
-        let result = compute_diff(&current, "HEAD", cwd, &config);
+        let result = compute_diff(&current, "HEAD", cwd, &config, false);
         assert!(result.is_err(), "should fail outside a git repo");
     }
 
@@ -887,7 +968,7 @@ mod tests {
 
         let config = Config::default();
         let current = crate::scanner::scan_directory(cwd, &config).unwrap();
-        let result = compute_diff(&current, "HEAD", cwd, &config).unwrap();
+        let result = compute_diff(&current, "HEAD", cwd, &config, false).unwrap();
 
         assert_eq!(result.added_count, 1);
         assert_eq!(result.removed_count, 1);
@@ -916,11 +997,15 @@ mod tests {
         let current = ScanResult {
             items: vec![],
             files_scanned: 1,
+            files_with_items: 0,
+            bytes_scanned: 0,
             ignored_items: vec![],
+            truncated: false,
+            total_found: None,
         };
         let config = Config::default();
 
-        let result = compute_diff(&current, "HEAD", cwd, &config).unwrap();
+        let result = compute_diff(&current, "HEAD", cwd, &config, false).unwrap();
         assert_eq!(result.added_count, 0);
         assert_eq!(result.removed_count, 0);
         assert!(result.entries.is_empty());
@@ -937,7 +1022,7 @@ mod tests {
         // What matters is that compute_diff doesn't crash.
         let config = Config::default();
         let current = crate::scanner::scan_directory(cwd, &config).unwrap();
-        let result = compute_diff(&current, "HEAD", cwd, &config);
+        let result = compute_diff(&current, "HEAD", cwd, &config, false);
 
         // Should succeed regardless
         assert!(result.is_ok());
@@ -958,7 +1043,7 @@ mod tests {
 
         let config = Config::default();
         let current = crate::scanner::scan_directory(cwd, &config).unwrap();
-        let result = compute_diff(&current, "HEAD", cwd, &config).unwrap();
+        let result = compute_diff(&current, "HEAD", cwd, &config, false).unwrap();
 
         assert_eq!(result.added_count, 1);
         assert_eq!(result.entries[0].item.file, "src/utils/helper.rs");
@@ -1008,7 +1093,7 @@ mod tests {
         let current = crate::scanner::scan_directory(cwd, &config).unwrap();
 
         // Diff against the first commit - should show both second commit and working tree additions
-        let result = compute_diff(&current, &first_commit, cwd, &config).unwrap();
+        let result = compute_diff(&current, &first_commit, cwd, &config, false).unwrap();
 
         assert_eq!(result.added_count, 2);
         let messages: Vec<&str> = result
@@ -1038,7 +1123,7 @@ mod tests {
 
         let config = Config::default();
         let current = crate::scanner::scan_directory(cwd, &config).unwrap();
-        let result = compute_diff(&current, "HEAD", cwd, &config).unwrap();
+        let result = compute_diff(&current, "HEAD", cwd, &config, false).unwrap();
 
         let actual_added = result
             .entries
@@ -0,0 +1,237 @@
+//! Line-ending aware file rewriting.
+//!
+//! Every feature that edits source files in place (autofixes, tag migration)
+//! goes through this module so that rewrites never flip a file's line endings.
+//! Each line keeps its original terminator, and lines introduced by an edit
+//! inherit the file's dominant ending.
+
+use anyhow::{Context, Result};
+use std::path::Path;
+
+/// Line-ending style detected in a file's content.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LineEnding {
+    Lf,
+    Crlf,
+    /// Both styles are present; `dominant` is the more frequent one (LF on ties).
+    Mixed {
+        dominant: LineEndingStyle,
+    },
+}
+
+/// A single concrete line terminator.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LineEndingStyle {
+    Lf,
+    Crlf,
+}
+
+impl LineEndingStyle {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            LineEndingStyle::Lf => "\n",
+            LineEndingStyle::Crlf => "\r\n",
+        }
+    }
+}
+
+impl LineEnding {
+    /// The terminator to use for newly introduced lines.
+    pub fn style(&self) -> LineEndingStyle {
+        match self {
+            LineEnding::Lf => LineEndingStyle::Lf,
+            LineEnding::Crlf => LineEndingStyle::Crlf,
+            LineEnding::Mixed { dominant } => *dominant,
+        }
+    }
+}
+
+/// Detect the line-ending style of `content`.
+///
+/// Content without any line terminator is reported as `Lf`.
+pub fn detect_line_ending(content: &str) -> LineEnding {
+    let crlf = content.matches("\r\n").count();
+    let lf = content.matches('\n').count() - crlf;
+
+    match (lf, crlf) {
+        (_, 0) => LineEnding::Lf,
+        (0, _) => LineEnding::Crlf,
+        (lf, crlf) => LineEnding::Mixed {
+            dominant: if crlf > lf {
+                LineEndingStyle::Crlf
+            } else {
+                LineEndingStyle::Lf
+            },
+        },
+    }
+}
+
+/// Split `content` into `(line, terminator)` pairs.
+///
+/// The terminator is `"\n"`, `"\r\n"`, or `""` for a final line without one.
+fn split_terminated(content: &str) -> Vec<(&str, &str)> {
+    let mut parts = Vec::new();
+    let mut rest = content;
+    while !rest.is_empty() {
+        match rest.find('\n') {
+            Some(idx) => {
+                let (line, term) = if idx > 0 && rest.as_bytes()[idx - 1] == b'\r' {
+                    (&rest[..idx - 1], &rest[idx - 1..=idx])
+                } else {
+                    (&rest[..idx], &rest[idx..=idx])
+                };
+                parts.push((line, term));
+                rest = &rest[idx + 1..];
+            }
+            None => {
+                parts.push((rest, ""));
+                rest = "";
+            }
+        }
+    }
+    parts
+}
+
+/// Rewrite `content` line by line, preserving every line's original terminator.
+///
+/// `edit` receives the 1-based line number and the line text (without its
+/// terminator) and returns `Some(replacement)` to change the line. A
+/// replacement containing `\n` is split into several lines, each new line
+/// terminated with the file's dominant ending.
+pub fn rewrite_lines<F>(content: &str, mut edit: F) -> String
+where
+    F: FnMut(usize, &str) -> Option<String>,
+{
+    let new_line = detect_line_ending(content).style().as_str();
+    let mut out = String::with_capacity(content.len());
+
+    for (idx, (line, term)) in split_terminated(content).into_iter().enumerate() {
+        match edit(idx + 1, line) {
+            Some(replacement) => {
+                let replacement = replacement.replace("\r\n", "\n");
+                out.push_str(&replacement.replace('\n', new_line));
+            }
+            None => out.push_str(line),
+        }
+        out.push_str(term);
+    }
+
+    out
+}
+
+/// Apply [`rewrite_lines`] to the file at `path`, writing it back only when
+/// the content changed. Returns whether the file was modified.
+pub fn rewrite_file<F>(path: &Path, edit: F) -> Result<bool>
+where
+    F: FnMut(usize, &str) -> Option<String>,
+{
+    let original = std::fs::read_to_string(path)
+        .with_context(|| format!("Failed to read {}", path.display()))?;
+    let updated = rewrite_lines(&original, edit);
+    if updated == original {
+        return Ok(false);
+    }
+    std::fs::write(path, updated).with_context(|| format!("Failed to write {}", path.display()))?;
+    Ok(true)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_detect_lf() {
+        assert_eq!(detect_line_ending("a\nb\n"), LineEnding::Lf);
+    }
+
+    #[test]
+    fn test_detect_crlf() {
+        assert_eq!(detect_line_ending("a\r\nb\r\n"), LineEnding::Crlf);
+    }
+
+    #[test]
+    fn test_detect_mixed_dominant_crlf() {
+        assert_eq!(
+            detect_line_ending("a\r\nb\r\nc\n"),
+            LineEnding::Mixed {
+                dominant: LineEndingStyle::Crlf
+            }
+        );
+    }
+
+    #[test]
+    fn test_detect_mixed_tie_prefers_lf() {
+        assert_eq!(
+            detect_line_ending("a\r\nb\n"),
+            LineEnding::Mixed {
+                dominant: LineEndingStyle::Lf
+            }
+        );
+    }
+
+    #[test]
+    fn test_detect_no_newline_is_lf() {
+        assert_eq!(detect_line_ending("single line"), LineEnding::Lf);
+        assert_eq!(detect_line_ending(""), LineEnding::Lf);
+    }
+
+    #[test]
+    fn test_rewrite_lines_no_edit_is_identity() {
+        let content = "a\r\nb\nc";
+        assert_eq!(rewrite_lines(content, |_, _| None), content);
+    }
+
+    #[test]
+    fn test_rewrite_lines_preserves_crlf() {
+        let content = "// TODO: one\r\nfn main() {}\r\n";
+        let out = rewrite_lines(content, |n, line| {
+            (n == 1).then(|| line.replace("TODO:", "TODO(alice):"))
+        });
+        assert_eq!(out, "// TODO(alice): one\r\nfn main() {}\r\n");
+    }
+
+    #[test]
+    fn test_rewrite_lines_preserves_mixed_per_line() {
+        let content = "a\r\nb\nc\r\n";
+        let out = rewrite_lines(content, |n, _| (n == 2).then(|| "B".to_string()));
+        assert_eq!(out, "a\r\nB\nc\r\n");
+    }
+
+    #[test]
+    fn test_rewrite_lines_preserves_missing_trailing_newline() {
+        let out = rewrite_lines("a\r\nb", |n, _| (n == 2).then(|| "B".to_string()));
+        assert_eq!(out, "a\r\nB");
+    }
+
+    #[test]
+    fn test_rewrite_lines_inserted_lines_use_dominant_ending() {
+        let out = rewrite_lines("a\r\nb\r\n", |n, _| (n == 1).then(|| "a\nx".to_string()));
+        assert_eq!(out, "a\r\nx\r\nb\r\n");
+    }
+
+    #[test]
+    fn test_rewrite_file_preserves_crlf_on_disk() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("win.rs");
+        std::fs::write(&path, "// TODO: fix\r\nlet x = 1;\r\n").unwrap();
+
+        let changed = rewrite_file(&path, |n, line| {
+            (n == 1).then(|| line.replace("fix", "fix it"))
+        })
+        .unwrap();
+
+        assert!(changed);
+        let written = std::fs::read(&path).unwrap();
+        assert_eq!(written, b"// TODO: fix it\r\nlet x = 1;\r\n");
+    }
+
+    #[test]
+    fn test_rewrite_file_unchanged_returns_false() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("a.rs");
+        std::fs::write(&path, "// TODO: keep\r\n").unwrap();
+
+        let changed = rewrite_file(&path, |_, line| Some(line.to_string())).unwrap();
+        assert!(!changed);
+    }
+}
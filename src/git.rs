@@ -1,6 +1,7 @@
 use anyhow::{Context, Result};
+use std::io::{BufRead, BufReader, Read, Write};
 use std::path::Path;
-use std::process::Command;
+use std::process::{Command, Stdio};
 
 pub fn git_command(args: &[&str], cwd: &Path) -> Result<String> {
     let output = Command::new("git")
@@ -20,6 +21,53 @@ pub fn git_command(args: &[&str], cwd: &Path) -> Result<String> {
     Ok(stdout)
 }
 
+/// Read every object in `oids` through a single `git cat-file --batch`
+/// process, calling `f` with each object's index in `oids` and its contents.
+/// Objects git reports as missing are skipped.
+pub fn cat_file_batch(oids: &[&str], cwd: &Path, mut f: impl FnMut(usize, &[u8])) -> Result<()> {
+    let mut child = Command::new("git")
+        .args(["cat-file", "--batch"])
+        .current_dir(cwd)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::null())
+        .spawn()
+        .context("Failed to execute git cat-file --batch")?;
+
+    // Feed requests from another thread so a full stdout pipe can't block us
+    let mut stdin = child.stdin.take().expect("stdin is piped");
+    let requests: String = oids.iter().map(|oid| format!("{}\n", oid)).collect();
+    let writer = std::thread::spawn(move || stdin.write_all(requests.as_bytes()));
+
+    let mut stdout = BufReader::new(child.stdout.take().expect("stdout is piped"));
+    let mut header = String::new();
+    let mut content = Vec::new();
+    for index in 0..oids.len() {
+        header.clear();
+        if stdout.read_line(&mut header)? == 0 {
+            break;
+        }
+        // `<oid> <type> <size>`, or `<oid> missing`
+        let Some(size) = header
+            .split_whitespace()
+            .nth(2)
+            .and_then(|s| s.parse::<usize>().ok())
+        else {
+            continue;
+        };
+        // The contents are followed by a newline
+        content.resize(size + 1, 0);
+        stdout.read_exact(&mut content)?;
+        f(index, &content[..size]);
+    }
+    drop(stdout);
+
+    let _ = writer.join();
+    let status = child.wait().context("Failed to wait for git cat-file")?;
+    anyhow::ensure!(status.success(), "git cat-file --batch failed");
+    Ok(())
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -59,6 +107,25 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_cat_file_batch_reads_blobs_and_skips_missing() {
+        let dir = TempDir::new().unwrap();
+        git_command(&["init", "-q"], dir.path()).unwrap();
+        std::fs::write(dir.path().join("a.txt"), "alpha\n").unwrap();
+        std::fs::write(dir.path().join("b.txt"), "").unwrap();
+        git_command(&["add", "."], dir.path()).unwrap();
+        let a = git_command(&["rev-parse", ":a.txt"], dir.path()).unwrap();
+        let b = git_command(&["rev-parse", ":b.txt"], dir.path()).unwrap();
+        let missing = "0123456789012345678901234567890123456789";
+
+        let mut blobs = Vec::new();
+        cat_file_batch(&[a.trim(), missing, b.trim()], dir.path(), |i, bytes| {
+            blobs.push((i, bytes.to_vec()))
+        })
+        .unwrap();
+        assert_eq!(blobs, vec![(0, b"alpha\n".to_vec()), (2, Vec::new())]);
+    }
+
     #[test]
     fn test_git_command_returns_stdout() {
         let result = git_command(&["--version"], Path::new("."));
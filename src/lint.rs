@@ -3,10 +3,23 @@ use std::path::Path;
 
 use regex::Regex;
 
+use anyhow::Result;
+
 use crate::config::Config;
-use crate::model::{LintResult, LintViolation, ScanResult, TodoItem};
+use crate::eol;
+use crate::model::{LintResult, LintViolation, ScanResult, Severity, TodoItem};
 use crate::scanner;
 
+/// Rule names reported by `run_lint`, as accepted by `lint.severity`.
+pub const RULES: &[&str] = &[
+    "no_bare_tags",
+    "max_message_length",
+    "require_author",
+    "require_issue_ref",
+    "uppercase_tag",
+    "require_colon",
+];
+
 pub struct LintOverrides {
     pub no_bare_tags: bool,
     pub max_message_length: Option<usize>,
@@ -65,16 +78,25 @@ pub fn run_lint(
         check_raw_text_rules(scan, config, root, &resolved, &mut violations);
     }
 
+    for violation in &mut violations {
+        if let Some(&severity) = config.lint.severity.get(&violation.rule) {
+            violation.severity = severity;
+        }
+    }
+
     // Sort by file, then line
     violations.sort_by(|a, b| a.file.cmp(&b.file).then(a.line.cmp(&b.line)));
 
     let violation_count = violations.len();
-    LintResult {
-        passed: violations.is_empty(),
+    let mut result = LintResult {
+        passed: true,
         total_items: scan.items.len(),
         violation_count,
         violations,
-    }
+    };
+    // Warnings and notices pass unless `lint.exit_codes` makes them fail
+    result.passed = exit_code(&result, config) == 0;
+    result
 }
 
 fn check_metadata_rules(
@@ -86,6 +108,7 @@ fn check_metadata_rules(
     if resolved.no_bare_tags && item.message.trim().is_empty() {
         violations.push(LintViolation {
             rule: "no_bare_tags".to_string(),
+            severity: Severity::Error,
             message: format!("Empty {} message", item.tag),
             file: item.file.clone(),
             line: item.line,
@@ -98,6 +121,7 @@ fn check_metadata_rules(
         if item.message.len() > max {
             violations.push(LintViolation {
                 rule: "max_message_length".to_string(),
+                severity: Severity::Error,
                 message: format!(
                     "Message length ({}) exceeds maximum ({})",
                     item.message.len(),
@@ -121,6 +145,7 @@ fn check_metadata_rules(
         {
             violations.push(LintViolation {
                 rule: "require_author".to_string(),
+                severity: Severity::Error,
                 message: format!("Missing author for {} comment", item.tag),
                 file: item.file.clone(),
                 line: item.line,
@@ -140,6 +165,7 @@ fn check_metadata_rules(
         {
             violations.push(LintViolation {
                 rule: "require_issue_ref".to_string(),
+                severity: Severity::Error,
                 message: format!("Missing issue reference for {} comment", item.tag),
                 file: item.file.clone(),
                 line: item.line,
@@ -163,7 +189,7 @@ fn check_raw_text_rules(
     }
 
     // Build regex for raw-text analysis
-    let tags = config.tags.join("|");
+    let tags = config.active_tags().join("|");
     let raw_re = Regex::new(&format!(r"(?i)\b({})(?:\([^)]*\))?(:)?", tags))
         .expect("invalid raw lint regex");
 
@@ -196,6 +222,7 @@ fn check_raw_text_rules(
                     if raw_tag != expected {
                         violations.push(LintViolation {
                             rule: "uppercase_tag".to_string(),
+                            severity: Severity::Error,
                             message: format!(
                                 "Tag '{}' should be uppercase '{}'",
                                 raw_tag, expected
@@ -211,10 +238,11 @@ fn check_raw_text_rules(
                 if resolved.require_colon && caps.get(2).is_none() {
                     violations.push(LintViolation {
                         rule: "require_colon".to_string(),
+                        severity: Severity::Error,
                         message: format!("Missing colon after {} tag", item.tag),
                         file: item.file.clone(),
                         line: item.line,
-                        suggestion: Some(format!("{}: <message>", item.tag)),
+                        suggestion: Some(colon_suggestion(&caps[0], &item.message)),
                     });
                 }
 
@@ -224,6 +252,124 @@ fn check_raw_text_rules(
     }
 }
 
+/// Rewrite a colon-less tag (including any `(author)`) as it should read.
+fn colon_suggestion(raw_tag: &str, message: &str) -> String {
+    if message.is_empty() {
+        format!("{}: <message>", raw_tag)
+    } else {
+        format!("{}: {}", raw_tag, message)
+    }
+}
+
+/// Exit code for `result`: the `lint.exit_codes` entry of its most severe
+/// violation, or 0 when there are none.
+pub fn exit_code(result: &LintResult, config: &Config) -> i32 {
+    result
+        .violations
+        .iter()
+        .map(|v| v.severity)
+        .reduce(Severity::escalate)
+        .map_or(0, |worst| config.lint.exit_codes.code_for(worst))
+}
+
+/// Replace the generic `require_author` suggestion with a concrete one naming `author`.
+pub fn suggest_author(result: &mut LintResult, author: &str) {
+    for v in result
+        .violations
+        .iter_mut()
+        .filter(|v| v.rule == "require_author")
+    {
+        if let Some(ref mut suggestion) = v.suggestion {
+            *suggestion = suggestion.replace("(author)", &format!("(@{})", author));
+        }
+    }
+}
+
+/// Insert `(@author)` directly after the first in-comment tag on `line`.
+///
+/// An existing deadline- or labels-only parenthesis keeps its content after
+/// the author, e.g. `(@author, <deadline>)`.
+/// Returns `None` when the line already names an author or has no tag,
+/// which keeps the fix idempotent.
+fn insert_author(line: &str, tag_re: &Regex, author: &str) -> Option<String> {
+    let caps = tag_re
+        .captures_iter(line)
+        .find(|c| scanner::is_in_comment(line, c.get(1).unwrap().start()))?;
+    let tag = caps.get(1).unwrap();
+
+    match caps.get(2) {
+        Some(paren) => {
+            let inner = caps.get(3).map(|m| m.as_str()).unwrap_or("");
+            let (rest, _) = scanner::split_labels(inner);
+            let (existing_author, _) = scanner::parse_paren_content(rest);
+            if existing_author.is_some() {
+                return None;
+            }
+            let content = if inner.trim().is_empty() {
+                format!("(@{})", author)
+            } else {
+                format!("(@{}, {})", author, inner.trim())
+            };
+            Some(format!(
+                "{}{}{}",
+                &line[..paren.start()],
+                content,
+                &line[paren.end()..]
+            ))
+        }
+        None => Some(format!(
+            "{}(@{}){}",
+            &line[..tag.end()],
+            author,
+            &line[tag.end()..]
+        )),
+    }
+}
+
+/// Apply the `require_author` autofix in place for every matching violation.
+///
+/// Files are rewritten through [`eol::rewrite_file`] so line endings survive.
+/// Returns the number of lines changed.
+pub fn fix_missing_author(
+    result: &LintResult,
+    config: &Config,
+    root: &Path,
+    author: &str,
+) -> Result<usize> {
+    let tags = config
+        .active_tags()
+        .into_iter()
+        .map(regex::escape)
+        .collect::<Vec<_>>()
+        .join("|");
+    let tag_re = Regex::new(&format!(r"(?i)\b({})\b(\(([^)]*)\))?", tags))?;
+
+    let mut file_lines: HashMap<&str, Vec<usize>> = HashMap::new();
+    for v in result
+        .violations
+        .iter()
+        .filter(|v| v.rule == "require_author")
+    {
+        file_lines.entry(v.file.as_str()).or_default().push(v.line);
+    }
+
+    let mut fixed = 0;
+    for (file, lines) in &file_lines {
+        eol::rewrite_file(&root.join(file), |n, line| {
+            if !lines.contains(&n) {
+                return None;
+            }
+            let updated = insert_author(line, &tag_re, author);
+            if updated.is_some() {
+                fixed += 1;
+            }
+            updated
+        })?;
+    }
+
+    Ok(fixed)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -246,7 +392,11 @@ mod tests {
         let scan = ScanResult {
             items: vec![make_item("a.rs", 1, Tag::Todo, "")],
             files_scanned: 1,
+            files_with_items: 1,
+            bytes_scanned: 0,
             ignored_items: vec![],
+            truncated: false,
+            total_found: None,
         };
         let config = Config::default();
         let overrides = default_overrides();
@@ -261,7 +411,11 @@ mod tests {
         let scan = ScanResult {
             items: vec![make_item("a.rs", 1, Tag::Todo, "real message")],
             files_scanned: 1,
+            files_with_items: 1,
+            bytes_scanned: 0,
             ignored_items: vec![],
+            truncated: false,
+            total_found: None,
         };
         let mut config = Config::default();
         config.lint.uppercase_tag = Some(false);
@@ -276,7 +430,11 @@ mod tests {
         let scan = ScanResult {
             items: vec![make_item("a.rs", 1, Tag::Todo, "this is a long message")],
             files_scanned: 1,
+            files_with_items: 1,
+            bytes_scanned: 0,
             ignored_items: vec![],
+            truncated: false,
+            total_found: None,
         };
         let mut config = Config::default();
         config.lint.no_bare_tags = Some(false);
@@ -296,7 +454,11 @@ mod tests {
         let scan = ScanResult {
             items: vec![make_item("a.rs", 1, Tag::Todo, "no author")],
             files_scanned: 1,
+            files_with_items: 1,
+            bytes_scanned: 0,
             ignored_items: vec![],
+            truncated: false,
+            total_found: None,
         };
         let mut config = Config::default();
         config.lint.no_bare_tags = Some(false);
@@ -318,7 +480,11 @@ mod tests {
         let scan = ScanResult {
             items: vec![item],
             files_scanned: 1,
+            files_with_items: 1,
+            bytes_scanned: 0,
             ignored_items: vec![],
+            truncated: false,
+            total_found: None,
         };
         let mut config = Config::default();
         config.lint.no_bare_tags = Some(false);
@@ -337,7 +503,11 @@ mod tests {
         let scan = ScanResult {
             items: vec![make_item("a.rs", 1, Tag::Bug, "no issue ref")],
             files_scanned: 1,
+            files_with_items: 1,
+            bytes_scanned: 0,
             ignored_items: vec![],
+            truncated: false,
+            total_found: None,
         };
         let mut config = Config::default();
         config.lint.no_bare_tags = Some(false);
@@ -359,7 +529,11 @@ mod tests {
         let scan = ScanResult {
             items: vec![item],
             files_scanned: 1,
+            files_with_items: 1,
+            bytes_scanned: 0,
             ignored_items: vec![],
+            truncated: false,
+            total_found: None,
         };
         let mut config = Config::default();
         config.lint.no_bare_tags = Some(false);
@@ -378,7 +552,11 @@ mod tests {
         let scan = ScanResult {
             items: vec![make_item("a.rs", 1, Tag::Note, "just a note")],
             files_scanned: 1,
+            files_with_items: 1,
+            bytes_scanned: 0,
             ignored_items: vec![],
+            truncated: false,
+            total_found: None,
         };
         let mut config = Config::default();
         config.lint.no_bare_tags = Some(false);
@@ -401,7 +579,11 @@ mod tests {
         let scan = ScanResult {
             items: vec![make_item("test.rs", 1, Tag::Todo, "lowercase tag")],
             files_scanned: 1,
+            files_with_items: 1,
+            bytes_scanned: 0,
             ignored_items: vec![],
+            truncated: false,
+            total_found: None,
         };
         let mut config = Config::default();
         config.lint.no_bare_tags = Some(false);
@@ -424,7 +606,11 @@ mod tests {
         let scan = ScanResult {
             items: vec![make_item("test.rs", 1, Tag::Todo, "uppercase tag")],
             files_scanned: 1,
+            files_with_items: 1,
+            bytes_scanned: 0,
             ignored_items: vec![],
+            truncated: false,
+            total_found: None,
         };
         let mut config = Config::default();
         config.lint.no_bare_tags = Some(false);
@@ -446,7 +632,11 @@ mod tests {
         let scan = ScanResult {
             items: vec![make_item("test.rs", 1, Tag::Todo, "fix without colon")],
             files_scanned: 1,
+            files_with_items: 1,
+            bytes_scanned: 0,
             ignored_items: vec![],
+            truncated: false,
+            total_found: None,
         };
         let mut config = Config::default();
         config.lint.no_bare_tags = Some(false);
@@ -460,6 +650,46 @@ mod tests {
         assert!(result.violations.iter().any(|v| v.rule == "require_colon"));
     }
 
+    #[test]
+    fn test_require_colon_suggestion_keeps_author_and_message() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(
+            dir.path().join("test.rs"),
+            "// TODO(alice) fix this later\n",
+        )
+        .unwrap();
+
+        let mut item = make_item("test.rs", 1, Tag::Todo, "fix this later");
+        item.author = Some("alice".to_string());
+        let scan = ScanResult {
+            items: vec![item],
+            files_scanned: 1,
+            files_with_items: 1,
+            bytes_scanned: 0,
+            ignored_items: vec![],
+            truncated: false,
+            total_found: None,
+        };
+        let mut config = Config::default();
+        config.lint.no_bare_tags = Some(false);
+        let result = run_lint(&scan, &config, &default_overrides(), dir.path());
+
+        let violation = result
+            .violations
+            .iter()
+            .find(|v| v.rule == "require_colon")
+            .unwrap();
+        assert_eq!(
+            violation.suggestion.as_deref(),
+            Some("TODO(alice): fix this later")
+        );
+    }
+
+    #[test]
+    fn test_colon_suggestion_for_empty_message() {
+        assert_eq!(colon_suggestion("FIXME", ""), "FIXME: <message>");
+    }
+
     #[test]
     fn test_require_colon_present() {
         let dir = tempfile::tempdir().unwrap();
@@ -469,7 +699,11 @@ mod tests {
         let scan = ScanResult {
             items: vec![make_item("test.rs", 1, Tag::Todo, "fix with colon")],
             files_scanned: 1,
+            files_with_items: 1,
+            bytes_scanned: 0,
             ignored_items: vec![],
+            truncated: false,
+            total_found: None,
         };
         let mut config = Config::default();
         config.lint.no_bare_tags = Some(false);
@@ -487,7 +721,11 @@ mod tests {
         let scan = ScanResult {
             items: vec![make_item("a.rs", 1, Tag::Todo, "valid message")],
             files_scanned: 1,
+            files_with_items: 1,
+            bytes_scanned: 0,
             ignored_items: vec![],
+            truncated: false,
+            total_found: None,
         };
         let mut config = Config::default();
         // Disable all default-true rules via config
@@ -504,7 +742,11 @@ mod tests {
         let scan = ScanResult {
             items: vec![make_item("a.rs", 1, Tag::Todo, "")],
             files_scanned: 1,
+            files_with_items: 1,
+            bytes_scanned: 0,
             ignored_items: vec![],
+            truncated: false,
+            total_found: None,
         };
         let mut config = Config::default();
         config.lint.no_bare_tags = Some(false); // Config disables
@@ -527,7 +769,11 @@ mod tests {
                 make_item("a.rs", 2, Tag::Bug, "no issue ref"),
             ],
             files_scanned: 1,
+            files_with_items: 1,
+            bytes_scanned: 0,
             ignored_items: vec![],
+            truncated: false,
+            total_found: None,
         };
         let mut config = Config::default();
         config.lint.uppercase_tag = Some(false);
@@ -546,7 +792,11 @@ mod tests {
         let scan = ScanResult {
             items: vec![make_item("a.rs", 1, Tag::Fixme, "missing author")],
             files_scanned: 1,
+            files_with_items: 1,
+            bytes_scanned: 0,
             ignored_items: vec![],
+            truncated: false,
+            total_found: None,
         };
         let mut config = Config::default();
         config.lint.no_bare_tags = Some(false);
@@ -564,7 +814,11 @@ mod tests {
         let scan = ScanResult {
             items: vec![make_item("a.rs", 1, Tag::Todo, "no ref")],
             files_scanned: 1,
+            files_with_items: 1,
+            bytes_scanned: 0,
             ignored_items: vec![],
+            truncated: false,
+            total_found: None,
         };
         let mut config = Config::default();
         config.lint.no_bare_tags = Some(false);
@@ -587,7 +841,11 @@ mod tests {
                 "a fairly long message here",
             )],
             files_scanned: 1,
+            files_with_items: 1,
+            bytes_scanned: 0,
             ignored_items: vec![],
+            truncated: false,
+            total_found: None,
         };
         let mut config = Config::default();
         config.lint.no_bare_tags = Some(false);
@@ -605,7 +863,11 @@ mod tests {
         let scan = ScanResult {
             items: vec![make_item("a.rs", 1, Tag::Todo, "12345")],
             files_scanned: 1,
+            files_with_items: 1,
+            bytes_scanned: 0,
             ignored_items: vec![],
+            truncated: false,
+            total_found: None,
         };
         let mut config = Config::default();
         config.lint.no_bare_tags = Some(false);
@@ -624,7 +886,11 @@ mod tests {
         let scan = ScanResult {
             items: vec![make_item("a.rs", 1, Tag::Todo, "no author")],
             files_scanned: 1,
+            files_with_items: 1,
+            bytes_scanned: 0,
             ignored_items: vec![],
+            truncated: false,
+            total_found: None,
         };
         let mut config = Config::default();
         config.lint.no_bare_tags = Some(false);
@@ -645,7 +911,11 @@ mod tests {
         let scan = ScanResult {
             items: vec![make_item("a.rs", 1, Tag::Todo, "   ")],
             files_scanned: 1,
+            files_with_items: 1,
+            bytes_scanned: 0,
             ignored_items: vec![],
+            truncated: false,
+            total_found: None,
         };
         let mut config = Config::default();
         config.lint.uppercase_tag = Some(false);
@@ -668,7 +938,11 @@ mod tests {
                 make_item("a.rs", 2, Tag::Fixme, ""),
             ],
             files_scanned: 2,
+            files_with_items: 2,
+            bytes_scanned: 0,
             ignored_items: vec![],
+            truncated: false,
+            total_found: None,
         };
         let mut config = Config::default();
         config.lint.uppercase_tag = Some(false);
@@ -693,7 +967,11 @@ mod tests {
         let scan = ScanResult {
             items: vec![],
             files_scanned: 0,
+            files_with_items: 0,
+            bytes_scanned: 0,
             ignored_items: vec![],
+            truncated: false,
+            total_found: None,
         };
         let config = Config::default();
         let overrides = LintOverrides {
@@ -714,7 +992,11 @@ mod tests {
         let scan = ScanResult {
             items: vec![make_item("nonexistent.rs", 1, Tag::Todo, "msg")],
             files_scanned: 1,
+            files_with_items: 1,
+            bytes_scanned: 0,
             ignored_items: vec![],
+            truncated: false,
+            total_found: None,
         };
         let mut config = Config::default();
         config.lint.no_bare_tags = Some(false);
@@ -733,7 +1015,11 @@ mod tests {
         let scan = ScanResult {
             items: vec![make_item("a.rs", 1, Tag::Fixme, "")],
             files_scanned: 1,
+            files_with_items: 1,
+            bytes_scanned: 0,
             ignored_items: vec![],
+            truncated: false,
+            total_found: None,
         };
         let mut config = Config::default();
         config.lint.uppercase_tag = Some(false);
@@ -747,4 +1033,147 @@ mod tests {
         assert!(suggestion.contains("FIXME"));
         assert!(suggestion.contains("<description>"));
     }
+
+    fn author_tag_re() -> Regex {
+        Regex::new(r"(?i)\b(TODO|FIXME)\b(\(([^)]*)\))?").unwrap()
+    }
+
+    #[test]
+    fn test_insert_author_after_tag() {
+        let out = insert_author("    // TODO: fix this", &author_tag_re(), "alice");
+        assert_eq!(out.as_deref(), Some("    // TODO(@alice): fix this"));
+    }
+
+    #[test]
+    fn test_insert_author_keeps_deadline() {
+        let out = insert_author("// TODO(2025-06-01): ship", &author_tag_re(), "alice");
+        assert_eq!(out.as_deref(), Some("// TODO(@alice, 2025-06-01): ship"));
+    }
+
+    #[test]
+    fn test_insert_author_keeps_labels() {
+        let out = insert_author("// TODO(labels=ui): ship", &author_tag_re(), "alice");
+        assert_eq!(out.as_deref(), Some("// TODO(@alice, labels=ui): ship"));
+    }
+
+    #[test]
+    fn test_insert_author_skips_existing_author() {
+        assert!(insert_author("// TODO(bob): ship", &author_tag_re(), "alice").is_none());
+    }
+
+    #[test]
+    fn test_insert_author_ignores_tag_outside_comment() {
+        let out = insert_author("let todo = 1; // TODO: x", &author_tag_re(), "alice");
+        assert_eq!(out.as_deref(), Some("let todo = 1; // TODO(@alice): x"));
+    }
+
+    #[test]
+    fn test_fix_missing_author_rewrites_file() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(dir.path().join("a.rs"), "// TODO: one\n// TODO(bob): two\n").unwrap();
+
+        let mut authored = make_item("a.rs", 2, Tag::Todo, "two");
+        authored.author = Some("bob".to_string());
+        let scan = ScanResult {
+            items: vec![make_item("a.rs", 1, Tag::Todo, "one"), authored],
+            files_scanned: 1,
+            files_with_items: 1,
+            bytes_scanned: 0,
+            ignored_items: vec![],
+            truncated: false,
+            total_found: None,
+        };
+        let mut config = Config::default();
+        config.lint.uppercase_tag = Some(false);
+        config.lint.require_colon = Some(false);
+        let overrides = LintOverrides {
+            require_author: vec!["TODO".to_string()],
+            ..default_overrides()
+        };
+        let result = run_lint(&scan, &config, &overrides, dir.path());
+
+        let fixed = fix_missing_author(&result, &config, dir.path(), "alice").unwrap();
+        assert_eq!(fixed, 1);
+        let content = std::fs::read_to_string(dir.path().join("a.rs")).unwrap();
+        assert_eq!(content, "// TODO(@alice): one\n// TODO(bob): two\n");
+    }
+
+    #[test]
+    fn test_suggest_author_replaces_placeholder() {
+        let mut result = LintResult {
+            passed: false,
+            total_items: 1,
+            violation_count: 1,
+            violations: vec![LintViolation {
+                rule: "require_author".to_string(),
+                severity: Severity::Error,
+                message: "Missing author for TODO comment".to_string(),
+                file: "a.rs".to_string(),
+                line: 1,
+                suggestion: Some("TODO(author): <message>".to_string()),
+            }],
+        };
+        suggest_author(&mut result, "alice");
+        assert_eq!(
+            result.violations[0].suggestion.as_deref(),
+            Some("TODO(@alice): <message>")
+        );
+    }
+
+    #[test]
+    fn test_exit_code_follows_most_severe_violation() {
+        let scan = ScanResult {
+            items: vec![
+                make_item("a.rs", 1, Tag::Todo, ""),
+                make_item("a.rs", 2, Tag::Todo, "a message that is far too long"),
+            ],
+            files_scanned: 1,
+            files_with_items: 1,
+            bytes_scanned: 0,
+            ignored_items: vec![],
+            truncated: false,
+            total_found: None,
+        };
+        let mut config = Config::default();
+        config.lint.uppercase_tag = Some(false);
+        config.lint.require_colon = Some(false);
+        config.lint.max_message_length = Some(10);
+        config
+            .lint
+            .severity
+            .insert("max_message_length".to_string(), Severity::Warning);
+        config.lint.exit_codes.warning = Some(3);
+        let root = Path::new(".");
+
+        // Mixed run: the fatal no_bare_tags wins
+        let result = run_lint(&scan, &config, &default_overrides(), root);
+        assert_eq!(result.violations[0].severity, Severity::Error);
+        assert_eq!(result.violations[1].severity, Severity::Warning);
+        assert_eq!(exit_code(&result, &config), 1);
+
+        // Only the advisory rule left
+        config.lint.no_bare_tags = Some(false);
+        let result = run_lint(&scan, &config, &default_overrides(), root);
+        assert_eq!(result.violation_count, 1);
+        assert_eq!(exit_code(&result, &config), 3);
+        assert!(!result.passed);
+
+        // `passed` follows the exit code, not the violation count
+        config.lint.exit_codes.warning = None;
+        let result = run_lint(&scan, &config, &default_overrides(), root);
+        assert_eq!(exit_code(&result, &config), 0);
+        assert_eq!(result.violation_count, 1);
+        assert!(result.passed);
+    }
+
+    #[test]
+    fn test_exit_code_zero_without_violations() {
+        let result = LintResult {
+            passed: true,
+            total_items: 0,
+            violation_count: 0,
+            violations: vec![],
+        };
+        assert_eq!(exit_code(&result, &Config::default()), 0);
+    }
 }
@@ -11,16 +11,22 @@ mod context;
 mod date_utils;
 mod deadline;
 mod diff;
+mod eol;
 mod git;
 mod init;
 mod lint;
 mod model;
+#[cfg(feature = "notebooks")]
+mod notebook;
 mod output;
+mod profile;
 mod relate;
 mod report;
 mod scanner;
 mod search;
+mod severity;
 mod stats;
+mod tags;
 mod tasks;
 #[cfg(test)]
 mod test_helpers;
@@ -33,25 +39,103 @@ use anyhow::{Context, Result};
 use clap::Parser;
 
 use check::CheckOverrides;
-use cli::{Cli, Command, WorkspaceAction};
+use cli::{Cli, Command, Format, PathStyle, WorkspaceAction};
 use cmd::*;
 use config::Config;
 use lint::LintOverrides;
+use output::OutputOptions;
+use profile::Profiler;
+use severity::SeverityOverrides;
 
 fn main() {
-    if let Err(e) = run() {
+    let cli = Cli::parse();
+    let profiler = Profiler::new(cli.profile);
+
+    let result = run(cli, profiler.clone());
+    profiler.report();
+
+    if let Err(e) = result {
         eprintln!("error: {:#}", e);
         process::exit(2);
     }
 }
 
-fn run() -> Result<()> {
-    let cli = Cli::parse();
+/// Reject formats that only one command can render.
+fn check_format_supported(format: &Format, command: &Command) -> Result<()> {
+    match format {
+        Format::Ics if !matches!(command, Command::Deadlines { .. }) => {
+            anyhow::bail!("--format ics is only supported by the deadlines command")
+        }
+        Format::CsvWide if !matches!(command, Command::List { .. }) => {
+            anyhow::bail!("--format csv-wide is only supported by the list command")
+        }
+        Format::CodeClimate if !matches!(command, Command::List { .. }) => {
+            anyhow::bail!("--format codeclimate is only supported by the list command")
+        }
+        Format::Clang if !matches!(command, Command::List { .. }) => {
+            anyhow::bail!("--format clang is only supported by the list command")
+        }
+        Format::Html if !matches!(command, Command::List { .. }) => {
+            anyhow::bail!("--format html is only supported by the list command")
+        }
+        Format::Prometheus if !matches!(command, Command::Stats { .. }) => {
+            anyhow::bail!("--format prometheus is only supported by the stats command")
+        }
+        Format::Junit if !matches!(command, Command::Diff { .. }) => {
+            anyhow::bail!("--format junit is only supported by the diff command")
+        }
+        Format::JunitByFile if !matches!(command, Command::Diff { .. }) => {
+            anyhow::bail!("--format junit-by-file is only supported by the diff command")
+        }
+        _ => Ok(()),
+    }
+}
+
+/// Name of a command that gates on or compares item sets, which must ignore
+/// `max_items`; `None` for commands that only display the items.
+fn uncapped_command(command: &Command) -> Option<&'static str> {
+    match command {
+        Command::Check { .. } => Some("check"),
+        Command::Lint { .. } => Some("lint"),
+        Command::Clean { .. } => Some("clean"),
+        Command::Diff { .. } => Some("diff"),
+        Command::Watch { .. } => Some("watch"),
+        Command::Brief { .. } => Some("brief"),
+        Command::Report { .. } => Some("report"),
+        Command::Bundle { .. } => Some("bundle"),
+        Command::Tasks { .. } => Some("tasks"),
+        Command::Workspace { .. } => Some("workspace"),
+        _ => None,
+    }
+}
 
+/// Name of a command that prints paths as scanned and so can't honour
+/// `--path-style`; `None` for commands that rewrite paths or print none.
+fn fixed_path_command(command: &Command) -> Option<&'static str> {
+    match command {
+        Command::Check { .. } => Some("check"),
+        Command::Lint { .. } => Some("lint"),
+        Command::Clean { .. } => Some("clean"),
+        Command::Tasks { .. } => Some("tasks"),
+        _ => None,
+    }
+}
+
+fn run(cli: Cli, profiler: Profiler) -> Result<()> {
     let root = match cli.root {
-        Some(p) => p,
+        Some(p) => {
+            anyhow::ensure!(p.is_dir(), "--root is not a directory: {}", p.display());
+            p
+        }
         None => std::env::current_dir().context("cannot determine current directory")?,
     };
+    check_format_supported(&cli.format, &cli.command)?;
+    if let Some(name) = fixed_path_command(&cli.command) {
+        anyhow::ensure!(
+            cli.path_style == PathStyle::Relative,
+            "--path-style is not supported by the {name} command"
+        );
+    }
 
     match cli.command {
         // Commands that don't need config
@@ -60,41 +144,134 @@ fn run() -> Result<()> {
 
         // Commands that need config
         command => {
-            let config = if let Some(ref config_path) = cli.config {
-                let content = std::fs::read_to_string(config_path)?;
-                toml::from_str(&content)?
-            } else {
-                Config::load(&root)?
+            let mut config = profiler.time("config", || -> Result<Config> {
+                if cli.no_config {
+                    Ok(Config::default())
+                } else if cli.config.is_empty() {
+                    Config::load(&root)
+                } else {
+                    Config::load_layered(&cli.config)
+                }
+            })?;
+            config.validate()?;
+            if cli.no_ignore {
+                config.respect_gitignore = Some(false);
+            }
+            if cli.no_default_excludes && config.exclude_dirs == config::DEFAULT_EXCLUDE_DIRS {
+                config.exclude_dirs.clear();
+            }
+            if cli.follow_symlinks {
+                config.follow_symlinks = Some(true);
+            }
+            if cli.scan_filenames {
+                config.scan_filenames = Some(true);
+            }
+            if cli.scan_large {
+                config.scan_large = Some(true);
+            }
+            if cli.permissive_unknown.is_some() {
+                config.permissive_unknown = cli.permissive_unknown;
+            }
+            if let Some(name) = uncapped_command(&command) {
+                // Gates and comparisons must see every item; a cap would let
+                // them pass on a truncated scan or report phantom removals
+                anyhow::ensure!(
+                    cli.max_items.is_none(),
+                    "--max-items is not supported by the {name} command"
+                );
+                config.max_items = None;
+            } else if cli.max_items.is_some() {
+                config.max_items = cli.max_items;
+            }
+            if cli.date_format.is_some() {
+                config.date_format = cli.date_format.clone();
+            }
+            if let Some(fmt) = &config.date_format {
+                deadline::check_format(fmt)?;
+            }
+            if let Some(template) = &cli.author_mention_format {
+                output::check_author_mention_format(template)?;
+            }
+            let out = OutputOptions {
+                severity: SeverityOverrides::from_config(&config)?.with_root(&root),
+                json_root_key: cli.json_root_key.clone(),
+                tag_labels: output::parse_tag_map(&config.tag_labels, "tag_labels")?,
+                sarif_baseline: cli
+                    .sarif_baseline
+                    .as_deref()
+                    .map(output::load_sarif_baseline)
+                    .transpose()?,
+                date_format: config.date_format.clone(),
+                author_mention: cli.author_mention_format.clone(),
+                github_actions_emoji: output::parse_tag_map(
+                    &config.github_actions_emoji,
+                    "github_actions_emoji",
+                )?,
+                trailing_newline: config.trailing_newline,
+                profiler,
+                ..OutputOptions::new(cli.format.clone())
             };
+            if cli.explain_config {
+                let sources = if cli.no_config {
+                    Vec::new()
+                } else {
+                    config::config_sources(&root, &cli.config)
+                };
+                output::print_config_explanation(&config.explain(sources), &out);
+                return Ok(());
+            }
+            if cli.show_pattern {
+                eprint!("{}", scanner::describe_patterns(&config)?);
+                return Ok(());
+            }
             let no_cache = cli.no_cache;
 
             match command {
                 Command::Init { .. } | Command::Completions { .. } => unreachable!(),
                 Command::List {
+                    roots,
                     tag,
                     sort,
                     group_by,
+                    stable_order,
                     priority,
                     author,
+                    label,
                     path,
                     limit,
                     context,
+                    summary,
+                    by_tag_section,
+                    badges,
+                    columns,
+                    repo_root_headers,
+                    staged,
                     package,
                 } => {
                     let opts = ListOptions {
+                        roots,
                         tag,
                         sort,
                         group_by,
+                        stable_order,
                         priority,
                         author,
+                        labels: label,
                         path,
                         limit,
                         context,
+                        summary,
+                        by_tag_section,
+                        badges,
+                        columns,
+                        repo_root_headers,
+                        staged,
                         show_ignored: cli.show_ignored,
                         detail: cli.detail.clone(),
+                        path_style: cli.path_style.clone(),
                     };
                     let scan_root = resolve_package_root(&root, &config, package.as_deref())?;
-                    cmd_list(&scan_root, &config, &cli.format, opts, no_cache)
+                    cmd_list(&scan_root, &config, &out, opts, no_cache)
                 }
                 Command::Blame {
                     sort,
@@ -103,6 +280,9 @@ fn run() -> Result<()> {
                     stale_threshold,
                     tag,
                     path,
+                    redact_emails,
+                    redact_authors,
+                    humanize_age,
                 } => {
                     let opts = BlameOptions {
                         sort,
@@ -111,39 +291,70 @@ fn run() -> Result<()> {
                         stale_threshold,
                         tag,
                         path,
+                        redact_emails,
+                        redact_authors,
+                        humanize_age,
+                        path_style: cli.path_style.clone(),
                     };
-                    cmd_blame(&root, &config, &cli.format, opts, no_cache)
+                    cmd_blame(&root, &config, &out, opts, no_cache)
                 }
                 Command::Search {
                     query,
                     exact,
+                    ignore_case,
                     context,
                     author,
                     tag,
                     path,
                     sort,
                     group_by,
+                    stable_order,
                 } => {
                     let opts = SearchOptions {
                         query,
                         exact,
+                        ignore_case,
                         context,
                         author,
                         tag,
                         path,
                         sort,
                         group_by,
+                        stable_order,
                         detail: cli.detail.clone(),
+                        path_style: cli.path_style.clone(),
                     };
-                    cmd_search(&root, &config, &cli.format, opts, no_cache)
+                    cmd_search(&root, &config, &out, opts, no_cache)
                 }
-                Command::Stats { since } => cmd_stats(&root, &config, &cli.format, since, no_cache),
-                Command::Brief { since, budget } => {
-                    cmd_brief(&root, &config, &cli.format, since, budget, no_cache)
+                Command::Stats {
+                    since,
+                    exclude_tests,
+                    by_dir,
+                    tree,
+                } => {
+                    let opts = StatsOptions {
+                        since,
+                        exclude_tests,
+                        by_dir,
+                        tree,
+                    };
+                    cmd_stats(&root, &config, &out, opts, no_cache)
                 }
+                Command::Tags => cmd_tags(&config, &out),
+                Command::Deadlines { tag } => {
+                    cmd_deadlines(&root, &config, &out, tag, &cli.path_style, no_cache)
+                }
+                Command::Brief {
+                    since,
+                    budget,
+                    brief_prefer,
+                } => cmd_brief(&root, &config, &out, since, budget, &brief_prefer, no_cache),
                 Command::Diff {
                     git_ref,
                     tag,
+                    symbols,
+                    no_color,
+                    no_fallback,
                     context,
                     package,
                 } => {
@@ -153,8 +364,12 @@ fn run() -> Result<()> {
                         tag,
                         context,
                         detail: cli.detail.clone(),
+                        path_style: cli.path_style.clone(),
+                        symbols,
+                        no_color,
+                        no_fallback,
                     };
-                    cmd_diff(&scan_root, &config, &cli.format, opts, no_cache)
+                    cmd_diff(&scan_root, &config, &out, opts, no_cache)
                 }
                 Command::Check {
                     max,
@@ -162,41 +377,94 @@ fn run() -> Result<()> {
                     max_new,
                     since,
                     expired,
+                    require_issue,
+                    max_age_days,
+                    exclude_tests,
+                    max_per_author,
+                    count_unassigned,
                     package,
                     workspace: ws_mode,
                 } => {
                     if ws_mode {
-                        cmd_workspace_check(&root, &config, &cli.format, no_cache)
+                        cmd_workspace_check(&root, &config, &out, no_cache)
                     } else {
-                        let overrides = CheckOverrides {
-                            max,
-                            block_tags,
-                            max_new,
-                            expired,
+                        let opts = CheckOptions {
+                            overrides: CheckOverrides {
+                                max,
+                                block_tags,
+                                max_new,
+                                expired,
+                                require_issue,
+                            },
+                            since,
+                            max_age_days,
+                            exclude_tests,
+                            max_per_author,
+                            count_unassigned,
                         };
                         let scan_root = resolve_package_root(&root, &config, package.as_deref())?;
-                        cmd_check(&scan_root, &config, &cli.format, overrides, since, no_cache)
+                        cmd_check(&scan_root, &config, &out, opts, no_cache)
                     }
                 }
-                Command::Context { location, context } => {
-                    cmd_context(&root, &config, &cli.format, &location, context, no_cache)
-                }
-                Command::Clean { check, since } => {
-                    cmd_clean(&root, &config, &cli.format, check, since, no_cache)
+                Command::Context {
+                    location,
+                    context,
+                    interactive,
+                } => cmd_context(
+                    &root,
+                    &config,
+                    &out,
+                    location.as_deref(),
+                    context,
+                    interactive,
+                    no_cache,
+                ),
+                Command::Clean {
+                    check,
+                    fail_on_stale,
+                    fail_on_duplicate,
+                    since,
+                    deadline_without_owner,
+                    stale_grace_days,
+                    dup_scope,
+                } => {
+                    if deadline_without_owner {
+                        config.clean.deadline_without_owner = Some(true);
+                    }
+                    if stale_grace_days.is_some() {
+                        config.clean.stale_grace_days = stale_grace_days;
+                    }
+                    if dup_scope.is_some() {
+                        config.clean.duplicate_scope = dup_scope;
+                    }
+                    let gate = clean::CleanGate {
+                        check,
+                        fail_on_stale,
+                        fail_on_duplicate,
+                    };
+                    cmd_clean(&root, &config, &out, gate, since, no_cache)
                 }
                 Command::Relate {
                     cluster,
                     r#for: for_item,
                     min_score,
                     proximity,
+                    max_cluster_size,
+                    max_clusters,
+                    explain,
+                    max_pairwise,
                 } => {
                     let opts = RelateOptions {
                         cluster,
                         for_item,
                         min_score,
                         proximity,
+                        max_cluster_size,
+                        max_clusters,
+                        explain,
+                        max_pairwise,
                     };
-                    cmd_relate(&root, &config, &cli.format, opts, no_cache)
+                    cmd_relate(&root, &config, &out, opts, no_cache)
                 }
                 Command::Lint {
                     no_bare_tags,
@@ -205,6 +473,8 @@ fn run() -> Result<()> {
                     require_issue_ref,
                     uppercase_tag,
                     require_colon,
+                    fix,
+                    author_from_git,
                 } => {
                     let overrides = LintOverrides {
                         no_bare_tags,
@@ -214,13 +484,40 @@ fn run() -> Result<()> {
                         uppercase_tag,
                         require_colon,
                     };
-                    cmd_lint(&root, &config, &cli.format, overrides, no_cache)
+                    let fix_opts = LintFixOptions {
+                        fix,
+                        author_from_git,
+                    };
+                    cmd_lint(&root, &config, &out, overrides, fix_opts, no_cache)
                 }
                 Command::Report {
                     output,
                     history,
+                    history_from,
+                    history_fast,
                     stale_threshold,
-                } => cmd_report(&root, &config, &output, history, stale_threshold, no_cache),
+                } => {
+                    let opts = ReportOptions {
+                        output_path: output,
+                        history_count: history,
+                        history_from,
+                        history_fast,
+                        stale_threshold,
+                    };
+                    cmd_report(&root, &config, &out, opts, no_cache)
+                }
+                Command::Bundle {
+                    out: out_dir,
+                    history,
+                    stale_threshold,
+                } => {
+                    let opts = BundleOptions {
+                        out_dir,
+                        history_count: history,
+                        stale_threshold,
+                    };
+                    cmd_bundle(&root, &config, &out, opts, no_cache)
+                }
                 Command::Tasks {
                     tag,
                     context,
@@ -241,15 +538,28 @@ fn run() -> Result<()> {
                         author,
                         path,
                     };
-                    cmd_tasks(&root, &config, &cli.format, opts, no_cache)
+                    cmd_tasks(&root, &config, &out, opts, no_cache)
                 }
-                Command::Watch { tag, max, debounce } => {
-                    watch::cmd_watch(&root, &config, &cli.format, &tag, max, debounce)
+                Command::Watch {
+                    tag,
+                    max,
+                    debounce,
+                    timestamps,
+                    once,
+                    timeout,
+                } => {
+                    let opts = watch::WatchOptions {
+                        tag,
+                        max,
+                        debounce_ms: debounce,
+                        timestamps,
+                        once,
+                        timeout_secs: timeout,
+                    };
+                    watch::cmd_watch(&root, &config, &out, opts)
                 }
                 Command::Workspace { action } => match action {
-                    WorkspaceAction::List => {
-                        cmd_workspace_list(&root, &config, &cli.format, no_cache)
-                    }
+                    WorkspaceAction::List => cmd_workspace_list(&root, &config, &out, no_cache),
                 },
             }
         }
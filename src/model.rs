@@ -1,4 +1,6 @@
+use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
 use std::fmt;
 use std::str::FromStr;
 
@@ -16,6 +18,16 @@ pub enum Tag {
 }
 
 impl Tag {
+    /// Every recognized tag, in declaration order.
+    pub const ALL: [Tag; 6] = [
+        Tag::Todo,
+        Tag::Fixme,
+        Tag::Hack,
+        Tag::Xxx,
+        Tag::Bug,
+        Tag::Note,
+    ];
+
     pub fn as_str(&self) -> &'static str {
         match self {
             Tag::Todo => "TODO",
@@ -61,7 +73,7 @@ impl fmt::Display for Tag {
     }
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize, JsonSchema)]
 #[serde(rename_all = "lowercase")]
 pub enum Priority {
     Normal,
@@ -91,23 +103,72 @@ impl Ord for Priority {
     }
 }
 
+/// Why an item landed in `ignored_items` instead of `items`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum IgnoreReason {
+    /// Suppressed by a `todo-scan:ignore` / `todo-scan:ignore-next-line` marker
+    Marker,
+    /// The tag sits inside a string literal rather than a comment
+    StringLiteral,
+    /// The item carries one of the configured `resolved_markers`
+    Resolved,
+}
+
+impl IgnoreReason {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            IgnoreReason::Marker => "marker",
+            IgnoreReason::StringLiteral => "string-literal",
+            IgnoreReason::Resolved => "resolved",
+        }
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct TodoItem {
     pub file: String,
     pub line: usize,
+    /// Byte offset of the tag from the start of the file (of the cell source
+    /// for notebooks; 0 for file-name items), so tools can patch it in place
+    #[serde(default)]
+    pub byte_offset: usize,
+    /// 1-based notebook cell holding the item (counting every cell); `line`
+    /// and `byte_offset` are then relative to the cell source
+    #[serde(default)]
+    pub cell: Option<usize>,
     pub tag: Tag,
     pub message: String,
     pub author: Option<String>,
     pub issue_ref: Option<String>,
     pub priority: Priority,
     pub deadline: Option<Deadline>,
+    /// Labels from a `labels=a,b` segment in the tag's parentheses.
+    #[serde(default)]
+    pub labels: Vec<String>,
+    /// Set on ignored items only. Not part of the item's own serialized form;
+    /// see [`ScanResult::ignored_items`] and the scan cache.
+    #[serde(skip)]
+    pub ignore_reason: Option<IgnoreReason>,
+    /// Severity resolved from `severity_overrides` while `file` was still
+    /// relative to the scan root, before `--path-style` rewrote it.
+    #[serde(skip)]
+    pub severity: Option<Severity>,
 }
 
 impl TodoItem {
     /// Matching key for diff comparison (excludes line number)
     pub fn match_key(&self) -> String {
         let normalized = self.message.trim().to_lowercase();
-        format!("{}:{}:{}", self.file, self.tag, normalized)
+        format!("{}:{}:{}", self.location(), self.tag, normalized)
+    }
+
+    /// The file for display, with a `#cellN` suffix for notebook items.
+    pub fn location(&self) -> String {
+        match self.cell {
+            Some(cell) => format!("{}#cell{}", self.file, cell),
+            None => self.file.clone(),
+        }
     }
 
     /// Stable, content-based identifier for this TODO item.
@@ -115,14 +176,55 @@ impl TodoItem {
     pub fn id(&self) -> String {
         self.match_key()
     }
+
+    /// Parent directory of the item's file, or `.` for top-level files.
+    pub fn dir(&self) -> String {
+        std::path::Path::new(&self.file)
+            .parent()
+            .map(|p| p.to_string_lossy().to_string())
+            .filter(|s| !s.is_empty())
+            .unwrap_or_else(|| ".".to_string())
+    }
 }
 
 #[derive(Debug, Serialize)]
 pub struct ScanResult {
     pub items: Vec<TodoItem>,
-    #[serde(skip_serializing_if = "Vec::is_empty")]
+    #[serde(
+        skip_serializing_if = "Vec::is_empty",
+        serialize_with = "serialize_ignored_items"
+    )]
     pub ignored_items: Vec<TodoItem>,
     pub files_scanned: usize,
+    /// Number of scanned files containing at least one (non-ignored) item.
+    pub files_with_items: usize,
+    /// Total size in bytes of the scanned files.
+    pub bytes_scanned: u64,
+    /// Set when `max_items` stopped item collection early.
+    #[serde(skip_serializing_if = "std::ops::Not::not")]
+    pub truncated: bool,
+    /// Number of items found, including those dropped by `max_items`; only
+    /// present when `truncated`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub total_found: Option<usize>,
+}
+
+/// Serialize ignored items with their `reason` alongside the usual fields.
+fn serialize_ignored_items<S: serde::Serializer>(
+    items: &[TodoItem],
+    serializer: S,
+) -> Result<S::Ok, S::Error> {
+    #[derive(Serialize)]
+    struct WithReason<'a> {
+        #[serde(flatten)]
+        item: &'a TodoItem,
+        reason: IgnoreReason,
+    }
+
+    serializer.collect_seq(items.iter().map(|item| WithReason {
+        item,
+        reason: item.ignore_reason.unwrap_or(IgnoreReason::Marker),
+    }))
 }
 
 #[derive(Debug, Clone, Serialize)]
@@ -163,13 +265,50 @@ pub struct CheckViolation {
 pub struct StatsResult {
     pub total_items: usize,
     pub total_files: usize,
+    pub files_scanned: usize,
+    pub files_with_items: usize,
+    pub bytes_scanned: u64,
     pub tag_counts: Vec<(Tag, usize)>,
     pub priority_counts: PriorityCounts,
     pub author_counts: Vec<(String, usize)>,
     pub hotspot_files: Vec<(String, usize)>,
+    /// Items per parent directory, most first; only filled by `stats --by-dir`.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub dir_counts: Vec<(String, usize)>,
+    /// Nested per-directory counts; only filled by `stats --tree`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tree: Option<DirNode>,
     pub trend: Option<TrendInfo>,
 }
 
+/// A directory (or file, when it has no children) in the `stats --tree`
+/// rollup. `count` includes every item below the node.
+#[derive(Debug, Serialize)]
+pub struct DirNode {
+    pub name: String,
+    pub count: usize,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub children: Vec<DirNode>,
+}
+
+/// Aggregate counts emitted by `list --summary` in place of the items.
+#[derive(Debug, Serialize)]
+pub struct ListSummary {
+    pub total: usize,
+    pub files: usize,
+    pub by_tag: BTreeMap<String, usize>,
+    pub by_priority: PriorityCounts,
+}
+
+/// Counts shown as shields.io badges above `list --badges` markdown output.
+#[derive(Debug)]
+pub struct BadgeCounts {
+    pub total: usize,
+    pub urgent: usize,
+    /// Items older than the blame stale threshold; `None` outside git.
+    pub stale: Option<usize>,
+}
+
 #[derive(Debug, Serialize)]
 pub struct PriorityCounts {
     pub normal: usize,
@@ -193,6 +332,23 @@ pub struct BlameInfo {
     pub commit: String,
 }
 
+impl BlameInfo {
+    /// Sentinel for lines git cannot attribute, such as TODOs in untracked files.
+    pub fn unknown() -> Self {
+        Self {
+            author: "unknown".to_string(),
+            email: String::new(),
+            date: String::new(),
+            age_days: 0,
+            commit: String::new(),
+        }
+    }
+
+    pub fn is_unknown(&self) -> bool {
+        self.commit.is_empty()
+    }
+}
+
 #[derive(Debug, Clone, Serialize)]
 pub struct BlameEntry {
     #[serde(flatten)]
@@ -207,6 +363,8 @@ pub struct BlameResult {
     pub total: usize,
     pub avg_age_days: u64,
     pub stale_count: usize,
+    /// Entries git could not attribute (e.g. untracked files)
+    pub unknown_count: usize,
     pub stale_threshold_days: u64,
 }
 
@@ -222,6 +380,8 @@ pub struct SearchResult {
 #[derive(Debug, Clone, Serialize)]
 pub struct LintViolation {
     pub rule: String,
+    /// Per `lint.severity`; `error` unless the config downgrades the rule
+    pub severity: Severity,
     pub message: String,
     pub file: String,
     pub line: usize,
@@ -269,6 +429,10 @@ pub struct CleanResult {
     pub total_items: usize,
     pub stale_count: usize,
     pub duplicate_count: usize,
+    pub deadline_without_owner_count: usize,
+    /// `created=` age violations on items without an issue reference,
+    /// counted apart from `stale_count`.
+    pub stale_age_count: usize,
     pub violations: Vec<CleanViolation>,
 }
 
@@ -339,19 +503,51 @@ pub struct TasksResult {
     pub output_dir: Option<String>,
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+/// One recognized tag and how the effective config treats it.
+#[derive(Debug, Serialize)]
+pub struct TagInfo {
+    pub tag: Tag,
+    pub severity: Severity,
+    /// Whether the tag is in `tags` and, when set, `enabled_tags`.
+    pub enabled: bool,
+    /// Display names from `tag_labels`.
+    pub aliases: Vec<String>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "lowercase")]
 pub enum Severity {
     Error,
     Warning,
+    #[serde(alias = "note")]
     Notice,
 }
 
 impl Severity {
+    /// Return the more severe of `self` and `other`.
+    pub fn escalate(self, other: Severity) -> Severity {
+        let rank = |s: Severity| match s {
+            Severity::Error => 2,
+            Severity::Warning => 1,
+            Severity::Notice => 0,
+        };
+        if rank(other) > rank(self) {
+            other
+        } else {
+            self
+        }
+    }
+
     pub fn from_item(item: &TodoItem) -> Self {
         if item.priority == Priority::Urgent {
             return Severity::Error;
         }
-        match item.tag {
+        Self::from_tag(&item.tag)
+    }
+
+    /// Default severity of a tag, before priority and path overrides.
+    pub fn from_tag(tag: &Tag) -> Self {
+        match tag {
             Tag::Bug | Tag::Fixme => Severity::Error,
             Tag::Todo | Tag::Hack | Tag::Xxx => Severity::Warning,
             Tag::Note => Severity::Notice,
@@ -366,6 +562,14 @@ impl Severity {
         }
     }
 
+    pub fn as_codeclimate_str(&self) -> &'static str {
+        match self {
+            Severity::Error => "major",
+            Severity::Warning => "minor",
+            Severity::Notice => "info",
+        }
+    }
+
     pub fn as_sarif_level(&self) -> &'static str {
         match self {
             Severity::Error => "error",
@@ -443,6 +647,8 @@ pub struct Relationship {
     pub to: String,
     pub score: f64,
     pub reason: String,
+    /// The reason as a full sentence, with each factor's share of the score
+    pub explanation: String,
 }
 
 #[derive(Debug, Clone, Serialize)]
@@ -484,12 +690,17 @@ mod tests {
         let item = TodoItem {
             file: "src/main.rs".to_string(),
             line: 42,
+            byte_offset: 0,
+            cell: None,
+            labels: vec![],
             tag: Tag::Todo,
             message: "fix this bug".to_string(),
             author: None,
             issue_ref: None,
             priority: Priority::Normal,
             deadline: None,
+            ignore_reason: None,
+            severity: None,
         };
         assert_eq!(item.id(), item.match_key());
     }
@@ -563,6 +774,7 @@ mod tests {
             to: "src/main.rs:15".to_string(),
             score: 0.65,
             reason: "proximity".to_string(),
+            explanation: String::new(),
         };
         let json = serde_json::to_string_pretty(&rel).unwrap();
         assert!(json.contains("\"from\": \"src/main.rs:10\""));
@@ -639,12 +851,17 @@ mod tests {
         let mut item = TodoItem {
             file: "a.rs".to_string(),
             line: 1,
+            byte_offset: 0,
+            cell: None,
+            labels: vec![],
             tag: Tag::Note, // Note normally => Notice
             message: "test".to_string(),
             author: None,
             issue_ref: None,
             priority: Priority::Urgent,
             deadline: None,
+            ignore_reason: None,
+            severity: None,
         };
         // Urgent overrides to Error regardless of tag
         assert_eq!(Severity::from_item(&item), Severity::Error);
@@ -658,12 +875,17 @@ mod tests {
         let make = |tag: Tag| TodoItem {
             file: "a.rs".to_string(),
             line: 1,
+            byte_offset: 0,
+            cell: None,
+            labels: vec![],
             tag,
             message: "test".to_string(),
             author: None,
             issue_ref: None,
             priority: Priority::Normal,
             deadline: None,
+            ignore_reason: None,
+            severity: None,
         };
         assert_eq!(Severity::from_item(&make(Tag::Bug)), Severity::Error);
         assert_eq!(Severity::from_item(&make(Tag::Fixme)), Severity::Error);
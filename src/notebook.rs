@@ -0,0 +1,126 @@
+//! Jupyter notebook (`.ipynb`) scanning.
+//!
+//! Notebooks are JSON documents, so scanning them as plain text would report
+//! JSON string fragments. Instead the source of each code cell is scanned on
+//! its own, and items keep the notebook's path with the cell index in
+//! `TodoItem::cell` and line numbers relative to the cell; display formats
+//! show them as `notebook.ipynb#cell3`. Markdown and raw cells are skipped
+//! since their text is not code comments.
+
+use std::path::Path;
+
+use regex::Regex;
+
+use crate::scanner::{scan_content, ScanContentResult, ScanOptions};
+
+/// Does `path` look like a Jupyter notebook?
+pub fn is_notebook(path: &Path) -> bool {
+    path.extension().is_some_and(|ext| ext == "ipynb")
+}
+
+/// Cell source is stored either as one string or as a list of lines.
+fn cell_source(cell: &serde_json::Value) -> String {
+    match &cell["source"] {
+        serde_json::Value::String(s) => s.clone(),
+        serde_json::Value::Array(lines) => lines.iter().filter_map(|l| l.as_str()).collect(),
+        _ => String::new(),
+    }
+}
+
+/// Source of the 1-based `cell` of a notebook, which the line numbers of its
+/// items count within.
+pub fn cell_text(content: &str, cell: usize) -> Option<String> {
+    let notebook: serde_json::Value = serde_json::from_str(content).ok()?;
+    let cell = notebook["cells"].as_array()?.get(cell.checked_sub(1)?)?;
+    Some(cell_source(cell))
+}
+
+/// Scan the code cells of a notebook. Content that is not a notebook (invalid
+/// JSON or no `cells`) is scanned as plain text instead.
+pub fn scan_notebook(
+    content: &str,
+    file_path: &str,
+    pattern: &Regex,
+    options: &ScanOptions,
+) -> ScanContentResult {
+    let notebook: Option<serde_json::Value> = serde_json::from_str(content).ok();
+    let Some(cells) = notebook.as_ref().and_then(|nb| nb["cells"].as_array()) else {
+        return scan_content(content, file_path, pattern, options);
+    };
+
+    let mut result = ScanContentResult::default();
+    for (idx, cell) in cells.iter().enumerate() {
+        if cell["cell_type"] != "code" {
+            continue;
+        }
+        let mut found = scan_content(&cell_source(cell), file_path, pattern, options);
+        for item in found.items.iter_mut().chain(&mut found.ignored_items) {
+            item.cell = Some(idx + 1);
+        }
+        for item in found.items {
+            result.keep(item, options.max_items);
+        }
+        result.dropped += found.dropped;
+        result.ignored_items.extend(found.ignored_items);
+    }
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::Config;
+    use crate::model::Tag;
+
+    fn pattern() -> Regex {
+        Regex::new(&Config::default().tags_pattern()).unwrap()
+    }
+
+    const NOTEBOOK: &str = r##"{
+  "cells": [
+    {"cell_type": "markdown", "metadata": {}, "source": ["# TODO: not code\n"]},
+    {"cell_type": "code", "metadata": {}, "outputs": [], "source": ["import pandas as pd\n", "df = pd.read_csv('x.csv')\n"]},
+    {"cell_type": "code", "metadata": {}, "outputs": [], "source": ["x = 1\n", "# TODO: drop outliers\n"]},
+    {"cell_type": "code", "metadata": {}, "outputs": [], "source": "# FIXME: flaky seed"}
+  ],
+  "metadata": {},
+  "nbformat": 4,
+  "nbformat_minor": 5
+}"##;
+
+    #[test]
+    fn test_code_cell_todos_located_by_cell_and_line() {
+        let result = scan_notebook(NOTEBOOK, "nb.ipynb", &pattern(), &ScanOptions::default());
+        assert_eq!(result.items.len(), 2);
+
+        assert_eq!(result.items[0].file, "nb.ipynb");
+        assert_eq!(result.items[0].cell, Some(3));
+        assert_eq!(result.items[0].location(), "nb.ipynb#cell3");
+        assert_eq!(result.items[0].line, 2);
+        assert_eq!(result.items[0].tag, Tag::Todo);
+        assert_eq!(result.items[0].message, "drop outliers");
+
+        assert_eq!(result.items[1].cell, Some(4));
+        assert_eq!(result.items[1].line, 1);
+        assert_eq!(result.items[1].message, "flaky seed");
+    }
+
+    #[test]
+    fn test_invalid_json_scanned_as_text() {
+        let result = scan_notebook(
+            "# TODO: half-written\n{",
+            "nb.ipynb",
+            &pattern(),
+            &ScanOptions::default(),
+        );
+        assert_eq!(result.items.len(), 1);
+        assert_eq!(result.items[0].file, "nb.ipynb");
+        assert_eq!(result.items[0].cell, None);
+    }
+
+    #[test]
+    fn test_is_notebook() {
+        assert!(is_notebook(Path::new("analysis/explore.ipynb")));
+        assert!(!is_notebook(Path::new("src/main.py")));
+    }
+}
@@ -0,0 +1,97 @@
+use crate::model::*;
+
+/// Clang diagnostic level for a tag: BUG and FIXME are errors, everything
+/// else a warning.
+fn level(tag: &Tag) -> &'static str {
+    match tag {
+        Tag::Bug | Tag::Fixme => "error",
+        _ => "warning",
+    }
+}
+
+/// Render items as clang-style diagnostics (`path:line: warning: [TAG] msg`),
+/// which Xcode and most editors pick out of build logs as inline issues.
+pub fn format_list(result: &ScanResult) -> String {
+    let mut out = String::new();
+    for item in &result.items {
+        let msg = item.message.replace(['\n', '\r'], " ");
+        // Items found in file names (line 0) are reported against the whole file
+        let location = match item.line {
+            0 => item.file.clone(),
+            line => format!("{}:{}", item.file, line),
+        };
+        out.push_str(&format!(
+            "{}: {}: [{}] {}\n",
+            location,
+            level(&item.tag),
+            item.tag,
+            msg
+        ));
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_helpers::helpers::make_item;
+
+    fn lines(items: Vec<TodoItem>) -> Vec<String> {
+        let result = ScanResult {
+            items,
+            ignored_items: vec![],
+            files_scanned: 1,
+            files_with_items: 1,
+            bytes_scanned: 0,
+            truncated: false,
+            total_found: None,
+        };
+        format_list(&result).lines().map(String::from).collect()
+    }
+
+    #[test]
+    fn test_bug_and_fixme_are_errors() {
+        let lines = lines(vec![
+            make_item("src/a.rs", 3, Tag::Bug, "crash on empty input"),
+            make_item("src/a.rs", 9, Tag::Fixme, "leaks handles"),
+        ]);
+        assert_eq!(lines[0], "src/a.rs:3: error: [BUG] crash on empty input");
+        assert_eq!(lines[1], "src/a.rs:9: error: [FIXME] leaks handles");
+    }
+
+    #[test]
+    fn test_other_tags_are_warnings() {
+        let lines = lines(vec![
+            make_item("a.rs", 1, Tag::Todo, "wire up"),
+            make_item("a.rs", 2, Tag::Hack, "workaround"),
+            make_item("a.rs", 3, Tag::Xxx, "ugly"),
+            make_item("a.rs", 4, Tag::Note, "context"),
+        ]);
+        assert_eq!(
+            lines,
+            vec![
+                "a.rs:1: warning: [TODO] wire up",
+                "a.rs:2: warning: [HACK] workaround",
+                "a.rs:3: warning: [XXX] ugly",
+                "a.rs:4: warning: [NOTE] context",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_multiline_message_stays_on_one_line() {
+        let lines = lines(vec![make_item("a.rs", 1, Tag::Todo, "first\nsecond")]);
+        assert_eq!(lines, vec!["a.rs:1: warning: [TODO] first second"]);
+    }
+
+    #[test]
+    fn test_file_name_item_has_no_line() {
+        let lines = lines(vec![make_item(
+            "src/TODO_x.rs",
+            0,
+            Tag::Todo,
+            "src/TODO_x.rs",
+        )]);
+        assert_eq!(lines, vec!["src/TODO_x.rs: warning: [TODO] src/TODO_x.rs"]);
+    }
+}
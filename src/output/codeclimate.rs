@@ -0,0 +1,120 @@
+use super::OutputOptions;
+use crate::model::*;
+
+/// CodeClimate issue categories for a tag.
+fn categories(tag: &Tag) -> &'static [&'static str] {
+    match tag {
+        Tag::Bug | Tag::Fixme => &["Bug Risk"],
+        Tag::Todo | Tag::Hack | Tag::Xxx | Tag::Note => &["Clarity"],
+    }
+}
+
+/// Stable fingerprint derived from [`TodoItem::id`], so an issue keeps its
+/// identity across line moves just like `--sarif-baseline` matching.
+fn fingerprint(item: &TodoItem) -> String {
+    blake3::hash(item.id().as_bytes()).to_hex()[..32].to_string()
+}
+
+fn item_to_issue(item: &TodoItem, out: &OutputOptions) -> serde_json::Value {
+    let severity = out.severity.severity_of(item);
+    serde_json::json!({
+        "type": "issue",
+        "check_name": format!("todo-scan/{}", item.tag.as_str()),
+        "description": format!("[{}] {}", item.tag.as_str(), item.message),
+        "categories": categories(&item.tag),
+        "fingerprint": fingerprint(item),
+        "severity": severity.as_codeclimate_str(),
+        "location": {
+            "path": item.file,
+            // Items found in file names sit on line 0; Code Climate lines start at 1
+            "lines": { "begin": item.line.max(1) }
+        }
+    })
+}
+
+/// Render list items as a CodeClimate issue array, the shape GitLab code
+/// quality reports also accept.
+pub fn format_list(result: &ScanResult, out: &OutputOptions) -> String {
+    let issues: Vec<serde_json::Value> = result
+        .items
+        .iter()
+        .map(|item| item_to_issue(item, out))
+        .collect();
+    let mut json = serde_json::to_string_pretty(&issues).expect("failed to serialize CodeClimate");
+    json.push('\n');
+    json
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cli::Format;
+    use crate::test_helpers::helpers::make_item;
+
+    fn issues(items: Vec<TodoItem>) -> Vec<serde_json::Value> {
+        let result = ScanResult {
+            items,
+            ignored_items: vec![],
+            files_scanned: 1,
+            files_with_items: 1,
+            bytes_scanned: 0,
+            truncated: false,
+            total_found: None,
+        };
+        serde_json::from_str(&format_list(
+            &result,
+            &OutputOptions::new(Format::CodeClimate),
+        ))
+        .unwrap()
+    }
+
+    #[test]
+    fn test_required_fields_present() {
+        let issues = issues(vec![make_item("src/a.rs", 12, Tag::Todo, "wire this up")]);
+        assert_eq!(issues.len(), 1);
+        let issue = &issues[0];
+        assert_eq!(issue["type"], "issue");
+        assert_eq!(issue["check_name"], "todo-scan/TODO");
+        assert_eq!(issue["description"], "[TODO] wire this up");
+        assert_eq!(issue["location"]["path"], "src/a.rs");
+        assert_eq!(issue["location"]["lines"]["begin"], 12);
+        assert_eq!(issue["fingerprint"].as_str().unwrap().len(), 32);
+    }
+
+    #[test]
+    fn test_severity_maps_from_tag() {
+        let issues = issues(vec![
+            make_item("a.rs", 1, Tag::Bug, "crash"),
+            make_item("a.rs", 2, Tag::Hack, "workaround"),
+            make_item("a.rs", 3, Tag::Note, "context"),
+        ]);
+        let severities: Vec<&str> = issues
+            .iter()
+            .map(|i| i["severity"].as_str().unwrap())
+            .collect();
+        assert_eq!(severities, vec!["major", "minor", "info"]);
+        assert_eq!(issues[0]["categories"], serde_json::json!(["Bug Risk"]));
+    }
+
+    #[test]
+    fn test_fingerprint_survives_line_moves() {
+        let issues = issues(vec![
+            make_item("a.rs", 1, Tag::Todo, "same"),
+            make_item("a.rs", 40, Tag::Todo, "same"),
+            make_item("a.rs", 41, Tag::Todo, "different"),
+        ]);
+        assert_eq!(issues[0]["fingerprint"], issues[1]["fingerprint"]);
+        assert_ne!(issues[0]["fingerprint"], issues[2]["fingerprint"]);
+    }
+
+    #[test]
+    fn test_file_name_item_starts_on_line_one() {
+        let issues = issues(vec![make_item(
+            "src/TODO_x.rs",
+            0,
+            Tag::Todo,
+            "src/TODO_x.rs",
+        )]);
+        assert_eq!(issues[0]["location"]["lines"]["begin"], 1);
+    }
+}
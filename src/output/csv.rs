@@ -0,0 +1,200 @@
+use std::collections::HashMap;
+
+use crate::cli::Column;
+use crate::context::{ContextInfo, ContextLine};
+use crate::model::*;
+
+const WIDE_COLUMNS: [Column; 10] = [
+    Column::File,
+    Column::Line,
+    Column::Tag,
+    Column::Priority,
+    Column::Author,
+    Column::IssueRef,
+    Column::Deadline,
+    Column::Message,
+    Column::ContextBefore,
+    Column::ContextAfter,
+];
+
+fn column_name(column: Column) -> &'static str {
+    match column {
+        Column::File => "file",
+        Column::Line => "line",
+        Column::Tag => "tag",
+        Column::Priority => "priority",
+        Column::Author => "author",
+        Column::IssueRef => "issue_ref",
+        Column::Deadline => "deadline",
+        Column::Message => "message",
+        Column::ContextBefore => "context_before",
+        Column::ContextAfter => "context_after",
+    }
+}
+
+/// Quote a field per RFC 4180 when it contains a comma, quote or line break.
+fn quote(field: &str) -> String {
+    if field.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", field.replace('"', "\"\""))
+    } else {
+        field.to_string()
+    }
+}
+
+fn push_record(out: &mut String, fields: &[String]) {
+    let record: Vec<String> = fields.iter().map(|f| quote(f)).collect();
+    out.push_str(&record.join(","));
+    out.push_str("\r\n");
+}
+
+fn join_lines(lines: &[ContextLine]) -> String {
+    lines
+        .iter()
+        .map(|l| l.content.as_str())
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+fn priority_str(priority: &Priority) -> &'static str {
+    match priority {
+        Priority::Normal => "normal",
+        Priority::High => "high",
+        Priority::Urgent => "urgent",
+    }
+}
+
+/// Render list items as CSV with one row per item, in `columns` order (all
+/// columns when empty). The `context_before` and `context_after` columns hold
+/// newline-joined context lines from `context_map`, and are empty when no
+/// context was collected.
+pub fn format_list_wide(
+    result: &ScanResult,
+    context_map: &HashMap<String, ContextInfo>,
+    columns: &[Column],
+) -> String {
+    let columns = if columns.is_empty() {
+        &WIDE_COLUMNS[..]
+    } else {
+        columns
+    };
+    let mut out = String::new();
+    let header: Vec<String> = columns
+        .iter()
+        .map(|&c| column_name(c).to_string())
+        .collect();
+    push_record(&mut out, &header);
+
+    for item in &result.items {
+        let ctx = context_map.get(&format!("{}:{}", item.location(), item.line));
+        let record: Vec<String> = columns
+            .iter()
+            .map(|column| match column {
+                Column::File => item.location(),
+                Column::Line => item.line.to_string(),
+                Column::Tag => item.tag.as_str().to_string(),
+                Column::Priority => priority_str(&item.priority).to_string(),
+                Column::Author => item.author.clone().unwrap_or_default(),
+                Column::IssueRef => item.issue_ref.clone().unwrap_or_default(),
+                Column::Deadline => item.deadline.map(|d| d.to_string()).unwrap_or_default(),
+                Column::Message => item.message.clone(),
+                Column::ContextBefore => ctx.map(|c| join_lines(&c.before)).unwrap_or_default(),
+                Column::ContextAfter => ctx.map(|c| join_lines(&c.after)).unwrap_or_default(),
+            })
+            .collect();
+        push_record(&mut out, &record);
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_helpers::helpers::make_item;
+
+    /// Minimal RFC 4180 reader, enough to check that quoted cells round-trip.
+    fn parse_csv(input: &str) -> Vec<Vec<String>> {
+        let mut rows = Vec::new();
+        let mut row = Vec::new();
+        let mut field = String::new();
+        let mut in_quotes = false;
+        let mut chars = input.chars().peekable();
+        while let Some(c) = chars.next() {
+            match (c, in_quotes) {
+                ('"', true) if chars.peek() == Some(&'"') => {
+                    chars.next();
+                    field.push('"');
+                }
+                ('"', _) => in_quotes = !in_quotes,
+                (',', false) => row.push(std::mem::take(&mut field)),
+                ('\r', false) => {}
+                ('\n', false) => {
+                    row.push(std::mem::take(&mut field));
+                    rows.push(std::mem::take(&mut row));
+                }
+                (c, _) => field.push(c),
+            }
+        }
+        rows
+    }
+
+    fn scan(items: Vec<TodoItem>) -> ScanResult {
+        ScanResult {
+            items,
+            ignored_items: vec![],
+            files_scanned: 1,
+            files_with_items: 1,
+            bytes_scanned: 0,
+            truncated: false,
+            total_found: None,
+        }
+    }
+
+    #[test]
+    fn test_context_cells_round_trip() {
+        let result = scan(vec![make_item("a.rs", 3, Tag::Todo, "say \"hi\", then go")]);
+        let line = |n: usize, content: &str| ContextLine {
+            line_number: n,
+            content: content.to_string(),
+        };
+        let context_map = HashMap::from([(
+            "a.rs:3".to_string(),
+            ContextInfo {
+                before: vec![line(1, "fn main() {"), line(2, "    let s = \"a,b\";")],
+                after: vec![line(4, "}")],
+            },
+        )]);
+
+        let csv = format_list_wide(&result, &context_map, &[]);
+        let rows = parse_csv(&csv);
+        assert_eq!(rows.len(), 2);
+        assert_eq!(rows[0], WIDE_COLUMNS.map(column_name));
+        assert_eq!(rows[1][7], "say \"hi\", then go");
+        assert_eq!(rows[1][8], "fn main() {\n    let s = \"a,b\";");
+        assert_eq!(rows[1][9], "}");
+    }
+
+    #[test]
+    fn test_context_columns_empty_without_context() {
+        let result = scan(vec![make_item("a.rs", 1, Tag::Fixme, "plain")]);
+        let csv = format_list_wide(&result, &HashMap::new(), &[]);
+        assert_eq!(
+            csv.lines().nth(1).unwrap(),
+            "a.rs,1,FIXME,normal,,,,plain,,"
+        );
+    }
+
+    #[test]
+    fn test_custom_columns_select_and_order() {
+        let mut item = make_item("a.rs", 4, Tag::Bug, "crash, badly");
+        item.author = Some("alice".to_string());
+        let result = scan(vec![item]);
+        let csv = format_list_wide(
+            &result,
+            &HashMap::new(),
+            &[Column::Message, Column::File, Column::Author],
+        );
+        let rows = parse_csv(&csv);
+        assert_eq!(rows[0], ["message", "file", "author"]);
+        assert_eq!(rows[1], ["crash, badly", "a.rs", "alice"]);
+    }
+}
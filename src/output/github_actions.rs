@@ -1,3 +1,4 @@
+use super::OutputOptions;
 use crate::model::*;
 
 /// Escape special characters per GitHub Actions workflow command spec.
@@ -12,23 +13,40 @@ fn escape_property(s: &str) -> String {
     escape_message(s).replace(':', "%3A").replace(',', "%2C")
 }
 
-fn format_item_annotation(item: &TodoItem) -> String {
-    let severity = Severity::from_item(item);
+/// `file` and `line` annotation properties. Items found in file names sit on
+/// line 0, which GitHub can't place, so they annotate the file as a whole.
+fn location_properties(file: &str, line: usize) -> String {
+    let file = escape_property(file);
+    if line == 0 {
+        format!("file={file}")
+    } else {
+        format!("file={file},line={line}")
+    }
+}
+
+fn format_item_annotation(item: &TodoItem, out: &OutputOptions) -> String {
+    let severity = out.severity.severity_of(item);
     let level = severity.as_github_actions_str();
-    let file = escape_property(&item.file);
+    let location = location_properties(&item.file, item.line);
     let title = item.tag.as_str();
     let mut msg = escape_message(&item.message);
     if let Some(ref deadline) = item.deadline {
         msg.push_str(&format!(" (deadline: {})", deadline));
     }
-    format!(
-        "::{level} file={file},line={},title={title}::[{title}] {msg}",
-        item.line
-    )
+    let prefix = out
+        .github_actions_emoji
+        .get(&item.tag)
+        .map(|e| format!("{} ", escape_message(e)))
+        .unwrap_or_default();
+    format!("::{level} {location},title={title}::{prefix}[{title}] {msg}")
 }
 
-pub fn format_list(result: &ScanResult) -> String {
-    let mut lines: Vec<String> = result.items.iter().map(format_item_annotation).collect();
+pub fn format_list(result: &ScanResult, out: &OutputOptions) -> String {
+    let mut lines: Vec<String> = result
+        .items
+        .iter()
+        .map(|item| format_item_annotation(item, out))
+        .collect();
     lines.push(format!(
         "::notice::todo-scan: {} items found",
         result.items.len()
@@ -37,8 +55,12 @@ pub fn format_list(result: &ScanResult) -> String {
     lines.join("\n")
 }
 
-pub fn format_search(result: &SearchResult) -> String {
-    let mut lines: Vec<String> = result.items.iter().map(format_item_annotation).collect();
+pub fn format_search(result: &SearchResult, out: &OutputOptions) -> String {
+    let mut lines: Vec<String> = result
+        .items
+        .iter()
+        .map(|item| format_item_annotation(item, out))
+        .collect();
     let query = escape_message(&result.query);
     lines.push(format!(
         "::notice::todo-scan search: {} matches (query: \"{query}\")",
@@ -48,20 +70,19 @@ pub fn format_search(result: &SearchResult) -> String {
     lines.join("\n")
 }
 
-pub fn format_diff(result: &DiffResult) -> String {
+pub fn format_diff(result: &DiffResult, out: &OutputOptions) -> String {
     let mut lines: Vec<String> = Vec::new();
     for entry in &result.entries {
         match entry.status {
             DiffStatus::Added => {
-                lines.push(format_item_annotation(&entry.item));
+                lines.push(format_item_annotation(&entry.item, out));
             }
             DiffStatus::Removed => {
-                let file = escape_property(&entry.item.file);
+                let location = location_properties(&entry.item.file, entry.item.line);
                 let tag = entry.item.tag.as_str();
                 let msg = escape_message(&entry.item.message);
                 lines.push(format!(
-                    "::notice file={file},line={},title=Removed {tag}::[{tag}] {msg}",
-                    entry.item.line
+                    "::notice {location},title=Removed {tag}::[{tag}] {msg}"
                 ));
             }
         }
@@ -79,7 +100,7 @@ pub fn format_blame(result: &BlameResult) -> String {
 
     for entry in &result.entries {
         let level = if entry.stale { "warning" } else { "notice" };
-        let file = escape_property(&entry.item.file);
+        let location = location_properties(&entry.item.file, entry.item.line);
         let tag = entry.item.tag.as_str();
         let msg = escape_message(&format!(
             "[{}] {} @{} {} ({} days ago)",
@@ -90,10 +111,7 @@ pub fn format_blame(result: &BlameResult) -> String {
         } else {
             tag.to_string()
         };
-        lines.push(format!(
-            "::{level} file={file},line={},title={title}::{msg}",
-            entry.item.line,
-        ));
+        lines.push(format!("::{level} {location},title={title}::{msg}"));
     }
 
     lines.push(format!(
@@ -106,21 +124,26 @@ pub fn format_blame(result: &BlameResult) -> String {
 
 pub fn format_lint(result: &LintResult) -> String {
     let mut lines: Vec<String> = Vec::new();
-    if result.passed {
+    if result.violations.is_empty() {
         lines.push("::notice::todo-scan lint: PASS".to_string());
     } else {
         for violation in &result.violations {
-            let file = escape_property(&violation.file);
+            let location = location_properties(&violation.file, violation.line);
             let msg = escape_message(&violation.message);
             let rule = escape_property(&violation.rule);
             lines.push(format!(
-                "::error file={file},line={},title={rule}::{msg}",
-                violation.line
+                "::{} {location},title={rule}::{msg}",
+                violation.severity.as_github_actions_str()
             ));
         }
+        let (level, verdict) = if result.passed {
+            ("notice", "PASS")
+        } else {
+            ("error", "FAIL")
+        };
         lines.push(format!(
-            "::error::todo-scan lint: FAIL ({} violations)",
-            result.violation_count
+            "::{}::todo-scan lint: {} ({} violations)",
+            level, verdict, result.violation_count
         ));
     }
     lines.push(String::new());
@@ -149,17 +172,16 @@ pub fn format_clean(result: &CleanResult) -> String {
         lines.push("::notice::todo-scan clean: PASS".to_string());
     } else {
         for violation in &result.violations {
-            let file = escape_property(&violation.file);
+            let location = location_properties(&violation.file, violation.line);
             let msg = escape_message(&violation.message);
             let rule = escape_property(&violation.rule);
-            lines.push(format!(
-                "::error file={file},line={},title={rule}::{msg}",
-                violation.line
-            ));
+            lines.push(format!("::error {location},title={rule}::{msg}"));
         }
         lines.push(format!(
-            "::error::todo-scan clean: FAIL ({} stale, {} duplicates)",
-            result.stale_count, result.duplicate_count
+            "::error::todo-scan clean: FAIL ({} stale, {} duplicates{})",
+            result.stale_count,
+            result.duplicate_count,
+            super::clean_extra_counts(result)
         ));
     }
     lines.push(String::new());
@@ -168,18 +190,26 @@ pub fn format_clean(result: &CleanResult) -> String {
 
 #[cfg(test)]
 mod tests {
+    use std::collections::HashMap;
+
     use super::*;
+    use crate::cli::Format;
 
     fn sample_item(tag: Tag, message: &str) -> TodoItem {
         TodoItem {
             file: "src/main.rs".to_string(),
             line: 10,
+            byte_offset: 0,
+            cell: None,
+            labels: vec![],
             tag,
             message: message.to_string(),
             author: None,
             issue_ref: None,
             priority: Priority::Normal,
             deadline: None,
+            ignore_reason: None,
+            severity: None,
         }
     }
 
@@ -188,9 +218,13 @@ mod tests {
         let result = ScanResult {
             items: vec![sample_item(Tag::Todo, "implement feature")],
             files_scanned: 1,
+            files_with_items: 1,
+            bytes_scanned: 0,
             ignored_items: vec![],
+            truncated: false,
+            total_found: None,
         };
-        let output = format_list(&result);
+        let output = format_list(&result, &OutputOptions::new(Format::GithubActions));
         assert!(output
             .contains("::warning file=src/main.rs,line=10,title=TODO::[TODO] implement feature"));
         assert!(output.contains("::notice::todo-scan: 1 items found"));
@@ -204,9 +238,13 @@ mod tests {
                 sample_item(Tag::Note, "a note"),
             ],
             files_scanned: 1,
+            files_with_items: 1,
+            bytes_scanned: 0,
             ignored_items: vec![],
+            truncated: false,
+            total_found: None,
         };
-        let output = format_list(&result);
+        let output = format_list(&result, &OutputOptions::new(Format::GithubActions));
         assert!(output.contains("::error file=src/main.rs,line=10,title=BUG::[BUG] critical bug"));
         assert!(output.contains("::notice file=src/main.rs,line=10,title=NOTE::[NOTE] a note"));
     }
@@ -217,17 +255,26 @@ mod tests {
             items: vec![TodoItem {
                 file: "lib.rs".to_string(),
                 line: 5,
+                byte_offset: 0,
+                cell: None,
+                labels: vec![],
                 tag: Tag::Todo,
                 message: "urgent task".to_string(),
                 author: None,
                 issue_ref: None,
                 priority: Priority::Urgent,
                 deadline: None,
+                ignore_reason: None,
+                severity: None,
             }],
             files_scanned: 1,
+            files_with_items: 1,
+            bytes_scanned: 0,
             ignored_items: vec![],
+            truncated: false,
+            total_found: None,
         };
-        let output = format_list(&result);
+        let output = format_list(&result, &OutputOptions::new(Format::GithubActions));
         assert!(output.contains("::error file=lib.rs,line=5,title=TODO::[TODO] urgent task"));
     }
 
@@ -236,9 +283,13 @@ mod tests {
         let result = ScanResult {
             items: vec![sample_item(Tag::Todo, "fix 100% of bugs\nline2")],
             files_scanned: 1,
+            files_with_items: 1,
+            bytes_scanned: 0,
             ignored_items: vec![],
+            truncated: false,
+            total_found: None,
         };
-        let output = format_list(&result);
+        let output = format_list(&result, &OutputOptions::new(Format::GithubActions));
         assert!(output.contains("fix 100%25 of bugs%0Aline2"));
     }
 
@@ -259,7 +310,7 @@ mod tests {
             removed_count: 1,
             base_ref: "main".to_string(),
         };
-        let output = format_diff(&result);
+        let output = format_diff(&result, &OutputOptions::new(Format::GithubActions));
         assert!(output.contains("::error file=src/main.rs,line=10,title=FIXME::[FIXME] new fix"));
         assert!(output
             .contains("::notice file=src/main.rs,line=10,title=Removed TODO::[TODO] old task"));
@@ -302,7 +353,7 @@ mod tests {
             exact: false,
             query: "evil\n::error::injected annotation".to_string(),
         };
-        let output = format_search(&result);
+        let output = format_search(&result, &OutputOptions::new(Format::GithubActions));
         // The newline in the query must be escaped to %0A so that
         // "::error::injected annotation" cannot appear on its own line
         // (GitHub Actions only parses workflow commands at line start)
@@ -325,6 +376,7 @@ mod tests {
                 file: "src/main.rs".to_string(),
                 line: 1,
                 rule: "rule:with:colons".to_string(),
+                severity: Severity::Error,
                 message: "bad".to_string(),
                 suggestion: None,
             }],
@@ -368,6 +420,7 @@ mod tests {
             total: 1,
             avg_age_days: 400,
             stale_count: 1,
+            unknown_count: 0,
             stale_threshold_days: 365,
         };
         let output = format_blame(&result);
@@ -394,6 +447,7 @@ mod tests {
             total: 1,
             avg_age_days: 10,
             stale_count: 0,
+            unknown_count: 0,
             stale_threshold_days: 365,
         };
         let output = format_blame(&result);
@@ -407,6 +461,8 @@ mod tests {
             total_items: 3,
             stale_count: 0,
             duplicate_count: 0,
+            deadline_without_owner_count: 0,
+            stale_age_count: 0,
             violations: vec![],
         };
         let output = format_clean(&result);
@@ -420,6 +476,8 @@ mod tests {
             total_items: 2,
             stale_count: 1,
             duplicate_count: 1,
+            deadline_without_owner_count: 0,
+            stale_age_count: 0,
             violations: vec![CleanViolation {
                 file: "test.rs".to_string(),
                 line: 10,
@@ -434,12 +492,38 @@ mod tests {
         assert!(output.contains("::error::todo-scan clean: FAIL (1 stale, 1 duplicates)"));
     }
 
+    #[test]
+    fn test_format_clean_fail_with_deadline_without_owner() {
+        let result = CleanResult {
+            passed: false,
+            total_items: 1,
+            stale_count: 0,
+            duplicate_count: 0,
+            deadline_without_owner_count: 1,
+            stale_age_count: 0,
+            violations: vec![CleanViolation {
+                file: "test.rs".to_string(),
+                line: 10,
+                rule: "deadline_without_owner".to_string(),
+                message: "ship it".to_string(),
+                issue_ref: None,
+                duplicate_of: None,
+            }],
+        };
+        let output = format_clean(&result);
+        assert!(output
+            .contains("::error::todo-scan clean: FAIL (0 stale, 0 duplicates, 1 without owner)"));
+    }
+
     #[test]
     fn test_format_item_annotation_with_deadline() {
         use crate::deadline::Deadline;
         let item = TodoItem {
             file: "test.rs".to_string(),
             line: 5,
+            byte_offset: 0,
+            cell: None,
+            labels: vec![],
             tag: Tag::Todo,
             message: "task".to_string(),
             author: None,
@@ -450,8 +534,10 @@ mod tests {
                 month: 6,
                 day: 15,
             }),
+            ignore_reason: None,
+            severity: None,
         };
-        let output = format_item_annotation(&item);
+        let output = format_item_annotation(&item, &OutputOptions::new(Format::GithubActions));
         assert!(output.contains("(deadline: 2025-06-15)"));
     }
 
@@ -464,7 +550,7 @@ mod tests {
             match_count: 1,
             file_count: 1,
         };
-        let output = format_search(&result);
+        let output = format_search(&result, &OutputOptions::new(Format::GithubActions));
         assert!(output.contains("::error file=src/main.rs,line=10,title=FIXME::[FIXME] fix this"));
         assert!(output.contains("::notice::todo-scan search: 1 matches"));
     }
@@ -478,4 +564,46 @@ mod tests {
     fn test_escape_property_commas() {
         assert_eq!(escape_property("a,b"), "a%2Cb");
     }
+
+    #[test]
+    fn test_emoji_prefix_only_for_configured_tags() {
+        let out = OutputOptions {
+            github_actions_emoji: HashMap::from([(Tag::Bug, "🐛".to_string())]),
+            ..OutputOptions::new(Format::GithubActions)
+        };
+        assert_eq!(
+            format_item_annotation(&sample_item(Tag::Bug, "crash"), &out),
+            "::error file=src/main.rs,line=10,title=BUG::🐛 [BUG] crash"
+        );
+        assert_eq!(
+            format_item_annotation(&sample_item(Tag::Todo, "later"), &out),
+            "::warning file=src/main.rs,line=10,title=TODO::[TODO] later"
+        );
+        assert_eq!(
+            format_item_annotation(
+                &sample_item(Tag::Bug, "crash"),
+                &OutputOptions::new(Format::GithubActions)
+            ),
+            "::error file=src/main.rs,line=10,title=BUG::[BUG] crash"
+        );
+    }
+
+    #[test]
+    fn test_format_list_file_name_item_has_no_line() {
+        let mut item = sample_item(Tag::Todo, "src/TODO_x.rs");
+        item.file = "src/TODO_x.rs".to_string();
+        item.line = 0;
+        let result = ScanResult {
+            items: vec![item],
+            files_scanned: 1,
+            files_with_items: 1,
+            bytes_scanned: 0,
+            ignored_items: vec![],
+            truncated: false,
+            total_found: None,
+        };
+        let output = format_list(&result, &OutputOptions::new(Format::GithubActions));
+        assert!(output.contains("::warning file=src/TODO_x.rs,title=TODO::"));
+        assert!(!output.contains("line=0"));
+    }
 }
@@ -1,22 +1,8 @@
-use crate::model::ReportResult;
+use super::OutputOptions;
+use crate::model::{Priority, ReportResult, ScanResult};
 
-/// Render a self-contained HTML dashboard report.
-pub fn render_html(report: &ReportResult) -> String {
-    let json_data = serde_json::to_string(report).expect("failed to serialize report");
-    // Escape all `<` in JSON data to prevent breaking the HTML script block.
-    // HTML5 parsers match </script> case-insensitively, so we must neutralize
-    // every `<` rather than just the lowercase variant.
-    let safe_json = json_data.replace('<', "\\u003c");
-
-    format!(
-        r##"<!DOCTYPE html>
-<html lang="en">
-<head>
-<meta charset="UTF-8">
-<meta name="viewport" content="width=device-width, initial-scale=1.0">
-<title>todo-scan Technical Debt Report</title>
-<style>
-:root {{
+/// Stylesheet shared by the report dashboard and the `list` table.
+const STYLE: &str = r#":root {
   --bg: #ffffff;
   --bg-card: #f8f9fa;
   --bg-table-head: #e9ecef;
@@ -28,9 +14,9 @@ pub fn render_html(report: &ReportResult) -> String {
   --warning: #ffc107;
   --success: #198754;
   --info: #0dcaf0;
-}}
-@media (prefers-color-scheme: dark) {{
-  :root {{
+}
+@media (prefers-color-scheme: dark) {
+  :root {
     --bg: #1a1a2e;
     --bg-card: #16213e;
     --bg-table-head: #0f3460;
@@ -42,10 +28,10 @@ pub fn render_html(report: &ReportResult) -> String {
     --warning: #ffd43b;
     --success: #51cf66;
     --info: #66d9e8;
-  }}
-}}
-* {{ margin: 0; padding: 0; box-sizing: border-box; }}
-body {{
+  }
+}
+* { margin: 0; padding: 0; box-sizing: border-box; }
+body {
   font-family: -apple-system, BlinkMacSystemFont, 'Segoe UI', Roboto, sans-serif;
   background: var(--bg);
   color: var(--text);
@@ -53,101 +39,123 @@ body {{
   padding: 2rem;
   max-width: 1200px;
   margin: 0 auto;
-}}
-h1 {{ margin-bottom: 0.25rem; }}
-.subtitle {{ color: var(--text-muted); margin-bottom: 2rem; font-size: 0.9rem; }}
-.cards {{
+}
+h1 { margin-bottom: 0.25rem; }
+.subtitle { color: var(--text-muted); margin-bottom: 2rem; font-size: 0.9rem; }
+.cards {
   display: grid;
   grid-template-columns: repeat(auto-fit, minmax(160px, 1fr));
   gap: 1rem;
   margin-bottom: 2rem;
-}}
-.card {{
+}
+.card {
   background: var(--bg-card);
   border: 1px solid var(--border);
   border-radius: 8px;
   padding: 1.25rem;
   text-align: center;
-}}
-.card .value {{
+}
+.card .value {
   font-size: 2rem;
   font-weight: 700;
   line-height: 1.2;
-}}
-.card .label {{
+}
+.card .label {
   font-size: 0.8rem;
   color: var(--text-muted);
   text-transform: uppercase;
   letter-spacing: 0.05em;
-}}
-.card.danger .value {{ color: var(--danger); }}
-.card.warning .value {{ color: var(--warning); }}
-.card.success .value {{ color: var(--success); }}
-.section {{
+}
+.card.danger .value { color: var(--danger); }
+.card.warning .value { color: var(--warning); }
+.card.success .value { color: var(--success); }
+.section {
   background: var(--bg-card);
   border: 1px solid var(--border);
   border-radius: 8px;
   padding: 1.5rem;
   margin-bottom: 1.5rem;
-}}
-.section h2 {{
+}
+.section h2 {
   font-size: 1.1rem;
   margin-bottom: 1rem;
   border-bottom: 1px solid var(--border);
   padding-bottom: 0.5rem;
-}}
-.chart-row {{
+}
+.chart-row {
   display: grid;
   grid-template-columns: repeat(auto-fit, minmax(300px, 1fr));
   gap: 1.5rem;
   margin-bottom: 1.5rem;
-}}
-canvas {{ width: 100% !important; height: 200px !important; }}
-table {{
+}
+canvas { width: 100% !important; height: 200px !important; }
+table {
   width: 100%;
   border-collapse: collapse;
   font-size: 0.875rem;
-}}
-th, td {{
+}
+th, td {
   text-align: left;
   padding: 0.5rem 0.75rem;
   border-bottom: 1px solid var(--border);
-}}
-th {{
+}
+th {
   background: var(--bg-table-head);
   cursor: pointer;
   user-select: none;
   white-space: nowrap;
-}}
-th:hover {{ opacity: 0.8; }}
-tr:hover td {{ background: var(--bg-table-head); }}
-.tag {{ font-weight: 600; }}
-.tag-TODO {{ color: var(--warning); }}
-.tag-FIXME, .tag-BUG {{ color: var(--danger); }}
-.tag-HACK, .tag-XXX {{ color: #e67700; }}
-.tag-NOTE {{ color: var(--info); }}
-.priority-urgent {{ color: var(--danger); font-weight: 700; }}
-.priority-high {{ color: #e67700; font-weight: 600; }}
-.bar-container {{
+}
+th:hover { opacity: 0.8; }
+tr:hover td { background: var(--bg-table-head); }
+.tag { font-weight: 600; }
+.tag-TODO { color: var(--warning); }
+.tag-FIXME, .tag-BUG { color: var(--danger); }
+.tag-HACK, .tag-XXX { color: #e67700; }
+.tag-NOTE { color: var(--info); }
+.priority-urgent { color: var(--danger); font-weight: 700; }
+.priority-high { color: #e67700; font-weight: 600; }
+.bar-container {
   display: flex;
   align-items: center;
   gap: 0.5rem;
-}}
-.bar {{
+}
+.bar {
   height: 18px;
   border-radius: 3px;
   background: var(--accent);
   min-width: 2px;
-}}
-footer {{
+}
+footer {
   text-align: center;
   color: var(--text-muted);
   font-size: 0.8rem;
   margin-top: 2rem;
   padding-top: 1rem;
   border-top: 1px solid var(--border);
-}}
-</style>
+}
+"#;
+
+/// Render a self-contained HTML dashboard report.
+pub fn render_html(report: &ReportResult, out: &OutputOptions) -> String {
+    let json_data = serde_json::to_string(report).expect("failed to serialize report");
+    // Escape all `<` in JSON data to prevent breaking the HTML script block.
+    // HTML5 parsers match </script> case-insensitively, so we must neutralize
+    // every `<` rather than just the lowercase variant.
+    let safe_json = json_data.replace('<', "\\u003c");
+    // Display labels from `tag_labels`; the data itself keeps canonical tags.
+    let labels_json =
+        serde_json::to_string(&out.tag_labels).expect("failed to serialize tag labels");
+    let safe_labels = labels_json.replace('<', "\\u003c");
+
+    format!(
+        r##"<!DOCTYPE html>
+<html lang="en">
+<head>
+<meta charset="UTF-8">
+<meta name="viewport" content="width=device-width, initial-scale=1.0">
+<title>todo-scan Technical Debt Report</title>
+<style>
+{STYLE}</style>
 </head>
 <body>
 <h1>todo-scan Technical Debt Report</h1>
@@ -210,9 +218,11 @@ footer {{
 
 <script>
 const REPORT_DATA = {safe_json};
+const TAG_LABELS = {safe_labels};
 
 (function() {{
   const D = REPORT_DATA;
+  const tagLabel = tag => TAG_LABELS[tag] || tag;
 
   // Summary cards
   document.getElementById('generated-at').textContent = D.generated_at;
@@ -374,7 +384,7 @@ const REPORT_DATA = {safe_json};
   }};
   drawBarChart(
     'chart-tags',
-    D.tag_counts.map(t => t[0]),
+    D.tag_counts.map(t => tagLabel(t[0])),
     D.tag_counts.map(t => t[1]),
     D.tag_counts.map(t => tagColors[t[0]] || '#6c757d')
   );
@@ -414,9 +424,9 @@ const REPORT_DATA = {safe_json};
     const tr = document.createElement('tr');
     const priorityClass = item.priority === 'urgent' ? 'priority-urgent' : item.priority === 'high' ? 'priority-high' : '';
     tr.innerHTML =
-      '<td>' + escapeHtml(item.file) + '</td>' +
+      '<td>' + escapeHtml(item.cell ? item.file + '#cell' + item.cell : item.file) + '</td>' +
       '<td>' + item.line + '</td>' +
-      '<td><span class="tag tag-' + escapeHtml(item.tag) + '">' + escapeHtml(item.tag) + '</span></td>' +
+      '<td><span class="tag tag-' + escapeHtml(item.tag) + '">' + escapeHtml(tagLabel(item.tag)) + '</span></td>' +
       '<td class="' + escapeHtml(priorityClass) + '">' + escapeHtml(item.priority) + '</td>' +
       '<td>' + escapeHtml(item.message) + '</td>' +
       '<td>' + escapeHtml(item.author || '') + '</td>';
@@ -453,9 +463,103 @@ const REPORT_DATA = {safe_json};
     )
 }
 
+/// Escape text for HTML element content and attribute values.
+fn escape_html(s: &str) -> String {
+    let mut out = String::with_capacity(s.len());
+    for c in s.chars() {
+        match c {
+            '&' => out.push_str("&amp;"),
+            '<' => out.push_str("&lt;"),
+            '>' => out.push_str("&gt;"),
+            '"' => out.push_str("&quot;"),
+            '\'' => out.push_str("&#39;"),
+            c => out.push(c),
+        }
+    }
+    out
+}
+
+/// Render a `list` result as a standalone HTML page with one table row per
+/// item. Unlike the report dashboard, rows are rendered server-side, so the
+/// page needs no script.
+pub fn format_list(result: &ScanResult, out: &OutputOptions) -> String {
+    let mut rows = String::new();
+    for item in &result.items {
+        let priority_class = match item.priority {
+            Priority::Urgent => "priority-urgent",
+            Priority::High => "priority-high",
+            Priority::Normal => "",
+        };
+        let priority = match item.priority {
+            Priority::Urgent => "urgent",
+            Priority::High => "high",
+            Priority::Normal => "normal",
+        };
+        rows.push_str(&format!(
+            "      <tr><td>{}</td><td>{}</td><td><span class=\"tag tag-{}\">{}</span></td><td class=\"{}\">{}</td><td>{}</td><td>{}</td><td>{}</td></tr>\n",
+            escape_html(&item.location()),
+            item.line,
+            item.tag.as_str(),
+            escape_html(super::tag_label(&item.tag, out)),
+            priority_class,
+            priority,
+            escape_html(&item.message),
+            escape_html(item.author.as_deref().unwrap_or("")),
+            escape_html(
+                &item
+                    .deadline
+                    .as_ref()
+                    .map(|d| super::display_deadline(d, out))
+                    .unwrap_or_default()
+            ),
+        ));
+    }
+
+    format!(
+        r##"<!DOCTYPE html>
+<html lang="en">
+<head>
+<meta charset="UTF-8">
+<meta name="viewport" content="width=device-width, initial-scale=1.0">
+<title>todo-scan TODO List</title>
+<style>
+{STYLE}</style>
+</head>
+<body>
+<h1>todo-scan TODO List</h1>
+<p class="subtitle">{count} items in {files} files</p>
+
+<div class="section">
+  <table id="items-table">
+    <thead>
+      <tr>
+        <th>File</th>
+        <th>Line</th>
+        <th>Tag</th>
+        <th>Priority</th>
+        <th>Message</th>
+        <th>Author</th>
+        <th>Deadline</th>
+      </tr>
+    </thead>
+    <tbody>
+{rows}    </tbody>
+  </table>
+</div>
+
+<footer>Generated by <strong>todo-scan</strong></footer>
+</body>
+</html>
+"##,
+        count = result.items.len(),
+        files = result.files_with_items,
+    )
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::cli::Format;
     use crate::model::*;
 
     fn minimal_report() -> ReportResult {
@@ -486,7 +590,7 @@ mod tests {
 
     #[test]
     fn test_render_html_contains_doctype() {
-        let html = render_html(&minimal_report());
+        let html = render_html(&minimal_report(), &OutputOptions::new(Format::Html));
         assert!(html.starts_with("<!DOCTYPE html>"));
     }
 
@@ -496,14 +600,19 @@ mod tests {
         report.items.push(TodoItem {
             file: "test.rs".to_string(),
             line: 1,
+            byte_offset: 0,
+            cell: None,
+            labels: vec![],
             tag: Tag::Todo,
             message: "hello world".to_string(),
             author: None,
             issue_ref: None,
             priority: Priority::Normal,
             deadline: None,
+            ignore_reason: None,
+            severity: None,
         });
-        let html = render_html(&report);
+        let html = render_html(&report, &OutputOptions::new(Format::Html));
         // Extract JSON from REPORT_DATA
         let start = html.find("const REPORT_DATA = ").unwrap() + "const REPORT_DATA = ".len();
         let end = html[start..].find(";\n").unwrap() + start;
@@ -518,14 +627,19 @@ mod tests {
         report.items.push(TodoItem {
             file: "test.rs".to_string(),
             line: 1,
+            byte_offset: 0,
+            cell: None,
+            labels: vec![],
             tag: Tag::Todo,
             message: "has </script> in it".to_string(),
             author: None,
             issue_ref: None,
             priority: Priority::Normal,
             deadline: None,
+            ignore_reason: None,
+            severity: None,
         });
-        let html = render_html(&report);
+        let html = render_html(&report, &OutputOptions::new(Format::Html));
         // The raw </script> should not appear inside our <script> block
         // (it should be escaped to <\/script>)
         let script_start = html.find("const REPORT_DATA = ").unwrap();
@@ -542,7 +656,7 @@ mod tests {
         let mut report = minimal_report();
         let xss_author = "<img src=x onerror=alert(1)>";
         report.author_counts.push((xss_author.to_string(), 5));
-        let html = render_html(&report);
+        let html = render_html(&report, &OutputOptions::new(Format::Html));
         // The JavaScript renderBarList() must use escapeHtml() on `name`,
         // so the raw HTML tag should not appear unescaped in the template.
         // We verify the JS source calls escapeHtml(name) rather than bare name.
@@ -559,14 +673,19 @@ mod tests {
             report.items.push(TodoItem {
                 file: "test.rs".to_string(),
                 line: 1,
+                byte_offset: 0,
+                cell: None,
+                labels: vec![],
                 tag: Tag::Todo,
                 message: format!("xss attempt {variant}"),
                 author: None,
                 issue_ref: None,
                 priority: Priority::Normal,
                 deadline: None,
+                ignore_reason: None,
+                severity: None,
             });
-            let html = render_html(&report);
+            let html = render_html(&report, &OutputOptions::new(Format::Html));
             let script_start = html.find("const REPORT_DATA = ").unwrap();
             let script_end = html[script_start..].find("</script>").unwrap() + script_start;
             let script_content = &html[script_start..script_end];
@@ -577,4 +696,39 @@ mod tests {
             );
         }
     }
+
+    #[test]
+    fn test_format_list_rows_and_escaping() {
+        let item = |line, message: &str| TodoItem {
+            file: "src/<lib>.rs".to_string(),
+            line,
+            byte_offset: 0,
+            cell: None,
+            labels: vec![],
+            tag: Tag::Fixme,
+            message: message.to_string(),
+            author: Some("alice".to_string()),
+            issue_ref: None,
+            priority: Priority::High,
+            deadline: None,
+            ignore_reason: None,
+            severity: None,
+        };
+        let result = ScanResult {
+            items: vec![item(3, "handle <b>bold</b> & \"quotes\""), item(9, "plain")],
+            ignored_items: vec![],
+            files_scanned: 1,
+            files_with_items: 1,
+            bytes_scanned: 0,
+            truncated: false,
+            total_found: None,
+        };
+        let html = format_list(&result, &OutputOptions::new(Format::Html));
+        assert!(html.starts_with("<!DOCTYPE html>"));
+        assert_eq!(html.matches("<tr><td>").count(), 2);
+        assert!(html.contains("<td>handle &lt;b&gt;bold&lt;/b&gt; &amp; &quot;quotes&quot;</td>"));
+        assert!(html.contains("<td>src/&lt;lib&gt;.rs</td>"));
+        assert!(!html.contains("<b>bold</b>"));
+        assert!(html.contains("<td class=\"priority-high\">high</td>"));
+    }
 }
@@ -0,0 +1,173 @@
+use crate::deadline::Deadline;
+use crate::model::*;
+
+/// Longest content line allowed by RFC 5545 before folding, in octets.
+const MAX_LINE_OCTETS: usize = 75;
+
+/// Escape a TEXT property value per RFC 5545 section 3.3.11.
+fn escape_text(s: &str) -> String {
+    let mut out = String::with_capacity(s.len());
+    for c in s.chars() {
+        match c {
+            '\\' => out.push_str("\\\\"),
+            ';' => out.push_str("\\;"),
+            ',' => out.push_str("\\,"),
+            '\n' => out.push_str("\\n"),
+            '\r' => {}
+            c => out.push(c),
+        }
+    }
+    out
+}
+
+/// Append `line` terminated by CRLF, folding it into 75-octet chunks without
+/// splitting a UTF-8 character.
+fn push_line(out: &mut String, line: &str) {
+    let mut rest = line;
+    let mut limit = MAX_LINE_OCTETS;
+    while rest.len() > limit {
+        let mut split = limit;
+        while !rest.is_char_boundary(split) {
+            split -= 1;
+        }
+        out.push_str(&rest[..split]);
+        out.push_str("\r\n ");
+        rest = &rest[split..];
+        // Continuation lines start with a space, which counts toward the limit
+        limit = MAX_LINE_OCTETS - 1;
+    }
+    out.push_str(rest);
+    out.push_str("\r\n");
+}
+
+fn ics_date(d: &Deadline) -> String {
+    format!("{:04}{:02}{:02}", d.year, d.month, d.day)
+}
+
+/// Render items with a deadline as an iCalendar file with one all-day VEVENT
+/// per item. Items without a deadline are skipped.
+pub fn format_deadlines(items: &[TodoItem], today: &Deadline) -> String {
+    let mut out = String::new();
+    push_line(&mut out, "BEGIN:VCALENDAR");
+    push_line(&mut out, "VERSION:2.0");
+    push_line(
+        &mut out,
+        &format!(
+            "PRODID:-//todo-scan//todo-scan {}//EN",
+            env!("CARGO_PKG_VERSION")
+        ),
+    );
+    push_line(&mut out, "CALSCALE:GREGORIAN");
+    let stamp = format!("DTSTAMP:{}T000000Z", ics_date(today));
+
+    for item in items {
+        let Some(deadline) = &item.deadline else {
+            continue;
+        };
+        push_line(&mut out, "BEGIN:VEVENT");
+        // The id has no line, so repeated messages in a file need it to stay unique
+        push_line(
+            &mut out,
+            &format!("UID:{}:{}@todo-scan", escape_text(&item.id()), item.line),
+        );
+        push_line(&mut out, &stamp);
+        push_line(
+            &mut out,
+            &format!("DTSTART;VALUE=DATE:{}", ics_date(deadline)),
+        );
+        push_line(
+            &mut out,
+            &format!(
+                "SUMMARY:{}",
+                escape_text(&format!("{}: {}", item.tag, item.message))
+            ),
+        );
+        push_line(
+            &mut out,
+            &format!(
+                "DESCRIPTION:{}",
+                escape_text(&format!("{}:{}", item.location(), item.line))
+            ),
+        );
+        push_line(&mut out, "END:VEVENT");
+    }
+
+    push_line(&mut out, "END:VCALENDAR");
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_helpers::helpers::make_item;
+
+    fn today() -> Deadline {
+        Deadline {
+            year: 2025,
+            month: 1,
+            day: 15,
+        }
+    }
+
+    #[test]
+    fn test_event_per_deadline_item() {
+        let mut due = make_item("src/a.rs", 3, Tag::Todo, "ship it");
+        due.deadline = Some(Deadline {
+            year: 2025,
+            month: 6,
+            day: 1,
+        });
+        let undated = make_item("src/b.rs", 1, Tag::Todo, "someday");
+
+        let ics = format_deadlines(&[due, undated], &today());
+        assert!(ics.starts_with("BEGIN:VCALENDAR\r\n"));
+        assert!(ics.ends_with("END:VCALENDAR\r\n"));
+        assert_eq!(ics.matches("BEGIN:VEVENT").count(), 1);
+        assert!(ics.contains("\r\nDTSTART;VALUE=DATE:20250601\r\n"));
+        assert!(ics.contains("\r\nDTSTAMP:20250115T000000Z\r\n"));
+        assert!(ics.contains("\r\nSUMMARY:TODO: ship it\r\n"));
+        assert!(ics.contains("\r\nDESCRIPTION:src/a.rs:3\r\n"));
+    }
+
+    #[test]
+    fn test_uid_unique_for_repeated_message_in_file() {
+        let items: Vec<TodoItem> = [3, 9]
+            .into_iter()
+            .map(|line| {
+                let mut item = make_item("src/a.rs", line, Tag::Todo, "ship it");
+                item.deadline = Some(today());
+                item
+            })
+            .collect();
+
+        let ics = format_deadlines(&items, &today());
+        let uids: Vec<&str> = ics.lines().filter(|l| l.starts_with("UID:")).collect();
+        assert_eq!(uids.len(), 2);
+        assert_ne!(uids[0], uids[1]);
+    }
+
+    #[test]
+    fn test_summary_escapes_special_characters() {
+        let mut item = make_item("a.rs", 1, Tag::Fixme, "a, b; c\\d");
+        item.deadline = Some(today());
+
+        let ics = format_deadlines(&[item], &today());
+        assert!(
+            ics.contains("\r\nSUMMARY:FIXME: a\\, b\\; c\\\\d\r\n"),
+            "got: {ics}"
+        );
+    }
+
+    #[test]
+    fn test_long_lines_are_folded() {
+        let mut item = make_item("a.rs", 1, Tag::Todo, &"é".repeat(60));
+        item.deadline = Some(today());
+
+        let ics = format_deadlines(&[item], &today());
+        for line in ics.split("\r\n") {
+            assert!(line.len() <= MAX_LINE_OCTETS, "line too long: {line}");
+        }
+        let unfolded = ics.replace("\r\n ", "");
+        assert!(unfolded.contains(&format!("SUMMARY:TODO: {}\r\n", "é".repeat(60))));
+    }
+}
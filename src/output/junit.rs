@@ -0,0 +1,211 @@
+use std::collections::BTreeMap;
+
+use crate::model::*;
+
+/// Escape text for XML attribute values and character data.
+fn escape_xml(s: &str) -> String {
+    let mut out = String::with_capacity(s.len());
+    for c in s.chars() {
+        match c {
+            '&' => out.push_str("&amp;"),
+            '<' => out.push_str("&lt;"),
+            '>' => out.push_str("&gt;"),
+            '"' => out.push_str("&quot;"),
+            '\'' => out.push_str("&apos;"),
+            c => out.push(c),
+        }
+    }
+    out
+}
+
+/// Render a diff as a JUnit report: every added TODO is a failing test case
+/// (new debt) and every removed TODO a passing one (resolved debt). The suite
+/// is named after the base ref.
+pub fn format_diff(result: &DiffResult) -> String {
+    let tests = result.entries.len();
+    let failures = result.added_count;
+    let suite = escape_xml(&format!("todo-scan diff {}", result.base_ref));
+
+    let mut out = String::from("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n");
+    out.push_str(&format!(
+        "<testsuites name=\"{suite}\" tests=\"{tests}\" failures=\"{failures}\">\n"
+    ));
+    out.push_str(&format!(
+        "  <testsuite name=\"{suite}\" tests=\"{tests}\" failures=\"{failures}\" errors=\"0\" skipped=\"0\">\n"
+    ));
+
+    for entry in &result.entries {
+        push_testcase(&mut out, entry, &result.base_ref);
+    }
+
+    out.push_str("  </testsuite>\n");
+    out.push_str("</testsuites>\n");
+    out
+}
+
+/// Render a diff as a JUnit report with one `<testsuite>` per file, for CI
+/// UIs that group results by suite. Test cases are the same as in
+/// [`format_diff`]; suites are ordered by file path.
+pub fn format_diff_by_file(result: &DiffResult) -> String {
+    let mut by_file: BTreeMap<&str, Vec<&DiffEntry>> = BTreeMap::new();
+    for entry in &result.entries {
+        by_file.entry(&entry.item.file).or_default().push(entry);
+    }
+
+    let tests = result.entries.len();
+    let failures = result.added_count;
+    let name = escape_xml(&format!("todo-scan diff {}", result.base_ref));
+
+    let mut out = String::from("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n");
+    out.push_str(&format!(
+        "<testsuites name=\"{name}\" tests=\"{tests}\" failures=\"{failures}\">\n"
+    ));
+
+    for (file, entries) in &by_file {
+        let suite = escape_xml(file);
+        let tests = entries.len();
+        let failures = entries
+            .iter()
+            .filter(|e| matches!(e.status, DiffStatus::Added))
+            .count();
+        out.push_str(&format!(
+            "  <testsuite name=\"{suite}\" tests=\"{tests}\" failures=\"{failures}\" errors=\"0\" skipped=\"0\">\n"
+        ));
+        for entry in entries {
+            push_testcase(&mut out, entry, &result.base_ref);
+        }
+        out.push_str("  </testsuite>\n");
+    }
+
+    out.push_str("</testsuites>\n");
+    out
+}
+
+/// Append one test case: failing for an added TODO, passing for a removed one.
+fn push_testcase(out: &mut String, entry: &DiffEntry, base_ref: &str) {
+    let item = &entry.item;
+    let classname = escape_xml(&item.file);
+    let name = escape_xml(&format!(
+        "{}:{} [{}] {}",
+        item.location(),
+        item.line,
+        item.tag,
+        item.message
+    ));
+    match entry.status {
+        DiffStatus::Added => {
+            out.push_str(&format!(
+                "    <testcase classname=\"{classname}\" name=\"{name}\">\n"
+            ));
+            out.push_str(&format!(
+                "      <failure type=\"added\" message=\"New {} since {}\">{}</failure>\n",
+                item.tag,
+                escape_xml(base_ref),
+                escape_xml(&item.message)
+            ));
+            out.push_str("    </testcase>\n");
+        }
+        DiffStatus::Removed => {
+            out.push_str(&format!(
+                "    <testcase classname=\"{classname}\" name=\"{name}\"/>\n"
+            ));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_helpers::helpers::make_item;
+
+    fn sample_diff() -> DiffResult {
+        DiffResult {
+            entries: vec![
+                DiffEntry {
+                    status: DiffStatus::Added,
+                    item: make_item("src/a.rs", 3, Tag::Todo, "handle <empty> & \"null\""),
+                },
+                DiffEntry {
+                    status: DiffStatus::Added,
+                    item: make_item("src/b.rs", 7, Tag::Bug, "crash"),
+                },
+                DiffEntry {
+                    status: DiffStatus::Removed,
+                    item: make_item("src/c.rs", 1, Tag::Fixme, "old fix"),
+                },
+            ],
+            added_count: 2,
+            removed_count: 1,
+            base_ref: "main".to_string(),
+        }
+    }
+
+    #[test]
+    fn test_added_entries_are_failures() {
+        let output = format_diff(&sample_diff());
+        assert_eq!(output.matches("<testcase ").count(), 3);
+        assert_eq!(output.matches("<failure ").count(), 2);
+        assert!(output.contains(
+            "<testcase classname=\"src/b.rs\" name=\"src/b.rs:7 [BUG] crash\">\n      <failure type=\"added\" message=\"New BUG since main\">crash</failure>"
+        ));
+        // Removed entries pass
+        assert!(output
+            .contains("<testcase classname=\"src/c.rs\" name=\"src/c.rs:1 [FIXME] old fix\"/>"));
+    }
+
+    #[test]
+    fn test_suite_counts_and_name() {
+        let output = format_diff(&sample_diff());
+        assert!(output.starts_with("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n"));
+        assert!(output.contains(
+            "<testsuite name=\"todo-scan diff main\" tests=\"3\" failures=\"2\" errors=\"0\" skipped=\"0\">"
+        ));
+        assert!(output.trim_end().ends_with("</testsuites>"));
+    }
+
+    #[test]
+    fn test_special_characters_are_escaped() {
+        let output = format_diff(&sample_diff());
+        assert!(output.contains("handle &lt;empty&gt; &amp; &quot;null&quot;"));
+        assert!(!output.contains("<empty>"));
+    }
+
+    #[test]
+    fn test_by_file_emits_one_suite_per_file() {
+        let diff = DiffResult {
+            entries: vec![
+                DiffEntry {
+                    status: DiffStatus::Added,
+                    item: make_item("src/b.rs", 7, Tag::Bug, "crash"),
+                },
+                DiffEntry {
+                    status: DiffStatus::Added,
+                    item: make_item("src/a.rs", 3, Tag::Todo, "handle empty"),
+                },
+                DiffEntry {
+                    status: DiffStatus::Removed,
+                    item: make_item("src/a.rs", 9, Tag::Note, "gone"),
+                },
+            ],
+            added_count: 2,
+            removed_count: 1,
+            base_ref: "main".to_string(),
+        };
+
+        let output = format_diff_by_file(&diff);
+        assert_eq!(output.matches("<testsuite ").count(), 2);
+        assert!(
+            output.contains("<testsuites name=\"todo-scan diff main\" tests=\"3\" failures=\"2\">")
+        );
+        assert!(output.contains(
+            "<testsuite name=\"src/a.rs\" tests=\"2\" failures=\"1\" errors=\"0\" skipped=\"0\">"
+        ));
+        assert!(output.contains(
+            "<testsuite name=\"src/b.rs\" tests=\"1\" failures=\"1\" errors=\"0\" skipped=\"0\">"
+        ));
+        // Suites are sorted by path and hold only their own file's cases
+        let b_suite = output.find("<testsuite name=\"src/b.rs\"").unwrap();
+        assert!(output.find("[NOTE] gone").unwrap() < b_suite);
+        assert!(output.find("[BUG] crash").unwrap() > b_suite);
+    }
+}
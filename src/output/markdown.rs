@@ -1,3 +1,5 @@
+use super::OutputOptions;
+use crate::cli::Column;
 use crate::model::*;
 
 /// Escape characters that break markdown table cells.
@@ -18,71 +20,192 @@ fn priority_str(priority: &Priority) -> &'static str {
     }
 }
 
-pub fn format_list(result: &ScanResult) -> String {
-    let mut lines: Vec<String> = Vec::new();
+const LIST_COLUMNS: [Column; 8] = [
+    Column::File,
+    Column::Line,
+    Column::Tag,
+    Column::Priority,
+    Column::Message,
+    Column::Author,
+    Column::IssueRef,
+    Column::Deadline,
+];
 
-    lines
-        .push("| File | Line | Tag | Priority | Message | Author | Issue | Deadline |".to_string());
-    lines
-        .push("|------|------|-----|----------|---------|--------|-------|----------|".to_string());
-
-    for item in &result.items {
-        let file = escape_cell(&item.file);
-        let tag = item.tag.as_str();
-        let priority = priority_str(&item.priority);
-        let message = escape_cell(&item.message);
-        let author = item.author.as_deref().map(escape_cell).unwrap_or_default();
-        let issue = item
-            .issue_ref
-            .as_deref()
-            .map(escape_cell)
-            .unwrap_or_default();
-        let deadline = item
-            .deadline
-            .as_ref()
-            .map(|d| escape_cell(&d.to_string()))
-            .unwrap_or_default();
-        lines.push(format!(
-            "| {file} | {} | {tag} | {priority} | {message} | {author} | {issue} | {deadline} |",
-            item.line
+fn column_title(column: Column) -> &'static str {
+    match column {
+        Column::File => "File",
+        Column::Line => "Line",
+        Column::Tag => "Tag",
+        Column::Priority => "Priority",
+        Column::Message => "Message",
+        Column::Author => "Author",
+        Column::IssueRef => "Issue",
+        Column::Deadline => "Deadline",
+        Column::ContextBefore => "Context Before",
+        Column::ContextAfter => "Context After",
+    }
+}
+
+/// The header and separator rows of a list table.
+fn list_header(columns: &[Column]) -> [String; 2] {
+    let titles: Vec<&str> = columns.iter().map(|&c| column_title(c)).collect();
+    let dashes: Vec<String> = titles.iter().map(|t| "-".repeat(t.len() + 2)).collect();
+    [
+        format!("| {} |", titles.join(" | ")),
+        format!("|{}|", dashes.join("|")),
+    ]
+}
+
+fn list_row(item: &TodoItem, columns: &[Column], out: &OutputOptions) -> String {
+    let cells: Vec<String> = columns
+        .iter()
+        .map(|column| match column {
+            Column::File => escape_cell(&item.location()),
+            Column::Line => item.line.to_string(),
+            Column::Tag => escape_cell(super::tag_label(&item.tag, out)),
+            Column::Priority => priority_str(&item.priority).to_string(),
+            Column::Message => escape_cell(&item.message),
+            Column::Author => item
+                .author
+                .as_deref()
+                .map(|a| {
+                    escape_cell(&super::author_mention(a, out).unwrap_or_else(|| a.to_string()))
+                })
+                .unwrap_or_default(),
+            Column::IssueRef => item
+                .issue_ref
+                .as_deref()
+                .map(escape_cell)
+                .unwrap_or_default(),
+            Column::Deadline => item
+                .deadline
+                .as_ref()
+                .map(|d| escape_cell(&super::display_deadline(d, out)))
+                .unwrap_or_default(),
+            // No context is collected for markdown; `list` rejects these columns
+            Column::ContextBefore | Column::ContextAfter => String::new(),
+        })
+        .collect();
+    format!("| {} |", cells.join(" | "))
+}
+
+/// Escape a shields.io static badge path segment.
+fn badge_text(s: &str) -> String {
+    s.replace('-', "--").replace('_', "__").replace(' ', "%20")
+}
+
+fn badge(label: &str, count: usize, color: &str) -> String {
+    format!(
+        "![{label}: {count}](https://img.shields.io/badge/{}-{count}-{color})",
+        badge_text(label)
+    )
+}
+
+/// One line of shields.io badges: total TODOs (blue), urgent and stale items
+/// (green at zero, red/orange otherwise). The stale badge is left out when
+/// no stale count is known.
+fn format_badges(counts: &BadgeCounts) -> String {
+    let mut badges = vec![
+        badge("TODOs", counts.total, "blue"),
+        badge(
+            "urgent",
+            counts.urgent,
+            if counts.urgent > 0 {
+                "red"
+            } else {
+                "brightgreen"
+            },
+        ),
+    ];
+    if let Some(stale) = counts.stale {
+        badges.push(badge(
+            "stale",
+            stale,
+            if stale > 0 { "orange" } else { "brightgreen" },
         ));
     }
+    badges.join(" ")
+}
+
+/// Render the list table. With `collapsible`, the table and its count line are
+/// wrapped in a `<details>` block whose summary shows the item count. With
+/// `by_tag_section`, each tag gets its own `## TAG` heading and table, most
+/// severe tag first; tags without items are omitted. `badges` adds a line of
+/// count badges before everything else. `columns` picks the table columns and
+/// their order; empty means the default set.
+pub fn format_list(
+    result: &ScanResult,
+    out: &OutputOptions,
+    collapsible: bool,
+    by_tag_section: bool,
+    badges: Option<&BadgeCounts>,
+    columns: &[Column],
+) -> String {
+    let columns = if columns.is_empty() {
+        &LIST_COLUMNS[..]
+    } else {
+        columns
+    };
+    let header = list_header(columns);
+    let mut lines: Vec<String> = Vec::new();
+
+    if let Some(counts) = badges {
+        lines.push(format_badges(counts));
+        lines.push(String::new());
+    }
+
+    if collapsible {
+        lines.push("<details>".to_string());
+        lines.push(format!("<summary>{} items</summary>", result.items.len()));
+        lines.push(String::new());
+    }
+
+    if by_tag_section {
+        let mut tags: Vec<Tag> = Vec::new();
+        for item in &result.items {
+            if !tags.contains(&item.tag) {
+                tags.push(item.tag);
+            }
+        }
+        tags.sort_by_key(|t| std::cmp::Reverse(t.severity()));
+        for tag in tags {
+            lines.push(format!("## {}", super::tag_label(&tag, out)));
+            lines.push(String::new());
+            lines.extend(header.iter().cloned());
+            lines.extend(
+                result
+                    .items
+                    .iter()
+                    .filter(|item| item.tag == tag)
+                    .map(|item| list_row(item, columns, out)),
+            );
+            lines.push(String::new());
+        }
+    } else {
+        lines.extend(header.iter().cloned());
+        lines.extend(result.items.iter().map(|item| list_row(item, columns, out)));
+        lines.push(String::new());
+    }
 
-    lines.push(String::new());
     lines.push(format!("**{} items found**", result.items.len()));
     lines.push(String::new());
+    if collapsible {
+        lines.push("</details>".to_string());
+        lines.push(String::new());
+    }
     lines.join("\n")
 }
 
-pub fn format_search(result: &SearchResult) -> String {
+pub fn format_search(result: &SearchResult, out: &OutputOptions) -> String {
     let mut lines: Vec<String> = Vec::new();
 
-    lines
-        .push("| File | Line | Tag | Priority | Message | Author | Issue | Deadline |".to_string());
-    lines
-        .push("|------|------|-----|----------|---------|--------|-------|----------|".to_string());
-
-    for item in &result.items {
-        let file = escape_cell(&item.file);
-        let tag = item.tag.as_str();
-        let priority = priority_str(&item.priority);
-        let message = escape_cell(&item.message);
-        let author = item.author.as_deref().map(escape_cell).unwrap_or_default();
-        let issue = item
-            .issue_ref
-            .as_deref()
-            .map(escape_cell)
-            .unwrap_or_default();
-        let deadline = item
-            .deadline
-            .as_ref()
-            .map(|d| escape_cell(&d.to_string()))
-            .unwrap_or_default();
-        lines.push(format!(
-            "| {file} | {} | {tag} | {priority} | {message} | {author} | {issue} | {deadline} |",
-            item.line
-        ));
-    }
+    lines.extend(list_header(&LIST_COLUMNS));
+    lines.extend(
+        result
+            .items
+            .iter()
+            .map(|item| list_row(item, &LIST_COLUMNS, out)),
+    );
 
     lines.push(String::new());
     lines.push(format!(
@@ -95,7 +218,7 @@ pub fn format_search(result: &SearchResult) -> String {
     lines.join("\n")
 }
 
-pub fn format_diff(result: &DiffResult) -> String {
+pub fn format_diff(result: &DiffResult, out: &OutputOptions) -> String {
     let mut lines: Vec<String> = Vec::new();
 
     lines.push("| Status | File | Line | Tag | Message |".to_string());
@@ -106,8 +229,8 @@ pub fn format_diff(result: &DiffResult) -> String {
             DiffStatus::Added => "+",
             DiffStatus::Removed => "-",
         };
-        let file = escape_cell(&entry.item.file);
-        let tag = entry.item.tag.as_str();
+        let file = escape_cell(&entry.item.location());
+        let tag = escape_cell(super::tag_label(&entry.item.tag, out));
         let message = escape_cell(&entry.item.message);
         lines.push(format!(
             "| {status} | {file} | {} | {tag} | {message} |",
@@ -126,29 +249,80 @@ pub fn format_diff(result: &DiffResult) -> String {
     lines.join("\n")
 }
 
-pub fn format_blame(result: &BlameResult) -> String {
+/// Render a diff as one row per tag with added/removed counts instead of
+/// listing every entry. Tags are ordered most severe first.
+pub fn format_diff_summary(result: &DiffResult, out: &OutputOptions) -> String {
+    let mut counts: Vec<(Tag, usize, usize)> = Vec::new();
+    for entry in &result.entries {
+        let idx = match counts.iter().position(|(tag, _, _)| *tag == entry.item.tag) {
+            Some(idx) => idx,
+            None => {
+                counts.push((entry.item.tag, 0, 0));
+                counts.len() - 1
+            }
+        };
+        match entry.status {
+            DiffStatus::Added => counts[idx].1 += 1,
+            DiffStatus::Removed => counts[idx].2 += 1,
+        }
+    }
+    counts.sort_by_key(|(tag, _, _)| std::cmp::Reverse(tag.severity()));
+
+    let mut lines: Vec<String> = Vec::new();
+    lines.push("| Tag | Added | Removed |".to_string());
+    lines.push("|-----|-------|---------|".to_string());
+    for (tag, added, removed) in &counts {
+        lines.push(format!(
+            "| {} | +{added} | -{removed} |",
+            escape_cell(super::tag_label(tag, out))
+        ));
+    }
+
+    lines.push(String::new());
+    lines.push(format!(
+        "**+{} -{}** (base: `{}`)",
+        result.added_count,
+        result.removed_count,
+        escape_cell(&result.base_ref)
+    ));
+    lines.push(String::new());
+    lines.join("\n")
+}
+
+pub fn format_blame(result: &BlameResult, out: &OutputOptions, humanize_age: bool) -> String {
     let mut lines: Vec<String> = Vec::new();
 
     lines.push("| File | Line | Tag | Message | Author | Date | Age (days) | Stale |".to_string());
     lines.push("|------|------|-----|---------|--------|------|------------|-------|".to_string());
 
     for entry in &result.entries {
-        let file = escape_cell(&entry.item.file);
-        let tag = entry.item.tag.as_str();
+        let file = escape_cell(&entry.item.location());
+        let tag = escape_cell(super::tag_label(&entry.item.tag, out));
         let message = escape_cell(&entry.item.message);
         let stale = if entry.stale { "Yes" } else { "" };
         let blame_author = escape_cell(&entry.blame.author);
-        let blame_date = escape_cell(&entry.blame.date);
+        let (blame_date, age) = if entry.blame.is_unknown() {
+            ("-".to_string(), "-".to_string())
+        } else {
+            (
+                escape_cell(&entry.blame.date),
+                entry.blame.age_days.to_string(),
+            )
+        };
         lines.push(format!(
-            "| {file} | {} | {tag} | {message} | {blame_author} | {blame_date} | {} | {stale} |",
-            entry.item.line, entry.blame.age_days,
+            "| {file} | {} | {tag} | {message} | {blame_author} | {blame_date} | {age} | {stale} |",
+            entry.item.line,
         ));
     }
 
     lines.push(String::new());
     lines.push(format!(
-        "**{} items, avg age {} days, {} stale** (threshold: {} days)",
-        result.total, result.avg_age_days, result.stale_count, result.stale_threshold_days,
+        "**{} items, avg age {}, {} stale{}** (threshold: {} days)",
+        result.total,
+        super::avg_age(result.avg_age_days, humanize_age),
+        result.stale_count,
+        super::unknown_suffix(result.unknown_count),
+        result.stale_threshold_days,
     ));
     lines.push(String::new());
     lines.join("\n")
@@ -157,7 +331,7 @@ pub fn format_blame(result: &BlameResult) -> String {
 pub fn format_lint(result: &LintResult) -> String {
     let mut lines: Vec<String> = Vec::new();
 
-    if result.passed {
+    if result.violations.is_empty() {
         lines.push("## PASS".to_string());
         lines.push(String::new());
         lines.push(format!(
@@ -165,7 +339,8 @@ pub fn format_lint(result: &LintResult) -> String {
             result.total_items
         ));
     } else {
-        lines.push("## FAIL".to_string());
+        let verdict = if result.passed { "PASS" } else { "FAIL" };
+        lines.push(format!("## {}", verdict));
         lines.push(String::new());
         lines.push("| File | Line | Rule | Message | Suggestion |".to_string());
         lines.push("|------|------|------|---------|------------|".to_string());
@@ -249,10 +424,11 @@ pub fn format_clean(result: &CleanResult) -> String {
 
         lines.push(String::new());
         lines.push(format!(
-            "**{} violations ({} stale, {} duplicates) in {} items**",
+            "**{} violations ({} stale, {} duplicates{}) in {} items**",
             result.violations.len(),
             result.stale_count,
             result.duplicate_count,
+            super::clean_extra_counts(result),
             result.total_items
         ));
     }
@@ -264,17 +440,23 @@ pub fn format_clean(result: &CleanResult) -> String {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::cli::Format;
 
     fn sample_item(tag: Tag, message: &str) -> TodoItem {
         TodoItem {
             file: "src/main.rs".to_string(),
             line: 10,
+            byte_offset: 0,
+            cell: None,
+            labels: vec![],
             tag,
             message: message.to_string(),
             author: None,
             issue_ref: None,
             priority: Priority::Normal,
             deadline: None,
+            ignore_reason: None,
+            severity: None,
         }
     }
 
@@ -283,35 +465,282 @@ mod tests {
         let result = ScanResult {
             items: vec![],
             files_scanned: 0,
+            files_with_items: 0,
+            bytes_scanned: 0,
             ignored_items: vec![],
+            truncated: false,
+            total_found: None,
         };
-        let output = format_list(&result);
+        let output = format_list(
+            &result,
+            &OutputOptions::new(Format::Markdown),
+            false,
+            false,
+            None,
+            &[],
+        );
         assert!(output
             .contains("| File | Line | Tag | Priority | Message | Author | Issue | Deadline |"));
         assert!(output.contains("**0 items found**"));
     }
 
+    #[test]
+    fn test_format_list_custom_columns() {
+        let result = ScanResult {
+            items: vec![TodoItem {
+                file: "lib.rs".to_string(),
+                line: 42,
+                byte_offset: 0,
+                cell: None,
+                labels: vec![],
+                tag: Tag::Todo,
+                message: "add tests".to_string(),
+                author: Some("alice".to_string()),
+                issue_ref: None,
+                priority: Priority::Normal,
+                deadline: None,
+                ignore_reason: None,
+                severity: None,
+            }],
+            files_scanned: 1,
+            files_with_items: 1,
+            bytes_scanned: 0,
+            ignored_items: vec![],
+            truncated: false,
+            total_found: None,
+        };
+        let output = format_list(
+            &result,
+            &OutputOptions::new(Format::Markdown),
+            false,
+            false,
+            None,
+            &[Column::Tag, Column::Message, Column::Line],
+        );
+        let lines: Vec<&str> = output.lines().collect();
+        assert_eq!(lines[0], "| Tag | Message | Line |");
+        assert_eq!(lines[1], "|-----|---------|------|");
+        assert_eq!(lines[2], "| TODO | add tests | 42 |");
+    }
+
     #[test]
     fn test_format_list_with_items() {
         let result = ScanResult {
             items: vec![TodoItem {
                 file: "lib.rs".to_string(),
                 line: 42,
+                byte_offset: 0,
+                cell: None,
+                labels: vec![],
                 tag: Tag::Todo,
                 message: "add tests".to_string(),
                 author: Some("alice".to_string()),
                 issue_ref: Some("#123".to_string()),
                 priority: Priority::High,
                 deadline: None,
+                ignore_reason: None,
+                severity: None,
             }],
             files_scanned: 1,
+            files_with_items: 1,
+            bytes_scanned: 0,
             ignored_items: vec![],
+            truncated: false,
+            total_found: None,
         };
-        let output = format_list(&result);
+        let output = format_list(
+            &result,
+            &OutputOptions::new(Format::Markdown),
+            false,
+            false,
+            None,
+            &[],
+        );
         assert!(output.contains("| lib.rs | 42 | TODO | ! | add tests | alice | #123 |  |"));
         assert!(output.contains("**1 items found**"));
     }
 
+    #[test]
+    fn test_format_list_collapsible_wraps_table() {
+        let result = ScanResult {
+            items: vec![TodoItem {
+                file: "lib.rs".to_string(),
+                line: 7,
+                byte_offset: 0,
+                cell: None,
+                labels: vec![],
+                tag: Tag::Fixme,
+                message: "tidy up".to_string(),
+                author: None,
+                issue_ref: None,
+                priority: Priority::Normal,
+                deadline: None,
+                ignore_reason: None,
+                severity: None,
+            }],
+            files_scanned: 1,
+            files_with_items: 1,
+            bytes_scanned: 0,
+            ignored_items: vec![],
+            truncated: false,
+            total_found: None,
+        };
+        let output = format_list(
+            &result,
+            &OutputOptions::new(Format::Markdown),
+            true,
+            false,
+            None,
+            &[],
+        );
+        let lines: Vec<&str> = output.lines().collect();
+        assert_eq!(lines[0], "<details>");
+        assert_eq!(lines[1], "<summary>1 items</summary>");
+        // GitHub needs a blank line before markdown inside <details>
+        assert_eq!(lines[2], "");
+        assert!(lines[3].starts_with("| File | Line |"));
+
+        let table = output.find("| lib.rs | 7 | FIXME |").unwrap();
+        let close = output.find("</details>").unwrap();
+        assert!(table < close);
+        assert!(output.find("**1 items found**").unwrap() < close);
+
+        assert!(!format_list(
+            &result,
+            &OutputOptions::new(Format::Markdown),
+            false,
+            false,
+            None,
+            &[]
+        )
+        .contains("<details>"));
+    }
+
+    #[test]
+    fn test_format_list_by_tag_section_orders_by_severity() {
+        let item = |file: &str, line, tag, message: &str| TodoItem {
+            file: file.to_string(),
+            line,
+            byte_offset: 0,
+            cell: None,
+            labels: vec![],
+            tag,
+            message: message.to_string(),
+            author: None,
+            issue_ref: None,
+            priority: Priority::Normal,
+            deadline: None,
+            ignore_reason: None,
+            severity: None,
+        };
+        let result = ScanResult {
+            items: vec![
+                item("a.rs", 1, Tag::Todo, "later"),
+                item("b.rs", 2, Tag::Bug, "crash"),
+                item("c.rs", 3, Tag::Fixme, "wrong"),
+                item("d.rs", 4, Tag::Todo, "also later"),
+            ],
+            files_scanned: 4,
+            files_with_items: 4,
+            bytes_scanned: 0,
+            ignored_items: vec![],
+            truncated: false,
+            total_found: None,
+        };
+        let output = format_list(
+            &result,
+            &OutputOptions::new(Format::Markdown),
+            false,
+            true,
+            None,
+            &[],
+        );
+
+        let bug = output.find("## BUG").unwrap();
+        let fixme = output.find("## FIXME").unwrap();
+        let todo = output.find("## TODO").unwrap();
+        assert!(bug < fixme && fixme < todo);
+        assert!(!output.contains("## HACK"));
+        assert!(!output.contains("## NOTE"));
+
+        let row = |text: &str| output.find(text).unwrap();
+        assert!(bug < row("| b.rs | 2 | BUG |") && row("| b.rs | 2 | BUG |") < fixme);
+        assert!(fixme < row("| c.rs | 3 | FIXME |") && row("| c.rs | 3 | FIXME |") < todo);
+        assert!(todo < row("| a.rs | 1 | TODO |"));
+        assert!(todo < row("| d.rs | 4 | TODO |"));
+        assert_eq!(output.matches("| File | Line |").count(), 3);
+        assert!(output.contains("**4 items found**"));
+    }
+
+    #[test]
+    fn test_format_list_badges_header() {
+        let result = ScanResult {
+            items: vec![TodoItem {
+                file: "a.rs".to_string(),
+                line: 1,
+                byte_offset: 0,
+                cell: None,
+                labels: vec![],
+                tag: Tag::Todo,
+                message: "later".to_string(),
+                author: None,
+                issue_ref: None,
+                priority: Priority::Normal,
+                deadline: None,
+                ignore_reason: None,
+                severity: None,
+            }],
+            files_scanned: 1,
+            files_with_items: 1,
+            bytes_scanned: 0,
+            ignored_items: vec![],
+            truncated: false,
+            total_found: None,
+        };
+        let counts = BadgeCounts {
+            total: 12,
+            urgent: 3,
+            stale: Some(0),
+        };
+        let output = format_list(
+            &result,
+            &OutputOptions::new(Format::Markdown),
+            false,
+            false,
+            Some(&counts),
+            &[],
+        );
+        let first = output.lines().next().unwrap();
+        assert_eq!(
+            first,
+            "![TODOs: 12](https://img.shields.io/badge/TODOs-12-blue) \
+             ![urgent: 3](https://img.shields.io/badge/urgent-3-red) \
+             ![stale: 0](https://img.shields.io/badge/stale-0-brightgreen)"
+        );
+        assert!(output.starts_with(&format!("{first}\n\n| File |")));
+
+        let counts = BadgeCounts {
+            total: 1,
+            urgent: 0,
+            stale: None,
+        };
+        let output = format_list(
+            &result,
+            &OutputOptions::new(Format::Markdown),
+            false,
+            false,
+            Some(&counts),
+            &[],
+        );
+        assert!(output.contains("https://img.shields.io/badge/urgent-0-brightgreen"));
+        assert!(!output.contains("stale"));
+    }
+
+    #[test]
+    fn test_badge_text_escapes_shields_separators() {
+        assert_eq!(badge_text("to-do_list x"), "to--do__list%20x");
+    }
+
     #[test]
     fn test_escape_cell_replaces_newline_with_space() {
         assert_eq!(escape_cell("line1\nline2"), "line1 line2");
@@ -343,17 +772,33 @@ mod tests {
             items: vec![TodoItem {
                 file: "test.rs".to_string(),
                 line: 1,
+                byte_offset: 0,
+                cell: None,
+                labels: vec![],
                 tag: Tag::Todo,
                 message: "task".to_string(),
                 author: Some("user\ninjected".to_string()),
                 issue_ref: None,
                 priority: Priority::Normal,
                 deadline: None,
+                ignore_reason: None,
+                severity: None,
             }],
             files_scanned: 1,
+            files_with_items: 1,
+            bytes_scanned: 0,
             ignored_items: vec![],
+            truncated: false,
+            total_found: None,
         };
-        let output = format_list(&result);
+        let output = format_list(
+            &result,
+            &OutputOptions::new(Format::Markdown),
+            false,
+            false,
+            None,
+            &[],
+        );
         assert!(output.contains("user injected"));
         assert!(!output.contains("user\ninjected"));
     }
@@ -364,17 +809,33 @@ mod tests {
             items: vec![TodoItem {
                 file: "test.rs".to_string(),
                 line: 1,
+                byte_offset: 0,
+                cell: None,
+                labels: vec![],
                 tag: Tag::Todo,
                 message: "task".to_string(),
                 author: None,
                 issue_ref: Some("[link](evil)".to_string()),
                 priority: Priority::Normal,
                 deadline: None,
+                ignore_reason: None,
+                severity: None,
             }],
             files_scanned: 1,
+            files_with_items: 1,
+            bytes_scanned: 0,
             ignored_items: vec![],
+            truncated: false,
+            total_found: None,
         };
-        let output = format_list(&result);
+        let output = format_list(
+            &result,
+            &OutputOptions::new(Format::Markdown),
+            false,
+            false,
+            None,
+            &[],
+        );
         assert!(output.contains("\\[link\\]"));
         assert!(!output.contains("[link](evil)"));
     }
@@ -396,9 +857,10 @@ mod tests {
             total: 1,
             avg_age_days: 10,
             stale_count: 0,
+            unknown_count: 0,
             stale_threshold_days: 180,
         };
-        let output = format_blame(&result);
+        let output = format_blame(&result, &OutputOptions::new(Format::Markdown), false);
         assert!(output.contains("user\\|inject"));
     }
 
@@ -412,6 +874,7 @@ mod tests {
                 file: "test.rs".to_string(),
                 line: 1,
                 rule: "no`bare".to_string(),
+                severity: Severity::Error,
                 message: "msg".to_string(),
                 suggestion: Some("use [this]".to_string()),
             }],
@@ -426,9 +889,20 @@ mod tests {
         let result = ScanResult {
             items: vec![sample_item(Tag::Todo, "a | b")],
             files_scanned: 1,
+            files_with_items: 1,
+            bytes_scanned: 0,
             ignored_items: vec![],
+            truncated: false,
+            total_found: None,
         };
-        let output = format_list(&result);
+        let output = format_list(
+            &result,
+            &OutputOptions::new(Format::Markdown),
+            false,
+            false,
+            None,
+            &[],
+        );
         assert!(output.contains("a \\| b"));
     }
 
@@ -449,12 +923,49 @@ mod tests {
             removed_count: 1,
             base_ref: "main".to_string(),
         };
-        let output = format_diff(&result);
+        let output = format_diff(&result, &OutputOptions::new(Format::Markdown));
         assert!(output.contains("| + | src/main.rs | 10 | FIXME | new fix |"));
         assert!(output.contains("| - | src/main.rs | 10 | TODO | old task |"));
         assert!(output.contains("**+1 -1** (base: `main`)"));
     }
 
+    #[test]
+    fn test_format_diff_summary_counts_per_tag() {
+        let entry = |status, tag, msg| DiffEntry {
+            status,
+            item: sample_item(tag, msg),
+        };
+        let result = DiffResult {
+            entries: vec![
+                entry(DiffStatus::Added, Tag::Todo, "a"),
+                entry(DiffStatus::Added, Tag::Todo, "b"),
+                entry(DiffStatus::Removed, Tag::Todo, "c"),
+                entry(DiffStatus::Added, Tag::Bug, "d"),
+                entry(DiffStatus::Removed, Tag::Fixme, "e"),
+                entry(DiffStatus::Removed, Tag::Fixme, "f"),
+            ],
+            added_count: 3,
+            removed_count: 3,
+            base_ref: "main".to_string(),
+        };
+        let output = format_diff_summary(&result, &OutputOptions::new(Format::Markdown));
+
+        let rows: Vec<&str> = output
+            .lines()
+            .filter(|l| l.starts_with("| ") && !l.starts_with("| Tag"))
+            .collect();
+        assert_eq!(
+            rows,
+            vec![
+                "| BUG | +1 | -0 |",
+                "| FIXME | +0 | -2 |",
+                "| TODO | +2 | -1 |"
+            ]
+        );
+        assert!(!output.contains("| a |"));
+        assert!(output.contains("**+3 -3** (base: `main`)"));
+    }
+
     #[test]
     fn test_format_check_pass() {
         let result = CheckResult {
@@ -491,7 +1002,7 @@ mod tests {
             match_count: 0,
             file_count: 0,
         };
-        let output = format_search(&result);
+        let output = format_search(&result, &OutputOptions::new(Format::Markdown));
         assert!(
             output.contains("\\[inject\\]"),
             "query should have brackets escaped, got: {}",
@@ -511,17 +1022,22 @@ mod tests {
             items: vec![TodoItem {
                 file: "lib.rs".to_string(),
                 line: 5,
+                byte_offset: 0,
+                cell: None,
+                labels: vec![],
                 tag: Tag::Fixme,
                 message: "fix this".to_string(),
                 author: Some("bob".to_string()),
                 issue_ref: Some("#42".to_string()),
                 priority: Priority::Urgent,
                 deadline: None,
+                ignore_reason: None,
+                severity: None,
             }],
             match_count: 1,
             file_count: 1,
         };
-        let output = format_search(&result);
+        let output = format_search(&result, &OutputOptions::new(Format::Markdown));
         assert!(output.contains("| lib.rs | 5 | FIXME | !! | fix this | bob | #42 |"));
         assert!(output.contains("**1 matches across 1 files**"));
     }
@@ -543,12 +1059,37 @@ mod tests {
             total: 1,
             avg_age_days: 700,
             stale_count: 1,
+            unknown_count: 0,
             stale_threshold_days: 365,
         };
-        let output = format_blame(&result);
+        let output = format_blame(&result, &OutputOptions::new(Format::Markdown), false);
         assert!(output
             .contains("| src/main.rs | 10 | TODO | old task | alice | 2023-01-01 | 700 | Yes |"));
         assert!(output.contains("**1 items, avg age 700 days, 1 stale**"));
+
+        // Humanized summary; the per-entry age column stays in days
+        let output = format_blame(&result, &OutputOptions::new(Format::Markdown), true);
+        assert!(output.contains("| 700 | Yes |"));
+        assert!(output.contains("**1 items, avg age 1.9 years, 1 stale**"));
+    }
+
+    #[test]
+    fn test_format_blame_unknown_entry() {
+        let result = BlameResult {
+            entries: vec![BlameEntry {
+                item: sample_item(Tag::Todo, "untracked"),
+                blame: BlameInfo::unknown(),
+                stale: false,
+            }],
+            total: 1,
+            avg_age_days: 0,
+            stale_count: 0,
+            unknown_count: 1,
+            stale_threshold_days: 365,
+        };
+        let output = format_blame(&result, &OutputOptions::new(Format::Markdown), false);
+        assert!(output.contains("| src/main.rs | 10 | TODO | untracked | unknown | - | - |  |"));
+        assert!(output.contains("**1 items, avg age 0 days, 0 stale, 1 unknown**"));
     }
 
     #[test]
@@ -568,9 +1109,10 @@ mod tests {
             total: 1,
             avg_age_days: 10,
             stale_count: 0,
+            unknown_count: 0,
             stale_threshold_days: 365,
         };
-        let output = format_blame(&result);
+        let output = format_blame(&result, &OutputOptions::new(Format::Markdown), false);
         assert!(
             output.contains("|  |"),
             "non-stale entry should have empty stale column"
@@ -600,6 +1142,7 @@ mod tests {
                 file: "test.rs".to_string(),
                 line: 5,
                 rule: "no_bare_tags".to_string(),
+                severity: Severity::Error,
                 message: "bare tag found".to_string(),
                 suggestion: Some("add a description".to_string()),
             }],
@@ -622,6 +1165,7 @@ mod tests {
                 file: "test.rs".to_string(),
                 line: 5,
                 rule: "uppercase_tag".to_string(),
+                severity: Severity::Error,
                 message: "tag not uppercase".to_string(),
                 suggestion: None,
             }],
@@ -637,6 +1181,8 @@ mod tests {
             total_items: 3,
             stale_count: 0,
             duplicate_count: 0,
+            deadline_without_owner_count: 0,
+            stale_age_count: 0,
             violations: vec![],
         };
         let output = format_clean(&result);
@@ -651,6 +1197,8 @@ mod tests {
             total_items: 2,
             stale_count: 0,
             duplicate_count: 1,
+            deadline_without_owner_count: 0,
+            stale_age_count: 0,
             violations: vec![CleanViolation {
                 file: "test.rs".to_string(),
                 line: 10,
@@ -672,6 +1220,8 @@ mod tests {
             total_items: 1,
             stale_count: 1,
             duplicate_count: 0,
+            deadline_without_owner_count: 0,
+            stale_age_count: 0,
             violations: vec![CleanViolation {
                 file: "test.rs".to_string(),
                 line: 10,
@@ -692,6 +1242,8 @@ mod tests {
             total_items: 1,
             stale_count: 0,
             duplicate_count: 0,
+            deadline_without_owner_count: 0,
+            stale_age_count: 0,
             violations: vec![CleanViolation {
                 file: "test.rs".to_string(),
                 line: 10,
@@ -705,6 +1257,30 @@ mod tests {
         assert!(output.contains("| test.rs | 10 | some_rule | violation |  |"));
     }
 
+    #[test]
+    fn test_format_clean_fail_with_deadline_without_owner() {
+        let result = CleanResult {
+            passed: false,
+            total_items: 1,
+            stale_count: 0,
+            duplicate_count: 0,
+            deadline_without_owner_count: 1,
+            stale_age_count: 0,
+            violations: vec![CleanViolation {
+                file: "test.rs".to_string(),
+                line: 10,
+                rule: "deadline_without_owner".to_string(),
+                message: "ship it".to_string(),
+                issue_ref: None,
+                duplicate_of: None,
+            }],
+        };
+        let output = format_clean(&result);
+        assert!(
+            output.contains("**1 violations (0 stale, 0 duplicates, 1 without owner) in 1 items**")
+        );
+    }
+
     #[test]
     fn test_priority_str_values() {
         assert_eq!(priority_str(&Priority::Normal), "");
@@ -719,6 +1295,9 @@ mod tests {
             items: vec![TodoItem {
                 file: "test.rs".to_string(),
                 line: 1,
+                byte_offset: 0,
+                cell: None,
+                labels: vec![],
                 tag: Tag::Todo,
                 message: "task".to_string(),
                 author: None,
@@ -729,11 +1308,24 @@ mod tests {
                     month: 6,
                     day: 15,
                 }),
+                ignore_reason: None,
+                severity: None,
             }],
             files_scanned: 1,
+            files_with_items: 1,
+            bytes_scanned: 0,
             ignored_items: vec![],
+            truncated: false,
+            total_found: None,
         };
-        let output = format_list(&result);
+        let output = format_list(
+            &result,
+            &OutputOptions::new(Format::Markdown),
+            false,
+            false,
+            None,
+            &[],
+        );
         assert!(output.contains("2025-06-15"));
     }
 }
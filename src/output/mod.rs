@@ -1,16 +1,165 @@
+mod clang;
+mod codeclimate;
+mod csv;
 mod github_actions;
 pub mod html;
+mod ics;
+mod junit;
 mod markdown;
+mod prometheus;
 mod sarif;
 
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 
 use colored::*;
 
-use crate::cli::{DetailLevel, Format, GroupBy};
+use crate::cli::{BriefPrefer, Column, DetailLevel, Format, GroupBy};
 use crate::context::{ContextInfo, RichContext};
+use crate::deadline::Deadline;
 use crate::model::*;
-use std::path::Path;
+use crate::profile::Profiler;
+use crate::severity::SeverityOverrides;
+
+/// How results are rendered: the `--format` and the config and CLI settings
+/// that shape it, built once per run and passed to every printer.
+pub struct OutputOptions {
+    pub format: Format,
+    pub severity: SeverityOverrides,
+    /// Wrap all JSON output under `{ key: <value> }`.
+    pub json_root_key: Option<String>,
+    /// Display names from the `tag_labels` config, used by text, markdown and
+    /// HTML output.
+    pub tag_labels: HashMap<Tag, String>,
+    /// Item ids from `--sarif-baseline`, if given.
+    pub sarif_baseline: Option<HashSet<String>>,
+    /// strftime-style `date_format` for deadlines in text, markdown and HTML
+    /// output.
+    pub date_format: Option<String>,
+    /// `--author-mention-format` template for authors in text and markdown
+    /// output; `{author}` is replaced with the author name.
+    pub author_mention: Option<String>,
+    /// `github_actions_emoji` prefixes put before the `[TAG]` of annotations.
+    pub github_actions_emoji: HashMap<Tag, String>,
+    /// `trailing_newline` policy for files written with [`write_file`].
+    pub trailing_newline: Option<bool>,
+    /// `--profile` phase timings.
+    pub profiler: Profiler,
+}
+
+impl OutputOptions {
+    /// Options for `format` with every setting at its default.
+    pub fn new(format: Format) -> Self {
+        Self {
+            format,
+            severity: SeverityOverrides::default(),
+            json_root_key: None,
+            tag_labels: HashMap::new(),
+            sarif_baseline: None,
+            date_format: None,
+            author_mention: None,
+            github_actions_emoji: HashMap::new(),
+            trailing_newline: None,
+            profiler: Profiler::new(false),
+        }
+    }
+}
+
+pub use sarif::load_baseline as load_sarif_baseline;
+
+/// Parse a config table keyed by tag name; `key` names the table in errors.
+pub fn parse_tag_map(
+    map: &HashMap<String, String>,
+    key: &str,
+) -> anyhow::Result<HashMap<Tag, String>> {
+    map.iter()
+        .map(|(tag, value)| {
+            let tag = tag
+                .parse::<Tag>()
+                .map_err(|_| anyhow::anyhow!("Unknown tag in {}: {}", key, tag))?;
+            Ok((tag, value.clone()))
+        })
+        .collect()
+}
+
+/// Reject an `--author-mention-format` template without `{author}`.
+pub fn check_author_mention_format(template: &str) -> anyhow::Result<()> {
+    anyhow::ensure!(
+        template.contains("{author}"),
+        "--author-mention-format must contain {{author}}, e.g. \"<@{{author}}>\""
+    );
+    Ok(())
+}
+
+/// `author` rendered through `template`, if one is set.
+fn mention_in(template: Option<&str>, author: &str) -> Option<String> {
+    template.map(|t| t.replace("{author}", author))
+}
+
+/// `author` rendered through the `--author-mention-format` template, if any.
+fn author_mention(author: &str, out: &OutputOptions) -> Option<String> {
+    mention_in(out.author_mention.as_deref(), author)
+}
+
+/// A deadline as shown to people, honoring `date_format`.
+fn display_deadline(deadline: &Deadline, out: &OutputOptions) -> String {
+    match &out.date_format {
+        Some(fmt) => deadline.format_with(fmt),
+        None => deadline.to_string(),
+    }
+}
+
+/// `content` ending in exactly one newline, or none, per `policy`; unchanged
+/// without a policy.
+fn with_trailing_newline(content: &str, policy: Option<bool>) -> String {
+    match policy {
+        Some(true) => format!("{}\n", content.trim_end_matches(['\n', '\r'])),
+        Some(false) => content.trim_end_matches(['\n', '\r']).to_string(),
+        None => content.to_string(),
+    }
+}
+
+/// Write an output file, applying the `trailing_newline` policy.
+pub fn write_file(
+    path: impl AsRef<std::path::Path>,
+    content: &str,
+    out: &OutputOptions,
+) -> std::io::Result<()> {
+    std::fs::write(path, with_trailing_newline(content, out.trailing_newline))
+}
+
+/// Display name for `tag`, falling back to the canonical uppercase name.
+fn label_in<'a>(labels: &'a HashMap<Tag, String>, tag: &Tag) -> &'a str {
+    labels.get(tag).map(String::as_str).unwrap_or(tag.as_str())
+}
+
+fn tag_label<'a>(tag: &Tag, out: &'a OutputOptions) -> &'a str {
+    label_in(&out.tag_labels, tag)
+}
+
+/// Serialize through a single-entry map when a root key is set, so the
+/// wrapped value keeps its field order.
+fn to_json_with<T: serde::Serialize + ?Sized>(
+    value: &T,
+    root_key: Option<&str>,
+    pretty: bool,
+) -> String {
+    let out = match (root_key, pretty) {
+        (Some(key), true) => serde_json::to_string_pretty(&HashMap::from([(key, value)])),
+        (Some(key), false) => serde_json::to_string(&HashMap::from([(key, value)])),
+        (None, true) => serde_json::to_string_pretty(value),
+        (None, false) => serde_json::to_string(value),
+    };
+    out.expect("failed to serialize")
+}
+
+fn to_json_pretty<T: serde::Serialize + ?Sized>(value: &T, out: &OutputOptions) -> String {
+    to_json_with(value, out.json_root_key.as_deref(), true)
+}
+
+/// Single-line variant for streamed (one object per line) output.
+fn to_json_line<T: serde::Serialize + ?Sized>(value: &T, out: &OutputOptions) -> String {
+    to_json_with(value, out.json_root_key.as_deref(), false)
+}
 
 /// Apply detail-level transformations to a flat JSON item (TodoItem-shaped object).
 /// - Always: inject stable `id` field
@@ -35,18 +184,51 @@ fn apply_detail_to_json_item(item_val: &mut serde_json::Value, detail: &DetailLe
     }
 }
 
-fn colorize_tag(tag: &Tag) -> ColoredString {
+fn colorize_tag(tag: &Tag, out: &OutputOptions) -> ColoredString {
+    let label = tag_label(tag, out);
     match tag {
-        Tag::Todo => tag.as_str().yellow(),
-        Tag::Fixme => tag.as_str().red(),
-        Tag::Hack => tag.as_str().magenta(),
-        Tag::Bug => tag.as_str().red().bold(),
-        Tag::Note => tag.as_str().blue(),
-        Tag::Xxx => tag.as_str().red(),
+        Tag::Todo => label.yellow(),
+        Tag::Fixme => label.red(),
+        Tag::Hack => label.magenta(),
+        Tag::Bug => label.red().bold(),
+        Tag::Note => label.blue(),
+        Tag::Xxx => label.red(),
+    }
+}
+
+/// Deadlines this many days away or fewer are highlighted as due soon.
+const DEADLINE_DUE_SOON_DAYS: i64 = 7;
+
+/// Render a deadline annotation colored by urgency: red once expired,
+/// yellow with a countdown when due within a week, plain otherwise.
+fn format_deadline(deadline: &Deadline, today: &Deadline, out: &OutputOptions) -> ColoredString {
+    let days = deadline.days_until(today);
+    let deadline = display_deadline(deadline, out);
+    if days < 0 {
+        format!("[expired: {}]", deadline).red()
+    } else if days <= DEADLINE_DUE_SOON_DAYS {
+        let countdown = match days {
+            0 => "due today".to_string(),
+            1 => "due in 1 day".to_string(),
+            n => format!("due in {} days", n),
+        };
+        format!("[deadline: {}, {}]", deadline, countdown).yellow()
+    } else {
+        format!("[deadline: {}]", deadline).normal()
     }
 }
 
-fn group_key(item: &TodoItem, group_by: &GroupBy) -> String {
+/// Direction indicator for a net TODO delta: a red `↑` when debt grew, a green
+/// `↓` when it shrank, and a plain `→` when flat.
+fn trend_indicator(net: i64) -> ColoredString {
+    match net.cmp(&0) {
+        std::cmp::Ordering::Greater => "↑".red(),
+        std::cmp::Ordering::Less => "↓".green(),
+        std::cmp::Ordering::Equal => "→".normal(),
+    }
+}
+
+fn group_key(item: &TodoItem, group_by: &GroupBy, severity: &SeverityOverrides) -> String {
     match group_by {
         GroupBy::File => item.file.clone(),
         GroupBy::Tag => item.tag.as_str().to_string(),
@@ -59,26 +241,25 @@ fn group_key(item: &TodoItem, group_by: &GroupBy) -> String {
             .author
             .clone()
             .unwrap_or_else(|| "unassigned".to_string()),
-        GroupBy::Dir => Path::new(&item.file)
-            .parent()
-            .map(|p| {
-                let s = p.to_string_lossy().to_string();
-                if s.is_empty() {
-                    ".".to_string()
-                } else {
-                    s
-                }
-            })
-            .unwrap_or_else(|| ".".to_string()),
+        GroupBy::Dir => item.dir(),
+        GroupBy::Severity => severity.severity_of(item).as_sarif_level().to_string(),
+        GroupBy::None => String::new(),
     }
 }
 
-fn group_items<'a>(items: &'a [TodoItem], group_by: &GroupBy) -> Vec<(String, Vec<&'a TodoItem>)> {
+/// Group items by `group_by`, keeping insertion order within each group unless
+/// `stable` asks for (file, line) order.
+fn group_items<'a>(
+    items: &'a [TodoItem],
+    group_by: &GroupBy,
+    severity: &SeverityOverrides,
+    stable: bool,
+) -> Vec<(String, Vec<&'a TodoItem>)> {
     let mut groups: Vec<(String, Vec<&'a TodoItem>)> = Vec::new();
     let mut key_index: std::collections::HashMap<String, usize> = std::collections::HashMap::new();
 
     for item in items {
-        let key = group_key(item, group_by);
+        let key = group_key(item, group_by, severity);
         if let Some(&idx) = key_index.get(&key) {
             groups[idx].1.push(item);
         } else {
@@ -98,7 +279,18 @@ fn group_items<'a>(items: &'a [TodoItem], group_by: &GroupBy) -> Vec<(String, Ve
                     _ => 3,
                 }
             };
-            groups.sort_by(|a, b| priority_order(&a.0).cmp(&priority_order(&b.0)));
+            groups.sort_by_key(|a| priority_order(&a.0));
+        }
+        GroupBy::Severity => {
+            let severity_order = |key: &str| -> u8 {
+                match key {
+                    "error" => 0,
+                    "warning" => 1,
+                    "note" => 2,
+                    _ => 3,
+                }
+            };
+            groups.sort_by_key(|a| severity_order(&a.0));
         }
         GroupBy::Tag => {
             groups.sort_by(|a, b| {
@@ -107,35 +299,93 @@ fn group_items<'a>(items: &'a [TodoItem], group_by: &GroupBy) -> Vec<(String, Ve
                 sb.cmp(&sa)
             });
         }
+        // A single group that keeps scan order
+        GroupBy::None => {}
         _ => {
             groups.sort_by(|a, b| a.0.cmp(&b.0));
         }
     }
 
+    if stable {
+        for (_, group) in &mut groups {
+            group.sort_by(|a, b| a.file.cmp(&b.file).then(a.line.cmp(&b.line)));
+        }
+    }
+
     groups
 }
 
+/// JSON for `list`: items get their stable `id` (and context, when present)
+/// and the result a `truncated` flag.
+fn list_json(
+    result: &ScanResult,
+    context_map: &HashMap<String, ContextInfo>,
+    omitted: usize,
+    detail: &DetailLevel,
+    out: &OutputOptions,
+) -> String {
+    let mut value: serde_json::Value = serde_json::to_value(result).expect("failed to serialize");
+    if let Some(items) = value.get_mut("items").and_then(|v| v.as_array_mut()) {
+        for item_val in items.iter_mut() {
+            let file = item_val
+                .get("file")
+                .and_then(|v| v.as_str())
+                .unwrap_or("")
+                .to_string();
+            let line = item_val.get("line").and_then(|v| v.as_u64()).unwrap_or(0);
+            let key = format!("{}:{}", file, line);
+            if let Some(ctx) = context_map.get(&key) {
+                let ctx_value = serde_json::to_value(ctx).expect("failed to serialize context");
+                item_val
+                    .as_object_mut()
+                    .unwrap()
+                    .insert("context".to_string(), ctx_value);
+            }
+            apply_detail_to_json_item(item_val, detail);
+        }
+    }
+    if let Some(obj) = value.as_object_mut() {
+        obj.insert(
+            "truncated".to_string(),
+            serde_json::json!(omitted > 0 || result.truncated),
+        );
+    }
+    to_json_pretty(&value, out)
+}
+
+/// Print list results. `omitted` is the number of items dropped by `--limit`.
+/// `file_headers` replaces the header of a `--group-by file` group (keyed by
+/// the items' path) for display only.
+#[allow(clippy::too_many_arguments)]
 pub fn print_list(
     result: &ScanResult,
-    format: &Format,
+    out: &OutputOptions,
     group_by: &GroupBy,
     context_map: &HashMap<String, ContextInfo>,
     ignored_count: usize,
     show_ignored: bool,
+    omitted: usize,
     detail: &DetailLevel,
+    by_tag_section: bool,
+    stable_order: bool,
+    badges: Option<&BadgeCounts>,
+    columns: &[Column],
+    file_headers: &HashMap<String, String>,
 ) {
     let has_context = !context_map.is_empty();
 
-    match format {
+    match &out.format {
         Format::Text => {
-            let groups = group_items(&result.items, group_by);
+            let groups = group_items(&result.items, group_by, &out.severity, stable_order);
             let group_count = groups.len();
             let is_file_group = matches!(group_by, GroupBy::File);
+            let is_flat = matches!(group_by, GroupBy::None);
 
             for (key, items) in &groups {
                 if is_file_group {
-                    println!("{}", key.bold().underline());
-                } else {
+                    let header = file_headers.get(key).unwrap_or(key);
+                    println!("{}", header.bold().underline());
+                } else if !is_flat {
                     println!(
                         "{}",
                         format!("{} ({} items)", key, items.len())
@@ -144,10 +394,10 @@ pub fn print_list(
                     );
                 }
                 for item in items {
-                    let tag_str = colorize_tag(&item.tag);
+                    let tag_str = colorize_tag(&item.tag, out);
 
                     // Print before-context lines
-                    let ctx_key = format!("{}:{}", item.file, item.line);
+                    let ctx_key = format!("{}:{}", item.location(), item.line);
                     if let Some(ctx) = context_map.get(&ctx_key) {
                         for cl in &ctx.before {
                             println!(
@@ -159,8 +409,10 @@ pub fn print_list(
                     }
 
                     let msg = sanitize_for_terminal(&item.message);
-                    let file = sanitize_for_terminal(&item.file);
-                    let mut line = if is_file_group {
+                    let file = sanitize_for_terminal(&item.location());
+                    let mut line = if is_flat {
+                        format!("{}:{} [{}] {}", file, item.line, tag_str, msg)
+                    } else if is_file_group {
                         format!("  L{}: [{}] {}", item.line, tag_str, msg)
                     } else {
                         format!("  {}:{}: [{}] {}", file, item.line, tag_str, msg)
@@ -168,21 +420,16 @@ pub fn print_list(
 
                     if *detail != DetailLevel::Minimal {
                         if let Some(ref author) = item.author {
-                            line.push_str(&format!(" (@{})", sanitize_for_terminal(author)));
+                            let author = author_mention(author, out)
+                                .unwrap_or_else(|| format!("@{}", author));
+                            line.push_str(&format!(" ({})", sanitize_for_terminal(&author)));
                         }
                         if let Some(ref issue) = item.issue_ref {
                             line.push_str(&format!(" ({})", sanitize_for_terminal(issue)));
                         }
                         if let Some(ref deadline) = item.deadline {
                             let today = crate::deadline::today();
-                            if deadline.is_expired(&today) {
-                                line.push_str(&format!(
-                                    " {}",
-                                    format!("[expired: {}]", deadline).red()
-                                ));
-                            } else {
-                                line.push_str(&format!(" [deadline: {}]", deadline));
-                            }
+                            line.push_str(&format!(" {}", format_deadline(deadline, &today, out)));
                         }
                     }
 
@@ -206,22 +453,33 @@ pub fn print_list(
                 }
             }
 
+            if omitted > 0 {
+                println!("{}", format!("... and {} more", omitted).dimmed());
+            }
+
             // Show ignored items section
             if show_ignored && !result.ignored_items.is_empty() {
                 println!();
                 println!("{}", "Ignored items".bold().underline());
-                let ignored_groups = group_items(&result.ignored_items, group_by);
+                let ignored_groups =
+                    group_items(&result.ignored_items, group_by, &out.severity, stable_order);
                 for (key, items) in &ignored_groups {
                     if is_file_group {
-                        println!("{}", key.dimmed());
-                    } else {
+                        let header = file_headers.get(key).unwrap_or(key);
+                        println!("{}", header.dimmed());
+                    } else if !is_flat {
                         println!("{}", format!("{} ({} items)", key, items.len()).dimmed());
                     }
                     for item in items {
-                        let tag_str = colorize_tag(&item.tag);
-                        let msg = sanitize_for_terminal(&item.message);
-                        let file = sanitize_for_terminal(&item.file);
-                        let line = if is_file_group {
+                        let tag_str = colorize_tag(&item.tag, out);
+                        let mut msg = sanitize_for_terminal(&item.message);
+                        if let Some(reason) = item.ignore_reason {
+                            msg.push_str(&format!(" ({})", reason.as_str()));
+                        }
+                        let file = sanitize_for_terminal(&item.location());
+                        let line = if is_flat {
+                            format!("{}:{} [{}] {}", file, item.line, tag_str, msg)
+                        } else if is_file_group {
                             format!("  L{}: [{}] {}", item.line, tag_str, msg)
                         } else {
                             format!("  {}:{}: [{}] {}", file, item.line, tag_str, msg)
@@ -238,7 +496,9 @@ pub fn print_list(
                 String::new()
             };
 
-            if is_file_group {
+            if is_flat {
+                println!("{} items{}", result.items.len(), ignored_suffix);
+            } else if is_file_group {
                 println!(
                     "{} items in {} files{}",
                     result.items.len(),
@@ -254,59 +514,50 @@ pub fn print_list(
                 );
             }
         }
-        Format::Json => {
-            let mut value: serde_json::Value =
-                serde_json::to_value(result).expect("failed to serialize");
-            if let Some(items) = value.get_mut("items").and_then(|v| v.as_array_mut()) {
-                for item_val in items.iter_mut() {
-                    let file = item_val
-                        .get("file")
-                        .and_then(|v| v.as_str())
-                        .unwrap_or("")
-                        .to_string();
-                    let line = item_val.get("line").and_then(|v| v.as_u64()).unwrap_or(0);
-                    let key = format!("{}:{}", file, line);
-                    if has_context {
-                        if let Some(ctx) = context_map.get(&key) {
-                            let ctx_value =
-                                serde_json::to_value(ctx).expect("failed to serialize context");
-                            item_val
-                                .as_object_mut()
-                                .unwrap()
-                                .insert("context".to_string(), ctx_value);
-                        }
-                    }
-                    apply_detail_to_json_item(item_val, detail);
-                }
+        Format::Json | Format::Ics | Format::Junit | Format::JunitByFile | Format::Prometheus => {
+            println!("{}", list_json(result, context_map, omitted, detail, out));
+        }
+        Format::CsvWide => print!("{}", csv::format_list_wide(result, context_map, columns)),
+        Format::CodeClimate => print!("{}", codeclimate::format_list(result, out)),
+        Format::Clang => print!("{}", clang::format_list(result)),
+        Format::Html => print!("{}", html::format_list(result, out)),
+        Format::GithubActions => print!("{}", github_actions::format_list(result, out)),
+        Format::Sarif => print!("{}", sarif::format_list(result, out)),
+        Format::Markdown | Format::MarkdownCollapsible | Format::MarkdownSummary => {
+            let collapsible = matches!(out.format, Format::MarkdownCollapsible);
+            print!(
+                "{}",
+                markdown::format_list(result, out, collapsible, by_tag_section, badges, columns)
+            );
+            if omitted > 0 {
+                println!("_... and {} more_", omitted);
+                println!();
             }
-            let json = serde_json::to_string_pretty(&value).expect("failed to serialize");
-            println!("{}", json);
         }
-        Format::GithubActions => print!("{}", github_actions::format_list(result)),
-        Format::Sarif => print!("{}", sarif::format_list(result)),
-        Format::Markdown => print!("{}", markdown::format_list(result)),
     }
 }
 
 pub fn print_search(
     result: &SearchResult,
-    format: &Format,
+    out: &OutputOptions,
     group_by: &GroupBy,
     context_map: &HashMap<String, ContextInfo>,
     detail: &DetailLevel,
+    stable_order: bool,
 ) {
     let has_context = !context_map.is_empty();
 
-    match format {
+    match &out.format {
         Format::Text => {
-            let groups = group_items(&result.items, group_by);
+            let groups = group_items(&result.items, group_by, &out.severity, stable_order);
             let group_count = groups.len();
             let is_file_group = matches!(group_by, GroupBy::File);
+            let is_flat = matches!(group_by, GroupBy::None);
 
             for (key, items) in &groups {
                 if is_file_group {
                     println!("{}", key.bold().underline());
-                } else {
+                } else if !is_flat {
                     println!(
                         "{}",
                         format!("{} ({} items)", key, items.len())
@@ -315,10 +566,10 @@ pub fn print_search(
                     );
                 }
                 for item in items {
-                    let tag_str = colorize_tag(&item.tag);
+                    let tag_str = colorize_tag(&item.tag, out);
 
                     // Print before-context lines
-                    let ctx_key = format!("{}:{}", item.file, item.line);
+                    let ctx_key = format!("{}:{}", item.location(), item.line);
                     if let Some(ctx) = context_map.get(&ctx_key) {
                         for cl in &ctx.before {
                             println!(
@@ -330,8 +581,10 @@ pub fn print_search(
                     }
 
                     let msg = sanitize_for_terminal(&item.message);
-                    let file = sanitize_for_terminal(&item.file);
-                    let mut line = if is_file_group {
+                    let file = sanitize_for_terminal(&item.location());
+                    let mut line = if is_flat {
+                        format!("{}:{} [{}] {}", file, item.line, tag_str, msg)
+                    } else if is_file_group {
                         format!("  L{}: [{}] {}", item.line, tag_str, msg)
                     } else {
                         format!("  {}:{}: [{}] {}", file, item.line, tag_str, msg)
@@ -339,21 +592,16 @@ pub fn print_search(
 
                     if *detail != DetailLevel::Minimal {
                         if let Some(ref author) = item.author {
-                            line.push_str(&format!(" (@{})", sanitize_for_terminal(author)));
+                            let author = author_mention(author, out)
+                                .unwrap_or_else(|| format!("@{}", author));
+                            line.push_str(&format!(" ({})", sanitize_for_terminal(&author)));
                         }
                         if let Some(ref issue) = item.issue_ref {
                             line.push_str(&format!(" ({})", sanitize_for_terminal(issue)));
                         }
                         if let Some(ref deadline) = item.deadline {
                             let today = crate::deadline::today();
-                            if deadline.is_expired(&today) {
-                                line.push_str(&format!(
-                                    " {}",
-                                    format!("[expired: {}]", deadline).red()
-                                ));
-                            } else {
-                                line.push_str(&format!(" [deadline: {}]", deadline));
-                            }
+                            line.push_str(&format!(" {}", format_deadline(deadline, &today, out)));
                         }
                     }
 
@@ -377,7 +625,13 @@ pub fn print_search(
                 }
             }
 
-            if is_file_group {
+            if is_flat {
+                println!(
+                    "{} matches (query: \"{}\")",
+                    result.match_count,
+                    sanitize_for_terminal(&result.query)
+                );
+            } else if is_file_group {
                 println!(
                     "{} matches across {} files (query: \"{}\")",
                     result.match_count,
@@ -393,7 +647,15 @@ pub fn print_search(
                 );
             }
         }
-        Format::Json => {
+        Format::Json
+        | Format::Ics
+        | Format::CsvWide
+        | Format::Junit
+        | Format::JunitByFile
+        | Format::Prometheus
+        | Format::CodeClimate
+        | Format::Clang
+        | Format::Html => {
             let mut value: serde_json::Value =
                 serde_json::to_value(result).expect("failed to serialize");
             if let Some(items) = value.get_mut("items").and_then(|v| v.as_array_mut()) {
@@ -418,34 +680,104 @@ pub fn print_search(
                     apply_detail_to_json_item(item_val, detail);
                 }
             }
-            let json = serde_json::to_string_pretty(&value).expect("failed to serialize");
+            let json = to_json_pretty(&value, out);
             println!("{}", json);
         }
-        Format::GithubActions => print!("{}", github_actions::format_search(result)),
-        Format::Sarif => print!("{}", sarif::format_search(result)),
-        Format::Markdown => print!("{}", markdown::format_search(result)),
+        Format::GithubActions => print!("{}", github_actions::format_search(result, out)),
+        Format::Sarif => print!("{}", sarif::format_search(result, out)),
+        Format::Markdown | Format::MarkdownCollapsible | Format::MarkdownSummary => {
+            print!("{}", markdown::format_search(result, out))
+        }
+    }
+}
+
+/// Render a single diff entry for text output.
+///
+/// With `symbols`, the status is spelled out as a `[+]`/`[-]` marker so it
+/// stays distinguishable without relying on red/green color.
+fn format_diff_entry(entry: &DiffEntry, symbols: bool, out: &OutputOptions) -> String {
+    let (prefix, color): (&str, fn(&str) -> ColoredString) = match (&entry.status, symbols) {
+        (DiffStatus::Added, false) => ("+", |s: &str| s.green()),
+        (DiffStatus::Removed, false) => ("-", |s: &str| s.red()),
+        (DiffStatus::Added, true) => ("[+]", |s: &str| s.green()),
+        (DiffStatus::Removed, true) => ("[-]", |s: &str| s.red()),
+    };
+
+    let tag_str = colorize_tag(&entry.item.tag, out);
+    let line = format!(
+        "{} {}:{} [{}] {}",
+        prefix,
+        sanitize_for_terminal(&entry.item.location()),
+        entry.item.line,
+        tag_str,
+        sanitize_for_terminal(&entry.item.message)
+    );
+    color(&line).to_string()
+}
+
+/// Print `list --summary` aggregates. Always JSON: the summary exists for
+/// dashboards that only need counts.
+pub fn print_list_summary(summary: &ListSummary, out: &OutputOptions) {
+    println!("{}", to_json_pretty(summary, out));
+}
+
+/// Print TODOs with deadlines, in the given (deadline) order.
+pub fn print_deadlines(items: &[TodoItem], out: &OutputOptions) {
+    match &out.format {
+        Format::Text => {
+            let today = crate::deadline::today();
+            for item in items {
+                let Some(deadline) = &item.deadline else {
+                    continue;
+                };
+                let date = display_deadline(deadline, out);
+                let date = if deadline.is_expired(&today) {
+                    date.red().to_string()
+                } else {
+                    date
+                };
+                println!(
+                    "{}  {}:{} [{}] {}",
+                    date,
+                    item.location(),
+                    item.line,
+                    colorize_tag(&item.tag, out),
+                    sanitize_for_terminal(&item.message)
+                );
+            }
+            println!("\n{} items with deadlines", items.len());
+        }
+        Format::Ics => print!(
+            "{}",
+            ics::format_deadlines(items, &crate::deadline::today())
+        ),
+        _ => println!("{}", to_json_pretty(items, out)),
     }
 }
 
 pub fn print_diff(
     result: &DiffResult,
-    format: &Format,
+    out: &OutputOptions,
     context_map: &HashMap<String, ContextInfo>,
+    base_context_map: &HashMap<String, ContextInfo>,
     detail: &DetailLevel,
+    symbols: bool,
 ) {
-    let has_context = !context_map.is_empty();
-
-    match format {
+    let has_context = !context_map.is_empty() || !base_context_map.is_empty();
+    // Added entries take context from the working tree, removed entries from
+    // the base ref.
+    let context_for = |status: &DiffStatus| match status {
+        DiffStatus::Added => context_map,
+        DiffStatus::Removed => base_context_map,
+    };
+
+    match &out.format {
         Format::Text => {
             for entry in &result.entries {
-                let (prefix, color): (&str, fn(&str) -> ColoredString) = match entry.status {
-                    DiffStatus::Added => ("+", |s: &str| s.green()),
-                    DiffStatus::Removed => ("-", |s: &str| s.red()),
-                };
-
                 // Print before-context
-                let ctx_key = format!("{}:{}", entry.item.file, entry.item.line);
-                if let Some(ctx) = context_map.get(&ctx_key) {
+                let ctx_key = format!("{}:{}", entry.item.location(), entry.item.line);
+                let ctx = context_for(&entry.status).get(&ctx_key);
+                if let Some(ctx) = ctx {
                     for cl in &ctx.before {
                         println!(
                             "    {} {}",
@@ -455,19 +787,10 @@ pub fn print_diff(
                     }
                 }
 
-                let tag_str = colorize_tag(&entry.item.tag);
-                let line = format!(
-                    "{} {}:{} [{}] {}",
-                    prefix,
-                    sanitize_for_terminal(&entry.item.file),
-                    entry.item.line,
-                    tag_str,
-                    sanitize_for_terminal(&entry.item.message)
-                );
-                println!("{}", color(&line));
+                println!("{}", format_diff_entry(entry, symbols, out));
 
                 // Print after-context
-                if let Some(ctx) = context_map.get(&ctx_key) {
+                if let Some(ctx) = ctx {
                     for cl in &ctx.after {
                         println!(
                             "    {} {}",
@@ -484,11 +807,17 @@ pub fn print_diff(
                 result.added_count, result.removed_count, result.base_ref
             );
         }
-        Format::Json => {
+        Format::Json
+        | Format::Ics
+        | Format::CsvWide
+        | Format::CodeClimate
+        | Format::Clang
+        | Format::Html
+        | Format::Prometheus => {
             let mut value: serde_json::Value =
                 serde_json::to_value(result).expect("failed to serialize");
             if let Some(entries) = value.get_mut("entries").and_then(|v| v.as_array_mut()) {
-                for entry_val in entries.iter_mut() {
+                for (entry, entry_val) in result.entries.iter().zip(entries.iter_mut()) {
                     // Read context key from item before mutation
                     let ctx_key = entry_val.get("item").map(|item_val| {
                         let file = item_val.get("file").and_then(|v| v.as_str()).unwrap_or("");
@@ -498,7 +827,7 @@ pub fn print_diff(
 
                     if let Some(ref key) = ctx_key {
                         if has_context {
-                            if let Some(ctx) = context_map.get(key) {
+                            if let Some(ctx) = context_for(&entry.status).get(key) {
                                 let ctx_value =
                                     serde_json::to_value(ctx).expect("failed to serialize context");
                                 entry_val
@@ -514,12 +843,35 @@ pub fn print_diff(
                     }
                 }
             }
-            let json = serde_json::to_string_pretty(&value).expect("failed to serialize");
+            let json = to_json_pretty(&value, out);
             println!("{}", json);
         }
-        Format::GithubActions => print!("{}", github_actions::format_diff(result)),
-        Format::Sarif => print!("{}", sarif::format_diff(result)),
-        Format::Markdown => print!("{}", markdown::format_diff(result)),
+        Format::Junit => print!("{}", junit::format_diff(result)),
+        Format::JunitByFile => print!("{}", junit::format_diff_by_file(result)),
+        Format::GithubActions => print!("{}", github_actions::format_diff(result, out)),
+        Format::Sarif => print!("{}", sarif::format_diff(result, out)),
+        Format::Markdown | Format::MarkdownCollapsible => {
+            print!("{}", markdown::format_diff(result, out))
+        }
+        Format::MarkdownSummary => print!("{}", markdown::format_diff_summary(result, out)),
+    }
+}
+
+/// `", N unknown"` for blame summaries, or nothing when every entry was blamed.
+fn unknown_suffix(unknown_count: usize) -> String {
+    if unknown_count > 0 {
+        format!(", {} unknown", unknown_count)
+    } else {
+        String::new()
+    }
+}
+
+/// Average age for blame summaries, in days unless `humanize` is set.
+fn avg_age(days: u64, humanize: bool) -> String {
+    if humanize {
+        crate::date_utils::humanize_duration_days(days)
+    } else {
+        format!("{} days", days)
     }
 }
 
@@ -531,87 +883,153 @@ fn bar(count: usize, max: usize, width: usize) -> String {
     "\u{2588}".repeat(filled)
 }
 
-pub fn print_brief(result: &BriefResult, format: &Format, budget: Option<usize>) {
-    match format {
+pub fn print_tags(tags: &[TagInfo], out: &OutputOptions) {
+    match &out.format {
         Format::Text => {
-            let mut lines: Vec<String> = Vec::new();
-
-            // Line 1: summary
-            let pc = &result.priority_counts;
-            let mut priority_parts: Vec<String> = Vec::new();
-            if pc.urgent > 0 {
-                priority_parts.push(format!("{} urgent", pc.urgent));
-            }
-            if pc.high > 0 {
-                priority_parts.push(format!("{} high", pc.high));
-            }
-
-            let summary = if priority_parts.is_empty() {
-                format!(
-                    "{} TODOs across {} files",
-                    result.total_items, result.total_files
-                )
-            } else {
-                format!(
-                    "{} TODOs across {} files ({})",
-                    result.total_items,
-                    result.total_files,
-                    priority_parts.join(", ")
-                )
-            };
-            lines.push(summary);
-
-            // Line 2: top urgent (if any)
-            if let Some(ref item) = result.top_urgent {
-                let priority_marker = match item.priority {
-                    Priority::Urgent => "!!",
-                    Priority::High => "!",
-                    Priority::Normal => "",
+            for info in tags {
+                let state = if info.enabled {
+                    format!("{:<8}", "enabled").green()
+                } else {
+                    format!("{:<8}", "disabled").dimmed()
                 };
-                let issue_suffix = item
-                    .issue_ref
-                    .as_ref()
-                    .map(|r| format!(" ({})", sanitize_for_terminal(r)))
-                    .unwrap_or_default();
-                lines.push(format!(
-                    "Top urgent: {}:{} {}{} {}{}",
-                    sanitize_for_terminal(&item.file),
-                    item.line,
-                    item.tag.as_str(),
-                    priority_marker,
-                    sanitize_for_terminal(&item.message),
-                    issue_suffix
-                ));
-            }
-
-            // Line 3: trend (if available)
-            if let Some(ref trend) = result.trend {
-                lines.push(format!(
-                    "Trends vs {}: +{} added, -{} removed",
-                    trend.base_ref, trend.added, trend.removed
-                ));
+                let mut line = format!(
+                    "{:<6} {:<8} {} sample: {}",
+                    info.tag.as_str(),
+                    info.severity.as_github_actions_str(),
+                    state,
+                    colorize_tag(&info.tag, out)
+                );
+                if !info.aliases.is_empty() {
+                    let aliases: Vec<String> = info
+                        .aliases
+                        .iter()
+                        .map(|a| sanitize_for_terminal(a))
+                        .collect();
+                    line.push_str(&format!("  (aliases: {})", aliases.join(", ")));
+                }
+                println!("{}", line);
             }
+        }
+        _ => println!("{}", to_json_pretty(tags, out)),
+    }
+}
 
-            let max_lines = budget.unwrap_or(lines.len());
-            for line in lines.iter().take(max_lines) {
+pub fn print_brief(
+    result: &BriefResult,
+    out: &OutputOptions,
+    budget: Option<usize>,
+    prefer: &BriefPrefer,
+) {
+    match &out.format {
+        Format::Text => {
+            for line in brief_lines(result, budget, prefer) {
                 println!("{}", line);
             }
         }
         _ => {
-            let json = serde_json::to_string_pretty(result).expect("failed to serialize");
+            let json = to_json_pretty(result, out);
             println!("{}", json);
         }
     }
 }
 
-pub fn print_stats(result: &StatsResult, format: &Format) {
-    match format {
+/// Build the text lines for `brief`, trimmed to `budget`.
+///
+/// The summary line always survives. The top-urgent and trend lines follow in
+/// that order; when the budget cannot fit both, `prefer` decides which is kept.
+fn brief_lines(result: &BriefResult, budget: Option<usize>, prefer: &BriefPrefer) -> Vec<String> {
+    // Summary
+    let pc = &result.priority_counts;
+    let mut priority_parts: Vec<String> = Vec::new();
+    if pc.urgent > 0 {
+        priority_parts.push(format!("{} urgent", pc.urgent));
+    }
+    if pc.high > 0 {
+        priority_parts.push(format!("{} high", pc.high));
+    }
+
+    let summary = if priority_parts.is_empty() {
+        format!(
+            "{} TODOs across {} files",
+            result.total_items, result.total_files
+        )
+    } else {
+        format!(
+            "{} TODOs across {} files ({})",
+            result.total_items,
+            result.total_files,
+            priority_parts.join(", ")
+        )
+    };
+
+    // Top urgent (if any)
+    let urgent = result.top_urgent.as_ref().map(|item| {
+        let priority_marker = match item.priority {
+            Priority::Urgent => "!!",
+            Priority::High => "!",
+            Priority::Normal => "",
+        };
+        let issue_suffix = item
+            .issue_ref
+            .as_ref()
+            .map(|r| format!(" ({})", sanitize_for_terminal(r)))
+            .unwrap_or_default();
+        format!(
+            "Top urgent: {}:{} {}{} {}{}",
+            sanitize_for_terminal(&item.location()),
+            item.line,
+            item.tag.as_str(),
+            priority_marker,
+            sanitize_for_terminal(&item.message),
+            issue_suffix
+        )
+    });
+
+    // Trend (if available)
+    let trend = result.trend.as_ref().map(|trend| {
+        let net = trend.added as i64 - trend.removed as i64;
+        format!(
+            "Trends vs {}: {} +{} added, -{} removed",
+            trend.base_ref,
+            trend_indicator(net),
+            trend.added,
+            trend.removed
+        )
+    });
+
+    if budget == Some(0) {
+        return Vec::new();
+    }
+
+    let available = usize::from(urgent.is_some()) + usize::from(trend.is_some());
+    let room = budget.map_or(available, |b| b - 1);
+
+    let mut lines = vec![summary];
+    if room >= available {
+        lines.extend(urgent);
+        lines.extend(trend);
+    } else if room > 0 {
+        lines.extend(match prefer {
+            BriefPrefer::Urgent => urgent.or(trend),
+            BriefPrefer::Trend => trend.or(urgent),
+        });
+    }
+    lines
+}
+
+/// Stats as Prometheus metrics; `stale` adds `todoscan_stale_total`.
+pub fn print_stats_prometheus(result: &StatsResult, stale: Option<usize>) {
+    print!("{}", prometheus::format_stats(result, stale));
+}
+
+pub fn print_stats(result: &StatsResult, out: &OutputOptions) {
+    match &out.format {
         Format::Text => {
             // Tag breakdown
             println!("{}", "Tags".bold().underline());
             let tag_max = result.tag_counts.first().map(|(_, c)| *c).unwrap_or(0);
             for (tag, count) in &result.tag_counts {
-                let tag_str = colorize_tag(tag);
+                let tag_str = colorize_tag(tag, out);
                 println!(
                     "  {:6} {:>4}  {}",
                     tag_str,
@@ -651,37 +1069,88 @@ pub fn print_stats(result: &StatsResult, format: &Format) {
                 }
             }
 
+            // Directory rollup (--by-dir)
+            if !result.dir_counts.is_empty() {
+                println!("\n{}", "Directories".bold().underline());
+                let dir_max = result.dir_counts.first().map(|(_, c)| *c).unwrap_or(0);
+                for (dir, count) in &result.dir_counts {
+                    println!(
+                        "  {:30} {:>4}  {}",
+                        sanitize_for_terminal(dir),
+                        count,
+                        bar(*count, dir_max, 20).dimmed()
+                    );
+                }
+            }
+
+            // Directory tree (--tree)
+            if let Some(ref tree) = result.tree {
+                println!("\n{}", "Tree".bold().underline());
+                print_dir_tree(tree, 1);
+            }
+
             // Total summary
             println!(
                 "\n{} items across {} files",
                 result.total_items, result.total_files
             );
+            if result.files_scanned > 0 {
+                println!(
+                    "{} of {} scanned files contain items ({:.1}%)",
+                    result.files_with_items,
+                    result.files_scanned,
+                    result.files_with_items as f64 / result.files_scanned as f64 * 100.0
+                );
+            }
 
             // Trend
             if let Some(ref trend) = result.trend {
                 let net: i64 = trend.added as i64 - trend.removed as i64;
                 let sign = if net > 0 { "+" } else { "" };
                 println!(
-                    "Trend since {}: {} added, {} removed ({}{})",
-                    trend.base_ref, trend.added, trend.removed, sign, net
+                    "Trend since {}: {} {} added, {} removed ({}{})",
+                    trend.base_ref,
+                    trend_indicator(net),
+                    trend.added,
+                    trend.removed,
+                    sign,
+                    net
                 );
             }
         }
         _ => {
-            let json = serde_json::to_string_pretty(result).expect("failed to serialize");
+            let json = to_json_pretty(result, out);
             println!("{}", json);
         }
     }
 }
 
-pub fn print_lint(result: &LintResult, format: &Format) {
-    match format {
+/// Print one `stats --tree` node and its children, indented by depth.
+/// Directories get a trailing `/`.
+fn print_dir_tree(node: &DirNode, depth: usize) {
+    let name = if node.children.is_empty() {
+        sanitize_for_terminal(&node.name)
+    } else {
+        format!("{}/", sanitize_for_terminal(&node.name))
+    };
+    println!("{}{} ({})", "  ".repeat(depth), name, node.count);
+    for child in &node.children {
+        print_dir_tree(child, depth + 1);
+    }
+}
+
+pub fn print_lint(result: &LintResult, out: &OutputOptions) {
+    match &out.format {
         Format::Text => {
-            if result.passed {
+            if result.violations.is_empty() {
                 println!("{}", "PASS".green().bold());
                 println!("{} items checked, no violations", result.total_items);
             } else {
-                println!("{}", "FAIL".red().bold());
+                if result.passed {
+                    println!("{}", "PASS".green().bold());
+                } else {
+                    println!("{}", "FAIL".red().bold());
+                }
 
                 // Group violations by file
                 let mut groups: Vec<(String, Vec<&LintViolation>)> = Vec::new();
@@ -701,10 +1170,15 @@ pub fn print_lint(result: &LintResult, format: &Format) {
                 for (file, violations) in &groups {
                     println!("{}", sanitize_for_terminal(file).bold().underline());
                     for v in violations {
+                        let level = match v.severity {
+                            Severity::Error => String::new(),
+                            other => format!(" ({})", other.as_github_actions_str()),
+                        };
                         println!(
-                            "  L{}: {} - {}",
+                            "  L{}: {}{} - {}",
                             v.line,
                             sanitize_for_terminal(&v.rule).yellow(),
+                            level.dimmed(),
                             sanitize_for_terminal(&v.message)
                         );
                         if let Some(ref suggestion) = v.suggestion {
@@ -723,18 +1197,44 @@ pub fn print_lint(result: &LintResult, format: &Format) {
                 );
             }
         }
-        Format::Json => {
-            let json = serde_json::to_string_pretty(result).expect("failed to serialize");
+        Format::Json
+        | Format::Ics
+        | Format::CsvWide
+        | Format::Junit
+        | Format::JunitByFile
+        | Format::Prometheus
+        | Format::CodeClimate
+        | Format::Clang
+        | Format::Html => {
+            let json = to_json_pretty(result, out);
             println!("{}", json);
         }
         Format::GithubActions => print!("{}", github_actions::format_lint(result)),
         Format::Sarif => print!("{}", sarif::format_lint(result)),
-        Format::Markdown => print!("{}", markdown::format_lint(result)),
+        Format::Markdown | Format::MarkdownCollapsible | Format::MarkdownSummary => {
+            print!("{}", markdown::format_lint(result))
+        }
+    }
+}
+
+/// The optional `clean` summary counts, e.g. `, 2 stale by age, 1 without
+/// owner`, or nothing when they are zero.
+fn clean_extra_counts(result: &CleanResult) -> String {
+    let mut out = String::new();
+    if result.stale_age_count > 0 {
+        out.push_str(&format!(", {} stale by age", result.stale_age_count));
     }
+    if result.deadline_without_owner_count > 0 {
+        out.push_str(&format!(
+            ", {} without owner",
+            result.deadline_without_owner_count
+        ));
+    }
+    out
 }
 
-pub fn print_clean(result: &CleanResult, format: &Format) {
-    match format {
+pub fn print_clean(result: &CleanResult, out: &OutputOptions) {
+    match &out.format {
         Format::Text => {
             if result.passed {
                 println!("{}", "PASS".green().bold());
@@ -778,23 +1278,37 @@ pub fn print_clean(result: &CleanResult, format: &Format) {
 
                 let violation_count = result.violations.len();
                 println!(
-                    "\n{} violations ({} stale, {} duplicates) in {} items",
-                    violation_count, result.stale_count, result.duplicate_count, result.total_items
+                    "\n{} violations ({} stale, {} duplicates{}) in {} items",
+                    violation_count,
+                    result.stale_count,
+                    result.duplicate_count,
+                    clean_extra_counts(result),
+                    result.total_items
                 );
             }
         }
-        Format::Json => {
-            let json = serde_json::to_string_pretty(result).expect("failed to serialize");
+        Format::Json
+        | Format::Ics
+        | Format::CsvWide
+        | Format::Junit
+        | Format::JunitByFile
+        | Format::Prometheus
+        | Format::CodeClimate
+        | Format::Clang
+        | Format::Html => {
+            let json = to_json_pretty(result, out);
             println!("{}", json);
         }
         Format::GithubActions => print!("{}", github_actions::format_clean(result)),
         Format::Sarif => print!("{}", sarif::format_clean(result)),
-        Format::Markdown => print!("{}", markdown::format_clean(result)),
+        Format::Markdown | Format::MarkdownCollapsible | Format::MarkdownSummary => {
+            print!("{}", markdown::format_clean(result))
+        }
     }
 }
 
-pub fn print_check(result: &CheckResult, format: &Format) {
-    match format {
+pub fn print_check(result: &CheckResult, out: &OutputOptions) {
+    match &out.format {
         Format::Text => {
             if result.passed {
                 println!("{}", "PASS".green().bold());
@@ -809,18 +1323,41 @@ pub fn print_check(result: &CheckResult, format: &Format) {
                 }
             }
         }
-        Format::Json => {
-            let json = serde_json::to_string_pretty(result).expect("failed to serialize");
+        Format::Json
+        | Format::Ics
+        | Format::CsvWide
+        | Format::Junit
+        | Format::JunitByFile
+        | Format::Prometheus
+        | Format::CodeClimate
+        | Format::Clang
+        | Format::Html => {
+            let json = to_json_pretty(result, out);
             println!("{}", json);
         }
         Format::GithubActions => print!("{}", github_actions::format_check(result)),
         Format::Sarif => print!("{}", sarif::format_check(result)),
-        Format::Markdown => print!("{}", markdown::format_check(result)),
+        Format::Markdown | Format::MarkdownCollapsible | Format::MarkdownSummary => {
+            print!("{}", markdown::format_check(result))
+        }
+    }
+}
+
+/// JSON for `blame`, with a stable `id` on every entry.
+fn blame_json(result: &BlameResult, out: &OutputOptions) -> String {
+    let mut value: serde_json::Value = serde_json::to_value(result).expect("failed to serialize");
+    if let Some(entries) = value.get_mut("entries").and_then(|v| v.as_array_mut()) {
+        for entry_val in entries.iter_mut() {
+            inject_id_field(entry_val);
+        }
     }
+    to_json_pretty(&value, out)
 }
 
-pub fn print_blame(result: &BlameResult, format: &Format) {
-    match format {
+/// Print blame results. With `humanize_age`, the text and markdown summaries
+/// show the average age in days, months or years; JSON keeps the raw days.
+pub fn print_blame(result: &BlameResult, out: &OutputOptions, humanize_age: bool) {
+    match &out.format {
         Format::Text => {
             // Group by file
             let mut groups: Vec<(String, Vec<&BlameEntry>)> = Vec::new();
@@ -840,44 +1377,56 @@ pub fn print_blame(result: &BlameResult, format: &Format) {
             for (file, entries) in &groups {
                 println!("{}", sanitize_for_terminal(file).bold().underline());
                 for entry in entries {
-                    let tag_str = colorize_tag(&entry.item.tag);
+                    let tag_str = colorize_tag(&entry.item.tag, out);
                     let stale_marker = if entry.stale {
                         " [STALE]".red().to_string()
                     } else {
                         String::new()
                     };
+                    let when = if entry.blame.is_unknown() {
+                        "(not in git history)".dimmed().to_string()
+                    } else {
+                        format!(
+                            "{} ({} days ago)",
+                            sanitize_for_terminal(&entry.blame.date),
+                            entry.blame.age_days
+                        )
+                    };
                     println!(
-                        "  L{}: [{}] {} @{} {} ({} days ago){}",
+                        "  L{}: [{}] {} @{} {}{}",
                         entry.item.line,
                         tag_str,
                         sanitize_for_terminal(&entry.item.message),
                         sanitize_for_terminal(&entry.blame.author),
-                        sanitize_for_terminal(&entry.blame.date),
-                        entry.blame.age_days,
+                        when,
                         stale_marker,
                     );
                 }
             }
 
             println!(
-                "\n{} items, avg age {} days, {} stale (threshold: {} days)",
-                result.total, result.avg_age_days, result.stale_count, result.stale_threshold_days,
+                "\n{} items, avg age {}, {} stale{} (threshold: {} days)",
+                result.total,
+                avg_age(result.avg_age_days, humanize_age),
+                result.stale_count,
+                unknown_suffix(result.unknown_count),
+                result.stale_threshold_days,
             );
         }
-        Format::Json => {
-            let mut value: serde_json::Value =
-                serde_json::to_value(result).expect("failed to serialize");
-            if let Some(entries) = value.get_mut("entries").and_then(|v| v.as_array_mut()) {
-                for entry_val in entries.iter_mut() {
-                    inject_id_field(entry_val);
-                }
-            }
-            let json = serde_json::to_string_pretty(&value).expect("failed to serialize");
-            println!("{}", json);
-        }
+        Format::Json
+        | Format::Ics
+        | Format::CsvWide
+        | Format::Junit
+        | Format::JunitByFile
+        | Format::Prometheus
+        | Format::CodeClimate
+        | Format::Clang
+        | Format::Html => println!("{}", blame_json(result, out)),
         Format::GithubActions => print!("{}", github_actions::format_blame(result)),
-        Format::Sarif => print!("{}", sarif::format_blame(result)),
-        Format::Markdown => print!("{}", markdown::format_blame(result)),
+        Format::Sarif => print!("{}", sarif::format_blame(result, out)),
+        Format::Markdown | Format::MarkdownCollapsible | Format::MarkdownSummary => {
+            print!("{}", markdown::format_blame(result, out, humanize_age))
+        }
     }
 }
 
@@ -904,13 +1453,18 @@ fn inject_id_field(val: &mut serde_json::Value) {
         .insert("id".to_string(), serde_json::Value::String(id));
 }
 
-pub fn print_context(rich: &RichContext, format: &Format) {
-    match format {
+pub fn print_context(rich: &RichContext, out: &OutputOptions) {
+    match &out.format {
         Format::Text => {
-            println!(
-                "{}",
-                format!("{}:{}", rich.file, rich.line).bold().underline()
-            );
+            let location = format!("{}:{}", rich.file, rich.line);
+            match &rich.enclosing_symbol {
+                Some(symbol) => println!(
+                    "{} {}",
+                    sanitize_for_terminal(&location).bold().underline(),
+                    format!("in {}", sanitize_for_terminal(symbol)).dimmed()
+                ),
+                None => println!("{}", sanitize_for_terminal(&location).bold().underline()),
+            }
             println!();
 
             for cl in &rich.before {
@@ -949,18 +1503,18 @@ pub fn print_context(rich: &RichContext, format: &Format) {
             }
         }
         _ => {
-            let json = serde_json::to_string_pretty(rich).expect("failed to serialize");
+            let json = to_json_pretty(rich, out);
             println!("{}", json);
         }
     }
 }
 
-pub fn print_initial_summary(tag_counts: &[(Tag, usize)], total: usize, format: &Format) {
-    match format {
+pub fn print_initial_summary(tag_counts: &[(Tag, usize)], total: usize, out: &OutputOptions) {
+    match &out.format {
         Format::Text => {
             println!("{}", "Initial scan".bold().underline());
             for (tag, count) in tag_counts {
-                println!("  {:6} {}", colorize_tag(tag), count);
+                println!("  {:6} {}", colorize_tag(tag, out), count);
             }
             println!("{} items total", total);
             println!();
@@ -973,16 +1527,13 @@ pub fn print_initial_summary(tag_counts: &[(Tag, usize)], total: usize, format:
                     serde_json::json!({ "tag": tag.as_str(), "count": count })
                 }).collect::<Vec<_>>(),
             });
-            println!(
-                "{}",
-                serde_json::to_string(&summary).expect("failed to serialize")
-            );
+            println!("{}", to_json_line(&summary, out));
         }
     }
 }
 
-pub fn print_watch_event(event: &WatchEvent, format: &Format, max: Option<usize>) {
-    match format {
+pub fn print_watch_event(event: &WatchEvent, out: &OutputOptions, max: Option<usize>) {
+    match &out.format {
         Format::Text => {
             println!(
                 "{} {}",
@@ -991,7 +1542,7 @@ pub fn print_watch_event(event: &WatchEvent, format: &Format, max: Option<usize>
             );
 
             for item in &event.added {
-                let tag_str = colorize_tag(&item.tag);
+                let tag_str = colorize_tag(&item.tag, out);
                 println!(
                     "  {} L{}: [{}] {}",
                     "+".green(),
@@ -1002,7 +1553,7 @@ pub fn print_watch_event(event: &WatchEvent, format: &Format, max: Option<usize>
             }
 
             for item in &event.removed {
-                let tag_str = colorize_tag(&item.tag);
+                let tag_str = colorize_tag(&item.tag, out);
                 println!(
                     "  {} L{}: [{}] {}",
                     "-".red(),
@@ -1037,14 +1588,14 @@ pub fn print_watch_event(event: &WatchEvent, format: &Format, max: Option<usize>
             println!();
         }
         _ => {
-            let json = serde_json::to_string(&event).expect("failed to serialize");
+            let json = to_json_line(&event, out);
             println!("{}", json);
         }
     }
 }
 
-pub fn print_tasks(result: &TasksResult, format: &Format) {
-    match format {
+pub fn print_tasks(result: &TasksResult, out: &OutputOptions) {
+    match &out.format {
         Format::Text => {
             if result.tasks.is_empty() {
                 println!("No tasks to export.");
@@ -1074,14 +1625,37 @@ pub fn print_tasks(result: &TasksResult, format: &Format) {
             }
         }
         _ => {
-            let json = serde_json::to_string_pretty(result).expect("failed to serialize");
+            let json = to_json_pretty(result, out);
             println!("{}", json);
         }
     }
 }
 
-pub fn print_relate(result: &RelateResult, format: &Format) {
-    match format {
+/// One relationship line; with `explain`, the full-sentence explanation goes on
+/// the next line instead of the short reason.
+/// One relationship as printed by [`print_relate`], without a trailing newline.
+fn format_relationship(rel: &Relationship, indent: &str, explain: bool) -> String {
+    let from = sanitize_for_terminal(&rel.from);
+    let to = sanitize_for_terminal(&rel.to);
+    if explain {
+        format!(
+            "{indent}{from} ↔ {to} (score: {:.2})\n{indent}  {}",
+            rel.score,
+            sanitize_for_terminal(&rel.explanation).dimmed()
+        )
+    } else {
+        format!(
+            "{indent}{from} ↔ {to} (score: {:.2}, {})",
+            rel.score,
+            sanitize_for_terminal(&rel.reason)
+        )
+    }
+}
+
+/// Print relationships and clusters. JSON always carries both `reason` and
+/// `explanation`; `explain` only affects text output.
+pub fn print_relate(result: &RelateResult, out: &OutputOptions, explain: bool) {
+    match &out.format {
         Format::Text => {
             if result.relationships.is_empty() {
                 println!("No relationships found (min_score: {})", result.min_score);
@@ -1116,25 +1690,13 @@ pub fn print_relate(result: &RelateResult, format: &Format) {
                     if !cluster.relationships.is_empty() {
                         println!("  Relationships:");
                         for rel in &cluster.relationships {
-                            println!(
-                                "    {} ↔ {} (score: {:.2}, {})",
-                                sanitize_for_terminal(&rel.from),
-                                sanitize_for_terminal(&rel.to),
-                                rel.score,
-                                sanitize_for_terminal(&rel.reason)
-                            );
+                            println!("{}", format_relationship(rel, "    ", explain));
                         }
                     }
                 }
             } else {
                 for rel in &result.relationships {
-                    println!(
-                        "  {} ↔ {} (score: {:.2}, {})",
-                        sanitize_for_terminal(&rel.from),
-                        sanitize_for_terminal(&rel.to),
-                        rel.score,
-                        sanitize_for_terminal(&rel.reason)
-                    );
+                    println!("{}", format_relationship(rel, "  ", explain));
                 }
             }
 
@@ -1143,8 +1705,9 @@ pub fn print_relate(result: &RelateResult, format: &Format) {
                 result.total_relationships, result.total_items
             );
         }
+        Format::Sarif => print!("{}", sarif::format_relate(result)),
         _ => {
-            let json = serde_json::to_string_pretty(result).expect("failed to serialize");
+            let json = to_json_pretty(result, out);
             println!("{}", json);
         }
     }
@@ -1152,7 +1715,7 @@ pub fn print_relate(result: &RelateResult, format: &Format) {
 
 /// Strip terminal control characters from user-controlled strings to prevent
 /// ANSI escape injection. Removes 0x00–0x1f (except tab 0x09) and 0x7f.
-fn sanitize_for_terminal(s: &str) -> String {
+pub(crate) fn sanitize_for_terminal(s: &str) -> String {
     s.chars()
         .filter(|c| {
             let code = *c as u32;
@@ -1161,19 +1724,61 @@ fn sanitize_for_terminal(s: &str) -> String {
         .collect()
 }
 
-pub fn print_report(report: &ReportResult, output_path: &str) -> std::io::Result<()> {
-    let content = html::render_html(report);
-    std::fs::write(output_path, content)?;
+/// `--explain-config` output; always JSON since it mirrors the config file.
+pub fn print_config_explanation(
+    explanation: &crate::config::ConfigExplanation,
+    out: &OutputOptions,
+) {
+    println!("{}", to_json_pretty(explanation, out));
+}
+
+pub fn print_report(
+    report: &ReportResult,
+    output_path: &str,
+    out: &OutputOptions,
+) -> std::io::Result<()> {
+    let content = html::render_html(report, out);
+    write_file(output_path, &content, out)?;
     println!("Report written to {}", sanitize_for_terminal(output_path));
     Ok(())
 }
 
+/// Write the `bundle` artifacts into `dir`: the JSON that `list`, `stats`
+/// and `blame` print, plus the HTML report.
+pub fn write_bundle(
+    dir: &std::path::Path,
+    scan: &ScanResult,
+    stats: &StatsResult,
+    blame: &BlameResult,
+    report: &ReportResult,
+    out: &OutputOptions,
+) -> std::io::Result<()> {
+    let files = [
+        (
+            "list.json",
+            list_json(scan, &HashMap::new(), 0, &DetailLevel::Normal, out),
+        ),
+        ("stats.json", to_json_pretty(stats, out)),
+        ("blame.json", blame_json(blame, out)),
+        ("report.html", html::render_html(report, out)),
+    ];
+    for (name, content) in &files {
+        write_file(dir.join(name), content, out)?;
+    }
+    println!(
+        "Bundle written to {} ({})",
+        sanitize_for_terminal(&dir.display().to_string()),
+        files.map(|(name, _)| name).join(", ")
+    );
+    Ok(())
+}
+
 pub fn print_workspace_list(
     result: &WorkspaceResult,
-    format: &Format,
+    out: &OutputOptions,
     kind: &crate::model::WorkspaceKind,
 ) {
-    match format {
+    match &out.format {
         Format::Text => {
             println!("{}", format!("Workspace ({kind})").bold().underline());
             println!(
@@ -1208,7 +1813,7 @@ pub fn print_workspace_list(
             );
         }
         _ => {
-            let json = serde_json::to_string_pretty(result).expect("failed to serialize");
+            let json = to_json_pretty(result, out);
             println!("{}", json);
         }
     }
@@ -1293,12 +1898,17 @@ mod tests {
         TodoItem {
             file: file.to_string(),
             line,
+            byte_offset: 0,
+            cell: None,
+            labels: vec![],
             tag,
             message: msg.to_string(),
             author: None,
             issue_ref: None,
             priority,
             deadline: None,
+            ignore_reason: None,
+            severity: None,
         }
     }
 
@@ -1313,12 +1923,17 @@ mod tests {
         TodoItem {
             file: file.to_string(),
             line,
+            byte_offset: 0,
+            cell: None,
+            labels: vec![],
             tag,
             message: msg.to_string(),
             author: author.map(|a| a.to_string()),
             issue_ref: None,
             priority,
             deadline: None,
+            ignore_reason: None,
+            severity: None,
         }
     }
 
@@ -1467,28 +2082,109 @@ mod tests {
         assert!(val.get("id").is_some());
     }
 
+    // --- tag label tests ---
+
+    #[test]
+    fn test_label_in_uses_custom_label() {
+        let labels = HashMap::from([(Tag::Bug, "Defect".to_string())]);
+        assert_eq!(label_in(&labels, &Tag::Bug), "Defect");
+        assert_eq!(label_in(&labels, &Tag::Todo), "TODO");
+        assert_eq!(label_in(&HashMap::new(), &Tag::Bug), "BUG");
+    }
+
+    // --- author mention tests ---
+
+    #[test]
+    fn test_mention_in_applies_template() {
+        assert_eq!(
+            mention_in(Some("<@{author}>"), "alice").as_deref(),
+            Some("<@alice>")
+        );
+        assert_eq!(mention_in(None, "alice"), None);
+    }
+
+    // --- trailing newline policy tests ---
+
+    #[test]
+    fn test_trailing_newline_policy_for_json_and_markdown() {
+        let json = to_json_pretty(
+            &serde_json::json!({ "total": 1 }),
+            &OutputOptions::new(Format::Json),
+        );
+        assert!(!json.ends_with('\n'));
+        assert_eq!(
+            with_trailing_newline(&json, Some(true)),
+            format!("{json}\n")
+        );
+        assert_eq!(with_trailing_newline(&json, Some(false)), json);
+
+        let result = ScanResult {
+            items: vec![make_item("a.rs", 1, Tag::Todo, "task", Priority::Normal)],
+            ignored_items: vec![],
+            files_scanned: 1,
+            files_with_items: 1,
+            bytes_scanned: 0,
+            truncated: false,
+            total_found: None,
+        };
+        let markdown = markdown::format_list(
+            &result,
+            &OutputOptions::new(Format::Markdown),
+            true,
+            false,
+            None,
+            &[],
+        );
+        assert!(markdown.ends_with("</details>\n"));
+        let one = with_trailing_newline(&markdown, Some(true));
+        assert!(one.ends_with("</details>\n") && !one.ends_with("\n\n"));
+        assert!(with_trailing_newline(&markdown, Some(false)).ends_with("</details>"));
+        assert_eq!(with_trailing_newline(&markdown, None), markdown);
+    }
+
+    #[test]
+    fn test_check_author_mention_format_requires_placeholder() {
+        assert!(check_author_mention_format("<@user>").is_err());
+    }
+
+    #[test]
+    fn test_parse_tag_labels_rejects_unknown_tag() {
+        let labels = HashMap::from([("WIP".to_string(), "Work".to_string())]);
+        let err = parse_tag_map(&labels, "tag_labels").unwrap_err();
+        assert!(err.to_string().contains("Unknown tag in tag_labels: WIP"));
+    }
+
     // --- colorize_tag tests ---
 
     #[test]
     fn test_colorize_tag_returns_correct_text_for_all_tags() {
         // We verify the underlying text is correct for each tag variant.
         // Colored strings deref to the original text.
-        assert_eq!(colorize_tag(&Tag::Todo).to_string().contains("TODO"), true);
-        assert_eq!(
-            colorize_tag(&Tag::Fixme).to_string().contains("FIXME"),
-            true
-        );
-        assert_eq!(colorize_tag(&Tag::Hack).to_string().contains("HACK"), true);
-        assert_eq!(colorize_tag(&Tag::Bug).to_string().contains("BUG"), true);
-        assert_eq!(colorize_tag(&Tag::Note).to_string().contains("NOTE"), true);
-        assert_eq!(colorize_tag(&Tag::Xxx).to_string().contains("XXX"), true);
+        assert!(colorize_tag(&Tag::Todo, &OutputOptions::new(Format::Text))
+            .to_string()
+            .contains("TODO"));
+        assert!(colorize_tag(&Tag::Fixme, &OutputOptions::new(Format::Text))
+            .to_string()
+            .contains("FIXME"));
+        assert!(colorize_tag(&Tag::Hack, &OutputOptions::new(Format::Text))
+            .to_string()
+            .contains("HACK"));
+        assert!(colorize_tag(&Tag::Bug, &OutputOptions::new(Format::Text))
+            .to_string()
+            .contains("BUG"));
+        assert!(colorize_tag(&Tag::Note, &OutputOptions::new(Format::Text))
+            .to_string()
+            .contains("NOTE"));
+        assert!(colorize_tag(&Tag::Xxx, &OutputOptions::new(Format::Text))
+            .to_string()
+            .contains("XXX"));
     }
 
     #[test]
     fn test_colorize_tag_todo_is_yellow() {
         // Disable coloring to test the underlying string
         colored::control::set_override(false);
-        let result = colorize_tag(&Tag::Todo);
+        let result = colorize_tag(&Tag::Todo, &OutputOptions::new(Format::Text));
         assert_eq!(&*result, "TODO");
         colored::control::unset_override();
     }
@@ -1496,7 +2192,7 @@ mod tests {
     #[test]
     fn test_colorize_tag_fixme_is_red() {
         colored::control::set_override(false);
-        let result = colorize_tag(&Tag::Fixme);
+        let result = colorize_tag(&Tag::Fixme, &OutputOptions::new(Format::Text));
         assert_eq!(&*result, "FIXME");
         colored::control::unset_override();
     }
@@ -1504,32 +2200,137 @@ mod tests {
     #[test]
     fn test_colorize_tag_hack_is_magenta() {
         colored::control::set_override(false);
-        let result = colorize_tag(&Tag::Hack);
+        let result = colorize_tag(&Tag::Hack, &OutputOptions::new(Format::Text));
         assert_eq!(&*result, "HACK");
         colored::control::unset_override();
     }
 
     #[test]
-    fn test_colorize_tag_bug_is_red_bold() {
-        colored::control::set_override(false);
-        let result = colorize_tag(&Tag::Bug);
-        assert_eq!(&*result, "BUG");
-        colored::control::unset_override();
+    fn test_colorize_tag_bug_is_red_bold() {
+        colored::control::set_override(false);
+        let result = colorize_tag(&Tag::Bug, &OutputOptions::new(Format::Text));
+        assert_eq!(&*result, "BUG");
+        colored::control::unset_override();
+    }
+
+    #[test]
+    fn test_colorize_tag_note_is_blue() {
+        colored::control::set_override(false);
+        let result = colorize_tag(&Tag::Note, &OutputOptions::new(Format::Text));
+        assert_eq!(&*result, "NOTE");
+        colored::control::unset_override();
+    }
+
+    #[test]
+    fn test_colorize_tag_xxx_is_red() {
+        colored::control::set_override(false);
+        let result = colorize_tag(&Tag::Xxx, &OutputOptions::new(Format::Text));
+        assert_eq!(&*result, "XXX");
+        colored::control::unset_override();
+    }
+
+    // --- trend_indicator tests ---
+
+    #[test]
+    fn test_trend_indicator_direction() {
+        assert_eq!(&*trend_indicator(2), "↑");
+        assert_eq!(trend_indicator(2).fgcolor, Some(Color::Red));
+        assert_eq!(&*trend_indicator(-1), "↓");
+        assert_eq!(trend_indicator(-1).fgcolor, Some(Color::Green));
+        assert_eq!(&*trend_indicator(0), "→");
+        assert_eq!(trend_indicator(0).fgcolor, None);
+    }
+
+    #[test]
+    fn test_trend_indicator_plain_without_color() {
+        colored::control::set_override(false);
+        assert_eq!(trend_indicator(5).to_string(), "↑");
+        colored::control::unset_override();
+    }
+
+    // --- format_deadline tests ---
+
+    fn date(year: u16, month: u8, day: u8) -> Deadline {
+        Deadline { year, month, day }
+    }
+
+    #[test]
+    fn test_to_json_with_root_key_wraps_value() {
+        let value = serde_json::json!({ "total": 2 });
+        let json = to_json_with(&value, Some("results"), true);
+        let parsed: serde_json::Value = serde_json::from_str(&json).unwrap();
+        assert_eq!(parsed, serde_json::json!({ "results": { "total": 2 } }));
+
+        let line = to_json_with(&value, Some("results"), false);
+        assert_eq!(line, r#"{"results":{"total":2}}"#);
+    }
+
+    #[test]
+    fn test_to_json_without_root_key_is_bare() {
+        let value = serde_json::json!({ "total": 2 });
+        let parsed: serde_json::Value =
+            serde_json::from_str(&to_json_with(&value, None, true)).unwrap();
+        assert_eq!(parsed, value);
     }
 
     #[test]
-    fn test_colorize_tag_note_is_blue() {
+    fn test_format_deadline_countdown_text() {
         colored::control::set_override(false);
-        let result = colorize_tag(&Tag::Note);
-        assert_eq!(&*result, "NOTE");
+        let today = date(2025, 6, 10);
+        assert_eq!(
+            &*format_deadline(&date(2025, 6, 1), &today, &OutputOptions::new(Format::Text)),
+            "[expired: 2025-06-01]"
+        );
+        assert_eq!(
+            &*format_deadline(
+                &date(2025, 6, 10),
+                &today,
+                &OutputOptions::new(Format::Text)
+            ),
+            "[deadline: 2025-06-10, due today]"
+        );
+        assert_eq!(
+            &*format_deadline(
+                &date(2025, 6, 11),
+                &today,
+                &OutputOptions::new(Format::Text)
+            ),
+            "[deadline: 2025-06-11, due in 1 day]"
+        );
+        assert_eq!(
+            &*format_deadline(
+                &date(2025, 6, 17),
+                &today,
+                &OutputOptions::new(Format::Text)
+            ),
+            "[deadline: 2025-06-17, due in 7 days]"
+        );
+        assert_eq!(
+            &*format_deadline(
+                &date(2025, 6, 18),
+                &today,
+                &OutputOptions::new(Format::Text)
+            ),
+            "[deadline: 2025-06-18]"
+        );
         colored::control::unset_override();
     }
 
     #[test]
-    fn test_colorize_tag_xxx_is_red() {
-        colored::control::set_override(false);
-        let result = colorize_tag(&Tag::Xxx);
-        assert_eq!(&*result, "XXX");
+    fn test_format_deadline_color_tiers_differ() {
+        colored::control::set_override(true);
+        let today = date(2025, 6, 10);
+        let expired = format_deadline(&date(2025, 6, 9), &today, &OutputOptions::new(Format::Text));
+        let soon = format_deadline(
+            &date(2025, 6, 12),
+            &today,
+            &OutputOptions::new(Format::Text),
+        );
+        let later = format_deadline(&date(2025, 9, 1), &today, &OutputOptions::new(Format::Text));
+        assert_eq!(expired.fgcolor, Some(Color::Red));
+        assert_eq!(soon.fgcolor, Some(Color::Yellow));
+        assert_eq!(later.fgcolor, None);
+        assert_ne!(expired.to_string(), soon.to_string());
         colored::control::unset_override();
     }
 
@@ -1538,13 +2339,19 @@ mod tests {
     #[test]
     fn test_group_key_file() {
         let item = make_item("src/main.rs", 10, Tag::Todo, "test", Priority::Normal);
-        assert_eq!(group_key(&item, &GroupBy::File), "src/main.rs");
+        assert_eq!(
+            group_key(&item, &GroupBy::File, &SeverityOverrides::default()),
+            "src/main.rs"
+        );
     }
 
     #[test]
     fn test_group_key_tag() {
         let item = make_item("src/main.rs", 10, Tag::Fixme, "test", Priority::Normal);
-        assert_eq!(group_key(&item, &GroupBy::Tag), "FIXME");
+        assert_eq!(
+            group_key(&item, &GroupBy::Tag, &SeverityOverrides::default()),
+            "FIXME"
+        );
     }
 
     #[test]
@@ -1558,39 +2365,57 @@ mod tests {
             (Tag::Xxx, "XXX"),
         ] {
             let item = make_item("f.rs", 1, tag, "msg", Priority::Normal);
-            assert_eq!(group_key(&item, &GroupBy::Tag), expected);
+            assert_eq!(
+                group_key(&item, &GroupBy::Tag, &SeverityOverrides::default()),
+                expected
+            );
         }
     }
 
     #[test]
     fn test_group_key_priority_urgent() {
         let item = make_item("f.rs", 1, Tag::Todo, "msg", Priority::Urgent);
-        assert_eq!(group_key(&item, &GroupBy::Priority), "!! Urgent");
+        assert_eq!(
+            group_key(&item, &GroupBy::Priority, &SeverityOverrides::default()),
+            "!! Urgent"
+        );
     }
 
     #[test]
     fn test_group_key_priority_high() {
         let item = make_item("f.rs", 1, Tag::Todo, "msg", Priority::High);
-        assert_eq!(group_key(&item, &GroupBy::Priority), "! High");
+        assert_eq!(
+            group_key(&item, &GroupBy::Priority, &SeverityOverrides::default()),
+            "! High"
+        );
     }
 
     #[test]
     fn test_group_key_priority_normal() {
         let item = make_item("f.rs", 1, Tag::Todo, "msg", Priority::Normal);
-        assert_eq!(group_key(&item, &GroupBy::Priority), "Normal");
+        assert_eq!(
+            group_key(&item, &GroupBy::Priority, &SeverityOverrides::default()),
+            "Normal"
+        );
     }
 
     #[test]
     fn test_group_key_author_with_author() {
         let item =
             make_item_with_author("f.rs", 1, Tag::Todo, "msg", Priority::Normal, Some("alice"));
-        assert_eq!(group_key(&item, &GroupBy::Author), "alice");
+        assert_eq!(
+            group_key(&item, &GroupBy::Author, &SeverityOverrides::default()),
+            "alice"
+        );
     }
 
     #[test]
     fn test_group_key_author_without_author() {
         let item = make_item("f.rs", 1, Tag::Todo, "msg", Priority::Normal);
-        assert_eq!(group_key(&item, &GroupBy::Author), "unassigned");
+        assert_eq!(
+            group_key(&item, &GroupBy::Author, &SeverityOverrides::default()),
+            "unassigned"
+        );
     }
 
     #[test]
@@ -1602,25 +2427,57 @@ mod tests {
             "msg",
             Priority::Normal,
         );
-        assert_eq!(group_key(&item, &GroupBy::Dir), "src/utils");
+        assert_eq!(
+            group_key(&item, &GroupBy::Dir, &SeverityOverrides::default()),
+            "src/utils"
+        );
     }
 
     #[test]
     fn test_group_key_dir_top_level_file() {
         let item = make_item("main.rs", 1, Tag::Todo, "msg", Priority::Normal);
-        assert_eq!(group_key(&item, &GroupBy::Dir), ".");
+        assert_eq!(
+            group_key(&item, &GroupBy::Dir, &SeverityOverrides::default()),
+            "."
+        );
     }
 
     #[test]
     fn test_group_key_dir_single_level() {
         let item = make_item("src/lib.rs", 1, Tag::Todo, "msg", Priority::Normal);
-        assert_eq!(group_key(&item, &GroupBy::Dir), "src");
+        assert_eq!(
+            group_key(&item, &GroupBy::Dir, &SeverityOverrides::default()),
+            "src"
+        );
     }
 
     #[test]
     fn test_group_key_dir_deeply_nested() {
         let item = make_item("a/b/c/d/e.rs", 1, Tag::Todo, "msg", Priority::Normal);
-        assert_eq!(group_key(&item, &GroupBy::Dir), "a/b/c/d");
+        assert_eq!(
+            group_key(&item, &GroupBy::Dir, &SeverityOverrides::default()),
+            "a/b/c/d"
+        );
+    }
+
+    #[test]
+    fn test_group_key_severity_buckets() {
+        let cases = [
+            (Tag::Bug, Priority::Normal, "error"),
+            (Tag::Fixme, Priority::Normal, "error"),
+            (Tag::Todo, Priority::Normal, "warning"),
+            (Tag::Hack, Priority::Normal, "warning"),
+            (Tag::Note, Priority::Normal, "note"),
+            // Urgent priority escalates any tag to error
+            (Tag::Note, Priority::Urgent, "error"),
+        ];
+        for (tag, priority, expected) in cases {
+            let item = make_item("f.rs", 1, tag, "msg", priority);
+            assert_eq!(
+                group_key(&item, &GroupBy::Severity, &SeverityOverrides::default()),
+                expected
+            );
+        }
     }
 
     // --- group_items tests ---
@@ -1633,7 +2490,7 @@ mod tests {
             make_item("a.rs", 10, Tag::Bug, "third", Priority::Urgent),
         ];
 
-        let groups = group_items(&items, &GroupBy::File);
+        let groups = group_items(&items, &GroupBy::File, &SeverityOverrides::default(), false);
 
         assert_eq!(groups.len(), 2);
         // Sorted alphabetically by filename
@@ -1643,6 +2500,67 @@ mod tests {
         assert_eq!(groups[1].1.len(), 1);
     }
 
+    #[test]
+    fn test_group_items_none_keeps_scan_order_in_one_group() {
+        let items = vec![
+            make_item("b.rs", 5, Tag::Fixme, "second", Priority::High),
+            make_item("a.rs", 1, Tag::Todo, "first", Priority::Normal),
+        ];
+
+        let groups = group_items(&items, &GroupBy::None, &SeverityOverrides::default(), false);
+
+        assert_eq!(groups.len(), 1);
+        let files: Vec<&str> = groups[0].1.iter().map(|i| i.file.as_str()).collect();
+        assert_eq!(files, vec!["b.rs", "a.rs"]);
+    }
+
+    #[test]
+    fn test_group_items_stable_orders_by_file_then_line() {
+        let items = vec![
+            make_item("b.rs", 5, Tag::Todo, "b5", Priority::Urgent),
+            make_item("a.rs", 9, Tag::Todo, "a9", Priority::High),
+            make_item("a.rs", 2, Tag::Todo, "a2", Priority::Normal),
+            make_item("c.rs", 1, Tag::Bug, "c1", Priority::Normal),
+        ];
+
+        let groups = group_items(&items, &GroupBy::Tag, &SeverityOverrides::default(), true);
+        let todos: Vec<&str> = groups[1].1.iter().map(|i| i.message.as_str()).collect();
+        assert_eq!(groups[1].0, "TODO");
+        assert_eq!(todos, vec!["a2", "a9", "b5"]);
+
+        // Any insertion order gives the same grouping
+        let mut reversed = items.clone();
+        reversed.reverse();
+        let again = group_items(
+            &reversed,
+            &GroupBy::Tag,
+            &SeverityOverrides::default(),
+            true,
+        );
+        let key = |g: &[(String, Vec<&TodoItem>)]| -> Vec<(String, Vec<String>)> {
+            g.iter()
+                .map(|(k, v)| (k.clone(), v.iter().map(|i| i.id()).collect()))
+                .collect()
+        };
+        assert_eq!(key(&groups), key(&again));
+
+        let flat = group_items(
+            &reversed,
+            &GroupBy::None,
+            &SeverityOverrides::default(),
+            true,
+        );
+        let files: Vec<(&str, usize)> = flat[0]
+            .1
+            .iter()
+            .map(|i| (i.file.as_str(), i.line))
+            .collect();
+        assert_eq!(
+            files,
+            vec![("a.rs", 2), ("a.rs", 9), ("b.rs", 5), ("c.rs", 1)]
+        );
+    }
+
     #[test]
     fn test_group_items_by_tag_sorted_by_severity_descending() {
         let items = vec![
@@ -1652,7 +2570,7 @@ mod tests {
             make_item("d.rs", 4, Tag::Fixme, "high2", Priority::Normal), // severity 4
         ];
 
-        let groups = group_items(&items, &GroupBy::Tag);
+        let groups = group_items(&items, &GroupBy::Tag, &SeverityOverrides::default(), false);
 
         // Should be ordered: BUG(5), FIXME(4), TODO(1), NOTE(0) — descending severity
         assert_eq!(groups.len(), 4);
@@ -1662,6 +2580,27 @@ mod tests {
         assert_eq!(groups[3].0, "NOTE");
     }
 
+    #[test]
+    fn test_group_items_by_severity_sorted_error_first() {
+        let items = vec![
+            make_item("a.rs", 1, Tag::Note, "note", Priority::Normal),
+            make_item("b.rs", 2, Tag::Todo, "warning", Priority::Normal),
+            make_item("c.rs", 3, Tag::Bug, "error", Priority::Normal),
+            make_item("d.rs", 4, Tag::Hack, "warning2", Priority::Normal),
+        ];
+
+        let groups = group_items(
+            &items,
+            &GroupBy::Severity,
+            &SeverityOverrides::default(),
+            false,
+        );
+
+        let keys: Vec<&str> = groups.iter().map(|(k, _)| k.as_str()).collect();
+        assert_eq!(keys, vec!["error", "warning", "note"]);
+        assert_eq!(groups[1].1.len(), 2);
+    }
+
     #[test]
     fn test_group_items_by_priority_sorted_urgency_first() {
         let items = vec![
@@ -1670,7 +2609,12 @@ mod tests {
             make_item("c.rs", 3, Tag::Todo, "high", Priority::High),
         ];
 
-        let groups = group_items(&items, &GroupBy::Priority);
+        let groups = group_items(
+            &items,
+            &GroupBy::Priority,
+            &SeverityOverrides::default(),
+            false,
+        );
 
         assert_eq!(groups.len(), 3);
         assert_eq!(groups[0].0, "!! Urgent");
@@ -1700,7 +2644,12 @@ mod tests {
             make_item_with_author("c.rs", 3, Tag::Todo, "msg3", Priority::Normal, None),
         ];
 
-        let groups = group_items(&items, &GroupBy::Author);
+        let groups = group_items(
+            &items,
+            &GroupBy::Author,
+            &SeverityOverrides::default(),
+            false,
+        );
 
         // Alphabetical: alice, charlie, unassigned
         assert_eq!(groups.len(), 3);
@@ -1717,7 +2666,7 @@ mod tests {
             make_item("src/core/c.rs", 3, Tag::Todo, "msg", Priority::Normal),
         ];
 
-        let groups = group_items(&items, &GroupBy::Dir);
+        let groups = group_items(&items, &GroupBy::Dir, &SeverityOverrides::default(), false);
 
         assert_eq!(groups.len(), 3);
         assert_eq!(groups[0].0, "lib");
@@ -1728,14 +2677,14 @@ mod tests {
     #[test]
     fn test_group_items_empty_input() {
         let items: Vec<TodoItem> = vec![];
-        let groups = group_items(&items, &GroupBy::File);
+        let groups = group_items(&items, &GroupBy::File, &SeverityOverrides::default(), false);
         assert!(groups.is_empty());
     }
 
     #[test]
     fn test_group_items_single_item() {
         let items = vec![make_item("a.rs", 1, Tag::Todo, "only", Priority::Normal)];
-        let groups = group_items(&items, &GroupBy::File);
+        let groups = group_items(&items, &GroupBy::File, &SeverityOverrides::default(), false);
         assert_eq!(groups.len(), 1);
         assert_eq!(groups[0].0, "a.rs");
         assert_eq!(groups[0].1.len(), 1);
@@ -1749,7 +2698,7 @@ mod tests {
             make_item("c.rs", 3, Tag::Bug, "bug1", Priority::Normal),
         ];
 
-        let groups = group_items(&items, &GroupBy::Tag);
+        let groups = group_items(&items, &GroupBy::Tag, &SeverityOverrides::default(), false);
 
         assert_eq!(groups.len(), 2);
         // BUG has higher severity (5) than TODO (1)
@@ -1766,7 +2715,12 @@ mod tests {
             make_item("b.rs", 2, Tag::Bug, "msg2", Priority::High),
         ];
 
-        let groups = group_items(&items, &GroupBy::Priority);
+        let groups = group_items(
+            &items,
+            &GroupBy::Priority,
+            &SeverityOverrides::default(),
+            false,
+        );
         assert_eq!(groups.len(), 1);
         assert_eq!(groups[0].0, "! High");
         assert_eq!(groups[0].1.len(), 2);
@@ -1780,7 +2734,7 @@ mod tests {
             make_item("a.rs", 5, Tag::Todo, "third", Priority::Normal),
         ];
 
-        let groups = group_items(&items, &GroupBy::File);
+        let groups = group_items(&items, &GroupBy::File, &SeverityOverrides::default(), false);
         assert_eq!(groups.len(), 1);
         // Within the group, items should appear in the original order
         assert_eq!(groups[0].1[0].line, 10);
@@ -1866,7 +2820,7 @@ mod tests {
             make_item("f.rs", 6, Tag::Bug, "bug", Priority::Normal),   // severity 5
         ];
 
-        let groups = group_items(&items, &GroupBy::Tag);
+        let groups = group_items(&items, &GroupBy::Tag, &SeverityOverrides::default(), false);
 
         assert_eq!(groups.len(), 6);
         assert_eq!(groups[0].0, "BUG"); // 5
@@ -1884,12 +2838,17 @@ mod tests {
         let item = TodoItem {
             file: "src/scanner.rs".to_string(),
             line: 42,
+            byte_offset: 0,
+            cell: None,
+            labels: vec![],
             tag: Tag::Hack,
             message: "Workaround for bug #123".to_string(),
             author: Some("dev".to_string()),
             issue_ref: Some("#123".to_string()),
             priority: Priority::High,
             deadline: None,
+            ignore_reason: None,
+            severity: None,
         };
         let mut val = serde_json::to_value(&item).unwrap();
         apply_detail_to_json_item(&mut val, &DetailLevel::Full);
@@ -1908,12 +2867,17 @@ mod tests {
         let item = TodoItem {
             file: "src/lib.rs".to_string(),
             line: 1,
+            byte_offset: 0,
+            cell: None,
+            labels: vec![],
             tag: Tag::Todo,
             message: "clean up".to_string(),
             author: Some("bob".to_string()),
             issue_ref: Some("JIRA-456".to_string()),
             priority: Priority::Urgent,
             deadline: None,
+            ignore_reason: None,
+            severity: None,
         };
         let mut val = serde_json::to_value(&item).unwrap();
         apply_detail_to_json_item(&mut val, &DetailLevel::Minimal);
@@ -1968,7 +2932,10 @@ mod tests {
         // std::path::Path handles this: on Unix, backslashes are part of the filename
         // On Linux, "src\\main.rs" has no parent directory separator
         let item = make_item("file.txt", 1, Tag::Todo, "msg", Priority::Normal);
-        assert_eq!(group_key(&item, &GroupBy::Dir), ".");
+        assert_eq!(
+            group_key(&item, &GroupBy::Dir, &SeverityOverrides::default()),
+            "."
+        );
     }
 
     // --- group_items by priority with only urgent items ---
@@ -1980,7 +2947,12 @@ mod tests {
             make_item("b.rs", 2, Tag::Fixme, "also critical", Priority::Urgent),
         ];
 
-        let groups = group_items(&items, &GroupBy::Priority);
+        let groups = group_items(
+            &items,
+            &GroupBy::Priority,
+            &SeverityOverrides::default(),
+            false,
+        );
         assert_eq!(groups.len(), 1);
         assert_eq!(groups[0].0, "!! Urgent");
         assert_eq!(groups[0].1.len(), 2);
@@ -1997,7 +2969,12 @@ mod tests {
             make_item_with_author("d.rs", 4, Tag::Todo, "msg4", Priority::Normal, Some("adam")),
         ];
 
-        let groups = group_items(&items, &GroupBy::Author);
+        let groups = group_items(
+            &items,
+            &GroupBy::Author,
+            &SeverityOverrides::default(),
+            false,
+        );
         assert_eq!(groups.len(), 3);
         // Alphabetically sorted
         assert_eq!(groups[0].0, "adam");
@@ -2098,13 +3075,19 @@ mod tests {
         // A file with no path separator at all, like "Makefile"
         let item = make_item("Makefile", 1, Tag::Todo, "msg", Priority::Normal);
         // Path::new("Makefile").parent() returns Some(""), which is mapped to "."
-        assert_eq!(group_key(&item, &GroupBy::Dir), ".");
+        assert_eq!(
+            group_key(&item, &GroupBy::Dir, &SeverityOverrides::default()),
+            "."
+        );
     }
 
     #[test]
     fn test_group_key_dir_dotfile() {
         let item = make_item(".gitignore", 1, Tag::Todo, "msg", Priority::Normal);
-        assert_eq!(group_key(&item, &GroupBy::Dir), ".");
+        assert_eq!(
+            group_key(&item, &GroupBy::Dir, &SeverityOverrides::default()),
+            "."
+        );
     }
 
     // --- group_items: multiple items in same group, verify insertion order ---
@@ -2117,7 +3100,7 @@ mod tests {
             make_item("z.rs", 20, Tag::Todo, "second", Priority::Normal),
         ];
 
-        let groups = group_items(&items, &GroupBy::Tag);
+        let groups = group_items(&items, &GroupBy::Tag, &SeverityOverrides::default(), false);
         assert_eq!(groups.len(), 1);
         assert_eq!(groups[0].0, "TODO");
         // Items within a group maintain insertion order
@@ -2135,7 +3118,7 @@ mod tests {
             make_item("a.rs", 4, Tag::Bug, "a-bug-2", Priority::Normal),
         ];
 
-        let groups = group_items(&items, &GroupBy::Tag);
+        let groups = group_items(&items, &GroupBy::Tag, &SeverityOverrides::default(), false);
         // BUG (severity 5) before TODO (severity 1)
         assert_eq!(groups[0].0, "BUG");
         assert_eq!(groups[0].1.len(), 2);
@@ -2302,6 +3285,10 @@ mod tests {
             ],
             ignored_items: vec![],
             files_scanned: 2,
+            files_with_items: 0,
+            bytes_scanned: 0,
+            truncated: false,
+            total_found: None,
         };
 
         // Replicate the JSON branch of print_list
@@ -2348,15 +3335,24 @@ mod tests {
             items: vec![TodoItem {
                 file: "src/main.rs".to_string(),
                 line: 10,
+                byte_offset: 0,
+                cell: None,
+                labels: vec![],
                 tag: Tag::Todo,
                 message: "do something".to_string(),
                 author: Some("bob".to_string()),
                 issue_ref: Some("#42".to_string()),
                 priority: Priority::Urgent,
                 deadline: None,
+                ignore_reason: None,
+                severity: None,
             }],
             ignored_items: vec![],
             files_scanned: 1,
+            files_with_items: 0,
+            bytes_scanned: 0,
+            truncated: false,
+            total_found: None,
         };
 
         let mut value: serde_json::Value =
@@ -2395,6 +3391,10 @@ mod tests {
             )],
             ignored_items: vec![],
             files_scanned: 1,
+            files_with_items: 0,
+            bytes_scanned: 0,
+            truncated: false,
+            total_found: None,
         };
 
         let mut value: serde_json::Value =
@@ -2532,7 +3532,7 @@ mod tests {
         let json = serde_json::to_string_pretty(&check_result).expect("failed to serialize");
         let parsed: serde_json::Value = serde_json::from_str(&json).unwrap();
 
-        assert_eq!(parsed["passed"].as_bool().unwrap(), false);
+        assert!(!parsed["passed"].as_bool().unwrap());
         assert_eq!(parsed["total"].as_u64().unwrap(), 15);
 
         let violations = parsed["violations"].as_array().unwrap();
@@ -2556,7 +3556,7 @@ mod tests {
         let json = serde_json::to_string_pretty(&check_result).expect("failed to serialize");
         let parsed: serde_json::Value = serde_json::from_str(&json).unwrap();
 
-        assert_eq!(parsed["passed"].as_bool().unwrap(), true);
+        assert!(parsed["passed"].as_bool().unwrap());
         assert_eq!(parsed["total"].as_u64().unwrap(), 5);
         assert_eq!(parsed["violations"].as_array().unwrap().len(), 0);
     }
@@ -2591,6 +3591,7 @@ mod tests {
             total: 2,
             avg_age_days: 227,
             stale_count: 1,
+            unknown_count: 0,
             stale_threshold_days: 180,
         };
 
@@ -2616,13 +3617,13 @@ mod tests {
         );
         assert_eq!(entries[0]["file"].as_str().unwrap(), "src/main.rs");
         assert_eq!(entries[0]["blame"]["author"].as_str().unwrap(), "alice");
-        assert_eq!(entries[0]["stale"].as_bool().unwrap(), false);
+        assert!(!entries[0]["stale"].as_bool().unwrap());
 
         assert_eq!(
             entries[1]["id"].as_str().unwrap(),
             "src/lib.rs:FIXME:urgent fix"
         );
-        assert_eq!(entries[1]["stale"].as_bool().unwrap(), true);
+        assert!(entries[1]["stale"].as_bool().unwrap());
         assert_eq!(entries[1]["blame"]["age_days"].as_u64().unwrap(), 365);
 
         // Top-level fields
@@ -2640,6 +3641,7 @@ mod tests {
             violations: vec![
                 LintViolation {
                     rule: "missing_author".to_string(),
+                    severity: Severity::Error,
                     message: "TODO has no author".to_string(),
                     file: "src/main.rs".to_string(),
                     line: 5,
@@ -2647,6 +3649,7 @@ mod tests {
                 },
                 LintViolation {
                     rule: "vague_message".to_string(),
+                    severity: Severity::Error,
                     message: "Message is too vague".to_string(),
                     file: "src/lib.rs".to_string(),
                     line: 12,
@@ -2659,7 +3662,7 @@ mod tests {
         let json = serde_json::to_string_pretty(&lint_result).expect("failed to serialize");
         let parsed: serde_json::Value = serde_json::from_str(&json).unwrap();
 
-        assert_eq!(parsed["passed"].as_bool().unwrap(), false);
+        assert!(!parsed["passed"].as_bool().unwrap());
         assert_eq!(parsed["total_items"].as_u64().unwrap(), 10);
         assert_eq!(parsed["violation_count"].as_u64().unwrap(), 2);
 
@@ -2690,7 +3693,7 @@ mod tests {
         let json = serde_json::to_string_pretty(&lint_result).expect("failed to serialize");
         let parsed: serde_json::Value = serde_json::from_str(&json).unwrap();
 
-        assert_eq!(parsed["passed"].as_bool().unwrap(), true);
+        assert!(parsed["passed"].as_bool().unwrap());
         assert_eq!(parsed["violation_count"].as_u64().unwrap(), 0);
         assert_eq!(parsed["violations"].as_array().unwrap().len(), 0);
     }
@@ -2702,6 +3705,8 @@ mod tests {
             total_items: 8,
             stale_count: 1,
             duplicate_count: 1,
+            deadline_without_owner_count: 0,
+            stale_age_count: 0,
             violations: vec![
                 CleanViolation {
                     rule: "stale".to_string(),
@@ -2726,7 +3731,7 @@ mod tests {
         let json = serde_json::to_string_pretty(&clean_result).expect("failed to serialize");
         let parsed: serde_json::Value = serde_json::from_str(&json).unwrap();
 
-        assert_eq!(parsed["passed"].as_bool().unwrap(), false);
+        assert!(!parsed["passed"].as_bool().unwrap());
         assert_eq!(parsed["total_items"].as_u64().unwrap(), 8);
         assert_eq!(parsed["stale_count"].as_u64().unwrap(), 1);
         assert_eq!(parsed["duplicate_count"].as_u64().unwrap(), 1);
@@ -2754,13 +3759,15 @@ mod tests {
             total_items: 5,
             stale_count: 0,
             duplicate_count: 0,
+            deadline_without_owner_count: 0,
+            stale_age_count: 0,
             violations: vec![],
         };
 
         let json = serde_json::to_string_pretty(&clean_result).expect("failed to serialize");
         let parsed: serde_json::Value = serde_json::from_str(&json).unwrap();
 
-        assert_eq!(parsed["passed"].as_bool().unwrap(), true);
+        assert!(parsed["passed"].as_bool().unwrap());
         assert_eq!(parsed["stale_count"].as_u64().unwrap(), 0);
         assert_eq!(parsed["duplicate_count"].as_u64().unwrap(), 0);
         assert_eq!(parsed["violations"].as_array().unwrap().len(), 0);
@@ -2772,6 +3779,10 @@ mod tests {
             items: vec![],
             ignored_items: vec![],
             files_scanned: 0,
+            files_with_items: 0,
+            bytes_scanned: 0,
+            truncated: false,
+            total_found: None,
         };
 
         let mut value: serde_json::Value =
@@ -2877,7 +3888,7 @@ mod tests {
         let parsed: serde_json::Value = serde_json::from_str(&json).unwrap();
 
         assert_eq!(parsed["query"].as_str().unwrap(), "memory");
-        assert_eq!(parsed["exact"].as_bool().unwrap(), false);
+        assert!(!parsed["exact"].as_bool().unwrap());
         assert_eq!(parsed["match_count"].as_u64().unwrap(), 2);
         assert_eq!(parsed["file_count"].as_u64().unwrap(), 2);
 
@@ -2909,6 +3920,10 @@ mod tests {
             )],
             ignored_items: vec![],
             files_scanned: 1,
+            files_with_items: 0,
+            bytes_scanned: 0,
+            truncated: false,
+            total_found: None,
         };
 
         let mut context_map: HashMap<String, ContextInfo> = HashMap::new();
@@ -2998,16 +4013,26 @@ mod tests {
             ],
             ignored_items: vec![],
             files_scanned: 2,
+            files_with_items: 0,
+            bytes_scanned: 0,
+            truncated: false,
+            total_found: None,
         };
         let ctx = HashMap::new();
         print_list(
             &result,
-            &Format::Text,
+            &OutputOptions::new(Format::Text),
             &GroupBy::File,
             &ctx,
             0,
             false,
+            0,
             &DetailLevel::Normal,
+            false,
+            false,
+            None,
+            &[],
+            &HashMap::new(),
         );
     }
 
@@ -3021,16 +4046,26 @@ mod tests {
             ],
             ignored_items: vec![],
             files_scanned: 2,
+            files_with_items: 0,
+            bytes_scanned: 0,
+            truncated: false,
+            total_found: None,
         };
         let ctx = HashMap::new();
         print_list(
             &result,
-            &Format::Text,
+            &OutputOptions::new(Format::Text),
             &GroupBy::Tag,
             &ctx,
             0,
             false,
+            0,
             &DetailLevel::Normal,
+            false,
+            false,
+            None,
+            &[],
+            &HashMap::new(),
         );
     }
 
@@ -3044,16 +4079,26 @@ mod tests {
             ],
             ignored_items: vec![],
             files_scanned: 1,
+            files_with_items: 0,
+            bytes_scanned: 0,
+            truncated: false,
+            total_found: None,
         };
         let ctx = HashMap::new();
         print_list(
             &result,
-            &Format::Text,
+            &OutputOptions::new(Format::Text),
             &GroupBy::Priority,
             &ctx,
             0,
             false,
+            0,
             &DetailLevel::Normal,
+            false,
+            false,
+            None,
+            &[],
+            &HashMap::new(),
         );
     }
 
@@ -3069,6 +4114,10 @@ mod tests {
             )],
             ignored_items: vec![],
             files_scanned: 1,
+            files_with_items: 0,
+            bytes_scanned: 0,
+            truncated: false,
+            total_found: None,
         };
         let mut ctx = HashMap::new();
         ctx.insert(
@@ -3080,12 +4129,18 @@ mod tests {
         );
         print_list(
             &result,
-            &Format::Text,
+            &OutputOptions::new(Format::Text),
             &GroupBy::File,
             &ctx,
             0,
             false,
+            0,
             &DetailLevel::Normal,
+            false,
+            false,
+            None,
+            &[],
+            &HashMap::new(),
         );
     }
 
@@ -3110,17 +4165,27 @@ mod tests {
                 make_item("src/lib.rs", 5, Tag::Hack, "ignored hack", Priority::Normal),
             ],
             files_scanned: 2,
+            files_with_items: 2,
+            bytes_scanned: 0,
+            truncated: false,
+            total_found: None,
         };
         let ctx = HashMap::new();
         // show_ignored=true, ignored_count=2
         print_list(
             &result,
-            &Format::Text,
+            &OutputOptions::new(Format::Text),
             &GroupBy::File,
             &ctx,
             2,
             true,
+            0,
             &DetailLevel::Normal,
+            false,
+            false,
+            None,
+            &[],
+            &HashMap::new(),
         );
     }
 
@@ -3130,16 +4195,26 @@ mod tests {
             items: vec![make_item("a.rs", 1, Tag::Todo, "active", Priority::Normal)],
             ignored_items: vec![make_item("b.rs", 2, Tag::Note, "ignored", Priority::Normal)],
             files_scanned: 2,
+            files_with_items: 2,
+            bytes_scanned: 0,
+            truncated: false,
+            total_found: None,
         };
         let ctx = HashMap::new();
         print_list(
             &result,
-            &Format::Text,
+            &OutputOptions::new(Format::Text),
             &GroupBy::Tag,
             &ctx,
             1,
             true,
+            0,
             &DetailLevel::Normal,
+            false,
+            false,
+            None,
+            &[],
+            &HashMap::new(),
         );
     }
 
@@ -3163,17 +4238,27 @@ mod tests {
             items: vec![item],
             ignored_items: vec![],
             files_scanned: 1,
+            files_with_items: 0,
+            bytes_scanned: 0,
+            truncated: false,
+            total_found: None,
         };
         let ctx = HashMap::new();
         // With Minimal, author/issue/deadline should not appear
         print_list(
             &result,
-            &Format::Text,
+            &OutputOptions::new(Format::Text),
             &GroupBy::File,
             &ctx,
             0,
             false,
+            0,
             &DetailLevel::Minimal,
+            false,
+            false,
+            None,
+            &[],
+            &HashMap::new(),
         );
     }
 
@@ -3198,16 +4283,26 @@ mod tests {
             items: vec![item],
             ignored_items: vec![],
             files_scanned: 1,
+            files_with_items: 0,
+            bytes_scanned: 0,
+            truncated: false,
+            total_found: None,
         };
         let ctx = HashMap::new();
         print_list(
             &result,
-            &Format::Text,
+            &OutputOptions::new(Format::Text),
             &GroupBy::File,
             &ctx,
             0,
             false,
+            0,
             &DetailLevel::Full,
+            false,
+            false,
+            None,
+            &[],
+            &HashMap::new(),
         );
     }
 
@@ -3223,16 +4318,26 @@ mod tests {
             items: vec![item],
             ignored_items: vec![],
             files_scanned: 1,
+            files_with_items: 0,
+            bytes_scanned: 0,
+            truncated: false,
+            total_found: None,
         };
         let ctx = HashMap::new();
         print_list(
             &result,
-            &Format::Text,
+            &OutputOptions::new(Format::Text),
             &GroupBy::File,
             &ctx,
             0,
             false,
+            0,
             &DetailLevel::Normal,
+            false,
+            false,
+            None,
+            &[],
+            &HashMap::new(),
         );
     }
 
@@ -3242,17 +4347,27 @@ mod tests {
             items: vec![make_item("a.rs", 1, Tag::Todo, "task", Priority::Normal)],
             ignored_items: vec![],
             files_scanned: 1,
+            files_with_items: 0,
+            bytes_scanned: 0,
+            truncated: false,
+            total_found: None,
         };
         let ctx = HashMap::new();
         // ignored_count > 0 but show_ignored=false => just summary suffix
         print_list(
             &result,
-            &Format::Text,
+            &OutputOptions::new(Format::Text),
             &GroupBy::File,
             &ctx,
             3,
             false,
+            0,
             &DetailLevel::Normal,
+            false,
+            false,
+            None,
+            &[],
+            &HashMap::new(),
         );
     }
 
@@ -3285,10 +4400,11 @@ mod tests {
         let ctx = HashMap::new();
         print_search(
             &result,
-            &Format::Text,
+            &OutputOptions::new(Format::Text),
             &GroupBy::File,
             &ctx,
             &DetailLevel::Normal,
+            false,
         );
     }
 
@@ -3317,10 +4433,11 @@ mod tests {
         );
         print_search(
             &result,
-            &Format::Text,
+            &OutputOptions::new(Format::Text),
             &GroupBy::File,
             &ctx,
             &DetailLevel::Normal,
+            false,
         );
     }
 
@@ -3339,10 +4456,11 @@ mod tests {
         let ctx = HashMap::new();
         print_search(
             &result,
-            &Format::Text,
+            &OutputOptions::new(Format::Text),
             &GroupBy::Priority,
             &ctx,
             &DetailLevel::Normal,
+            false,
         );
     }
 
@@ -3372,10 +4490,11 @@ mod tests {
         let ctx = HashMap::new();
         print_search(
             &result,
-            &Format::Text,
+            &OutputOptions::new(Format::Text),
             &GroupBy::File,
             &ctx,
             &DetailLevel::Minimal,
+            false,
         );
     }
 
@@ -3405,10 +4524,11 @@ mod tests {
         let ctx = HashMap::new();
         print_search(
             &result,
-            &Format::Text,
+            &OutputOptions::new(Format::Text),
             &GroupBy::File,
             &ctx,
             &DetailLevel::Full,
+            false,
         );
     }
 
@@ -3432,7 +4552,14 @@ mod tests {
             base_ref: "main".to_string(),
         };
         let ctx = HashMap::new();
-        print_diff(&result, &Format::Text, &ctx, &DetailLevel::Normal);
+        print_diff(
+            &result,
+            &OutputOptions::new(Format::Text),
+            &ctx,
+            &ctx,
+            &DetailLevel::Normal,
+            false,
+        );
     }
 
     #[test]
@@ -3454,7 +4581,83 @@ mod tests {
                 after: vec![ctx_line(11, "}")],
             },
         );
-        print_diff(&result, &Format::Text, &ctx, &DetailLevel::Normal);
+        print_diff(
+            &result,
+            &OutputOptions::new(Format::Text),
+            &ctx,
+            &ctx,
+            &DetailLevel::Normal,
+            false,
+        );
+    }
+
+    #[test]
+    fn text_print_diff_symbols() {
+        let result = DiffResult {
+            entries: vec![DiffEntry {
+                status: DiffStatus::Removed,
+                item: make_item("src/main.rs", 5, Tag::Fixme, "old fix", Priority::High),
+            }],
+            added_count: 0,
+            removed_count: 1,
+            base_ref: "main".to_string(),
+        };
+        let ctx = HashMap::new();
+        print_diff(
+            &result,
+            &OutputOptions::new(Format::Text),
+            &ctx,
+            &ctx,
+            &DetailLevel::Normal,
+            true,
+        );
+    }
+
+    #[test]
+    fn test_format_diff_entry_symbols_markers() {
+        colored::control::set_override(false);
+        let added = DiffEntry {
+            status: DiffStatus::Added,
+            item: make_item("a.rs", 1, Tag::Todo, "new", Priority::Normal),
+        };
+        let removed = DiffEntry {
+            status: DiffStatus::Removed,
+            item: make_item("b.rs", 2, Tag::Bug, "old", Priority::Normal),
+        };
+        assert_eq!(
+            format_diff_entry(&added, true, &OutputOptions::new(Format::Text)),
+            "[+] a.rs:1 [TODO] new"
+        );
+        assert_eq!(
+            format_diff_entry(&removed, true, &OutputOptions::new(Format::Text)),
+            "[-] b.rs:2 [BUG] old"
+        );
+        assert_eq!(
+            format_diff_entry(&added, false, &OutputOptions::new(Format::Text)),
+            "+ a.rs:1 [TODO] new"
+        );
+        colored::control::unset_override();
+    }
+
+    // --- print_tags ---
+
+    #[test]
+    fn text_print_tags_with_aliases_and_disabled() {
+        let tags = vec![
+            TagInfo {
+                tag: Tag::Bug,
+                severity: Severity::Error,
+                enabled: true,
+                aliases: vec!["Defect".to_string()],
+            },
+            TagInfo {
+                tag: Tag::Note,
+                severity: Severity::Notice,
+                enabled: false,
+                aliases: vec![],
+            },
+        ];
+        print_tags(&tags, &OutputOptions::new(Format::Text));
     }
 
     // --- print_brief: Text format ---
@@ -3486,7 +4689,12 @@ mod tests {
                 base_ref: "main".to_string(),
             }),
         };
-        print_brief(&result, &Format::Text, None);
+        print_brief(
+            &result,
+            &OutputOptions::new(Format::Text),
+            None,
+            &BriefPrefer::Urgent,
+        );
     }
 
     #[test]
@@ -3502,7 +4710,12 @@ mod tests {
             top_urgent: None,
             trend: None,
         };
-        print_brief(&result, &Format::Text, None);
+        print_brief(
+            &result,
+            &OutputOptions::new(Format::Text),
+            None,
+            &BriefPrefer::Urgent,
+        );
     }
 
     #[test]
@@ -3523,7 +4736,84 @@ mod tests {
             }),
         };
         // Budget of 1 means only the summary line is printed
-        print_brief(&result, &Format::Text, Some(1));
+        print_brief(
+            &result,
+            &OutputOptions::new(Format::Text),
+            Some(1),
+            &BriefPrefer::Urgent,
+        );
+    }
+
+    fn brief_with_urgent_and_trend() -> BriefResult {
+        BriefResult {
+            total_items: 15,
+            total_files: 5,
+            priority_counts: PriorityCounts {
+                normal: 13,
+                high: 0,
+                urgent: 2,
+            },
+            top_urgent: Some(make_item("a.rs", 1, Tag::Bug, "crash", Priority::Urgent)),
+            trend: Some(TrendInfo {
+                added: 3,
+                removed: 1,
+                base_ref: "main".to_string(),
+            }),
+        }
+    }
+
+    #[test]
+    fn test_brief_lines_unbudgeted_order() {
+        let lines = brief_lines(&brief_with_urgent_and_trend(), None, &BriefPrefer::Trend);
+        assert_eq!(lines.len(), 3);
+        assert!(lines[0].starts_with("15 TODOs across 5 files"));
+        assert!(lines[1].starts_with("Top urgent: a.rs:1 BUG!! crash"));
+        assert!(lines[2].starts_with("Trends vs main"));
+    }
+
+    #[test]
+    fn test_brief_lines_budget_one_keeps_summary() {
+        for prefer in [BriefPrefer::Urgent, BriefPrefer::Trend] {
+            let lines = brief_lines(&brief_with_urgent_and_trend(), Some(1), &prefer);
+            assert_eq!(lines, vec!["15 TODOs across 5 files (2 urgent)"]);
+        }
+    }
+
+    #[test]
+    fn test_brief_lines_budget_two_prefers_urgent() {
+        let lines = brief_lines(
+            &brief_with_urgent_and_trend(),
+            Some(2),
+            &BriefPrefer::Urgent,
+        );
+        assert_eq!(lines.len(), 2);
+        assert!(lines[1].starts_with("Top urgent:"));
+    }
+
+    #[test]
+    fn test_brief_lines_budget_two_prefers_trend() {
+        let lines = brief_lines(&brief_with_urgent_and_trend(), Some(2), &BriefPrefer::Trend);
+        assert_eq!(lines.len(), 2);
+        assert!(lines[1].starts_with("Trends vs main"));
+    }
+
+    #[test]
+    fn test_brief_lines_budget_two_falls_back_when_preferred_missing() {
+        let mut result = brief_with_urgent_and_trend();
+        result.trend = None;
+        let lines = brief_lines(&result, Some(2), &BriefPrefer::Trend);
+        assert_eq!(lines.len(), 2);
+        assert!(lines[1].starts_with("Top urgent:"));
+    }
+
+    #[test]
+    fn test_brief_lines_budget_zero_is_empty() {
+        let lines = brief_lines(
+            &brief_with_urgent_and_trend(),
+            Some(0),
+            &BriefPrefer::Urgent,
+        );
+        assert!(lines.is_empty());
     }
 
     #[test]
@@ -3539,7 +4829,12 @@ mod tests {
             top_urgent: Some(make_item("a.rs", 1, Tag::Todo, "high prio", Priority::High)),
             trend: None,
         };
-        print_brief(&result, &Format::Text, None);
+        print_brief(
+            &result,
+            &OutputOptions::new(Format::Text),
+            None,
+            &BriefPrefer::Urgent,
+        );
     }
 
     // --- print_stats: Text format ---
@@ -3549,6 +4844,9 @@ mod tests {
         let result = StatsResult {
             total_items: 20,
             total_files: 5,
+            files_scanned: 40,
+            files_with_items: 5,
+            bytes_scanned: 0,
             tag_counts: vec![
                 (Tag::Todo, 10),
                 (Tag::Fixme, 5),
@@ -3569,13 +4867,15 @@ mod tests {
                 ("src/main.rs".to_string(), 8),
                 ("src/lib.rs".to_string(), 5),
             ],
+            dir_counts: vec![("src".to_string(), 13), (".".to_string(), 7)],
+            tree: None,
             trend: Some(TrendInfo {
                 added: 5,
                 removed: 2,
                 base_ref: "main".to_string(),
             }),
         };
-        print_stats(&result, &Format::Text);
+        print_stats(&result, &OutputOptions::new(Format::Text));
     }
 
     #[test]
@@ -3583,6 +4883,9 @@ mod tests {
         let result = StatsResult {
             total_items: 0,
             total_files: 0,
+            files_scanned: 0,
+            files_with_items: 0,
+            bytes_scanned: 0,
             tag_counts: vec![],
             priority_counts: PriorityCounts {
                 normal: 0,
@@ -3591,9 +4894,11 @@ mod tests {
             },
             author_counts: vec![],
             hotspot_files: vec![],
+            dir_counts: vec![],
+            tree: None,
             trend: None,
         };
-        print_stats(&result, &Format::Text);
+        print_stats(&result, &OutputOptions::new(Format::Text));
     }
 
     #[test]
@@ -3601,6 +4906,9 @@ mod tests {
         let result = StatsResult {
             total_items: 5,
             total_files: 2,
+            files_scanned: 2,
+            files_with_items: 2,
+            bytes_scanned: 0,
             tag_counts: vec![(Tag::Todo, 5)],
             priority_counts: PriorityCounts {
                 normal: 5,
@@ -3609,13 +4917,15 @@ mod tests {
             },
             author_counts: vec![],
             hotspot_files: vec![],
+            dir_counts: vec![],
+            tree: None,
             trend: Some(TrendInfo {
                 added: 1,
                 removed: 3,
                 base_ref: "develop".to_string(),
             }),
         };
-        print_stats(&result, &Format::Text);
+        print_stats(&result, &OutputOptions::new(Format::Text));
     }
 
     // --- print_lint: Text format ---
@@ -3628,7 +4938,7 @@ mod tests {
             violation_count: 0,
             violations: vec![],
         };
-        print_lint(&result, &Format::Text);
+        print_lint(&result, &OutputOptions::new(Format::Text));
     }
 
     #[test]
@@ -3640,6 +4950,7 @@ mod tests {
             violations: vec![
                 LintViolation {
                     rule: "missing-author".to_string(),
+                    severity: Severity::Error,
                     message: "TODO has no author".to_string(),
                     file: "src/main.rs".to_string(),
                     line: 10,
@@ -3647,6 +4958,7 @@ mod tests {
                 },
                 LintViolation {
                     rule: "vague-message".to_string(),
+                    severity: Severity::Error,
                     message: "Message is too vague".to_string(),
                     file: "src/main.rs".to_string(),
                     line: 20,
@@ -3654,6 +4966,7 @@ mod tests {
                 },
                 LintViolation {
                     rule: "missing-issue".to_string(),
+                    severity: Severity::Error,
                     message: "TODO lacks issue reference".to_string(),
                     file: "src/lib.rs".to_string(),
                     line: 5,
@@ -3661,7 +4974,7 @@ mod tests {
                 },
             ],
         };
-        print_lint(&result, &Format::Text);
+        print_lint(&result, &OutputOptions::new(Format::Text));
     }
 
     // --- print_clean: Text format ---
@@ -3673,9 +4986,11 @@ mod tests {
             total_items: 10,
             stale_count: 0,
             duplicate_count: 0,
+            deadline_without_owner_count: 0,
+            stale_age_count: 0,
             violations: vec![],
         };
-        print_clean(&result, &Format::Text);
+        print_clean(&result, &OutputOptions::new(Format::Text));
     }
 
     #[test]
@@ -3685,6 +5000,8 @@ mod tests {
             total_items: 10,
             stale_count: 2,
             duplicate_count: 1,
+            deadline_without_owner_count: 0,
+            stale_age_count: 0,
             violations: vec![
                 CleanViolation {
                     rule: "stale".to_string(),
@@ -3712,7 +5029,7 @@ mod tests {
                 },
             ],
         };
-        print_clean(&result, &Format::Text);
+        print_clean(&result, &OutputOptions::new(Format::Text));
     }
 
     // --- print_check: Text format ---
@@ -3724,7 +5041,7 @@ mod tests {
             total: 10,
             violations: vec![],
         };
-        print_check(&result, &Format::Text);
+        print_check(&result, &OutputOptions::new(Format::Text));
     }
 
     #[test]
@@ -3743,7 +5060,7 @@ mod tests {
                 },
             ],
         };
-        print_check(&result, &Format::Text);
+        print_check(&result, &OutputOptions::new(Format::Text));
     }
 
     // --- print_blame: Text format ---
@@ -3789,9 +5106,10 @@ mod tests {
             total: 3,
             avg_age_days: 203,
             stale_count: 2,
+            unknown_count: 0,
             stale_threshold_days: 90,
         };
-        print_blame(&result, &Format::Text);
+        print_blame(&result, &OutputOptions::new(Format::Text), false);
     }
 
     // --- print_context: Text format ---
@@ -3804,6 +5122,7 @@ mod tests {
             before: vec![ctx_line(8, "fn main() {"), ctx_line(9, "    let x = 1;")],
             todo_line: "    // TODO: fix this".to_string(),
             after: vec![ctx_line(11, "    let y = 2;"), ctx_line(12, "}")],
+            enclosing_symbol: Some("main".to_string()),
             related_todos: vec![
                 RelatedTodo {
                     line: 25,
@@ -3817,7 +5136,7 @@ mod tests {
                 },
             ],
         };
-        print_context(&rich, &Format::Text);
+        print_context(&rich, &OutputOptions::new(Format::Text));
     }
 
     #[test]
@@ -3828,9 +5147,10 @@ mod tests {
             before: vec![],
             todo_line: "// NOTE: important".to_string(),
             after: vec![ctx_line(6, "fn foo() {}")],
+            enclosing_symbol: None,
             related_todos: vec![],
         };
-        print_context(&rich, &Format::Text);
+        print_context(&rich, &OutputOptions::new(Format::Text));
     }
 
     // --- print_initial_summary ---
@@ -3838,13 +5158,13 @@ mod tests {
     #[test]
     fn text_print_initial_summary() {
         let tag_counts = vec![(Tag::Todo, 10), (Tag::Fixme, 5), (Tag::Bug, 2)];
-        print_initial_summary(&tag_counts, 17, &Format::Text);
+        print_initial_summary(&tag_counts, 17, &OutputOptions::new(Format::Text));
     }
 
     #[test]
     fn text_print_initial_summary_json_format() {
         let tag_counts = vec![(Tag::Todo, 3)];
-        print_initial_summary(&tag_counts, 3, &Format::Json);
+        print_initial_summary(&tag_counts, 3, &OutputOptions::new(Format::Json));
     }
 
     // --- print_watch_event ---
@@ -3863,7 +5183,7 @@ mod tests {
             total: 20,
             total_delta: 2,
         };
-        print_watch_event(&event, &Format::Text, None);
+        print_watch_event(&event, &OutputOptions::new(Format::Text), None);
     }
 
     #[test]
@@ -3883,7 +5203,7 @@ mod tests {
             total: 18,
             total_delta: -1,
         };
-        print_watch_event(&event, &Format::Text, None);
+        print_watch_event(&event, &OutputOptions::new(Format::Text), None);
     }
 
     #[test]
@@ -3909,7 +5229,7 @@ mod tests {
             total: 20,
             total_delta: 0,
         };
-        print_watch_event(&event, &Format::Text, None);
+        print_watch_event(&event, &OutputOptions::new(Format::Text), None);
     }
 
     #[test]
@@ -3930,7 +5250,7 @@ mod tests {
             total_delta: 1,
         };
         // total (100) >= max (100), should print warning
-        print_watch_event(&event, &Format::Text, Some(100));
+        print_watch_event(&event, &OutputOptions::new(Format::Text), Some(100));
     }
 
     #[test]
@@ -3945,7 +5265,7 @@ mod tests {
             total_delta: 0,
         };
         // total (50) < max (100), no warning
-        print_watch_event(&event, &Format::Text, Some(100));
+        print_watch_event(&event, &OutputOptions::new(Format::Text), Some(100));
     }
 
     // --- print_tasks ---
@@ -4000,7 +5320,7 @@ mod tests {
             total: 3,
             output_dir: Some("/tmp/tasks".to_string()),
         };
-        print_tasks(&result, &Format::Text);
+        print_tasks(&result, &OutputOptions::new(Format::Text));
     }
 
     #[test]
@@ -4010,7 +5330,7 @@ mod tests {
             total: 0,
             output_dir: None,
         };
-        print_tasks(&result, &Format::Text);
+        print_tasks(&result, &OutputOptions::new(Format::Text));
     }
 
     #[test]
@@ -4033,7 +5353,7 @@ mod tests {
             total: 1,
             output_dir: None,
         };
-        print_tasks(&result, &Format::Text);
+        print_tasks(&result, &OutputOptions::new(Format::Text));
     }
 
     // --- print_relate ---
@@ -4047,12 +5367,14 @@ mod tests {
                     to: "src/main.rs:20".to_string(),
                     score: 0.85,
                     reason: "same file, similar message".to_string(),
+                    explanation: String::new(),
                 },
                 Relationship {
                     from: "src/lib.rs:5".to_string(),
                     to: "src/auth.rs:15".to_string(),
                     score: 0.65,
                     reason: "related keywords".to_string(),
+                    explanation: String::new(),
                 },
             ],
             clusters: None,
@@ -4061,7 +5383,7 @@ mod tests {
             min_score: 0.5,
             target: None,
         };
-        print_relate(&result, &Format::Text);
+        print_relate(&result, &OutputOptions::new(Format::Text), false);
     }
 
     #[test]
@@ -4072,6 +5394,7 @@ mod tests {
                 to: "src/main.rs:20".to_string(),
                 score: 0.9,
                 reason: "proximity".to_string(),
+                explanation: String::new(),
             }],
             clusters: None,
             total_relationships: 1,
@@ -4079,7 +5402,31 @@ mod tests {
             min_score: 0.3,
             target: Some("src/main.rs:10".to_string()),
         };
-        print_relate(&result, &Format::Text);
+        print_relate(&result, &OutputOptions::new(Format::Text), false);
+        print_relate(&result, &OutputOptions::new(Format::Text), true);
+    }
+
+    #[test]
+    fn format_relationship_explain_uses_explanation() {
+        let rel = Relationship {
+            from: "src/main.rs:10".to_string(),
+            to: "src/main.rs:20".to_string(),
+            score: 0.9,
+            reason: "proximity".to_string(),
+            explanation: "Related because they are 10 lines apart in src/main.rs (+0.40)."
+                .to_string(),
+        };
+
+        let explained = format_relationship(&rel, "  ", true);
+        assert!(explained.starts_with("  src/main.rs:10 ↔ src/main.rs:20 (score: 0.90)\n    "));
+        assert!(explained.contains("Related because they are 10 lines apart in src/main.rs"));
+        assert!(!explained.contains("proximity"));
+
+        let short = format_relationship(&rel, "  ", false);
+        assert_eq!(
+            short,
+            "  src/main.rs:10 ↔ src/main.rs:20 (score: 0.90, proximity)"
+        );
     }
 
     #[test]
@@ -4090,6 +5437,7 @@ mod tests {
                 to: "src/auth.rs:20".to_string(),
                 score: 0.8,
                 reason: "same module".to_string(),
+                explanation: String::new(),
             }],
             clusters: Some(vec![
                 Cluster {
@@ -4105,6 +5453,7 @@ mod tests {
                         to: "src/auth.rs:20".to_string(),
                         score: 0.8,
                         reason: "same module".to_string(),
+                        explanation: String::new(),
                     }],
                 },
                 Cluster {
@@ -4120,7 +5469,7 @@ mod tests {
             min_score: 0.5,
             target: None,
         };
-        print_relate(&result, &Format::Text);
+        print_relate(&result, &OutputOptions::new(Format::Text), false);
     }
 
     #[test]
@@ -4133,7 +5482,7 @@ mod tests {
             min_score: 0.5,
             target: None,
         };
-        print_relate(&result, &Format::Text);
+        print_relate(&result, &OutputOptions::new(Format::Text), false);
     }
 
     // --- print_workspace_list ---
@@ -4167,7 +5516,11 @@ mod tests {
             total_todos: 23,
             total_packages: 3,
         };
-        print_workspace_list(&result, &Format::Text, &WorkspaceKind::Cargo);
+        print_workspace_list(
+            &result,
+            &OutputOptions::new(Format::Text),
+            &WorkspaceKind::Cargo,
+        );
     }
 
     #[test]
@@ -4183,7 +5536,11 @@ mod tests {
             total_todos: 2,
             total_packages: 1,
         };
-        print_workspace_list(&result, &Format::Text, &WorkspaceKind::Npm);
+        print_workspace_list(
+            &result,
+            &OutputOptions::new(Format::Text),
+            &WorkspaceKind::Npm,
+        );
     }
 
     // --- print_report ---
@@ -4243,7 +5600,7 @@ mod tests {
         let tmp = tempfile::TempDir::new().unwrap();
         let path = tmp.path().join("report.html");
         let path_str = path.to_str().unwrap();
-        print_report(&report, path_str).unwrap();
+        print_report(&report, path_str, &OutputOptions::new(Format::Text)).unwrap();
         assert!(path.exists());
         let content = std::fs::read_to_string(&path).unwrap();
         assert!(content.contains("html"));
@@ -4287,7 +5644,12 @@ mod tests {
                 Some("alice"),
             ),
         ];
-        let groups = group_items(&items, &GroupBy::Author);
+        let groups = group_items(
+            &items,
+            &GroupBy::Author,
+            &SeverityOverrides::default(),
+            false,
+        );
         // alice has 2 items, unassigned has 1
         assert_eq!(groups.len(), 2);
     }
@@ -4300,7 +5662,7 @@ mod tests {
             make_item("tests/test.rs", 3, Tag::Todo, "task3", Priority::Normal),
             make_item("root_file.rs", 4, Tag::Todo, "task4", Priority::Normal),
         ];
-        let groups = group_items(&items, &GroupBy::Dir);
+        let groups = group_items(&items, &GroupBy::Dir, &SeverityOverrides::default(), false);
         // src, tests, . (root)
         assert_eq!(groups.len(), 3);
     }
@@ -4310,11 +5672,11 @@ mod tests {
     #[test]
     fn test_colorize_tag_all_variants() {
         // Just ensure no panic for every tag variant
-        colorize_tag(&Tag::Todo);
-        colorize_tag(&Tag::Fixme);
-        colorize_tag(&Tag::Hack);
-        colorize_tag(&Tag::Bug);
-        colorize_tag(&Tag::Note);
-        colorize_tag(&Tag::Xxx);
+        colorize_tag(&Tag::Todo, &OutputOptions::new(Format::Text));
+        colorize_tag(&Tag::Fixme, &OutputOptions::new(Format::Text));
+        colorize_tag(&Tag::Hack, &OutputOptions::new(Format::Text));
+        colorize_tag(&Tag::Bug, &OutputOptions::new(Format::Text));
+        colorize_tag(&Tag::Note, &OutputOptions::new(Format::Text));
+        colorize_tag(&Tag::Xxx, &OutputOptions::new(Format::Text));
     }
 }
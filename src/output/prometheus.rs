@@ -0,0 +1,183 @@
+use std::fmt::Write;
+
+use crate::model::*;
+
+/// Escape a label value per the Prometheus text exposition format.
+fn escape_label(value: &str) -> String {
+    value
+        .replace('\\', "\\\\")
+        .replace('"', "\\\"")
+        .replace('\n', "\\n")
+}
+
+/// Append the `# HELP` and `# TYPE` lines that introduce a gauge.
+fn push_header(out: &mut String, name: &str, help: &str) {
+    let _ = writeln!(out, "# HELP {name} {help}");
+    let _ = writeln!(out, "# TYPE {name} gauge");
+}
+
+/// Append a gauge with one sample per `(label value, count)` pair.
+fn push_labeled<'a>(
+    out: &mut String,
+    name: &str,
+    help: &str,
+    label: &str,
+    samples: impl IntoIterator<Item = (&'a str, usize)>,
+) {
+    push_header(out, name, help);
+    for (value, count) in samples {
+        let _ = writeln!(out, "{name}{{{label}=\"{}\"}} {count}", escape_label(value));
+    }
+}
+
+/// Append a gauge with a single unlabeled sample.
+fn push_value(out: &mut String, name: &str, help: &str, value: impl std::fmt::Display) {
+    push_header(out, name, help);
+    let _ = writeln!(out, "{name} {value}");
+}
+
+/// Render stats as Prometheus text exposition metrics. `stale` is the number
+/// of items older than the blame stale threshold; its metric is left out when
+/// unknown (outside git).
+pub fn format_stats(result: &StatsResult, stale: Option<usize>) -> String {
+    let mut out = String::new();
+    push_labeled(
+        &mut out,
+        "todoscan_items_total",
+        "TODO comments found, by tag.",
+        "tag",
+        result.tag_counts.iter().map(|(tag, n)| (tag.as_str(), *n)),
+    );
+    let priorities = &result.priority_counts;
+    push_labeled(
+        &mut out,
+        "todoscan_priority_items_total",
+        "TODO comments found, by priority.",
+        "priority",
+        [
+            ("normal", priorities.normal),
+            ("high", priorities.high),
+            ("urgent", priorities.urgent),
+        ],
+    );
+    push_value(
+        &mut out,
+        "todoscan_urgent_total",
+        "TODO comments marked urgent (!!).",
+        priorities.urgent,
+    );
+    if let Some(stale) = stale {
+        push_value(
+            &mut out,
+            "todoscan_stale_total",
+            "TODO comments older than the blame stale threshold.",
+            stale,
+        );
+    }
+    push_labeled(
+        &mut out,
+        "todoscan_author_items_total",
+        "TODO comments found, by author.",
+        "author",
+        result.author_counts.iter().map(|(a, n)| (a.as_str(), *n)),
+    );
+    push_value(
+        &mut out,
+        "todoscan_files_with_items",
+        "Files containing at least one TODO comment.",
+        result.files_with_items,
+    );
+    push_value(
+        &mut out,
+        "todoscan_files_scanned",
+        "Files scanned.",
+        result.files_scanned,
+    );
+    if let Some(trend) = &result.trend {
+        push_value(
+            &mut out,
+            "todoscan_added_total",
+            "TODO comments added since the --since ref.",
+            trend.added,
+        );
+        push_value(
+            &mut out,
+            "todoscan_removed_total",
+            "TODO comments removed since the --since ref.",
+            trend.removed,
+        );
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample_stats() -> StatsResult {
+        StatsResult {
+            total_items: 4,
+            total_files: 2,
+            files_scanned: 5,
+            files_with_items: 2,
+            bytes_scanned: 0,
+            tag_counts: vec![(Tag::Todo, 3), (Tag::Bug, 1)],
+            priority_counts: PriorityCounts {
+                normal: 2,
+                high: 1,
+                urgent: 1,
+            },
+            author_counts: vec![("alice".to_string(), 2), ("o\"brien\\x".to_string(), 1)],
+            hotspot_files: vec![],
+            dir_counts: vec![],
+            tree: None,
+            trend: None,
+        }
+    }
+
+    #[test]
+    fn test_metrics_names_labels_and_values() {
+        let out = format_stats(&sample_stats(), Some(2));
+        let samples: Vec<&str> = out.lines().filter(|l| !l.starts_with('#')).collect();
+        assert_eq!(
+            samples,
+            [
+                "todoscan_items_total{tag=\"TODO\"} 3",
+                "todoscan_items_total{tag=\"BUG\"} 1",
+                "todoscan_priority_items_total{priority=\"normal\"} 2",
+                "todoscan_priority_items_total{priority=\"high\"} 1",
+                "todoscan_priority_items_total{priority=\"urgent\"} 1",
+                "todoscan_urgent_total 1",
+                "todoscan_stale_total 2",
+                "todoscan_author_items_total{author=\"alice\"} 2",
+                "todoscan_author_items_total{author=\"o\\\"brien\\\\x\"} 1",
+                "todoscan_files_with_items 2",
+                "todoscan_files_scanned 5",
+            ]
+        );
+        assert!(out.contains("# TYPE todoscan_items_total gauge\n"));
+        assert!(out.ends_with('\n'));
+    }
+
+    #[test]
+    fn test_stale_and_trend_metrics_are_optional() {
+        let out = format_stats(&sample_stats(), None);
+        assert!(!out.contains("todoscan_stale_total"));
+        assert!(!out.contains("todoscan_added_total"));
+
+        let mut stats = sample_stats();
+        stats.trend = Some(TrendInfo {
+            added: 3,
+            removed: 1,
+            base_ref: "main".to_string(),
+        });
+        let out = format_stats(&stats, None);
+        assert!(out.contains("\ntodoscan_added_total 3\n"));
+        assert!(out.contains("\ntodoscan_removed_total 1\n"));
+    }
+
+    #[test]
+    fn test_escape_label() {
+        assert_eq!(escape_label("a\\b\"c\nd"), "a\\\\b\\\"c\\nd");
+    }
+}
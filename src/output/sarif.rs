@@ -1,23 +1,185 @@
+use std::collections::HashSet;
+use std::path::Path;
+
+use anyhow::{Context, Result};
+
+use super::OutputOptions;
 use crate::model::*;
 
-fn build_sarif_envelope(results: Vec<serde_json::Value>, rules: Vec<serde_json::Value>) -> String {
+/// Load the item ids of a `--sarif-baseline` file, against which SARIF
+/// results are marked with `baselineState` `new` or `unchanged`.
+pub fn load_baseline(path: &Path) -> Result<HashSet<String>> {
+    let content = std::fs::read_to_string(path)
+        .with_context(|| format!("Failed to read SARIF baseline: {}", path.display()))?;
+    parse_baseline(&content)
+        .with_context(|| format!("Failed to parse SARIF baseline: {}", path.display()))
+}
+
+/// Item ids from a previous SARIF log, or from a plain list with one id per
+/// line (as printed in the `id` field of JSON output).
+fn parse_baseline(content: &str) -> Result<HashSet<String>> {
+    if !content.trim_start().starts_with('{') {
+        return Ok(content
+            .lines()
+            .map(str::trim)
+            .filter(|l| !l.is_empty())
+            .map(str::to_string)
+            .collect());
+    }
+    let sarif: serde_json::Value = serde_json::from_str(content)?;
+    let runs = sarif["runs"]
+        .as_array()
+        .context("not a SARIF log: missing runs")?;
+    let mut ids = HashSet::new();
+    for run in runs {
+        let Some(results) = run["results"].as_array() else {
+            continue;
+        };
+        ids.extend(
+            results
+                .iter()
+                .filter_map(|r| result_id(r, &run["artifacts"])),
+        );
+    }
+    Ok(ids)
+}
+
+/// Rebuild [`TodoItem::id`] from a result written by [`item_to_result`],
+/// resolving its location through the run's `artifacts` when it only has an
+/// `index`.
+fn result_id(result: &serde_json::Value, artifacts: &serde_json::Value) -> Option<String> {
+    // Lint, check and clean results use nested rule ids and are skipped
+    let tag: Tag = result["ruleId"]
+        .as_str()?
+        .strip_prefix("todo-scan/")?
+        .parse()
+        .ok()?;
+    let location = result.pointer("/locations/0/physicalLocation/artifactLocation")?;
+    let uri = match location.get("uri") {
+        Some(uri) => uri.as_str()?,
+        None => artifacts
+            .get(location.get("index")?.as_u64()? as usize)?
+            .pointer("/location/uri")?
+            .as_str()?,
+    };
+    let message = result.pointer("/message/text")?.as_str()?;
+    Some(format!("{}:{}:{}", uri, tag, message.trim().to_lowercase()))
+}
+
+fn baseline_state(item: &TodoItem, baseline: &HashSet<String>) -> &'static str {
+    if baseline.contains(&item.id()) {
+        "unchanged"
+    } else {
+        "new"
+    }
+}
+
+fn build_sarif_envelope(
+    mut results: Vec<serde_json::Value>,
+    rules: Vec<serde_json::Value>,
+) -> String {
+    let artifacts = index_artifacts(&mut results);
+    let invocation = build_invocation(&results);
+    let mut run = serde_json::json!({
+        "tool": {
+            "driver": {
+                "name": "todo-scan",
+                "version": env!("CARGO_PKG_VERSION"),
+                "rules": rules
+            }
+        },
+        "invocations": [invocation],
+        "results": results
+    });
+    if !artifacts.is_empty() {
+        run.as_object_mut()
+            .expect("SARIF run should be a JSON object")
+            .insert("artifacts".to_string(), serde_json::Value::Array(artifacts));
+    }
     let sarif = serde_json::json!({
         "$schema": "https://raw.githubusercontent.com/oasis-tcs/sarif-spec/main/sarif-2.1/schema/sarif-schema-2.1.0.json",
         "version": "2.1.0",
-        "runs": [{
-            "tool": {
-                "driver": {
-                    "name": "todo-scan",
-                    "version": env!("CARGO_PKG_VERSION"),
-                    "rules": rules
-                }
-            },
-            "results": results
-        }]
+        "runs": [run]
     });
     serde_json::to_string_pretty(&sarif).expect("failed to serialize SARIF")
 }
 
+/// Describe this run: its arguments (as an array, so none containing spaces
+/// get split), when it finished, and a note with the result total and
+/// per-level counts.
+fn build_invocation(results: &[serde_json::Value]) -> serde_json::Value {
+    let count = |level: &str| results.iter().filter(|r| r["level"] == level).count();
+    let summary = format!(
+        "todo-scan: {} results ({} error, {} warning, {} note)",
+        results.len(),
+        count("error"),
+        count("warning"),
+        count("note")
+    );
+    serde_json::json!({
+        "executionSuccessful": true,
+        "arguments": std::env::args_os()
+            .skip(1)
+            .map(|a| a.to_string_lossy().into_owned())
+            .collect::<Vec<_>>(),
+        "endTimeUtc": crate::date_utils::now_iso8601(),
+        "toolExecutionNotifications": [{
+            "level": "note",
+            "message": { "text": summary }
+        }]
+    })
+}
+
+/// Build the run-level `artifacts` array and point every result location at it.
+///
+/// Each distinct URI appears once, in first-seen order. Locations and related
+/// locations keep their `uri`, which GitHub code scanning requires, next to
+/// the `index`.
+fn index_artifacts(results: &mut [serde_json::Value]) -> Vec<serde_json::Value> {
+    let mut artifacts = Vec::new();
+    let mut index_of: std::collections::HashMap<String, usize> = std::collections::HashMap::new();
+
+    let locations = results.iter_mut().flat_map(|r| {
+        r.as_object_mut()
+            .into_iter()
+            .flat_map(|r| r.iter_mut())
+            .filter(|(key, _)| *key == "locations" || *key == "relatedLocations")
+            .filter_map(|(_, l)| l.as_array_mut())
+            .flatten()
+    });
+    for location in locations {
+        let Some(artifact) = location
+            .pointer_mut("/physicalLocation/artifactLocation")
+            .and_then(|a| a.as_object_mut())
+        else {
+            continue;
+        };
+        let Some(uri) = artifact.get("uri").and_then(|u| u.as_str()) else {
+            continue;
+        };
+        let index = *index_of.entry(uri.to_string()).or_insert_with(|| {
+            artifacts.push(serde_json::json!({ "location": { "uri": uri } }));
+            artifacts.len() - 1
+        });
+        artifact.insert("index".to_string(), serde_json::json!(index));
+    }
+
+    artifacts
+}
+
+/// SARIF physical location for `file` at `line`. Items found in file names sit
+/// on line 0, which SARIF forbids, so they get no `region`.
+fn physical_location(file: &str, line: usize) -> serde_json::Value {
+    if line == 0 {
+        serde_json::json!({ "artifactLocation": { "uri": file } })
+    } else {
+        serde_json::json!({
+            "artifactLocation": { "uri": file },
+            "region": { "startLine": line }
+        })
+    }
+}
+
 fn rule_id(tag: &Tag) -> String {
     format!("todo-scan/{}", tag.as_str())
 }
@@ -39,24 +201,15 @@ fn collect_rules(items: &[&TodoItem]) -> Vec<serde_json::Value> {
     rules
 }
 
-fn item_to_result(item: &TodoItem) -> serde_json::Value {
-    let severity = Severity::from_item(item);
+fn item_to_result(item: &TodoItem, out: &OutputOptions) -> serde_json::Value {
+    let severity = out.severity.severity_of(item);
     let mut result = serde_json::json!({
         "ruleId": rule_id(&item.tag),
         "level": severity.as_sarif_level(),
         "message": {
             "text": item.message
         },
-        "locations": [{
-            "physicalLocation": {
-                "artifactLocation": {
-                    "uri": item.file
-                },
-                "region": {
-                    "startLine": item.line
-                }
-            }
-        }]
+        "locations": [{ "physicalLocation": physical_location(&item.file, item.line) }]
     });
     if let Some(ref deadline) = item.deadline {
         result
@@ -67,11 +220,24 @@ fn item_to_result(item: &TodoItem) -> serde_json::Value {
                 serde_json::json!({ "deadline": deadline.to_string() }),
             );
     }
+    if let Some(baseline) = &out.sarif_baseline {
+        result
+            .as_object_mut()
+            .expect("SARIF result should be a JSON object")
+            .insert(
+                "baselineState".to_string(),
+                serde_json::json!(baseline_state(item, baseline)),
+            );
+    }
     result
 }
 
-pub fn format_list(result: &ScanResult) -> String {
-    let results: Vec<serde_json::Value> = result.items.iter().map(item_to_result).collect();
+pub fn format_list(result: &ScanResult, out: &OutputOptions) -> String {
+    let results: Vec<serde_json::Value> = result
+        .items
+        .iter()
+        .map(|item| item_to_result(item, out))
+        .collect();
     let all_items: Vec<&TodoItem> = result.items.iter().collect();
     let rules = collect_rules(&all_items);
     let mut output = build_sarif_envelope(results, rules);
@@ -79,8 +245,12 @@ pub fn format_list(result: &ScanResult) -> String {
     output
 }
 
-pub fn format_search(result: &SearchResult) -> String {
-    let results: Vec<serde_json::Value> = result.items.iter().map(item_to_result).collect();
+pub fn format_search(result: &SearchResult, out: &OutputOptions) -> String {
+    let results: Vec<serde_json::Value> = result
+        .items
+        .iter()
+        .map(|item| item_to_result(item, out))
+        .collect();
     let all_items: Vec<&TodoItem> = result.items.iter().collect();
     let rules = collect_rules(&all_items);
     let mut output = build_sarif_envelope(results, rules);
@@ -88,12 +258,12 @@ pub fn format_search(result: &SearchResult) -> String {
     output
 }
 
-pub fn format_diff(result: &DiffResult) -> String {
+pub fn format_diff(result: &DiffResult, out: &OutputOptions) -> String {
     let results: Vec<serde_json::Value> = result
         .entries
         .iter()
         .map(|entry| {
-            let mut r = item_to_result(&entry.item);
+            let mut r = item_to_result(&entry.item, out);
             let status = match entry.status {
                 DiffStatus::Added => "added",
                 DiffStatus::Removed => "removed",
@@ -115,26 +285,33 @@ pub fn format_diff(result: &DiffResult) -> String {
     output
 }
 
-pub fn format_blame(result: &BlameResult) -> String {
+pub fn format_blame(result: &BlameResult, out: &OutputOptions) -> String {
     let results: Vec<serde_json::Value> = result
         .entries
         .iter()
         .map(|entry| {
-            let mut r = item_to_result(&entry.item);
+            let mut r = item_to_result(&entry.item, out);
+            let blame = if entry.blame.is_unknown() {
+                serde_json::json!({
+                    "author": entry.blame.author,
+                    "unknown": true,
+                    "stale": false,
+                })
+            } else {
+                serde_json::json!({
+                    "author": entry.blame.author,
+                    "email": entry.blame.email,
+                    "date": entry.blame.date,
+                    "ageDays": entry.blame.age_days,
+                    "commit": entry.blame.commit,
+                    "stale": entry.stale,
+                })
+            };
             r.as_object_mut()
                 .expect("SARIF result should be a JSON object")
                 .insert(
                     "properties".to_string(),
-                    serde_json::json!({
-                        "blame": {
-                            "author": entry.blame.author,
-                            "email": entry.blame.email,
-                            "date": entry.blame.date,
-                            "ageDays": entry.blame.age_days,
-                            "commit": entry.blame.commit,
-                            "stale": entry.stale,
-                        }
-                    }),
+                    serde_json::json!({ "blame": blame }),
                 );
             r
         })
@@ -154,20 +331,11 @@ pub fn format_lint(result: &LintResult) -> String {
         .map(|v| {
             let mut r = serde_json::json!({
                 "ruleId": format!("todo-scan/lint/{}", v.rule),
-                "level": "error",
+                "level": v.severity.as_sarif_level(),
                 "message": {
                     "text": v.message
                 },
-                "locations": [{
-                    "physicalLocation": {
-                        "artifactLocation": {
-                            "uri": v.file
-                        },
-                        "region": {
-                            "startLine": v.line
-                        }
-                    }
-                }]
+                "locations": [{ "physicalLocation": physical_location(&v.file, v.line) }]
             });
             if let Some(ref suggestion) = v.suggestion {
                 r.as_object_mut()
@@ -300,16 +468,7 @@ pub fn format_clean(result: &CleanResult) -> String {
                 "message": {
                     "text": v.message
                 },
-                "locations": [{
-                    "physicalLocation": {
-                        "artifactLocation": {
-                            "uri": v.file
-                        },
-                        "region": {
-                            "startLine": v.line
-                        }
-                    }
-                }]
+                "locations": [{ "physicalLocation": physical_location(&v.file, v.line) }]
             });
             let mut props = serde_json::Map::new();
             if let Some(ref issue_ref) = v.issue_ref {
@@ -380,20 +539,212 @@ pub fn format_clean(result: &CleanResult) -> String {
     output
 }
 
+/// SARIF physical location for a relate `file:line` reference.
+fn loc_to_physical(loc: &str) -> serde_json::Value {
+    match loc
+        .rsplit_once(':')
+        .and_then(|(file, line)| Some((file, line.parse::<usize>().ok()?)))
+    {
+        Some((file, line)) => physical_location(file, line),
+        None => serde_json::json!({ "artifactLocation": { "uri": loc } }),
+    }
+}
+
+fn relate_to_result(
+    rule: &str,
+    loc: &str,
+    text: String,
+    peers: &[&str],
+    peer_text: &str,
+) -> serde_json::Value {
+    let related: Vec<serde_json::Value> = (1..)
+        .zip(peers)
+        .map(|(id, peer)| {
+            serde_json::json!({
+                "id": id,
+                "physicalLocation": loc_to_physical(peer),
+                "message": { "text": peer_text }
+            })
+        })
+        .collect();
+    serde_json::json!({
+        "ruleId": rule,
+        "level": "note",
+        "message": { "text": text },
+        "locations": [{ "physicalLocation": loc_to_physical(loc) }],
+        "relatedLocations": related
+    })
+}
+
+/// Render `relate` output as SARIF. With clusters, every cluster member is a
+/// result whose `relatedLocations` are the other members; without, each item
+/// points at the items it is directly related to.
+pub fn format_relate(result: &RelateResult) -> String {
+    let mut results = Vec::new();
+    let rule = if let Some(ref clusters) = result.clusters {
+        for cluster in clusters {
+            for loc in &cluster.items {
+                let peers: Vec<&str> = cluster
+                    .items
+                    .iter()
+                    .filter(|other| *other != loc)
+                    .map(String::as_str)
+                    .collect();
+                let mut r = relate_to_result(
+                    "todo-scan/relate/cluster",
+                    loc,
+                    format!(
+                        "Cluster {}: {} ({} items)",
+                        cluster.id,
+                        cluster.theme,
+                        cluster.items.len()
+                    ),
+                    &peers,
+                    &format!("Cluster {} peer", cluster.id),
+                );
+                r.as_object_mut()
+                    .expect("SARIF result should be a JSON object")
+                    .insert(
+                        "properties".to_string(),
+                        serde_json::json!({ "clusterId": cluster.id, "theme": cluster.theme }),
+                    );
+                results.push(r);
+            }
+        }
+        "todo-scan/relate/cluster"
+    } else {
+        let mut partners: std::collections::BTreeMap<&str, Vec<&str>> =
+            std::collections::BTreeMap::new();
+        for rel in &result.relationships {
+            partners.entry(&rel.from).or_default().push(&rel.to);
+            partners.entry(&rel.to).or_default().push(&rel.from);
+        }
+        for (loc, peers) in &partners {
+            results.push(relate_to_result(
+                "todo-scan/relate/related",
+                loc,
+                format!("Related to {} other TODO(s)", peers.len()),
+                peers,
+                "Related TODO",
+            ));
+        }
+        "todo-scan/relate/related"
+    };
+
+    let rules = if results.is_empty() {
+        vec![]
+    } else {
+        vec![serde_json::json!({
+            "id": rule,
+            "shortDescription": {
+                "text": "Related TODO comments"
+            }
+        })]
+    };
+    let mut output = build_sarif_envelope(results, rules);
+    output.push('\n');
+    output
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::cli::Format;
+
+    // --- baseline ---
+
+    #[test]
+    fn test_parse_baseline_id_list() {
+        let ids = parse_baseline("a.rs:TODO:fix\n\n  b.rs:BUG:crash  \n").unwrap();
+        assert_eq!(ids.len(), 2);
+        assert!(ids.contains("b.rs:BUG:crash"));
+    }
+
+    #[test]
+    fn test_parse_baseline_from_sarif_matches_item_ids() {
+        let item = crate::test_helpers::helpers::make_item("a.rs", 3, Tag::Todo, "Fix This");
+        let previous = format_list(
+            &ScanResult {
+                items: vec![item.clone()],
+                ignored_items: vec![],
+                files_scanned: 1,
+                files_with_items: 1,
+                bytes_scanned: 0,
+                truncated: false,
+                total_found: None,
+            },
+            &OutputOptions::new(Format::Sarif),
+        );
+        let ids = parse_baseline(&previous).unwrap();
+        assert_eq!(ids, HashSet::from([item.id()]));
+    }
+
+    #[test]
+    fn test_parse_baseline_resolves_index_only_locations() {
+        let previous = r#"{"runs": [{
+            "artifacts": [{"location": {"uri": "a.rs"}}],
+            "results": [{
+                "ruleId": "todo-scan/TODO",
+                "message": {"text": "Fix This"},
+                "locations": [{"physicalLocation": {"artifactLocation": {"index": 0}}}]
+            }]
+        }]}"#;
+        let ids = parse_baseline(previous).unwrap();
+        assert_eq!(ids, HashSet::from(["a.rs:TODO:fix this".to_string()]));
+    }
+
+    #[test]
+    fn test_parse_baseline_rejects_non_sarif_json() {
+        assert!(parse_baseline("{\"items\": []}").is_err());
+    }
+
+    #[test]
+    fn test_baseline_state_unchanged_and_new() {
+        let known = crate::test_helpers::helpers::make_item("a.rs", 3, Tag::Todo, "old");
+        let novel = crate::test_helpers::helpers::make_item("a.rs", 9, Tag::Todo, "fresh");
+        let baseline = HashSet::from([known.id()]);
+        assert_eq!(baseline_state(&known, &baseline), "unchanged");
+        assert_eq!(baseline_state(&novel, &baseline), "new");
+    }
+
+    #[test]
+    fn test_item_to_result_marks_baseline_state_from_options() {
+        let known = crate::test_helpers::helpers::make_item("a.rs", 3, Tag::Todo, "old");
+        let out = OutputOptions {
+            sarif_baseline: Some(HashSet::from([known.id()])),
+            ..OutputOptions::new(Format::Sarif)
+        };
+        assert_eq!(item_to_result(&known, &out)["baselineState"], "unchanged");
+        let plain = item_to_result(&known, &OutputOptions::new(Format::Sarif));
+        assert!(plain.get("baselineState").is_none());
+    }
+
+    /// URI of the run artifact a (related) location points at.
+    fn artifact_uri<'a>(sarif: &'a serde_json::Value, location: &serde_json::Value) -> &'a str {
+        let index = location
+            .pointer("/physicalLocation/artifactLocation/index")
+            .and_then(|i| i.as_u64())
+            .unwrap() as usize;
+        sarif["runs"][0]["artifacts"][index]["location"]["uri"]
+            .as_str()
+            .unwrap()
+    }
 
     fn sample_item(tag: Tag, message: &str) -> TodoItem {
         TodoItem {
             file: "src/main.rs".to_string(),
             line: 10,
+            byte_offset: 0,
+            cell: None,
+            labels: vec![],
             tag,
             message: message.to_string(),
             author: None,
             issue_ref: None,
             priority: Priority::Normal,
             deadline: None,
+            ignore_reason: None,
+            severity: None,
         }
     }
 
@@ -402,9 +753,13 @@ mod tests {
         let result = ScanResult {
             items: vec![sample_item(Tag::Todo, "implement feature")],
             files_scanned: 1,
+            files_with_items: 1,
+            bytes_scanned: 0,
             ignored_items: vec![],
+            truncated: false,
+            total_found: None,
         };
-        let output = format_list(&result);
+        let output = format_list(&result, &OutputOptions::new(Format::Sarif));
         let sarif: serde_json::Value = serde_json::from_str(&output).unwrap();
 
         assert_eq!(sarif["version"], "2.1.0");
@@ -416,7 +771,7 @@ mod tests {
         assert_eq!(results[0]["level"], "warning");
         assert_eq!(results[0]["message"]["text"], "implement feature");
         assert_eq!(
-            results[0]["locations"][0]["physicalLocation"]["artifactLocation"]["uri"],
+            artifact_uri(&sarif, &results[0]["locations"][0]),
             "src/main.rs"
         );
         assert_eq!(
@@ -425,6 +780,85 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_format_list_sarif_invocation() {
+        let result = ScanResult {
+            items: vec![
+                sample_item(Tag::Todo, "implement feature"),
+                sample_item(Tag::Bug, "crash"),
+            ],
+            files_scanned: 1,
+            files_with_items: 1,
+            bytes_scanned: 0,
+            ignored_items: vec![],
+            truncated: false,
+            total_found: None,
+        };
+        let output = format_list(&result, &OutputOptions::new(Format::Sarif));
+        let sarif: serde_json::Value = serde_json::from_str(&output).unwrap();
+
+        let invocation = &sarif["runs"][0]["invocations"][0];
+        assert_eq!(invocation["executionSuccessful"], true);
+        assert!(invocation["arguments"].is_array());
+        assert!(invocation.get("commandLine").is_none());
+        assert!(invocation["endTimeUtc"].as_str().unwrap().ends_with('Z'));
+        assert_eq!(
+            invocation["toolExecutionNotifications"][0]["message"]["text"],
+            "todo-scan: 2 results (1 error, 1 warning, 0 note)"
+        );
+    }
+
+    #[test]
+    fn test_format_list_sarif_artifacts_deduplicated() {
+        let mut other = sample_item(Tag::Bug, "elsewhere");
+        other.file = "src/lib.rs".to_string();
+        let result = ScanResult {
+            items: vec![
+                sample_item(Tag::Todo, "first"),
+                other,
+                sample_item(Tag::Fixme, "second"),
+            ],
+            files_scanned: 2,
+            files_with_items: 2,
+            bytes_scanned: 0,
+            ignored_items: vec![],
+            truncated: false,
+            total_found: None,
+        };
+        let output = format_list(&result, &OutputOptions::new(Format::Sarif));
+        let sarif: serde_json::Value = serde_json::from_str(&output).unwrap();
+
+        let artifacts = sarif["runs"][0]["artifacts"].as_array().unwrap();
+        let uris: Vec<&str> = artifacts
+            .iter()
+            .map(|a| a["location"]["uri"].as_str().unwrap())
+            .collect();
+        assert_eq!(uris, vec!["src/main.rs", "src/lib.rs"]);
+
+        let results = sarif["runs"][0]["results"].as_array().unwrap();
+        for r in results {
+            let loc = &r["locations"][0]["physicalLocation"]["artifactLocation"];
+            let index = loc["index"].as_u64().unwrap() as usize;
+            assert_eq!(artifacts[index]["location"]["uri"], loc["uri"]);
+        }
+        assert_eq!(
+            results[2]["locations"][0]["physicalLocation"]["artifactLocation"]["index"],
+            0
+        );
+    }
+
+    #[test]
+    fn test_format_check_sarif_has_no_artifacts() {
+        let result = CheckResult {
+            passed: true,
+            total: 0,
+            violations: vec![],
+        };
+        let output = format_check(&result);
+        let sarif: serde_json::Value = serde_json::from_str(&output).unwrap();
+        assert!(sarif["runs"][0].get("artifacts").is_none());
+    }
+
     #[test]
     fn test_format_list_sarif_severity() {
         let result = ScanResult {
@@ -433,9 +867,13 @@ mod tests {
                 sample_item(Tag::Note, "info"),
             ],
             files_scanned: 1,
+            files_with_items: 1,
+            bytes_scanned: 0,
             ignored_items: vec![],
+            truncated: false,
+            total_found: None,
         };
-        let output = format_list(&result);
+        let output = format_list(&result, &OutputOptions::new(Format::Sarif));
         let sarif: serde_json::Value = serde_json::from_str(&output).unwrap();
         let results = sarif["runs"][0]["results"].as_array().unwrap();
         assert_eq!(results[0]["level"], "error");
@@ -451,9 +889,13 @@ mod tests {
                 sample_item(Tag::Bug, "a bug"),
             ],
             files_scanned: 1,
+            files_with_items: 1,
+            bytes_scanned: 0,
             ignored_items: vec![],
+            truncated: false,
+            total_found: None,
         };
-        let output = format_list(&result);
+        let output = format_list(&result, &OutputOptions::new(Format::Sarif));
         let sarif: serde_json::Value = serde_json::from_str(&output).unwrap();
         let rules = sarif["runs"][0]["tool"]["driver"]["rules"]
             .as_array()
@@ -472,7 +914,7 @@ mod tests {
             removed_count: 0,
             base_ref: "main".to_string(),
         };
-        let output = format_diff(&result);
+        let output = format_diff(&result, &OutputOptions::new(Format::Sarif));
         let sarif: serde_json::Value = serde_json::from_str(&output).unwrap();
         let results = sarif["runs"][0]["results"].as_array().unwrap();
         assert_eq!(results[0]["properties"]["diffStatus"], "added");
@@ -522,7 +964,7 @@ mod tests {
             match_count: 1,
             file_count: 1,
         };
-        let output = format_search(&result);
+        let output = format_search(&result, &OutputOptions::new(Format::Sarif));
         let sarif: serde_json::Value = serde_json::from_str(&output).unwrap();
         let results = sarif["runs"][0]["results"].as_array().unwrap();
         assert_eq!(results.len(), 1);
@@ -547,9 +989,10 @@ mod tests {
             total: 1,
             avg_age_days: 400,
             stale_count: 1,
+            unknown_count: 0,
             stale_threshold_days: 365,
         };
-        let output = format_blame(&result);
+        let output = format_blame(&result, &OutputOptions::new(Format::Sarif));
         let sarif: serde_json::Value = serde_json::from_str(&output).unwrap();
         let results = sarif["runs"][0]["results"].as_array().unwrap();
         assert_eq!(results.len(), 1);
@@ -585,6 +1028,7 @@ mod tests {
                 file: "test.rs".to_string(),
                 line: 5,
                 rule: "no_bare_tags".to_string(),
+                severity: Severity::Error,
                 message: "bare tag".to_string(),
                 suggestion: Some("add a message".to_string()),
             }],
@@ -610,6 +1054,7 @@ mod tests {
                 file: "test.rs".to_string(),
                 line: 5,
                 rule: "uppercase_tag".to_string(),
+                severity: Severity::Error,
                 message: "tag not uppercase".to_string(),
                 suggestion: None,
             }],
@@ -627,6 +1072,8 @@ mod tests {
             total_items: 3,
             stale_count: 0,
             duplicate_count: 0,
+            deadline_without_owner_count: 0,
+            stale_age_count: 0,
             violations: vec![],
         };
         let output = format_clean(&result);
@@ -644,6 +1091,8 @@ mod tests {
             total_items: 2,
             stale_count: 0,
             duplicate_count: 1,
+            deadline_without_owner_count: 0,
+            stale_age_count: 0,
             violations: vec![CleanViolation {
                 file: "test.rs".to_string(),
                 line: 10,
@@ -666,6 +1115,8 @@ mod tests {
             total_items: 1,
             stale_count: 1,
             duplicate_count: 0,
+            deadline_without_owner_count: 0,
+            stale_age_count: 0,
             violations: vec![CleanViolation {
                 file: "test.rs".to_string(),
                 line: 10,
@@ -688,6 +1139,8 @@ mod tests {
             total_items: 1,
             stale_count: 0,
             duplicate_count: 0,
+            deadline_without_owner_count: 0,
+            stale_age_count: 0,
             violations: vec![CleanViolation {
                 file: "test.rs".to_string(),
                 line: 10,
@@ -715,7 +1168,7 @@ mod tests {
             removed_count: 1,
             base_ref: "main".to_string(),
         };
-        let output = format_diff(&result);
+        let output = format_diff(&result, &OutputOptions::new(Format::Sarif));
         let sarif: serde_json::Value = serde_json::from_str(&output).unwrap();
         let results = sarif["runs"][0]["results"].as_array().unwrap();
         assert_eq!(results[0]["properties"]["diffStatus"], "removed");
@@ -727,6 +1180,9 @@ mod tests {
         let item = TodoItem {
             file: "test.rs".to_string(),
             line: 1,
+            byte_offset: 0,
+            cell: None,
+            labels: vec![],
             tag: Tag::Todo,
             message: "task".to_string(),
             author: None,
@@ -737,8 +1193,10 @@ mod tests {
                 month: 6,
                 day: 1,
             }),
+            ignore_reason: None,
+            severity: None,
         };
-        let result = item_to_result(&item);
+        let result = item_to_result(&item, &OutputOptions::new(Format::Sarif));
         assert!(result["properties"]["deadline"].as_str().is_some());
     }
 
@@ -747,9 +1205,13 @@ mod tests {
         let result = ScanResult {
             items: vec![],
             files_scanned: 0,
+            files_with_items: 0,
+            bytes_scanned: 0,
             ignored_items: vec![],
+            truncated: false,
+            total_found: None,
         };
-        let output = format_list(&result);
+        let output = format_list(&result, &OutputOptions::new(Format::Sarif));
         let sarif: serde_json::Value = serde_json::from_str(&output).unwrap();
         let results = sarif["runs"][0]["results"].as_array().unwrap();
         assert!(results.is_empty());
@@ -766,6 +1228,7 @@ mod tests {
                     file: "a.rs".to_string(),
                     line: 1,
                     rule: "no_bare_tags".to_string(),
+                    severity: Severity::Error,
                     message: "first".to_string(),
                     suggestion: None,
                 },
@@ -773,6 +1236,7 @@ mod tests {
                     file: "b.rs".to_string(),
                     line: 2,
                     rule: "no_bare_tags".to_string(),
+                    severity: Severity::Error,
                     message: "second".to_string(),
                     suggestion: None,
                 },
@@ -793,6 +1257,8 @@ mod tests {
             total_items: 2,
             stale_count: 2,
             duplicate_count: 0,
+            deadline_without_owner_count: 0,
+            stale_age_count: 0,
             violations: vec![
                 CleanViolation {
                     file: "a.rs".to_string(),
@@ -819,4 +1285,103 @@ mod tests {
             .unwrap();
         assert_eq!(rules.len(), 1);
     }
+
+    // --- relate ---
+
+    #[test]
+    fn test_format_relate_cluster_members_are_related_locations() {
+        let locs = ["src/auth.rs:10", "src/auth.rs:20", "src/login.rs:4"];
+        let result = RelateResult {
+            relationships: vec![],
+            clusters: Some(vec![Cluster {
+                id: 1,
+                theme: "auth".to_string(),
+                items: locs.iter().map(|l| l.to_string()).collect(),
+                suggested_order: locs.iter().map(|l| l.to_string()).collect(),
+                relationships: vec![],
+            }]),
+            total_relationships: 0,
+            total_items: 3,
+            min_score: 0.3,
+            target: None,
+        };
+        let sarif: serde_json::Value = serde_json::from_str(&format_relate(&result)).unwrap();
+        let results = sarif["runs"][0]["results"].as_array().unwrap();
+        assert_eq!(results.len(), 3);
+
+        let first = &results[0];
+        assert_eq!(first["ruleId"], "todo-scan/relate/cluster");
+        assert_eq!(
+            first.pointer("/locations/0/physicalLocation/region/startLine"),
+            Some(&serde_json::json!(10))
+        );
+        let related = first["relatedLocations"].as_array().unwrap();
+        let peers: Vec<(String, u64)> = related
+            .iter()
+            .map(|r| {
+                (
+                    artifact_uri(&sarif, r).to_string(),
+                    r.pointer("/physicalLocation/region/startLine")
+                        .unwrap()
+                        .as_u64()
+                        .unwrap(),
+                )
+            })
+            .collect();
+        assert_eq!(
+            peers,
+            vec![
+                ("src/auth.rs".to_string(), 20),
+                ("src/login.rs".to_string(), 4)
+            ]
+        );
+        assert_eq!(related[0]["id"], 1);
+        assert_eq!(related[1]["id"], 2);
+    }
+
+    #[test]
+    fn test_format_relate_without_clusters_uses_relationships() {
+        let result = RelateResult {
+            relationships: vec![Relationship {
+                from: "a.rs:1".to_string(),
+                to: "b.rs:2".to_string(),
+                score: 0.8,
+                reason: "same keywords".to_string(),
+                explanation: String::new(),
+            }],
+            clusters: None,
+            total_relationships: 1,
+            total_items: 2,
+            min_score: 0.3,
+            target: None,
+        };
+        let sarif: serde_json::Value = serde_json::from_str(&format_relate(&result)).unwrap();
+        let results = sarif["runs"][0]["results"].as_array().unwrap();
+        assert_eq!(results.len(), 2);
+        assert_eq!(
+            artifact_uri(&sarif, &results[0]["relatedLocations"][0]),
+            "b.rs"
+        );
+    }
+
+    #[test]
+    fn test_format_list_sarif_file_name_item_has_no_region() {
+        let mut item = sample_item(Tag::Todo, "src/TODO_x.rs");
+        item.line = 0;
+        let result = ScanResult {
+            items: vec![item],
+            files_scanned: 1,
+            files_with_items: 1,
+            bytes_scanned: 0,
+            ignored_items: vec![],
+            truncated: false,
+            total_found: None,
+        };
+        let sarif: serde_json::Value =
+            serde_json::from_str(&format_list(&result, &OutputOptions::new(Format::Sarif)))
+                .unwrap();
+        let location = &sarif["runs"][0]["results"][0]["locations"][0]["physicalLocation"];
+        assert!(location.get("region").is_none());
+        assert_eq!(location["artifactLocation"]["uri"], "src/main.rs");
+    }
 }
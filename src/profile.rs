@@ -0,0 +1,177 @@
+//! Opt-in timing diagnostics for `--profile`.
+//!
+//! A [`Profiler`] is created once per run and handed down with the output
+//! options; clones share one accumulator, so `main` can report what the
+//! commands recorded. Nothing is recorded unless profiling was enabled, and
+//! the report goes to stderr so stdout stays machine-readable.
+
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+#[derive(Default)]
+struct Profile {
+    phases: Vec<(&'static str, Duration)>,
+    files_scanned: usize,
+    bytes_scanned: u64,
+    reported: bool,
+}
+
+/// Phase timings for one run; a no-op unless created enabled.
+#[derive(Clone)]
+pub struct Profiler {
+    start: Instant,
+    profile: Option<Arc<Mutex<Profile>>>,
+}
+
+impl Profiler {
+    pub fn new(enabled: bool) -> Self {
+        Self {
+            start: Instant::now(),
+            profile: enabled.then(|| Arc::new(Mutex::new(Profile::default()))),
+        }
+    }
+
+    fn with_profile(&self, f: impl FnOnce(&mut Profile)) {
+        if let Some(profile) = &self.profile {
+            f(&mut profile.lock().expect("profile lock poisoned"));
+        }
+    }
+
+    /// Run `f`, attributing its wall-clock time to `phase` when enabled.
+    ///
+    /// Repeated phases accumulate, so commands that scan twice report the sum.
+    pub fn time<T>(&self, phase: &'static str, f: impl FnOnce() -> T) -> T {
+        let start = Instant::now();
+        let out = f();
+        let elapsed = start.elapsed();
+
+        self.with_profile(|profile| {
+            match profile.phases.iter_mut().find(|(name, _)| *name == phase) {
+                Some((_, total)) => *total += elapsed,
+                None => profile.phases.push((phase, elapsed)),
+            }
+        });
+        out
+    }
+
+    /// Add to the files and bytes scanned, used for the throughput lines.
+    pub fn record_scan(&self, files: usize, bytes: u64) {
+        self.with_profile(|profile| {
+            profile.files_scanned += files;
+            profile.bytes_scanned += bytes;
+        });
+    }
+
+    /// Print the profile block to stderr, once. Time since the profiler was
+    /// created that no recorded phase covers is reported as `other`.
+    pub fn report(&self) {
+        let total = self.start.elapsed();
+        self.with_profile(|profile| {
+            if std::mem::replace(&mut profile.reported, true) {
+                return;
+            }
+            let mut phases = profile.phases.clone();
+            let recorded: Duration = phases.iter().map(|(_, d)| *d).sum();
+            phases.push(("other", total.saturating_sub(recorded)));
+
+            for line in format_report(&phases, total, profile.files_scanned, profile.bytes_scanned)
+            {
+                eprintln!("{}", line);
+            }
+        });
+    }
+}
+
+/// Format phase durations into the lines printed by [`report`].
+pub fn format_report(
+    phases: &[(&'static str, Duration)],
+    total: Duration,
+    files_scanned: usize,
+    bytes_scanned: u64,
+) -> Vec<String> {
+    let mut lines = vec!["profile:".to_string()];
+    for (name, duration) in phases {
+        lines.push(format!("  {:<8} {:>10.2}ms", name, millis(*duration)));
+    }
+    lines.push(format!("  {:<8} {:>10.2}ms", "total", millis(total)));
+
+    let scan_secs = phases
+        .iter()
+        .find(|(name, _)| *name == "scan")
+        .map(|(_, d)| d.as_secs_f64())
+        .unwrap_or(0.0);
+    let per_sec = |n: f64| if scan_secs > 0.0 { n / scan_secs } else { 0.0 };
+    lines.push(format!(
+        "  {:<8} {:>10} ({:.1} files/s)",
+        "files",
+        files_scanned,
+        per_sec(files_scanned as f64)
+    ));
+    lines.push(format!(
+        "  {:<8} {:>10} ({:.2} MB/s)",
+        "bytes",
+        bytes_scanned,
+        per_sec(bytes_scanned as f64) / 1_000_000.0
+    ));
+    lines
+}
+
+fn millis(d: Duration) -> f64 {
+    d.as_secs_f64() * 1000.0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_format_report_lines() {
+        let phases = [
+            ("config", Duration::from_millis(2)),
+            ("scan", Duration::from_millis(500)),
+            ("other", Duration::from_micros(1500)),
+        ];
+        let lines = format_report(&phases, Duration::from_micros(503_500), 1000, 25_000_000);
+        assert_eq!(
+            lines,
+            vec![
+                "profile:",
+                "  config         2.00ms",
+                "  scan         500.00ms",
+                "  other          1.50ms",
+                "  total        503.50ms",
+                "  files          1000 (2000.0 files/s)",
+                "  bytes      25000000 (50.00 MB/s)",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_profiler_accumulates_repeated_phases() {
+        let profiler = Profiler::new(true);
+        profiler.time("scan", || std::thread::sleep(Duration::from_millis(2)));
+        profiler.clone().time("scan", || ());
+        profiler.time("git", || ());
+        let profile = profiler.profile.as_ref().unwrap().lock().unwrap();
+        let names: Vec<&str> = profile.phases.iter().map(|(name, _)| *name).collect();
+        assert_eq!(names, vec!["scan", "git"]);
+        assert!(profile.phases[0].1 >= Duration::from_millis(2));
+    }
+
+    #[test]
+    fn test_disabled_profiler_records_nothing() {
+        let profiler = Profiler::new(false);
+        assert_eq!(profiler.time("scan", || 7), 7);
+        assert!(profiler.profile.is_none());
+    }
+
+    #[test]
+    fn test_format_report_without_scan_phase() {
+        let lines = format_report(&[], Duration::from_millis(1), 0, 0);
+        assert_eq!(
+            lines[lines.len() - 2],
+            "  files             0 (0.0 files/s)"
+        );
+        assert_eq!(lines[lines.len() - 1], "  bytes             0 (0.00 MB/s)");
+    }
+}
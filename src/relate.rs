@@ -14,6 +14,10 @@ const KEYWORD_WEIGHT: f64 = 0.35;
 const CROSSREF_WEIGHT: f64 = 0.25;
 const TAG_WEIGHT: f64 = 0.10;
 
+/// Above this many items, `compute_relations` only scores candidate pairs
+/// instead of every pair.
+pub const DEFAULT_MAX_PAIRWISE_ITEMS: usize = 2000;
+
 pub fn extract_keywords(message: &str) -> HashSet<String> {
     let stopwords: HashSet<&str> = STOPWORDS.iter().copied().collect();
     message
@@ -106,6 +110,81 @@ fn build_reason(c: &ScoreComponents) -> String {
     parts.join(", ")
 }
 
+/// The long form of [`build_reason`]: one sentence naming each contributing
+/// factor with the share of the score it adds.
+fn build_explanation(c: &ScoreComponents) -> String {
+    let mut parts = Vec::new();
+    if c.prox > 0.0 {
+        let distance = c.a.line.abs_diff(c.b.line);
+        parts.push(format!(
+            "they are {} line{} apart in {} (+{:.2})",
+            distance,
+            if distance == 1 { "" } else { "s" },
+            c.a.file,
+            PROXIMITY_WEIGHT * c.prox
+        ));
+    }
+    if c.kw_sim > 0.0 {
+        let mut shared: Vec<_> = c.keywords_a.intersection(c.keywords_b).cloned().collect();
+        shared.sort();
+        parts.push(format!(
+            "their messages share the keyword{} {} (+{:.2})",
+            if shared.len() == 1 { "" } else { "s" },
+            shared.join(", "),
+            KEYWORD_WEIGHT * c.kw_sim
+        ));
+    }
+    if c.cross > 0.0 {
+        let cross = CROSSREF_WEIGHT * c.cross;
+        match (&c.a.issue_ref, &c.b.issue_ref) {
+            (Some(ra), Some(rb)) if ra == rb => {
+                parts.push(format!("both reference issue {} (+{:.2})", ra, cross));
+            }
+            _ => {
+                if let Some(author) = &c.a.author {
+                    parts.push(format!("both are assigned to {} (+{:.2})", author, cross));
+                }
+            }
+        }
+    }
+    if c.tag > 0.0 {
+        parts.push(format!(
+            "both are {} items (+{:.2})",
+            c.a.tag,
+            TAG_WEIGHT * c.tag
+        ));
+    }
+    match parts.len() {
+        0 => "Nothing links these items.".to_string(),
+        1 => format!("Related because {}.", parts[0]),
+        n => format!(
+            "Related because {} and {}.",
+            parts[..n - 1].join(", "),
+            parts[n - 1]
+        ),
+    }
+}
+
+fn score_components<'a>(
+    a: &'a TodoItem,
+    b: &'a TodoItem,
+    proximity_threshold: usize,
+    keywords_a: &'a HashSet<String>,
+    keywords_b: &'a HashSet<String>,
+) -> ScoreComponents<'a> {
+    ScoreComponents {
+        prox: proximity_score(a, b, proximity_threshold),
+        kw_sim: jaccard_similarity(keywords_a, keywords_b),
+        cross: cross_ref_score(a, b),
+        tag: tag_score(a, b),
+        a,
+        b,
+        keywords_a,
+        keywords_b,
+    }
+}
+
+/// Score a pair of items, returning the score and the short reason.
 pub fn score_pair(
     a: &TodoItem,
     b: &TodoItem,
@@ -113,34 +192,129 @@ pub fn score_pair(
     keywords_a: &HashSet<String>,
     keywords_b: &HashSet<String>,
 ) -> (f64, String) {
-    let prox = proximity_score(a, b, proximity_threshold);
-    let kw_sim = jaccard_similarity(keywords_a, keywords_b);
-    let cross = cross_ref_score(a, b);
-    let tag = tag_score(a, b);
-
-    let score = (PROXIMITY_WEIGHT * prox
-        + KEYWORD_WEIGHT * kw_sim
-        + CROSSREF_WEIGHT * cross
-        + TAG_WEIGHT * tag)
+    let c = score_components(a, b, proximity_threshold, keywords_a, keywords_b);
+    let score = (PROXIMITY_WEIGHT * c.prox
+        + KEYWORD_WEIGHT * c.kw_sim
+        + CROSSREF_WEIGHT * c.cross
+        + TAG_WEIGHT * c.tag)
         .clamp(0.0, 1.0);
+    (score, build_reason(&c))
+}
 
-    let reason = build_reason(&ScoreComponents {
-        prox,
-        kw_sim,
-        cross,
-        tag,
+/// The full-sentence explanation for a pair [`score_pair`] scored. Kept
+/// separate so it is only built for pairs that reach `min_score`.
+pub fn explain_pair(
+    a: &TodoItem,
+    b: &TodoItem,
+    proximity_threshold: usize,
+    keywords_a: &HashSet<String>,
+    keywords_b: &HashSet<String>,
+) -> String {
+    build_explanation(&score_components(
         a,
         b,
+        proximity_threshold,
         keywords_a,
         keywords_b,
-    });
-    (score, reason)
+    ))
+}
+
+/// Keys shared by more items than this don't propose candidates when
+/// blocking: a keyword, issue ref or author that common says little about a
+/// pair, and pairing all its items would bring back the n² work.
+const MAX_BLOCK_SIZE: usize = 500;
+
+/// What two items can share, besides their file, to be scored when blocking.
+#[derive(PartialEq, Eq, Hash)]
+enum BlockKey<'a> {
+    Keyword(&'a str),
+    IssueRef(&'a str),
+    Author(&'a str),
+}
+
+fn block_keys<'a>(
+    item: &'a TodoItem,
+    keywords: &'a HashSet<String>,
+) -> impl Iterator<Item = BlockKey<'a>> {
+    keywords
+        .iter()
+        .map(|k| BlockKey::Keyword(k))
+        .chain(item.issue_ref.as_deref().map(BlockKey::IssueRef))
+        .chain(item.author.as_deref().map(BlockKey::Author))
+}
+
+/// Inverted index proposing the items worth scoring against a given item.
+struct CandidateIndex<'a> {
+    /// Item indices per key, ascending.
+    blocks: HashMap<BlockKey<'a>, Vec<usize>>,
+    /// Item indices per file, sorted by line.
+    files: HashMap<&'a str, Vec<usize>>,
+}
+
+impl<'a> CandidateIndex<'a> {
+    fn new(items: &'a [TodoItem], keywords: &'a [HashSet<String>]) -> Self {
+        let mut blocks: HashMap<BlockKey, Vec<usize>> = HashMap::new();
+        let mut files: HashMap<&str, Vec<usize>> = HashMap::new();
+        for (i, item) in items.iter().enumerate() {
+            for key in block_keys(item, &keywords[i]) {
+                blocks.entry(key).or_default().push(i);
+            }
+            files.entry(&item.file).or_default().push(i);
+        }
+        for members in files.values_mut() {
+            members.sort_by_key(|&i| items[i].line);
+        }
+        Self { blocks, files }
+    }
+
+    /// Fill `out` with the indices `j > i`, ascending, of items sharing a
+    /// keyword, issue ref or author with item `i` (keys over
+    /// [`MAX_BLOCK_SIZE`] aside), or within `proximity_threshold` lines of it
+    /// in the same file.
+    fn candidates(
+        &self,
+        i: usize,
+        items: &[TodoItem],
+        keywords: &[HashSet<String>],
+        proximity_threshold: usize,
+        out: &mut Vec<usize>,
+    ) {
+        out.clear();
+        let item = &items[i];
+        for key in block_keys(item, &keywords[i]) {
+            if let Some(members) = self.blocks.get(&key) {
+                if members.len() <= MAX_BLOCK_SIZE {
+                    let start = members.partition_point(|&j| j <= i);
+                    out.extend(&members[start..]);
+                }
+            }
+        }
+        if let Some(members) = self.files.get(item.file.as_str()) {
+            let low = item.line.saturating_sub(proximity_threshold);
+            let start = members.partition_point(|&j| items[j].line < low);
+            out.extend(
+                members[start..]
+                    .iter()
+                    .take_while(|&&j| items[j].line <= item.line + proximity_threshold)
+                    .filter(|&&j| j > i),
+            );
+        }
+        out.sort_unstable();
+        out.dedup();
+    }
 }
 
+/// Score item pairs and keep those reaching `min_score`. Up to `max_pairwise`
+/// items every pair is compared; above it each item is only scored against
+/// the candidates from a [`CandidateIndex`]. That gives the same result
+/// whenever `min_score` is above the tag-only score of 0.1, except for pairs
+/// whose only link is a keyword, issue ref or author shared by more than
+/// [`MAX_BLOCK_SIZE`] items.
 pub fn compute_relations(
     scan: &ScanResult,
     min_score: f64,
     proximity_threshold: usize,
+    max_pairwise: usize,
 ) -> RelateResult {
     let items = &scan.items;
     let mut relationships = Vec::new();
@@ -160,22 +334,44 @@ pub fn compute_relations(
     let keywords: Vec<HashSet<String>> =
         items.iter().map(|i| extract_keywords(&i.message)).collect();
 
-    for i in 0..items.len() {
-        for j in (i + 1)..items.len() {
-            let (score, reason) = score_pair(
-                &items[i],
-                &items[j],
-                proximity_threshold,
-                &keywords[i],
-                &keywords[j],
-            );
-            if score >= min_score {
-                relationships.push(Relationship {
-                    from: format!("{}:{}", items[i].file, items[i].line),
-                    to: format!("{}:{}", items[j].file, items[j].line),
-                    score,
-                    reason,
-                });
+    let mut relate = |i: usize, j: usize| {
+        let (score, reason) = score_pair(
+            &items[i],
+            &items[j],
+            proximity_threshold,
+            &keywords[i],
+            &keywords[j],
+        );
+        if score >= min_score {
+            relationships.push(Relationship {
+                from: format!("{}:{}", items[i].file, items[i].line),
+                to: format!("{}:{}", items[j].file, items[j].line),
+                score,
+                reason,
+                explanation: explain_pair(
+                    &items[i],
+                    &items[j],
+                    proximity_threshold,
+                    &keywords[i],
+                    &keywords[j],
+                ),
+            });
+        }
+    };
+
+    if items.len() > max_pairwise {
+        let index = CandidateIndex::new(items, &keywords);
+        let mut candidates = Vec::new();
+        for i in 0..items.len() {
+            index.candidates(i, items, &keywords, proximity_threshold, &mut candidates);
+            for &j in &candidates {
+                relate(i, j);
+            }
+        }
+    } else {
+        for i in 0..items.len() {
+            for j in (i + 1)..items.len() {
+                relate(i, j);
             }
         }
     }
@@ -214,6 +410,7 @@ pub fn filter_for_item(result: RelateResult, file: &str, line: usize) -> RelateR
 struct UnionFind {
     parent: Vec<usize>,
     rank: Vec<usize>,
+    size: Vec<usize>,
 }
 
 impl UnionFind {
@@ -221,9 +418,16 @@ impl UnionFind {
         Self {
             parent: (0..n).collect(),
             rank: vec![0; n],
+            size: vec![1; n],
         }
     }
 
+    /// Number of members in the set containing `x`.
+    fn size(&mut self, x: usize) -> usize {
+        let root = self.find(x);
+        self.size[root]
+    }
+
     fn find(&mut self, x: usize) -> usize {
         if self.parent[x] != x {
             self.parent[x] = self.find(self.parent[x]);
@@ -237,12 +441,20 @@ impl UnionFind {
         if rx == ry {
             return;
         }
+        let merged = self.size[rx] + self.size[ry];
         match self.rank[rx].cmp(&self.rank[ry]) {
-            std::cmp::Ordering::Less => self.parent[rx] = ry,
-            std::cmp::Ordering::Greater => self.parent[ry] = rx,
+            std::cmp::Ordering::Less => {
+                self.parent[rx] = ry;
+                self.size[ry] = merged;
+            }
+            std::cmp::Ordering::Greater => {
+                self.parent[ry] = rx;
+                self.size[rx] = merged;
+            }
             std::cmp::Ordering::Equal => {
                 self.parent[ry] = rx;
                 self.rank[rx] += 1;
+                self.size[rx] = merged;
             }
         }
     }
@@ -288,7 +500,16 @@ pub fn compute_suggested_order(items: &mut [&TodoItem]) {
     });
 }
 
-pub fn build_clusters(relationships: &[Relationship], items: &[TodoItem]) -> Vec<Cluster> {
+/// Group related items into clusters of 2+ members, largest first.
+///
+/// With `max_size`, relationships are linked strongest-first and any link that
+/// would grow a cluster past the cap is skipped, splitting oversized groups
+/// along their weakest relationships.
+pub fn build_clusters(
+    relationships: &[Relationship],
+    items: &[TodoItem],
+    max_size: Option<usize>,
+) -> Vec<Cluster> {
     if items.is_empty() {
         return Vec::new();
     }
@@ -302,8 +523,16 @@ pub fn build_clusters(relationships: &[Relationship], items: &[TodoItem]) -> Vec
 
     let mut uf = UnionFind::new(items.len());
 
-    for rel in relationships {
+    let mut ordered: Vec<&Relationship> = relationships.iter().collect();
+    ordered.sort_by(|a, b| b.score.total_cmp(&a.score));
+
+    for rel in ordered {
         if let (Some(&i), Some(&j)) = (loc_to_idx.get(&rel.from), loc_to_idx.get(&rel.to)) {
+            if let Some(cap) = max_size {
+                if uf.find(i) != uf.find(j) && uf.size(i) + uf.size(j) > cap {
+                    continue;
+                }
+            }
             uf.union(i, j);
         }
     }
@@ -317,12 +546,11 @@ pub fn build_clusters(relationships: &[Relationship], items: &[TodoItem]) -> Vec
 
     // Filter to clusters with 2+ members and build
     let mut clusters: Vec<Cluster> = Vec::new();
-    let mut cluster_id = 1;
 
     let mut sorted_groups: Vec<_> = groups.into_iter().filter(|(_, v)| v.len() >= 2).collect();
     sorted_groups.sort_by_key(|(_, v)| std::cmp::Reverse(v.len()));
 
-    for (_, member_indices) in sorted_groups {
+    for (cluster_id, (_, member_indices)) in (1..).zip(sorted_groups) {
         let mut member_items: Vec<&TodoItem> = member_indices.iter().map(|&i| &items[i]).collect();
         let theme = generate_theme(&member_items);
 
@@ -348,12 +576,33 @@ pub fn build_clusters(relationships: &[Relationship], items: &[TodoItem]) -> Vec
             suggested_order: item_locs,
             relationships: cluster_rels,
         });
-        cluster_id += 1;
     }
 
     clusters
 }
 
+/// Keep the `max` clusters with the highest total relationship score.
+///
+/// Surviving clusters keep their relative order and are renumbered from 1.
+pub fn limit_clusters(clusters: Vec<Cluster>, max: usize) -> Vec<Cluster> {
+    let total_score = |c: &Cluster| c.relationships.iter().map(|r| r.score).sum::<f64>();
+
+    let mut ranked: Vec<usize> = (0..clusters.len()).collect();
+    ranked.sort_by(|&a, &b| total_score(&clusters[b]).total_cmp(&total_score(&clusters[a])));
+    let keep: HashSet<usize> = ranked.into_iter().take(max).collect();
+
+    clusters
+        .into_iter()
+        .enumerate()
+        .filter(|(i, _)| keep.contains(i))
+        .zip(1..)
+        .map(|((_, mut cluster), id)| {
+            cluster.id = id;
+            cluster
+        })
+        .collect()
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -507,6 +756,38 @@ mod tests {
         assert!(reason.contains("same_tag:TODO"));
     }
 
+    #[test]
+    fn explain_pair_names_each_factor() {
+        let mut a = make_item("src/main.rs", 10, Tag::Todo, "authentication token");
+        let mut b = make_item("src/main.rs", 13, Tag::Todo, "authentication token expires");
+        a.issue_ref = Some("#42".to_string());
+        b.issue_ref = Some("#42".to_string());
+        let kw_a = extract_keywords(&a.message);
+        let kw_b = extract_keywords(&b.message);
+        let explanation = explain_pair(&a, &b, 10, &kw_a, &kw_b);
+        assert_eq!(
+            explanation,
+            "Related because they are 3 lines apart in src/main.rs (+0.21), \
+             their messages share the keywords authentication, token (+0.23), \
+             both reference issue #42 (+0.25) and both are TODO items (+0.10)."
+        );
+    }
+
+    #[test]
+    fn explain_pair_single_factor_and_author() {
+        let mut a = make_item("src/auth.rs", 10, Tag::Todo, "alpha");
+        let mut b = make_item("src/db.rs", 50, Tag::Fixme, "beta");
+        a.author = Some("alice".to_string());
+        b.author = Some("alice".to_string());
+        let kw_a = extract_keywords(&a.message);
+        let kw_b = extract_keywords(&b.message);
+        let explanation = explain_pair(&a, &b, 10, &kw_a, &kw_b);
+        assert_eq!(
+            explanation,
+            "Related because both are assigned to alice (+0.05)."
+        );
+    }
+
     // --- compute_relations ---
 
     #[test]
@@ -514,9 +795,13 @@ mod tests {
         let scan = ScanResult {
             items: vec![],
             files_scanned: 0,
+            files_with_items: 0,
+            bytes_scanned: 0,
             ignored_items: vec![],
+            truncated: false,
+            total_found: None,
         };
-        let result = compute_relations(&scan, 0.3, 10);
+        let result = compute_relations(&scan, 0.3, 10, DEFAULT_MAX_PAIRWISE_ITEMS);
         assert!(result.relationships.is_empty());
         assert_eq!(result.total_items, 0);
     }
@@ -526,9 +811,13 @@ mod tests {
         let scan = ScanResult {
             items: vec![make_item("src/main.rs", 10, Tag::Todo, "fix something")],
             files_scanned: 1,
+            files_with_items: 1,
+            bytes_scanned: 0,
             ignored_items: vec![],
+            truncated: false,
+            total_found: None,
         };
-        let result = compute_relations(&scan, 0.3, 10);
+        let result = compute_relations(&scan, 0.3, 10, DEFAULT_MAX_PAIRWISE_ITEMS);
         assert!(result.relationships.is_empty());
         assert_eq!(result.total_items, 1);
     }
@@ -541,17 +830,88 @@ mod tests {
                 make_item("src/main.rs", 12, Tag::Fixme, "broken authentication"),
             ],
             files_scanned: 1,
+            files_with_items: 1,
+            bytes_scanned: 0,
             ignored_items: vec![],
+            truncated: false,
+            total_found: None,
         };
         // With min_score=0.0, should find relationship
-        let result_low = compute_relations(&scan, 0.0, 10);
+        let result_low = compute_relations(&scan, 0.0, 10, DEFAULT_MAX_PAIRWISE_ITEMS);
         assert!(!result_low.relationships.is_empty());
 
         // With min_score=1.0, should not find relationship (max score < 1.0 unless identical)
-        let result_high = compute_relations(&scan, 1.0, 10);
+        let result_high = compute_relations(&scan, 1.0, 10, DEFAULT_MAX_PAIRWISE_ITEMS);
         assert!(result_high.relationships.is_empty());
     }
 
+    #[test]
+    fn compute_relations_blocking_matches_full_comparison() {
+        let mut items = vec![
+            make_item("src/auth.rs", 10, Tag::Todo, "validate session token"),
+            make_item("src/auth.rs", 14, Tag::Fixme, "token refresh races"),
+            make_item("src/db.rs", 3, Tag::Todo, "pool size from config"),
+            make_item("src/db.rs", 90, Tag::Bug, "deadlock under load"),
+            make_item("src/api.rs", 7, Tag::Todo, "session expiry header"),
+            make_item("src/ui.rs", 1, Tag::Hack, "hardcoded colors"),
+            make_item("src/cli.rs", 5, Tag::Todo, "unrelated cleanup"),
+        ];
+        items[3].issue_ref = Some("#12".to_string());
+        items[5].issue_ref = Some("#12".to_string());
+        items[2].author = Some("bob".to_string());
+        items[6].author = Some("bob".to_string());
+        let scan = ScanResult {
+            items,
+            files_scanned: 5,
+            files_with_items: 5,
+            bytes_scanned: 0,
+            ignored_items: vec![],
+            truncated: false,
+            total_found: None,
+        };
+
+        let pairs = |result: RelateResult| -> Vec<(String, String, String)> {
+            result
+                .relationships
+                .into_iter()
+                .map(|r| (r.from, r.to, format!("{:.6}", r.score)))
+                .collect()
+        };
+        for min_score in [0.11, 0.15, 0.2] {
+            let full = pairs(compute_relations(&scan, min_score, 10, usize::MAX));
+            let blocked = pairs(compute_relations(&scan, min_score, 10, 0));
+            assert!(!full.is_empty());
+            assert_eq!(blocked, full, "min_score {min_score}");
+        }
+    }
+
+    #[test]
+    fn compute_relations_blocking_skips_oversized_keys() {
+        // Every item shares "cache"; only the two close lines in one file
+        // remain candidates once that key is too common to block on
+        let mut items: Vec<TodoItem> = (0..=MAX_BLOCK_SIZE)
+            .map(|n| make_item(&format!("src/m{n}.rs"), 1, Tag::Todo, "cache"))
+            .collect();
+        items.push(make_item("src/m0.rs", 3, Tag::Todo, "cache"));
+        let scan = ScanResult {
+            items,
+            files_scanned: MAX_BLOCK_SIZE + 1,
+            files_with_items: MAX_BLOCK_SIZE + 1,
+            bytes_scanned: 0,
+            ignored_items: vec![],
+            truncated: false,
+            total_found: None,
+        };
+
+        let result = compute_relations(&scan, 0.2, 10, 0);
+        let pairs: Vec<(&str, &str)> = result
+            .relationships
+            .iter()
+            .map(|r| (r.from.as_str(), r.to.as_str()))
+            .collect();
+        assert_eq!(pairs, vec![("src/m0.rs:1", "src/m0.rs:3")]);
+    }
+
     // --- filter_for_item ---
 
     #[test]
@@ -563,18 +923,21 @@ mod tests {
                     to: "src/b.rs:20".to_string(),
                     score: 0.5,
                     reason: "proximity".to_string(),
+                    explanation: String::new(),
                 },
                 Relationship {
                     from: "src/c.rs:30".to_string(),
                     to: "src/d.rs:40".to_string(),
                     score: 0.5,
                     reason: "keyword".to_string(),
+                    explanation: String::new(),
                 },
                 Relationship {
                     from: "src/b.rs:20".to_string(),
                     to: "src/e.rs:50".to_string(),
                     score: 0.4,
                     reason: "tag".to_string(),
+                    explanation: String::new(),
                 },
             ],
             clusters: None,
@@ -603,9 +966,10 @@ mod tests {
             to: "src/a.rs:12".to_string(),
             score: 0.5,
             reason: "proximity".to_string(),
+            explanation: String::new(),
         }];
 
-        let clusters = build_clusters(&relationships, &items);
+        let clusters = build_clusters(&relationships, &items, None);
         assert_eq!(clusters.len(), 1);
         assert_eq!(clusters[0].items.len(), 2);
         assert!(clusters[0].items.contains(&"src/a.rs:10".to_string()));
@@ -626,16 +990,18 @@ mod tests {
                 to: "src/a.rs:12".to_string(),
                 score: 0.5,
                 reason: "proximity".to_string(),
+                explanation: String::new(),
             },
             Relationship {
                 from: "src/b.rs:20".to_string(),
                 to: "src/b.rs:22".to_string(),
                 score: 0.5,
                 reason: "proximity".to_string(),
+                explanation: String::new(),
             },
         ];
 
-        let clusters = build_clusters(&relationships, &items);
+        let clusters = build_clusters(&relationships, &items, None);
         assert_eq!(clusters.len(), 2);
     }
 
@@ -663,7 +1029,7 @@ mod tests {
 
     #[test]
     fn build_clusters_empty_items() {
-        let clusters = build_clusters(&[], &[]);
+        let clusters = build_clusters(&[], &[], None);
         assert!(clusters.is_empty());
     }
 
@@ -674,7 +1040,7 @@ mod tests {
             make_item("src/b.rs", 20, Tag::Bug, "crash"),
         ];
         // No relationships means no clusters with 2+ members
-        let clusters = build_clusters(&[], &items);
+        let clusters = build_clusters(&[], &items, None);
         assert!(clusters.is_empty());
     }
 
@@ -692,15 +1058,17 @@ mod tests {
                 to: "src/a.rs:20".to_string(),
                 score: 0.5,
                 reason: "proximity".to_string(),
+                explanation: String::new(),
             },
             Relationship {
                 from: "src/a.rs:20".to_string(),
                 to: "src/a.rs:30".to_string(),
                 score: 0.4,
                 reason: "proximity".to_string(),
+                explanation: String::new(),
             },
         ];
-        let clusters = build_clusters(&relationships, &items);
+        let clusters = build_clusters(&relationships, &items, None);
         assert_eq!(clusters.len(), 1);
         assert_eq!(clusters[0].items.len(), 3);
         assert_eq!(clusters[0].id, 1);
@@ -724,27 +1092,118 @@ mod tests {
                 to: "src/a.rs:20".to_string(),
                 score: 0.5,
                 reason: "proximity".to_string(),
+                explanation: String::new(),
             },
             Relationship {
                 from: "src/b.rs:10".to_string(),
                 to: "src/b.rs:20".to_string(),
                 score: 0.5,
                 reason: "proximity".to_string(),
+                explanation: String::new(),
             },
             Relationship {
                 from: "src/b.rs:20".to_string(),
                 to: "src/b.rs:30".to_string(),
                 score: 0.4,
                 reason: "proximity".to_string(),
+                explanation: String::new(),
             },
         ];
-        let clusters = build_clusters(&relationships, &items);
+        let clusters = build_clusters(&relationships, &items, None);
         assert_eq!(clusters.len(), 2);
         // Larger cluster (3 items) should come first
         assert_eq!(clusters[0].items.len(), 3);
         assert_eq!(clusters[1].items.len(), 2);
     }
 
+    fn rel(from: &str, to: &str, score: f64) -> Relationship {
+        Relationship {
+            from: from.to_string(),
+            to: to.to_string(),
+            score,
+            reason: "proximity".to_string(),
+            explanation: String::new(),
+        }
+    }
+
+    #[test]
+    fn build_clusters_max_size_partitions_large_group() {
+        // A fully connected group of 10 items
+        let items: Vec<TodoItem> = (1..=10)
+            .map(|line| make_item("src/a.rs", line, Tag::Todo, "fix parser"))
+            .collect();
+        let mut relationships = Vec::new();
+        for i in 1..=10 {
+            for j in (i + 1)..=10 {
+                let score = 1.0 - (j - i) as f64 / 20.0;
+                relationships.push(rel(
+                    &format!("src/a.rs:{i}"),
+                    &format!("src/a.rs:{j}"),
+                    score,
+                ));
+            }
+        }
+
+        assert_eq!(build_clusters(&relationships, &items, None).len(), 1);
+
+        let clusters = build_clusters(&relationships, &items, Some(3));
+        // Adjacent lines link first: {1,2,3}, {4,5,6}, {7,8,9}; 10 is left alone
+        assert_eq!(clusters.len(), 3);
+        assert!(clusters.iter().all(|c| c.items.len() <= 3));
+        // Every member lands in exactly one cluster
+        let members: HashSet<&String> = clusters.iter().flat_map(|c| &c.items).collect();
+        let total: usize = clusters.iter().map(|c| c.items.len()).sum();
+        assert_eq!(members.len(), total);
+        // Relationships are restricted to each cluster's own members
+        for c in &clusters {
+            assert!(c
+                .relationships
+                .iter()
+                .all(|r| c.items.contains(&r.from) && c.items.contains(&r.to)));
+        }
+    }
+
+    #[test]
+    fn build_clusters_max_size_keeps_strongest_links() {
+        let items = vec![
+            make_item("src/a.rs", 1, Tag::Todo, "a"),
+            make_item("src/a.rs", 2, Tag::Todo, "b"),
+            make_item("src/a.rs", 3, Tag::Todo, "c"),
+        ];
+        let relationships = vec![
+            rel("src/a.rs:1", "src/a.rs:2", 0.4),
+            rel("src/a.rs:2", "src/a.rs:3", 0.9),
+        ];
+        let clusters = build_clusters(&relationships, &items, Some(2));
+        assert_eq!(clusters.len(), 1);
+        assert!(clusters[0].items.contains(&"src/a.rs:2".to_string()));
+        assert!(clusters[0].items.contains(&"src/a.rs:3".to_string()));
+    }
+
+    #[test]
+    fn limit_clusters_keeps_highest_scoring() {
+        let items = vec![
+            make_item("src/a.rs", 1, Tag::Todo, "a"),
+            make_item("src/a.rs", 2, Tag::Todo, "b"),
+            make_item("src/b.rs", 1, Tag::Todo, "c"),
+            make_item("src/b.rs", 2, Tag::Todo, "d"),
+            make_item("src/c.rs", 1, Tag::Todo, "e"),
+            make_item("src/c.rs", 2, Tag::Todo, "f"),
+        ];
+        let relationships = vec![
+            rel("src/a.rs:1", "src/a.rs:2", 0.3),
+            rel("src/b.rs:1", "src/b.rs:2", 0.9),
+            rel("src/c.rs:1", "src/c.rs:2", 0.6),
+        ];
+        let clusters = limit_clusters(build_clusters(&relationships, &items, None), 2);
+        assert_eq!(clusters.len(), 2);
+        assert!(clusters
+            .iter()
+            .all(|c| !c.items.contains(&"src/a.rs:1".to_string())));
+        let ids: Vec<usize> = clusters.iter().map(|c| c.id).collect();
+        assert_eq!(ids, vec![1, 2]);
+    }
+
     // --- generate_theme edge cases ---
 
     #[test]
@@ -787,6 +1246,7 @@ mod tests {
                 to: "src/b.rs:20".to_string(),
                 score: 0.5,
                 reason: "proximity".to_string(),
+                explanation: String::new(),
             }],
             clusters: None,
             total_relationships: 1,
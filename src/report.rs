@@ -8,8 +8,8 @@ use crate::config::Config;
 use crate::date_utils;
 use crate::git::git_command;
 use crate::model::*;
-use crate::scanner::scan_content;
-use crate::stats::compute_stats;
+use crate::scanner::{scan_file_content, ScanOptions};
+use crate::stats::{compute_stats, tag_priority_floors};
 
 /// Compute the full report data from a scan result.
 pub fn compute_report(
@@ -17,30 +17,52 @@ pub fn compute_report(
     root: &Path,
     config: &Config,
     history_count: usize,
+    history_from: Option<&str>,
+    history_fast: bool,
     stale_threshold_days: u64,
+) -> Result<ReportResult> {
+    let blame_result = compute_blame(scan, root, stale_threshold_days).ok();
+    compute_report_with_blame(
+        scan,
+        root,
+        config,
+        history_count,
+        history_from,
+        history_fast,
+        blame_result.as_ref(),
+    )
+}
+
+/// [`compute_report`] with blame data the caller already has (e.g. `bundle`,
+/// which also writes it out). Without blame the age data stays empty.
+pub fn compute_report_with_blame(
+    scan: &ScanResult,
+    root: &Path,
+    config: &Config,
+    history_count: usize,
+    history_from: Option<&str>,
+    history_fast: bool,
+    blame_result: Option<&BlameResult>,
 ) -> Result<ReportResult> {
     // Reuse stats computation
-    let stats = compute_stats(scan, None);
-
-    // Compute blame for age data
-    let (age_histogram, stale_count, avg_age_days) =
-        match compute_blame(scan, root, stale_threshold_days) {
-            Ok(blame_result) => {
-                let histogram = build_age_histogram(&blame_result);
-                (
-                    histogram,
-                    blame_result.stale_count,
-                    blame_result.avg_age_days,
-                )
-            }
-            Err(_) => (default_age_histogram(), 0, 0),
-        };
+    let stats = compute_stats(scan, None, &tag_priority_floors(config)?, false);
+
+    // Age data from blame
+    let (age_histogram, stale_count, avg_age_days) = match blame_result {
+        Some(blame_result) => (
+            build_age_histogram(blame_result),
+            blame_result.stale_count,
+            blame_result.avg_age_days,
+        ),
+        None => (default_age_histogram(), 0, 0),
+    };
 
-    // Compute history trend
-    let history = if history_count > 0 {
-        compute_history(root, config, history_count).unwrap_or_default()
-    } else {
-        Vec::new()
+    // Compute history trend; an explicit window must resolve, the default
+    // one is best-effort (e.g. outside a git checkout)
+    let history = match (history_count, history_from) {
+        (0, _) => Vec::new(),
+        (n, Some(from)) => compute_history(root, config, n, Some(from), history_fast)?,
+        (n, None) => compute_history(root, config, n, None, history_fast).unwrap_or_default(),
     };
 
     let generated_at = date_utils::now_iso8601();
@@ -69,23 +91,33 @@ pub fn compute_report(
 }
 
 /// Sample N commits from git history and count tagged items at each.
+///
+/// Without `from` the last 500 commits are sampled; with it, every commit in
+/// `from..HEAD` (the ref itself excluded). With `fast`, each sample is a
+/// single `git grep` instead of a `git show` per file (see [`grep_count`]).
 pub fn compute_history(
     root: &Path,
     config: &Config,
     sample_count: usize,
+    from: Option<&str>,
+    fast: bool,
 ) -> Result<Vec<HistoryPoint>> {
     // Get commit list (hash + date)
-    let log_output = git_command(
-        &[
-            "log",
-            "--format=%H %aI",
-            "--first-parent",
-            "--no-merges",
-            "-n",
-            "500",
-        ],
-        root,
-    )?;
+    let mut args = vec!["log", "--format=%H %aI", "--first-parent", "--no-merges"];
+    let range;
+    match from {
+        Some(from) => {
+            anyhow::ensure!(
+                !from.starts_with('-'),
+                "invalid git ref '{}': must not start with '-'",
+                from
+            );
+            range = format!("{}..HEAD", from);
+            args.extend([range.as_str(), "--"]);
+        }
+        None => args.extend(["-n", "500"]),
+    }
+    let log_output = git_command(&args, root)?;
 
     let commits: Vec<(&str, &str)> = log_output
         .lines()
@@ -102,6 +134,7 @@ pub fn compute_history(
     let indices = select_sample_indices(commits.len(), sample_count);
     let pattern_str = config.tags_pattern();
     let pattern = Regex::new(&pattern_str)?;
+    let options = ScanOptions::from_config(config);
 
     let mut history = Vec::new();
 
@@ -110,6 +143,17 @@ pub fn compute_history(
         let short_hash = &hash[..hash.len().min(8)];
         let date_str = date.split('T').next().unwrap_or(date);
 
+        if fast {
+            if let Ok(count) = grep_count(root, config, hash) {
+                history.push(HistoryPoint {
+                    commit: short_hash.to_string(),
+                    date: date_str.to_string(),
+                    count,
+                });
+            }
+            continue;
+        }
+
         // List files at this commit
         let file_list = match git_command(&["ls-tree", "-r", "--name-only", "--", hash], root) {
             Ok(output) => output,
@@ -128,7 +172,15 @@ pub fn compute_history(
                 Err(_) => continue,
             };
 
-            count += scan_content(&content, file_path, &pattern).items.len();
+            count += scan_file_content(
+                Path::new(file_path),
+                &content,
+                file_path,
+                &pattern,
+                &options,
+            )
+            .items
+            .len();
         }
 
         history.push(HistoryPoint {
@@ -144,12 +196,57 @@ pub fn compute_history(
     Ok(history)
 }
 
+/// Count lines mentioning an enabled tag at `commit` with one `git grep -c`.
+///
+/// This is an approximation of a full scan: it counts matching lines rather
+/// than parsed items, so tags outside comments (string literals, prose),
+/// `todo-scan:ignore` markers and two tags on one line are all counted
+/// differently, and binary files are skipped. Expect it to run high on
+/// codebases that mention tag words in code or docs.
+fn grep_count(root: &Path, config: &Config, commit: &str) -> Result<usize> {
+    let pattern = config
+        .active_tags()
+        .into_iter()
+        .map(regex::escape)
+        .collect::<Vec<_>>()
+        .join("|");
+    let mut args = vec!["grep", "-c", "-I", "-w", "-E"];
+    if config.case_insensitive_tags.unwrap_or(true) {
+        args.push("-i");
+    }
+    args.extend(["-e", pattern.as_str(), commit, "--"]);
+
+    let output = std::process::Command::new("git")
+        .args(&args)
+        .current_dir(root)
+        .output()?;
+    // Exit status 1 means no line matched
+    match output.status.code() {
+        Some(0) => {}
+        Some(1) => return Ok(0),
+        _ => anyhow::bail!(
+            "git grep failed: {}",
+            String::from_utf8_lossy(&output.stderr).trim()
+        ),
+    }
+
+    // Lines read `<commit>:<path>:<count>`
+    Ok(String::from_utf8_lossy(&output.stdout)
+        .lines()
+        .filter_map(|line| line.rsplit_once(':')?.1.parse::<usize>().ok())
+        .sum())
+}
+
 /// Build age histogram from blame result.
 pub fn build_age_histogram(blame_result: &BlameResult) -> Vec<AgeBucket> {
     let mut buckets = [0usize; 6];
     // Buckets: <1w, 1-4w, 1-3m, 3-6m, 6-12m, >1y
 
-    for entry in &blame_result.entries {
+    for entry in blame_result
+        .entries
+        .iter()
+        .filter(|e| !e.blame.is_unknown())
+    {
         let days = entry.blame.age_days;
         let idx = if days < 7 {
             0
@@ -262,6 +359,7 @@ mod tests {
             total: 0,
             avg_age_days: 0,
             stale_count: 0,
+            unknown_count: 0,
             stale_threshold_days: 365,
         };
         let histogram = build_age_histogram(&blame);
@@ -277,12 +375,17 @@ mod tests {
             item: TodoItem {
                 file: "test.rs".to_string(),
                 line: 1,
+                byte_offset: 0,
+                cell: None,
+                labels: vec![],
                 tag: Tag::Todo,
                 message: "test".to_string(),
                 author: None,
                 issue_ref: None,
                 priority: Priority::Normal,
                 deadline: None,
+                ignore_reason: None,
+                severity: None,
             },
             blame: BlameInfo {
                 author: "test".to_string(),
@@ -298,6 +401,7 @@ mod tests {
             total: 1,
             avg_age_days: 3,
             stale_count: 0,
+            unknown_count: 0,
             stale_threshold_days: 365,
         };
         let histogram = build_age_histogram(&blame);
@@ -316,12 +420,17 @@ mod tests {
                 item: TodoItem {
                     file: "test.rs".to_string(),
                     line: 1,
+                    byte_offset: 0,
+                    cell: None,
+                    labels: vec![],
                     tag: Tag::Todo,
                     message: "test".to_string(),
                     author: None,
                     issue_ref: None,
                     priority: Priority::Normal,
                     deadline: None,
+                    ignore_reason: None,
+                    severity: None,
                 },
                 blame: BlameInfo {
                     author: "test".to_string(),
@@ -339,6 +448,7 @@ mod tests {
             total: 6,
             avg_age_days: 141,
             stale_count: 1,
+            unknown_count: 0,
             stale_threshold_days: 365,
         };
         let histogram = build_age_histogram(&blame);
@@ -353,12 +463,17 @@ mod tests {
             item: TodoItem {
                 file: "test.rs".to_string(),
                 line: 1,
+                byte_offset: 0,
+                cell: None,
+                labels: vec![],
                 tag: Tag::Todo,
                 message: "test".to_string(),
                 author: None,
                 issue_ref: None,
                 priority: Priority::Normal,
                 deadline: None,
+                ignore_reason: None,
+                severity: None,
             },
             blame: BlameInfo {
                 author: "tester".to_string(),
@@ -411,6 +526,7 @@ mod tests {
             total: 1,
             avg_age_days: 6,
             stale_count: 0,
+            unknown_count: 0,
             stale_threshold_days: 365,
         };
         let histogram = build_age_histogram(&blame);
@@ -426,6 +542,7 @@ mod tests {
             total: 1,
             avg_age_days: 7,
             stale_count: 0,
+            unknown_count: 0,
             stale_threshold_days: 365,
         };
         let histogram = build_age_histogram(&blame);
@@ -441,6 +558,7 @@ mod tests {
             total: 1,
             avg_age_days: 28,
             stale_count: 0,
+            unknown_count: 0,
             stale_threshold_days: 365,
         };
         let histogram = build_age_histogram(&blame);
@@ -455,6 +573,7 @@ mod tests {
             total: 1,
             avg_age_days: 27,
             stale_count: 0,
+            unknown_count: 0,
             stale_threshold_days: 365,
         };
         let histogram = build_age_histogram(&blame);
@@ -469,6 +588,7 @@ mod tests {
             total: 1,
             avg_age_days: 90,
             stale_count: 0,
+            unknown_count: 0,
             stale_threshold_days: 365,
         };
         let histogram = build_age_histogram(&blame);
@@ -483,6 +603,7 @@ mod tests {
             total: 1,
             avg_age_days: 89,
             stale_count: 0,
+            unknown_count: 0,
             stale_threshold_days: 365,
         };
         let histogram = build_age_histogram(&blame);
@@ -497,6 +618,7 @@ mod tests {
             total: 1,
             avg_age_days: 180,
             stale_count: 0,
+            unknown_count: 0,
             stale_threshold_days: 365,
         };
         let histogram = build_age_histogram(&blame);
@@ -514,6 +636,7 @@ mod tests {
             total: 1,
             avg_age_days: 179,
             stale_count: 0,
+            unknown_count: 0,
             stale_threshold_days: 365,
         };
         let histogram = build_age_histogram(&blame);
@@ -528,6 +651,7 @@ mod tests {
             total: 1,
             avg_age_days: 365,
             stale_count: 1,
+            unknown_count: 0,
             stale_threshold_days: 365,
         };
         let histogram = build_age_histogram(&blame);
@@ -545,6 +669,7 @@ mod tests {
             total: 1,
             avg_age_days: 364,
             stale_count: 0,
+            unknown_count: 0,
             stale_threshold_days: 365,
         };
         let histogram = build_age_histogram(&blame);
@@ -558,6 +683,7 @@ mod tests {
             total: 1,
             avg_age_days: 0,
             stale_count: 0,
+            unknown_count: 0,
             stale_threshold_days: 365,
         };
         let histogram = build_age_histogram(&blame);
@@ -571,6 +697,7 @@ mod tests {
             total: 1,
             avg_age_days: 3650,
             stale_count: 1,
+            unknown_count: 0,
             stale_threshold_days: 365,
         };
         let histogram = build_age_histogram(&blame);
@@ -592,6 +719,7 @@ mod tests {
             total: 3,
             avg_age_days: 2,
             stale_count: 0,
+            unknown_count: 0,
             stale_threshold_days: 365,
         };
         let histogram = build_age_histogram(&blame);
@@ -687,10 +815,14 @@ mod tests {
         let scan = ScanResult {
             items: vec![],
             files_scanned: 0,
+            files_with_items: 0,
+            bytes_scanned: 0,
             ignored_items: vec![],
+            truncated: false,
+            total_found: None,
         };
 
-        let result = compute_report(&scan, tmp.path(), &config, 0, 365).unwrap();
+        let result = compute_report(&scan, tmp.path(), &config, 0, None, false, 365).unwrap();
 
         // Summary should be all zeros
         assert_eq!(result.summary.total_items, 0);
@@ -727,39 +859,58 @@ mod tests {
                 TodoItem {
                     file: "foo.rs".to_string(),
                     line: 10,
+                    byte_offset: 0,
+                    cell: None,
+                    labels: vec![],
                     tag: Tag::Todo,
                     message: "implement this".to_string(),
                     author: Some("alice".to_string()),
                     issue_ref: None,
                     priority: Priority::Normal,
                     deadline: None,
+                    ignore_reason: None,
+                    severity: None,
                 },
                 TodoItem {
                     file: "bar.rs".to_string(),
                     line: 20,
+                    byte_offset: 0,
+                    cell: None,
+                    labels: vec![],
                     tag: Tag::Fixme,
                     message: "urgent fix".to_string(),
                     author: None,
                     issue_ref: Some("#123".to_string()),
                     priority: Priority::Urgent,
                     deadline: None,
+                    ignore_reason: None,
+                    severity: None,
                 },
                 TodoItem {
                     file: "foo.rs".to_string(),
                     line: 30,
+                    byte_offset: 0,
+                    cell: None,
+                    labels: vec![],
                     tag: Tag::Hack,
                     message: "workaround".to_string(),
                     author: None,
                     issue_ref: None,
                     priority: Priority::High,
                     deadline: None,
+                    ignore_reason: None,
+                    severity: None,
                 },
             ],
             files_scanned: 5,
+            files_with_items: 5,
+            bytes_scanned: 0,
             ignored_items: vec![],
+            truncated: false,
+            total_found: None,
         };
 
-        let result = compute_report(&scan, tmp.path(), &config, 0, 365).unwrap();
+        let result = compute_report(&scan, tmp.path(), &config, 0, None, false, 365).unwrap();
 
         // Stats should reflect the items
         assert_eq!(result.summary.total_items, 3);
@@ -797,10 +948,14 @@ mod tests {
         let scan = ScanResult {
             items: vec![],
             files_scanned: 0,
+            files_with_items: 0,
+            bytes_scanned: 0,
             ignored_items: vec![],
+            truncated: false,
+            total_found: None,
         };
 
-        let result = compute_report(&scan, tmp.path(), &config, 5, 365).unwrap();
+        let result = compute_report(&scan, tmp.path(), &config, 5, None, false, 365).unwrap();
 
         // History should be empty because git commands fail in non-git dir
         assert!(result.history.is_empty());
@@ -810,7 +965,7 @@ mod tests {
     fn test_compute_history_non_git_returns_error() {
         let tmp = tempfile::tempdir().unwrap();
         let config = Config::default();
-        let result = compute_history(tmp.path(), &config, 5);
+        let result = compute_history(tmp.path(), &config, 5, None, false);
         assert!(result.is_err());
     }
 
@@ -824,8 +979,88 @@ mod tests {
             .unwrap();
 
         let config = Config::default();
-        let result = compute_history(dir.path(), &config, 5);
+        let result = compute_history(dir.path(), &config, 5, None, false);
         // Either an error or empty vec (no commits)
         assert!(result.is_err() || result.unwrap().is_empty());
     }
+
+    #[test]
+    fn test_compute_history_from_ref_only_includes_later_commits() {
+        let dir = tempfile::tempdir().unwrap();
+        let git = |args: &[&str]| git_command(args, dir.path()).unwrap();
+        git(&["init"]);
+        git(&["config", "user.email", "test@test.com"]);
+        git(&["config", "user.name", "Test"]);
+
+        let mut hashes = Vec::new();
+        for i in 1..=4 {
+            let content = "// TODO: item\n".repeat(i);
+            std::fs::write(dir.path().join("main.rs"), content).unwrap();
+            git(&["add", "."]);
+            git(&["commit", "-m", &format!("commit {}", i)]);
+            hashes.push(git(&["rev-parse", "--short=8", "HEAD"]).trim().to_string());
+            if i == 2 {
+                git(&["tag", "v1"]);
+            }
+        }
+
+        let config = Config::default();
+        let history = compute_history(dir.path(), &config, 10, Some("v1"), false).unwrap();
+        let commits: Vec<&str> = history.iter().map(|p| p.commit.as_str()).collect();
+        assert_eq!(commits.len(), 2);
+        assert!(commits.contains(&hashes[2].as_str()));
+        assert!(commits.contains(&hashes[3].as_str()));
+        let mut counts: Vec<usize> = history.iter().map(|p| p.count).collect();
+        counts.sort();
+        assert_eq!(counts, vec![3, 4]);
+
+        // The default window still covers everything
+        assert_eq!(
+            compute_history(dir.path(), &config, 10, None, false)
+                .unwrap()
+                .len(),
+            4
+        );
+    }
+
+    #[test]
+    fn test_compute_history_from_unknown_ref_is_error() {
+        let dir = tempfile::tempdir().unwrap();
+        git_command(&["init"], dir.path()).unwrap();
+        let config = Config::default();
+        assert!(compute_history(dir.path(), &config, 5, Some("no-such-ref"), false).is_err());
+        assert!(compute_history(dir.path(), &config, 5, Some("--all"), false).is_err());
+    }
+
+    #[test]
+    fn test_compute_history_fast_matches_full_on_comment_only_repo() {
+        let dir = tempfile::tempdir().unwrap();
+        let git = |args: &[&str]| git_command(args, dir.path()).unwrap();
+        git(&["init"]);
+        git(&["config", "user.email", "test@test.com"]);
+        git(&["config", "user.name", "Test"]);
+
+        std::fs::write(dir.path().join("a.rs"), "// TODO: one\n// fixme: two\n").unwrap();
+        git(&["add", "."]);
+        git(&["commit", "-m", "first"]);
+        std::fs::write(dir.path().join("b.py"), "# HACK: three\nx = 1\n").unwrap();
+        git(&["add", "."]);
+        git(&["commit", "-m", "second"]);
+
+        let config = Config::default();
+        let full = compute_history(dir.path(), &config, 10, None, false).unwrap();
+        let fast = compute_history(dir.path(), &config, 10, None, true).unwrap();
+        let counts = |h: &[HistoryPoint]| h.iter().map(|p| p.count).collect::<Vec<_>>();
+        assert_eq!(counts(&full), vec![2, 3]);
+        assert_eq!(counts(&fast), counts(&full));
+
+        // Fast mode also counts tag words outside comments
+        std::fs::write(dir.path().join("c.rs"), "let s = \"TODO: later\";\n").unwrap();
+        git(&["add", "."]);
+        git(&["commit", "-m", "third"]);
+        let full = compute_history(dir.path(), &config, 1, None, false).unwrap();
+        let fast = compute_history(dir.path(), &config, 1, None, true).unwrap();
+        assert_eq!(full[0].count, 3);
+        assert_eq!(fast[0].count, 4);
+    }
 }
@@ -1,15 +1,17 @@
 use anyhow::Result;
 use ignore::{WalkBuilder, WalkState};
 use regex::Regex;
-use std::collections::HashSet;
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::io::{BufRead, BufReader};
 use std::path::Path;
-use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::atomic::{AtomicU64, AtomicUsize, Ordering};
 use std::sync::{Arc, LazyLock, Mutex};
 
 use crate::cache::ScanCache;
 use crate::config::Config;
 use crate::deadline::{parse_deadline, Deadline};
-use crate::model::{Priority, ScanResult, Tag, TodoItem};
+use crate::git::{cat_file_batch, git_command};
+use crate::model::{IgnoreReason, Priority, ScanResult, Tag, TodoItem};
 
 /// Maximum file size (10 MiB) to prevent OOM when scanning very large files.
 pub(crate) const MAX_FILE_SIZE: u64 = 10 * 1024 * 1024;
@@ -45,6 +47,87 @@ const COMMENT_PREFIXES: &[&str] = &["//", "#", "/*", "--", "<!--", ";", "(*", "{
 /// Prefixes that only match at line start (after trimming whitespace).
 const LINE_START_PREFIXES: &[&str] = &["*"];
 
+/// Comment prefixes of known languages by lowercase file extension. With
+/// `permissive_unknown = false`, files whose extension is neither here nor in
+/// the `comment_prefixes` config are skipped.
+const LANGUAGE_COMMENT_PREFIXES: &[(&[&str], &[&str])] = &[
+    (
+        &[
+            "rs", "c", "h", "cc", "cpp", "cxx", "hh", "hpp", "hxx", "cs", "java", "kt", "kts",
+            "go", "js", "jsx", "mjs", "cjs", "ts", "tsx", "mts", "cts", "swift", "scala", "dart",
+            "groovy", "gradle", "zig", "proto", "mm", "sol",
+        ],
+        &["//", "/*"],
+    ),
+    (&["css", "scss", "sass", "less"], &["/*", "//"]),
+    (&["php"], &["//", "/*", "#"]),
+    (
+        &[
+            "py",
+            "pyi",
+            "ipynb",
+            "rb",
+            "sh",
+            "bash",
+            "zsh",
+            "fish",
+            "pl",
+            "pm",
+            "r",
+            "yaml",
+            "yml",
+            "toml",
+            "cfg",
+            "conf",
+            "mk",
+            "cmake",
+            "tf",
+            "nix",
+            "ps1",
+            "ex",
+            "exs",
+            "jl",
+            "coffee",
+            "dockerfile",
+        ],
+        &["#"],
+    ),
+    (&["sql", "lua", "elm", "ada", "adb", "ads"], &["--"]),
+    (&["hs", "lhs"], &["--", "{-"]),
+    (&["ml", "mli"], &["(*"]),
+    (&["html", "htm", "xml", "svg", "md", "markdown"], &["<!--"]),
+    (&["vue", "svelte", "astro"], &["<!--", "//", "/*"]),
+    (
+        &["lisp", "cl", "el", "clj", "cljs", "scm", "asm", "s"],
+        &[";"],
+    ),
+    (&["ini"], &[";", "#"]),
+    (&["tex", "sty", "erl", "hrl"], &["%"]),
+];
+
+/// The extension of `file_path`, lowercased.
+fn extension_of(file_path: &str) -> Option<String> {
+    let ext = Path::new(file_path).extension()?.to_str()?;
+    Some(ext.to_ascii_lowercase())
+}
+
+/// Built-in comment prefixes for `file_path`'s language, if it is known.
+fn language_comment_prefixes(file_path: &str) -> Option<&'static [&'static str]> {
+    let ext = extension_of(file_path)?;
+    LANGUAGE_COMMENT_PREFIXES
+        .iter()
+        .find(|(exts, _)| exts.contains(&ext.as_str()))
+        .map(|(_, prefixes)| *prefixes)
+}
+
+/// Block comment terminators and the openers that make them a comment close.
+const COMMENT_TERMINATORS: &[(&str, &[&str])] = &[
+    ("*/", &["/*", "*"]),
+    ("-->", &["<!--"]),
+    ("*)", &["(*"]),
+    ("-}", &["{-"]),
+];
+
 /// Parse the parenthesized content after a tag.
 /// Returns `(author, deadline)` extracted from the content.
 ///
@@ -52,7 +135,7 @@ const LINE_START_PREFIXES: &[&str] = &["*"];
 /// - `"alice"` → author only
 /// - `"2025-06-01"` → deadline only
 /// - `"alice, 2025-06-01"` → both author and deadline
-fn parse_paren_content(s: &str) -> (Option<String>, Option<Deadline>) {
+pub(crate) fn parse_paren_content(s: &str) -> (Option<String>, Option<Deadline>) {
     let s = s.trim();
     if s.is_empty() {
         return (None, None);
@@ -95,16 +178,41 @@ fn parse_paren_content(s: &str) -> (Option<String>, Option<Deadline>) {
     (Some(s.to_string()), None)
 }
 
+/// Split a trailing `labels=a,b` segment off the parenthesized content.
+/// Returns the remaining content (for [`parse_paren_content`]) and the labels.
+///
+/// The labels segment runs to the end of the content, so it must come last:
+/// `TODO(alice, labels=frontend,perf)`.
+pub(crate) fn split_labels(s: &str) -> (&str, Vec<String>) {
+    let Some(idx) = s.find("labels=") else {
+        return (s, Vec::new());
+    };
+    if s[..idx]
+        .chars()
+        .last()
+        .is_some_and(|c| !(c == ',' || c.is_whitespace()))
+    {
+        return (s, Vec::new());
+    }
+    let labels = s[idx + "labels=".len()..]
+        .split(',')
+        .map(str::trim)
+        .filter(|l| !l.is_empty())
+        .map(str::to_string)
+        .collect();
+    let rest = s[..idx].trim_end().trim_end_matches(',');
+    (rest, labels)
+}
+
 /// Returns true if the prefix at `pos` in `text` is outside any string literal,
 /// using a quote-parity heuristic (even number of `"` before the position).
 fn prefix_outside_quotes(text: &str, pos: usize) -> bool {
     text[..pos].chars().filter(|&c| c == '"').count() % 2 == 0
 }
 
-/// Heuristic: does the tag at `tag_start` appear to be inside a comment?
-pub(crate) fn is_in_comment(line: &str, tag_start: usize) -> bool {
-    let before_tag = &line[..tag_start];
-    for prefix in COMMENT_PREFIXES {
+/// Does any of `prefixes` occur in `before_tag` outside a string literal?
+fn has_prefix_before<'a>(before_tag: &str, prefixes: impl IntoIterator<Item = &'a str>) -> bool {
+    for prefix in prefixes {
         let mut start = 0;
         while let Some(pos) = before_tag[start..].find(prefix) {
             let abs_pos = start + pos;
@@ -114,6 +222,24 @@ pub(crate) fn is_in_comment(line: &str, tag_start: usize) -> bool {
             start = abs_pos + prefix.len();
         }
     }
+    false
+}
+
+/// Heuristic: does the tag at `tag_start` appear to be inside a comment?
+pub(crate) fn is_in_comment(line: &str, tag_start: usize) -> bool {
+    is_in_comment_with(line, tag_start, COMMENT_PREFIXES)
+}
+
+/// [`is_in_comment`] against `prefixes`; the line-start `*` of a block comment
+/// body only counts when `prefixes` has `/*`.
+fn is_in_comment_with(line: &str, tag_start: usize, prefixes: &[&str]) -> bool {
+    let before_tag = &line[..tag_start];
+    if has_prefix_before(before_tag, prefixes.iter().copied()) {
+        return true;
+    }
+    if !prefixes.contains(&"/*") {
+        return false;
+    }
     let trimmed = before_tag.trim_start();
     if LINE_START_PREFIXES.iter().any(|p| trimmed.starts_with(p)) {
         let leading_ws = before_tag.len() - trimmed.len();
@@ -122,10 +248,179 @@ pub(crate) fn is_in_comment(line: &str, tag_start: usize) -> bool {
     false
 }
 
+/// Heuristic: does the tag at `tag_start` sit inside a `"..."` string literal?
+/// Only meaningful once [`is_in_comment`] has said no.
+fn is_in_string_literal(line: &str, tag_start: usize) -> bool {
+    !prefix_outside_quotes(line, tag_start)
+}
+
+/// Default number of continuation comment lines joined into a message.
+/// Joining is opt-in: a longer message would change the id of every existing
+/// multi-line TODO.
+pub const DEFAULT_CONTINUATION_LINES: usize = 0;
+
+/// Default maximum length in bytes of a line that is matched against the tag
+/// pattern; longer lines (typically minified code) are skipped.
+pub const DEFAULT_MAX_LINE_LENGTH: usize = 10_000;
+
+/// Settings that shape how individual files are scanned, derived from `Config`.
+#[derive(Debug, Clone)]
+pub struct ScanOptions {
+    /// Maximum number of continuation comment lines joined into a TODO message.
+    pub continuation_lines: usize,
+    /// Stream-scan files over `MAX_FILE_SIZE` instead of skipping them.
+    pub scan_large: bool,
+    /// Strip a trailing block comment terminator (`*/`, `-->`) from messages.
+    pub strip_comment_terminators: bool,
+    /// Collapse runs of whitespace inside messages to a single space. Off by
+    /// default because it changes the id of any message it rewrites.
+    pub collapse_whitespace: bool,
+    /// Keep tags inside string literals as ignored items instead of dropping them.
+    pub string_literals_as_ignored: bool,
+    /// Skip lines longer than this many bytes; 0 disables the limit.
+    pub max_line_length: usize,
+    /// Message prefixes (e.g. `[done]`) that mark an item as resolved.
+    pub resolved_markers: Vec<String>,
+    /// Comment prefixes per lowercase file extension, replacing the built-in
+    /// list for files with that extension.
+    pub comment_prefixes: HashMap<String, Vec<String>>,
+    /// Scan files whose extension has no `comment_prefixes` entry with the
+    /// generic built-in prefixes; when false, known languages use their own
+    /// prefixes and other files yield no items.
+    pub permissive_unknown: bool,
+    /// Keep at most this many items per file; the rest are only counted in
+    /// [`ScanContentResult::dropped`].
+    pub max_items: Option<usize>,
+}
+
+impl Default for ScanOptions {
+    fn default() -> Self {
+        Self {
+            continuation_lines: DEFAULT_CONTINUATION_LINES,
+            scan_large: false,
+            strip_comment_terminators: true,
+            collapse_whitespace: false,
+            string_literals_as_ignored: true,
+            max_line_length: DEFAULT_MAX_LINE_LENGTH,
+            resolved_markers: Vec::new(),
+            comment_prefixes: HashMap::new(),
+            permissive_unknown: true,
+            max_items: None,
+        }
+    }
+}
+
+impl ScanOptions {
+    pub fn from_config(config: &Config) -> Self {
+        Self {
+            continuation_lines: config
+                .continuation_lines
+                .unwrap_or(DEFAULT_CONTINUATION_LINES),
+            scan_large: config.scan_large.unwrap_or(false),
+            strip_comment_terminators: config.strip_comment_terminators.unwrap_or(true),
+            collapse_whitespace: config.collapse_whitespace.unwrap_or(false),
+            string_literals_as_ignored: config.string_literals_as_ignored.unwrap_or(true),
+            max_line_length: config.max_line_length.unwrap_or(DEFAULT_MAX_LINE_LENGTH),
+            resolved_markers: config.resolved_markers.clone(),
+            comment_prefixes: config
+                .comment_prefixes
+                .iter()
+                .map(|(ext, prefixes)| {
+                    let ext = ext.trim_start_matches('.').to_ascii_lowercase();
+                    (ext, prefixes.clone())
+                })
+                .collect(),
+            permissive_unknown: config.permissive_unknown.unwrap_or(true),
+            max_items: config.max_items,
+        }
+    }
+
+    /// The configured comment prefixes for `file_path`'s extension, if any.
+    fn comment_prefixes_for(&self, file_path: &str) -> Option<&[String]> {
+        self.comment_prefixes
+            .get(&extension_of(file_path)?)
+            .map(Vec::as_slice)
+    }
+
+    /// Whether `line` is too long to be matched.
+    fn is_too_long(&self, line: &str) -> bool {
+        self.max_line_length > 0 && line.len() > self.max_line_length
+    }
+}
+
+/// Find the comment prefix that immediately precedes the tag and the number of
+/// whitespace characters between that prefix and the tag.
+fn comment_lead(line: &str, tag_start: usize) -> Option<(&'static str, usize)> {
+    let before_tag = &line[..tag_start];
+    let trimmed = before_tag.trim_end();
+    let gap = before_tag.len() - trimmed.len();
+
+    let mut prefixes: Vec<&'static str> = COMMENT_PREFIXES
+        .iter()
+        .chain(LINE_START_PREFIXES)
+        .copied()
+        .collect();
+    prefixes.sort_by_key(|p| std::cmp::Reverse(p.len()));
+    prefixes
+        .into_iter()
+        .find(|p| trimmed.ends_with(p))
+        .map(|p| (p, gap))
+}
+
+/// If `line` continues a comment started with `prefix`, return its text.
+///
+/// A continuation uses the same prefix at the start of the line and indents its
+/// text further than the tag was indented after the prefix, e.g.
+/// `//   and also this` following `// TODO: do the thing`.
+fn continuation_text<'a>(line: &'a str, prefix: &str, tag_gap: usize) -> Option<&'a str> {
+    let rest = line.trim_start().strip_prefix(prefix)?;
+    let text = rest.trim();
+    let indent = rest.len() - rest.trim_start().len();
+    if indent <= tag_gap || text.is_empty() {
+        return None;
+    }
+    Some(text)
+}
+
+/// Remove a trailing block comment terminator from `message`.
+///
+/// Only terminators whose opener appears before the tag on `line` are removed,
+/// so `i-->` in a `//` comment or `*)` closing a parenthesis are kept.
+fn strip_comment_terminator(message: &mut String, line: &str, tag_start: usize) {
+    let before_tag = &line[..tag_start];
+    for (terminator, openers) in COMMENT_TERMINATORS {
+        let Some(rest) = message.strip_suffix(terminator) else {
+            continue;
+        };
+        let opened = openers.iter().any(|opener| match *opener {
+            "*" => before_tag.trim_start().starts_with('*'),
+            opener => before_tag.contains(opener),
+        });
+        if opened {
+            *message = rest.trim_end().to_string();
+            return;
+        }
+    }
+}
+
 /// Result of scanning content, separating normal items from suppressed ones.
+#[derive(Default)]
 pub struct ScanContentResult {
     pub items: Vec<TodoItem>,
     pub ignored_items: Vec<TodoItem>,
+    /// Items found past `ScanOptions::max_items` and not kept.
+    pub dropped: usize,
+}
+
+impl ScanContentResult {
+    /// Keep `item` unless `max_items` are already kept, else count it as dropped.
+    pub(crate) fn keep(&mut self, item: TodoItem, max_items: Option<usize>) {
+        if max_items.is_some_and(|max| self.items.len() >= max) {
+            self.dropped += 1;
+        } else {
+            self.items.push(item);
+        }
+    }
 }
 
 /// The inline suppression marker for the current line.
@@ -142,7 +437,22 @@ const IGNORE_NEXT_LINE_MARKER: &str = "todo-scan:ignore-next-line";
 /// Suppression markers:
 /// - `todo-scan:ignore` on the same line as a TODO suppresses that item
 /// - `todo-scan:ignore-next-line` on any line suppresses the immediately following line
-pub fn scan_content(content: &str, file_path: &str, pattern: &Regex) -> ScanContentResult {
+///
+/// Continuation comment lines directly below a TODO are joined into its message,
+/// up to `options.continuation_lines`; the reported line stays the tag's line.
+///
+/// Lines longer than `options.max_line_length` are skipped, so one huge
+/// minified line doesn't stall the scan.
+///
+/// Without `options.permissive_unknown`, a file whose extension has no
+/// `comment_prefixes` entry is matched against its language's prefixes
+/// (`LANGUAGE_COMMENT_PREFIXES`), and yields nothing if the language is unknown.
+pub fn scan_content(
+    content: &str,
+    file_path: &str,
+    pattern: &Regex,
+    options: &ScanOptions,
+) -> ScanContentResult {
     let lines: Vec<&str> = content.lines().collect();
 
     // Pre-scan for todo-scan:ignore-next-line markers
@@ -157,15 +467,41 @@ pub fn scan_content(content: &str, file_path: &str, pattern: &Regex) -> ScanCont
         }
     }
 
-    let mut items = Vec::new();
-    let mut ignored_items = Vec::new();
+    let mut result = ScanContentResult::default();
+    let comment_prefixes = options.comment_prefixes_for(file_path);
+    // Without permissive_unknown, known languages use their own prefixes and
+    // files of unknown languages are skipped
+    let language_prefixes = match comment_prefixes {
+        None if !options.permissive_unknown => match language_comment_prefixes(file_path) {
+            Some(prefixes) => Some(prefixes),
+            None => return result,
+        },
+        _ => None,
+    };
 
     for (line_idx, line) in lines.iter().enumerate() {
+        if options.is_too_long(line) {
+            continue;
+        }
         if let Some(caps) = pattern.captures(line) {
             let tag_match = caps.get(1).unwrap();
-            if !is_in_comment(line, tag_match.start()) {
+            let in_comment = match (comment_prefixes, language_prefixes) {
+                (Some(prefixes), _) => has_prefix_before(
+                    &line[..tag_match.start()],
+                    prefixes.iter().map(String::as_str),
+                ),
+                (None, Some(prefixes)) => is_in_comment_with(line, tag_match.start(), prefixes),
+                (None, None) => is_in_comment(line, tag_match.start()),
+            };
+            let in_string = if in_comment {
+                false
+            } else if options.string_literals_as_ignored
+                && is_in_string_literal(line, tag_match.start())
+            {
+                true
+            } else {
                 continue;
-            }
+            };
 
             // Skip if the tag is immediately followed by a hyphen (e.g., "todo-scan:ignore")
             if line.as_bytes().get(tag_match.end()) == Some(&b'-') {
@@ -178,10 +514,11 @@ pub fn scan_content(content: &str, file_path: &str, pattern: &Regex) -> ScanCont
                 Err(_) => continue,
             };
 
-            let (author, deadline) = match caps.get(2) {
-                Some(m) => parse_paren_content(m.as_str()),
-                None => (None, None),
+            let (paren, labels) = match caps.get(2) {
+                Some(m) => split_labels(m.as_str()),
+                None => ("", Vec::new()),
             };
+            let (author, deadline) = parse_paren_content(paren);
 
             let priority = match caps.get(3).map(|m| m.as_str()) {
                 Some("!!") => Priority::Urgent,
@@ -194,11 +531,31 @@ pub fn scan_content(content: &str, file_path: &str, pattern: &Regex) -> ScanCont
                 .map(|m| m.as_str().trim().to_string())
                 .unwrap_or_default();
 
+            // A string literal's message ends at its closing quote
+            if in_string {
+                if let Some(end) = message.find('"') {
+                    message.truncate(end);
+                    message.truncate(message.trim_end().len());
+                }
+            }
+
+            // A resolved marker right after the tag (`TODO[done]: x`) or at the
+            // start of the message (`TODO: [done] x`) is dropped from the text
+            let resolved = strip_resolved_marker(&mut message, &options.resolved_markers);
+
             // Check if this line is suppressed
             let has_inline_ignore =
                 line.contains(IGNORE_MARKER) && !line.contains(IGNORE_NEXT_LINE_MARKER);
             let is_next_line_suppressed = suppressed_lines.contains(&line_idx);
-            let is_suppressed = has_inline_ignore || is_next_line_suppressed;
+            let ignore_reason = if in_string {
+                Some(IgnoreReason::StringLiteral)
+            } else if has_inline_ignore || is_next_line_suppressed {
+                Some(IgnoreReason::Marker)
+            } else if resolved {
+                Some(IgnoreReason::Resolved)
+            } else {
+                None
+            };
 
             // Strip trailing todo-scan:ignore from message text
             if has_inline_ignore {
@@ -207,36 +564,396 @@ pub fn scan_content(content: &str, file_path: &str, pattern: &Regex) -> ScanCont
                 }
             }
 
+            // Join indented continuation lines that share the tag's comment prefix
+            let lead = if in_string {
+                None
+            } else {
+                comment_lead(line, tag_match.start())
+            };
+            if let Some((prefix, tag_gap)) = lead {
+                let following = lines.iter().skip(line_idx + 1);
+                for next in following.take(options.continuation_lines) {
+                    if options.is_too_long(next) || is_tag_comment(next, pattern) {
+                        break;
+                    }
+                    match continuation_text(next, prefix, tag_gap) {
+                        Some(text) if message.is_empty() => message = text.to_string(),
+                        Some(text) => {
+                            message.push(' ');
+                            message.push_str(text);
+                        }
+                        None => break,
+                    }
+                }
+            }
+
+            if options.strip_comment_terminators {
+                strip_comment_terminator(&mut message, line, tag_match.start());
+            }
+            if options.collapse_whitespace {
+                message = message.split_whitespace().collect::<Vec<_>>().join(" ");
+            }
+
             let issue_ref = extract_issue_ref(&message);
 
+            // `lines()` yields subslices of `content`, so their distance from
+            // its start is the line's byte offset whatever the line endings
+            let line_start = line.as_ptr() as usize - content.as_ptr() as usize;
+
             let item = TodoItem {
                 file: file_path.to_string(),
                 line: line_idx + 1,
+                byte_offset: line_start + tag_match.start(),
+                cell: None,
                 tag,
                 message,
                 author,
                 issue_ref,
                 priority,
                 deadline,
+                labels,
+                ignore_reason,
+                severity: None,
             };
 
-            if is_suppressed {
-                ignored_items.push(item);
+            if item.ignore_reason.is_some() {
+                result.ignored_items.push(item);
             } else {
-                items.push(item);
+                result.keep(item, options.max_items);
             }
         }
     }
 
-    ScanContentResult {
-        items,
-        ignored_items,
+    result
+}
+
+/// Remove a leading resolved marker (matched case-insensitively) and any colon
+/// after it from `message`. Returns whether a marker was found.
+fn strip_resolved_marker(message: &mut String, markers: &[String]) -> bool {
+    let Some(marker) = markers.iter().find(|m| {
+        !m.is_empty()
+            && message
+                .get(..m.len())
+                .is_some_and(|prefix| prefix.eq_ignore_ascii_case(m))
+    }) else {
+        return false;
+    };
+    let rest = message[marker.len()..].trim_start_matches(':').trim();
+    *message = rest.to_string();
+    true
+}
+
+/// Scan a whole file's content, handing notebooks to the cell-aware scanner.
+pub(crate) fn scan_file_content(
+    path: &Path,
+    content: &str,
+    relative_path: &str,
+    pattern: &Regex,
+    options: &ScanOptions,
+) -> ScanContentResult {
+    #[cfg(feature = "notebooks")]
+    if crate::notebook::is_notebook(path) {
+        return crate::notebook::scan_notebook(content, relative_path, pattern, options);
+    }
+    #[cfg(not(feature = "notebooks"))]
+    let _ = path;
+    scan_content(content, relative_path, pattern, options)
+}
+
+/// Does `line` contain a tag match inside a comment?
+fn is_tag_comment(line: &str, pattern: &Regex) -> bool {
+    pattern
+        .captures(line)
+        .and_then(|caps| caps.get(1))
+        .is_some_and(|m| is_in_comment(line, m.start()))
+}
+
+/// Stream-scan `reader` line by line, for files too large to read whole.
+///
+/// Each line matching `pattern` is handed to [`scan_content`] together with
+/// the line before it (for `ignore-next-line`) and up to
+/// `options.continuation_lines` lines after it, so results match a whole-file
+/// scan while memory stays bounded by that window. Fails on invalid UTF-8,
+/// like `read_to_string`.
+pub fn scan_reader<R: BufRead>(
+    mut reader: R,
+    file_path: &str,
+    pattern: &Regex,
+    options: &ScanOptions,
+) -> std::io::Result<ScanContentResult> {
+    let lookahead = options.continuation_lines;
+    // Snippets hold neighbouring lines too, so the cap applies to the
+    // target items kept below rather than inside each snippet
+    let snippet_options = ScanOptions {
+        max_items: None,
+        ..options.clone()
+    };
+    let mut result = ScanContentResult::default();
+    // `window[0]` is line `first` (0-based); `next` is the next line to check.
+    // Each line is kept with its byte offset in the file.
+    let mut window: VecDeque<(String, usize)> = VecDeque::with_capacity(lookahead + 2);
+    let mut first = 0;
+    let mut next = 0;
+    let mut offset = 0;
+
+    loop {
+        let mut line = String::new();
+        let len = reader.read_line(&mut line)?;
+        let eof = len == 0;
+        if !eof {
+            // Strip the terminator as `lines()` would
+            if line.ends_with('\n') {
+                line.pop();
+                if line.ends_with('\r') {
+                    line.pop();
+                }
+            }
+            window.push_back((line, offset));
+            offset += len;
+        }
+        let read = first + window.len();
+
+        while next < read && (eof || next + lookahead < read) {
+            let (line, line_offset) = &window[next - first];
+            if !options.is_too_long(line) && pattern.is_match(line) {
+                let start = next.saturating_sub(1).max(first);
+                let snippet = window
+                    .range(start - first..)
+                    .map(|(line, _)| line.as_str())
+                    .collect::<Vec<_>>()
+                    .join("\n");
+                let target = next - start + 1;
+                // Where the target line begins inside the snippet
+                let snippet_start = if start < next {
+                    window[start - first].0.len() + 1
+                } else {
+                    0
+                };
+                let found = scan_content(&snippet, file_path, pattern, &snippet_options);
+                let at_target = |mut item: TodoItem| {
+                    (item.line == target).then(|| {
+                        item.line = next + 1;
+                        item.byte_offset = item.byte_offset - snippet_start + line_offset;
+                        item
+                    })
+                };
+                for item in found.items.into_iter().filter_map(at_target) {
+                    result.keep(item, options.max_items);
+                }
+                result
+                    .ignored_items
+                    .extend(found.ignored_items.into_iter().filter_map(at_target));
+            }
+            next += 1;
+            // Keep the line before `next` for ignore-next-line markers
+            while first + 1 < next {
+                window.pop_front();
+                first += 1;
+            }
+        }
+
+        if eof {
+            return Ok(result);
+        }
+    }
+}
+
+/// Stream-scan a file over `MAX_FILE_SIZE`; `None` if it can't be read as text.
+fn scan_large_file(
+    path: &Path,
+    relative_path: &str,
+    pattern: &Regex,
+    options: &ScanOptions,
+) -> Option<ScanContentResult> {
+    let file = std::fs::File::open(path).ok()?;
+    scan_reader(BufReader::new(file), relative_path, pattern, options).ok()
+}
+
+/// Regex matching a configured tag as a whole segment of a file or directory
+/// name, e.g. `TODO` in `TODO_rename_this.rs`. Unlike comment scanning, `_`
+/// separates segments. `None` unless `scan_filenames` is enabled.
+fn filename_pattern(config: &Config) -> Result<Option<Regex>> {
+    if !config.scan_filenames.unwrap_or(false) {
+        return Ok(None);
+    }
+    let tags = config
+        .active_tags()
+        .into_iter()
+        .map(regex::escape)
+        .collect::<Vec<_>>()
+        .join("|");
+    let pattern = format!(r"(?i)(?:^|[^[:alnum:]])({tags})(?:[^[:alnum:]]|$)");
+    Ok(Some(Regex::new(&pattern)?))
+}
+
+/// Describe the regexes a scan would use with `config`, after `tags` and
+/// `enabled_tags` are applied (for `--show-pattern`).
+pub fn describe_patterns(config: &Config) -> Result<String> {
+    let tags = config.tags_pattern();
+    Regex::new(&tags)?;
+
+    let mut out = String::new();
+    out.push_str(&format!("tags: {}\n", config.active_tags().join(", ")));
+    out.push_str(&format!("pattern: {}\n", tags));
+    out.push_str("  group 1: tag\n");
+    out.push_str("  group 2: (author, deadline) in parentheses\n");
+    out.push_str("  group 3: priority (! or !!)\n");
+    out.push_str("  group 4: message\n");
+    out.push_str(&format!("issue ref: {}\n", ISSUE_REF_RE.as_str()));
+    if let Some(names) = filename_pattern(config)? {
+        out.push_str(&format!("file names: {}\n", names.as_str()));
+    }
+    Ok(out)
+}
+
+/// Report a tag in the last component of `relative_path` as an item on
+/// line 0 whose message is the path itself.
+fn scan_path_name(relative_path: &str, pattern: &Regex) -> Option<TodoItem> {
+    let name = Path::new(relative_path).file_name()?.to_str()?;
+    let tag = pattern
+        .captures(name)?
+        .get(1)?
+        .as_str()
+        .parse::<Tag>()
+        .ok()?;
+    Some(TodoItem {
+        file: relative_path.to_string(),
+        line: 0,
+        byte_offset: 0,
+        cell: None,
+        labels: vec![],
+        tag,
+        message: relative_path.to_string(),
+        author: None,
+        issue_ref: None,
+        priority: Priority::Normal,
+        deadline: None,
+        ignore_reason: None,
+        severity: None,
+    })
+}
+
+/// Collects scanned items up to `max_items` while counting every item found,
+/// so a pathological tree cannot grow the result without bound. The kept
+/// items are the first `max_items` by file and line, whatever order the
+/// parallel walker delivers files in.
+#[derive(Debug)]
+struct ItemCollector {
+    items: Vec<TodoItem>,
+    total_found: usize,
+    max_items: Option<usize>,
+}
+
+impl ItemCollector {
+    fn new(max_items: Option<usize>) -> Self {
+        Self {
+            items: Vec::new(),
+            total_found: 0,
+            max_items,
+        }
+    }
+
+    fn extend(&mut self, found: impl IntoIterator<Item = TodoItem>) {
+        for item in found {
+            self.total_found += 1;
+            self.items.push(item);
+        }
+        // Trim in batches so memory stays within twice the cap
+        if let Some(max) = self.max_items {
+            if self.items.len() > max.saturating_mul(2) {
+                self.trim(max);
+            }
+        }
+    }
+
+    /// Count items a file scan found but did not keep.
+    fn count_dropped(&mut self, dropped: usize) {
+        self.total_found += dropped;
+    }
+
+    fn trim(&mut self, max: usize) {
+        self.items.sort_by(|a, b| {
+            (&a.file, a.line, a.byte_offset).cmp(&(&b.file, b.line, b.byte_offset))
+        });
+        self.items.truncate(max);
+    }
+
+    /// Returns the collected items, whether any were dropped, and the total
+    /// found when they were.
+    fn finish(mut self) -> (Vec<TodoItem>, bool, Option<usize>) {
+        if let Some(max) = self.max_items {
+            self.trim(max);
+        }
+        let truncated = self.total_found > self.items.len();
+        let total_found = truncated.then_some(self.total_found);
+        (self.items, truncated, total_found)
     }
 }
 
+/// Device and inode of a directory, used to spot one reached twice through
+/// symlinks. `None` where the platform has no inode numbers.
+#[cfg(unix)]
+fn dir_identity(path: &Path) -> Option<(u64, u64)> {
+    use std::os::unix::fs::MetadataExt;
+    let meta = std::fs::metadata(path).ok()?;
+    Some((meta.dev(), meta.ino()))
+}
+
+#[cfg(not(unix))]
+fn dir_identity(_path: &Path) -> Option<(u64, u64)> {
+    None
+}
+
+/// Whether any directory component of `relative_path` is listed in
+/// `exclude_dirs`. The path must be relative to the scan root, so that a root
+/// which itself lives under e.g. `vendor/` is still scanned.
+pub fn in_excluded_dir(relative_path: &Path, exclude_dirs: &[String]) -> bool {
+    relative_path.components().any(|c| {
+        c.as_os_str()
+            .to_str()
+            .is_some_and(|s| exclude_dirs.iter().any(|dir| dir == s))
+    })
+}
+
+/// Build the directory walker shared by the scanners.
+///
+/// Ignore files (`.gitignore`, `.git/info/exclude`, the global gitignore and
+/// `.ignore`) are honored unless `respect_gitignore` is disabled. `.gitignore`
+/// applies even outside a git checkout so exported trees behave the same.
+///
+/// Symlinked directories are only entered with `follow_symlinks`; each
+/// directory is then walked once, however many links point at it, which also
+/// breaks symlink cycles.
+fn walk_builder(root: &Path, config: &Config) -> WalkBuilder {
+    let respect = config.respect_gitignore.unwrap_or(true);
+    let follow = config.follow_symlinks.unwrap_or(false);
+    let mut builder = WalkBuilder::new(root);
+    builder
+        .git_ignore(respect)
+        .git_exclude(respect)
+        .git_global(respect)
+        .ignore(respect)
+        .parents(respect)
+        .require_git(false)
+        .follow_links(follow);
+    if follow {
+        let visited = Mutex::new(HashSet::new());
+        builder.filter_entry(move |entry| {
+            if !entry.file_type().is_some_and(|t| t.is_dir()) {
+                return true;
+            }
+            match dir_identity(entry.path()) {
+                Some(id) => visited.lock().expect("scan thread panicked").insert(id),
+                None => true,
+            }
+        });
+    }
+    builder
+}
+
 /// Walk a directory tree and scan all files for TODO-style comments.
 ///
-/// Respects `.gitignore` via `ignore::WalkBuilder`. Applies the exclude
+/// Respects `.gitignore` via [`walk_builder`]. Applies the exclude
 /// directories and exclude patterns from `Config`. Returns a `ScanResult`
 /// with every matched item and the total number of files scanned.
 pub fn scan_directory(root: &Path, config: &Config) -> Result<ScanResult> {
@@ -249,22 +966,30 @@ pub fn scan_directory(root: &Path, config: &Config) -> Result<ScanResult> {
         .filter_map(|p| Regex::new(p).ok())
         .collect();
 
-    let items = Arc::new(Mutex::new(Vec::new()));
+    let options = Arc::new(ScanOptions::from_config(config));
+    let name_pattern = Arc::new(filename_pattern(config)?);
+    let items = Arc::new(Mutex::new(ItemCollector::new(config.max_items)));
     let ignored_items = Arc::new(Mutex::new(Vec::new()));
     let files_scanned = Arc::new(AtomicUsize::new(0));
+    let files_with_items = Arc::new(AtomicUsize::new(0));
+    let bytes_scanned = Arc::new(AtomicU64::new(0));
     let exclude_dirs = Arc::new(config.exclude_dirs.clone());
     let exclude_regexes = Arc::new(exclude_regexes);
     let root = root.to_path_buf();
 
-    let walker = WalkBuilder::new(&root).build_parallel();
+    let walker = walk_builder(&root, config).build_parallel();
 
     walker.run(|| {
         let items = Arc::clone(&items);
         let ignored_items = Arc::clone(&ignored_items);
         let files_scanned = Arc::clone(&files_scanned);
+        let files_with_items = Arc::clone(&files_with_items);
+        let bytes_scanned = Arc::clone(&bytes_scanned);
         let exclude_dirs = Arc::clone(&exclude_dirs);
         let exclude_regexes = Arc::clone(&exclude_regexes);
         let pattern = pattern.clone();
+        let options = Arc::clone(&options);
+        let name_pattern = Arc::clone(&name_pattern);
         let root = root.clone();
 
         Box::new(move |entry| {
@@ -274,17 +999,15 @@ pub fn scan_directory(root: &Path, config: &Config) -> Result<ScanResult> {
             };
 
             let path = entry.path();
+            let is_file = path.is_file();
 
-            if !path.is_file() {
+            if !is_file && name_pattern.is_none() {
                 return WalkState::Continue;
             }
 
-            // Check exclude_dirs
-            let should_exclude_dir = exclude_dirs.iter().any(|dir| {
-                path.components()
-                    .any(|c| c.as_os_str().to_str().map(|s| s == dir).unwrap_or(false))
-            });
-            if should_exclude_dir {
+            // Check exclude_dirs below the scan root
+            let relative = path.strip_prefix(&root).unwrap_or(path);
+            if in_excluded_dir(relative, &exclude_dirs) {
                 return WalkState::Continue;
             }
 
@@ -295,31 +1018,48 @@ pub fn scan_directory(root: &Path, config: &Config) -> Result<ScanResult> {
                 return WalkState::Continue;
             }
 
-            // Skip oversized files to prevent OOM
-            if let Ok(meta) = path.metadata() {
-                if should_skip_file(&meta, MAX_FILE_SIZE) {
-                    return WalkState::Continue;
+            let relative_path = relative.to_string_lossy().to_string();
+
+            // Tags in file and directory names (--scan-filenames)
+            if let Some(name_pattern) = name_pattern.as_ref() {
+                if entry.depth() > 0 {
+                    if let Some(item) = scan_path_name(&relative_path, name_pattern) {
+                        items.lock().expect("scan thread panicked").extend([item]);
+                    }
                 }
             }
 
-            // Read the file; skip binary or unreadable files
-            let content = match std::fs::read_to_string(path) {
-                Ok(c) => c,
-                Err(_) => return WalkState::Continue,
-            };
-
-            let relative_path = path
-                .strip_prefix(&root)
-                .unwrap_or(path)
-                .to_string_lossy()
-                .to_string();
+            if !is_file {
+                return WalkState::Continue;
+            }
 
-            let result = scan_content(&content, &relative_path, &pattern);
+            // Oversized files are skipped to prevent OOM, or streamed with --scan-large
+            let oversized = path
+                .metadata()
+                .ok()
+                .filter(|meta| should_skip_file(meta, MAX_FILE_SIZE));
+            let (result, len) = match oversized {
+                Some(_) if !options.scan_large => return WalkState::Continue,
+                Some(meta) => match scan_large_file(path, &relative_path, &pattern, &options) {
+                    Some(result) => (result, meta.len()),
+                    None => return WalkState::Continue,
+                },
+                None => {
+                    // Read the file; skip binary or unreadable files
+                    let content = match std::fs::read_to_string(path) {
+                        Ok(c) => c,
+                        Err(_) => return WalkState::Continue,
+                    };
+                    let result =
+                        scan_file_content(path, &content, &relative_path, &pattern, &options);
+                    (result, content.len() as u64)
+                }
+            };
             if !result.items.is_empty() {
-                items
-                    .lock()
-                    .expect("scan thread panicked")
-                    .extend(result.items);
+                files_with_items.fetch_add(1, Ordering::Relaxed);
+                let mut items = items.lock().expect("scan thread panicked");
+                items.extend(result.items);
+                items.count_dropped(result.dropped);
             }
             if !result.ignored_items.is_empty() {
                 ignored_items
@@ -328,25 +1068,33 @@ pub fn scan_directory(root: &Path, config: &Config) -> Result<ScanResult> {
                     .extend(result.ignored_items);
             }
             files_scanned.fetch_add(1, Ordering::Relaxed);
+            bytes_scanned.fetch_add(len, Ordering::Relaxed);
 
             WalkState::Continue
         })
     });
 
-    let items = Arc::try_unwrap(items)
+    let (items, truncated, total_found) = Arc::try_unwrap(items)
         .expect("all walker threads should have finished")
         .into_inner()
-        .unwrap();
+        .unwrap()
+        .finish();
     let ignored_items = Arc::try_unwrap(ignored_items)
         .expect("all walker threads should have finished")
         .into_inner()
         .unwrap();
     let files_scanned = files_scanned.load(Ordering::Relaxed);
+    let files_with_items = files_with_items.load(Ordering::Relaxed);
+    let bytes_scanned = bytes_scanned.load(Ordering::Relaxed);
 
     Ok(ScanResult {
         items,
         ignored_items,
         files_scanned,
+        files_with_items,
+        bytes_scanned,
+        truncated,
+        total_found,
     })
 }
 
@@ -378,14 +1126,22 @@ pub fn scan_directory_cached(
         .filter_map(|p| Regex::new(p).ok())
         .collect();
 
-    let mut items = Vec::new();
+    // Cache entries must hold every item of a file, so only the collector caps
+    let options = ScanOptions {
+        max_items: None,
+        ..ScanOptions::from_config(config)
+    };
+    let name_pattern = filename_pattern(config)?;
+    let mut items = ItemCollector::new(config.max_items);
     let mut ignored_items = Vec::new();
     let mut files_scanned: usize = 0;
+    let mut files_with_items: usize = 0;
+    let mut bytes_scanned: u64 = 0;
     let mut cache_hits: usize = 0;
     let mut cache_misses: usize = 0;
     let mut seen_paths = HashSet::new();
 
-    let walker = WalkBuilder::new(root).build();
+    let walker = walk_builder(root, config).build();
 
     for entry in walker {
         let entry = match entry {
@@ -394,17 +1150,15 @@ pub fn scan_directory_cached(
         };
 
         let path = entry.path();
+        let is_file = path.is_file();
 
-        if !path.is_file() {
+        if !is_file && name_pattern.is_none() {
             continue;
         }
 
-        // Check exclude_dirs
-        let should_exclude_dir = config.exclude_dirs.iter().any(|dir| {
-            path.components()
-                .any(|c| c.as_os_str().to_str().map(|s| s == dir).unwrap_or(false))
-        });
-        if should_exclude_dir {
+        // Check exclude_dirs below the scan root
+        let relative_path = path.strip_prefix(root).unwrap_or(path).to_path_buf();
+        if in_excluded_dir(&relative_path, &config.exclude_dirs) {
             continue;
         }
 
@@ -415,7 +1169,20 @@ pub fn scan_directory_cached(
             continue;
         }
 
-        let relative_path = path.strip_prefix(root).unwrap_or(path).to_path_buf();
+        // Tags in file and directory names (--scan-filenames); cheap enough
+        // to recompute on every walk, so they are never cached
+        if let Some(name_pattern) = &name_pattern {
+            if entry.depth() > 0 {
+                items.extend(scan_path_name(
+                    &relative_path.to_string_lossy(),
+                    name_pattern,
+                ));
+            }
+        }
+
+        if !is_file {
+            continue;
+        }
 
         seen_paths.insert(relative_path.clone());
 
@@ -425,6 +1192,21 @@ pub fn scan_directory_cached(
             Err(_) => continue,
         };
         if should_skip_file(&metadata, MAX_FILE_SIZE) {
+            // Streamed files are never cached: the content hash would need a full read
+            if !options.scan_large {
+                continue;
+            }
+            let relative_str = relative_path.to_string_lossy();
+            if let Some(result) = scan_large_file(path, &relative_str, &pattern, &options) {
+                if !result.items.is_empty() {
+                    files_with_items += 1;
+                }
+                items.extend(result.items);
+                items.count_dropped(result.dropped);
+                ignored_items.extend(result.ignored_items);
+                files_scanned += 1;
+                bytes_scanned += metadata.len();
+            }
             continue;
         }
 
@@ -434,9 +1216,13 @@ pub fn scan_directory_cached(
             .unwrap_or(std::time::SystemTime::UNIX_EPOCH);
 
         if let Some(cached) = cache.check(&relative_path, mtime) {
+            if !cached.items.is_empty() {
+                files_with_items += 1;
+            }
             items.extend(cached.items.iter().cloned());
             ignored_items.extend(cached.ignored_items.iter().cloned());
             files_scanned += 1;
+            bytes_scanned += metadata.len();
             cache_hits += 1;
             continue;
         }
@@ -466,16 +1252,20 @@ pub fn scan_directory_cached(
                 cloned_ignored.clone(),
                 mtime,
             );
+            if !cloned_items.is_empty() {
+                files_with_items += 1;
+            }
             items.extend(cloned_items);
             ignored_items.extend(cloned_ignored);
             files_scanned += 1;
+            bytes_scanned += content_bytes.len() as u64;
             cache_hits += 1;
             continue;
         }
 
         // Cache miss: full scan
         let relative_str = relative_path.to_string_lossy().to_string();
-        let result = scan_content(&content, &relative_str, &pattern);
+        let result = scan_file_content(path, &content, &relative_str, &pattern, &options);
         let content_hash = *blake3::hash(content_bytes).as_bytes();
         cache.insert(
             relative_path,
@@ -484,26 +1274,151 @@ pub fn scan_directory_cached(
             result.ignored_items.clone(),
             mtime,
         );
+        if !result.items.is_empty() {
+            files_with_items += 1;
+        }
         items.extend(result.items);
+        items.count_dropped(result.dropped);
         ignored_items.extend(result.ignored_items);
         files_scanned += 1;
+        bytes_scanned += content.len() as u64;
         cache_misses += 1;
     }
 
     // Prune deleted files
     cache.prune(&seen_paths);
+    let (items, truncated, total_found) = items.finish();
 
     Ok(CachedScanResult {
         result: ScanResult {
             items,
             ignored_items,
             files_scanned,
+            files_with_items,
+            bytes_scanned,
+            truncated,
+            total_found,
         },
         cache_hits,
         cache_misses,
     })
 }
 
+/// Scan one file named on the command line, reporting items under `label`
+/// (the path as given). Exclusion settings do not apply since the file was
+/// asked for explicitly; files over `MAX_FILE_SIZE` are stream-scanned and
+/// non-text files yield no items.
+pub fn scan_file(path: &Path, label: &str, config: &Config) -> Result<ScanResult> {
+    let pattern = Regex::new(&config.tags_pattern())?;
+    let options = ScanOptions::from_config(config);
+    let metadata = std::fs::metadata(path)?;
+
+    let result = if should_skip_file(&metadata, MAX_FILE_SIZE) {
+        scan_large_file(path, label, &pattern, &options)
+    } else {
+        std::fs::read_to_string(path)
+            .ok()
+            .map(|content| scan_file_content(path, &content, label, &pattern, &options))
+    }
+    .unwrap_or_default();
+
+    let mut items = ItemCollector::new(config.max_items);
+    let files_with_items = usize::from(!result.items.is_empty());
+    items.extend(result.items);
+    items.count_dropped(result.dropped);
+    let (items, truncated, total_found) = items.finish();
+    Ok(ScanResult {
+        items,
+        ignored_items: result.ignored_items,
+        files_scanned: 1,
+        files_with_items,
+        bytes_scanned: metadata.len(),
+        truncated,
+        total_found,
+    })
+}
+
+/// Scan the staged (git index) version of every tracked file under `root`,
+/// as read from the index with a single `git cat-file --batch` process,
+/// instead of the working tree.
+///
+/// `exclude_dirs` and `exclude_patterns` apply to the paths as usual; files
+/// that aren't valid UTF-8 are skipped.
+pub fn scan_staged(root: &Path, config: &Config) -> Result<ScanResult> {
+    anyhow::ensure!(
+        git_command(&["rev-parse", "--is-inside-work-tree"], root).is_ok(),
+        "--staged requires a git repository: {}",
+        root.display()
+    );
+
+    let pattern = Regex::new(&config.tags_pattern())?;
+    let options = ScanOptions::from_config(config);
+    let exclude_regexes: Vec<Regex> = config
+        .exclude_patterns
+        .iter()
+        .filter_map(|p| Regex::new(p).ok())
+        .collect();
+
+    // `<mode> <oid> <stage>\t<path>`; paths are relative to `root`
+    let listing = git_command(&["ls-files", "-z", "--cached", "--stage"], root)?;
+    let mut staged: Vec<(&str, &str)> = Vec::new();
+    for entry in listing.split('\0').filter(|e| !e.is_empty()) {
+        let Some((meta, path)) = entry.split_once('\t') else {
+            continue;
+        };
+        let mut meta = meta.split_whitespace();
+        let (Some(mode), Some(oid), Some(stage)) = (meta.next(), meta.next(), meta.next()) else {
+            continue;
+        };
+        // Skip submodules and unmerged entries, which have no single blob
+        if mode == "160000" || stage != "0" {
+            continue;
+        }
+
+        let excluded_dir = in_excluded_dir(Path::new(path), &config.exclude_dirs);
+        let excluded_pattern = exclude_regexes
+            .iter()
+            .any(|re| re.is_match(&root.join(path).to_string_lossy()));
+        if !excluded_dir && !excluded_pattern {
+            staged.push((oid, path));
+        }
+    }
+
+    let mut items = ItemCollector::new(config.max_items);
+    let mut ignored_items = Vec::new();
+    let mut files_scanned = 0;
+    let mut files_with_items = 0;
+    let mut bytes_scanned = 0;
+
+    let oids: Vec<&str> = staged.iter().map(|(oid, _)| *oid).collect();
+    cat_file_batch(&oids, root, |index, blob| {
+        let Ok(content) = std::str::from_utf8(blob) else {
+            return;
+        };
+        let path = staged[index].1;
+        let result = scan_file_content(&root.join(path), content, path, &pattern, &options);
+        if !result.items.is_empty() {
+            files_with_items += 1;
+        }
+        items.extend(result.items);
+        items.count_dropped(result.dropped);
+        ignored_items.extend(result.ignored_items);
+        files_scanned += 1;
+        bytes_scanned += content.len() as u64;
+    })?;
+
+    let (items, truncated, total_found) = items.finish();
+    Ok(ScanResult {
+        items,
+        ignored_items,
+        files_scanned,
+        files_with_items,
+        bytes_scanned,
+        truncated,
+        total_found,
+    })
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -517,7 +1432,7 @@ mod tests {
     fn test_basic_todo_detection() {
         let pattern = default_pattern();
         let content = "// TODO: implement this feature\n";
-        let result = scan_content(content, "test.rs", &pattern);
+        let result = scan_content(content, "test.rs", &pattern, &ScanOptions::default());
 
         assert_eq!(result.items.len(), 1);
         assert_eq!(result.items[0].tag, Tag::Todo);
@@ -532,7 +1447,7 @@ mod tests {
     fn test_fixme_with_author() {
         let pattern = default_pattern();
         let content = "// FIXME(alice): broken parsing logic\n";
-        let result = scan_content(content, "lib.rs", &pattern);
+        let result = scan_content(content, "lib.rs", &pattern, &ScanOptions::default());
 
         assert_eq!(result.items.len(), 1);
         assert_eq!(result.items[0].tag, Tag::Fixme);
@@ -544,7 +1459,7 @@ mod tests {
     fn test_priority_high() {
         let pattern = default_pattern();
         let content = "# TODO: ! fix memory leak\n";
-        let result = scan_content(content, "main.py", &pattern);
+        let result = scan_content(content, "main.py", &pattern, &ScanOptions::default());
 
         assert_eq!(result.items.len(), 1);
         assert_eq!(result.items[0].priority, Priority::High);
@@ -554,7 +1469,7 @@ mod tests {
     fn test_priority_urgent() {
         let pattern = default_pattern();
         let content = "// BUG: !! crashes on empty input\n";
-        let result = scan_content(content, "app.rs", &pattern);
+        let result = scan_content(content, "app.rs", &pattern, &ScanOptions::default());
 
         assert_eq!(result.items.len(), 1);
         assert_eq!(result.items[0].tag, Tag::Bug);
@@ -565,7 +1480,7 @@ mod tests {
     fn test_issue_ref_hash() {
         let pattern = default_pattern();
         let content = "// TODO: fix layout issue #123\n";
-        let result = scan_content(content, "ui.rs", &pattern);
+        let result = scan_content(content, "ui.rs", &pattern, &ScanOptions::default());
 
         assert_eq!(result.items.len(), 1);
         assert_eq!(result.items[0].issue_ref.as_deref(), Some("#123"));
@@ -575,7 +1490,7 @@ mod tests {
     fn test_issue_ref_jira() {
         let pattern = default_pattern();
         let content = "// FIXME: address JIRA-456 regression\n";
-        let result = scan_content(content, "api.rs", &pattern);
+        let result = scan_content(content, "api.rs", &pattern, &ScanOptions::default());
 
         assert_eq!(result.items.len(), 1);
         assert_eq!(result.items[0].issue_ref.as_deref(), Some("JIRA-456"));
@@ -585,7 +1500,7 @@ mod tests {
     fn test_case_insensitivity() {
         let pattern = default_pattern();
         let content = "// todo: lowercase tag\n// Todo: mixed case\n// TODO: uppercase\n";
-        let result = scan_content(content, "test.rs", &pattern);
+        let result = scan_content(content, "test.rs", &pattern, &ScanOptions::default());
 
         assert_eq!(result.items.len(), 3);
         for item in &result.items {
@@ -593,6 +1508,20 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_case_sensitive_tags_skip_lowercase() {
+        let config = Config {
+            case_insensitive_tags: Some(false),
+            ..Config::default()
+        };
+        let pattern = Regex::new(&config.tags_pattern()).unwrap();
+        let content = "// todo: lowercase tag\n// Note: prose\n// TODO: uppercase\n";
+        let result = scan_content(content, "test.rs", &pattern, &ScanOptions::default());
+
+        assert_eq!(result.items.len(), 1);
+        assert_eq!(result.items[0].line, 3);
+    }
+
     #[test]
     fn test_multiple_tags_in_content() {
         let pattern = default_pattern();
@@ -603,7 +1532,7 @@ fn foo() {}
 // HACK: workaround for upstream bug
 // NOTE: remember to update docs
 ";
-        let result = scan_content(content, "multi.rs", &pattern);
+        let result = scan_content(content, "multi.rs", &pattern, &ScanOptions::default());
 
         assert_eq!(result.items.len(), 4);
         assert_eq!(result.items[0].tag, Tag::Todo);
@@ -623,7 +1552,7 @@ line three
 line four
 // FIXME: on line five
 ";
-        let result = scan_content(content, "lines.rs", &pattern);
+        let result = scan_content(content, "lines.rs", &pattern, &ScanOptions::default());
 
         assert_eq!(result.items.len(), 2);
         assert_eq!(result.items[0].line, 2);
@@ -634,7 +1563,7 @@ line four
     fn test_xxx_tag() {
         let pattern = default_pattern();
         let content = "// XXX: dangerous code path\n";
-        let result = scan_content(content, "danger.rs", &pattern);
+        let result = scan_content(content, "danger.rs", &pattern, &ScanOptions::default());
 
         assert_eq!(result.items.len(), 1);
         assert_eq!(result.items[0].tag, Tag::Xxx);
@@ -644,7 +1573,7 @@ line four
     fn test_no_match_on_plain_text() {
         let pattern = default_pattern();
         let content = "This is just a regular comment with no tags.\n";
-        let result = scan_content(content, "plain.rs", &pattern);
+        let result = scan_content(content, "plain.rs", &pattern, &ScanOptions::default());
 
         assert!(result.items.is_empty());
     }
@@ -653,7 +1582,7 @@ line four
     fn test_author_with_special_chars() {
         let pattern = default_pattern();
         let content = "// TODO(user@domain.com): email-style author\n";
-        let result = scan_content(content, "test.rs", &pattern);
+        let result = scan_content(content, "test.rs", &pattern, &ScanOptions::default());
 
         assert_eq!(result.items.len(), 1);
         assert_eq!(result.items[0].author.as_deref(), Some("user@domain.com"));
@@ -675,7 +1604,7 @@ line four
     fn test_no_match_in_identifier() {
         let pattern = default_pattern();
         let content = "let service = TodoService::new();\n";
-        let result = scan_content(content, "test.rs", &pattern);
+        let result = scan_content(content, "test.rs", &pattern, &ScanOptions::default());
         assert!(
             result.items.is_empty(),
             "should not match TODO inside identifier"
@@ -686,7 +1615,7 @@ line four
     fn test_no_match_in_camel_case() {
         let pattern = default_pattern();
         let content = "if isTodoCompleted() { return; }\n";
-        let result = scan_content(content, "test.rs", &pattern);
+        let result = scan_content(content, "test.rs", &pattern, &ScanOptions::default());
         assert!(
             result.items.is_empty(),
             "should not match Todo in camelCase"
@@ -697,7 +1626,7 @@ line four
     fn test_no_match_in_string_literal() {
         let pattern = default_pattern();
         let content = "let msg = \"TODO: not a real comment\";\n";
-        let result = scan_content(content, "test.rs", &pattern);
+        let result = scan_content(content, "test.rs", &pattern, &ScanOptions::default());
         assert!(
             result.items.is_empty(),
             "should not match TODO inside string literal"
@@ -708,7 +1637,7 @@ line four
     fn test_no_match_in_plain_code() {
         let pattern = default_pattern();
         let content = "let todo_count = get_todos().len();\n";
-        let result = scan_content(content, "test.rs", &pattern);
+        let result = scan_content(content, "test.rs", &pattern, &ScanOptions::default());
         assert!(
             result.items.is_empty(),
             "should not match todo in variable name"
@@ -719,7 +1648,7 @@ line four
     fn test_no_match_enum_variant() {
         let pattern = default_pattern();
         let content = "enum State { Todo, InProgress, Done }\n";
-        let result = scan_content(content, "test.rs", &pattern);
+        let result = scan_content(content, "test.rs", &pattern, &ScanOptions::default());
         assert!(
             result.items.is_empty(),
             "should not match Todo enum variant"
@@ -730,7 +1659,7 @@ line four
     fn test_no_match_struct_name() {
         let pattern = default_pattern();
         let content = "struct TodoItem { title: String }\n";
-        let result = scan_content(content, "test.rs", &pattern);
+        let result = scan_content(content, "test.rs", &pattern, &ScanOptions::default());
         assert!(
             result.items.is_empty(),
             "should not match Todo in struct name"
@@ -741,7 +1670,7 @@ line four
     fn test_no_match_comment_prefix_in_string_literal() {
         let pattern = default_pattern();
         let content = r#"let s = "// TODO: not real";"#;
-        let result = scan_content(content, "test.rs", &pattern);
+        let result = scan_content(content, "test.rs", &pattern, &ScanOptions::default());
         assert!(
             result.items.is_empty(),
             "should not match TODO when // is inside a string literal"
@@ -752,7 +1681,7 @@ line four
     fn test_no_match_hash_prefix_in_string_literal() {
         let pattern = default_pattern();
         let content = r##"let s = "# TODO: not real";"##;
-        let result = scan_content(content, "test.py", &pattern);
+        let result = scan_content(content, "test.py", &pattern, &ScanOptions::default());
         assert!(
             result.items.is_empty(),
             "should not match TODO when # is inside a string literal"
@@ -763,7 +1692,7 @@ line four
     fn test_match_real_comment_after_quoted_prefix() {
         let pattern = default_pattern();
         let content = r#""//"; // TODO: fix this"#;
-        let result = scan_content(content, "test.rs", &pattern);
+        let result = scan_content(content, "test.rs", &pattern, &ScanOptions::default());
         assert_eq!(
             result.items.len(),
             1,
@@ -778,7 +1707,7 @@ line four
     fn test_comment_double_slash() {
         let pattern = default_pattern();
         let content = "// TODO: rust/js/c++ style comment\n";
-        let result = scan_content(content, "test.rs", &pattern);
+        let result = scan_content(content, "test.rs", &pattern, &ScanOptions::default());
         assert_eq!(result.items.len(), 1);
     }
 
@@ -786,7 +1715,7 @@ line four
     fn test_comment_hash() {
         let pattern = default_pattern();
         let content = "# TODO: python/ruby/shell style comment\n";
-        let result = scan_content(content, "test.py", &pattern);
+        let result = scan_content(content, "test.py", &pattern, &ScanOptions::default());
         assert_eq!(result.items.len(), 1);
     }
 
@@ -794,7 +1723,7 @@ line four
     fn test_comment_block_start() {
         let pattern = default_pattern();
         let content = "/* TODO: c-style block comment */\n";
-        let result = scan_content(content, "test.c", &pattern);
+        let result = scan_content(content, "test.c", &pattern, &ScanOptions::default());
         assert_eq!(result.items.len(), 1);
     }
 
@@ -802,7 +1731,7 @@ line four
     fn test_comment_block_middle_star() {
         let pattern = default_pattern();
         let content = " * TODO: middle of block comment\n";
-        let result = scan_content(content, "test.java", &pattern);
+        let result = scan_content(content, "test.java", &pattern, &ScanOptions::default());
         assert_eq!(result.items.len(), 1);
     }
 
@@ -810,7 +1739,7 @@ line four
     fn test_comment_double_dash() {
         let pattern = default_pattern();
         let content = "-- TODO: sql/haskell style comment\n";
-        let result = scan_content(content, "test.sql", &pattern);
+        let result = scan_content(content, "test.sql", &pattern, &ScanOptions::default());
         assert_eq!(result.items.len(), 1);
     }
 
@@ -818,7 +1747,7 @@ line four
     fn test_comment_percent() {
         let pattern = default_pattern();
         let content = "% TODO: latex/erlang style comment\n";
-        let result = scan_content(content, "test.erl", &pattern);
+        let result = scan_content(content, "test.erl", &pattern, &ScanOptions::default());
         assert_eq!(result.items.len(), 1);
     }
 
@@ -826,7 +1755,7 @@ line four
     fn test_comment_html() {
         let pattern = default_pattern();
         let content = "<!-- TODO: html comment -->\n";
-        let result = scan_content(content, "test.html", &pattern);
+        let result = scan_content(content, "test.html", &pattern, &ScanOptions::default());
         assert_eq!(result.items.len(), 1);
     }
 
@@ -834,7 +1763,7 @@ line four
     fn test_comment_semicolon() {
         let pattern = default_pattern();
         let content = "; TODO: lisp/asm style comment\n";
-        let result = scan_content(content, "test.lisp", &pattern);
+        let result = scan_content(content, "test.lisp", &pattern, &ScanOptions::default());
         assert_eq!(result.items.len(), 1);
     }
 
@@ -842,7 +1771,7 @@ line four
     fn test_comment_ocaml_paren_star() {
         let pattern = default_pattern();
         let content = "(* TODO: ocaml/pascal style comment *)\n";
-        let result = scan_content(content, "test.ml", &pattern);
+        let result = scan_content(content, "test.ml", &pattern, &ScanOptions::default());
         assert_eq!(result.items.len(), 1);
     }
 
@@ -850,7 +1779,7 @@ line four
     fn test_comment_haskell_brace_dash() {
         let pattern = default_pattern();
         let content = "{- TODO: haskell block comment -}\n";
-        let result = scan_content(content, "test.hs", &pattern);
+        let result = scan_content(content, "test.hs", &pattern, &ScanOptions::default());
         assert_eq!(result.items.len(), 1);
     }
 
@@ -858,7 +1787,7 @@ line four
     fn test_indented_comment() {
         let pattern = default_pattern();
         let content = "    // TODO: indented with spaces\n\t# FIXME: indented with tab\n";
-        let result = scan_content(content, "test.rs", &pattern);
+        let result = scan_content(content, "test.rs", &pattern, &ScanOptions::default());
         assert_eq!(result.items.len(), 2);
     }
 
@@ -866,7 +1795,7 @@ line four
     fn test_inline_comment() {
         let pattern = default_pattern();
         let content = "let x = 42; // TODO: fix this value\n";
-        let result = scan_content(content, "test.rs", &pattern);
+        let result = scan_content(content, "test.rs", &pattern, &ScanOptions::default());
         assert_eq!(result.items.len(), 1);
         assert_eq!(result.items[0].message, "fix this value");
     }
@@ -975,42 +1904,336 @@ line four
             .unwrap();
         }
 
-        let config = Config::default();
-        let result = scan_directory(dir.path(), &config).unwrap();
+        let config = Config::default();
+        let result = scan_directory(dir.path(), &config).unwrap();
+
+        assert_eq!(result.items.len(), 10);
+        assert_eq!(result.files_scanned, 10);
+    }
+
+    #[test]
+    fn test_scan_directory_exclude_dirs() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(dir.path().join("keep.rs"), "// TODO: keep this\n").unwrap();
+        let vendor = dir.path().join("vendor");
+        std::fs::create_dir(&vendor).unwrap();
+        std::fs::write(vendor.join("skip.rs"), "// TODO: skip this\n").unwrap();
+
+        let config = Config {
+            exclude_dirs: vec!["vendor".to_string()],
+            ..Config::default()
+        };
+        let result = scan_directory(dir.path(), &config).unwrap();
+
+        assert_eq!(result.items.len(), 1);
+        assert_eq!(result.items[0].message, "keep this");
+    }
+
+    #[test]
+    fn test_scan_directory_max_items_truncates_but_counts() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(dir.path().join("flood.rs"), "// TODO\n".repeat(500)).unwrap();
+
+        let config = Config {
+            max_items: Some(50),
+            ..Config::default()
+        };
+        let result = scan_directory(dir.path(), &config).unwrap();
+        assert_eq!(result.items.len(), 50);
+        assert!(result.truncated);
+        assert_eq!(result.total_found, Some(500));
+
+        let mut cache = ScanCache::new(ScanCache::config_hash(&config));
+        let cached = scan_directory_cached(dir.path(), &config, &mut cache).unwrap();
+        assert_eq!(cached.result.items.len(), 50);
+        assert_eq!(cached.result.total_found, Some(500));
+    }
+
+    #[test]
+    fn test_scan_content_caps_items_per_file() {
+        let pattern = default_pattern();
+        let options = ScanOptions {
+            max_items: Some(3),
+            ..ScanOptions::default()
+        };
+        let content = "// TODO: a\n".repeat(10);
+        let result = scan_content(&content, "a.rs", &pattern, &options);
+        let lines: Vec<usize> = result.items.iter().map(|i| i.line).collect();
+        assert_eq!(lines, vec![1, 2, 3]);
+        assert_eq!(result.dropped, 7);
+
+        let streamed = scan_reader(content.as_bytes(), "a.rs", &pattern, &options).unwrap();
+        assert_eq!(streamed.items.len(), 3);
+        assert_eq!(streamed.dropped, 7);
+    }
+
+    #[test]
+    fn test_max_items_keeps_first_items_by_file_and_line() {
+        let dir = tempfile::tempdir().unwrap();
+        for name in ["d.rs", "b.rs", "a.rs", "c.rs", "e.rs"] {
+            std::fs::write(dir.path().join(name), "// TODO: x\n// TODO: y\n").unwrap();
+        }
+        let config = Config {
+            max_items: Some(3),
+            ..Config::default()
+        };
+        let kept = |items: &[TodoItem]| -> Vec<(String, usize)> {
+            items.iter().map(|i| (i.file.clone(), i.line)).collect()
+        };
+        let expected = vec![
+            ("a.rs".to_string(), 1),
+            ("a.rs".to_string(), 2),
+            ("b.rs".to_string(), 1),
+        ];
+
+        let result = scan_directory(dir.path(), &config).unwrap();
+        assert_eq!(kept(&result.items), expected);
+        assert_eq!(result.total_found, Some(10));
+
+        let mut cache = ScanCache::new(ScanCache::config_hash(&config));
+        let cached = scan_directory_cached(dir.path(), &config, &mut cache).unwrap();
+        assert_eq!(kept(&cached.result.items), expected);
+    }
+
+    #[test]
+    fn test_scan_directory_under_max_items_not_truncated() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(dir.path().join("a.rs"), "// TODO: one\n// FIXME: two\n").unwrap();
+
+        let config = Config {
+            max_items: Some(2),
+            ..Config::default()
+        };
+        let result = scan_directory(dir.path(), &config).unwrap();
+        assert_eq!(result.items.len(), 2);
+        assert!(!result.truncated);
+        assert_eq!(result.total_found, None);
+    }
+
+    /// A tree with `src/a.rs`, a `src/loop -> ..` cycle and an `alias -> src`
+    /// second route to the same directory.
+    #[cfg(unix)]
+    fn symlinked_tree() -> tempfile::TempDir {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::create_dir(dir.path().join("src")).unwrap();
+        std::fs::write(dir.path().join("src/a.rs"), "// TODO: once\n").unwrap();
+        std::os::unix::fs::symlink("..", dir.path().join("src/loop")).unwrap();
+        std::os::unix::fs::symlink("src", dir.path().join("alias")).unwrap();
+        dir
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_scan_directory_does_not_follow_symlinks_by_default() {
+        let dir = symlinked_tree();
+        let config = Config::default();
+
+        let result = scan_directory(dir.path(), &config).unwrap();
+        assert_eq!(result.items.len(), 1);
+        assert_eq!(result.items[0].file, "src/a.rs");
+
+        let mut cache = ScanCache::new(ScanCache::config_hash(&config));
+        let cached = scan_directory_cached(dir.path(), &config, &mut cache).unwrap();
+        assert_eq!(cached.result.items.len(), 1);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_scan_directory_follow_symlinks_visits_each_dir_once() {
+        let dir = symlinked_tree();
+        let config = Config {
+            follow_symlinks: Some(true),
+            ..Config::default()
+        };
+
+        // Terminates despite the cycle and reports the file once, via
+        // whichever route reached `src` first
+        let result = scan_directory(dir.path(), &config).unwrap();
+        assert_eq!(result.items.len(), 1);
+        assert!(result.items[0].file.ends_with("a.rs"));
+
+        let mut cache = ScanCache::new(ScanCache::config_hash(&config));
+        let cached = scan_directory_cached(dir.path(), &config, &mut cache).unwrap();
+        assert_eq!(cached.result.items.len(), 1);
+    }
+
+    #[test]
+    fn test_scan_directory_files_scanned_count() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(dir.path().join("with_todo.rs"), "// TODO: has todo\n").unwrap();
+        std::fs::write(dir.path().join("no_todo.rs"), "fn main() {}\n").unwrap();
+
+        let config = Config::default();
+        let result = scan_directory(dir.path(), &config).unwrap();
+
+        assert_eq!(result.items.len(), 1);
+        assert_eq!(result.files_scanned, 2);
+    }
+
+    #[test]
+    fn test_scan_directory_skips_gitignored_files() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(dir.path().join(".gitignore"), "generated.rs\n").unwrap();
+        std::fs::write(dir.path().join("generated.rs"), "// TODO: ignored\n").unwrap();
+        std::fs::write(dir.path().join("main.rs"), "// TODO: kept\n").unwrap();
+
+        let result = scan_directory(dir.path(), &Config::default()).unwrap();
+
+        assert_eq!(result.items.len(), 1);
+        assert_eq!(result.items[0].file, "main.rs");
+    }
+
+    #[test]
+    fn test_scan_directory_respect_gitignore_disabled() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(dir.path().join(".gitignore"), "generated.rs\n").unwrap();
+        std::fs::write(dir.path().join("generated.rs"), "// TODO: ignored\n").unwrap();
+        std::fs::write(dir.path().join("main.rs"), "// TODO: kept\n").unwrap();
+
+        let config = Config {
+            respect_gitignore: Some(false),
+            ..Config::default()
+        };
+        let result = scan_directory(dir.path(), &config).unwrap();
+
+        assert_eq!(result.items.len(), 2);
+    }
+
+    #[test]
+    fn test_scan_directory_excludes_compose_with_disabled_gitignore() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(dir.path().join(".gitignore"), "generated.rs\n").unwrap();
+        std::fs::write(dir.path().join("generated.rs"), "// TODO: unignored\n").unwrap();
+        std::fs::create_dir(dir.path().join("vendor")).unwrap();
+        std::fs::write(dir.path().join("vendor/lib.rs"), "// TODO: vendored\n").unwrap();
+        std::fs::write(dir.path().join("skip.min.js"), "// TODO: minified\n").unwrap();
+
+        let config = Config {
+            respect_gitignore: Some(false),
+            exclude_dirs: vec!["vendor".to_string()],
+            exclude_patterns: vec![r"\.min\.js$".to_string()],
+            ..Config::default()
+        };
+        let result = scan_directory(dir.path(), &config).unwrap();
+
+        assert_eq!(result.items.len(), 1);
+        assert_eq!(result.items[0].file, "generated.rs");
+    }
+
+    // --- scan_filenames ---
+
+    #[test]
+    fn test_scan_path_name_matches_tag_segment() {
+        let config = Config {
+            scan_filenames: Some(true),
+            ..Config::default()
+        };
+        let pattern = filename_pattern(&config).unwrap().unwrap();
+
+        let item = scan_path_name("src/TODO_rename_this.rs", &pattern).unwrap();
+        assert_eq!(item.tag, Tag::Todo);
+        assert_eq!(item.line, 0);
+        assert_eq!(item.message, "src/TODO_rename_this.rs");
 
-        assert_eq!(result.items.len(), 10);
-        assert_eq!(result.files_scanned, 10);
+        assert_eq!(
+            scan_path_name("fixme-later", &pattern).map(|i| i.tag),
+            Some(Tag::Fixme)
+        );
+        // Only the last component counts, and tags must be whole segments
+        assert!(scan_path_name("TODO/main.rs", &pattern).is_none());
+        assert!(scan_path_name("notes.rs", &pattern).is_none());
+        assert!(scan_path_name("todoist.rs", &pattern).is_none());
     }
 
     #[test]
-    fn test_scan_directory_exclude_dirs() {
+    fn test_filename_pattern_off_by_default() {
+        assert!(filename_pattern(&Config::default()).unwrap().is_none());
+    }
+
+    #[test]
+    fn test_scan_directory_reports_tagged_file_names() {
         let dir = tempfile::tempdir().unwrap();
-        std::fs::write(dir.path().join("keep.rs"), "// TODO: keep this\n").unwrap();
-        let vendor = dir.path().join("vendor");
-        std::fs::create_dir(&vendor).unwrap();
-        std::fs::write(vendor.join("skip.rs"), "// TODO: skip this\n").unwrap();
+        std::fs::create_dir(dir.path().join("HACK_tmp")).unwrap();
+        std::fs::write(
+            dir.path().join("HACK_tmp/TODO_rename_this.rs"),
+            "fn a() {}\n",
+        )
+        .unwrap();
+        std::fs::write(dir.path().join("main.rs"), "fn main() {}\n").unwrap();
+
+        let result = scan_directory(dir.path(), &Config::default()).unwrap();
+        assert!(result.items.is_empty());
 
         let config = Config {
-            exclude_dirs: vec!["vendor".to_string()],
+            scan_filenames: Some(true),
             ..Config::default()
         };
+        for result in [
+            scan_directory(dir.path(), &config).unwrap(),
+            scan_directory_cached(dir.path(), &config, &mut ScanCache::new([0; 32]))
+                .unwrap()
+                .result,
+        ] {
+            let mut found: Vec<_> = result
+                .items
+                .iter()
+                .map(|i| (i.file.as_str(), i.line, i.tag))
+                .collect();
+            found.sort_by_key(|(file, _, _)| *file);
+            assert_eq!(
+                found,
+                vec![
+                    ("HACK_tmp", 0, Tag::Hack),
+                    ("HACK_tmp/TODO_rename_this.rs", 0, Tag::Todo),
+                ]
+            );
+        }
+    }
+
+    #[test]
+    fn test_bytes_scanned_is_sum_of_file_sizes() {
+        let dir = tempfile::tempdir().unwrap();
+        let files = [
+            ("a.rs", "// TODO: first\nfn a() {}\n"),
+            ("b.py", "# FIXME: second\n"),
+            ("c.txt", "no tags here\n"),
+        ];
+        for (name, content) in files {
+            std::fs::write(dir.path().join(name), content).unwrap();
+        }
+        let expected: u64 = files.iter().map(|(_, c)| c.len() as u64).sum();
+
+        let config = Config::default();
         let result = scan_directory(dir.path(), &config).unwrap();
+        assert_eq!(result.bytes_scanned, expected);
 
-        assert_eq!(result.items.len(), 1);
-        assert_eq!(result.items[0].message, "keep this");
+        // Cache misses and cache hits both contribute their file sizes
+        let mut cache = ScanCache::new(ScanCache::config_hash(&config));
+        let cold = scan_directory_cached(dir.path(), &config, &mut cache).unwrap();
+        let warm = scan_directory_cached(dir.path(), &config, &mut cache).unwrap();
+        assert_eq!(cold.result.bytes_scanned, expected);
+        assert_eq!(warm.result.bytes_scanned, expected);
     }
 
     #[test]
-    fn test_scan_directory_files_scanned_count() {
+    fn test_scan_directory_files_with_items_matches_distinct_files() {
         let dir = tempfile::tempdir().unwrap();
-        std::fs::write(dir.path().join("with_todo.rs"), "// TODO: has todo\n").unwrap();
-        std::fs::write(dir.path().join("no_todo.rs"), "fn main() {}\n").unwrap();
+        std::fs::write(dir.path().join("a.rs"), "// TODO: one\n// FIXME: two\n").unwrap();
+        std::fs::write(dir.path().join("b.rs"), "// HACK: three\n").unwrap();
+        std::fs::write(dir.path().join("c.rs"), "fn main() {}\n").unwrap();
+        std::fs::write(
+            dir.path().join("d.rs"),
+            "// TODO: ignored todo-scan:ignore\n",
+        )
+        .unwrap();
 
         let config = Config::default();
         let result = scan_directory(dir.path(), &config).unwrap();
 
-        assert_eq!(result.items.len(), 1);
-        assert_eq!(result.files_scanned, 2);
+        let distinct: HashSet<&str> = result.items.iter().map(|i| i.file.as_str()).collect();
+        assert_eq!(result.files_scanned, 4);
+        assert_eq!(result.files_with_items, 2);
+        assert_eq!(result.files_with_items, distinct.len());
     }
 
     // --- parse_paren_content tests ---
@@ -1069,13 +2292,49 @@ line four
         assert!(deadline.is_none());
     }
 
+    #[test]
+    fn test_split_labels() {
+        assert_eq!(
+            split_labels("labels=frontend,perf"),
+            ("", vec!["frontend".to_string(), "perf".to_string()])
+        );
+        assert_eq!(
+            split_labels("alice, 2025-06-01, labels= ui , ,db"),
+            (
+                "alice, 2025-06-01",
+                vec!["ui".to_string(), "db".to_string()]
+            )
+        );
+        assert_eq!(split_labels("alice"), ("alice", vec![]));
+        // Only a standalone segment counts
+        assert_eq!(split_labels("mylabels=x"), ("mylabels=x", vec![]));
+    }
+
+    #[test]
+    fn test_scan_todo_with_labels() {
+        let pattern = default_pattern();
+        let content = "// TODO(labels=frontend,perf): lazy-load images\n\
+                       // TODO(alice, 2025-06-01, labels=backend): add index\n\
+                       // TODO: no labels\n";
+        let result = scan_content(content, "test.rs", &pattern, &ScanOptions::default());
+
+        assert_eq!(result.items.len(), 3);
+        assert_eq!(result.items[0].labels, vec!["frontend", "perf"]);
+        assert!(result.items[0].author.is_none());
+        assert_eq!(result.items[0].message, "lazy-load images");
+        assert_eq!(result.items[1].labels, vec!["backend"]);
+        assert_eq!(result.items[1].author.as_deref(), Some("alice"));
+        assert!(result.items[1].deadline.is_some());
+        assert!(result.items[2].labels.is_empty());
+    }
+
     // --- Scanning TODOs with dates ---
 
     #[test]
     fn test_scan_todo_with_date() {
         let pattern = default_pattern();
         let content = "// TODO(2025-06-01): finish this by June\n";
-        let result = scan_content(content, "test.rs", &pattern);
+        let result = scan_content(content, "test.rs", &pattern, &ScanOptions::default());
 
         assert_eq!(result.items.len(), 1);
         assert_eq!(result.items[0].tag, Tag::Todo);
@@ -1091,7 +2350,7 @@ line four
     fn test_scan_todo_with_author_and_date() {
         let pattern = default_pattern();
         let content = "// TODO(alice, 2025-06-01): finish this\n";
-        let result = scan_content(content, "test.rs", &pattern);
+        let result = scan_content(content, "test.rs", &pattern, &ScanOptions::default());
 
         assert_eq!(result.items.len(), 1);
         assert_eq!(result.items[0].author.as_deref(), Some("alice"));
@@ -1105,7 +2364,7 @@ line four
     fn test_scan_todo_with_quarter() {
         let pattern = default_pattern();
         let content = "// TODO(2025-Q4): year-end cleanup\n";
-        let result = scan_content(content, "test.rs", &pattern);
+        let result = scan_content(content, "test.rs", &pattern, &ScanOptions::default());
 
         assert_eq!(result.items.len(), 1);
         assert!(result.items[0].author.is_none());
@@ -1119,7 +2378,7 @@ line four
     fn test_scan_todo_author_only_still_works() {
         let pattern = default_pattern();
         let content = "// TODO(bob): no date here\n";
-        let result = scan_content(content, "test.rs", &pattern);
+        let result = scan_content(content, "test.rs", &pattern, &ScanOptions::default());
 
         assert_eq!(result.items.len(), 1);
         assert_eq!(result.items[0].author.as_deref(), Some("bob"));
@@ -1130,7 +2389,7 @@ line four
     fn test_scan_todo_no_parens_no_deadline() {
         let pattern = default_pattern();
         let content = "// TODO: plain task\n";
-        let result = scan_content(content, "test.rs", &pattern);
+        let result = scan_content(content, "test.rs", &pattern, &ScanOptions::default());
 
         assert_eq!(result.items.len(), 1);
         assert!(result.items[0].author.is_none());
@@ -1143,7 +2402,7 @@ line four
     fn test_no_match_todox_in_comment() {
         let pattern = default_pattern();
         let content = "// todox report generates HTML\n";
-        let result = scan_content(content, "test.rs", &pattern);
+        let result = scan_content(content, "test.rs", &pattern, &ScanOptions::default());
         assert!(
             result.items.is_empty(),
             "should not match TODO as prefix of 'todox'"
@@ -1154,7 +2413,7 @@ line four
     fn test_no_match_todo_scan_in_comment() {
         let pattern = default_pattern();
         let content = "// todo-scan report generates HTML\n";
-        let result = scan_content(content, "test.rs", &pattern);
+        let result = scan_content(content, "test.rs", &pattern, &ScanOptions::default());
         assert!(
             result.items.is_empty(),
             "should not match TODO as prefix of 'todo-scan'"
@@ -1165,7 +2424,7 @@ line four
     fn test_no_match_todos_in_comment() {
         let pattern = default_pattern();
         let content = "// TODOS remaining in the backlog\n";
-        let result = scan_content(content, "test.rs", &pattern);
+        let result = scan_content(content, "test.rs", &pattern, &ScanOptions::default());
         assert!(
             result.items.is_empty(),
             "should not match TODO as prefix of 'TODOS'"
@@ -1176,7 +2435,7 @@ line four
     fn test_no_match_noted_in_comment() {
         let pattern = default_pattern();
         let content = "# NOTEd this for future reference\n";
-        let result = scan_content(content, "test.rs", &pattern);
+        let result = scan_content(content, "test.rs", &pattern, &ScanOptions::default());
         assert!(
             result.items.is_empty(),
             "should not match NOTE as prefix of 'NOTEd'"
@@ -1187,7 +2446,7 @@ line four
     fn test_no_match_fixme_suffix_in_comment() {
         let pattern = default_pattern();
         let content = "// FIXMEd the issue yesterday\n";
-        let result = scan_content(content, "test.rs", &pattern);
+        let result = scan_content(content, "test.rs", &pattern, &ScanOptions::default());
         assert!(
             result.items.is_empty(),
             "should not match FIXME as prefix of 'FIXMEd'"
@@ -1200,7 +2459,7 @@ line four
     fn test_still_matches_todo_colon() {
         let pattern = default_pattern();
         let content = "// TODO: fix this\n";
-        let result = scan_content(content, "test.rs", &pattern);
+        let result = scan_content(content, "test.rs", &pattern, &ScanOptions::default());
         assert_eq!(result.items.len(), 1, "TODO: should still match");
     }
 
@@ -1208,7 +2467,7 @@ line four
     fn test_still_matches_todo_paren() {
         let pattern = default_pattern();
         let content = "// TODO(alice): fix this\n";
-        let result = scan_content(content, "test.rs", &pattern);
+        let result = scan_content(content, "test.rs", &pattern, &ScanOptions::default());
         assert_eq!(result.items.len(), 1, "TODO(author) should still match");
     }
 
@@ -1216,7 +2475,7 @@ line four
     fn test_still_matches_todo_space() {
         let pattern = default_pattern();
         let content = "// TODO fix this\n";
-        let result = scan_content(content, "test.rs", &pattern);
+        let result = scan_content(content, "test.rs", &pattern, &ScanOptions::default());
         assert_eq!(
             result.items.len(),
             1,
@@ -1228,7 +2487,7 @@ line four
     fn test_still_matches_todo_bang() {
         let pattern = default_pattern();
         let content = "// TODO! fix this\n";
-        let result = scan_content(content, "test.rs", &pattern);
+        let result = scan_content(content, "test.rs", &pattern, &ScanOptions::default());
         assert_eq!(result.items.len(), 1, "TODO! should still match");
     }
 
@@ -1271,6 +2530,7 @@ line four
         assert_eq!(result.result.items.len(), 2);
         assert_eq!(result.cache_hits, 2);
         assert_eq!(result.cache_misses, 0);
+        assert_eq!(result.result.files_with_items, 2);
     }
 
     #[test]
@@ -1371,7 +2631,7 @@ line four
     fn test_ignore_inline_suppresses_item() {
         let pattern = default_pattern();
         let content = "// TODO: keep this\n// TODO: suppress this todo-scan:ignore\n";
-        let result = scan_content(content, "test.rs", &pattern);
+        let result = scan_content(content, "test.rs", &pattern, &ScanOptions::default());
 
         assert_eq!(result.items.len(), 1);
         assert_eq!(result.items[0].message, "keep this");
@@ -1379,11 +2639,146 @@ line four
         assert_eq!(result.ignored_items[0].message, "suppress this");
     }
 
+    #[test]
+    fn test_string_literal_todo_is_ignored_with_reason() {
+        let pattern = default_pattern();
+        let content = "let s = \"TODO: fix this\";\n// TODO: real\n";
+        let result = scan_content(content, "test.rs", &pattern, &ScanOptions::default());
+
+        assert_eq!(result.items.len(), 1);
+        assert_eq!(result.items[0].message, "real");
+        assert_eq!(result.items[0].ignore_reason, None);
+        assert_eq!(result.ignored_items.len(), 1);
+        assert_eq!(result.ignored_items[0].line, 1);
+        assert_eq!(result.ignored_items[0].message, "fix this");
+        assert_eq!(
+            result.ignored_items[0].ignore_reason,
+            Some(IgnoreReason::StringLiteral)
+        );
+    }
+
+    #[test]
+    fn test_string_literal_todo_dropped_when_disabled() {
+        let pattern = default_pattern();
+        let content = "let s = \"TODO: fix this\";\n";
+        let options = ScanOptions {
+            string_literals_as_ignored: false,
+            ..ScanOptions::default()
+        };
+        let result = scan_content(content, "test.rs", &pattern, &options);
+        assert!(result.items.is_empty());
+        assert!(result.ignored_items.is_empty());
+    }
+
+    #[test]
+    fn test_comment_prefixes_override_per_extension() {
+        let pattern = default_pattern();
+        let content = "# TODO: heading\n<!-- TODO: hidden note -->\nSee above; TODO: prose\n";
+        let options = ScanOptions {
+            comment_prefixes: HashMap::from([("md".to_string(), vec!["<!--".to_string()])]),
+            ..ScanOptions::default()
+        };
+
+        let result = scan_content(content, "README.md", &pattern, &options);
+        assert_eq!(result.items.len(), 1);
+        assert_eq!(result.items[0].message, "hidden note");
+
+        // Other extensions keep the built-in prefixes
+        let result = scan_content(content, "notes.txt", &pattern, &options);
+        assert_eq!(result.items.len(), 3);
+    }
+
+    #[test]
+    fn test_permissive_unknown_controls_unlisted_extensions() {
+        let pattern = default_pattern();
+        let content = "# TODO: shell or config?
+";
+        let permissive = ScanOptions {
+            comment_prefixes: HashMap::from([("md".to_string(), vec!["<!--".to_string()])]),
+            ..ScanOptions::default()
+        };
+        let strict = ScanOptions {
+            permissive_unknown: false,
+            ..permissive.clone()
+        };
+
+        // Unknown extension (and no extension): built-in prefixes vs skipped
+        for path in ["deploy.dat", "Procfile"] {
+            let result = scan_content(content, path, &pattern, &permissive);
+            assert_eq!(result.items.len(), 1, "{path}");
+            let result = scan_content(content, path, &pattern, &strict);
+            assert!(result.items.is_empty(), "{path}");
+            assert!(result.ignored_items.is_empty(), "{path}");
+        }
+
+        // Extensions in the table are scanned either way
+        let result = scan_content("<!-- TODO: note -->\n", "a.md", &pattern, &strict);
+        assert_eq!(result.items.len(), 1);
+
+        // Known languages use their own prefixes: `#` is no comment in Rust
+        let result = scan_content(content, "main.rs", &pattern, &strict);
+        assert!(result.items.is_empty());
+        let content = "// TODO: a\n/*\n * TODO: b\n */\n";
+        let result = scan_content(content, "main.rs", &pattern, &strict);
+        assert_eq!(result.items.len(), 2);
+        let result = scan_content("# TODO: c\n", "deploy.conf", &pattern, &strict);
+        assert_eq!(result.items.len(), 1);
+        let result = scan_content("# TODO: d\n", "nb.ipynb", &pattern, &strict);
+        assert_eq!(result.items.len(), 1);
+    }
+
+    #[test]
+    fn test_resolved_marker_routes_item_to_ignored() {
+        let pattern = default_pattern();
+        let content = "// TODO[done]: ship it\n// TODO: [DONE] tidy up\n// TODO: still open\n";
+        let options = ScanOptions {
+            resolved_markers: vec!["[done]".to_string()],
+            ..ScanOptions::default()
+        };
+        let result = scan_content(content, "test.rs", &pattern, &options);
+
+        assert_eq!(result.items.len(), 1);
+        assert_eq!(result.items[0].message, "still open");
+        assert_eq!(result.items[0].ignore_reason, None);
+        let ignored: Vec<(&str, Option<IgnoreReason>)> = result
+            .ignored_items
+            .iter()
+            .map(|i| (i.message.as_str(), i.ignore_reason))
+            .collect();
+        assert_eq!(
+            ignored,
+            vec![
+                ("ship it", Some(IgnoreReason::Resolved)),
+                ("tidy up", Some(IgnoreReason::Resolved)),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_resolved_marker_off_by_default() {
+        let pattern = default_pattern();
+        let content = "// TODO[done]: ship it\n";
+        let result = scan_content(content, "test.rs", &pattern, &ScanOptions::default());
+        assert_eq!(result.items.len(), 1);
+        assert_eq!(result.items[0].message, "[done]: ship it");
+    }
+
+    #[test]
+    fn test_marker_ignored_item_has_marker_reason() {
+        let pattern = default_pattern();
+        let content = "// TODO: suppress this todo-scan:ignore\n";
+        let result = scan_content(content, "test.rs", &pattern, &ScanOptions::default());
+        assert_eq!(
+            result.ignored_items[0].ignore_reason,
+            Some(IgnoreReason::Marker)
+        );
+    }
+
     #[test]
     fn test_ignore_next_line_suppresses_following_item() {
         let pattern = default_pattern();
         let content = "// todo-scan:ignore-next-line\n// TODO: suppressed by next-line\n// TODO: not suppressed\n";
-        let result = scan_content(content, "test.rs", &pattern);
+        let result = scan_content(content, "test.rs", &pattern, &ScanOptions::default());
 
         assert_eq!(result.items.len(), 1);
         assert_eq!(result.items[0].message, "not suppressed");
@@ -1396,7 +2791,7 @@ line four
         let pattern = default_pattern();
         let content =
             "// todo-scan:ignore-next-line\n// TODO: suppressed\n// TODO: not suppressed\n";
-        let result = scan_content(content, "test.rs", &pattern);
+        let result = scan_content(content, "test.rs", &pattern, &ScanOptions::default());
 
         assert_eq!(result.items.len(), 1);
         assert_eq!(result.items[0].message, "not suppressed");
@@ -1407,7 +2802,7 @@ line four
     fn test_ignore_next_line_blank_line_between_does_not_suppress() {
         let pattern = default_pattern();
         let content = "// todo-scan:ignore-next-line\n\n// TODO: should not be suppressed\n";
-        let result = scan_content(content, "test.rs", &pattern);
+        let result = scan_content(content, "test.rs", &pattern, &ScanOptions::default());
 
         assert_eq!(result.items.len(), 1);
         assert_eq!(result.items[0].message, "should not be suppressed");
@@ -1424,7 +2819,7 @@ line four
 // HACK: normal hack
 // BUG: suppressed bug todo-scan:ignore
 ";
-        let result = scan_content(content, "test.rs", &pattern);
+        let result = scan_content(content, "test.rs", &pattern, &ScanOptions::default());
 
         assert_eq!(result.items.len(), 2);
         assert_eq!(result.items[0].message, "normal item");
@@ -1482,11 +2877,111 @@ line four
         assert_eq!(result.items[0].message, "keep this");
     }
 
+    #[test]
+    fn test_scan_large_streams_file_just_over_limit() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut big_content = "x".repeat(MAX_FILE_SIZE as usize);
+        big_content.push_str("\n// TODO: found at the end\n");
+        std::fs::write(dir.path().join("bindings.rs"), &big_content).unwrap();
+
+        let config = Config::default();
+        assert!(scan_directory(dir.path(), &config)
+            .unwrap()
+            .items
+            .is_empty());
+
+        let config = Config {
+            scan_large: Some(true),
+            ..Config::default()
+        };
+        for result in [
+            scan_directory(dir.path(), &config).unwrap(),
+            scan_directory_cached(dir.path(), &config, &mut ScanCache::new([0; 32]))
+                .unwrap()
+                .result,
+        ] {
+            assert_eq!(result.items.len(), 1);
+            assert_eq!(result.items[0].line, 2);
+            assert_eq!(result.items[0].message, "found at the end");
+            assert_eq!(result.bytes_scanned, big_content.len() as u64);
+        }
+    }
+
+    #[test]
+    fn test_scan_reader_matches_scan_content() {
+        let pattern = default_pattern();
+        let content = "// TODO: first line\n\
+                       //   continued\n\
+                       fn a() {}\n\
+                       // todo-scan:ignore-next-line\n\
+                       // FIXME: suppressed\n\
+                       // HACK: inline todo-scan:ignore\n\
+                       // BUG: one\n\
+                       // NOTE: two\n\
+                       //   more\n\
+                       //   and more\n\
+                       //   past the limit\n\
+                       let s = \"// TODO: in a string\";\n\
+                       // XXX: last line";
+        let summary = |items: &[TodoItem]| -> Vec<(usize, usize, String)> {
+            items
+                .iter()
+                .map(|i| (i.line, i.byte_offset, i.message.clone()))
+                .collect()
+        };
+
+        for continuation_lines in [0, 1, 3] {
+            let options = ScanOptions {
+                continuation_lines,
+                ..ScanOptions::default()
+            };
+            let whole = scan_content(content, "a.rs", &pattern, &options);
+            let streamed = scan_reader(content.as_bytes(), "a.rs", &pattern, &options).unwrap();
+            assert_eq!(summary(&streamed.items), summary(&whole.items));
+            assert_eq!(
+                summary(&streamed.ignored_items),
+                summary(&whole.ignored_items)
+            );
+        }
+    }
+
+    #[test]
+    fn test_byte_offset_points_at_tag() {
+        let pattern = default_pattern();
+        let content = "fn main() {\n    // TODO: first\n    let x = 1; // fixme(bob): second\n}\n";
+        let result = scan_content(content, "a.rs", &pattern, &ScanOptions::default());
+
+        assert_eq!(result.items.len(), 2);
+        assert_eq!(result.items[0].byte_offset, 19);
+        assert_eq!(&content[result.items[0].byte_offset..][..4], "TODO");
+        assert_eq!(&content[result.items[1].byte_offset..][..5], "fixme");
+    }
+
+    #[test]
+    fn test_byte_offset_with_crlf_and_streaming() {
+        let pattern = default_pattern();
+        let content = "// intro\r\n// TODO: a\r\n\r\n  # HACK: b\r\n";
+        let whole = scan_content(content, "a.py", &pattern, &ScanOptions::default());
+        let streamed = scan_reader(
+            content.as_bytes(),
+            "a.py",
+            &pattern,
+            &ScanOptions::default(),
+        )
+        .unwrap();
+
+        let offsets: Vec<usize> = whole.items.iter().map(|i| i.byte_offset).collect();
+        assert_eq!(offsets, vec![13, 28]);
+        assert_eq!(&content[28..32], "HACK");
+        let streamed: Vec<usize> = streamed.items.iter().map(|i| i.byte_offset).collect();
+        assert_eq!(streamed, offsets);
+    }
+
     #[test]
     fn test_ignore_no_items_affected_when_no_markers() {
         let pattern = default_pattern();
         let content = "// TODO: first\n// FIXME: second\n";
-        let result = scan_content(content, "test.rs", &pattern);
+        let result = scan_content(content, "test.rs", &pattern, &ScanOptions::default());
 
         assert_eq!(result.items.len(), 2);
         assert!(result.ignored_items.is_empty());
@@ -1496,7 +2991,7 @@ line four
     fn test_ignore_strips_marker_from_message() {
         let pattern = default_pattern();
         let content = "// TODO: fix this todo-scan:ignore\n";
-        let result = scan_content(content, "test.rs", &pattern);
+        let result = scan_content(content, "test.rs", &pattern, &ScanOptions::default());
 
         assert_eq!(result.ignored_items.len(), 1);
         assert_eq!(result.ignored_items[0].message, "fix this");
@@ -1783,7 +3278,7 @@ line four
         };
         let pattern = Regex::new(&config.tags_pattern()).unwrap();
         let content = "// WARN: this is a warning\n";
-        let result = scan_content(content, "test.rs", &pattern);
+        let result = scan_content(content, "test.rs", &pattern, &ScanOptions::default());
         // WARN matches the regex but Tag::from_str("WARN") returns Err,
         // so the item should be skipped (not included in results)
         assert!(
@@ -1819,7 +3314,7 @@ line four
         // Write binary content (invalid UTF-8)
         std::fs::write(
             dir.path().join("binary.dat"),
-            &[0xFF, 0xFE, 0x00, 0x01, 0x80, 0x81],
+            [0xFF, 0xFE, 0x00, 0x01, 0x80, 0x81],
         )
         .unwrap();
 
@@ -1856,6 +3351,25 @@ line four
         assert_eq!(result.result.items[0].message, "keep");
     }
 
+    #[test]
+    fn test_exclude_dirs_ignore_components_above_root() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path().join("vendor").join("proj");
+        std::fs::create_dir_all(root.join("vendor")).unwrap();
+        std::fs::write(root.join("keep.rs"), "// TODO: keep\n").unwrap();
+        std::fs::write(root.join("vendor/skip.rs"), "// TODO: skip\n").unwrap();
+
+        let config = Config::default();
+        let result = scan_directory(&root, &config).unwrap();
+        assert_eq!(result.items.len(), 1);
+        assert_eq!(result.items[0].message, "keep");
+
+        let mut cache = ScanCache::new(ScanCache::config_hash(&config));
+        let cached = scan_directory_cached(&root, &config, &mut cache).unwrap();
+        assert_eq!(cached.result.items.len(), 1);
+        assert_eq!(cached.result.items[0].message, "keep");
+    }
+
     // --- scan_directory_cached: exclude_patterns ---
 
     #[test]
@@ -1905,7 +3419,7 @@ line four
         let dir = tempfile::tempdir().unwrap();
         std::fs::write(dir.path().join("good.rs"), "// TODO: keep\n").unwrap();
         // Write binary content (invalid UTF-8)
-        std::fs::write(dir.path().join("binary.dat"), &[0xFF, 0xFE, 0x00, 0x01]).unwrap();
+        std::fs::write(dir.path().join("binary.dat"), [0xFF, 0xFE, 0x00, 0x01]).unwrap();
 
         let config = Config::default();
         let config_hash = ScanCache::config_hash(&config);
@@ -1956,7 +3470,7 @@ line four
     #[test]
     fn test_scan_content_empty() {
         let pattern = default_pattern();
-        let result = scan_content("", "empty.rs", &pattern);
+        let result = scan_content("", "empty.rs", &pattern, &ScanOptions::default());
         assert!(result.items.is_empty());
         assert!(result.ignored_items.is_empty());
     }
@@ -1967,7 +3481,7 @@ line four
     fn test_scan_content_no_matches() {
         let pattern = default_pattern();
         let content = "fn main() {\n    println!(\"hello\");\n}\n";
-        let result = scan_content(content, "main.rs", &pattern);
+        let result = scan_content(content, "main.rs", &pattern, &ScanOptions::default());
         assert!(result.items.is_empty());
     }
 
@@ -2004,7 +3518,7 @@ line four
         let pattern = default_pattern();
         // "todo-" should be skipped (e.g., "todo-scan" tool name)
         let content = "// todo-scan:ignore is a suppression marker\n";
-        let result = scan_content(content, "test.rs", &pattern);
+        let result = scan_content(content, "test.rs", &pattern, &ScanOptions::default());
         assert!(
             result.items.is_empty(),
             "tag followed by hyphen should be skipped"
@@ -2017,7 +3531,7 @@ line four
     fn test_scan_content_no_colon() {
         let pattern = default_pattern();
         let content = "// TODO fix this now\n";
-        let result = scan_content(content, "test.rs", &pattern);
+        let result = scan_content(content, "test.rs", &pattern, &ScanOptions::default());
         assert_eq!(result.items.len(), 1);
         assert_eq!(result.items[0].message, "fix this now");
     }
@@ -2028,7 +3542,7 @@ line four
     fn test_scan_content_empty_message() {
         let pattern = default_pattern();
         let content = "// TODO:\n";
-        let result = scan_content(content, "test.rs", &pattern);
+        let result = scan_content(content, "test.rs", &pattern, &ScanOptions::default());
         assert_eq!(result.items.len(), 1);
         assert_eq!(result.items[0].message, "");
     }
@@ -2048,4 +3562,225 @@ line four
         assert_eq!(result.items.len(), 1);
         assert_eq!(result.files_scanned, 3);
     }
+
+    fn joining_options() -> ScanOptions {
+        ScanOptions {
+            continuation_lines: 3,
+            ..ScanOptions::default()
+        }
+    }
+
+    #[test]
+    fn test_continuation_line_joined_into_message() {
+        let pattern = default_pattern();
+        let content = "// TODO: do the thing\n//   and also this\nfn main() {}\n";
+        let result = scan_content(content, "test.rs", &pattern, &joining_options());
+        assert_eq!(result.items.len(), 1);
+        assert_eq!(result.items[0].message, "do the thing and also this");
+        assert_eq!(result.items[0].line, 1);
+    }
+
+    #[test]
+    fn test_continuation_off_by_default() {
+        let pattern = default_pattern();
+        let content = "// TODO: do the thing\n//   and also this\n";
+        let result = scan_content(content, "test.rs", &pattern, &ScanOptions::default());
+        assert_eq!(result.items[0].message, "do the thing");
+        let result = scan_content(
+            content,
+            "test.rs",
+            &pattern,
+            &ScanOptions::from_config(&Config::default()),
+        );
+        assert_eq!(result.items[0].message, "do the thing");
+    }
+
+    #[test]
+    fn test_continuation_same_indent_comment_not_joined() {
+        let pattern = default_pattern();
+        let content = "// TODO: do the thing\n// unrelated comment\n";
+        let result = scan_content(content, "test.rs", &pattern, &joining_options());
+        assert_eq!(result.items[0].message, "do the thing");
+    }
+
+    #[test]
+    fn test_continuation_different_prefix_not_joined() {
+        let pattern = default_pattern();
+        let content = "# TODO: do the thing\n//   not a continuation\n";
+        let result = scan_content(content, "test.py", &pattern, &joining_options());
+        assert_eq!(result.items[0].message, "do the thing");
+    }
+
+    #[test]
+    fn test_continuation_stops_at_next_tag() {
+        let pattern = default_pattern();
+        let content = "// TODO: first\n//   FIXME: second\n";
+        let result = scan_content(content, "test.rs", &pattern, &joining_options());
+        assert_eq!(result.items.len(), 2);
+        assert_eq!(result.items[0].message, "first");
+        assert_eq!(result.items[1].message, "second");
+    }
+
+    #[test]
+    fn test_continuation_respects_max_lines() {
+        let pattern = default_pattern();
+        let content = "// TODO: a\n//   b\n//   c\n";
+        let options = ScanOptions {
+            continuation_lines: 1,
+            ..ScanOptions::default()
+        };
+        let result = scan_content(content, "test.rs", &pattern, &options);
+        assert_eq!(result.items[0].message, "a b");
+
+        let disabled = ScanOptions {
+            continuation_lines: 0,
+            ..ScanOptions::default()
+        };
+        let result = scan_content(content, "test.rs", &pattern, &disabled);
+        assert_eq!(result.items[0].message, "a");
+    }
+
+    #[test]
+    fn test_long_line_skipped_neighbors_scanned() {
+        let pattern = default_pattern();
+        let long = format!(
+            "var a=1;// TODO: minified {}\n",
+            "x".repeat(DEFAULT_MAX_LINE_LENGTH)
+        );
+        let content = format!("// TODO: before\n{long}// TODO: after\n");
+        let result = scan_content(&content, "app.min.js", &pattern, &ScanOptions::default());
+        let lines: Vec<usize> = result.items.iter().map(|i| i.line).collect();
+        assert_eq!(lines, vec![1, 3]);
+    }
+
+    #[test]
+    fn test_max_line_length_configurable_and_disabled_by_zero() {
+        let pattern = default_pattern();
+        let content = "// TODO: a fairly long message here\n";
+        let short = ScanOptions {
+            max_line_length: 10,
+            ..ScanOptions::default()
+        };
+        assert!(scan_content(content, "a.rs", &pattern, &short)
+            .items
+            .is_empty());
+
+        let unlimited = ScanOptions {
+            max_line_length: 0,
+            ..ScanOptions::default()
+        };
+        assert_eq!(
+            scan_content(content, "a.rs", &pattern, &unlimited)
+                .items
+                .len(),
+            1
+        );
+    }
+
+    #[test]
+    fn test_scan_reader_skips_long_lines() {
+        let pattern = default_pattern();
+        let options = ScanOptions {
+            max_line_length: 40,
+            ..ScanOptions::default()
+        };
+        let content = format!(
+            "// TODO: short\n// TODO: {}\n// FIXME: also short\n",
+            "y".repeat(50)
+        );
+        let result = scan_reader(content.as_bytes(), "big.js", &pattern, &options).unwrap();
+        let lines: Vec<usize> = result.items.iter().map(|i| i.line).collect();
+        assert_eq!(lines, vec![1, 3]);
+    }
+
+    #[test]
+    fn test_continuation_extracts_issue_ref_from_joined_text() {
+        let pattern = default_pattern();
+        let content = "    # TODO: migrate storage\n    #   tracked in #42\n";
+        let result = scan_content(content, "test.py", &pattern, &joining_options());
+        assert_eq!(result.items[0].message, "migrate storage tracked in #42");
+        assert_eq!(result.items[0].issue_ref.as_deref(), Some("#42"));
+    }
+
+    #[test]
+    fn test_block_comment_terminator_stripped() {
+        let pattern = default_pattern();
+        let content = "/* TODO: x */
+<!-- FIXME: fix markup -->
+ * HACK: inner */
+";
+        let result = scan_content(content, "test.c", &pattern, &ScanOptions::default());
+        let messages: Vec<&str> = result.items.iter().map(|i| i.message.as_str()).collect();
+        assert_eq!(messages, vec!["x", "fix markup", "inner"]);
+    }
+
+    #[test]
+    fn test_terminator_without_opener_kept() {
+        let pattern = default_pattern();
+        let content = "// TODO: loop while i-->
+# NOTE: (see *)
+";
+        let result = scan_content(content, "test.rs", &pattern, &ScanOptions::default());
+        assert_eq!(result.items[0].message, "loop while i-->");
+        assert_eq!(result.items[1].message, "(see *)");
+    }
+
+    #[test]
+    fn test_terminator_stripping_can_be_disabled() {
+        let pattern = default_pattern();
+        let options = ScanOptions {
+            strip_comment_terminators: false,
+            ..ScanOptions::default()
+        };
+        let result = scan_content("/* TODO: x */\n", "test.c", &pattern, &options);
+        assert_eq!(result.items[0].message, "x */");
+    }
+
+    #[test]
+    fn test_describe_patterns_lists_active_tags() {
+        let config = Config {
+            tags: vec!["TODO".into(), "FIXME".into(), "C++".into()],
+            enabled_tags: Some(vec!["todo".into(), "C++".into()]),
+            ..Config::default()
+        };
+        let described = describe_patterns(&config).unwrap();
+        assert!(described.starts_with("tags: TODO, C++\n"));
+        assert!(described.contains(r"pattern: (?i)\b(TODO|C\+\+)\b"));
+        assert!(!described.contains("FIXME"));
+        assert!(!described.contains("file names:"));
+    }
+
+    #[test]
+    fn test_message_whitespace_collapsed() {
+        let pattern = default_pattern();
+        let content = "// TODO: fix    this\t\tnow\n//       and   later\n";
+        let options = ScanOptions {
+            collapse_whitespace: true,
+            ..joining_options()
+        };
+        let result = scan_content(content, "test.rs", &pattern, &options);
+        assert_eq!(result.items[0].message, "fix this now and later");
+    }
+
+    #[test]
+    fn test_message_whitespace_kept_by_default() {
+        let pattern = default_pattern();
+        let result = scan_content(
+            "// TODO: fix    this\t\tnow\n",
+            "test.rs",
+            &pattern,
+            &ScanOptions::default(),
+        );
+        assert_eq!(result.items[0].message, "fix    this\t\tnow");
+    }
+
+    #[test]
+    fn test_collapse_whitespace_from_config() {
+        let config = Config {
+            collapse_whitespace: Some(true),
+            ..Config::default()
+        };
+        assert!(ScanOptions::from_config(&config).collapse_whitespace);
+        assert!(!ScanOptions::from_config(&Config::default()).collapse_whitespace);
+    }
 }
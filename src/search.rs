@@ -2,8 +2,10 @@ use std::collections::HashSet;
 
 use crate::model::{ScanResult, SearchResult, TodoItem};
 
-fn matches_query(item: &TodoItem, query: &str, exact: bool) -> bool {
-    if exact {
+/// Substring match against the message and issue ref. Matching is
+/// case-insensitive unless `case_sensitive` is set.
+fn matches_query(item: &TodoItem, query: &str, case_sensitive: bool) -> bool {
+    if case_sensitive {
         item.message.contains(query) || item.issue_ref.as_deref().is_some_and(|r| r.contains(query))
     } else {
         let lower_query = query.to_lowercase();
@@ -15,11 +17,21 @@ fn matches_query(item: &TodoItem, query: &str, exact: bool) -> bool {
     }
 }
 
-pub fn search_items(scan: &ScanResult, query: &str, exact: bool) -> SearchResult {
+/// Find items whose message or issue ref contains `query`.
+///
+/// `exact` makes the match case-sensitive; `ignore_case` overrides that, so
+/// `--exact --ignore-case` is a plain case-insensitive substring match.
+pub fn search_items(
+    scan: &ScanResult,
+    query: &str,
+    exact: bool,
+    ignore_case: bool,
+) -> SearchResult {
+    let case_sensitive = exact && !ignore_case;
     let items: Vec<TodoItem> = scan
         .items
         .iter()
-        .filter(|item| matches_query(item, query, exact))
+        .filter(|item| matches_query(item, query, case_sensitive))
         .cloned()
         .collect();
 
@@ -44,15 +56,19 @@ mod tests {
     fn make_scan(items: Vec<TodoItem>) -> ScanResult {
         ScanResult {
             files_scanned: 1,
+            files_with_items: 1,
+            bytes_scanned: 0,
             ignored_items: vec![],
             items,
+            truncated: false,
+            total_found: None,
         }
     }
 
     #[test]
     fn test_case_insensitive_match() {
         let scan = make_scan(vec![make_item("a.rs", 1, Tag::Todo, "Fix the BUG")]);
-        let result = search_items(&scan, "fix the bug", false);
+        let result = search_items(&scan, "fix the bug", false, false);
         assert_eq!(result.match_count, 1);
     }
 
@@ -60,13 +76,48 @@ mod tests {
     fn test_exact_match_case_sensitive() {
         let scan = make_scan(vec![make_item("a.rs", 1, Tag::Todo, "Fix the BUG")]);
 
-        let result = search_items(&scan, "Fix the BUG", true);
+        let result = search_items(&scan, "Fix the BUG", true, false);
         assert_eq!(result.match_count, 1);
 
-        let result = search_items(&scan, "fix the bug", true);
+        let result = search_items(&scan, "fix the bug", true, false);
         assert_eq!(result.match_count, 0);
     }
 
+    #[test]
+    fn test_ignore_case_with_exact() {
+        let scan = make_scan(vec![make_item(
+            "a.rs",
+            1,
+            Tag::Todo,
+            "Remove FIXME workaround",
+        )]);
+
+        assert_eq!(search_items(&scan, "fixme", true, false).match_count, 0);
+        assert_eq!(search_items(&scan, "fixme", true, true).match_count, 1);
+        assert_eq!(search_items(&scan, "FiXmE WoRk", true, true).match_count, 1);
+    }
+
+    #[test]
+    fn test_ignore_case_substring() {
+        let scan = make_scan(vec![
+            make_item("a.rs", 1, Tag::Todo, "Handle UTF-8 input"),
+            make_item("b.rs", 1, Tag::Todo, "unrelated"),
+        ]);
+        let result = search_items(&scan, "utf-8", false, true);
+        assert_eq!(result.match_count, 1);
+        assert_eq!(result.items[0].file, "a.rs");
+    }
+
+    #[test]
+    fn test_ignore_case_matches_issue_ref() {
+        let scan = make_scan(vec![{
+            let mut item = make_item("a.rs", 1, Tag::Todo, "some task");
+            item.issue_ref = Some("JIRA-456".to_string());
+            item
+        }]);
+        assert_eq!(search_items(&scan, "jira-456", true, true).match_count, 1);
+    }
+
     #[test]
     fn test_issue_ref_match() {
         let scan = make_scan(vec![{
@@ -74,14 +125,14 @@ mod tests {
             item.issue_ref = Some("#123".to_string());
             item
         }]);
-        let result = search_items(&scan, "#123", false);
+        let result = search_items(&scan, "#123", false, false);
         assert_eq!(result.match_count, 1);
     }
 
     #[test]
     fn test_no_match_empty_result() {
         let scan = make_scan(vec![make_item("a.rs", 1, Tag::Todo, "something")]);
-        let result = search_items(&scan, "nonexistent", false);
+        let result = search_items(&scan, "nonexistent", false, false);
         assert_eq!(result.match_count, 0);
         assert_eq!(result.file_count, 0);
         assert!(result.items.is_empty());
@@ -94,7 +145,7 @@ mod tests {
             make_item("a.rs", 1, Tag::Todo, "fix bar"),
             make_item("b.rs", 1, Tag::Todo, "fix baz"),
         ]);
-        let result = search_items(&scan, "fix", false);
+        let result = search_items(&scan, "fix", false, false);
         assert_eq!(result.match_count, 3);
         assert_eq!(result.file_count, 2);
     }
@@ -106,7 +157,7 @@ mod tests {
             item.issue_ref = Some("JIRA-456".to_string());
             item
         }]);
-        let result = search_items(&scan, "JIRA-456", true);
+        let result = search_items(&scan, "JIRA-456", true, false);
         assert_eq!(result.match_count, 1);
     }
 
@@ -118,7 +169,7 @@ mod tests {
             item
         }]);
         // exact=true should be case-sensitive
-        let result = search_items(&scan, "jira-456", true);
+        let result = search_items(&scan, "jira-456", true, false);
         assert_eq!(result.match_count, 0);
     }
 
@@ -129,14 +180,14 @@ mod tests {
             item.issue_ref = Some("JIRA-456".to_string());
             item
         }]);
-        let result = search_items(&scan, "jira-456", false);
+        let result = search_items(&scan, "jira-456", false, false);
         assert_eq!(result.match_count, 1);
     }
 
     #[test]
     fn test_empty_scan() {
         let scan = make_scan(vec![]);
-        let result = search_items(&scan, "anything", false);
+        let result = search_items(&scan, "anything", false, false);
         assert_eq!(result.match_count, 0);
         assert_eq!(result.file_count, 0);
         assert!(result.items.is_empty());
@@ -145,7 +196,7 @@ mod tests {
     #[test]
     fn test_query_stored_in_result() {
         let scan = make_scan(vec![]);
-        let result = search_items(&scan, "my query", false);
+        let result = search_items(&scan, "my query", false, false);
         assert_eq!(result.query, "my query");
         assert!(!result.exact);
     }
@@ -153,7 +204,7 @@ mod tests {
     #[test]
     fn test_exact_flag_stored_in_result() {
         let scan = make_scan(vec![]);
-        let result = search_items(&scan, "my query", true);
+        let result = search_items(&scan, "my query", true, false);
         assert!(result.exact);
     }
 
@@ -165,7 +216,7 @@ mod tests {
             Tag::Todo,
             "implement user authentication",
         )]);
-        let result = search_items(&scan, "auth", false);
+        let result = search_items(&scan, "auth", false, false);
         assert_eq!(result.match_count, 1);
     }
 
@@ -173,7 +224,7 @@ mod tests {
     fn test_no_issue_ref_does_not_match() {
         let scan = make_scan(vec![make_item("a.rs", 1, Tag::Todo, "some task")]);
         // issue_ref is None, query is an issue ref
-        let result = search_items(&scan, "#999", false);
+        let result = search_items(&scan, "#999", false, false);
         assert_eq!(result.match_count, 0);
     }
 }
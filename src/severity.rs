@@ -0,0 +1,165 @@
+//! Path-based severity overrides.
+//!
+//! `severity_overrides` in the config raise the severity of every TODO in
+//! matching files. The compiled rules travel with the output options, so the
+//! SARIF, GitHub Actions and CodeClimate formatters resolve an item's
+//! effective severity from the value they are given.
+//!
+//! Rules match paths relative to `--root`. Commands resolve severity with
+//! [`SeverityOverrides::resolve`] before `--path-style` rewrites item paths.
+
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context, Result};
+use globset::{GlobBuilder, GlobMatcher};
+
+use crate::config::Config;
+use crate::model::{Severity, TodoItem};
+
+/// Compiled `severity_overrides` rules.
+#[derive(Debug, Default)]
+pub struct SeverityOverrides {
+    rules: Vec<(GlobMatcher, Severity)>,
+    /// Directory the rule paths are relative to (`--root`).
+    root: PathBuf,
+}
+
+impl SeverityOverrides {
+    pub fn from_config(config: &Config) -> Result<Self> {
+        let rules = config
+            .severity_overrides
+            .iter()
+            .map(|rule| {
+                let glob = GlobBuilder::new(&rule.path)
+                    .literal_separator(true)
+                    .build()
+                    .with_context(|| format!("Invalid severity_overrides path: {}", rule.path))?;
+                Ok((glob.compile_matcher(), rule.level))
+            })
+            .collect::<Result<_>>()?;
+        Ok(Self {
+            rules,
+            root: PathBuf::new(),
+        })
+    }
+
+    /// Set the directory rule paths are relative to.
+    pub fn with_root(self, root: &Path) -> Self {
+        let root = root.canonicalize().unwrap_or_else(|_| root.to_path_buf());
+        Self { root, ..self }
+    }
+
+    /// Severity of `item`: the value [`resolve`](Self::resolve) stored, or
+    /// else the result of matching its file as given.
+    pub fn severity_of(&self, item: &TodoItem) -> Severity {
+        item.severity
+            .unwrap_or_else(|| self.matched(item, Path::new(&item.file)))
+    }
+
+    /// Store the severity of each item found under `scan_root`, whose paths
+    /// are still relative to it (or absolute, for absolute positional roots).
+    /// Rules see the path relative to `--root`, so a `--package` scan matches
+    /// the same paths as a full scan.
+    pub fn resolve<'a>(&self, items: impl IntoIterator<Item = &'a mut TodoItem>, scan_root: &Path) {
+        let scan_root = scan_root
+            .canonicalize()
+            .unwrap_or_else(|_| scan_root.to_path_buf());
+        let prefix = scan_root.strip_prefix(&self.root).unwrap_or(Path::new(""));
+        for item in items {
+            let file = prefix.join(&item.file);
+            let file = file.strip_prefix(&self.root).unwrap_or(&file);
+            item.severity = Some(self.matched(item, file));
+        }
+    }
+
+    /// Apply every rule whose glob matches `file`. Overrides only raise
+    /// severity; a lower level never downgrades an item.
+    fn matched(&self, item: &TodoItem, file: &Path) -> Severity {
+        self.rules
+            .iter()
+            .filter(|(glob, _)| glob.is_match(file))
+            .fold(Severity::from_item(item), |acc, (_, level)| {
+                acc.escalate(*level)
+            })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::model::Tag;
+    use crate::test_helpers::helpers::make_item;
+
+    fn overrides(toml_str: &str) -> SeverityOverrides {
+        let config: Config = toml::from_str(toml_str).unwrap();
+        SeverityOverrides::from_config(&config).unwrap()
+    }
+
+    #[test]
+    fn test_override_raises_matching_path() {
+        let o = overrides(r#"severity_overrides = [{ path = "src/crypto/**", level = "error" }]"#);
+        let crypto = make_item("src/crypto/aes.rs", 1, Tag::Todo, "rotate keys");
+        let other = make_item("src/ui/view.rs", 1, Tag::Todo, "rotate icons");
+        assert_eq!(o.severity_of(&crypto), Severity::Error);
+        assert_eq!(o.severity_of(&other), Severity::Warning);
+    }
+
+    #[test]
+    fn test_override_never_lowers_severity() {
+        let o = overrides(r#"severity_overrides = [{ path = "**/*.rs", level = "note" }]"#);
+        let bug = make_item("src/lib.rs", 1, Tag::Bug, "crash");
+        let note = make_item("src/lib.rs", 2, Tag::Note, "fyi");
+        assert_eq!(o.severity_of(&bug), Severity::Error);
+        assert_eq!(o.severity_of(&note), Severity::Notice);
+    }
+
+    #[test]
+    fn test_override_star_does_not_cross_directories() {
+        let o = overrides(r#"severity_overrides = [{ path = "src/*.rs", level = "error" }]"#);
+        assert_eq!(
+            o.severity_of(&make_item("src/main.rs", 1, Tag::Todo, "a")),
+            Severity::Error
+        );
+        assert_eq!(
+            o.severity_of(&make_item("src/nested/mod.rs", 1, Tag::Todo, "b")),
+            Severity::Warning
+        );
+    }
+
+    #[test]
+    fn test_resolve_survives_path_rewrite() {
+        let o = overrides(r#"severity_overrides = [{ path = "src/**", level = "error" }]"#);
+        let mut items = vec![make_item("src/lib.rs", 1, Tag::Todo, "a")];
+        o.resolve(&mut items, Path::new(""));
+        items[0].file = "/repo/src/lib.rs".to_string();
+        assert_eq!(o.severity_of(&items[0]), Severity::Error);
+    }
+
+    #[test]
+    fn test_resolve_prefixes_package_scan_root() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::create_dir_all(dir.path().join("crates/core")).unwrap();
+        let o =
+            overrides(r#"severity_overrides = [{ path = "crates/core/src/**", level = "error" }]"#)
+                .with_root(dir.path());
+        let mut items = vec![make_item("src/lib.rs", 1, Tag::Todo, "a")];
+        o.resolve(&mut items, &dir.path().join("crates/core"));
+        assert_eq!(items[0].severity, Some(Severity::Error));
+    }
+
+    #[test]
+    fn test_invalid_glob_is_an_error() {
+        let config: Config =
+            toml::from_str(r#"severity_overrides = [{ path = "src/[", level = "error" }]"#)
+                .unwrap();
+        let err = SeverityOverrides::from_config(&config).unwrap_err();
+        assert!(format!("{:#}", err).contains("Invalid severity_overrides path"));
+    }
+
+    #[test]
+    fn test_unknown_level_is_rejected() {
+        let result: std::result::Result<Config, _> =
+            toml::from_str(r#"severity_overrides = [{ path = "src/**", level = "fatal" }]"#);
+        assert!(result.is_err());
+    }
+}
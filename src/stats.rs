@@ -1,9 +1,38 @@
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
 
+use anyhow::{anyhow, Result};
+
+use crate::config::Config;
 use crate::model::*;
 
-pub fn compute_stats(scan: &ScanResult, diff: Option<&DiffResult>) -> StatsResult {
-    let total_items = scan.items.len();
+/// Parse `stats.tag_priority` into per-tag minimum priorities.
+pub fn tag_priority_floors(config: &Config) -> Result<HashMap<Tag, Priority>> {
+    config
+        .stats
+        .tag_priority
+        .iter()
+        .map(|(tag, priority)| {
+            let tag = tag
+                .parse::<Tag>()
+                .map_err(|_| anyhow!("Unknown tag in stats.tag_priority: {}", tag))?;
+            Ok((tag, *priority))
+        })
+        .collect()
+}
+
+/// Compute summary statistics for a scan.
+///
+/// `tag_priority` gives a minimum priority per tag: an item counts toward the
+/// higher of its own priority and its tag's floor. An empty map counts items
+/// by priority alone.
+pub fn compute_stats(
+    scan: &ScanResult,
+    diff: Option<&DiffResult>,
+    tag_priority: &HashMap<Tag, Priority>,
+    by_dir: bool,
+) -> StatsResult {
+    // Items dropped by `max_items` still count toward the total
+    let total_items = scan.total_found.unwrap_or(scan.items.len());
 
     // Unique file count
     let mut file_set: HashMap<&str, usize> = HashMap::new();
@@ -17,15 +46,24 @@ pub fn compute_stats(scan: &ScanResult, diff: Option<&DiffResult>) -> StatsResul
     for item in &scan.items {
         *tag_map.entry(item.tag).or_insert(0) += 1;
     }
+    // Most items first; ties by severity, then name, so the order is stable
     let mut tag_counts: Vec<(Tag, usize)> = tag_map.into_iter().collect();
-    tag_counts.sort_by(|a, b| b.1.cmp(&a.1));
+    tag_counts.sort_by(|a, b| {
+        b.1.cmp(&a.1)
+            .then(b.0.severity().cmp(&a.0.severity()))
+            .then(a.0.as_str().cmp(b.0.as_str()))
+    });
 
     // Priority counts
     let mut normal = 0;
     let mut high = 0;
     let mut urgent = 0;
     for item in &scan.items {
-        match item.priority {
+        let priority = match tag_priority.get(&item.tag) {
+            Some(floor) if floor.numeric_order() > item.priority.numeric_order() => *floor,
+            _ => item.priority,
+        };
+        match priority {
             Priority::Normal => normal += 1,
             Priority::High => high += 1,
             Priority::Urgent => urgent += 1,
@@ -47,16 +85,27 @@ pub fn compute_stats(scan: &ScanResult, diff: Option<&DiffResult>) -> StatsResul
         *author_map.entry(key).or_insert(0) += 1;
     }
     let mut author_counts: Vec<(String, usize)> = author_map.into_iter().collect();
-    author_counts.sort_by(|a, b| b.1.cmp(&a.1));
+    author_counts.sort_by_key(|e| std::cmp::Reverse(e.1));
 
     // Hotspot files (top 5 by count)
     let mut hotspot_files: Vec<(String, usize)> = file_set
         .into_iter()
         .map(|(k, v)| (k.to_string(), v))
         .collect();
-    hotspot_files.sort_by(|a, b| b.1.cmp(&a.1));
+    hotspot_files.sort_by_key(|e| std::cmp::Reverse(e.1));
     hotspot_files.truncate(5);
 
+    // Per-directory rollup, most items first
+    let mut dir_counts: Vec<(String, usize)> = Vec::new();
+    if by_dir {
+        let mut dir_map: HashMap<String, usize> = HashMap::new();
+        for item in &scan.items {
+            *dir_map.entry(item.dir()).or_insert(0) += 1;
+        }
+        dir_counts = dir_map.into_iter().collect();
+        dir_counts.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+    }
+
     // Trend info from diff
     let trend = diff.map(|d| TrendInfo {
         added: d.added_count,
@@ -67,14 +116,67 @@ pub fn compute_stats(scan: &ScanResult, diff: Option<&DiffResult>) -> StatsResul
     StatsResult {
         total_items,
         total_files,
+        files_scanned: scan.files_scanned,
+        files_with_items: scan.files_with_items,
+        bytes_scanned: scan.bytes_scanned,
         tag_counts,
         priority_counts,
         author_counts,
         hotspot_files,
+        dir_counts,
+        tree: None,
         trend,
     }
 }
 
+/// Build the `stats --tree` rollup from item paths. The root node is `.`;
+/// children are sorted by name and each count includes its descendants.
+pub fn build_dir_tree(items: &[TodoItem]) -> DirNode {
+    #[derive(Default)]
+    struct Node {
+        count: usize,
+        children: BTreeMap<String, Node>,
+    }
+
+    fn finish(name: String, node: Node) -> DirNode {
+        DirNode {
+            name,
+            count: node.count,
+            children: node
+                .children
+                .into_iter()
+                .map(|(name, child)| finish(name, child))
+                .collect(),
+        }
+    }
+
+    let mut root = Node::default();
+    for item in items {
+        root.count += 1;
+        let mut node = &mut root;
+        for part in item.file.split('/').filter(|p| !p.is_empty() && *p != ".") {
+            node = node.children.entry(part.to_string()).or_default();
+            node.count += 1;
+        }
+    }
+    finish(".".to_string(), root)
+}
+
+/// Summarize a scan into the aggregates reported by `list --summary`.
+pub fn summarize(scan: &ScanResult, tag_priority: &HashMap<Tag, Priority>) -> ListSummary {
+    let stats = compute_stats(scan, None, tag_priority, false);
+    ListSummary {
+        total: stats.total_items,
+        files: stats.total_files,
+        by_tag: stats
+            .tag_counts
+            .into_iter()
+            .map(|(tag, count)| (tag.as_str().to_string(), count))
+            .collect(),
+        by_priority: stats.priority_counts,
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -90,16 +192,53 @@ mod tests {
                 make_item("b.rs", 1, Tag::Fixme, "fix this"),
             ],
             files_scanned: 2,
+            files_with_items: 2,
+            bytes_scanned: 0,
             ignored_items: vec![],
+            truncated: false,
+            total_found: None,
         };
 
-        let result = compute_stats(&scan, None);
+        let result = compute_stats(&scan, None, &HashMap::new(), false);
         assert_eq!(result.total_items, 3);
         assert_eq!(result.total_files, 2);
         assert_eq!(result.tag_counts.len(), 2);
         assert!(result.trend.is_none());
     }
 
+    #[test]
+    fn test_tag_counts_tie_break_by_severity() {
+        let scan = ScanResult {
+            items: vec![
+                make_item("a.rs", 1, Tag::Note, "note"),
+                make_item("a.rs", 2, Tag::Todo, "todo"),
+                make_item("a.rs", 3, Tag::Bug, "bug"),
+                make_item("a.rs", 4, Tag::Hack, "hack"),
+                make_item("a.rs", 5, Tag::Hack, "hack again"),
+            ],
+            files_scanned: 1,
+            files_with_items: 1,
+            bytes_scanned: 0,
+            ignored_items: vec![],
+            truncated: false,
+            total_found: None,
+        };
+
+        // HashMap iteration order varies between runs; the result must not
+        for _ in 0..10 {
+            let result = compute_stats(&scan, None, &HashMap::new(), false);
+            assert_eq!(
+                result.tag_counts,
+                vec![
+                    (Tag::Hack, 2),
+                    (Tag::Bug, 1),
+                    (Tag::Todo, 1),
+                    (Tag::Note, 1)
+                ]
+            );
+        }
+    }
+
     #[test]
     fn test_priority_counts() {
         let mut items = vec![
@@ -113,15 +252,122 @@ mod tests {
         let scan = ScanResult {
             items,
             files_scanned: 1,
+            files_with_items: 1,
+            bytes_scanned: 0,
             ignored_items: vec![],
+            truncated: false,
+            total_found: None,
         };
 
-        let result = compute_stats(&scan, None);
+        let result = compute_stats(&scan, None, &HashMap::new(), false);
         assert_eq!(result.priority_counts.normal, 1);
         assert_eq!(result.priority_counts.high, 1);
         assert_eq!(result.priority_counts.urgent, 1);
     }
 
+    fn bug_scan() -> ScanResult {
+        ScanResult {
+            items: vec![
+                make_item("a.rs", 1, Tag::Bug, "crash on save"),
+                make_item("a.rs", 2, Tag::Xxx, "odd edge case"),
+            ],
+            files_scanned: 1,
+            files_with_items: 1,
+            bytes_scanned: 0,
+            ignored_items: vec![],
+            truncated: false,
+            total_found: None,
+        }
+    }
+
+    #[test]
+    fn test_priority_counts_default_ignores_tag() {
+        let result = compute_stats(&bug_scan(), None, &HashMap::new(), false);
+        assert_eq!(result.priority_counts.normal, 2);
+        assert_eq!(result.priority_counts.urgent, 0);
+    }
+
+    #[test]
+    fn test_priority_counts_with_tag_priority() {
+        let config: Config =
+            toml::from_str("[stats.tag_priority]\nBUG = \"urgent\"\nXXX = \"high\"\n").unwrap();
+        let floors = tag_priority_floors(&config).unwrap();
+        let result = compute_stats(&bug_scan(), None, &floors, false);
+        assert_eq!(result.priority_counts.normal, 0);
+        assert_eq!(result.priority_counts.high, 1);
+        assert_eq!(result.priority_counts.urgent, 1);
+    }
+
+    #[test]
+    fn test_tag_priority_never_lowers_priority() {
+        let mut scan = bug_scan();
+        scan.items[1].priority = Priority::Urgent;
+        let floors = HashMap::from([(Tag::Xxx, Priority::High)]);
+        let result = compute_stats(&scan, None, &floors, false);
+        assert_eq!(result.priority_counts.urgent, 1);
+        assert_eq!(result.priority_counts.high, 0);
+    }
+
+    #[test]
+    fn test_tag_priority_unknown_tag_errors() {
+        let config: Config = toml::from_str("[stats.tag_priority]\nBOGUS = \"urgent\"\n").unwrap();
+        let err = tag_priority_floors(&config).unwrap_err();
+        assert!(err.to_string().contains("BOGUS"));
+    }
+
+    #[test]
+    fn test_dir_tree_rolls_up_children() {
+        let items = vec![
+            make_item("src/cmd/a.rs", 1, Tag::Todo, "one"),
+            make_item("src/cmd/a.rs", 2, Tag::Todo, "two"),
+            make_item("src/cmd/b.rs", 1, Tag::Fixme, "three"),
+            make_item("src/lib.rs", 1, Tag::Todo, "four"),
+            make_item("README.md", 1, Tag::Note, "five"),
+        ];
+        let tree = build_dir_tree(&items);
+
+        fn assert_rolled_up(node: &DirNode) {
+            if !node.children.is_empty() {
+                let sum: usize = node.children.iter().map(|c| c.count).sum();
+                assert_eq!(node.count, sum, "node {}", node.name);
+                node.children.iter().for_each(assert_rolled_up);
+            }
+        }
+        assert_rolled_up(&tree);
+
+        assert_eq!(tree.name, ".");
+        assert_eq!(tree.count, 5);
+        let names: Vec<&str> = tree.children.iter().map(|c| c.name.as_str()).collect();
+        assert_eq!(names, ["README.md", "src"]);
+        let src = &tree.children[1];
+        assert_eq!(src.count, 4);
+        assert_eq!(src.children[0].name, "cmd");
+        assert_eq!(src.children[0].count, 3);
+        assert_eq!(src.children[0].children[0].count, 2);
+    }
+
+    #[test]
+    fn test_dir_tree_empty() {
+        let tree = build_dir_tree(&[]);
+        assert_eq!(tree.count, 0);
+        assert!(tree.children.is_empty());
+    }
+
+    #[test]
+    fn test_summarize_counts_by_tag_and_priority() {
+        let mut scan = bug_scan();
+        scan.items.push(make_item("b.rs", 1, Tag::Bug, "leak"));
+        scan.items[2].priority = Priority::High;
+
+        let summary = summarize(&scan, &HashMap::new());
+        assert_eq!(summary.total, 3);
+        assert_eq!(summary.files, 2);
+        assert_eq!(summary.by_tag["BUG"], 2);
+        assert_eq!(summary.by_tag["XXX"], 1);
+        assert_eq!(summary.by_priority.normal, 2);
+        assert_eq!(summary.by_priority.high, 1);
+    }
+
     #[test]
     fn test_author_counts() {
         let mut items = vec![
@@ -135,10 +381,14 @@ mod tests {
         let scan = ScanResult {
             items,
             files_scanned: 1,
+            files_with_items: 1,
+            bytes_scanned: 0,
             ignored_items: vec![],
+            truncated: false,
+            total_found: None,
         };
 
-        let result = compute_stats(&scan, None);
+        let result = compute_stats(&scan, None, &HashMap::new(), false);
         assert_eq!(result.author_counts.len(), 3);
     }
 
@@ -151,10 +401,14 @@ mod tests {
         let scan = ScanResult {
             items,
             files_scanned: 10,
+            files_with_items: 10,
+            bytes_scanned: 0,
             ignored_items: vec![],
+            truncated: false,
+            total_found: None,
         };
 
-        let result = compute_stats(&scan, None);
+        let result = compute_stats(&scan, None, &HashMap::new(), false);
         assert_eq!(result.hotspot_files.len(), 5);
     }
 
@@ -163,7 +417,11 @@ mod tests {
         let scan = ScanResult {
             items: vec![make_item("a.rs", 1, Tag::Todo, "task")],
             files_scanned: 1,
+            files_with_items: 1,
+            bytes_scanned: 0,
             ignored_items: vec![],
+            truncated: false,
+            total_found: None,
         };
         let diff = DiffResult {
             entries: vec![],
@@ -172,7 +430,7 @@ mod tests {
             base_ref: "main".to_string(),
         };
 
-        let result = compute_stats(&scan, Some(&diff));
+        let result = compute_stats(&scan, Some(&diff), &HashMap::new(), false);
         assert!(result.trend.is_some());
         let trend = result.trend.unwrap();
         assert_eq!(trend.added, 3);
@@ -185,14 +443,50 @@ mod tests {
         let scan = ScanResult {
             items: vec![],
             files_scanned: 0,
+            files_with_items: 0,
+            bytes_scanned: 0,
             ignored_items: vec![],
+            truncated: false,
+            total_found: None,
         };
 
-        let result = compute_stats(&scan, None);
+        let result = compute_stats(&scan, None, &HashMap::new(), false);
         assert_eq!(result.total_items, 0);
         assert_eq!(result.total_files, 0);
         assert!(result.tag_counts.is_empty());
         assert!(result.author_counts.is_empty());
         assert!(result.hotspot_files.is_empty());
     }
+
+    #[test]
+    fn test_dir_counts_aggregate_nested_dirs() {
+        let scan = ScanResult {
+            items: vec![
+                make_item("src/cmd/a.rs", 1, Tag::Todo, "a"),
+                make_item("src/cmd/b.rs", 1, Tag::Todo, "b"),
+                make_item("src/cmd/b.rs", 2, Tag::Fixme, "c"),
+                make_item("src/lib.rs", 1, Tag::Hack, "d"),
+                make_item("main.rs", 1, Tag::Todo, "e"),
+            ],
+            files_scanned: 4,
+            files_with_items: 4,
+            bytes_scanned: 0,
+            ignored_items: vec![],
+            truncated: false,
+            total_found: None,
+        };
+
+        let result = compute_stats(&scan, None, &HashMap::new(), true);
+        assert_eq!(
+            result.dir_counts,
+            vec![
+                ("src/cmd".to_string(), 3),
+                (".".to_string(), 1),
+                ("src".to_string(), 1),
+            ]
+        );
+
+        let result = compute_stats(&scan, None, &HashMap::new(), false);
+        assert!(result.dir_counts.is_empty());
+    }
 }
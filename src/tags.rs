@@ -0,0 +1,63 @@
+use crate::config::Config;
+use crate::model::{Severity, Tag, TagInfo};
+
+/// Describe every recognized tag under `config`: its default severity,
+/// whether scanning matches it, and its configured display labels.
+pub fn compute_tags(config: &Config) -> Vec<TagInfo> {
+    let active = config.active_tags();
+    Tag::ALL
+        .iter()
+        .map(|tag| TagInfo {
+            tag: *tag,
+            severity: Severity::from_tag(tag),
+            enabled: active.iter().any(|t| t.eq_ignore_ascii_case(tag.as_str())),
+            aliases: config
+                .tag_labels
+                .iter()
+                .filter(|(key, _)| key.eq_ignore_ascii_case(tag.as_str()))
+                .map(|(_, label)| label.clone())
+                .collect(),
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn info(tags: &[TagInfo], tag: Tag) -> &TagInfo {
+        tags.iter().find(|t| t.tag == tag).unwrap()
+    }
+
+    #[test]
+    fn test_all_tags_enabled_by_default() {
+        let tags = compute_tags(&Config::default());
+        assert_eq!(tags.len(), Tag::ALL.len());
+        assert!(tags.iter().all(|t| t.enabled));
+        assert_eq!(info(&tags, Tag::Bug).severity, Severity::Error);
+        assert_eq!(info(&tags, Tag::Note).severity, Severity::Notice);
+    }
+
+    #[test]
+    fn test_disabled_tag_is_not_enabled() {
+        let config: Config = toml::from_str("enabled_tags = [\"TODO\", \"FIXME\"]\n").unwrap();
+        let tags = compute_tags(&config);
+        assert!(info(&tags, Tag::Todo).enabled);
+        assert!(!info(&tags, Tag::Hack).enabled);
+    }
+
+    #[test]
+    fn test_tag_missing_from_tags_is_not_enabled() {
+        let config: Config = toml::from_str("tags = [\"TODO\"]\n").unwrap();
+        let tags = compute_tags(&config);
+        assert!(!info(&tags, Tag::Fixme).enabled);
+    }
+
+    #[test]
+    fn test_aliases_from_tag_labels() {
+        let config: Config = toml::from_str("[tag_labels]\nBUG = \"Defect\"\n").unwrap();
+        let tags = compute_tags(&config);
+        assert_eq!(info(&tags, Tag::Bug).aliases, vec!["Defect"]);
+        assert!(info(&tags, Tag::Todo).aliases.is_empty());
+    }
+}
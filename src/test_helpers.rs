@@ -6,12 +6,17 @@ pub mod helpers {
         TodoItem {
             file: file.to_string(),
             line,
+            byte_offset: 0,
+            cell: None,
+            labels: vec![],
             tag,
             message: message.to_string(),
             author: None,
             issue_ref: None,
             priority: Priority::Normal,
             deadline: None,
+            ignore_reason: None,
+            severity: None,
         }
     }
 }
@@ -1,24 +1,28 @@
 use std::collections::HashMap;
 use std::path::{Path, PathBuf};
 use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::mpsc::{Receiver, RecvTimeoutError};
 use std::sync::Arc;
-use std::time::Duration;
+use std::time::{Duration, Instant};
 
 use anyhow::{Context, Result};
-use notify_debouncer_mini::{new_debouncer, DebouncedEventKind};
+use notify_debouncer_mini::{new_debouncer, DebounceEventResult, DebouncedEventKind};
 use regex::Regex;
 
-use crate::cli::Format;
+use crate::cli::WatchTimestamps;
 use crate::config::Config;
 use crate::date_utils;
 use crate::model::{FileUpdate, Tag, TodoItem, WatchEvent};
-use crate::output::{print_initial_summary, print_watch_event};
-use crate::scanner::{scan_content, scan_directory, MAX_FILE_SIZE};
+use crate::output::{print_initial_summary, print_watch_event, OutputOptions};
+use crate::scanner::{
+    in_excluded_dir, scan_directory, scan_file_content, scan_reader, ScanOptions, MAX_FILE_SIZE,
+};
 
 /// In-memory index of TODO items grouped by file path.
 pub struct TodoIndex {
     items: HashMap<String, Vec<TodoItem>>,
     pattern: Regex,
+    options: ScanOptions,
     root: PathBuf,
     exclude_dirs: Vec<String>,
     exclude_regexes: Vec<Regex>,
@@ -44,6 +48,7 @@ impl TodoIndex {
         Ok(Self {
             items,
             pattern,
+            options: ScanOptions::from_config(config),
             root: root.to_path_buf(),
             exclude_dirs: config.exclude_dirs.clone(),
             exclude_regexes,
@@ -57,18 +62,34 @@ impl TodoIndex {
         // Check file size before reading to prevent OOM on large files
         let metadata = std::fs::metadata(&abs_path)
             .with_context(|| format!("failed to stat {}", abs_path.display()))?;
-        if metadata.len() > MAX_FILE_SIZE {
-            let removed = self.items.remove(relative_path).unwrap_or_default();
-            return Ok(FileUpdate {
-                added: vec![],
-                removed,
-            });
-        }
-
-        let content = std::fs::read_to_string(&abs_path)
-            .with_context(|| format!("failed to read {}", abs_path.display()))?;
-
-        let scan_result = scan_content(&content, relative_path, &self.pattern);
+        let scan_result = if metadata.len() > MAX_FILE_SIZE {
+            if !self.options.scan_large {
+                let removed = self.items.remove(relative_path).unwrap_or_default();
+                return Ok(FileUpdate {
+                    added: vec![],
+                    removed,
+                });
+            }
+            let file = std::fs::File::open(&abs_path)
+                .with_context(|| format!("failed to open {}", abs_path.display()))?;
+            scan_reader(
+                std::io::BufReader::new(file),
+                relative_path,
+                &self.pattern,
+                &self.options,
+            )
+            .with_context(|| format!("failed to read {}", abs_path.display()))?
+        } else {
+            let content = std::fs::read_to_string(&abs_path)
+                .with_context(|| format!("failed to read {}", abs_path.display()))?;
+            scan_file_content(
+                &abs_path,
+                &content,
+                relative_path,
+                &self.pattern,
+                &self.options,
+            )
+        };
         let new_items = scan_result.items;
         let old_items = self.items.remove(relative_path).unwrap_or_default();
 
@@ -114,19 +135,13 @@ impl TodoIndex {
             }
         }
         let mut result: Vec<(Tag, usize)> = counts.into_iter().collect();
-        result.sort_by(|a, b| b.1.cmp(&a.1));
+        result.sort_by_key(|e| std::cmp::Reverse(e.1));
         result
     }
 
     /// Check if a path should be excluded based on config.
     pub fn should_exclude(&self, relative_path: &str) -> bool {
-        let path = Path::new(relative_path);
-
-        let excluded_by_dir = self.exclude_dirs.iter().any(|dir| {
-            path.components()
-                .any(|c| c.as_os_str().to_str().is_some_and(|s| s == dir))
-        });
-        if excluded_by_dir {
+        if in_excluded_dir(Path::new(relative_path), &self.exclude_dirs) {
             return true;
         }
 
@@ -186,26 +201,136 @@ fn build_watch_event(
     }
 }
 
+/// Why the watch event loop returned.
+#[derive(Debug, PartialEq, Eq)]
+enum LoopExit {
+    /// Ctrl+C cleared the running flag.
+    Interrupted,
+    /// `once` was set and an event was emitted.
+    Emitted,
+    /// `once` was set and no event arrived before the timeout.
+    TimedOut,
+    /// The watcher's channel closed.
+    Disconnected,
+}
+
+/// Watch behavior chosen on the command line.
+struct LoopOptions<'a> {
+    filter_tags: &'a [Tag],
+    once: bool,
+    timeout: Option<Duration>,
+}
+
+/// Turn a changed file into a watch event, or `None` if nothing visible changed.
+fn process_change(
+    index: &mut TodoIndex,
+    root: &Path,
+    file: &str,
+    filter_tags: &[Tag],
+) -> Option<WatchEvent> {
+    if index.should_exclude(file) {
+        return None;
+    }
+
+    let abs_path = root.join(file);
+    let previous_total = index.total_count();
+
+    let update = if abs_path.is_file() {
+        index.update_file(file).ok()?
+    } else {
+        let removed = index.remove_file(file);
+        FileUpdate {
+            added: vec![],
+            removed,
+        }
+    };
+
+    if update.added.is_empty() && update.removed.is_empty() {
+        return None;
+    }
+
+    let mut event = build_watch_event(file, &update, index, previous_total);
+
+    // Apply tag filter to displayed items
+    if !filter_tags.is_empty() {
+        event.added.retain(|i| filter_tags.contains(&i.tag));
+        event.removed.retain(|i| filter_tags.contains(&i.tag));
+        if event.added.is_empty() && event.removed.is_empty() {
+            return None;
+        }
+    }
+
+    Some(event)
+}
+
+/// Consume debounced batches, passing each resulting event to `emit`, until
+/// interrupted, the channel closes, or (with `once`) the first event is
+/// emitted or the timeout passes.
+fn run_event_loop(
+    rx: &Receiver<DebounceEventResult>,
+    running: &AtomicBool,
+    index: &mut TodoIndex,
+    root: &Path,
+    opts: &LoopOptions,
+    mut emit: impl FnMut(&mut WatchEvent),
+) -> LoopExit {
+    let deadline = opts.timeout.map(|t| Instant::now() + t);
+
+    while running.load(Ordering::SeqCst) {
+        if deadline.is_some_and(|d| Instant::now() >= d) {
+            return LoopExit::TimedOut;
+        }
+        match rx.recv_timeout(Duration::from_millis(200)) {
+            Ok(Ok(events)) => {
+                for file in collect_changed_files(&events, root) {
+                    let Some(mut event) = process_change(index, root, &file, opts.filter_tags)
+                    else {
+                        continue;
+                    };
+                    emit(&mut event);
+                    if opts.once {
+                        return LoopExit::Emitted;
+                    }
+                }
+            }
+            Ok(Err(_)) => continue,
+            Err(RecvTimeoutError::Timeout) => continue,
+            Err(RecvTimeoutError::Disconnected) => return LoopExit::Disconnected,
+        }
+    }
+    LoopExit::Interrupted
+}
+
+pub struct WatchOptions {
+    pub tag: Vec<String>,
+    pub max: Option<usize>,
+    pub debounce_ms: u64,
+    pub timestamps: WatchTimestamps,
+    pub once: bool,
+    pub timeout_secs: Option<u64>,
+}
+
 /// Main watch command entry point.
 pub fn cmd_watch(
     root: &Path,
     config: &Config,
-    format: &Format,
-    tag_filter: &[String],
-    max: Option<usize>,
-    debounce_ms: u64,
+    out: &OutputOptions,
+    opts: WatchOptions,
 ) -> Result<()> {
+    let started = Instant::now();
+
     // Canonicalize root to match paths reported by the OS watcher
     // (e.g., macOS resolves /tmp → /private/tmp)
     let root = root.canonicalize().unwrap_or_else(|_| root.to_path_buf());
 
     let mut index = TodoIndex::new(&root, config)?;
-    let filter_tags: Vec<Tag> = tag_filter
+    let filter_tags: Vec<Tag> = opts
+        .tag
         .iter()
         .filter_map(|s| s.parse::<Tag>().ok())
         .collect();
 
-    print_initial_summary(&index.tag_counts(), index.total_count(), format);
+    print_initial_summary(&index.tag_counts(), index.total_count(), out);
 
     let running = Arc::new(AtomicBool::new(true));
     let r = running.clone();
@@ -215,7 +340,7 @@ pub fn cmd_watch(
     .context("failed to set Ctrl+C handler")?;
 
     let (tx, rx) = std::sync::mpsc::channel();
-    let mut debouncer = new_debouncer(Duration::from_millis(debounce_ms), tx)
+    let mut debouncer = new_debouncer(Duration::from_millis(opts.debounce_ms), tx)
         .context("failed to create watcher")?;
 
     debouncer
@@ -223,58 +348,33 @@ pub fn cmd_watch(
         .watch(&root, notify::RecursiveMode::Recursive)
         .context("failed to watch directory")?;
 
-    eprintln!("Watching for changes... (Ctrl+C to stop)");
-
-    while running.load(Ordering::SeqCst) {
-        match rx.recv_timeout(Duration::from_millis(200)) {
-            Ok(Ok(events)) => {
-                let files = collect_changed_files(&events, &root);
-                for file in files {
-                    if index.should_exclude(&file) {
-                        continue;
-                    }
-
-                    let abs_path = root.join(&file);
-                    let previous_total = index.total_count();
-
-                    let update = if abs_path.is_file() {
-                        match index.update_file(&file) {
-                            Ok(u) => u,
-                            Err(_) => continue,
-                        }
-                    } else {
-                        let removed = index.remove_file(&file);
-                        FileUpdate {
-                            added: vec![],
-                            removed,
-                        }
-                    };
-
-                    if update.added.is_empty() && update.removed.is_empty() {
-                        continue;
-                    }
-
-                    let mut event = build_watch_event(&file, &update, &index, previous_total);
-
-                    // Apply tag filter to displayed items
-                    if !filter_tags.is_empty() {
-                        event.added.retain(|i| filter_tags.contains(&i.tag));
-                        event.removed.retain(|i| filter_tags.contains(&i.tag));
-                        if event.added.is_empty() && event.removed.is_empty() {
-                            continue;
-                        }
-                    }
+    if opts.once {
+        eprintln!("Waiting for the first change... (Ctrl+C to stop)");
+    } else {
+        eprintln!("Watching for changes... (Ctrl+C to stop)");
+    }
 
-                    print_watch_event(&event, format, max);
-                }
-            }
-            Ok(Err(_)) => continue,
-            Err(std::sync::mpsc::RecvTimeoutError::Timeout) => continue,
-            Err(std::sync::mpsc::RecvTimeoutError::Disconnected) => break,
+    let loop_opts = LoopOptions {
+        filter_tags: &filter_tags,
+        once: opts.once,
+        timeout: opts.timeout_secs.map(Duration::from_secs),
+    };
+    let exit = run_event_loop(&rx, &running, &mut index, &root, &loop_opts, |event| {
+        if matches!(opts.timestamps, WatchTimestamps::Relative) {
+            event.timestamp = format!("+{}", date_utils::format_hms(started.elapsed()));
+        }
+        print_watch_event(event, out, opts.max);
+    });
+
+    match exit {
+        LoopExit::Emitted => {}
+        LoopExit::TimedOut => {
+            // Exit 1 so a save hook can tell a timeout from an emitted event
+            eprintln!("No changes before timeout.");
+            crate::cmd::exit_with(out, 1);
         }
+        LoopExit::Interrupted | LoopExit::Disconnected => eprintln!("Watching stopped."),
     }
-
-    eprintln!("Watching stopped.");
     Ok(())
 }
 
@@ -409,6 +509,22 @@ mod tests {
         assert!(!index.should_exclude("src/main.rs"));
     }
 
+    #[test]
+    fn test_root_under_excluded_dir_is_indexed() {
+        let dir = TempDir::new().unwrap();
+        let root = dir.path().join("node_modules").join("proj");
+        std::fs::create_dir_all(&root).unwrap();
+        std::fs::write(root.join("main.rs"), "// TODO: inside\n").unwrap();
+        let config = Config {
+            exclude_dirs: vec!["node_modules".to_string()],
+            ..Config::default()
+        };
+        let index = TodoIndex::new(&root, &config).unwrap();
+
+        assert_eq!(index.total_count(), 1);
+        assert!(!index.should_exclude("main.rs"));
+    }
+
     #[test]
     fn test_should_exclude_patterns() {
         let config = Config {
@@ -422,6 +538,107 @@ mod tests {
         assert!(!index.should_exclude("src/app.js"));
     }
 
+    fn change_batch(dir: &Path, files: &[&str]) -> DebounceEventResult {
+        Ok(files
+            .iter()
+            .map(|f| notify_debouncer_mini::DebouncedEvent {
+                path: dir.join(f),
+                kind: DebouncedEventKind::Any,
+            })
+            .collect())
+    }
+
+    #[test]
+    fn test_event_loop_once_exits_after_first_event() {
+        let (dir, mut index) = setup_index(&[("a.rs", "// TODO: one\n"), ("b.rs", "")]);
+        fs::write(dir.path().join("a.rs"), "// TODO: one\n// TODO: two\n").unwrap();
+        fs::write(dir.path().join("b.rs"), "// FIXME: three\n").unwrap();
+
+        let (tx, rx) = std::sync::mpsc::channel();
+        tx.send(change_batch(dir.path(), &["a.rs", "b.rs"]))
+            .unwrap();
+        tx.send(change_batch(dir.path(), &["b.rs"])).unwrap();
+
+        let running = AtomicBool::new(true);
+        let opts = LoopOptions {
+            filter_tags: &[],
+            once: true,
+            timeout: None,
+        };
+        let mut emitted = Vec::new();
+        let exit = run_event_loop(&rx, &running, &mut index, dir.path(), &opts, |e| {
+            emitted.push(e.file.clone())
+        });
+
+        assert_eq!(exit, LoopExit::Emitted);
+        assert_eq!(emitted, vec!["a.rs"]);
+    }
+
+    #[test]
+    fn test_event_loop_once_skips_changes_without_events() {
+        let (dir, mut index) = setup_index(&[("a.rs", "// TODO: one\n"), ("b.rs", "")]);
+        fs::write(dir.path().join("b.rs"), "// FIXME: new\n").unwrap();
+
+        let (tx, rx) = std::sync::mpsc::channel();
+        // a.rs is unchanged, so the first batch yields nothing
+        tx.send(change_batch(dir.path(), &["a.rs"])).unwrap();
+        tx.send(change_batch(dir.path(), &["b.rs"])).unwrap();
+
+        let running = AtomicBool::new(true);
+        let opts = LoopOptions {
+            filter_tags: &[],
+            once: true,
+            timeout: None,
+        };
+        let mut emitted = Vec::new();
+        let exit = run_event_loop(&rx, &running, &mut index, dir.path(), &opts, |e| {
+            emitted.push(e.file.clone())
+        });
+
+        assert_eq!(exit, LoopExit::Emitted);
+        assert_eq!(emitted, vec!["b.rs"]);
+    }
+
+    #[test]
+    fn test_event_loop_once_times_out() {
+        let (dir, mut index) = setup_index(&[("a.rs", "// TODO: one\n")]);
+        let (_tx, rx) = std::sync::mpsc::channel::<DebounceEventResult>();
+
+        let running = AtomicBool::new(true);
+        let opts = LoopOptions {
+            filter_tags: &[],
+            once: true,
+            timeout: Some(Duration::from_millis(50)),
+        };
+        let exit = run_event_loop(&rx, &running, &mut index, dir.path(), &opts, |_| {
+            panic!("no event expected")
+        });
+        assert_eq!(exit, LoopExit::TimedOut);
+    }
+
+    #[test]
+    fn test_event_loop_without_once_runs_until_disconnected() {
+        let (dir, mut index) = setup_index(&[("a.rs", ""), ("b.rs", "")]);
+        fs::write(dir.path().join("a.rs"), "// TODO: one\n").unwrap();
+        fs::write(dir.path().join("b.rs"), "// TODO: two\n").unwrap();
+
+        let (tx, rx) = std::sync::mpsc::channel();
+        tx.send(change_batch(dir.path(), &["a.rs", "b.rs"]))
+            .unwrap();
+        drop(tx);
+
+        let running = AtomicBool::new(true);
+        let opts = LoopOptions {
+            filter_tags: &[],
+            once: false,
+            timeout: None,
+        };
+        let mut count = 0;
+        let exit = run_event_loop(&rx, &running, &mut index, dir.path(), &opts, |_| count += 1);
+        assert_eq!(exit, LoopExit::Disconnected);
+        assert_eq!(count, 2);
+    }
+
     #[test]
     fn test_collect_changed_files_dedup() {
         let dir = TempDir::new().unwrap();
@@ -460,6 +677,26 @@ mod tests {
         assert_eq!(index.total_count(), 0);
     }
 
+    #[test]
+    fn test_update_file_streams_oversized_file_with_scan_large() {
+        let dir = TempDir::new().unwrap();
+        fs::write(dir.path().join("big.rs"), "fn a() {}\n").unwrap();
+        let config = Config {
+            scan_large: Some(true),
+            ..Config::default()
+        };
+        let mut index = TodoIndex::new(dir.path(), &config).unwrap();
+
+        let mut oversized = "x".repeat(MAX_FILE_SIZE as usize);
+        oversized.push_str("\n// TODO: generated binding\n");
+        fs::write(dir.path().join("big.rs"), &oversized).unwrap();
+
+        let update = index.update_file("big.rs").unwrap();
+        assert_eq!(update.added.len(), 1);
+        assert_eq!(update.added[0].line, 2);
+        assert_eq!(index.total_count(), 1);
+    }
+
     #[test]
     fn test_build_watch_event_delta() {
         let (dir, mut index) = setup_index(&[("a.rs", "// TODO: one\n")]);
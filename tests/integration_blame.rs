@@ -377,3 +377,147 @@ fn test_blame_config_stale_threshold() {
         .success()
         .stdout(predicate::str::contains("threshold: 1 days"));
 }
+
+#[test]
+fn test_blame_redact_emails_json_and_sarif() {
+    let dir = setup_git_repo(&[("main.rs", "// TODO: private\n")]);
+    let cwd = dir.path();
+
+    for format in ["json", "sarif"] {
+        todo_scan()
+            .args([
+                "blame",
+                "--redact-emails",
+                "--root",
+                cwd.to_str().unwrap(),
+                "--format",
+                format,
+            ])
+            .assert()
+            .success()
+            .stdout(predicate::str::contains("test@test.com").not())
+            .stdout(predicate::str::contains("redacted"))
+            .stdout(predicate::str::contains("Test Author"));
+    }
+}
+
+#[test]
+fn test_blame_redact_authors_markdown() {
+    let dir = setup_git_repo(&[("main.rs", "// TODO: private\n")]);
+    let cwd = dir.path();
+
+    todo_scan()
+        .args([
+            "blame",
+            "--redact-authors",
+            "--root",
+            cwd.to_str().unwrap(),
+            "--format",
+            "markdown",
+        ])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Test Author").not())
+        .stdout(predicate::str::contains("test@test.com").not())
+        .stdout(predicate::str::contains("| redacted |"));
+}
+
+#[test]
+fn test_blame_untracked_file_is_unknown() {
+    let dir = setup_git_repo(&[("main.rs", "// TODO: committed task\nfn main() {}\n")]);
+    let cwd = dir.path();
+    fs::write(cwd.join("scratch.rs"), "// FIXME: not committed yet\n").unwrap();
+
+    let output = todo_scan()
+        .args(["blame", "--root", cwd.to_str().unwrap(), "--format", "json"])
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+    let json: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+
+    assert_eq!(json["total"], 2);
+    assert_eq!(json["unknown_count"], 1);
+    let unknown = json["entries"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .find(|e| e["file"] == "scratch.rs")
+        .expect("untracked TODO should be kept");
+    assert_eq!(unknown["blame"]["author"], "unknown");
+    assert_eq!(unknown["blame"]["age_days"], 0);
+    assert_eq!(unknown["stale"], false);
+
+    todo_scan()
+        .args(["blame", "--root", cwd.to_str().unwrap()])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(
+            "L1: [FIXME] not committed yet @unknown (not in git history)",
+        ))
+        .stdout(predicate::str::contains(
+            "2 items, avg age 0 days, 0 stale, 1 unknown",
+        ));
+}
+
+#[test]
+fn test_blame_per_file_matches_per_line_blame() {
+    // Two commits a year apart, interleaving lines in one file
+    let dir = setup_git_repo(&[(
+        "main.rs",
+        "// TODO: old one\nfn a() {}\n// FIXME: old two\n",
+    )]);
+    let cwd = dir.path();
+    fs::write(
+        cwd.join("main.rs"),
+        "// TODO: old one\n// HACK: new one\nfn a() {}\n// FIXME: old two\n// BUG: new two\n",
+    )
+    .unwrap();
+    for args in [&["add", "."][..], &["commit", "-m", "second"][..]] {
+        process::Command::new("git")
+            .args(args)
+            .env("GIT_AUTHOR_DATE", "2001-02-03T04:05:06Z")
+            .env("GIT_COMMITTER_DATE", "2001-02-03T04:05:06Z")
+            .current_dir(cwd)
+            .output()
+            .unwrap();
+    }
+
+    let output = todo_scan()
+        .args(["blame", "--root", cwd.to_str().unwrap(), "--format", "json"])
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+    let json: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    let entries = json["entries"].as_array().unwrap();
+    assert_eq!(entries.len(), 4);
+
+    // One `git blame` for the whole file must agree with blaming each line
+    for entry in entries {
+        let line = entry["line"].as_u64().unwrap();
+        let range = format!("{line},{line}");
+        let out = process::Command::new("git")
+            .args(["blame", "--porcelain", "-L", &range, "--", "main.rs"])
+            .current_dir(cwd)
+            .output()
+            .unwrap();
+        let porcelain = String::from_utf8(out.stdout).unwrap();
+        let commit = &porcelain[..8];
+        let author_time: i64 = porcelain
+            .lines()
+            .find_map(|l| l.strip_prefix("author-time "))
+            .unwrap()
+            .parse()
+            .unwrap();
+        let now = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .unwrap()
+            .as_secs() as i64;
+
+        assert_eq!(entry["blame"]["commit"], commit, "line {line}");
+        assert_eq!(
+            entry["blame"]["age_days"],
+            ((now - author_time) / 86400) as u64,
+            "line {line}"
+        );
+    }
+}
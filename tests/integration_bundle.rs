@@ -0,0 +1,92 @@
+use assert_cmd::Command;
+use predicates::prelude::*;
+use std::fs;
+use std::process;
+use tempfile::TempDir;
+
+fn todo_scan() -> Command {
+    assert_cmd::cargo_bin_cmd!("todo-scan")
+}
+
+fn setup_git_project(files: &[(&str, &str)]) -> TempDir {
+    let dir = TempDir::new().unwrap();
+    for (path, content) in files {
+        let full_path = dir.path().join(path);
+        if let Some(parent) = full_path.parent() {
+            fs::create_dir_all(parent).unwrap();
+        }
+        fs::write(full_path, content).unwrap();
+    }
+    for args in [
+        &["init"][..],
+        &["config", "user.email", "test@test.com"][..],
+        &["config", "user.name", "Test"][..],
+        &["add", "."][..],
+        &["commit", "-m", "initial"][..],
+    ] {
+        process::Command::new("git")
+            .args(args)
+            .current_dir(dir.path())
+            .output()
+            .unwrap();
+    }
+    dir
+}
+
+fn read_json(path: &std::path::Path) -> serde_json::Value {
+    let content =
+        fs::read_to_string(path).unwrap_or_else(|_| panic!("{} should exist", path.display()));
+    serde_json::from_str(&content).unwrap()
+}
+
+#[test]
+fn test_bundle_writes_consistent_artifacts() {
+    let dir = setup_git_project(&[
+        (
+            "src/main.rs",
+            "// TODO: task one\n// FIXME(bob): task two\n",
+        ),
+        ("src/lib.rs", "// BUG!!: critical\n"),
+    ]);
+    let out = TempDir::new().unwrap();
+    let bundle_dir = out.path().join("artifacts/todo");
+
+    todo_scan()
+        .args([
+            "bundle",
+            "--root",
+            dir.path().to_str().unwrap(),
+            "--out",
+            bundle_dir.to_str().unwrap(),
+            "--history",
+            "0",
+        ])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Bundle written to"))
+        .stdout(predicate::str::contains(
+            "list.json, stats.json, blame.json, report.html",
+        ));
+
+    let list = read_json(&bundle_dir.join("list.json"));
+    let stats = read_json(&bundle_dir.join("stats.json"));
+    let blame = read_json(&bundle_dir.join("blame.json"));
+    let html = fs::read_to_string(bundle_dir.join("report.html")).unwrap();
+    assert!(html.starts_with("<!DOCTYPE html>"));
+    let start_marker = "const REPORT_DATA = ";
+    let start = html.find(start_marker).unwrap() + start_marker.len();
+    let end = html[start..].find(";\n").unwrap() + start;
+    let report: serde_json::Value = serde_json::from_str(&html[start..end]).unwrap();
+
+    // Every artifact comes from the same scan
+    assert_eq!(list["items"].as_array().unwrap().len(), 3);
+    assert_eq!(stats["total_items"], 3);
+    assert_eq!(blame["total"], 3);
+    assert_eq!(report["summary"]["total_items"], 3);
+    assert_eq!(report["summary"]["urgent_count"], 1);
+    assert_eq!(stats["priority_counts"]["urgent"], 1);
+
+    // list.json carries the same stable ids as `list --format json`
+    assert!(list["items"][0]["id"].is_string());
+    assert_eq!(blame["entries"][0]["blame"]["author"], "Test");
+}
@@ -57,6 +57,25 @@ fn test_check_fail_over_max() {
         .stdout(predicate::str::contains("exceeds max"));
 }
 
+#[test]
+fn test_check_fail_still_prints_profile() {
+    let dir = setup_project(&[("main.rs", "// TODO: one\n// TODO: two\n")]);
+
+    todo_scan()
+        .args([
+            "check",
+            "--profile",
+            "--root",
+            dir.path().to_str().unwrap(),
+            "--max",
+            "1",
+        ])
+        .assert()
+        .code(1)
+        .stderr(predicate::str::contains("profile:"))
+        .stderr(predicate::str::contains("scan"));
+}
+
 #[test]
 fn test_check_block_tags() {
     let dir = setup_project(&[("main.rs", "// BUG: critical issue\n// TODO: normal task\n")]);
@@ -338,6 +357,30 @@ fn test_check_expired_deadline_fails() {
         .stdout(predicate::str::contains("2020-01-01"));
 }
 
+#[test]
+fn test_check_require_issue() {
+    let dir = setup_project(&[(
+        "main.rs",
+        "// BUG: crash on save #12\n// TODO: untracked is fine\n",
+    )]);
+    let root = dir.path().to_str().unwrap();
+
+    todo_scan()
+        .args(["check", "--root", root, "--require-issue"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("PASS"));
+
+    fs::write(dir.path().join("lib.rs"), "// FIXME: leaks handles\n").unwrap();
+
+    todo_scan()
+        .args(["check", "--root", root, "--require-issue"])
+        .assert()
+        .code(1)
+        .stdout(predicate::str::contains("untracked_fixme"))
+        .stdout(predicate::str::contains("lib.rs:1"));
+}
+
 #[test]
 fn test_check_future_deadline_passes() {
     let dir = setup_project(&[("main.rs", "// TODO(2099-12-31): far future task\n")]);
@@ -424,3 +467,235 @@ fn test_check_max_fails_without_ignore() {
         .code(1)
         .stdout(predicate::str::contains("FAIL"));
 }
+
+// --- Check with --max-age-days (blame-based) ---
+
+fn git_commit_file(cwd: &std::path::Path, path: &str, content: &str, date: Option<&str>) {
+    fs::write(cwd.join(path), content).unwrap();
+    std::process::Command::new("git")
+        .args(["add", path])
+        .current_dir(cwd)
+        .output()
+        .unwrap();
+    let mut commit = std::process::Command::new("git");
+    commit.args(["commit", "-m", path]).current_dir(cwd);
+    if let Some(date) = date {
+        commit
+            .env("GIT_AUTHOR_DATE", date)
+            .env("GIT_COMMITTER_DATE", date);
+    }
+    commit.output().unwrap();
+}
+
+#[test]
+fn test_check_max_age_days_flags_only_old_todo() {
+    let dir = setup_git_repo(&[("README", "readme\n")]);
+    let cwd = dir.path();
+    git_commit_file(
+        cwd,
+        "old.rs",
+        "// TODO: ancient task\n",
+        Some("2000-01-01T00:00:00"),
+    );
+    git_commit_file(cwd, "recent.rs", "// TODO: fresh task\n", None);
+
+    todo_scan()
+        .args([
+            "check",
+            "--root",
+            cwd.to_str().unwrap(),
+            "--max-age-days",
+            "365",
+            "--format",
+            "json",
+        ])
+        .assert()
+        .code(1)
+        .stdout(predicate::str::contains("old_todo"))
+        .stdout(predicate::str::contains("old.rs:1"))
+        .stdout(predicate::str::contains("recent.rs").not());
+}
+
+#[test]
+fn test_check_max_age_days_skipped_outside_git() {
+    let dir = setup_project(&[("main.rs", "// TODO: task\n")]);
+
+    todo_scan()
+        .args([
+            "check",
+            "--root",
+            dir.path().to_str().unwrap(),
+            "--max-age-days",
+            "1",
+        ])
+        .assert()
+        .success()
+        .stderr(predicate::str::contains("skipping --max-age-days"));
+}
+
+#[test]
+fn test_check_exclude_tests_drops_test_todos_from_budget() {
+    let dir = setup_project(&[
+        ("src/main.rs", "// TODO: ship it\n"),
+        ("tests/it.rs", "// TODO: test one\n// TODO: test two\n"),
+        (".todo-scan.toml", "test_paths = [\"tests/**\"]\n"),
+    ]);
+    let root = dir.path().to_str().unwrap();
+
+    todo_scan()
+        .args(["check", "--root", root, "--max", "1"])
+        .assert()
+        .code(1);
+
+    let output = todo_scan()
+        .args([
+            "check",
+            "--root",
+            root,
+            "--max",
+            "1",
+            "--exclude-tests",
+            "--format",
+            "json",
+        ])
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+    let json: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(json["total"], 1);
+
+    // list is unaffected by test_paths
+    todo_scan()
+        .args(["list", "--root", root])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("3 items"));
+}
+
+#[test]
+fn test_check_exclude_tests_without_test_paths_fails() {
+    let dir = setup_project(&[("main.rs", "// TODO: one\n")]);
+
+    todo_scan()
+        .args([
+            "check",
+            "--root",
+            dir.path().to_str().unwrap(),
+            "--exclude-tests",
+        ])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("requires test_paths"));
+}
+
+#[test]
+fn test_check_max_per_author_uses_blame_for_unattributed_items() {
+    let dir = setup_git_repo(&[(
+        "main.rs",
+        "// TODO: first\n// TODO: second\n// TODO(alice): third\n",
+    )]);
+    let root = dir.path().to_str().unwrap();
+
+    // The committer "Test" owns two items, alice one: both at the limit
+    todo_scan()
+        .args(["check", "--root", root, "--max-per-author", "2"])
+        .assert()
+        .success();
+
+    todo_scan()
+        .args(["check", "--root", root, "--max-per-author", "1"])
+        .assert()
+        .code(1)
+        .stdout(predicate::str::contains("author_budget"))
+        .stdout(predicate::str::contains(
+            "Test owns 2 TODOs (max per author: 1)",
+        ))
+        .stdout(predicate::str::contains("alice").not());
+}
+
+#[test]
+fn test_check_max_per_author_count_unassigned() {
+    let dir = setup_project(&[(
+        "main.rs",
+        "// TODO: first\n// TODO: second\n// TODO(bob): third\n",
+    )]);
+    let root = dir.path().to_str().unwrap();
+
+    // Outside git only explicit authors count; unassigned items are exempt
+    todo_scan()
+        .args(["check", "--root", root, "--max-per-author", "1"])
+        .assert()
+        .success();
+
+    todo_scan()
+        .args([
+            "check",
+            "--root",
+            root,
+            "--max-per-author",
+            "1",
+            "--count-unassigned",
+        ])
+        .assert()
+        .code(1)
+        .stdout(predicate::str::contains("unassigned owns 2 TODOs"));
+}
+
+#[test]
+fn test_check_rejects_max_items() {
+    let dir = setup_project(&[("main.rs", "// TODO: one\n")]);
+
+    todo_scan()
+        .args([
+            "--max-items",
+            "3",
+            "check",
+            "--root",
+            dir.path().to_str().unwrap(),
+            "--max",
+            "5",
+        ])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains(
+            "--max-items is not supported by the check command",
+        ));
+}
+
+#[test]
+fn test_check_ignores_config_max_items() {
+    let todos: String = (1..=20).map(|i| format!("// TODO: task {i}\n")).collect();
+    let dir = setup_project(&[("main.rs", &todos), (".todo-scan.toml", "max_items = 3\n")]);
+
+    todo_scan()
+        .args([
+            "check",
+            "--root",
+            dir.path().to_str().unwrap(),
+            "--max",
+            "5",
+        ])
+        .assert()
+        .code(1)
+        .stdout(predicate::str::contains("Total TODOs (20) exceeds max (5)"));
+}
+
+#[test]
+fn test_check_rejects_path_style() {
+    let dir = setup_project(&[("main.rs", "// TODO: one\n")]);
+
+    todo_scan()
+        .args([
+            "--path-style",
+            "absolute",
+            "check",
+            "--root",
+            dir.path().to_str().unwrap(),
+        ])
+        .assert()
+        .failure()
+        .code(2)
+        .stderr(predicate::str::contains(
+            "--path-style is not supported by the check command",
+        ));
+}
@@ -19,6 +19,19 @@ fn setup_project(files: &[(&str, &str)]) -> TempDir {
     dir
 }
 
+// --- Stale detection ---
+
+#[test]
+fn test_clean_created_metadata_flags_old_todo() {
+    let dir = setup_project(&[("a.rs", "// TODO: migrate config created=2000-01-01\n")]);
+
+    todo_scan()
+        .args(["clean", "--root", dir.path().to_str().unwrap(), "--check"])
+        .assert()
+        .code(1)
+        .stdout(predicate::str::contains("Created 2000-01-01"));
+}
+
 // --- Duplicate detection ---
 
 #[test]
@@ -103,6 +116,84 @@ fn test_clean_check_exit_1_with_violations() {
         .code(1);
 }
 
+// --- --fail-on-stale / --fail-on-duplicate ---
+
+fn clean_exit_code(files: &[(&str, &str)], flags: &[&str]) -> i32 {
+    let dir = setup_project(files);
+    let mut args = vec!["clean", "--root", dir.path().to_str().unwrap()];
+    args.extend_from_slice(flags);
+    todo_scan()
+        .args(&args)
+        .output()
+        .unwrap()
+        .status
+        .code()
+        .unwrap()
+}
+
+const STALE: (&str, &str) = ("old.rs", "// TODO: migrate config #12 created=2000-01-01\n");
+const AGED: (&str, &str) = ("aged.rs", "// TODO: tidy up created=2000-01-01\n");
+const DUP_A: (&str, &str) = ("a.rs", "// TODO: same message\n");
+const DUP_B: (&str, &str) = ("b.rs", "// TODO: same message\n");
+
+#[test]
+fn test_clean_fail_on_stale_only_blocks_stale() {
+    assert_eq!(clean_exit_code(&[STALE], &["--fail-on-stale"]), 1);
+    assert_eq!(clean_exit_code(&[DUP_A, DUP_B], &["--fail-on-stale"]), 0);
+}
+
+#[test]
+fn test_clean_old_item_without_issue_is_stale_age() {
+    // No issue reference, so it's reported apart from stale issues
+    assert_eq!(clean_exit_code(&[AGED], &["--fail-on-stale"]), 0);
+    assert_eq!(clean_exit_code(&[AGED], &["--check"]), 1);
+
+    let dir = setup_project(&[AGED]);
+    let output = todo_scan()
+        .args([
+            "clean",
+            "--root",
+            dir.path().to_str().unwrap(),
+            "--format",
+            "json",
+        ])
+        .output()
+        .unwrap();
+    let json: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(json["stale_count"], 0);
+    assert_eq!(json["stale_age_count"], 1);
+    assert_eq!(json["violations"][0]["rule"], "stale_age");
+}
+
+#[test]
+fn test_clean_fail_on_duplicate_only_blocks_duplicates() {
+    assert_eq!(
+        clean_exit_code(&[DUP_A, DUP_B], &["--fail-on-duplicate"]),
+        1
+    );
+    assert_eq!(clean_exit_code(&[STALE], &["--fail-on-duplicate"]), 0);
+}
+
+#[test]
+fn test_clean_fail_on_both_flags() {
+    let both = ["--fail-on-stale", "--fail-on-duplicate"];
+    assert_eq!(clean_exit_code(&[STALE], &both), 1);
+    assert_eq!(clean_exit_code(&[DUP_A, DUP_B], &both), 1);
+    assert_eq!(
+        clean_exit_code(&[("a.rs", "// TODO: unique message\n")], &both),
+        0
+    );
+}
+
+#[test]
+fn test_clean_fail_on_flag_narrows_check() {
+    assert_eq!(
+        clean_exit_code(&[DUP_A, DUP_B], &["--check", "--fail-on-stale"]),
+        0
+    );
+    assert_eq!(clean_exit_code(&[DUP_A, DUP_B], &["--check"]), 1);
+}
+
 #[test]
 fn test_clean_no_check_exit_0_even_with_violations() {
     let dir = setup_project(&[
@@ -342,3 +433,206 @@ duplicates = false
         .success()
         .stdout(predicate::str::contains("PASS"));
 }
+
+// --- Deadline ownership ---
+
+#[test]
+fn test_clean_deadline_without_owner_flag() {
+    let dir = setup_project(&[
+        ("a.rs", "// TODO(2099-01-01): ship the migration\n"),
+        ("b.rs", "// TODO(alice, 2099-01-01): owned deadline\n"),
+    ]);
+
+    // Off by default
+    todo_scan()
+        .args(["clean", "--root", dir.path().to_str().unwrap(), "--check"])
+        .assert()
+        .success();
+
+    todo_scan()
+        .args([
+            "clean",
+            "--root",
+            dir.path().to_str().unwrap(),
+            "--check",
+            "--deadline-without-owner",
+        ])
+        .assert()
+        .code(1)
+        .stdout(predicate::str::contains("a.rs"))
+        .stdout(predicate::str::contains("deadline_without_owner"))
+        .stdout(predicate::str::contains("b.rs").not());
+}
+
+#[test]
+fn test_clean_config_enables_deadline_without_owner() {
+    let dir = setup_project(&[
+        ("a.rs", "// TODO(2099-01-01): ship the migration\n"),
+        (
+            ".todo-scan.toml",
+            r#"
+[clean]
+deadline_without_owner = true
+"#,
+        ),
+    ]);
+
+    let output = todo_scan()
+        .args([
+            "clean",
+            "--root",
+            dir.path().to_str().unwrap(),
+            "--format",
+            "json",
+        ])
+        .output()
+        .unwrap();
+    let json: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(json["deadline_without_owner_count"], 1);
+    assert_eq!(json["violations"][0]["rule"], "deadline_without_owner");
+}
+
+// --- Stale grace period ---
+
+fn git(cwd: &std::path::Path, args: &[&str], date: Option<&str>) {
+    let mut cmd = std::process::Command::new("git");
+    cmd.args(args).current_dir(cwd);
+    if let Some(date) = date {
+        cmd.env("GIT_AUTHOR_DATE", date)
+            .env("GIT_COMMITTER_DATE", date);
+    }
+    assert!(cmd.output().unwrap().status.success(), "git {:?}", args);
+}
+
+#[test]
+fn test_clean_stale_grace_days_exempts_moved_todo() {
+    let dir = setup_project(&[
+        ("old.rs", "// TODO: migrate config created=2000-01-01\n"),
+        ("stay.rs", "// TODO: drop shim created=2000-01-01\n"),
+    ]);
+    let cwd = dir.path();
+    git(cwd, &["init"], None);
+    git(cwd, &["config", "user.email", "test@test.com"], None);
+    git(cwd, &["config", "user.name", "Test"], None);
+    git(cwd, &["add", "."], None);
+    git(cwd, &["commit", "-m", "init"], Some("2000-01-01T00:00:00"));
+    // The old TODO moves to a new file today
+    git(cwd, &["mv", "old.rs", "moved.rs"], None);
+    git(cwd, &["commit", "-m", "move"], None);
+
+    let output = todo_scan()
+        .args([
+            "clean",
+            "--root",
+            cwd.to_str().unwrap(),
+            "--stale-grace-days",
+            "30",
+            "--format",
+            "json",
+        ])
+        .output()
+        .unwrap();
+    let json: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(json["stale_age_count"], 1);
+    assert_eq!(json["violations"][0]["file"], "stay.rs");
+}
+
+#[test]
+fn test_clean_without_grace_flags_moved_todo() {
+    let dir = setup_project(&[("moved.rs", "// TODO: migrate config created=2000-01-01\n")]);
+    let cwd = dir.path();
+    git(cwd, &["init"], None);
+    git(cwd, &["config", "user.email", "test@test.com"], None);
+    git(cwd, &["config", "user.name", "Test"], None);
+    git(cwd, &["add", "."], None);
+    git(cwd, &["commit", "-m", "init"], None);
+
+    todo_scan()
+        .args(["clean", "--root", cwd.to_str().unwrap(), "--check"])
+        .assert()
+        .code(1)
+        .stdout(predicate::str::contains("moved.rs"));
+}
+
+#[test]
+fn test_clean_stale_grace_days_without_old_commit_keeps_created_rule() {
+    let dir = setup_project(&[("a.rs", "// TODO: migrate config created=2000-01-01\n")]);
+    let cwd = dir.path();
+    git(cwd, &["init"], None);
+    git(cwd, &["config", "user.email", "test@test.com"], None);
+    git(cwd, &["config", "user.name", "Test"], None);
+    git(cwd, &["add", "."], None);
+    git(cwd, &["commit", "-m", "init"], None);
+
+    todo_scan()
+        .args([
+            "clean",
+            "--root",
+            cwd.to_str().unwrap(),
+            "--stale-grace-days",
+            "30",
+            "--check",
+        ])
+        .assert()
+        .code(1)
+        .stdout(predicate::str::contains("a.rs"))
+        .stderr(predicate::str::contains("skipping --stale-grace-days"));
+}
+
+#[test]
+fn test_clean_stale_grace_days_in_repo_without_head() {
+    let dir = setup_project(&[("a.rs", "// TODO: migrate config created=2000-01-01\n")]);
+    git(dir.path(), &["init"], None);
+
+    todo_scan()
+        .args([
+            "clean",
+            "--root",
+            dir.path().to_str().unwrap(),
+            "--stale-grace-days",
+            "30",
+            "--check",
+        ])
+        .assert()
+        .code(1)
+        .stderr(predicate::str::contains("skipping --stale-grace-days"));
+}
+
+#[test]
+fn test_clean_stale_grace_days_skipped_outside_git() {
+    let dir = setup_project(&[("a.rs", "// TODO: migrate config created=2000-01-01\n")]);
+
+    todo_scan()
+        .args([
+            "clean",
+            "--root",
+            dir.path().to_str().unwrap(),
+            "--stale-grace-days",
+            "30",
+            "--check",
+        ])
+        .assert()
+        .code(1)
+        .stderr(predicate::str::contains("skipping --stale-grace-days"));
+}
+
+#[test]
+fn test_clean_dup_scope_file_ignores_cross_file_duplicates() {
+    let dir = setup_project(&[
+        ("a.rs", "// TODO: implement feature\n"),
+        ("b.rs", "// TODO: implement feature\n"),
+    ]);
+    let root = dir.path().to_str().unwrap();
+
+    todo_scan()
+        .args(["clean", "--root", root, "--dup-scope", "repo"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("duplicate"));
+
+    todo_scan()
+        .args(["clean", "--root", root, "--dup-scope", "file"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("PASS"));
+}
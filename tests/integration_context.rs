@@ -35,7 +35,7 @@ fn test_context_text_output() {
         ])
         .assert()
         .success()
-        .stdout(predicate::str::contains("main.rs:3"))
+        .stdout(predicate::str::contains("main.rs:3 in main"))
         .stdout(predicate::str::contains("TODO: fix this"))
         .stdout(predicate::str::contains("let x = 1"))
         .stdout(predicate::str::contains("let y = 2"));
@@ -62,6 +62,7 @@ fn test_context_json_output() {
         .stdout(predicate::str::contains("\"file\": \"main.rs\""))
         .stdout(predicate::str::contains("\"line\": 3"))
         .stdout(predicate::str::contains("\"todo_line\""))
+        .stdout(predicate::str::contains("\"enclosing_symbol\": \"main\""))
         .stdout(predicate::str::contains("\"before\""))
         .stdout(predicate::str::contains("\"after\""));
 }
@@ -186,3 +187,49 @@ fn test_context_resolves_stable_id() {
         .stdout(predicate::str::contains("main.rs:3"))
         .stdout(predicate::str::contains("TODO: fix this"));
 }
+
+#[test]
+fn test_context_interactive_non_tty_prints_all_matches() {
+    let dir = setup_project(&[
+        ("src/a.rs", "fn a() {}\n// TODO: first task\n"),
+        ("src/b.rs", "// FIXME: second task\n"),
+        ("other.rs", "// TODO: elsewhere\n"),
+    ]);
+
+    todo_scan()
+        .args([
+            "context",
+            "src",
+            "--interactive",
+            "--root",
+            dir.path().to_str().unwrap(),
+        ])
+        .write_stdin("1\n")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("src/a.rs:2"))
+        .stdout(predicate::str::contains("src/b.rs:1"))
+        .stdout(predicate::str::contains("other.rs").not())
+        .stdout(predicate::str::contains("Select").not());
+}
+
+#[test]
+fn test_context_interactive_no_matches() {
+    let dir = setup_project(&[("main.rs", "fn main() {}\n")]);
+
+    todo_scan()
+        .args([
+            "context",
+            "--interactive",
+            "--root",
+            dir.path().to_str().unwrap(),
+        ])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("no TODOs match"));
+}
+
+#[test]
+fn test_context_requires_location_without_interactive() {
+    todo_scan().args(["context"]).assert().failure();
+}
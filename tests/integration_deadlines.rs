@@ -0,0 +1,154 @@
+use assert_cmd::Command;
+use predicates::prelude::*;
+use std::fs;
+use tempfile::TempDir;
+
+fn todo_scan() -> Command {
+    assert_cmd::cargo_bin_cmd!("todo-scan")
+}
+
+fn setup_project(files: &[(&str, &str)]) -> TempDir {
+    let dir = TempDir::new().unwrap();
+    for (path, content) in files {
+        let full_path = dir.path().join(path);
+        if let Some(parent) = full_path.parent() {
+            fs::create_dir_all(parent).unwrap();
+        }
+        fs::write(full_path, content).unwrap();
+    }
+    dir
+}
+
+#[test]
+fn test_deadlines_text_sorted_soonest_first() {
+    let dir = setup_project(&[(
+        "main.rs",
+        "// TODO(2031-03-01): later task\n// FIXME(alice, 2030-01-15): sooner task\n// TODO: no deadline\n",
+    )]);
+
+    todo_scan()
+        .args(["deadlines", "--root", dir.path().to_str().unwrap()])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(
+            "2030-01-15  main.rs:2 [FIXME] sooner task\n2031-03-01  main.rs:1 [TODO] later task",
+        ))
+        .stdout(predicate::str::contains("no deadline").not())
+        .stdout(predicate::str::contains("2 items with deadlines"));
+}
+
+#[test]
+fn test_deadlines_ics_format() {
+    let dir = setup_project(&[(
+        "main.rs",
+        "// TODO(2030-06-01): migrate, then drop; v1\n// TODO: no deadline\n",
+    )]);
+
+    let output = todo_scan()
+        .args([
+            "deadlines",
+            "--root",
+            dir.path().to_str().unwrap(),
+            "--format",
+            "ics",
+        ])
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+    let ics = String::from_utf8(output.stdout).unwrap();
+
+    assert!(ics.starts_with("BEGIN:VCALENDAR\r\n"));
+    assert_eq!(ics.matches("BEGIN:VEVENT").count(), 1);
+    assert!(ics.contains("\r\nDTSTART;VALUE=DATE:20300601\r\n"));
+    assert!(ics.contains("\r\nSUMMARY:TODO: migrate\\, then drop\\; v1\r\n"));
+}
+
+#[test]
+fn test_ics_format_rejected_for_other_commands() {
+    let dir = setup_project(&[("main.rs", "// TODO: x\n")]);
+
+    todo_scan()
+        .args([
+            "list",
+            "--root",
+            dir.path().to_str().unwrap(),
+            "--format",
+            "ics",
+        ])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains(
+            "only supported by the deadlines command",
+        ));
+}
+
+#[test]
+fn test_date_format_flag_applies_to_text_and_markdown_not_json() {
+    let dir = setup_project(&[("main.rs", "// TODO(alice, 2030-06-01): migrate\n")]);
+    let root = dir.path().to_str().unwrap();
+
+    todo_scan()
+        .args(["deadlines", "--root", root, "--date-format", "%b %-d, %Y"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Jun 1, 2030"));
+
+    todo_scan()
+        .args([
+            "list",
+            "--root",
+            root,
+            "--format",
+            "markdown",
+            "--date-format",
+            "%d/%m/%Y",
+        ])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("| 01/06/2030 |"));
+
+    todo_scan()
+        .args([
+            "list",
+            "--root",
+            root,
+            "--format",
+            "json",
+            "--date-format",
+            "%d/%m/%Y",
+        ])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("\"deadline\": \"2030-06-01\""));
+}
+
+#[test]
+fn test_date_format_from_config() {
+    let dir = setup_project(&[
+        (".todo-scan.toml", "date_format = \"%B %-d\"\n"),
+        ("main.rs", "// TODO(alice, 2030-06-01): migrate\n"),
+    ]);
+
+    todo_scan()
+        .args(["list", "--root", dir.path().to_str().unwrap()])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("[deadline: June 1]"));
+}
+
+#[test]
+fn test_date_format_rejects_unsupported_specifier() {
+    let dir = setup_project(&[("main.rs", "// TODO: x\n")]);
+
+    todo_scan()
+        .args([
+            "list",
+            "--root",
+            dir.path().to_str().unwrap(),
+            "--date-format",
+            "%H:%M",
+        ])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("Unsupported date format"));
+}
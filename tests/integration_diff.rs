@@ -88,6 +88,29 @@ fn test_diff_shows_removed_todos() {
         .stdout(predicate::str::contains("old task"));
 }
 
+#[test]
+fn test_diff_symbols_no_color() {
+    let dir = setup_git_repo(&[("main.rs", "// TODO: old task\nfn main() {}\n")]);
+    let cwd = dir.path();
+
+    fs::write(cwd.join("main.rs"), "// FIXME: new task\nfn main() {}\n").unwrap();
+
+    todo_scan()
+        .args([
+            "diff",
+            "HEAD",
+            "--symbols",
+            "--no-color",
+            "--root",
+            cwd.to_str().unwrap(),
+        ])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("[+] main.rs:1 [FIXME] new task"))
+        .stdout(predicate::str::contains("[-] main.rs:1 [TODO] old task"))
+        .stdout(predicate::str::contains("\x1b[").not());
+}
+
 #[test]
 fn test_diff_json_format() {
     let dir = setup_git_repo(&[("main.rs", "fn main() {}\n")]);
@@ -110,6 +133,84 @@ fn test_diff_json_format() {
         .stdout(predicate::str::contains("\"tag\": \"FIXME\""));
 }
 
+#[test]
+fn test_diff_junit_format() {
+    let dir = setup_git_repo(&[("main.rs", "// TODO: old task\nfn main() {}\n")]);
+    let cwd = dir.path();
+
+    fs::write(cwd.join("main.rs"), "// FIXME: new task\nfn main() {}\n").unwrap();
+
+    todo_scan()
+        .args([
+            "diff",
+            "HEAD",
+            "--root",
+            cwd.to_str().unwrap(),
+            "--format",
+            "junit",
+        ])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(
+            "<testsuite name=\"todo-scan diff HEAD\" tests=\"2\" failures=\"1\"",
+        ))
+        .stdout(predicate::str::contains(
+            "<failure type=\"added\" message=\"New FIXME since HEAD\">new task</failure>",
+        ))
+        .stdout(predicate::str::contains(
+            "<testcase classname=\"main.rs\" name=\"main.rs:1 [TODO] old task\"/>",
+        ));
+}
+
+#[test]
+fn test_diff_junit_by_file_format() {
+    let dir = setup_git_repo(&[("a.rs", "// TODO: old task\n"), ("b.rs", "fn b() {}\n")]);
+    let cwd = dir.path();
+
+    fs::write(cwd.join("a.rs"), "// TODO: new task\n").unwrap();
+    fs::write(cwd.join("b.rs"), "// BUG: broken\nfn b() {}\n").unwrap();
+
+    todo_scan()
+        .args([
+            "diff",
+            "HEAD",
+            "--root",
+            cwd.to_str().unwrap(),
+            "--format",
+            "junit-by-file",
+        ])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(
+            "<testsuites name=\"todo-scan diff HEAD\" tests=\"3\" failures=\"2\">",
+        ))
+        .stdout(predicate::str::contains(
+            "<testsuite name=\"a.rs\" tests=\"2\" failures=\"1\"",
+        ))
+        .stdout(predicate::str::contains(
+            "<testsuite name=\"b.rs\" tests=\"1\" failures=\"1\"",
+        ));
+}
+
+#[test]
+fn test_junit_format_rejected_for_other_commands() {
+    let dir = setup_git_repo(&[("main.rs", "// TODO: x\n")]);
+
+    todo_scan()
+        .args([
+            "list",
+            "--root",
+            dir.path().to_str().unwrap(),
+            "--format",
+            "junit",
+        ])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains(
+            "only supported by the diff command",
+        ));
+}
+
 #[test]
 fn test_diff_no_changes() {
     let dir = setup_git_repo(&[("main.rs", "// TODO: existing\nfn main() {}\n")]);
@@ -123,6 +224,36 @@ fn test_diff_no_changes() {
         .stdout(predicate::str::contains("+0 -0"));
 }
 
+#[test]
+fn test_diff_ignores_config_max_items() {
+    let dir = setup_git_repo(&[
+        ("main.rs", "// TODO: one\n// TODO: two\n// TODO: three\n"),
+        (".todo-scan.toml", "max_items = 1\n"),
+    ]);
+    let cwd = dir.path();
+
+    todo_scan()
+        .args(["diff", "HEAD", "--root", cwd.to_str().unwrap()])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("+0 -0"));
+
+    todo_scan()
+        .args([
+            "--max-items",
+            "1",
+            "diff",
+            "HEAD",
+            "--root",
+            cwd.to_str().unwrap(),
+        ])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains(
+            "--max-items is not supported by the diff command",
+        ));
+}
+
 #[test]
 fn test_diff_with_multiple_files_only_one_changed() {
     let dir = setup_git_repo(&[
@@ -376,6 +507,41 @@ fn test_diff_with_context() {
         .stdout(predicate::str::contains("let x = 1"));
 }
 
+#[test]
+fn test_diff_removed_context_comes_from_base_ref() {
+    let dir = setup_git_repo(&[(
+        "main.rs",
+        "fn main() {\n    let before = 1;\n    // TODO: old task\n    let after = 2;\n}\n",
+    )]);
+    let cwd = dir.path();
+
+    fs::write(cwd.join("main.rs"), "fn main() {\n    let other = 3;\n}\n").unwrap();
+
+    let output = todo_scan()
+        .args([
+            "diff",
+            "HEAD",
+            "--root",
+            cwd.to_str().unwrap(),
+            "-C",
+            "1",
+            "--format",
+            "json",
+        ])
+        .output()
+        .unwrap();
+
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    let json: serde_json::Value = serde_json::from_str(&stdout).unwrap();
+    let entry = &json["entries"][0];
+    assert_eq!(entry["status"], "removed");
+    let context = &entry["context"];
+    assert_eq!(context["before"][0]["line_number"], 2);
+    assert_eq!(context["before"][0]["content"], "    let before = 1;");
+    assert_eq!(context["after"][0]["line_number"], 4);
+    assert_eq!(context["after"][0]["content"], "    let after = 2;");
+}
+
 #[test]
 fn test_diff_detail_minimal_json() {
     let dir = setup_git_repo(&[("main.rs", "fn main() {}\n")]);
@@ -560,3 +726,52 @@ fn test_diff_full_detail_auto_context_json() {
         "full detail should include match_key"
     );
 }
+
+#[test]
+fn test_diff_no_fallback_fails_outside_git() {
+    let dir = TempDir::new().unwrap();
+    fs::write(dir.path().join("main.rs"), "// TODO: task\n").unwrap();
+
+    todo_scan()
+        .args([
+            "diff",
+            "HEAD",
+            "--no-fallback",
+            "--root",
+            dir.path().to_str().unwrap(),
+        ])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("error:"));
+}
+
+#[cfg(feature = "notebooks")]
+#[test]
+fn test_diff_notebook_scans_base_cells_like_current() {
+    let notebook = |code: &str| {
+        format!(
+            r##"{{"cells": [{{"cell_type": "code", "outputs": [], "source": ["{code}\n", "# TODO: drop outliers\n"]}}], "metadata": {{}}, "nbformat": 4, "nbformat_minor": 5}}"##
+        )
+    };
+    let dir = setup_git_repo(&[("n.ipynb", &notebook("df = load()"))]);
+    let cwd = dir.path();
+
+    // Touch the cell without changing its TODO
+    fs::write(cwd.join("n.ipynb"), notebook("df = load(cache=True)")).unwrap();
+
+    let output = todo_scan()
+        .args([
+            "diff",
+            "HEAD",
+            "--root",
+            cwd.to_str().unwrap(),
+            "--format",
+            "json",
+        ])
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+    let json: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(json["added_count"], 0);
+    assert_eq!(json["removed_count"], 0);
+}
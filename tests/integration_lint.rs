@@ -68,6 +68,18 @@ fn test_lint_fail_bare_tag() {
         .stdout(predicate::str::contains("no_bare_tags"));
 }
 
+#[test]
+fn test_lint_fail_still_prints_profile() {
+    let dir = setup_project(&[("main.rs", "// TODO:\n")]);
+
+    todo_scan()
+        .args(["lint", "--profile", "--root", dir.path().to_str().unwrap()])
+        .assert()
+        .code(1)
+        .stderr(predicate::str::contains("profile:"))
+        .stderr(predicate::str::contains("other"));
+}
+
 #[test]
 fn test_lint_fail_bare_tag_no_colon() {
     let dir = setup_project(&[("main.rs", "// TODO\n")]);
@@ -506,3 +518,214 @@ fn test_lint_multiple_files() {
         .success()
         .stdout(predicate::str::contains("PASS"));
 }
+
+// --- Autofix ---
+
+fn init_git_user(dir: &TempDir, name: &str) {
+    std::process::Command::new("git")
+        .args(["init"])
+        .current_dir(dir.path())
+        .output()
+        .unwrap();
+    std::process::Command::new("git")
+        .args(["config", "user.name", name])
+        .current_dir(dir.path())
+        .output()
+        .unwrap();
+}
+
+#[test]
+fn test_lint_fix_author_from_git_inserts_author() {
+    let dir = setup_project(&[("main.rs", "fn main() {} // TODO: add logging\n")]);
+    init_git_user(&dir, "alice");
+
+    todo_scan()
+        .args([
+            "lint",
+            "--root",
+            dir.path().to_str().unwrap(),
+            "--require-author",
+            "TODO",
+            "--fix",
+            "--author-from-git",
+        ])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("PASS"))
+        .stderr(predicate::str::contains("Fixed 1"));
+
+    let content = fs::read_to_string(dir.path().join("main.rs")).unwrap();
+    assert_eq!(content, "fn main() {} // TODO(@alice): add logging\n");
+}
+
+#[test]
+fn test_lint_fix_author_from_git_is_idempotent() {
+    let dir = setup_project(&[("main.rs", "// TODO: add logging\r\n")]);
+    init_git_user(&dir, "alice");
+
+    for _ in 0..2 {
+        todo_scan()
+            .args([
+                "lint",
+                "--root",
+                dir.path().to_str().unwrap(),
+                "--no-cache",
+                "--require-author",
+                "TODO",
+                "--fix",
+                "--author-from-git",
+            ])
+            .assert()
+            .success();
+    }
+
+    let content = fs::read_to_string(dir.path().join("main.rs")).unwrap();
+    assert_eq!(content, "// TODO(@alice): add logging\r\n");
+}
+
+#[test]
+fn test_lint_author_from_git_without_fix_is_dry_run() {
+    let dir = setup_project(&[("main.rs", "// TODO: add logging\n")]);
+    init_git_user(&dir, "alice");
+
+    todo_scan()
+        .args([
+            "lint",
+            "--root",
+            dir.path().to_str().unwrap(),
+            "--require-author",
+            "TODO",
+            "--author-from-git",
+        ])
+        .assert()
+        .code(1)
+        .stdout(predicate::str::contains("TODO(@alice): <message>"));
+
+    let content = fs::read_to_string(dir.path().join("main.rs")).unwrap();
+    assert_eq!(content, "// TODO: add logging\n");
+}
+
+#[test]
+fn test_lint_fix_requires_author_source() {
+    let dir = setup_project(&[("main.rs", "// TODO: add logging\n")]);
+
+    todo_scan()
+        .args(["lint", "--root", dir.path().to_str().unwrap(), "--fix"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("--author-from-git"));
+}
+
+// --- Severity and exit codes ---
+
+#[test]
+fn test_lint_mixed_severity_exit_codes() {
+    let dir = setup_project(&[
+        ("main.rs", "// TODO:\n// TODO: this message is rather long\n"),
+        (
+            ".todo-scan.toml",
+            "[lint]\nmax_message_length = 10\n[lint.severity]\nmax_message_length = \"warning\"\n[lint.exit_codes]\nerror = 2\nwarning = 3\n",
+        ),
+    ]);
+
+    // The error-level no_bare_tags violation decides the exit code
+    todo_scan()
+        .args(["lint", "--root", dir.path().to_str().unwrap()])
+        .assert()
+        .code(2)
+        .stdout(predicate::str::contains("max_message_length (warning)"));
+
+    fs::write(
+        dir.path().join("main.rs"),
+        "// TODO: this message is rather long\n",
+    )
+    .unwrap();
+    todo_scan()
+        .args(["lint", "--root", dir.path().to_str().unwrap()])
+        .assert()
+        .code(3);
+}
+
+#[test]
+fn test_lint_warning_is_advisory_by_default() {
+    let dir = setup_project(&[
+        ("main.rs", "// todo: lowercase tag\n"),
+        (
+            ".todo-scan.toml",
+            "[lint.severity]\nuppercase_tag = \"warning\"\n",
+        ),
+    ]);
+
+    todo_scan()
+        .args([
+            "lint",
+            "--root",
+            dir.path().to_str().unwrap(),
+            "--format",
+            "json",
+        ])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("\"severity\": \"warning\""))
+        .stdout(predicate::str::contains("\"passed\": true"));
+
+    todo_scan()
+        .args(["lint", "--root", dir.path().to_str().unwrap()])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("PASS"))
+        .stdout(predicate::str::contains("uppercase_tag (warning)"));
+}
+
+#[test]
+fn test_lint_rejects_max_items() {
+    let dir = setup_project(&[("main.rs", "// TODO: implement this feature\n")]);
+
+    todo_scan()
+        .args([
+            "--max-items",
+            "1",
+            "lint",
+            "--root",
+            dir.path().to_str().unwrap(),
+        ])
+        .assert()
+        .code(2)
+        .stderr(predicate::str::contains(
+            "--max-items is not supported by the lint command",
+        ));
+}
+
+#[test]
+fn test_lint_ignores_config_max_items() {
+    let dir = setup_project(&[
+        ("a.rs", "// TODO: implement this feature\n"),
+        ("b.rs", "// TODO:\n"),
+        (".todo-scan.toml", "max_items = 1\n"),
+    ]);
+
+    todo_scan()
+        .args(["lint", "--root", dir.path().to_str().unwrap()])
+        .assert()
+        .code(1);
+}
+
+#[test]
+fn test_lint_rejects_path_style() {
+    let dir = setup_project(&[("main.rs", "// TODO: one\n")]);
+
+    todo_scan()
+        .args([
+            "--path-style",
+            "absolute",
+            "lint",
+            "--root",
+            dir.path().to_str().unwrap(),
+        ])
+        .assert()
+        .failure()
+        .code(2)
+        .stderr(predicate::str::contains(
+            "--path-style is not supported by the lint command",
+        ));
+}
@@ -79,80 +79,55 @@ fn test_list_json_format() {
 }
 
 #[test]
-fn test_list_alias_ls() {
-    let dir = setup_project(&[("main.rs", "// TODO: alias test\n")]);
-
-    todo_scan()
-        .args(["ls", "--root", dir.path().to_str().unwrap()])
-        .assert()
-        .success()
-        .stdout(predicate::str::contains("TODO"));
-}
-
-#[test]
-fn test_list_empty_project() {
-    let dir = setup_project(&[("main.rs", "fn main() {}\n")]);
+fn test_list_json_byte_offset_points_at_tag() {
+    let content = "fn main() {}\n\n    // FIXME: here\n";
+    let dir = setup_project(&[("main.rs", content)]);
 
-    todo_scan()
-        .args(["list", "--root", dir.path().to_str().unwrap()])
-        .assert()
-        .success()
-        .stdout(predicate::str::contains("0 items"));
+    let output = todo_scan()
+        .args([
+            "list",
+            "--root",
+            dir.path().to_str().unwrap(),
+            "--format",
+            "json",
+        ])
+        .output()
+        .unwrap();
+    let json: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    let offset = json["items"][0]["byte_offset"].as_u64().unwrap() as usize;
+    assert_eq!(offset, 21);
+    assert_eq!(&content[offset..offset + 5], "FIXME");
 }
 
 #[test]
-fn test_list_with_author_and_issue() {
-    let dir = setup_project(&[("main.rs", "// TODO(alice): fix issue #123\n")]);
+fn test_list_json_root_key_wraps_output() {
+    let dir = setup_project(&[("main.rs", "// TODO: json test\n")]);
 
-    todo_scan()
+    let output = todo_scan()
         .args([
             "list",
             "--root",
             dir.path().to_str().unwrap(),
             "--format",
             "json",
+            "--json-root-key",
+            "results",
         ])
-        .assert()
-        .success()
-        .stdout(predicate::str::contains("\"author\": \"alice\""))
-        .stdout(predicate::str::contains("\"issue_ref\": \"#123\""));
-}
-
-#[test]
-fn test_list_text_issue_ref_no_double_hash() {
-    let dir = setup_project(&[("main.rs", "// TODO(alice): fix issue #123\n")]);
+        .output()
+        .unwrap();
+    assert!(output.status.success());
 
-    todo_scan()
-        .args(["list", "--root", dir.path().to_str().unwrap()])
-        .assert()
-        .success()
-        .stdout(predicate::str::contains("(#123)"))
-        .stdout(predicate::str::contains("(##123)").not());
+    let json: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    let obj = json.as_object().unwrap();
+    assert_eq!(obj.len(), 1);
+    assert_eq!(json["results"]["items"][0]["message"], "json test");
 }
 
 #[test]
-fn test_list_filters_false_positives() {
-    let dir = setup_project(&[(
-        "main.rs",
-        r#"// TODO: real comment
-let service = TodoService::new();
-if isTodoCompleted() { return; }
-let msg = "TODO: not real";
-let todo_count = 42;
-// FIXME(bob): another real comment
-struct TodoItem { done: bool }
-"#,
-    )]);
-
-    // Verify only 2 real comment items found (text output)
-    todo_scan()
-        .args(["list", "--root", dir.path().to_str().unwrap()])
-        .assert()
-        .success()
-        .stdout(predicate::str::contains("2 items"));
+fn test_list_json_without_root_key_is_bare() {
+    let dir = setup_project(&[("main.rs", "// TODO: json test\n")]);
 
-    // Verify correct messages via JSON output
-    todo_scan()
+    let output = todo_scan()
         .args([
             "list",
             "--root",
@@ -160,156 +135,214 @@ struct TodoItem { done: bool }
             "--format",
             "json",
         ])
-        .assert()
-        .success()
-        .stdout(predicate::str::contains("\"message\": \"real comment\""))
-        .stdout(predicate::str::contains(
-            "\"message\": \"another real comment\"",
-        ));
+        .output()
+        .unwrap();
+
+    let json: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert!(json.get("results").is_none());
+    assert_eq!(json["items"][0]["message"], "json test");
+}
+
+fn list_output(root: &std::path::Path, format: &str) -> String {
+    let output = todo_scan()
+        .args(["list", "--root", root.to_str().unwrap(), "--format", format])
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+    String::from_utf8(output.stdout).unwrap()
 }
 
 #[test]
-fn test_list_multi_language_comments() {
+fn test_list_summary_matches_full_scan() {
     let dir = setup_project(&[
-        ("app.py", "# TODO: python todo\nx = 1\n"),
-        ("style.css", "/* FIXME: css fixme */\n"),
-        ("query.sql", "-- HACK: sql hack\n"),
-        ("page.html", "<!-- NOTE: html note -->\n"),
+        ("a.rs", "// TODO: one\n// TODO!!: two\n// FIXME!: three\n"),
+        ("b.rs", "// BUG: four\n"),
     ]);
 
-    todo_scan()
-        .args(["list", "--root", dir.path().to_str().unwrap()])
-        .assert()
-        .success()
-        .stdout(predicate::str::contains("4 items"));
+    let full: serde_json::Value = serde_json::from_str(&list_output(dir.path(), "json")).unwrap();
+    let items = full["items"].as_array().unwrap();
+
+    let output = todo_scan()
+        .args(["list", "--root", dir.path().to_str().unwrap(), "--summary"])
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+    let summary: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+
+    assert!(summary.get("items").is_none());
+    assert_eq!(summary["total"], items.len());
+    assert_eq!(summary["files"], 2);
+    assert_eq!(summary["by_tag"]["TODO"], 2);
+    assert_eq!(summary["by_tag"]["FIXME"], 1);
+    assert_eq!(summary["by_tag"]["BUG"], 1);
+    assert_eq!(summary["by_priority"]["normal"], 2);
+    assert_eq!(summary["by_priority"]["high"], 1);
+    assert_eq!(summary["by_priority"]["urgent"], 1);
 }
 
 #[test]
-fn test_list_github_actions_format() {
+fn test_list_csv_wide_with_context() {
     let dir = setup_project(&[(
         "main.rs",
-        "// TODO: implement feature\n// BUG: critical issue\n",
+        "fn main() {\n    // TODO: add, \"quoted\" arg\n}\n",
     )]);
 
-    todo_scan()
+    let output = todo_scan()
         .args([
             "list",
             "--root",
             dir.path().to_str().unwrap(),
             "--format",
-            "github-actions",
+            "csv-wide",
+            "-C",
+            "1",
         ])
-        .assert()
-        .success()
-        .stdout(predicate::str::contains(
-            "::warning file=main.rs,line=1,title=TODO::[TODO] implement feature",
-        ))
-        .stdout(predicate::str::contains(
-            "::error file=main.rs,line=2,title=BUG::[BUG] critical issue",
-        ))
-        .stdout(predicate::str::contains(
-            "::notice::todo-scan: 2 items found",
-        ));
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+    let csv = String::from_utf8(output.stdout).unwrap();
+    assert_eq!(
+        csv,
+        "file,line,tag,priority,author,issue_ref,deadline,message,context_before,context_after\r\n\
+         main.rs,2,TODO,normal,,,,\"add, \"\"quoted\"\" arg\",fn main() {,}\r\n"
+    );
 }
 
 #[test]
-fn test_list_sarif_format() {
-    let dir = setup_project(&[("main.rs", "// TODO: sarif test\n")]);
+fn test_list_codeclimate_format() {
+    let dir = setup_project(&[("src/main.rs", "// FIXME: leaks handles\n")]);
 
-    todo_scan()
+    let output = todo_scan()
         .args([
             "list",
             "--root",
             dir.path().to_str().unwrap(),
             "--format",
-            "sarif",
+            "codeclimate",
         ])
-        .assert()
-        .success()
-        .stdout(predicate::str::contains("\"version\": \"2.1.0\""))
-        .stdout(predicate::str::contains("\"ruleId\": \"todo-scan/TODO\""))
-        .stdout(predicate::str::contains("\"text\": \"sarif test\""));
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+    let issues: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    let issue = &issues[0];
+    assert_eq!(issue["type"], "issue");
+    assert_eq!(issue["check_name"], "todo-scan/FIXME");
+    assert_eq!(issue["severity"], "major");
+    assert_eq!(issue["location"]["path"], "src/main.rs");
+    assert_eq!(issue["location"]["lines"]["begin"], 1);
+    assert!(issue["fingerprint"].is_string());
 }
 
 #[test]
-fn test_list_markdown_format() {
-    let dir = setup_project(&[("main.rs", "// TODO(alice): implement feature #42\n")]);
+fn test_list_markdown_by_tag_section() {
+    let dir = setup_project(&[("main.rs", "// TODO: later\n// BUG: crash\n")]);
+    let root = dir.path().to_str().unwrap();
 
     todo_scan()
         .args([
             "list",
             "--root",
-            dir.path().to_str().unwrap(),
+            root,
             "--format",
             "markdown",
+            "--by-tag-section",
         ])
         .assert()
         .success()
-        .stdout(predicate::str::contains(
-            "| File | Line | Tag | Priority | Message | Author | Issue |",
-        ))
-        .stdout(predicate::str::contains("TODO"))
-        .stdout(predicate::str::contains("alice"))
-        .stdout(predicate::str::contains("**1 items found**"));
-}
+        .stdout(
+            predicate::str::is_match(
+                r"(?s)## BUG.*\| main.rs \| 2 \| BUG \|.*## TODO.*\| main.rs \| 1 \| TODO \|",
+            )
+            .unwrap(),
+        );
 
-// --- Deadline in output ---
+    todo_scan()
+        .args(["list", "--root", root, "--by-tag-section"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains(
+            "--by-tag-section requires a markdown format",
+        ));
+}
 
+#[cfg(feature = "notebooks")]
 #[test]
-fn test_list_deadline_in_json() {
-    let dir = setup_project(&[("main.rs", "// TODO(2025-06-01): deadline task\n")]);
+fn test_list_scans_notebook_code_cells() {
+    let notebook = r##"{"cells": [
+        {"cell_type": "markdown", "source": ["TODO: prose, not code"]},
+        {"cell_type": "code", "outputs": [], "source": ["df = load()\n", "# TODO: drop outliers\n"]}
+    ], "metadata": {}, "nbformat": 4, "nbformat_minor": 5}"##;
+    let dir = setup_project(&[("analysis.ipynb", notebook)]);
 
     todo_scan()
         .args([
             "list",
             "--root",
             dir.path().to_str().unwrap(),
-            "--format",
-            "json",
+            "--group-by",
+            "none",
         ])
         .assert()
         .success()
-        .stdout(predicate::str::contains("\"deadline\": \"2025-06-01\""));
+        .stdout(predicate::str::contains(
+            "analysis.ipynb#cell2:2 [TODO] drop outliers",
+        ))
+        .stdout(predicate::str::contains("1 items"));
 }
 
+#[cfg(feature = "notebooks")]
 #[test]
-fn test_list_author_and_deadline_in_json() {
-    let dir = setup_project(&[("main.rs", "// TODO(alice, 2025-06-01): task with both\n")]);
+fn test_list_notebook_items_keep_real_path() {
+    let notebook = r##"{"cells": [
+        {"cell_type": "markdown", "source": ["# Analysis"]},
+        {"cell_type": "code", "outputs": [], "source": ["df = load()\n", "# TODO: drop outliers\n"]}
+    ], "metadata": {}, "nbformat": 4, "nbformat_minor": 5}"##;
+    let dir = setup_project(&[("analysis.ipynb", notebook)]);
+    let root = dir.path().to_str().unwrap();
 
+    let output = todo_scan()
+        .args(["list", "--root", root, "--format", "json"])
+        .output()
+        .unwrap();
+    let json: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(json["items"][0]["file"], "analysis.ipynb");
+    assert_eq!(json["items"][0]["cell"], 2);
+    assert_eq!(json["items"][0]["line"], 2);
+
+    let output = todo_scan()
+        .args(["list", "--root", root, "--format", "sarif"])
+        .output()
+        .unwrap();
+    let sarif: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(
+        sarif["runs"][0]["results"][0]["locations"][0]["physicalLocation"]["artifactLocation"]
+            ["uri"],
+        "analysis.ipynb"
+    );
+
+    // Context comes from the cell source, not the notebook's JSON
     todo_scan()
-        .args([
-            "list",
-            "--root",
-            dir.path().to_str().unwrap(),
-            "--format",
-            "json",
-        ])
+        .args(["list", "--root", root, "--group-by", "none", "-C", "1"])
         .assert()
         .success()
-        .stdout(predicate::str::contains("\"author\": \"alice\""))
-        .stdout(predicate::str::contains("\"deadline\": \"2025-06-01\""));
-}
-
-#[test]
-fn test_list_no_deadline_null_in_json() {
-    let dir = setup_project(&[("main.rs", "// TODO: no deadline\n")]);
+        .stdout(predicate::str::contains("analysis.ipynb#cell2:2"))
+        .stdout(predicate::str::contains("df = load()"));
 
     todo_scan()
-        .args([
-            "list",
-            "--root",
-            dir.path().to_str().unwrap(),
-            "--format",
-            "json",
-        ])
+        .args(["context", "analysis.ipynb#cell2:2", "--root", root])
         .assert()
         .success()
-        .stdout(predicate::str::contains("\"deadline\": null"));
+        .stdout(predicate::str::contains("df = load()"));
 }
 
 #[test]
-fn test_list_quarter_deadline_in_json() {
-    let dir = setup_project(&[("main.rs", "// TODO(2025-Q2): quarter deadline\n")]);
+fn test_list_clang_format() {
+    let dir = setup_project(&[(
+        "src/main.rs",
+        "// FIXME: leaks handles
+// TODO: add retries
+",
+    )]);
 
     todo_scan()
         .args([
@@ -317,43 +350,42 @@ fn test_list_quarter_deadline_in_json() {
             "--root",
             dir.path().to_str().unwrap(),
             "--format",
-            "json",
+            "clang",
         ])
         .assert()
         .success()
-        .stdout(predicate::str::contains("\"deadline\": \"2025-06-30\""));
+        .stdout(predicate::str::contains(
+            "src/main.rs:1: error: [FIXME] leaks handles\n",
+        ))
+        .stdout(predicate::str::contains(
+            "src/main.rs:2: warning: [TODO] add retries\n",
+        ));
 }
 
-// --- Filtering ---
-
 #[test]
-fn test_list_filter_priority() {
-    let dir = setup_project(&[(
-        "main.rs",
-        "// TODO!!: urgent task\n// TODO!: high task\n// TODO: normal task\n",
-    )]);
+fn test_clang_rejected_for_other_commands() {
+    let dir = setup_project(&[("main.rs", "// TODO: x\n")]);
 
     todo_scan()
         .args([
-            "list",
+            "stats",
             "--root",
             dir.path().to_str().unwrap(),
-            "--priority",
-            "urgent",
+            "--format",
+            "clang",
         ])
         .assert()
-        .success()
-        .stdout(predicate::str::contains("urgent task"))
-        .stdout(predicate::str::contains("high task").not())
-        .stdout(predicate::str::contains("normal task").not())
-        .stdout(predicate::str::contains("1 items"));
+        .failure()
+        .stderr(predicate::str::contains(
+            "--format clang is only supported by the list command",
+        ));
 }
 
 #[test]
-fn test_list_filter_author() {
+fn test_list_html_format() {
     let dir = setup_project(&[(
         "main.rs",
-        "// TODO(alice): alice task\n// TODO(bob): bob task\n// TODO: no author\n",
+        "// TODO: escape <script> tags\n// FIXME: second\n",
     )]);
 
     todo_scan()
@@ -361,44 +393,40 @@ fn test_list_filter_author() {
             "list",
             "--root",
             dir.path().to_str().unwrap(),
-            "--author",
-            "alice",
+            "--format",
+            "html",
         ])
         .assert()
         .success()
-        .stdout(predicate::str::contains("alice task"))
-        .stdout(predicate::str::contains("bob task").not())
-        .stdout(predicate::str::contains("no author").not())
-        .stdout(predicate::str::contains("1 items"));
+        .stdout(predicate::str::starts_with("<!DOCTYPE html>"))
+        .stdout(predicate::str::contains("escape &lt;script&gt; tags"))
+        .stdout(predicate::str::contains("2 items in 1 files"));
 }
 
 #[test]
-fn test_list_filter_path() {
-    let dir = setup_project(&[
-        ("src/lib.rs", "// TODO: in src\n"),
-        ("tests/test.rs", "// TODO: in tests\n"),
-    ]);
+fn test_html_rejected_for_other_commands() {
+    let dir = setup_project(&[("main.rs", "// TODO: x\n")]);
 
     todo_scan()
         .args([
-            "list",
+            "stats",
             "--root",
             dir.path().to_str().unwrap(),
-            "--path",
-            "src/**",
+            "--format",
+            "html",
         ])
         .assert()
-        .success()
-        .stdout(predicate::str::contains("in src"))
-        .stdout(predicate::str::contains("in tests").not())
-        .stdout(predicate::str::contains("1 items"));
+        .failure()
+        .stderr(predicate::str::contains(
+            "--format html is only supported by the list command",
+        ));
 }
 
 #[test]
-fn test_list_limit() {
+fn test_list_markdown_badges() {
     let dir = setup_project(&[(
         "main.rs",
-        "// TODO: first\n// TODO: second\n// TODO: third\n",
+        "// TODO!!: urgent one\n// TODO: normal\n// FIXME: also normal\n",
     )]);
 
     todo_scan()
@@ -406,477 +434,1938 @@ fn test_list_limit() {
             "list",
             "--root",
             dir.path().to_str().unwrap(),
+            "--format",
+            "markdown",
+            "--badges",
             "--limit",
-            "2",
+            "1",
         ])
         .assert()
         .success()
-        .stdout(predicate::str::contains("2 items"));
+        .stdout(predicate::str::starts_with(
+            "![TODOs: 3](https://img.shields.io/badge/TODOs-3-blue) \
+             ![urgent: 1](https://img.shields.io/badge/urgent-1-red)\n",
+        ));
 }
 
-// --- Group by ---
-
 #[test]
-fn test_list_group_by_tag() {
-    let dir = setup_project(&[(
-        "main.rs",
-        "// TODO: task one\n// FIXME: task two\n// TODO: task three\n",
-    )]);
+fn test_list_badges_requires_markdown() {
+    let dir = setup_project(&[("main.rs", "// TODO: x\n")]);
 
     todo_scan()
-        .args([
-            "list",
-            "--root",
-            dir.path().to_str().unwrap(),
-            "--group-by",
-            "tag",
-        ])
+        .args(["list", "--root", dir.path().to_str().unwrap(), "--badges"])
         .assert()
-        .success()
-        .stdout(predicate::str::contains("TODO (2 items)"))
-        .stdout(predicate::str::contains("FIXME (1 items)"))
-        .stdout(predicate::str::contains("3 items in 2 groups"));
+        .failure()
+        .stderr(predicate::str::contains(
+            "--badges requires a markdown format",
+        ));
 }
 
 #[test]
-fn test_list_group_by_priority() {
-    let dir = setup_project(&[(
-        "main.rs",
-        "// TODO!!: urgent task\n// TODO!: high task\n// TODO: normal task\n",
-    )]);
+fn test_csv_wide_rejected_for_other_commands() {
+    let dir = setup_project(&[("main.rs", "// TODO: x\n")]);
 
     todo_scan()
         .args([
-            "list",
+            "stats",
             "--root",
             dir.path().to_str().unwrap(),
-            "--group-by",
-            "priority",
+            "--format",
+            "csv-wide",
         ])
         .assert()
-        .success()
-        .stdout(predicate::str::contains("!! Urgent (1 items)"))
-        .stdout(predicate::str::contains("! High (1 items)"))
-        .stdout(predicate::str::contains("Normal (1 items)"))
-        .stdout(predicate::str::contains("3 items in 3 groups"));
+        .failure()
+        .stderr(predicate::str::contains(
+            "only supported by the list command",
+        ));
 }
 
 #[test]
-fn test_list_group_by_author() {
-    let dir = setup_project(&[(
-        "main.rs",
-        "// TODO(alice): alice task\n// TODO(bob): bob task\n// TODO: no author\n",
-    )]);
+fn test_list_tag_labels_in_markdown_not_sarif() {
+    let dir = setup_project(&[
+        ("main.rs", "// BUG: off by one\n"),
+        (".todo-scan.toml", "[tag_labels]\nBUG = \"🐛 Bug\"\n"),
+    ]);
 
-    todo_scan()
-        .args([
-            "list",
-            "--root",
-            dir.path().to_str().unwrap(),
-            "--group-by",
-            "author",
-        ])
-        .assert()
-        .success()
-        .stdout(predicate::str::contains("alice (1 items)"))
-        .stdout(predicate::str::contains("bob (1 items)"))
-        .stdout(predicate::str::contains("unassigned (1 items)"))
-        .stdout(predicate::str::contains("3 items in 3 groups"));
+    let markdown = list_output(dir.path(), "markdown");
+    assert!(markdown.contains("| 🐛 Bug |"), "got: {markdown}");
+
+    let sarif: serde_json::Value = serde_json::from_str(&list_output(dir.path(), "sarif")).unwrap();
+    let result = &sarif["runs"][0]["results"][0];
+    assert_eq!(result["ruleId"], "todo-scan/BUG");
+    assert!(!sarif.to_string().contains("🐛"));
 }
 
 #[test]
-fn test_list_group_by_dir() {
+fn test_list_tag_labels_unknown_tag_fails() {
     let dir = setup_project(&[
-        ("src/lib.rs", "// TODO: in src\n"),
-        ("src/main.rs", "// TODO: also in src\n"),
-        ("tests/test.rs", "// TODO: in tests\n"),
+        ("main.rs", "// TODO: x\n"),
+        (".todo-scan.toml", "[tag_labels]\nWIP = \"Work\"\n"),
     ]);
 
     todo_scan()
-        .args([
-            "list",
-            "--root",
-            dir.path().to_str().unwrap(),
-            "--group-by",
-            "dir",
-        ])
+        .args(["list", "--root", dir.path().to_str().unwrap()])
         .assert()
-        .success()
-        .stdout(predicate::str::contains("src (2 items)"))
-        .stdout(predicate::str::contains("tests (1 items)"))
-        .stdout(predicate::str::contains("3 items in 2 groups"));
+        .failure()
+        .stderr(predicate::str::contains("Unknown tag in tag_labels: WIP"));
 }
 
 #[test]
-fn test_list_group_by_with_json() {
-    let dir = setup_project(&[("main.rs", "// TODO!!: urgent task\n// TODO: normal task\n")]);
+fn test_list_enabled_tags_skips_disabled_markers() {
+    let dir = setup_project(&[
+        (
+            "main.rs",
+            "// TODO: keep me\n// HACK: quick fix\n// NOTE: background\n",
+        ),
+        (".todo-scan.toml", "enabled_tags = [\"TODO\", \"FIXME\"]\n"),
+    ]);
 
-    // JSON output should be flat regardless of --group-by
     todo_scan()
-        .args([
-            "list",
-            "--root",
-            dir.path().to_str().unwrap(),
-            "--group-by",
-            "priority",
-            "--format",
-            "json",
-        ])
+        .args(["list", "--root", dir.path().to_str().unwrap()])
         .assert()
         .success()
-        .stdout(predicate::str::contains("\"items\""))
-        .stdout(predicate::str::contains("\"tag\": \"TODO\""));
+        .stdout(predicate::str::contains("keep me"))
+        .stdout(predicate::str::contains("quick fix").not())
+        .stdout(predicate::str::contains("background").not());
 }
 
 #[test]
-fn test_list_filter_composition() {
+fn test_list_enabled_tags_empty_fails() {
     let dir = setup_project(&[
-        ("src/lib.rs", "// TODO(alice)!!: urgent alice in src\n"),
-        ("src/main.rs", "// TODO(alice): normal alice in src\n"),
-        ("src/other.rs", "// TODO(bob)!!: urgent bob in src\n"),
-        ("tests/test.rs", "// TODO(alice)!!: urgent alice in tests\n"),
+        ("main.rs", "// TODO: x\n"),
+        (".todo-scan.toml", "enabled_tags = []\n"),
     ]);
 
-    // Combine: --priority urgent --author alice --path "src/**"
     todo_scan()
+        .args(["list", "--root", dir.path().to_str().unwrap()])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("No tags enabled"));
+}
+
+fn list_json_files(root: &std::path::Path, style: &str) -> Vec<String> {
+    let output = todo_scan()
         .args([
             "list",
             "--root",
-            dir.path().to_str().unwrap(),
-            "--priority",
-            "urgent",
-            "--author",
-            "alice",
-            "--path",
-            "src/**",
+            root.to_str().unwrap(),
+            "--format",
+            "json",
+            "--path-style",
+            style,
         ])
-        .assert()
-        .success()
-        .stdout(predicate::str::contains("urgent alice in src"))
-        .stdout(predicate::str::contains("normal alice in src").not())
-        .stdout(predicate::str::contains("urgent bob in src").not())
-        .stdout(predicate::str::contains("urgent alice in tests").not())
-        .stdout(predicate::str::contains("1 items"));
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+    let json: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    json["items"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|i| i["file"].as_str().unwrap().to_string())
+        .collect()
 }
 
-// --- Context display ---
+#[test]
+fn test_list_path_style_relative_and_absolute() {
+    let dir = setup_project(&[("src/main.rs", "// TODO: paths\n")]);
+
+    assert_eq!(list_json_files(dir.path(), "relative"), vec!["src/main.rs"]);
+
+    let expected = dir.path().canonicalize().unwrap().join("src/main.rs");
+    assert_eq!(
+        list_json_files(dir.path(), "absolute"),
+        vec![expected.to_string_lossy().to_string()]
+    );
+}
 
 #[test]
-fn test_list_context_shows_surrounding_lines() {
-    let dir = setup_project(&[(
-        "main.rs",
-        "fn main() {\n    let x = 1;\n    // TODO: fix this\n    let y = 2;\n    let z = 3;\n}\n",
-    )]);
+fn test_list_path_style_repo_root() {
+    let dir = setup_project(&[("pkg/src/main.rs", "// TODO: paths\n")]);
+    std::process::Command::new("git")
+        .args(["init", "-q"])
+        .current_dir(dir.path())
+        .output()
+        .unwrap();
 
-    todo_scan()
-        .args(["list", "--root", dir.path().to_str().unwrap(), "-C", "2"])
-        .assert()
-        .success()
-        .stdout(predicate::str::contains("let x = 1"))
-        .stdout(predicate::str::contains("TODO"))
-        .stdout(predicate::str::contains("let y = 2"));
+    assert_eq!(
+        list_json_files(&dir.path().join("pkg"), "repo-root"),
+        vec!["pkg/src/main.rs"]
+    );
 }
 
 #[test]
-fn test_list_context_json_includes_context_field() {
-    let dir = setup_project(&[(
-        "main.rs",
-        "fn main() {\n    let x = 1;\n    // TODO: fix this\n    let y = 2;\n}\n",
-    )]);
+fn test_list_repo_root_headers() {
+    let dir = setup_project(&[("pkg/src/main.rs", "// TODO: paths\n")]);
+    std::process::Command::new("git")
+        .args(["init", "-q"])
+        .current_dir(dir.path())
+        .output()
+        .unwrap();
+    let root = dir.path().join("pkg");
 
-    todo_scan()
+    let output = todo_scan()
         .args([
             "list",
             "--root",
-            dir.path().to_str().unwrap(),
-            "-C",
-            "2",
-            "--format",
-            "json",
+            root.to_str().unwrap(),
+            "--repo-root-headers",
         ])
-        .assert()
-        .success()
-        .stdout(predicate::str::contains("\"context\""))
-        .stdout(predicate::str::contains("\"before\""))
-        .stdout(predicate::str::contains("\"after\""));
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    assert_eq!(stdout.lines().next(), Some("pkg/src/main.rs"));
+
+    // Only the header changes; item paths keep their --path-style form
+    assert_eq!(list_json_files(&root, "relative"), vec!["src/main.rs"]);
 }
 
 #[test]
-fn test_list_without_context_no_context_lines() {
+fn test_list_path_style_absolute_rewrites_ignored_items() {
     let dir = setup_project(&[(
-        "main.rs",
-        "fn main() {\n    let x = 1;\n    // TODO: fix this\n    let y = 2;\n}\n",
+        "src/main.rs",
+        "// TODO: visible\n// TODO: hidden todo-scan:ignore\n",
     )]);
 
-    // Without -C flag, no surrounding code should appear
-    todo_scan()
+    let output = todo_scan()
         .args([
             "list",
             "--root",
             dir.path().to_str().unwrap(),
-            "--format",
-            "json",
+            "--show-ignored",
+            "--path-style",
+            "absolute",
         ])
-        .assert()
-        .success()
-        .stdout(predicate::str::contains("\"context\"").not());
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+    let stdout = String::from_utf8(output.stdout).unwrap();
+
+    let expected = dir.path().canonicalize().unwrap().join("src/main.rs");
+    let ignored = stdout.split("Ignored items").nth(1).unwrap();
+    assert!(ignored.contains(expected.to_string_lossy().as_ref()));
 }
 
-// --- todo-scan:ignore suppression tests ---
+#[test]
+fn test_list_path_style_repo_root_outside_git_is_relative() {
+    let dir = setup_project(&[("pkg/src/main.rs", "// TODO: paths\n")]);
+
+    assert_eq!(
+        list_json_files(&dir.path().join("pkg"), "repo-root"),
+        vec!["src/main.rs"]
+    );
+}
+
+fn git(dir: &std::path::Path, args: &[&str]) {
+    let status = std::process::Command::new("git")
+        .args(args)
+        .current_dir(dir)
+        .status()
+        .unwrap();
+    assert!(status.success(), "git {:?} failed", args);
+}
 
 #[test]
-fn test_list_excludes_ignored_items_by_default() {
-    let dir = setup_project(&[(
-        "main.rs",
-        "// TODO: visible item\n// TODO: hidden item todo-scan:ignore\n",
-    )]);
+fn test_list_staged_reads_index_not_working_tree() {
+    let dir = setup_project(&[
+        ("src/main.rs", "// TODO: staged version\n"),
+        ("vendor/lib.rs", "// TODO: vendored\n"),
+    ]);
+    git(dir.path(), &["init", "-q"]);
+    git(dir.path(), &["add", "."]);
+    fs::write(
+        dir.path().join("src/main.rs"),
+        "// TODO: working tree version\n",
+    )
+    .unwrap();
+    fs::write(dir.path().join("untracked.rs"), "// TODO: untracked\n").unwrap();
+    let root = dir.path().to_str().unwrap();
 
     todo_scan()
-        .args(["list", "--root", dir.path().to_str().unwrap()])
+        .args(["list", "--root", root, "--staged"])
         .assert()
         .success()
-        .stdout(predicate::str::contains("visible item"))
-        .stdout(predicate::str::contains("hidden item").not())
-        .stdout(predicate::str::contains("1 items"))
-        .stdout(predicate::str::contains("(1 ignored)"));
+        .stdout(predicate::str::contains("staged version"))
+        .stdout(predicate::str::contains("working tree version").not())
+        .stdout(predicate::str::contains("untracked").not())
+        .stdout(predicate::str::contains("vendored").not());
+
+    todo_scan()
+        .args(["list", "--root", root])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("working tree version"));
 }
 
 #[test]
-fn test_list_show_ignored_reveals_suppressed() {
-    let dir = setup_project(&[(
-        "main.rs",
-        "// TODO: visible item\n// TODO: hidden item todo-scan:ignore\n",
-    )]);
+fn test_list_staged_with_root_below_repo_root() {
+    let dir = setup_project(&[
+        ("pkg/src/main.rs", "// TODO: in package\n"),
+        ("other.rs", "// TODO: outside package\n"),
+    ]);
+    git(dir.path(), &["init", "-q"]);
+    git(dir.path(), &["add", "."]);
 
     todo_scan()
         .args([
             "list",
             "--root",
-            dir.path().to_str().unwrap(),
-            "--show-ignored",
+            dir.path().join("pkg").to_str().unwrap(),
+            "--staged",
         ])
         .assert()
         .success()
-        .stdout(predicate::str::contains("visible item"))
-        .stdout(predicate::str::contains("Ignored items"))
-        .stdout(predicate::str::contains("hidden item"))
-        .stdout(predicate::str::contains("(1 ignored)"));
+        .stdout(predicate::str::contains("src/main.rs"))
+        .stdout(predicate::str::contains("in package"))
+        .stdout(predicate::str::contains("outside package").not());
 }
 
 #[test]
-fn test_list_ignore_next_line_works_e2e() {
-    let dir = setup_project(&[(
-        "main.rs",
-        "// todo-scan:ignore-next-line\n// TODO: suppressed\n// TODO: visible\n",
-    )]);
+fn test_list_staged_outside_git_errors() {
+    let dir = setup_project(&[("main.rs", "// TODO: x\n")]);
 
     todo_scan()
-        .args(["list", "--root", dir.path().to_str().unwrap()])
+        .args(["list", "--root", dir.path().to_str().unwrap(), "--staged"])
         .assert()
-        .success()
-        .stdout(predicate::str::contains("visible"))
-        .stdout(predicate::str::contains("suppressed").not())
-        .stdout(predicate::str::contains("1 items"))
-        .stdout(predicate::str::contains("(1 ignored)"));
+        .failure()
+        .stderr(predicate::str::contains(
+            "--staged requires a git repository",
+        ));
 }
 
 #[test]
-fn test_list_no_ignored_shows_no_suffix() {
-    let dir = setup_project(&[("main.rs", "// TODO: just a normal todo\n")]);
+fn test_list_author_mention_format() {
+    let dir = setup_project(&[("main.rs", "// TODO(alice): assigned\n// TODO: unassigned\n")]);
+    let root = dir.path().to_str().unwrap();
 
     todo_scan()
-        .args(["list", "--root", dir.path().to_str().unwrap()])
+        .args([
+            "list",
+            "--root",
+            root,
+            "--author-mention-format",
+            "<@{author}>",
+        ])
         .assert()
         .success()
-        .stdout(predicate::str::contains("1 items in 1 files"))
-        .stdout(predicate::str::contains("ignored").not());
-}
-
-#[test]
-fn test_list_ignore_strips_marker_from_message() {
-    let dir = setup_project(&[("main.rs", "// TODO: fix this todo-scan:ignore\n")]);
+        .stdout(predicate::str::contains("assigned (<@alice>)"))
+        .stdout(predicate::str::contains("unassigned\n"))
+        .stdout(predicate::str::contains("(@alice)").not());
 
     todo_scan()
         .args([
             "list",
             "--root",
-            dir.path().to_str().unwrap(),
-            "--show-ignored",
+            root,
             "--format",
-            "json",
+            "markdown",
+            "--author-mention-format",
+            "<@{author}>",
         ])
         .assert()
         .success()
-        .stdout(predicate::str::contains("\"message\": \"fix this\""))
-        .stdout(predicate::str::contains("todo-scan:ignore").not());
+        .stdout(predicate::str::contains("| assigned | <@alice> |"))
+        .stdout(predicate::str::contains("| unassigned |  |"));
 }
 
-// --- Detail level tests ---
-
 #[test]
-fn test_list_detail_minimal_hides_author_and_issue() {
-    let dir = setup_project(&[(
-        "main.rs",
-        "// TODO(alice): fix issue #123\n// FIXME(bob): broken thing\n",
-    )]);
+fn test_list_alias_ls() {
+    let dir = setup_project(&[("main.rs", "// TODO: alias test\n")]);
 
-    let output = todo_scan()
-        .args([
-            "list",
-            "--root",
+    todo_scan()
+        .args(["ls", "--root", dir.path().to_str().unwrap()])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("TODO"));
+}
+
+#[test]
+fn test_list_empty_project() {
+    let dir = setup_project(&[("main.rs", "fn main() {}\n")]);
+
+    todo_scan()
+        .args(["list", "--root", dir.path().to_str().unwrap()])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("0 items"));
+}
+
+#[test]
+fn test_list_with_author_and_issue() {
+    let dir = setup_project(&[("main.rs", "// TODO(alice): fix issue #123\n")]);
+
+    todo_scan()
+        .args([
+            "list",
+            "--root",
+            dir.path().to_str().unwrap(),
+            "--format",
+            "json",
+        ])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("\"author\": \"alice\""))
+        .stdout(predicate::str::contains("\"issue_ref\": \"#123\""));
+}
+
+#[test]
+fn test_list_text_issue_ref_no_double_hash() {
+    let dir = setup_project(&[("main.rs", "// TODO(alice): fix issue #123\n")]);
+
+    todo_scan()
+        .args(["list", "--root", dir.path().to_str().unwrap()])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("(#123)"))
+        .stdout(predicate::str::contains("(##123)").not());
+}
+
+#[test]
+fn test_list_filters_false_positives() {
+    let dir = setup_project(&[(
+        "main.rs",
+        r#"// TODO: real comment
+let service = TodoService::new();
+if isTodoCompleted() { return; }
+let msg = "TODO: not real";
+let todo_count = 42;
+// FIXME(bob): another real comment
+struct TodoItem { done: bool }
+"#,
+    )]);
+
+    // Verify only 2 real comment items found (text output)
+    todo_scan()
+        .args(["list", "--root", dir.path().to_str().unwrap()])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("2 items"));
+
+    // Verify correct messages via JSON output
+    todo_scan()
+        .args([
+            "list",
+            "--root",
+            dir.path().to_str().unwrap(),
+            "--format",
+            "json",
+        ])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("\"message\": \"real comment\""))
+        .stdout(predicate::str::contains(
+            "\"message\": \"another real comment\"",
+        ));
+}
+
+#[test]
+fn test_list_multi_language_comments() {
+    let dir = setup_project(&[
+        ("app.py", "# TODO: python todo\nx = 1\n"),
+        ("style.css", "/* FIXME: css fixme */\n"),
+        ("query.sql", "-- HACK: sql hack\n"),
+        ("page.html", "<!-- NOTE: html note -->\n"),
+    ]);
+
+    todo_scan()
+        .args(["list", "--root", dir.path().to_str().unwrap()])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("4 items"));
+}
+
+#[test]
+fn test_list_github_actions_format() {
+    let dir = setup_project(&[(
+        "main.rs",
+        "// TODO: implement feature\n// BUG: critical issue\n",
+    )]);
+
+    todo_scan()
+        .args([
+            "list",
+            "--root",
+            dir.path().to_str().unwrap(),
+            "--format",
+            "github-actions",
+        ])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(
+            "::warning file=main.rs,line=1,title=TODO::[TODO] implement feature",
+        ))
+        .stdout(predicate::str::contains(
+            "::error file=main.rs,line=2,title=BUG::[BUG] critical issue",
+        ))
+        .stdout(predicate::str::contains(
+            "::notice::todo-scan: 2 items found",
+        ));
+}
+
+#[test]
+fn test_list_sarif_format() {
+    let dir = setup_project(&[("main.rs", "// TODO: sarif test\n")]);
+
+    todo_scan()
+        .args([
+            "list",
+            "--root",
+            dir.path().to_str().unwrap(),
+            "--format",
+            "sarif",
+        ])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("\"version\": \"2.1.0\""))
+        .stdout(predicate::str::contains("\"ruleId\": \"todo-scan/TODO\""))
+        .stdout(predicate::str::contains("\"text\": \"sarif test\""));
+}
+
+#[test]
+fn test_list_markdown_format() {
+    let dir = setup_project(&[("main.rs", "// TODO(alice): implement feature #42\n")]);
+
+    todo_scan()
+        .args([
+            "list",
+            "--root",
+            dir.path().to_str().unwrap(),
+            "--format",
+            "markdown",
+        ])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(
+            "| File | Line | Tag | Priority | Message | Author | Issue |",
+        ))
+        .stdout(predicate::str::contains("TODO"))
+        .stdout(predicate::str::contains("alice"))
+        .stdout(predicate::str::contains("**1 items found**"));
+}
+
+// --- Deadline in output ---
+
+#[test]
+fn test_list_deadline_in_json() {
+    let dir = setup_project(&[("main.rs", "// TODO(2025-06-01): deadline task\n")]);
+
+    todo_scan()
+        .args([
+            "list",
+            "--root",
+            dir.path().to_str().unwrap(),
+            "--format",
+            "json",
+        ])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("\"deadline\": \"2025-06-01\""));
+}
+
+#[test]
+fn test_list_author_and_deadline_in_json() {
+    let dir = setup_project(&[("main.rs", "// TODO(alice, 2025-06-01): task with both\n")]);
+
+    todo_scan()
+        .args([
+            "list",
+            "--root",
+            dir.path().to_str().unwrap(),
+            "--format",
+            "json",
+        ])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("\"author\": \"alice\""))
+        .stdout(predicate::str::contains("\"deadline\": \"2025-06-01\""));
+}
+
+#[test]
+fn test_list_no_deadline_null_in_json() {
+    let dir = setup_project(&[("main.rs", "// TODO: no deadline\n")]);
+
+    todo_scan()
+        .args([
+            "list",
+            "--root",
+            dir.path().to_str().unwrap(),
+            "--format",
+            "json",
+        ])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("\"deadline\": null"));
+}
+
+#[test]
+fn test_list_quarter_deadline_in_json() {
+    let dir = setup_project(&[("main.rs", "// TODO(2025-Q2): quarter deadline\n")]);
+
+    todo_scan()
+        .args([
+            "list",
+            "--root",
+            dir.path().to_str().unwrap(),
+            "--format",
+            "json",
+        ])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("\"deadline\": \"2025-06-30\""));
+}
+
+// --- Filtering ---
+
+#[test]
+fn test_list_filter_priority() {
+    let dir = setup_project(&[(
+        "main.rs",
+        "// TODO!!: urgent task\n// TODO!: high task\n// TODO: normal task\n",
+    )]);
+
+    todo_scan()
+        .args([
+            "list",
+            "--root",
+            dir.path().to_str().unwrap(),
+            "--priority",
+            "urgent",
+        ])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("urgent task"))
+        .stdout(predicate::str::contains("high task").not())
+        .stdout(predicate::str::contains("normal task").not())
+        .stdout(predicate::str::contains("1 items"));
+}
+
+#[test]
+fn test_list_filter_author() {
+    let dir = setup_project(&[(
+        "main.rs",
+        "// TODO(alice): alice task\n// TODO(bob): bob task\n// TODO: no author\n",
+    )]);
+
+    todo_scan()
+        .args([
+            "list",
+            "--root",
+            dir.path().to_str().unwrap(),
+            "--author",
+            "alice",
+        ])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("alice task"))
+        .stdout(predicate::str::contains("bob task").not())
+        .stdout(predicate::str::contains("no author").not())
+        .stdout(predicate::str::contains("1 items"));
+}
+
+#[test]
+fn test_list_filter_label() {
+    let dir = setup_project(&[(
+        "main.rs",
+        "// TODO(labels=frontend,perf): lazy-load images\n\
+         // TODO(bob, labels=backend): add index\n\
+         // TODO: unlabeled\n",
+    )]);
+
+    todo_scan()
+        .args([
+            "list",
+            "--root",
+            dir.path().to_str().unwrap(),
+            "--label",
+            "perf",
+        ])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("lazy-load images"))
+        .stdout(predicate::str::contains("add index").not())
+        .stdout(predicate::str::contains("unlabeled").not())
+        .stdout(predicate::str::contains("1 items"));
+
+    todo_scan()
+        .args([
+            "list",
+            "--root",
+            dir.path().to_str().unwrap(),
+            "--label",
+            "frontend",
+            "--label",
+            "backend",
+            "--format",
+            "json",
+        ])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(
+            "\"labels\": [\n        \"frontend\",\n        \"perf\"\n      ]",
+        ))
+        .stdout(predicate::str::contains("\"author\": \"bob\""))
+        .stdout(predicate::str::contains("unlabeled").not());
+}
+
+#[test]
+fn test_list_filter_path() {
+    let dir = setup_project(&[
+        ("src/lib.rs", "// TODO: in src\n"),
+        ("tests/test.rs", "// TODO: in tests\n"),
+    ]);
+
+    todo_scan()
+        .args([
+            "list",
+            "--root",
+            dir.path().to_str().unwrap(),
+            "--path",
+            "src/**",
+        ])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("in src"))
+        .stdout(predicate::str::contains("in tests").not())
+        .stdout(predicate::str::contains("1 items"));
+}
+
+#[test]
+fn test_list_limit() {
+    let dir = setup_project(&[(
+        "main.rs",
+        "// TODO: first\n// TODO: second\n// TODO: third\n",
+    )]);
+
+    todo_scan()
+        .args([
+            "list",
+            "--root",
+            dir.path().to_str().unwrap(),
+            "--limit",
+            "2",
+        ])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("2 items"))
+        .stdout(predicate::str::contains("... and 1 more"));
+}
+
+#[test]
+fn test_list_limit_json_sets_truncated() {
+    let dir = setup_project(&[(
+        "main.rs",
+        "// TODO: first\n// TODO: second\n// TODO: third\n",
+    )]);
+
+    let output = todo_scan()
+        .args([
+            "list",
+            "--root",
+            dir.path().to_str().unwrap(),
+            "--format",
+            "json",
+            "--limit",
+            "2",
+        ])
+        .output()
+        .unwrap();
+
+    let json: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(json["items"].as_array().unwrap().len(), 2);
+    assert_eq!(json["truncated"], true);
+}
+
+#[test]
+fn test_list_limit_not_exceeded_is_not_truncated() {
+    let dir = setup_project(&[("main.rs", "// TODO: first\n// TODO: second\n")]);
+
+    let output = todo_scan()
+        .args([
+            "list",
+            "--root",
+            dir.path().to_str().unwrap(),
+            "--format",
+            "json",
+            "--limit",
+            "5",
+        ])
+        .output()
+        .unwrap();
+
+    let json: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(json["items"].as_array().unwrap().len(), 2);
+    assert_eq!(json["truncated"], false);
+}
+
+// --- Group by ---
+
+#[test]
+fn test_list_group_by_tag() {
+    let dir = setup_project(&[(
+        "main.rs",
+        "// TODO: task one\n// FIXME: task two\n// TODO: task three\n",
+    )]);
+
+    todo_scan()
+        .args([
+            "list",
+            "--root",
+            dir.path().to_str().unwrap(),
+            "--group-by",
+            "tag",
+        ])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("TODO (2 items)"))
+        .stdout(predicate::str::contains("FIXME (1 items)"))
+        .stdout(predicate::str::contains("3 items in 2 groups"));
+}
+
+#[test]
+fn test_list_stable_order_within_groups() {
+    let dir = setup_project(&[
+        ("b.rs", "// TODO!!: b urgent\n"),
+        ("a.rs", "// TODO: a normal\n// TODO!: a high\n"),
+    ]);
+    let root = dir.path().to_str().unwrap();
+    let run = || {
+        let output = todo_scan()
+            .args([
+                "list",
+                "--root",
+                root,
+                "--no-cache",
+                "--group-by",
+                "tag",
+                "--sort",
+                "priority",
+                "--stable-order",
+            ])
+            .output()
+            .unwrap();
+        assert!(output.status.success());
+        String::from_utf8(output.stdout).unwrap()
+    };
+
+    let stdout = run();
+    let positions: Vec<usize> = ["a normal", "a high", "b urgent"]
+        .iter()
+        .map(|m| stdout.find(m).unwrap())
+        .collect();
+    assert!(positions.windows(2).all(|w| w[0] < w[1]), "{}", stdout);
+    assert_eq!(stdout, run());
+}
+
+#[test]
+fn test_list_group_by_priority() {
+    let dir = setup_project(&[(
+        "main.rs",
+        "// TODO!!: urgent task\n// TODO!: high task\n// TODO: normal task\n",
+    )]);
+
+    todo_scan()
+        .args([
+            "list",
+            "--root",
+            dir.path().to_str().unwrap(),
+            "--group-by",
+            "priority",
+        ])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("!! Urgent (1 items)"))
+        .stdout(predicate::str::contains("! High (1 items)"))
+        .stdout(predicate::str::contains("Normal (1 items)"))
+        .stdout(predicate::str::contains("3 items in 3 groups"));
+}
+
+#[test]
+fn test_list_group_by_author() {
+    let dir = setup_project(&[(
+        "main.rs",
+        "// TODO(alice): alice task\n// TODO(bob): bob task\n// TODO: no author\n",
+    )]);
+
+    todo_scan()
+        .args([
+            "list",
+            "--root",
+            dir.path().to_str().unwrap(),
+            "--group-by",
+            "author",
+        ])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("alice (1 items)"))
+        .stdout(predicate::str::contains("bob (1 items)"))
+        .stdout(predicate::str::contains("unassigned (1 items)"))
+        .stdout(predicate::str::contains("3 items in 3 groups"));
+}
+
+#[test]
+fn test_list_group_by_dir() {
+    let dir = setup_project(&[
+        ("src/lib.rs", "// TODO: in src\n"),
+        ("src/main.rs", "// TODO: also in src\n"),
+        ("tests/test.rs", "// TODO: in tests\n"),
+    ]);
+
+    todo_scan()
+        .args([
+            "list",
+            "--root",
+            dir.path().to_str().unwrap(),
+            "--group-by",
+            "dir",
+        ])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("src (2 items)"))
+        .stdout(predicate::str::contains("tests (1 items)"))
+        .stdout(predicate::str::contains("3 items in 2 groups"));
+}
+
+#[test]
+fn test_list_group_by_none_is_flat() {
+    let dir = setup_project(&[
+        ("src/lib.rs", "// TODO: in src\n"),
+        ("tests/test.rs", "// FIXME: in tests\n"),
+    ]);
+
+    let output = todo_scan()
+        .args([
+            "list",
+            "--root",
+            dir.path().to_str().unwrap(),
+            "--group-by",
+            "none",
+        ])
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    let lines: Vec<&str> = stdout.lines().collect();
+    assert_eq!(
+        lines,
+        vec![
+            "src/lib.rs:1 [TODO] in src",
+            "tests/test.rs:1 [FIXME] in tests",
+            "2 items",
+        ]
+    );
+}
+
+#[test]
+fn test_list_group_by_with_json() {
+    let dir = setup_project(&[("main.rs", "// TODO!!: urgent task\n// TODO: normal task\n")]);
+
+    // JSON output should be flat regardless of --group-by
+    todo_scan()
+        .args([
+            "list",
+            "--root",
+            dir.path().to_str().unwrap(),
+            "--group-by",
+            "priority",
+            "--format",
+            "json",
+        ])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("\"items\""))
+        .stdout(predicate::str::contains("\"tag\": \"TODO\""));
+}
+
+#[test]
+fn test_list_filter_composition() {
+    let dir = setup_project(&[
+        ("src/lib.rs", "// TODO(alice)!!: urgent alice in src\n"),
+        ("src/main.rs", "// TODO(alice): normal alice in src\n"),
+        ("src/other.rs", "// TODO(bob)!!: urgent bob in src\n"),
+        ("tests/test.rs", "// TODO(alice)!!: urgent alice in tests\n"),
+    ]);
+
+    // Combine: --priority urgent --author alice --path "src/**"
+    todo_scan()
+        .args([
+            "list",
+            "--root",
+            dir.path().to_str().unwrap(),
+            "--priority",
+            "urgent",
+            "--author",
+            "alice",
+            "--path",
+            "src/**",
+        ])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("urgent alice in src"))
+        .stdout(predicate::str::contains("normal alice in src").not())
+        .stdout(predicate::str::contains("urgent bob in src").not())
+        .stdout(predicate::str::contains("urgent alice in tests").not())
+        .stdout(predicate::str::contains("1 items"));
+}
+
+// --- Context display ---
+
+#[test]
+fn test_list_context_shows_surrounding_lines() {
+    let dir = setup_project(&[(
+        "main.rs",
+        "fn main() {\n    let x = 1;\n    // TODO: fix this\n    let y = 2;\n    let z = 3;\n}\n",
+    )]);
+
+    todo_scan()
+        .args(["list", "--root", dir.path().to_str().unwrap(), "-C", "2"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("let x = 1"))
+        .stdout(predicate::str::contains("TODO"))
+        .stdout(predicate::str::contains("let y = 2"));
+}
+
+#[test]
+fn test_list_context_json_includes_context_field() {
+    let dir = setup_project(&[(
+        "main.rs",
+        "fn main() {\n    let x = 1;\n    // TODO: fix this\n    let y = 2;\n}\n",
+    )]);
+
+    todo_scan()
+        .args([
+            "list",
+            "--root",
+            dir.path().to_str().unwrap(),
+            "-C",
+            "2",
+            "--format",
+            "json",
+        ])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("\"context\""))
+        .stdout(predicate::str::contains("\"before\""))
+        .stdout(predicate::str::contains("\"after\""));
+}
+
+#[test]
+fn test_list_without_context_no_context_lines() {
+    let dir = setup_project(&[(
+        "main.rs",
+        "fn main() {\n    let x = 1;\n    // TODO: fix this\n    let y = 2;\n}\n",
+    )]);
+
+    // Without -C flag, no surrounding code should appear
+    todo_scan()
+        .args([
+            "list",
+            "--root",
+            dir.path().to_str().unwrap(),
+            "--format",
+            "json",
+        ])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("\"context\"").not());
+}
+
+// --- todo-scan:ignore suppression tests ---
+
+#[test]
+fn test_list_excludes_ignored_items_by_default() {
+    let dir = setup_project(&[(
+        "main.rs",
+        "// TODO: visible item\n// TODO: hidden item todo-scan:ignore\n",
+    )]);
+
+    todo_scan()
+        .args(["list", "--root", dir.path().to_str().unwrap()])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("visible item"))
+        .stdout(predicate::str::contains("hidden item").not())
+        .stdout(predicate::str::contains("1 items"))
+        .stdout(predicate::str::contains("(1 ignored)"));
+}
+
+#[test]
+fn test_list_show_ignored_reveals_suppressed() {
+    let dir = setup_project(&[(
+        "main.rs",
+        "// TODO: visible item\n// TODO: hidden item todo-scan:ignore\n",
+    )]);
+
+    todo_scan()
+        .args([
+            "list",
+            "--root",
+            dir.path().to_str().unwrap(),
+            "--show-ignored",
+        ])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("visible item"))
+        .stdout(predicate::str::contains("Ignored items"))
+        .stdout(predicate::str::contains("hidden item"))
+        .stdout(predicate::str::contains("(1 ignored)"));
+}
+
+#[test]
+fn test_list_ignore_next_line_works_e2e() {
+    let dir = setup_project(&[(
+        "main.rs",
+        "// todo-scan:ignore-next-line\n// TODO: suppressed\n// TODO: visible\n",
+    )]);
+
+    todo_scan()
+        .args(["list", "--root", dir.path().to_str().unwrap()])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("visible"))
+        .stdout(predicate::str::contains("suppressed").not())
+        .stdout(predicate::str::contains("1 items"))
+        .stdout(predicate::str::contains("(1 ignored)"));
+}
+
+#[test]
+fn test_list_no_ignored_shows_no_suffix() {
+    let dir = setup_project(&[("main.rs", "// TODO: just a normal todo\n")]);
+
+    todo_scan()
+        .args(["list", "--root", dir.path().to_str().unwrap()])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("1 items in 1 files"))
+        .stdout(predicate::str::contains("ignored").not());
+}
+
+#[test]
+fn test_list_ignore_strips_marker_from_message() {
+    let dir = setup_project(&[("main.rs", "// TODO: fix this todo-scan:ignore\n")]);
+
+    todo_scan()
+        .args([
+            "list",
+            "--root",
+            dir.path().to_str().unwrap(),
+            "--show-ignored",
+            "--format",
+            "json",
+        ])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("\"message\": \"fix this\""))
+        .stdout(predicate::str::contains("todo-scan:ignore").not());
+}
+
+#[test]
+fn test_list_string_literal_todo_shown_as_ignored_with_reason() {
+    let dir = setup_project(&[("main.rs", "let s = \"TODO: fix this\";\n// TODO: real\n")]);
+
+    todo_scan()
+        .args([
+            "list",
+            "--root",
+            dir.path().to_str().unwrap(),
+            "--show-ignored",
+            "--format",
+            "json",
+        ])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("\"message\": \"real\""))
+        .stdout(predicate::str::contains("\"reason\": \"string-literal\""));
+
+    todo_scan()
+        .args([
+            "list",
+            "--root",
+            dir.path().to_str().unwrap(),
+            "--show-ignored",
+        ])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("fix this (string-literal)"))
+        .stdout(predicate::str::contains("(1 ignored)"));
+}
+
+#[test]
+fn test_list_resolved_marker_shown_as_ignored() {
+    let dir = setup_project(&[
+        ("main.rs", "// TODO[done]: old task\n// TODO: open task\n"),
+        (".todo-scan.toml", "resolved_markers = [\"[done]\"]\n"),
+    ]);
+
+    todo_scan()
+        .args([
+            "list",
+            "--root",
+            dir.path().to_str().unwrap(),
+            "--show-ignored",
+            "--format",
+            "json",
+        ])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("\"message\": \"open task\""))
+        .stdout(predicate::str::contains("\"reason\": \"resolved\""));
+
+    todo_scan()
+        .args(["list", "--root", dir.path().to_str().unwrap()])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("open task"))
+        .stdout(predicate::str::contains("old task").not())
+        .stdout(predicate::str::contains("(1 ignored)"));
+}
+
+// --- Detail level tests ---
+
+#[test]
+fn test_list_detail_minimal_hides_author_and_issue() {
+    let dir = setup_project(&[(
+        "main.rs",
+        "// TODO(alice): fix issue #123\n// FIXME(bob): broken thing\n",
+    )]);
+
+    let output = todo_scan()
+        .args([
+            "list",
+            "--root",
+            dir.path().to_str().unwrap(),
+            "--detail",
+            "minimal",
+        ])
+        .assert()
+        .success();
+
+    // Minimal should NOT show (@author) or (#issue)
+    output
+        .stdout(predicate::str::contains("TODO"))
+        .stdout(predicate::str::contains("FIXME"))
+        .stdout(predicate::str::contains("(@alice)").not())
+        .stdout(predicate::str::contains("(@bob)").not())
+        .stdout(predicate::str::contains("(#123)").not());
+}
+
+#[test]
+fn test_list_detail_default_is_normal() {
+    // Omitting --detail should give the same as --detail normal
+    let dir = setup_project(&[("main.rs", "// TODO(alice): fix issue #123\n")]);
+
+    // Without --detail flag
+    let without = todo_scan()
+        .args(["list", "--root", dir.path().to_str().unwrap()])
+        .output()
+        .unwrap();
+
+    // With --detail normal
+    let with_normal = todo_scan()
+        .args([
+            "list",
+            "--root",
+            dir.path().to_str().unwrap(),
+            "--detail",
+            "normal",
+        ])
+        .output()
+        .unwrap();
+
+    assert_eq!(without.stdout, with_normal.stdout);
+}
+
+#[test]
+fn test_list_detail_minimal_json_only_core_fields() {
+    let dir = setup_project(&[("main.rs", "// TODO(alice): fix issue #123\n")]);
+
+    let output = todo_scan()
+        .args([
+            "list",
+            "--root",
+            dir.path().to_str().unwrap(),
+            "--detail",
+            "minimal",
+            "--format",
+            "json",
+        ])
+        .output()
+        .unwrap();
+
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    let json: serde_json::Value = serde_json::from_str(&stdout).unwrap();
+    let item = &json["items"][0];
+
+    // Core fields must be present
+    assert!(item["file"].is_string());
+    assert!(item["line"].is_number());
+    assert!(item["tag"].is_string());
+    assert!(item["message"].is_string());
+
+    // Non-core fields must be absent in minimal
+    assert!(
+        item.get("author").is_none(),
+        "author should not be in minimal JSON"
+    );
+    assert!(
+        item.get("issue_ref").is_none(),
+        "issue_ref should not be in minimal JSON"
+    );
+    assert!(
+        item.get("priority").is_none(),
+        "priority should not be in minimal JSON"
+    );
+    assert!(
+        item.get("deadline").is_none(),
+        "deadline should not be in minimal JSON"
+    );
+}
+
+#[test]
+fn test_list_detail_full_json_includes_match_key() {
+    let dir = setup_project(&[("main.rs", "// TODO(alice): fix issue #123\n")]);
+
+    let output = todo_scan()
+        .args([
+            "list",
+            "--root",
+            dir.path().to_str().unwrap(),
+            "--detail",
+            "full",
+            "--format",
+            "json",
+        ])
+        .output()
+        .unwrap();
+
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    let json: serde_json::Value = serde_json::from_str(&stdout).unwrap();
+    let item = &json["items"][0];
+
+    // Full mode should include match_key
+    assert!(
+        item.get("match_key").is_some(),
+        "match_key should be present in full JSON"
+    );
+    assert!(item["match_key"].as_str().unwrap().contains("TODO"));
+
+    // Full mode should also include context even without -C flag
+    assert!(
+        item.get("context").is_some(),
+        "context should be auto-included in full JSON"
+    );
+}
+
+#[test]
+fn test_list_detail_full_text_auto_context() {
+    let dir = setup_project(&[(
+        "main.rs",
+        "fn main() {\n    let x = 1;\n    // TODO: fix this\n    let y = 2;\n    let z = 3;\n}\n",
+    )]);
+
+    // Full detail without -C flag should still show context
+    todo_scan()
+        .args([
+            "list",
+            "--root",
+            dir.path().to_str().unwrap(),
+            "--detail",
+            "full",
+        ])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("let x = 1"))
+        .stdout(predicate::str::contains("TODO"))
+        .stdout(predicate::str::contains("let y = 2"));
+}
+
+#[test]
+fn test_list_json_contains_id_field_at_normal_detail() {
+    let dir = setup_project(&[("main.rs", "// TODO: stable id test\n")]);
+
+    let output = todo_scan()
+        .args([
+            "list",
+            "--root",
+            dir.path().to_str().unwrap(),
+            "--format",
+            "json",
+        ])
+        .output()
+        .unwrap();
+
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    let json: serde_json::Value = serde_json::from_str(&stdout).unwrap();
+    let items = json["items"].as_array().unwrap();
+    assert_eq!(items.len(), 1);
+    let item = &items[0];
+    assert_eq!(item["id"].as_str().unwrap(), "main.rs:TODO:stable id test");
+}
+
+#[test]
+fn test_list_json_contains_id_field_at_minimal_detail() {
+    let dir = setup_project(&[("main.rs", "// TODO: minimal id test\n")]);
+
+    let output = todo_scan()
+        .args([
+            "list",
+            "--root",
+            dir.path().to_str().unwrap(),
+            "--format",
+            "json",
+            "--detail",
+            "minimal",
+        ])
+        .output()
+        .unwrap();
+
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    let json: serde_json::Value = serde_json::from_str(&stdout).unwrap();
+    let item = &json["items"][0];
+    assert!(
+        item["id"].is_string(),
+        "id field must be present in minimal detail"
+    );
+}
+
+#[test]
+fn test_list_json_full_detail_has_both_id_and_match_key() {
+    let dir = setup_project(&[("main.rs", "// TODO: full detail test\n")]);
+
+    let output = todo_scan()
+        .args([
+            "list",
+            "--root",
+            dir.path().to_str().unwrap(),
+            "--format",
+            "json",
+            "--detail",
+            "full",
+        ])
+        .output()
+        .unwrap();
+
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    let json: serde_json::Value = serde_json::from_str(&stdout).unwrap();
+    let item = &json["items"][0];
+    assert!(item["id"].is_string(), "id field must be present");
+    assert!(
+        item["match_key"].is_string(),
+        "match_key must still be present in full detail"
+    );
+    assert_eq!(item["id"].as_str(), item["match_key"].as_str());
+}
+
+// --- Text format context lines (list -C) ---
+
+#[test]
+fn test_list_text_context_lines() {
+    let dir = setup_project(&[(
+        "main.rs",
+        "fn main() {\n    let x = 1;\n    // TODO: fix this\n    let y = 2;\n}\n",
+    )]);
+
+    todo_scan()
+        .args(["list", "--root", dir.path().to_str().unwrap(), "-C", "1"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("let x = 1"))
+        .stdout(predicate::str::contains("let y = 2"))
+        .stdout(predicate::str::contains("TODO"))
+        .stdout(predicate::str::contains("→"));
+}
+
+#[test]
+fn test_list_text_context_lines_with_group_by_tag() {
+    let dir = setup_project(&[(
+        "main.rs",
+        "fn main() {\n    let x = 1;\n    // TODO: fix this\n    let y = 2;\n}\n",
+    )]);
+
+    todo_scan()
+        .args([
+            "list",
+            "--root",
+            dir.path().to_str().unwrap(),
+            "-C",
+            "1",
+            "--group-by",
+            "tag",
+        ])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("TODO (1 items)"))
+        .stdout(predicate::str::contains("→"));
+}
+
+// --- Text format with deadlines ---
+
+#[test]
+fn test_list_text_shows_deadline() {
+    let dir = setup_project(&[("main.rs", "// TODO(2099-06-15): future deadline task\n")]);
+
+    todo_scan()
+        .args(["list", "--root", dir.path().to_str().unwrap()])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("[deadline: 2099-06-15]"));
+}
+
+#[test]
+fn test_list_text_shows_expired_deadline() {
+    let dir = setup_project(&[("main.rs", "// TODO(2020-01-01): expired task\n")]);
+
+    todo_scan()
+        .args(["list", "--root", dir.path().to_str().unwrap()])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("[expired: 2020-01-01]"));
+}
+
+// --- Text format with author and issue_ref ---
+
+#[test]
+fn test_list_text_shows_author_and_issue_ref() {
+    let dir = setup_project(&[("main.rs", "// TODO(alice): fix bug #42\n")]);
+
+    todo_scan()
+        .args(["list", "--root", dir.path().to_str().unwrap()])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("@alice"))
+        .stdout(predicate::str::contains("#42"));
+}
+
+// --- Show-ignored with group-by tag ---
+
+#[test]
+fn test_list_show_ignored_with_group_by_tag() {
+    let dir = setup_project(&[(
+        "main.rs",
+        "// TODO: visible\n// FIXME: hidden todo-scan:ignore\n",
+    )]);
+
+    todo_scan()
+        .args([
+            "list",
+            "--root",
+            dir.path().to_str().unwrap(),
+            "--show-ignored",
+            "--group-by",
+            "tag",
+        ])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Ignored items"))
+        .stdout(predicate::str::contains("FIXME"));
+}
+
+// --- Text format minimal detail suppresses author/issue/deadline ---
+
+#[test]
+fn test_list_text_minimal_suppresses_metadata() {
+    let dir = setup_project(&[("main.rs", "// TODO(alice, 2099-06-15): fix this bug\n")]);
+
+    todo_scan()
+        .args([
+            "list",
+            "--root",
             dir.path().to_str().unwrap(),
             "--detail",
             "minimal",
         ])
         .assert()
-        .success();
-
-    // Minimal should NOT show (@author) or (#issue)
-    output
-        .stdout(predicate::str::contains("TODO"))
-        .stdout(predicate::str::contains("FIXME"))
-        .stdout(predicate::str::contains("(@alice)").not())
-        .stdout(predicate::str::contains("(@bob)").not())
-        .stdout(predicate::str::contains("(#123)").not());
+        .success()
+        // Minimal mode should not show author annotation or deadline
+        .stdout(predicate::str::contains("@alice").not())
+        .stdout(predicate::str::contains("deadline").not())
+        .stdout(predicate::str::contains("TODO"));
+}
+
+// --- Sort by tag ---
+
+#[test]
+fn test_list_sort_by_tag() {
+    let dir = setup_project(&[(
+        "main.rs",
+        "// NOTE: low severity\n// BUG: high severity\n// TODO: medium severity\n",
+    )]);
+
+    let output = todo_scan()
+        .args([
+            "list",
+            "--root",
+            dir.path().to_str().unwrap(),
+            "--sort",
+            "tag",
+            "--format",
+            "json",
+        ])
+        .output()
+        .unwrap();
+
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    let json: serde_json::Value = serde_json::from_str(&stdout).unwrap();
+    let items = json["items"].as_array().unwrap();
+    // Should be sorted by severity descending: BUG > TODO > NOTE
+    assert_eq!(items[0]["tag"].as_str().unwrap(), "BUG");
+    assert_eq!(items[1]["tag"].as_str().unwrap(), "TODO");
+    assert_eq!(items[2]["tag"].as_str().unwrap(), "NOTE");
+}
+
+// --- Sort by priority ---
+
+#[test]
+fn test_list_sort_by_priority() {
+    let dir = setup_project(&[(
+        "main.rs",
+        "// TODO: normal\n// TODO!!: urgent\n// TODO!: high\n",
+    )]);
+
+    let output = todo_scan()
+        .args([
+            "list",
+            "--root",
+            dir.path().to_str().unwrap(),
+            "--sort",
+            "priority",
+            "--format",
+            "json",
+        ])
+        .output()
+        .unwrap();
+
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    let json: serde_json::Value = serde_json::from_str(&stdout).unwrap();
+    let items = json["items"].as_array().unwrap();
+    // Should be sorted by priority descending: urgent > high > normal
+    assert_eq!(items[0]["priority"].as_str().unwrap(), "urgent");
+    assert_eq!(items[1]["priority"].as_str().unwrap(), "high");
+    assert_eq!(items[2]["priority"].as_str().unwrap(), "normal");
+}
+
+// --- Full detail level with auto-context ---
+
+#[test]
+fn test_list_full_detail_auto_context_json() {
+    let dir = setup_project(&[(
+        "main.rs",
+        "fn main() {\n    let x = 1;\n    // TODO: fix this\n    let y = 2;\n}\n",
+    )]);
+
+    let output = todo_scan()
+        .args([
+            "list",
+            "--root",
+            dir.path().to_str().unwrap(),
+            "--detail",
+            "full",
+            "--format",
+            "json",
+        ])
+        .output()
+        .unwrap();
+
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    let json: serde_json::Value = serde_json::from_str(&stdout).unwrap();
+    let items = json["items"].as_array().unwrap();
+    // Full detail should include match_key
+    assert!(
+        items[0].get("match_key").is_some(),
+        "full detail should include match_key"
+    );
+    // Full detail should auto-include context
+    assert!(
+        items[0].get("context").is_some(),
+        "full detail should auto-include context"
+    );
+}
+
+// --- Scanner exclude_dirs config ---
+
+#[test]
+fn test_list_exclude_dirs_config() {
+    let dir = setup_project(&[
+        ("src/lib.rs", "// TODO: keep this\n"),
+        ("vendor/dep.rs", "// TODO: exclude this\n"),
+        ("node_modules/pkg.js", "// TODO: exclude this too\n"),
+        (
+            ".todo-scan.toml",
+            r#"
+exclude_dirs = ["vendor", "node_modules"]
+"#,
+        ),
+    ]);
+
+    todo_scan()
+        .args(["list", "--root", dir.path().to_str().unwrap()])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("keep this"))
+        .stdout(predicate::str::contains("exclude this").not())
+        .stdout(predicate::str::contains("exclude this too").not())
+        .stdout(predicate::str::contains("1 items"));
+}
+
+// --- List filter by normal priority ---
+
+#[test]
+fn test_list_filter_normal_priority() {
+    let dir = setup_project(&[(
+        "main.rs",
+        "// TODO!!: urgent\n// TODO!: high\n// TODO: normal one\n// TODO: normal two\n",
+    )]);
+
+    todo_scan()
+        .args([
+            "list",
+            "--root",
+            dir.path().to_str().unwrap(),
+            "--priority",
+            "normal",
+        ])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("normal one"))
+        .stdout(predicate::str::contains("normal two"))
+        .stdout(predicate::str::contains("urgent").not())
+        .stdout(predicate::str::contains("2 items"));
+}
+
+// --- Diff text format with expired deadline ---
+
+#[test]
+fn test_list_text_search_expired_deadline_in_group_by_tag() {
+    let dir = setup_project(&[("main.rs", "// TODO(2020-01-01): old expired task\n")]);
+
+    todo_scan()
+        .args([
+            "list",
+            "--root",
+            dir.path().to_str().unwrap(),
+            "--group-by",
+            "tag",
+        ])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("[expired: 2020-01-01]"));
 }
 
 #[test]
-fn test_list_detail_default_is_normal() {
-    // Omitting --detail should give the same as --detail normal
-    let dir = setup_project(&[("main.rs", "// TODO(alice): fix issue #123\n")]);
-
-    // Without --detail flag
-    let without = todo_scan()
-        .args(["list", "--root", dir.path().to_str().unwrap()])
-        .output()
-        .unwrap();
+fn test_list_profile_writes_timings_to_stderr_only() {
+    let dir = setup_project(&[("main.rs", "// TODO: profile me\n")]);
 
-    // With --detail normal
-    let with_normal = todo_scan()
+    let output = todo_scan()
         .args([
             "list",
+            "--profile",
+            "--format",
+            "json",
             "--root",
             dir.path().to_str().unwrap(),
-            "--detail",
-            "normal",
         ])
         .output()
         .unwrap();
 
-    assert_eq!(without.stdout, with_normal.stdout);
+    assert!(output.status.success());
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stderr.contains("profile:"));
+    assert!(stderr.contains("scan"));
+    assert!(stderr.contains("files/s"));
+    let json: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(json["items"].as_array().unwrap().len(), 1);
 }
 
 #[test]
-fn test_list_detail_minimal_json_only_core_fields() {
-    let dir = setup_project(&[("main.rs", "// TODO(alice): fix issue #123\n")]);
+fn test_list_skips_gitignored_files_by_default() {
+    let dir = setup_project(&[
+        (".gitignore", "build/\n"),
+        ("build/out.rs", "// TODO: generated output\n"),
+        ("main.rs", "// TODO: real work\n"),
+    ]);
 
-    let output = todo_scan()
+    todo_scan()
+        .args(["list", "--root", dir.path().to_str().unwrap()])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("real work"))
+        .stdout(predicate::str::contains("generated output").not());
+}
+
+#[test]
+fn test_list_no_ignore_scans_gitignored_files() {
+    let dir = setup_project(&[
+        (".gitignore", "build/\n"),
+        ("build/out.rs", "// TODO: generated output\n"),
+        ("main.rs", "// TODO: real work\n"),
+    ]);
+
+    todo_scan()
         .args([
             "list",
+            "--no-ignore",
+            "--no-cache",
             "--root",
             dir.path().to_str().unwrap(),
-            "--detail",
-            "minimal",
+        ])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("real work"))
+        .stdout(predicate::str::contains("generated output"));
+}
+
+#[test]
+fn test_list_scan_filenames_reports_line_zero() {
+    let dir = setup_project(&[("src/TODO_rename_this.rs", "fn main() {}\n")]);
+
+    let output = todo_scan()
+        .args([
+            "list",
+            "--scan-filenames",
+            "--no-cache",
             "--format",
             "json",
+            "--root",
+            dir.path().to_str().unwrap(),
         ])
         .output()
         .unwrap();
+    assert!(output.status.success());
 
-    let stdout = String::from_utf8(output.stdout).unwrap();
-    let json: serde_json::Value = serde_json::from_str(&stdout).unwrap();
-    let item = &json["items"][0];
+    let json: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    let items = json["items"].as_array().unwrap();
+    assert_eq!(items.len(), 1);
+    assert_eq!(items[0]["file"], "src/TODO_rename_this.rs");
+    assert_eq!(items[0]["line"], 0);
+    assert_eq!(items[0]["tag"], "TODO");
+    assert_eq!(items[0]["message"], "src/TODO_rename_this.rs");
 
-    // Core fields must be present
-    assert!(item["file"].is_string());
-    assert!(item["line"].is_number());
-    assert!(item["tag"].is_string());
-    assert!(item["message"].is_string());
+    // Off by default
+    todo_scan()
+        .args(["list", "--root", dir.path().to_str().unwrap()])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("0 items"));
+}
 
-    // Non-core fields must be absent in minimal
-    assert!(
-        item.get("author").is_none(),
-        "author should not be in minimal JSON"
-    );
-    assert!(
-        item.get("issue_ref").is_none(),
-        "issue_ref should not be in minimal JSON"
-    );
-    assert!(
-        item.get("priority").is_none(),
-        "priority should not be in minimal JSON"
-    );
-    assert!(
-        item.get("deadline").is_none(),
-        "deadline should not be in minimal JSON"
+#[test]
+fn test_list_scan_filenames_sarif_and_github_actions_omit_line_zero() {
+    let dir = setup_project(&[("src/TODO_rename_this.rs", "fn main() {}\n")]);
+    let root = dir.path().to_str().unwrap();
+    let run = |format: &str| {
+        let output = todo_scan()
+            .args([
+                "list",
+                "--scan-filenames",
+                "--format",
+                format,
+                "--root",
+                root,
+            ])
+            .output()
+            .unwrap();
+        assert!(output.status.success());
+        String::from_utf8(output.stdout).unwrap()
+    };
+
+    let sarif: serde_json::Value = serde_json::from_str(&run("sarif")).unwrap();
+    let location = &sarif["runs"][0]["results"][0]["locations"][0]["physicalLocation"];
+    assert_eq!(
+        location["artifactLocation"]["uri"],
+        "src/TODO_rename_this.rs"
     );
+    assert!(location.get("region").is_none());
+
+    let annotations = run("github-actions");
+    assert!(annotations.contains("file=src/TODO_rename_this.rs,title=TODO"));
+    assert!(!annotations.contains("line=0"));
 }
 
 #[test]
-fn test_list_detail_full_json_includes_match_key() {
-    let dir = setup_project(&[("main.rs", "// TODO(alice): fix issue #123\n")]);
+fn test_list_multiple_roots_merges_results() {
+    let dir = setup_project(&[
+        ("a/src/lib.rs", "// TODO: in a\nfn a() {}\n"),
+        ("b/main.rs", "// FIXME: in b\n"),
+        ("b/util.rs", "fn util() {}\n"),
+        ("c/skipped.rs", "// TODO: not listed\n"),
+    ]);
 
     let output = todo_scan()
         .args([
             "list",
+            "a",
+            "b",
             "--root",
             dir.path().to_str().unwrap(),
-            "--detail",
-            "full",
             "--format",
             "json",
         ])
         .output()
         .unwrap();
-
-    let stdout = String::from_utf8(output.stdout).unwrap();
-    let json: serde_json::Value = serde_json::from_str(&stdout).unwrap();
-    let item = &json["items"][0];
-
-    // Full mode should include match_key
-    assert!(
-        item.get("match_key").is_some(),
-        "match_key should be present in full JSON"
-    );
-    assert!(item["match_key"].as_str().unwrap().contains("TODO"));
-
-    // Full mode should also include context even without -C flag
-    assert!(
-        item.get("context").is_some(),
-        "context should be auto-included in full JSON"
-    );
+    assert!(output.status.success());
+    let json: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+
+    let mut files: Vec<&str> = json["items"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|item| item["file"].as_str().unwrap())
+        .collect();
+    files.sort();
+    assert_eq!(files, vec!["a/src/lib.rs", "b/main.rs"]);
+    assert_eq!(json["files_scanned"], 3);
+    assert_eq!(json["files_with_items"], 2);
 }
 
 #[test]
-fn test_list_detail_full_text_auto_context() {
-    let dir = setup_project(&[(
-        "main.rs",
-        "fn main() {\n    let x = 1;\n    // TODO: fix this\n    let y = 2;\n    let z = 3;\n}\n",
-    )]);
+fn test_list_multiple_absolute_roots() {
+    let a = setup_project(&[("x.rs", "// TODO: from a\n")]);
+    let b = setup_project(&[("y.rs", "// HACK: from b\n")]);
 
-    // Full detail without -C flag should still show context
     todo_scan()
         .args([
             "list",
-            "--root",
-            dir.path().to_str().unwrap(),
-            "--detail",
-            "full",
+            a.path().to_str().unwrap(),
+            b.path().to_str().unwrap(),
+            "--group-by",
+            "none",
         ])
         .assert()
         .success()
-        .stdout(predicate::str::contains("let x = 1"))
-        .stdout(predicate::str::contains("TODO"))
-        .stdout(predicate::str::contains("let y = 2"));
+        .stdout(predicate::str::contains(format!(
+            "{}:1 [TODO] from a",
+            a.path().join("x.rs").display()
+        )))
+        .stdout(predicate::str::contains(format!(
+            "{}:1 [HACK] from b",
+            b.path().join("y.rs").display()
+        )))
+        .stdout(predicate::str::contains("2 items"));
 }
 
 #[test]
-fn test_list_json_contains_id_field_at_normal_detail() {
-    let dir = setup_project(&[("main.rs", "// TODO: stable id test\n")]);
+fn test_list_root_must_be_directory() {
+    let dir = setup_project(&[("main.rs", "// TODO: x\n")]);
+
+    todo_scan()
+        .args(["list", "missing", "--root", dir.path().to_str().unwrap()])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("not a directory: missing"));
+}
+
+#[test]
+fn test_list_single_file_argument() {
+    let dir = setup_project(&[
+        ("src/main.rs", "// TODO: in main\n// FIXME: also main\n"),
+        ("src/other.rs", "// TODO: in other\n"),
+    ]);
 
     let output = todo_scan()
         .args([
             "list",
+            "src/main.rs",
             "--root",
             dir.path().to_str().unwrap(),
             "--format",
@@ -884,360 +2373,610 @@ fn test_list_json_contains_id_field_at_normal_detail() {
         ])
         .output()
         .unwrap();
-
-    let stdout = String::from_utf8(output.stdout).unwrap();
-    let json: serde_json::Value = serde_json::from_str(&stdout).unwrap();
+    assert!(output.status.success());
+    let json: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
     let items = json["items"].as_array().unwrap();
-    assert_eq!(items.len(), 1);
-    let item = &items[0];
-    assert_eq!(item["id"].as_str().unwrap(), "main.rs:TODO:stable id test");
+    let found: Vec<(&str, &str)> = items
+        .iter()
+        .map(|i| (i["file"].as_str().unwrap(), i["message"].as_str().unwrap()))
+        .collect();
+    assert_eq!(
+        found,
+        vec![("src/main.rs", "in main"), ("src/main.rs", "also main")]
+    );
+    assert_eq!(json["files_scanned"], 1);
 }
 
 #[test]
-fn test_list_json_contains_id_field_at_minimal_detail() {
-    let dir = setup_project(&[("main.rs", "// TODO: minimal id test\n")]);
+fn test_list_root_sets_path_prefix() {
+    let dir = setup_project(&[("pkg/src/lib.rs", "// TODO: nested\n")]);
 
-    let output = todo_scan()
+    todo_scan()
         .args([
             "list",
             "--root",
             dir.path().to_str().unwrap(),
-            "--format",
-            "json",
-            "--detail",
-            "minimal",
+            "--group-by",
+            "none",
         ])
-        .output()
-        .unwrap();
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("pkg/src/lib.rs:1 [TODO] nested"));
 
-    let stdout = String::from_utf8(output.stdout).unwrap();
-    let json: serde_json::Value = serde_json::from_str(&stdout).unwrap();
-    let item = &json["items"][0];
-    assert!(
-        item["id"].is_string(),
-        "id field must be present in minimal detail"
-    );
+    todo_scan()
+        .args([
+            "list",
+            "--root",
+            dir.path().join("pkg").to_str().unwrap(),
+            "--group-by",
+            "none",
+        ])
+        .assert()
+        .success()
+        .stdout(
+            predicate::str::contains("\nsrc/lib.rs:1 [TODO] nested")
+                .or(predicate::str::starts_with("src/lib.rs:1 [TODO] nested")),
+        )
+        .stdout(predicate::str::contains("pkg/").not());
 }
 
 #[test]
-fn test_list_json_full_detail_has_both_id_and_match_key() {
-    let dir = setup_project(&[("main.rs", "// TODO: full detail test\n")]);
+fn test_list_missing_root_errors() {
+    let dir = setup_project(&[("main.rs", "// TODO: x\n")]);
+
+    todo_scan()
+        .args(["list", "--root", dir.path().join("nope").to_str().unwrap()])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("--root is not a directory"));
+}
+
+#[test]
+fn test_list_max_items_truncates_json() {
+    let dir = setup_project(&[("flood.rs", &"// TODO: again\n".repeat(30))]);
 
     let output = todo_scan()
         .args([
             "list",
             "--root",
             dir.path().to_str().unwrap(),
+            "--max-items",
+            "10",
             "--format",
             "json",
-            "--detail",
-            "full",
         ])
         .output()
         .unwrap();
-
-    let stdout = String::from_utf8(output.stdout).unwrap();
-    let json: serde_json::Value = serde_json::from_str(&stdout).unwrap();
-    let item = &json["items"][0];
-    assert!(item["id"].is_string(), "id field must be present");
+    assert!(output.status.success());
+    let json: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(json["items"].as_array().unwrap().len(), 10);
+    assert_eq!(json["truncated"], true);
+    assert_eq!(json["total_found"], 30);
     assert!(
-        item["match_key"].is_string(),
-        "match_key must still be present in full detail"
+        String::from_utf8_lossy(&output.stderr).contains("stopped collecting at 10 of 30 items")
     );
-    assert_eq!(item["id"].as_str(), item["match_key"].as_str());
-}
-
-// --- Text format context lines (list -C) ---
-
-#[test]
-fn test_list_text_context_lines() {
-    let dir = setup_project(&[(
-        "main.rs",
-        "fn main() {\n    let x = 1;\n    // TODO: fix this\n    let y = 2;\n}\n",
-    )]);
-
-    todo_scan()
-        .args(["list", "--root", dir.path().to_str().unwrap(), "-C", "1"])
-        .assert()
-        .success()
-        .stdout(predicate::str::contains("let x = 1"))
-        .stdout(predicate::str::contains("let y = 2"))
-        .stdout(predicate::str::contains("TODO"))
-        .stdout(predicate::str::contains("→"));
 }
 
 #[test]
-fn test_list_text_context_lines_with_group_by_tag() {
-    let dir = setup_project(&[(
-        "main.rs",
-        "fn main() {\n    let x = 1;\n    // TODO: fix this\n    let y = 2;\n}\n",
-    )]);
+fn test_list_layered_config_files() {
+    let dir = setup_project(&[
+        ("org.toml", "exclude_dirs = [\"vendor\"]\n"),
+        ("repo.toml", "tags = [\"FIXME\"]\n"),
+        ("main.rs", "// TODO: not scanned\n// FIXME: kept\n"),
+        ("vendor/lib.rs", "// FIXME: vendored\n"),
+    ]);
+    let root = dir.path();
 
     todo_scan()
         .args([
             "list",
             "--root",
-            dir.path().to_str().unwrap(),
-            "-C",
-            "1",
-            "--group-by",
-            "tag",
+            root.to_str().unwrap(),
+            "--config",
+            root.join("org.toml").to_str().unwrap(),
+            "--config",
+            root.join("repo.toml").to_str().unwrap(),
         ])
         .assert()
         .success()
-        .stdout(predicate::str::contains("TODO (1 items)"))
-        .stdout(predicate::str::contains("→"));
+        .stdout(predicate::str::contains("kept"))
+        .stdout(predicate::str::contains("not scanned").not())
+        .stdout(predicate::str::contains("vendored").not());
 }
 
-// --- Text format with deadlines ---
-
 #[test]
-fn test_list_text_shows_deadline() {
-    let dir = setup_project(&[("main.rs", "// TODO(2099-06-15): future deadline task\n")]);
+fn test_list_no_config_ignores_discovered_config() {
+    let dir = setup_project(&[
+        (
+            ".todo-scan.toml",
+            "tags = [\"FIXME\"]\nexclude_dirs = [\"third_party\"]\n",
+        ),
+        (
+            "main.rs",
+            "// TODO: default tag\n// FIXME: configured tag\n",
+        ),
+        ("third_party/lib.rs", "// TODO: vendored\n"),
+    ]);
+    let root = dir.path().to_str().unwrap();
 
     todo_scan()
-        .args(["list", "--root", dir.path().to_str().unwrap()])
+        .args(["list", "--root", root])
         .assert()
         .success()
-        .stdout(predicate::str::contains("[deadline: 2099-06-15]"));
+        .stdout(predicate::str::contains("default tag").not())
+        .stdout(predicate::str::contains("vendored").not());
+
+    todo_scan()
+        .args(["list", "--root", root, "--no-config"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("default tag"))
+        .stdout(predicate::str::contains("configured tag"))
+        .stdout(predicate::str::contains("vendored"));
+
+    let output = todo_scan()
+        .args(["list", "--root", root, "--no-config", "--explain-config"])
+        .output()
+        .unwrap();
+    let json: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(json["config_files"], serde_json::json!([]));
 }
 
 #[test]
-fn test_list_text_shows_expired_deadline() {
-    let dir = setup_project(&[("main.rs", "// TODO(2020-01-01): expired task\n")]);
+fn test_list_default_excludes_skip_target() {
+    let dir = setup_project(&[
+        ("main.rs", "// TODO: own code\n"),
+        ("target/debug/build.rs", "// TODO: build output\n"),
+    ]);
+    let root = dir.path().to_str().unwrap();
+
+    todo_scan()
+        .args(["list", "--root", root])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("own code"))
+        .stdout(predicate::str::contains("build output").not());
 
     todo_scan()
-        .args(["list", "--root", dir.path().to_str().unwrap()])
+        .args(["list", "--root", root, "--no-default-excludes"])
         .assert()
         .success()
-        .stdout(predicate::str::contains("[expired: 2020-01-01]"));
+        .stdout(predicate::str::contains("build output"));
 }
 
-// --- Text format with author and issue_ref ---
-
 #[test]
-fn test_list_text_shows_author_and_issue_ref() {
-    let dir = setup_project(&[("main.rs", "// TODO(alice): fix bug #42\n")]);
+fn test_list_configured_exclude_dirs_replace_defaults() {
+    let dir = setup_project(&[
+        (".todo-scan.toml", "exclude_dirs = [\"dist\"]\n"),
+        ("dist/out.js", "// TODO: bundled\n"),
+        ("target/build.rs", "// TODO: build output\n"),
+    ]);
 
     todo_scan()
         .args(["list", "--root", dir.path().to_str().unwrap()])
         .assert()
         .success()
-        .stdout(predicate::str::contains("@alice"))
-        .stdout(predicate::str::contains("#42"));
+        .stdout(predicate::str::contains("bundled").not())
+        .stdout(predicate::str::contains("build output"));
 }
 
-// --- Show-ignored with group-by tag ---
-
 #[test]
-fn test_list_show_ignored_with_group_by_tag() {
-    let dir = setup_project(&[(
-        "main.rs",
-        "// TODO: visible\n// FIXME: hidden todo-scan:ignore\n",
-    )]);
+fn test_explain_config_reflects_cli_override() {
+    let dir = setup_project(&[
+        (
+            ".todo-scan.toml",
+            "respect_gitignore = true\nexclude_dirs = [\"vendor\"]\nexclude_patterns = [\"[\"]\n",
+        ),
+        ("main.rs", "// TODO: never scanned\n"),
+    ]);
 
-    todo_scan()
+    let output = todo_scan()
         .args([
             "list",
+            "--explain-config",
+            "--no-ignore",
             "--root",
             dir.path().to_str().unwrap(),
-            "--show-ignored",
-            "--group-by",
-            "tag",
         ])
-        .assert()
-        .success()
-        .stdout(predicate::str::contains("Ignored items"))
-        .stdout(predicate::str::contains("FIXME"));
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+
+    let json: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert!(json["config_files"][0]
+        .as_str()
+        .unwrap()
+        .ends_with(".todo-scan.toml"));
+    // --no-ignore wins over the file's respect_gitignore = true
+    assert_eq!(json["config"]["respect_gitignore"], false);
+    assert_eq!(json["ignore"]["respect_gitignore"], false);
+    assert_eq!(json["ignore"]["ignore_files"], serde_json::json!([]));
+    assert_eq!(
+        json["ignore"]["exclude_dirs"],
+        serde_json::json!(["vendor"])
+    );
+    assert_eq!(
+        json["ignore"]["invalid_exclude_patterns"],
+        serde_json::json!(["["])
+    );
+    assert!(json.get("items").is_none());
 }
 
-// --- Text format minimal detail suppresses author/issue/deadline ---
-
 #[test]
-fn test_list_text_minimal_suppresses_metadata() {
-    let dir = setup_project(&[("main.rs", "// TODO(alice, 2099-06-15): fix this bug\n")]);
+fn test_show_pattern_includes_enabled_custom_tag() {
+    let dir = setup_project(&[
+        (
+            ".todo-scan.toml",
+            "tags = [\"TODO\", \"FIXME\", \"REVIEW\"]\nenabled_tags = [\"TODO\", \"REVIEW\"]\n",
+        ),
+        ("main.rs", "// TODO: never scanned\n"),
+    ]);
 
     todo_scan()
         .args([
             "list",
+            "--show-pattern",
             "--root",
             dir.path().to_str().unwrap(),
-            "--detail",
-            "minimal",
         ])
         .assert()
         .success()
-        // Minimal mode should not show author annotation or deadline
-        .stdout(predicate::str::contains("@alice").not())
-        .stdout(predicate::str::contains("deadline").not())
-        .stdout(predicate::str::contains("TODO"));
+        .stdout(predicate::str::is_empty())
+        .stderr(predicate::str::contains("tags: TODO, REVIEW\n"))
+        .stderr(predicate::str::contains(r"(?i)\b(TODO|REVIEW)\b"))
+        .stderr(predicate::str::contains("FIXME").not());
 }
 
-// --- Sort by tag ---
+fn sarif_baseline_states(
+    root: &std::path::Path,
+    baseline: &std::path::Path,
+) -> Vec<(String, String)> {
+    let output = todo_scan()
+        .args([
+            "list",
+            "--no-cache",
+            "--format",
+            "sarif",
+            "--sarif-baseline",
+            baseline.to_str().unwrap(),
+            "--root",
+            root.to_str().unwrap(),
+        ])
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+    let sarif: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    let mut states: Vec<(String, String)> = sarif["runs"][0]["results"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|r| {
+            (
+                r["message"]["text"].as_str().unwrap().to_string(),
+                r["baselineState"].as_str().unwrap().to_string(),
+            )
+        })
+        .collect();
+    states.sort();
+    states
+}
 
 #[test]
-fn test_list_sort_by_tag() {
-    let dir = setup_project(&[(
-        "main.rs",
-        "// NOTE: low severity\n// BUG: high severity\n// TODO: medium severity\n",
-    )]);
+fn test_list_sarif_baseline_from_previous_log() {
+    let dir = setup_project(&[("main.rs", "// TODO: existing work\n")]);
+    let baseline_dir = TempDir::new().unwrap();
+    let baseline = baseline_dir.path().join("previous.sarif");
+    fs::write(&baseline, list_output(dir.path(), "sarif")).unwrap();
+
+    fs::write(
+        dir.path().join("main.rs"),
+        "// TODO: existing work\n// FIXME: brand new\n",
+    )
+    .unwrap();
+
+    assert_eq!(
+        sarif_baseline_states(dir.path(), &baseline),
+        vec![
+            ("brand new".to_string(), "new".to_string()),
+            ("existing work".to_string(), "unchanged".to_string()),
+        ]
+    );
+}
+
+#[test]
+fn test_list_sarif_baseline_from_id_list() {
+    let dir = setup_project(&[("main.rs", "// TODO: known\n// TODO: novel\n")]);
+    let baseline_dir = TempDir::new().unwrap();
+    let baseline = baseline_dir.path().join("ids.txt");
+    fs::write(&baseline, "main.rs:TODO:known\n").unwrap();
+
+    assert_eq!(
+        sarif_baseline_states(dir.path(), &baseline),
+        vec![
+            ("known".to_string(), "unchanged".to_string()),
+            ("novel".to_string(), "new".to_string()),
+        ]
+    );
+}
+
+#[test]
+fn test_list_sarif_severity_override_for_path() {
+    let dir = setup_project(&[
+        (
+            ".todo-scan.toml",
+            "severity_overrides = [{ path = \"src/crypto/**\", level = \"error\" }]\n",
+        ),
+        ("src/crypto/aes.rs", "// TODO: rotate keys\n"),
+        ("src/ui.rs", "// TODO: tweak colors\n"),
+    ]);
 
     let output = todo_scan()
         .args([
             "list",
+            "--format",
+            "sarif",
             "--root",
             dir.path().to_str().unwrap(),
-            "--sort",
-            "tag",
-            "--format",
-            "json",
         ])
         .output()
         .unwrap();
-
-    let stdout = String::from_utf8(output.stdout).unwrap();
-    let json: serde_json::Value = serde_json::from_str(&stdout).unwrap();
-    let items = json["items"].as_array().unwrap();
-    // Should be sorted by severity descending: BUG > TODO > NOTE
-    assert_eq!(items[0]["tag"].as_str().unwrap(), "BUG");
-    assert_eq!(items[1]["tag"].as_str().unwrap(), "TODO");
-    assert_eq!(items[2]["tag"].as_str().unwrap(), "NOTE");
+    assert!(output.status.success());
+
+    let sarif: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    let results = sarif["runs"][0]["results"].as_array().unwrap();
+    let level_for = |text: &str| {
+        results
+            .iter()
+            .find(|r| r["message"]["text"] == text)
+            .map(|r| r["level"].as_str().unwrap().to_string())
+            .unwrap()
+    };
+    assert_eq!(level_for("rotate keys"), "error");
+    assert_eq!(level_for("tweak colors"), "warning");
 }
 
-// --- Sort by priority ---
+#[test]
+fn test_list_sarif_invocation_arguments_keep_spaces() {
+    let dir = setup_project(&[("my project/main.rs", "// TODO: spaced\n")]);
+    let root = dir.path().join("my project");
+    let root = root.to_str().unwrap();
+
+    let output = todo_scan()
+        .args(["list", "--format", "sarif", "--root", root])
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+
+    let sarif: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(
+        sarif["runs"][0]["invocations"][0]["arguments"],
+        serde_json::json!(["list", "--format", "sarif", "--root", root])
+    );
+}
 
 #[test]
-fn test_list_sort_by_priority() {
-    let dir = setup_project(&[(
-        "main.rs",
-        "// TODO: normal\n// TODO!!: urgent\n// TODO!: high\n",
-    )]);
+fn test_list_sarif_severity_override_with_absolute_paths() {
+    let dir = setup_project(&[
+        (
+            ".todo-scan.toml",
+            "severity_overrides = [{ path = \"src/crypto/**\", level = \"error\" }]\n",
+        ),
+        ("src/crypto/aes.rs", "// TODO: rotate keys\n"),
+    ]);
 
     let output = todo_scan()
         .args([
             "list",
+            "--format",
+            "sarif",
+            "--path-style",
+            "absolute",
             "--root",
             dir.path().to_str().unwrap(),
-            "--sort",
-            "priority",
-            "--format",
-            "json",
         ])
         .output()
         .unwrap();
+    assert!(output.status.success());
 
-    let stdout = String::from_utf8(output.stdout).unwrap();
-    let json: serde_json::Value = serde_json::from_str(&stdout).unwrap();
-    let items = json["items"].as_array().unwrap();
-    // Should be sorted by priority descending: urgent > high > normal
-    assert_eq!(items[0]["priority"].as_str().unwrap(), "urgent");
-    assert_eq!(items[1]["priority"].as_str().unwrap(), "high");
-    assert_eq!(items[2]["priority"].as_str().unwrap(), "normal");
+    let sarif: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(sarif["runs"][0]["results"][0]["level"], "error");
 }
 
-// --- Full detail level with auto-context ---
-
 #[test]
-fn test_list_full_detail_auto_context_json() {
-    let dir = setup_project(&[(
-        "main.rs",
-        "fn main() {\n    let x = 1;\n    // TODO: fix this\n    let y = 2;\n}\n",
-    )]);
+fn test_list_sarif_severity_override_with_package() {
+    let dir = setup_project(&[
+        ("Cargo.toml", "[workspace]\nmembers = [\"crates/core\"]\n"),
+        (
+            ".todo-scan.toml",
+            "severity_overrides = [{ path = \"crates/core/src/**\", level = \"error\" }]\n",
+        ),
+        ("crates/core/src/lib.rs", "// TODO: rotate keys\n"),
+    ]);
 
     let output = todo_scan()
         .args([
             "list",
+            "--format",
+            "sarif",
             "--root",
             dir.path().to_str().unwrap(),
-            "--detail",
-            "full",
-            "--format",
-            "json",
+            "--package",
+            "core",
         ])
         .output()
         .unwrap();
+    assert!(output.status.success());
 
-    let stdout = String::from_utf8(output.stdout).unwrap();
-    let json: serde_json::Value = serde_json::from_str(&stdout).unwrap();
-    let items = json["items"].as_array().unwrap();
-    // Full detail should include match_key
-    assert!(
-        items[0].get("match_key").is_some(),
-        "full detail should include match_key"
-    );
-    // Full detail should auto-include context
-    assert!(
-        items[0].get("context").is_some(),
-        "full detail should auto-include context"
-    );
+    let sarif: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(sarif["runs"][0]["results"][0]["level"], "error");
 }
 
-// --- Scanner exclude_dirs config ---
-
 #[test]
-fn test_list_exclude_dirs_config() {
+fn test_list_sarif_severity_override_with_absolute_root() {
     let dir = setup_project(&[
-        ("src/lib.rs", "// TODO: keep this\n"),
-        ("vendor/dep.rs", "// TODO: exclude this\n"),
-        ("node_modules/pkg.js", "// TODO: exclude this too\n"),
         (
             ".todo-scan.toml",
-            r#"
-exclude_dirs = ["vendor", "node_modules"]
-"#,
+            "severity_overrides = [{ path = \"src/crypto/**\", level = \"error\" }]\n",
         ),
+        ("src/crypto/aes.rs", "// TODO: rotate keys\n"),
     ]);
+    let crypto = dir.path().join("src/crypto");
 
-    todo_scan()
-        .args(["list", "--root", dir.path().to_str().unwrap()])
-        .assert()
-        .success()
-        .stdout(predicate::str::contains("keep this"))
-        .stdout(predicate::str::contains("exclude this").not())
-        .stdout(predicate::str::contains("exclude this too").not())
-        .stdout(predicate::str::contains("1 items"));
+    let output = todo_scan()
+        .args([
+            "list",
+            crypto.to_str().unwrap(),
+            "--format",
+            "sarif",
+            "--root",
+            dir.path().to_str().unwrap(),
+        ])
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+
+    let sarif: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(sarif["runs"][0]["results"][0]["level"], "error");
 }
 
-// --- List filter by normal priority ---
+#[test]
+fn test_list_columns_csv_subset_and_order() {
+    let dir = setup_project(&[("main.rs", "// TODO(alice): first\n// BUG: second\n")]);
+
+    let output = todo_scan()
+        .args([
+            "list",
+            "--root",
+            dir.path().to_str().unwrap(),
+            "--format",
+            "csv-wide",
+            "--columns",
+            "message,line,tag",
+        ])
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    let lines: Vec<&str> = stdout.lines().collect();
+    assert_eq!(lines, ["message,line,tag", "first,1,TODO", "second,2,BUG"]);
+}
 
 #[test]
-fn test_list_filter_normal_priority() {
-    let dir = setup_project(&[(
-        "main.rs",
-        "// TODO!!: urgent\n// TODO!: high\n// TODO: normal one\n// TODO: normal two\n",
-    )]);
+fn test_list_columns_rejects_unknown_and_unsupported() {
+    let dir = setup_project(&[("main.rs", "// TODO: first\n")]);
+    let root = dir.path().to_str().unwrap();
+
+    todo_scan()
+        .args(["list", "--root", root, "--format", "csv-wide"])
+        .args(["--columns", "file,owner"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("owner"));
+
+    todo_scan()
+        .args(["list", "--root", root, "--columns", "file,line"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains(
+            "--columns requires the csv-wide or a markdown format",
+        ));
 
     todo_scan()
+        .args(["list", "--root", root, "--format", "markdown"])
+        .args(["--columns", "file,context_after"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("require the csv-wide format"));
+}
+
+#[test]
+fn test_list_comment_prefixes_limit_markdown_matches() {
+    let dir = setup_project(&[
+        (
+            "notes.md",
+            "# TODO: heading\n<!-- TODO: hidden note -->\nSee above; TODO: prose\n",
+        ),
+        (".todo-scan.toml", "[comment_prefixes]\nmd = [\"<!--\"]\n"),
+    ]);
+
+    let output = todo_scan()
         .args([
             "list",
             "--root",
             dir.path().to_str().unwrap(),
-            "--priority",
-            "normal",
+            "--format",
+            "json",
         ])
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+    let json: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    let messages: Vec<&str> = json["items"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|i| i["message"].as_str().unwrap())
+        .collect();
+    assert_eq!(messages, vec!["hidden note"]);
+}
+
+#[test]
+fn test_list_permissive_unknown_false_skips_unlisted_extensions() {
+    let dir = setup_project(&[
+        ("notes.md", "<!-- TODO: hidden note -->\n"),
+        ("deploy.dat", "# TODO: unknown extension\n"),
+        (".todo-scan.toml", "[comment_prefixes]\nmd = [\"<!--\"]\n"),
+    ]);
+    let root = dir.path().to_str().unwrap();
+
+    // Default: unknown extensions match with the built-in prefixes
+    todo_scan()
+        .args(["list", "--root", root])
         .assert()
         .success()
-        .stdout(predicate::str::contains("normal one"))
-        .stdout(predicate::str::contains("normal two"))
-        .stdout(predicate::str::contains("urgent").not())
-        .stdout(predicate::str::contains("2 items"));
-}
+        .stdout(predicate::str::contains("unknown extension"))
+        .stdout(predicate::str::contains("hidden note"));
 
-// --- Diff text format with expired deadline ---
+    todo_scan()
+        .args(["list", "--root", root, "--permissive-unknown", "false"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("unknown extension").not())
+        .stdout(predicate::str::contains("hidden note"));
+
+    fs::write(
+        dir.path().join(".todo-scan.toml"),
+        "permissive_unknown = false\n[comment_prefixes]\nmd = [\"<!--\"]\n",
+    )
+    .unwrap();
+    todo_scan()
+        .args(["list", "--root", root])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("unknown extension").not())
+        .stdout(predicate::str::contains("1 items"));
+}
 
 #[test]
-fn test_list_text_search_expired_deadline_in_group_by_tag() {
-    let dir = setup_project(&[("main.rs", "// TODO(2020-01-01): old expired task\n")]);
+fn test_list_permissive_unknown_false_with_default_config() {
+    let dir = setup_project(&[
+        ("a.rs", "// TODO: rust task\n"),
+        ("b.py", "# TODO: python task\n"),
+        ("notes.dat", "# TODO: unknown language\n"),
+    ]);
 
     todo_scan()
         .args([
             "list",
             "--root",
             dir.path().to_str().unwrap(),
-            "--group-by",
-            "tag",
+            "--permissive-unknown",
+            "false",
         ])
         .assert()
         .success()
-        .stdout(predicate::str::contains("[expired: 2020-01-01]"));
+        .stdout(predicate::str::contains("rust task"))
+        .stdout(predicate::str::contains("python task"))
+        .stdout(predicate::str::contains("unknown language").not())
+        .stdout(predicate::str::contains("2 items"));
 }
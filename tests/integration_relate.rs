@@ -96,6 +96,64 @@ fn test_relate_cluster_output() {
         .stdout(predicate::str::contains("Cluster 1"));
 }
 
+#[test]
+fn test_relate_cluster_sarif_has_related_locations() {
+    let dir = setup_project(&[(
+        "main.rs",
+        "// TODO: fix authentication\n// FIXME: broken authentication\nfn main() {}\n",
+    )]);
+
+    todo_scan()
+        .args([
+            "relate",
+            "--cluster",
+            "--format",
+            "sarif",
+            "--root",
+            dir.path().to_str().unwrap(),
+        ])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(
+            "\"ruleId\": \"todo-scan/relate/cluster\"",
+        ))
+        .stdout(predicate::str::contains("\"relatedLocations\""));
+}
+
+#[test]
+fn test_relate_max_cluster_size_requires_cluster() {
+    let dir = setup_project(&[("main.rs", "// TODO: fix auth\n")]);
+
+    todo_scan()
+        .args([
+            "relate",
+            "--max-cluster-size",
+            "3",
+            "--root",
+            dir.path().to_str().unwrap(),
+        ])
+        .assert()
+        .failure();
+}
+
+#[test]
+fn test_relate_max_cluster_size_rejects_below_two() {
+    let dir = setup_project(&[("main.rs", "// TODO: fix auth\n")]);
+
+    todo_scan()
+        .args([
+            "relate",
+            "--cluster",
+            "--max-cluster-size",
+            "1",
+            "--root",
+            dir.path().to_str().unwrap(),
+        ])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("at least 2"));
+}
+
 #[test]
 fn test_relate_for_filter() {
     let dir = setup_project(&[(
@@ -225,3 +283,35 @@ fn test_relate_proximity_threshold_flag() {
         .success()
         .stdout(predicate::str::contains("proximity"));
 }
+
+#[test]
+fn test_relate_explain() {
+    let dir = setup_project(&[(
+        "main.rs",
+        "// TODO: fix input validation\n// FIXME: broken input handling\nfn main() {}\n",
+    )]);
+    let root = dir.path().to_str().unwrap();
+
+    todo_scan()
+        .args(["relate", "--root", root, "--explain"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(
+            "Related because they are 1 line apart in main.rs",
+        ))
+        .stdout(predicate::str::contains("share the keyword input"))
+        .stdout(predicate::str::contains("shared_keyword").not());
+
+    // JSON carries both forms regardless of --explain
+    let output = todo_scan()
+        .args(["relate", "--root", root, "--format", "json"])
+        .output()
+        .unwrap();
+    let json: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    let rel = &json["relationships"][0];
+    assert!(rel["reason"].as_str().unwrap().contains("proximity"));
+    assert!(rel["explanation"]
+        .as_str()
+        .unwrap()
+        .starts_with("Related because"));
+}
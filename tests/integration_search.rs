@@ -570,3 +570,30 @@ fn test_search_text_shows_expired_deadline() {
         .success()
         .stdout(predicate::str::contains("[expired: 2020-01-01]"));
 }
+
+#[test]
+fn test_search_group_by_none_is_flat() {
+    let dir = setup_project(&[
+        ("a.rs", "// TODO: fix parser\n"),
+        ("b.rs", "// BUG: parser crash\n"),
+    ]);
+
+    let output = todo_scan()
+        .args([
+            "search",
+            "parser",
+            "--group-by",
+            "none",
+            "--root",
+            dir.path().to_str().unwrap(),
+        ])
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    let lines: Vec<&str> = stdout.lines().collect();
+    assert_eq!(lines.len(), 3, "got: {stdout}");
+    assert!(lines.contains(&"a.rs:1 [TODO] fix parser"));
+    assert!(lines.contains(&"b.rs:1 [BUG] parser crash"));
+    assert_eq!(lines[2], "2 matches (query: \"parser\")");
+}
@@ -56,6 +56,94 @@ fn test_stats_priority_counts() {
         .stdout(predicate::str::contains("urgent: 1"));
 }
 
+#[test]
+fn test_stats_tag_priority_counts_bug_as_urgent() {
+    let dir = setup_project(&[
+        (
+            "main.rs",
+            "// BUG: normal priority bug
+// TODO: normal task
+",
+        ),
+        (
+            ".todo-scan.toml",
+            "[stats.tag_priority]\nBUG = \"urgent\"\n",
+        ),
+    ]);
+
+    todo_scan()
+        .args(["stats", "--root", dir.path().to_str().unwrap()])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("normal: 1"))
+        .stdout(predicate::str::contains("urgent: 1"));
+}
+
+#[test]
+fn test_stats_exclude_tests() {
+    let dir = setup_project(&[
+        ("src/main.rs", "// TODO: ship it\n"),
+        ("src/util_test.rs", "// TODO: test helper\n"),
+        (".todo-scan.toml", "test_paths = [\"**/*_test.rs\"]\n"),
+    ]);
+
+    todo_scan()
+        .args(["stats", "--root", dir.path().to_str().unwrap()])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("2 items across 2 files"));
+
+    todo_scan()
+        .args([
+            "stats",
+            "--root",
+            dir.path().to_str().unwrap(),
+            "--exclude-tests",
+        ])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("1 items across 1 files"));
+}
+
+#[test]
+fn test_stats_by_dir() {
+    let dir = setup_project(&[
+        ("src/cmd/a.rs", "// TODO: one\n// FIXME: two\n"),
+        ("src/lib.rs", "// TODO: three\n"),
+    ]);
+
+    todo_scan()
+        .args(["stats", "--root", dir.path().to_str().unwrap(), "--by-dir"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Directories"))
+        .stdout(predicate::str::is_match(r"src/cmd\s+2").unwrap())
+        .stdout(predicate::str::is_match(r"src\s+1").unwrap());
+
+    let output = todo_scan()
+        .args([
+            "stats",
+            "--root",
+            dir.path().to_str().unwrap(),
+            "--by-dir",
+            "--format",
+            "json",
+        ])
+        .output()
+        .unwrap();
+    let json: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(
+        json["dir_counts"],
+        serde_json::json!([["src/cmd", 2], ["src", 1]])
+    );
+
+    todo_scan()
+        .args(["stats", "--root", dir.path().to_str().unwrap()])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Directories").not());
+}
+
 #[test]
 fn test_stats_with_authors() {
     let dir = setup_project(&[(
@@ -105,6 +193,7 @@ fn test_stats_json_format() {
         .success()
         .stdout(predicate::str::contains("\"total_items\": 2"))
         .stdout(predicate::str::contains("\"total_files\": 1"))
+        .stdout(predicate::str::contains("\"bytes_scanned\": 37"))
         .stdout(predicate::str::contains("\"tag_counts\""))
         .stdout(predicate::str::contains("\"priority_counts\""));
 }
@@ -224,3 +313,91 @@ fn test_stats_with_trend_json() {
     assert!(json.get("trend").is_some());
     assert_eq!(json["trend"]["base_ref"].as_str().unwrap(), "HEAD");
 }
+
+#[test]
+fn test_stats_prometheus_format() {
+    let dir = setup_project(&[(
+        "main.rs",
+        "// TODO(alice): one\n// TODO!!: two\n// BUG: three\n",
+    )]);
+    let root = dir.path().to_str().unwrap();
+
+    let output = todo_scan()
+        .args(["stats", "--root", root, "--format", "prometheus"])
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    assert!(stdout.contains("# TYPE todoscan_items_total gauge\n"));
+    assert!(stdout.contains("\ntodoscan_items_total{tag=\"TODO\"} 2\n"));
+    assert!(stdout.contains("\ntodoscan_items_total{tag=\"BUG\"} 1\n"));
+    assert!(stdout.contains("\ntodoscan_urgent_total 1\n"));
+    assert!(stdout.contains("\ntodoscan_author_items_total{author=\"alice\"} 1\n"));
+    // Not a git repository, so there is no stale count
+    assert!(!stdout.contains("todoscan_stale_total"));
+
+    todo_scan()
+        .args(["list", "--root", root, "--format", "prometheus"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains(
+            "--format prometheus is only supported by the stats command",
+        ));
+}
+
+#[test]
+fn test_stats_tree() {
+    let dir = setup_project(&[
+        ("src/cmd/a.rs", "// TODO: one\n// FIXME: two\n"),
+        ("src/lib.rs", "// TODO: three\n"),
+    ]);
+
+    todo_scan()
+        .args(["stats", "--root", dir.path().to_str().unwrap(), "--tree"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Tree"))
+        .stdout(predicate::str::contains(
+            "  ./ (3)\n    src/ (3)\n      cmd/ (2)\n        a.rs (2)\n      lib.rs (1)\n",
+        ));
+
+    let output = todo_scan()
+        .args([
+            "stats",
+            "--root",
+            dir.path().to_str().unwrap(),
+            "--tree",
+            "--format",
+            "json",
+        ])
+        .output()
+        .unwrap();
+    let json: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    let src = &json["tree"]["children"][0];
+    assert_eq!(json["tree"]["count"], 3);
+    assert_eq!(src["name"], "src");
+    assert_eq!(src["count"], 3);
+    assert_eq!(src["children"][0]["name"], "cmd");
+    assert_eq!(src["children"][0]["count"], 2);
+    assert_eq!(src["children"][1]["name"], "lib.rs");
+    assert_eq!(src["children"][1]["count"], 1);
+    assert!(src["children"][1].get("children").is_none());
+}
+
+#[test]
+fn test_stats_total_counts_items_beyond_max_items() {
+    let todos: String = (1..=20).map(|i| format!("// TODO: task {i}\n")).collect();
+    let dir = setup_project(&[("main.rs", &todos)]);
+
+    todo_scan()
+        .args([
+            "--max-items",
+            "3",
+            "stats",
+            "--root",
+            dir.path().to_str().unwrap(),
+        ])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("20 items across 1 files"));
+}
@@ -0,0 +1,63 @@
+use assert_cmd::Command;
+use predicates::prelude::*;
+use std::fs;
+use tempfile::TempDir;
+
+fn todo_scan() -> Command {
+    assert_cmd::cargo_bin_cmd!("todo-scan")
+}
+
+fn setup_project(files: &[(&str, &str)]) -> TempDir {
+    let dir = TempDir::new().unwrap();
+    for (path, content) in files {
+        let full_path = dir.path().join(path);
+        if let Some(parent) = full_path.parent() {
+            fs::create_dir_all(parent).unwrap();
+        }
+        fs::write(full_path, content).unwrap();
+    }
+    dir
+}
+
+#[test]
+fn test_tags_text_lists_every_tag() {
+    let dir = setup_project(&[]);
+
+    todo_scan()
+        .args(["tags", "--root", dir.path().to_str().unwrap()])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("TODO"))
+        .stdout(predicate::str::contains("NOTE"))
+        .stdout(predicate::str::contains("disabled").not());
+}
+
+#[test]
+fn test_tags_json_disabled_tag_shows_enabled_false() {
+    let dir = setup_project(&[(
+        ".todo-scan.toml",
+        "enabled_tags = [\"TODO\", \"BUG\"]\n[tag_labels]\nBUG = \"Defect\"\n",
+    )]);
+
+    let output = todo_scan()
+        .args([
+            "tags",
+            "--root",
+            dir.path().to_str().unwrap(),
+            "--format",
+            "json",
+        ])
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+
+    let json: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    let tags = json.as_array().unwrap();
+    assert_eq!(tags.len(), 6);
+    let hack = tags.iter().find(|t| t["tag"] == "HACK").unwrap();
+    assert_eq!(hack["enabled"], false);
+    assert_eq!(hack["severity"], "warning");
+    let bug = tags.iter().find(|t| t["tag"] == "BUG").unwrap();
+    assert_eq!(bug["enabled"], true);
+    assert_eq!(bug["aliases"], serde_json::json!(["Defect"]));
+}
@@ -472,3 +472,27 @@ fn test_tasks_filter_by_normal_priority() {
         .stdout(predicate::str::contains("\"total\": 1"))
         .stdout(predicate::str::contains("normal task"));
 }
+
+#[test]
+fn test_tasks_output_trailing_newline_policy() {
+    let dir = setup_project(&[
+        ("main.rs", "// TODO: first task\n"),
+        (".todo-scan.toml", "trailing_newline = true\n"),
+    ]);
+    let output_dir = dir.path().join("tasks-output");
+
+    todo_scan()
+        .args([
+            "tasks",
+            "--root",
+            dir.path().to_str().unwrap(),
+            "--output",
+            output_dir.to_str().unwrap(),
+        ])
+        .assert()
+        .success();
+
+    let content = fs::read_to_string(output_dir.join("task-0001.json")).unwrap();
+    assert!(content.ends_with("}\n"));
+    assert!(!content.ends_with("\n\n"));
+}
@@ -292,3 +292,22 @@ fn test_watch_stopped_message() {
     // On Unix, killed processes exit with signal, not success code
     assert!(!status.success() || status.success());
 }
+
+#[test]
+fn test_watch_once_timeout_exits_nonzero() {
+    let dir = setup_project(&[("a.rs", "// TODO: test\n")]);
+
+    todo_scan()
+        .args([
+            "watch",
+            "--root",
+            dir.path().to_str().unwrap(),
+            "--once",
+            "--timeout",
+            "1",
+        ])
+        .timeout(Duration::from_secs(30))
+        .assert()
+        .code(1)
+        .stderr(predicate::str::contains("No changes before timeout."));
+}
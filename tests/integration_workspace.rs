@@ -377,6 +377,34 @@ block_tags = ["BUG"]
 
 // --- error cases ---
 
+#[test]
+fn check_workspace_rejects_age_and_author_limits() {
+    let dir = setup_project(&[
+        (
+            "Cargo.toml",
+            r#"
+[workspace]
+members = ["crates/core"]
+"#,
+        ),
+        ("crates/core/main.rs", "// TODO: core task\n"),
+    ]);
+
+    for flag in [["--max-age-days", "30"], ["--max-per-author", "1"]] {
+        todo_scan()
+            .args([
+                "check",
+                "--workspace",
+                "--root",
+                dir.path().to_str().unwrap(),
+            ])
+            .args(flag)
+            .assert()
+            .failure()
+            .stderr(predicate::str::contains("cannot be used with"));
+    }
+}
+
 #[test]
 fn workspace_list_no_workspace() {
     let dir = setup_project(&[("main.rs", "// TODO: standalone\n")]);